@@ -1,4 +1,6 @@
 use eden_utils::{error::exts::*, Result};
+use std::time::{Duration, Instant};
+use tracing::warn;
 
 use crate::Bot;
 
@@ -7,9 +9,11 @@ impl Bot {
     /// Obtain a database connection from the primary pool.
     #[tracing::instrument(skip_all)]
     pub async fn db_read(&self) -> Result<sqlx::pool::PoolConnection<sqlx::Postgres>> {
-        self.pool
-            .acquire()
-            .await
+        let started = Instant::now();
+        let result = self.pool.acquire().await;
+        self.record_db_acquire(started.elapsed());
+
+        result
             .anonymize_error_into()
             .attach_printable("could not obtain database connection")
     }
@@ -17,30 +21,52 @@ impl Bot {
     /// Obtain a database transaction from the primary pool.
     #[tracing::instrument(skip_all)]
     pub async fn db_write(&self) -> Result<sqlx::Transaction<'_, sqlx::Postgres>> {
-        self.pool
-            .begin()
-            .await
+        let started = Instant::now();
+        let result = self.pool.begin().await;
+        self.record_db_acquire(started.elapsed());
+
+        result
             .anonymize_error_into()
             .attach_printable("could not obtain database transaction")
     }
+
+    /// Amount of connections from the pool that are currently used.
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn db_connections_in_use(&self) -> u32 {
+        self.pool.size().saturating_sub(self.pool.num_idle() as u32)
+    }
+
+    fn record_db_acquire(&self, waited: Duration) {
+        let threshold = self.settings.database.slow_query_threshold.get();
+        let is_slow = !threshold.is_zero() && waited >= threshold;
+        if is_slow {
+            warn!(
+                ?waited,
+                in_use = self.db_connections_in_use(),
+                "waited too long for a database connection"
+            );
+        }
+        self.metrics.record_db_acquire(waited, is_slow);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use eden_utils::sql::SqlErrorExt;
+    use eden_utils::types::HumanDuration;
     use eden_utils::Result;
     use std::sync::Arc;
-    use std::time::Duration;
 
     #[tokio::test]
     async fn test_statement_timeout() -> Result<()> {
         eden_utils::error::Error::init();
 
-        let mut settings = crate::tests::generate_real_settings();
-        settings.database.query_timeout = Duration::from_secs(2);
+        let mut settings = crate::testkit::generate_real_settings();
+        settings.database.query_timeout = HumanDuration::from_secs(2);
 
-        let bot = Bot::new(Arc::new(settings));
+        let bot = Bot::new(Arc::new(settings)).anonymize_error()?;
 
         let mut conn = bot.db_read().await?;
         let result = sqlx::query("SELECT pg_sleep(3)")
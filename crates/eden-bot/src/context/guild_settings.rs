@@ -0,0 +1,117 @@
+use dashmap::DashMap;
+use eden_schema::types::{GuildSettings, GuildSettingsRow};
+use eden_utils::{error::exts::*, Result};
+use std::time::{Duration, Instant};
+use tracing::{trace, warn};
+use twilight_model::id::marker::{ChannelMarker, GuildMarker};
+use twilight_model::id::Id;
+
+use crate::Bot;
+
+/// How long the loaded settings of a guild will be reused before
+/// loading them again, in case another instance of Eden modified them.
+const GUILD_SETTINGS_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Short-lived cache of the settings of every guild.
+///
+/// This is to avoid querying the database every time a feature
+/// (like father belt) needs the settings while handling events.
+pub(crate) struct GuildSettingsCache {
+    entries: DashMap<Id<GuildMarker>, (Instant, GuildSettingsRow)>,
+    ttl: Duration,
+}
+
+impl GuildSettingsCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl: GUILD_SETTINGS_CACHE_TTL,
+        }
+    }
+
+    fn get(&self, guild_id: Id<GuildMarker>) -> Option<GuildSettingsRow> {
+        let entry = self.entries.get(&guild_id)?;
+        let (loaded_at, settings) = entry.value();
+        (loaded_at.elapsed() <= self.ttl).then(|| settings.clone())
+    }
+
+    fn insert(&self, settings: GuildSettingsRow) {
+        self.entries.insert(settings.id, (Instant::now(), settings));
+    }
+
+    fn invalidate(&self, guild_id: Id<GuildMarker>) {
+        self.entries.remove(&guild_id);
+    }
+}
+
+impl Bot {
+    /// Gets the settings of a guild. Default settings are stored
+    /// if the guild does not have any settings yet.
+    ///
+    /// Settings are cached for a short period of time until they're
+    /// modified with [`Bot::update_guild_settings`] or invalidated
+    /// with [`Bot::invalidate_guild_settings`].
+    #[tracing::instrument(skip(self))]
+    pub async fn guild_settings(&self, guild_id: Id<GuildMarker>) -> Result<GuildSettingsRow> {
+        if let Some(cached) = self.0.guild_settings_cache.get(guild_id) {
+            trace!("cache hit, got guild settings from cache");
+            return Ok(cached);
+        }
+
+        let mut conn = self.db_read().await?;
+        let settings = GuildSettings::upsert(&mut conn, guild_id)
+            .await
+            .anonymize_error()
+            .attach_printable_lazy(|| format!("could not get settings of guild {guild_id}"))?;
+
+        self.0.guild_settings_cache.insert(settings.clone());
+        Ok(settings)
+    }
+
+    /// Invalidates the cached settings of a guild. It must be called
+    /// once the transaction modifying the guild's settings is committed.
+    pub(crate) fn invalidate_guild_settings(&self, guild_id: Id<GuildMarker>) {
+        self.0.guild_settings_cache.invalidate(guild_id);
+    }
+
+    /// Modifies the settings of a guild and saves them.
+    #[tracing::instrument(skip(self, modify))]
+    pub async fn update_guild_settings(
+        &self,
+        guild_id: Id<GuildMarker>,
+        modify: impl FnOnce(&mut GuildSettings),
+    ) -> Result<GuildSettings> {
+        let mut conn = self.db_write().await?;
+        let mut settings = GuildSettings::upsert(&mut conn, guild_id).await?.data;
+        modify(&mut settings);
+
+        GuildSettings::update(&mut conn, guild_id, &settings).await?;
+        conn.commit()
+            .await
+            .anonymize_error_into()
+            .attach_printable("could not commit transaction")?;
+
+        self.invalidate_guild_settings(guild_id);
+        Ok(settings)
+    }
+
+    /// Gets the alert channel of the local guild, preferring the one
+    /// from the local guild's settings over `bot.local_guild.alert_channel_id`.
+    ///
+    /// It falls back to the setting if the local guild's settings cannot
+    /// be loaded, since alerts may be about the database being unavailable.
+    pub async fn alert_channel_id(&self) -> Id<ChannelMarker> {
+        let local_guild = &self.settings.bot.local_guild;
+        match self.guild_settings(local_guild.id).await {
+            Ok(settings) => settings
+                .alerts
+                .channel_id
+                .unwrap_or(local_guild.alert_channel_id),
+            Err(error) => {
+                warn!(%error, "could not get alert channel from local guild settings");
+                local_guild.alert_channel_id
+            }
+        }
+    }
+}
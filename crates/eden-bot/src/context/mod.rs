@@ -1,5 +1,7 @@
 use eden_settings::Settings;
+use eden_tasks::queue_worker::QueueWorkerBuilder;
 use eden_tasks::QueueWorker;
+use eden_utils::{error::exts::*, Result};
 use sqlx::postgres::PgPoolOptions;
 use std::fmt::Debug;
 use std::ops::Deref;
@@ -10,34 +12,84 @@ use twilight_cache_inmemory::{InMemoryCache, ResourceType};
 use twilight_http::client::InteractionClient;
 use twilight_model::id::{marker::ApplicationMarker, Id};
 
+use self::guild_settings::GuildSettingsCache;
+use self::permissions::{PermissionsCache, RoleCacheMetrics};
+use crate::errors::CreateBotError;
+use crate::features::anti_spam::DuplicateMessageDetector;
+use crate::features::blacklist::Blacklist;
+use crate::features::command_alias::CommandAliases;
+use crate::features::father_belt::{FatherBeltMetrics, ReplySuppressions};
+use crate::features::feature_gate::FeatureGates;
+use crate::features::guild_profile::GuildProfiles;
+use crate::features::health_report::BotMetrics;
+use crate::features::leader::Leadership;
+use crate::features::quiet_hours::QuietHours;
+use crate::features::raid::JoinRateMonitor;
+use crate::features::translation::Translator;
+use crate::features::voice_stats::VoiceSessions;
+use crate::features::{FeatureEvent, EVENT_BUS_CAPACITY};
+use crate::interactions::cooldowns::CommandCooldowns;
+use crate::interactions::response_cache::ResponseCache;
 use crate::interactions::state::CommandStates;
 use crate::shard::ShardManager;
+use crate::util::alerts::Alerter;
+use crate::util::dry_run::DryRunSink;
+use crate::util::event_bus::EventBus;
+use crate::util::webhooks::WebhookManager;
 
 // involves database functionality for Bot struct.
 mod database;
+// typed access to per-guild settings stored in the database
+mod guild_settings;
+// computing and caching bot permissions per guild and channel
+mod permissions;
 // useful functions that will make my life easier
 mod util;
 
+pub use self::permissions::{BotPermissions, RoleCacheMetrics};
+
 pub struct BotInner {
+    pub alerter: Alerter,
+    pub anti_spam: DuplicateMessageDetector,
+    pub blacklist: Blacklist,
     pub cache: Arc<InMemoryCache>,
+    pub command_aliases: CommandAliases,
     pub command_state: CommandStates,
+    pub cooldowns: CommandCooldowns,
+    pub events: EventBus<FeatureEvent>,
+    pub father_belt: ReplySuppressions,
+    pub father_belt_metrics: FatherBeltMetrics,
+    pub feature_gates: FeatureGates,
+    pub guild_profiles: GuildProfiles,
     pub http: Arc<twilight_http::Client>,
+    pub join_monitor: JoinRateMonitor,
+    pub leadership: Leadership,
+    pub metrics: BotMetrics,
     pub pool: sqlx::PgPool,
     pub queue: BotQueue,
+    pub quiet_hours: QuietHours,
+    pub response_cache: ResponseCache,
     pub shard_manager: Arc<ShardManager>,
     pub settings: Arc<Settings>,
+    pub translator: Option<Translator>,
+    pub voice_sessions: VoiceSessions,
+    pub webhooks: WebhookManager,
 
     // Since application IDs are just u64 values, we can retain it
     // as long as it is a valid Twilight application ID.
     application_id: AtomicU64,
+    dry_run_sink: Option<DryRunSink>,
+    guild_settings_cache: GuildSettingsCache,
     is_local_guild_loaded: AtomicBool,
+    permissions_cache: PermissionsCache,
+    role_cache_metrics: RoleCacheMetrics,
 }
 
 impl Bot {
     #[allow(clippy::unwrap_used)]
-    pub fn new(settings: Arc<Settings>) -> Self {
+    pub fn new(settings: Arc<Settings>) -> Result<Self, CreateBotError> {
         let mut http = twilight_http::Client::builder()
-            .timeout(settings.bot.http.timeout)
+            .timeout(settings.bot.http.timeout.get())
             .token(settings.bot.token.expose().into());
 
         if let Some(proxy) = settings.bot.http.proxy.as_ref() {
@@ -46,7 +98,7 @@ impl Bot {
 
         let cache = InMemoryCache::builder()
             .resource_types(if settings.bot.http.use_cache {
-                crate::flags::CACHE_RESOURCE_TYPES
+                crate::flags::cache_resource_types(&settings.bot.http.cache_resources)
             } else {
                 ResourceType::empty()
             })
@@ -56,11 +108,11 @@ impl Bot {
         let cache = Arc::new(cache);
 
         let connect_options = settings.database.as_postgres_connect_options();
-        let statement_timeout = settings.database.query_timeout;
+        let statement_timeout = settings.database.query_timeout.get();
 
         let pool = PgPoolOptions::new()
-            .idle_timeout(settings.database.idle_timeout)
-            .acquire_timeout(settings.database.connect_timeout)
+            .idle_timeout(settings.database.idle_timeout.get())
+            .acquire_timeout(settings.database.connect_timeout.get())
             .max_connections(settings.database.max_connections)
             .min_connections(settings.database.min_connections)
             .test_before_acquire(true)
@@ -80,31 +132,71 @@ impl Bot {
             })
             .connect_lazy_with(connect_options);
 
+        let dry_run_sink = settings
+            .bot
+            .dry_run
+            .then(|| DryRunSink::new(settings.bot.dry_run_output.clone()));
+
+        let translator = settings.translation.as_ref().map(Translator::from_settings);
+
+        // followers must not run recurring tasks until they're elected
+        let leadership = Leadership::new(&settings.coordination);
+
+        // the queue worker needs a reference to the bot
+        let mut queue = QueueWorkerBuilder::new(settings.worker.id, pool.clone(), &settings.worker)
+            .change_context(CreateBotError)?;
+
+        // claims of instances that missed two heartbeats have expired
+        if settings.coordination.enabled {
+            let lease = settings.coordination.heartbeat_interval.to_time_delta() * 2;
+            queue = queue.instance(leadership.id(), lease);
+        }
+
         let inner = Arc::<BotInner>::new_cyclic(move |bot_weak| {
             let bot_weak = BotRef(bot_weak.clone());
             let command_state = CommandStates::new(bot_weak.clone(), &settings);
-            let queue = crate::tasks::register_all_tasks(QueueWorker::new(
-                settings.worker.id,
-                pool.clone(),
-                &settings.worker,
-                bot_weak.clone(),
-            ));
+            let queue = crate::tasks::register_all_tasks(queue.build(bot_weak.clone()));
+            queue.set_runs_recurring_tasks(leadership.is_leader());
+
             let shard_manager = ShardManager::new(bot_weak.clone(), settings.clone());
+            let webhooks = WebhookManager::new(bot_weak.clone());
             BotInner {
+                alerter: Alerter::new(bot_weak.clone()),
+                anti_spam: DuplicateMessageDetector::new(&settings.bot.moderation),
                 // no application id of 0 in twilight-model will accept this
                 application_id: AtomicU64::new(0),
+                blacklist: Blacklist::new(),
                 cache,
+                command_aliases: CommandAliases::new(),
+                dry_run_sink,
+                events: EventBus::new(EVENT_BUS_CAPACITY),
+                father_belt: ReplySuppressions::new(),
+                father_belt_metrics: FatherBeltMetrics::new(),
+                feature_gates: FeatureGates::from_settings(&settings.bot.feature_gates),
+                guild_profiles: GuildProfiles::new(),
+                guild_settings_cache: GuildSettingsCache::new(),
                 is_local_guild_loaded: AtomicBool::new(false),
                 http,
+                join_monitor: JoinRateMonitor::new(),
+                leadership,
+                metrics: BotMetrics::new(),
+                permissions_cache: PermissionsCache::new(),
+                role_cache_metrics: RoleCacheMetrics::default(),
                 command_state,
+                cooldowns: CommandCooldowns::new(&settings.bot.commands),
                 queue,
+                quiet_hours: QuietHours::new(),
+                response_cache: ResponseCache::new(&settings.bot.commands),
                 shard_manager,
                 settings,
                 pool,
+                translator,
+                voice_sessions: VoiceSessions::new(),
+                webhooks,
             }
         });
 
-        Self(inner)
+        Ok(Self(inner))
     }
 
     /// Gets the resolved application ID if it is loaded.
@@ -129,6 +221,13 @@ impl Bot {
         self.0.settings.bot.http.use_cache
     }
 
+    /// Whether all outgoing Discord mutations are recorded instead
+    /// of being executed.
+    #[must_use]
+    pub fn is_dry_run(&self) -> bool {
+        self.0.dry_run_sink.is_some()
+    }
+
     #[must_use]
     pub fn is_local_guild_loaded(&self) -> bool {
         self.is_local_guild_loaded.load(Ordering::Relaxed)
@@ -142,6 +241,10 @@ impl Bot {
         self.0.http.interaction(application_id)
     }
 
+    pub(crate) fn dry_run_sink(&self) -> Option<&DryRunSink> {
+        self.0.dry_run_sink.as_ref()
+    }
+
     pub(crate) fn on_local_guild_loaded(&self) {
         self.is_local_guild_loaded.store(true, Ordering::Relaxed);
     }
@@ -204,6 +307,7 @@ impl BotRef {
 
 pub(crate) type BotQueue = QueueWorker<BotRef>;
 
+#[allow(clippy::unwrap_used)]
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,25 +315,23 @@ mod tests {
     #[tokio::test]
     #[should_panic]
     async fn should_crash_in_interaction_fn_if_no_application_id() {
-        let settings = Arc::new(crate::tests::generate_fake_settings());
-        let bot = Bot::new(settings.clone());
+        let bot = crate::testkit::generate_fake_bot();
         let _client = bot.interaction();
     }
 
     #[tokio::test]
     async fn test_is_cache_enabled() {
-        let mut settings = crate::tests::generate_fake_settings();
+        let mut settings = crate::testkit::generate_fake_settings();
         settings.bot.http.use_cache = false;
 
         let settings = Arc::new(settings);
-        let bot = Bot::new(settings.clone());
+        let bot = Bot::new(settings.clone()).unwrap();
         assert_eq!(bot.is_cache_enabled(), false);
     }
 
     #[tokio::test]
     async fn test_override_application_id() {
-        let settings = crate::tests::generate_fake_settings();
-        let bot = Bot::new(Arc::new(settings));
+        let bot = crate::testkit::generate_fake_bot();
         assert_eq!(bot.checked_application_id(), None);
 
         let new_id = Id::new(273534239310479360);
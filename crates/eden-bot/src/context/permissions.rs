@@ -0,0 +1,397 @@
+use dashmap::DashMap;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::trace;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::permission_calculator::PermissionCalculator;
+
+use crate::util::http::request_for_model;
+use crate::Bot;
+
+/// How long the computed bot permissions per guild and channel
+/// will be reused before computing it again.
+const PERMISSIONS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Computed permissions of the bot in a specific guild and channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BotPermissions {
+    /// Permissions of the bot in the entire guild.
+    pub guild: Permissions,
+    /// Permissions of the bot in the specified channel.
+    ///
+    /// It is `None` if the channel information is not available
+    /// and it is not required to be fetched.
+    pub channel: Option<Permissions>,
+}
+
+/// Keeps track of how many times the guild roles were resolved from
+/// the cache instead of fetching the entire guild from Discord.
+#[derive(Debug, Default)]
+pub struct RoleCacheMetrics {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl RoleCacheMetrics {
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Ratio of cache hits from all role lookups, ranging from `0.0` to `1.0`.
+    ///
+    /// It returns `0.0` if there are no role lookups yet.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let hits = self.hits();
+        let total = hits.saturating_add(self.misses());
+        if total == 0 {
+            0.
+        } else {
+            hits as f64 / total as f64
+        }
+    }
+
+    fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedPermissions {
+    computed_at: Instant,
+    value: BotPermissions,
+}
+
+/// Short-lived cache of the bot's computed permissions per guild and channel.
+///
+/// This is to save HTTP request quota from Discord as computing
+/// permissions requires the entire guild information.
+pub(crate) struct PermissionsCache {
+    entries: DashMap<(Id<GuildMarker>, Id<ChannelMarker>), CachedPermissions>,
+    ttl: Duration,
+}
+
+impl PermissionsCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl: PERMISSIONS_CACHE_TTL,
+        }
+    }
+
+    fn get(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+        needs_channel_info: bool,
+    ) -> Option<BotPermissions> {
+        let entry = self.entries.get(&(guild_id, channel_id))?;
+        if entry.computed_at.elapsed() > self.ttl {
+            return None;
+        }
+
+        // cached entry may not have any channel permissions computed
+        if needs_channel_info && entry.value.channel.is_none() {
+            return None;
+        }
+
+        Some(entry.value)
+    }
+
+    fn insert(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+        value: BotPermissions,
+    ) {
+        // remove stale entries so it won't grow indefinitely
+        let ttl = self.ttl;
+        self.entries.retain(|_, v| v.computed_at.elapsed() <= ttl);
+        self.entries.insert(
+            (guild_id, channel_id),
+            CachedPermissions {
+                computed_at: Instant::now(),
+                value,
+            },
+        );
+    }
+
+    /// Invalidates all cached permissions from a specific guild.
+    pub fn invalidate_guild(&self, guild_id: Id<GuildMarker>) {
+        self.entries.retain(|(id, _), _| *id != guild_id);
+    }
+
+    /// Invalidates cached permissions of a specific guild channel.
+    pub fn invalidate_channel(&self, guild_id: Id<GuildMarker>, channel_id: Id<ChannelMarker>) {
+        self.entries.remove(&(guild_id, channel_id));
+    }
+}
+
+impl Bot {
+    /// Checks whether the bot has the `needed` permissions in a specific
+    /// guild channel before performing anything that requires it.
+    ///
+    /// Unlike command permission checks, this is intended to be used by
+    /// features that do not have any interaction to respond with. It returns
+    /// `false` if the bot is lacking permissions or has no access to
+    /// the channel at all.
+    #[tracing::instrument(skip(self))]
+    pub async fn preflight_permissions(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+        needed: Permissions,
+    ) -> Result<bool> {
+        if needed.is_empty() {
+            return Ok(true);
+        }
+
+        let result = self.permissions_in(guild_id, channel_id, true).await;
+        if let Some(info) = result.discord_http_error_info()
+            && info.has_missing_access()
+        {
+            trace!("got missing access error while trying to get channel info");
+            return Ok(false);
+        }
+
+        let permissions = result?;
+        let current = permissions.channel.unwrap_or(permissions.guild);
+        Ok(current.contains(needed))
+    }
+
+    /// Computes the bot's guild and channel permissions in a specific guild
+    /// channel. Computed permissions are cached for a short period of time.
+    ///
+    /// Channel permissions will not be fetched from Discord if it is not
+    /// in the cache and `needs_channel_info` is set to `false`.
+    #[tracing::instrument(skip(self))]
+    pub async fn permissions_in(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+        needs_channel_info: bool,
+    ) -> Result<BotPermissions> {
+        if let Some(cached) = self
+            .0
+            .permissions_cache
+            .get(guild_id, channel_id, needs_channel_info)
+        {
+            trace!("cache hit, got computed permissions from cache");
+            return Ok(cached);
+        }
+
+        let permissions = self
+            .compute_permissions(guild_id, channel_id, needs_channel_info)
+            .await?;
+
+        self.0
+            .permissions_cache
+            .insert(guild_id, channel_id, permissions);
+
+        Ok(permissions)
+    }
+
+    /// Invalidates all computed bot permissions from a specific guild.
+    pub(crate) fn invalidate_permissions(&self, guild_id: Id<GuildMarker>) {
+        self.0.permissions_cache.invalidate_guild(guild_id);
+    }
+
+    /// Invalidates computed bot permissions of a specific guild channel.
+    pub(crate) fn invalidate_channel_permissions(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+    ) {
+        self.0
+            .permissions_cache
+            .invalidate_channel(guild_id, channel_id);
+    }
+
+    async fn compute_permissions(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+        needs_channel_info: bool,
+    ) -> Result<BotPermissions> {
+        let cache = &self.cache;
+        let bot_id = self.application_id().cast::<UserMarker>();
+
+        let member_roles = if let Some(member) = cache.member(guild_id, bot_id) {
+            trace!("cache hit, got member info from cache");
+            member.roles().to_vec()
+        } else {
+            trace!("cache miss, getting member info from Discord API");
+            request_for_model(self, self.http.guild_member(guild_id, bot_id))
+                .await?
+                .roles
+        };
+
+        let (everyone_role, member_roles) = self.role_permissions(guild_id, &member_roles).await?;
+
+        let mut channel_kind = None;
+        let mut overwrites = None;
+
+        if let Some(channel) = cache.channel(channel_id) {
+            trace!("cache hit, got channel info from cache");
+
+            let overwrites_data = channel.permission_overwrites.clone().unwrap_or_default();
+            channel_kind = Some(channel.kind);
+            overwrites = Some(overwrites_data);
+        } else if needs_channel_info {
+            // do not request for channels stuff if it is not really required anyways.
+            trace!("cache miss, getting channel info from Discord API");
+
+            let channel = request_for_model(self, self.http.channel(channel_id)).await?;
+            channel_kind = Some(channel.kind);
+            overwrites = Some(channel.permission_overwrites.unwrap_or_default());
+        } else {
+            trace!("cache miss, not getting channel info from Discord API");
+        }
+
+        trace!(?member_roles, ?everyone_role);
+        let calculator = PermissionCalculator::new(guild_id, bot_id, everyone_role, &member_roles);
+
+        let channel = channel_kind
+            .zip(overwrites)
+            .map(|(channel_kind, overwrites)| calculator.in_channel(channel_kind, &overwrites));
+
+        Ok(BotPermissions {
+            guild: calculator.root(),
+            channel,
+        })
+    }
+
+    /// Gets the permissions of the `@everyone` role and the member's roles
+    /// from the cache first before fetching the entire guild from Discord.
+    async fn role_permissions(
+        &self,
+        guild_id: Id<GuildMarker>,
+        member_roles: &[Id<RoleMarker>],
+    ) -> Result<(Permissions, Vec<(Id<RoleMarker>, Permissions)>)> {
+        if let Some(cached) = self.cached_role_permissions(guild_id, member_roles) {
+            trace!("cache hit, got role permissions from cache");
+            self.0.role_cache_metrics.record_hit();
+            return Ok(cached);
+        }
+
+        trace!("cache miss, getting guild roles from Discord API");
+        self.0.role_cache_metrics.record_miss();
+
+        let guild = request_for_model(self, self.http.guild(guild_id)).await?;
+        let everyone_role = crate::util::get_everyone_role(&guild)
+            .map(|v| v.permissions)
+            .unwrap_or_else(Permissions::empty);
+
+        let member_roles = crate::util::get_member_role_perms(member_roles, &guild.roles);
+        Ok((everyone_role, member_roles))
+    }
+
+    fn cached_role_permissions(
+        &self,
+        guild_id: Id<GuildMarker>,
+        member_roles: &[Id<RoleMarker>],
+    ) -> Option<(Permissions, Vec<(Id<RoleMarker>, Permissions)>)> {
+        // @everyone role's ID is the same as the guild's ID
+        let everyone_role = self.cache.role(guild_id.cast())?.permissions;
+        let member_roles = member_roles
+            .iter()
+            .map(|id| self.cache.role(*id).map(|role| (*id, role.permissions)))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some((everyone_role, member_roles))
+    }
+
+    /// Gets the hit rate metrics of guild roles cache while computing
+    /// the bot's permissions.
+    #[must_use]
+    pub fn role_cache_metrics(&self) -> &RoleCacheMetrics {
+        &self.0.role_cache_metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_permissions() -> BotPermissions {
+        BotPermissions {
+            guild: Permissions::SEND_MESSAGES,
+            channel: None,
+        }
+    }
+
+    #[test]
+    fn should_not_return_entry_without_channel_info_if_needed() {
+        let cache = PermissionsCache::new();
+        let guild_id = Id::new(1);
+        let channel_id = Id::new(2);
+
+        cache.insert(guild_id, channel_id, sample_permissions());
+        assert_eq!(
+            cache.get(guild_id, channel_id, false),
+            Some(sample_permissions())
+        );
+        assert_eq!(cache.get(guild_id, channel_id, true), None);
+    }
+
+    #[test]
+    fn should_not_return_expired_entries() {
+        let mut cache = PermissionsCache::new();
+        cache.ttl = Duration::ZERO;
+
+        let guild_id = Id::new(1);
+        let channel_id = Id::new(2);
+        cache.insert(guild_id, channel_id, sample_permissions());
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.get(guild_id, channel_id, false), None);
+    }
+
+    #[test]
+    fn role_cache_hit_rate() {
+        let metrics = RoleCacheMetrics::default();
+        assert_eq!(metrics.hit_rate(), 0.);
+
+        metrics.record_hit();
+        metrics.record_hit();
+        metrics.record_hit();
+        metrics.record_miss();
+        assert_eq!(metrics.hit_rate(), 0.75);
+    }
+
+    #[test]
+    fn invalidate_guild() {
+        let cache = PermissionsCache::new();
+        cache.insert(Id::new(1), Id::new(2), sample_permissions());
+        cache.insert(Id::new(3), Id::new(4), sample_permissions());
+
+        cache.invalidate_guild(Id::new(1));
+        assert_eq!(cache.get(Id::new(1), Id::new(2), false), None);
+        assert!(cache.get(Id::new(3), Id::new(4), false).is_some());
+    }
+    #[test]
+    fn invalidate_channel() {
+        let cache = PermissionsCache::new();
+        cache.insert(Id::new(1), Id::new(2), sample_permissions());
+        cache.insert(Id::new(1), Id::new(3), sample_permissions());
+
+        cache.invalidate_channel(Id::new(1), Id::new(2));
+        assert_eq!(cache.get(Id::new(1), Id::new(2), false), None);
+        assert!(cache.get(Id::new(1), Id::new(3), false).is_some());
+    }
+}
@@ -0,0 +1,200 @@
+//! Local control socket where administrators (or a future web dashboard)
+//! can inspect and manage Eden's task queue without exposing a public
+//! HTTP API.
+//!
+//! Every request and response is a single JSON line. Requests are
+//! REST-ish, for example:
+//!
+//! ```text
+//! {"method":"GET","path":"/tasks?status=failed&type=foo&page=1&limit=25"}
+//! {"method":"GET","path":"/tasks/<id>"}
+//! {"method":"POST","path":"/tasks/<id>/requeue"}
+//! {"method":"POST","path":"/tasks/<id>/cancel"}
+//! {"method":"DELETE","path":"/tasks/<id>"}
+//! {"method":"GET","path":"/tasks/events"}
+//! ```
+//!
+//! Subscribing to task events keeps sending every task event as a
+//! JSON line until the connection is closed.
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::Serialize;
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+use tokio::io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, instrument, warn};
+
+use self::request::{ControlRequest, ControlResponse, Route};
+use crate::errors::ControlSocketError;
+use crate::Bot;
+
+mod request;
+mod tasks;
+
+/// Listens for control socket connections until Eden shuts down
+/// if `bot.control_socket` is set.
+#[instrument(skip_all)]
+pub async fn serve(bot: Bot) {
+    let Some(path) = bot.settings.bot.control_socket.clone() else {
+        return;
+    };
+
+    // the socket file from the previous run is not removed if
+    // Eden crashed or got killed. anything else in that path is
+    // left alone so a misconfigured path cannot delete a file.
+    match std::fs::symlink_metadata(&path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            if let Err(error) = std::fs::remove_file(&path) {
+                warn!(%error, ?path, "could not remove old control socket");
+                return;
+            }
+        }
+        Ok(..) => {
+            warn!(
+                ?path,
+                "could not bind control socket because the path is not a socket"
+            );
+            return;
+        }
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => {
+            warn!(%error, ?path, "could not check old control socket");
+            return;
+        }
+    }
+
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(error) => {
+            warn!(%error, ?path, "could not bind control socket");
+            return;
+        }
+    };
+    info!(?path, "listening for control socket connections");
+
+    loop {
+        tokio::select! {
+            result = listener.accept() => match result {
+                Ok((stream, ..)) => {
+                    eden_utils::tokio::spawn(
+                        "eden_bot::control::handle_connection",
+                        handle_connection(bot.clone(), stream),
+                    );
+                }
+                Err(error) => warn!(%error, "could not accept control socket connection"),
+            },
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+    }
+
+    remove_socket(&path);
+}
+
+fn remove_socket(path: &Path) {
+    if let Err(error) = std::fs::remove_file(path) {
+        warn!(%error, ?path, "could not remove control socket");
+    }
+}
+
+#[instrument(skip_all)]
+async fn handle_connection(bot: Bot, stream: UnixStream) {
+    debug!("accepted control socket connection");
+
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line,
+            _ = eden_utils::shutdown::graceful() => break,
+        };
+        let line = match line {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(error) => {
+                debug!(%error, "could not read control socket request");
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let route = serde_json::from_str::<ControlRequest>(&line)
+            .map_err(|error| ControlResponse::error(400, format!("invalid request: {error}")))
+            .and_then(|request| request.route());
+
+        let result = match route {
+            Ok(route) => handle_route(&bot, route, &mut writer).await,
+            Err(response) => write_line(&mut writer, &response).await,
+        };
+
+        if let Err(error) = result {
+            debug!(%error, "control socket connection closed");
+            break;
+        }
+    }
+}
+
+async fn handle_route<W>(bot: &Bot, route: Route, writer: &mut W) -> Result<(), ControlSocketError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let result = match route {
+        Route::ListTasks(filter) => self::tasks::list(bot, &filter).await,
+        Route::GetTask(id) => self::tasks::get(bot, id).await,
+        Route::RequeueTask(id) => self::tasks::requeue(bot, id).await,
+        Route::CancelTask(id) => self::tasks::cancel(bot, id).await,
+        Route::DeleteTask(id) => self::tasks::delete(bot, id).await,
+        Route::TaskEvents => return stream_task_events(bot, writer).await,
+    };
+
+    let response = result.unwrap_or_else(|error| {
+        warn!(error = %error.anonymize(), "could not handle control socket request");
+        ControlResponse::error(500, "internal error. check the logs for more details")
+    });
+    write_line(writer, &response).await
+}
+
+async fn stream_task_events<W>(bot: &Bot, writer: &mut W) -> Result<(), ControlSocketError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut events = bot.queue.subscribe_events();
+    write_line(writer, &ControlResponse::ok(&())).await?;
+
+    loop {
+        let event = tokio::select! {
+            event = events.recv() => event,
+            _ = eden_utils::shutdown::graceful() => return Ok(()),
+        };
+        match event {
+            Ok(event) => write_line(writer, &event).await?,
+            Err(RecvError::Lagged(skipped)) => {
+                let message = format!("skipped {skipped} task event(s)");
+                write_line(writer, &ControlResponse::error(500, message)).await?;
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+async fn write_line<W, T>(writer: &mut W, value: &T) -> Result<(), ControlSocketError>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_vec(value)
+        .into_typed_error()
+        .change_context(ControlSocketError)
+        .attach_printable("could not serialize control socket response")?;
+    line.push(b'\n');
+
+    writer
+        .write_all(&line)
+        .await
+        .into_typed_error()
+        .change_context(ControlSocketError)
+        .attach_printable("could not write control socket response")
+}
@@ -0,0 +1,235 @@
+use eden_tasks_schema::types::TaskStatus;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+use uuid::Uuid;
+
+/// Maximum amount of tasks that can be listed at once.
+const MAX_TASKS_PER_PAGE: u64 = 100;
+const DEFAULT_TASKS_PER_PAGE: u64 = 25;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Method {
+    Get,
+    Post,
+    Delete,
+}
+
+/// A request sent from the control socket as a single JSON line
+/// (like `{"method":"GET","path":"/tasks?status=failed"}`).
+#[derive(Debug, Deserialize)]
+pub struct ControlRequest {
+    pub method: Method,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskFilter {
+    pub kind: Option<String>,
+    pub limit: u64,
+    pub page: u64,
+    pub periodic: Option<bool>,
+    pub status: Option<TaskStatus>,
+}
+
+impl Default for TaskFilter {
+    fn default() -> Self {
+        Self {
+            kind: None,
+            limit: DEFAULT_TASKS_PER_PAGE,
+            page: 1,
+            periodic: None,
+            status: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// `GET /tasks`
+    ListTasks(TaskFilter),
+    /// `GET /tasks/events`
+    TaskEvents,
+    /// `GET /tasks/{id}`
+    GetTask(Uuid),
+    /// `POST /tasks/{id}/requeue`
+    RequeueTask(Uuid),
+    /// `POST /tasks/{id}/cancel`
+    CancelTask(Uuid),
+    /// `DELETE /tasks/{id}`
+    DeleteTask(Uuid),
+}
+
+impl ControlRequest {
+    pub fn route(&self) -> Result<Route, ControlResponse> {
+        let (path, query) = self.path.split_once('?').unwrap_or((&self.path, ""));
+        let segments = path
+            .split('/')
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>();
+
+        let route = match (self.method, segments.as_slice()) {
+            (Method::Get, ["tasks"]) => Route::ListTasks(parse_task_filter(query)?),
+            (Method::Get, ["tasks", "events"]) => Route::TaskEvents,
+            (Method::Get, ["tasks", id]) => Route::GetTask(parse_task_id(id)?),
+            (Method::Post, ["tasks", id, "requeue"]) => Route::RequeueTask(parse_task_id(id)?),
+            (Method::Post, ["tasks", id, "cancel"]) => Route::CancelTask(parse_task_id(id)?),
+            (Method::Delete, ["tasks", id]) => Route::DeleteTask(parse_task_id(id)?),
+            _ => {
+                return Err(ControlResponse::error(
+                    404,
+                    format!("no route for {:?} {path}", self.method),
+                ))
+            }
+        };
+        Ok(route)
+    }
+}
+
+fn parse_task_id(value: &str) -> Result<Uuid, ControlResponse> {
+    Uuid::parse_str(value)
+        .map_err(|_| ControlResponse::error(400, format!("invalid task id: {value:?}")))
+}
+
+fn parse_task_filter(query: &str) -> Result<TaskFilter, ControlResponse> {
+    let invalid = |key: &str, value: &str| {
+        ControlResponse::error(400, format!("invalid value for {key:?}: {value:?}"))
+    };
+
+    let mut filter = TaskFilter::default();
+    for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        match key.as_ref() {
+            "limit" => {
+                let limit = value.parse::<u64>().map_err(|_| invalid(&key, &value))?;
+                filter.limit = limit.clamp(1, MAX_TASKS_PER_PAGE);
+            }
+            "page" => {
+                filter.page = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|v| *v > 0)
+                    .ok_or_else(|| invalid(&key, &value))?;
+            }
+            "periodic" => {
+                filter.periodic = Some(value.parse().map_err(|_| invalid(&key, &value))?);
+            }
+            "status" => {
+                let status = serde_json::from_value(Json::String(value.to_string()))
+                    .map_err(|_| invalid(&key, &value))?;
+                filter.status = Some(status);
+            }
+            "type" => filter.kind = Some(value.into_owned()),
+            _ => {
+                return Err(ControlResponse::error(
+                    400,
+                    format!("unknown query parameter: {key:?}"),
+                ))
+            }
+        }
+    }
+    Ok(filter)
+}
+
+/// A response sent back to the control socket as a single JSON line.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ControlResponse {
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Json>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ControlResponse {
+    #[must_use]
+    pub fn ok(body: &impl Serialize) -> Self {
+        match serde_json::to_value(body) {
+            Ok(body) => Self {
+                status: 200,
+                body: Some(body),
+                error: None,
+            },
+            Err(error) => Self::error(500, format!("could not serialize response: {error}")),
+        }
+    }
+
+    #[must_use]
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: None,
+            error: Some(message.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(method: Method, path: &str) -> Result<Route, ControlResponse> {
+        ControlRequest {
+            method,
+            path: path.into(),
+        }
+        .route()
+    }
+
+    #[test]
+    fn should_route_task_endpoints() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            route(Method::Get, "/tasks"),
+            Ok(Route::ListTasks(TaskFilter::default()))
+        );
+        assert_eq!(route(Method::Get, "/tasks/events"), Ok(Route::TaskEvents));
+        assert_eq!(
+            route(Method::Get, &format!("/tasks/{id}")),
+            Ok(Route::GetTask(id))
+        );
+        assert_eq!(
+            route(Method::Post, &format!("/tasks/{id}/requeue")),
+            Ok(Route::RequeueTask(id))
+        );
+        assert_eq!(
+            route(Method::Post, &format!("/tasks/{id}/cancel")),
+            Ok(Route::CancelTask(id))
+        );
+        assert_eq!(
+            route(Method::Delete, &format!("/tasks/{id}/")),
+            Ok(Route::DeleteTask(id))
+        );
+
+        assert_eq!(
+            route(Method::Delete, "/tasks").map_err(|v| v.status),
+            Err(404)
+        );
+        assert_eq!(
+            route(Method::Get, "/tasks/123").map_err(|v| v.status),
+            Err(400)
+        );
+    }
+
+    #[test]
+    fn should_parse_task_filters() {
+        let filter = route(
+            Method::Get,
+            "/tasks?status=failed&type=foo%20bar&page=2&limit=500",
+        );
+        assert_eq!(
+            filter,
+            Ok(Route::ListTasks(TaskFilter {
+                kind: Some("foo bar".into()),
+                limit: MAX_TASKS_PER_PAGE,
+                page: 2,
+                periodic: None,
+                status: Some(TaskStatus::Failed),
+            }))
+        );
+
+        for query in ["status=unknown", "page=0", "periodic=maybe", "foo=bar"] {
+            let result = route(Method::Get, &format!("/tasks?{query}"));
+            assert_eq!(result.map_err(|v| v.status), Err(400), "{query}");
+        }
+    }
+}
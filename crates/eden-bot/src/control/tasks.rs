@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use eden_tasks::TaskErrorEntry;
+use eden_tasks_schema::types::{Task, TaskStatus};
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::Serialize;
+use serde_json::Value as Json;
+use uuid::Uuid;
+
+use super::request::{ControlResponse, TaskFilter};
+use crate::Bot;
+
+/// Task as seen from the control socket.
+#[derive(Debug, Serialize)]
+struct TaskView {
+    id: Uuid,
+    #[serde(rename = "type")]
+    kind: String,
+    status: TaskStatus,
+    priority: String,
+    attempts: i32,
+    periodic: bool,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    deadline: DateTime<Utc>,
+    last_retry: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Json>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<Vec<TaskErrorEntry>>,
+}
+
+impl TaskView {
+    fn summary(task: Task) -> Self {
+        Self {
+            id: task.id,
+            kind: task.data.kind,
+            status: task.status,
+            priority: format!("{:?}", task.priority).to_lowercase(),
+            attempts: task.attempts,
+            periodic: task.periodic,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            deadline: task.deadline,
+            last_retry: task.last_retry,
+            data: None,
+            errors: None,
+        }
+    }
+
+    fn detailed(bot: &Bot, task: Task) -> Self {
+        let errors = bot.queue.task_errors(task.id);
+        let data = task.data.inner.clone();
+        Self {
+            data: Some(data),
+            errors: Some(errors),
+            ..Self::summary(task)
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TaskList {
+    page: u64,
+    tasks: Vec<TaskView>,
+}
+
+pub async fn list(bot: &Bot, filter: &TaskFilter) -> Result<ControlResponse> {
+    let mut query = Task::get_all(bot.queue.id()).read_only();
+    if let Some(kind) = filter.kind.as_deref() {
+        query = query.task_type(kind);
+    }
+    if let Some(periodic) = filter.periodic {
+        query = query.periodic(periodic);
+    }
+    if let Some(status) = filter.status {
+        query = query.status(status);
+    }
+
+    let mut conn = bot.db_read().await?;
+    let mut pages = query.build().size(filter.limit);
+    let mut tasks = Vec::new();
+    for _ in 0..filter.page {
+        match pages.next(&mut conn).await.anonymize_error()? {
+            Some(page) => tasks = page,
+            None => {
+                tasks.clear();
+                break;
+            }
+        }
+    }
+
+    Ok(ControlResponse::ok(&TaskList {
+        page: filter.page,
+        tasks: tasks.into_iter().map(TaskView::summary).collect(),
+    }))
+}
+
+pub async fn get(bot: &Bot, id: Uuid) -> Result<ControlResponse> {
+    let mut conn = bot.db_read().await?;
+    let task = Task::from_id(&mut conn, id).await.anonymize_error()?;
+    Ok(match task {
+        Some(task) => ControlResponse::ok(&TaskView::detailed(bot, task)),
+        None => not_found(id),
+    })
+}
+
+pub async fn requeue(bot: &Bot, id: Uuid) -> Result<ControlResponse> {
+    if bot.queue.requeue_task(id).await.anonymize_error()? {
+        Ok(ControlResponse::ok(&serde_json::json!({ "id": id })))
+    } else {
+        Ok(ControlResponse::error(
+            404,
+            format!("task {id} does not exist or is currently running"),
+        ))
+    }
+}
+
+pub async fn cancel(bot: &Bot, id: Uuid) -> Result<ControlResponse> {
+    if bot.queue.cancel(id).await.anonymize_error()? {
+        Ok(ControlResponse::ok(&serde_json::json!({ "id": id })))
+    } else {
+        Ok(ControlResponse::error(
+            404,
+            format!("task {id} does not exist or has already finished"),
+        ))
+    }
+}
+
+pub async fn delete(bot: &Bot, id: Uuid) -> Result<ControlResponse> {
+    if bot.queue.delete_queued_task(id).await.anonymize_error()? {
+        Ok(ControlResponse::ok(&serde_json::json!({ "id": id })))
+    } else {
+        Ok(not_found(id))
+    }
+}
+
+fn not_found(id: Uuid) -> ControlResponse {
+    ControlResponse::error(404, format!("task {id} does not exist"))
+}
@@ -4,10 +4,18 @@ use thiserror::Error;
 #[error("Eden bot failed")]
 pub struct StartBotError;
 
+#[derive(Debug, Error)]
+#[error("could not create bot")]
+pub struct CreateBotError;
+
 #[derive(Debug, Error)]
 #[error("could not perform database migrations")]
 pub struct MigrateError;
 
+#[derive(Debug, Error)]
+#[error("could not wait for the services Eden depends on")]
+pub struct WaitForDependenciesError;
+
 #[derive(Debug, Error)]
 #[error("could not update local guild admins")]
 pub struct UpdateLocalGuildAdminsError;
@@ -20,6 +28,18 @@ pub struct SetupLocalGuildError;
 #[error("failed to send welcome message to local guild")]
 pub struct SendWelcomeMessageError;
 
+#[derive(Debug, Error)]
+#[error("failed to send alert")]
+pub struct SendAlertError;
+
+#[derive(Debug, Error)]
+#[error("failed to send log to the member log channel")]
+pub struct SendMemberLogError;
+
+#[derive(Debug, Error)]
+#[error("failed to send log to the server log channel")]
+pub struct SendServerLogError;
+
 #[derive(Debug, Error)]
 #[error("failed to perform HTTP request to Discord")]
 pub struct RequestHttpError;
@@ -37,6 +57,7 @@ pub mod tags {
         crate::interactions::tags::install_hook();
     }
 
+    #[derive(Clone)]
     pub struct RequestHttpTag {
         method: twilight_http::request::Method,
         path: String,
@@ -73,3 +94,7 @@ pub mod tags {
         }
     }
 }
+
+#[derive(Debug, Error)]
+#[error("could not respond to control socket request")]
+pub struct ControlSocketError;
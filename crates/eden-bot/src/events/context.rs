@@ -21,3 +21,14 @@ impl EventContext {
             .unwrap_or(Duration::ZERO)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn should_have_no_latency_before_heartbeats() {
+        let bot = crate::testkit::generate_fake_bot();
+        let ctx = crate::testkit::generate_fake_event_context(bot);
+        assert_eq!(ctx.get_latency(), std::time::Duration::ZERO);
+        assert_eq!(ctx.shard.id().number(), 0);
+    }
+}
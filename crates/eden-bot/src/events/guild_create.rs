@@ -1,8 +1,12 @@
+use chrono::Utc;
 use eden_tasks::Scheduled;
 use eden_utils::Result;
 use tracing::{debug, warn};
 use twilight_model::guild::Guild;
 
+use crate::features::blacklist;
+use crate::features::guild_profile::GuildProfile;
+use crate::features::scheduled_events;
 use crate::tasks;
 
 use super::EventContext;
@@ -14,13 +18,24 @@ use super::EventContext;
 ))]
 pub async fn handle(ctx: &EventContext, guild: Guild) -> Result<()> {
     if !ctx.bot.is_local_guild(&guild) {
-        return Ok(());
+        return blacklist::on_guild_create(&ctx.bot, guild.id).await;
     }
 
     // We may want to load their settings in and save it as cache
     ctx.bot.on_local_guild_loaded();
     debug!("found local guild of {}", guild.id);
 
+    let profile = GuildProfile::from_guild(&guild);
+    ctx.bot.guild_profiles.store(guild.id, profile);
+
+    // members may already be in voice channels before the bot connects
+    let now = Utc::now();
+    for state in guild.voice_states.iter().filter(|v| v.channel_id.is_some()) {
+        ctx.bot.voice_sessions.join(guild.id, state.user_id, now);
+    }
+
+    scheduled_events::on_guild_create(&ctx.bot, &guild).await;
+
     if let Err(error) = crate::local_guild::setup(&ctx.bot, &guild).await {
         let error = error.anonymize();
         warn!(%error, "unable to setup local guild. scheduling task to setup local guild later...");
@@ -1,11 +1,19 @@
+use eden_utils::error::exts::*;
+use eden_utils::trace::TraceId;
 use eden_utils::Result;
 use tracing::{debug, warn};
 use twilight_model::application::interaction::{
-    application_command::CommandData, Interaction, InteractionData, InteractionType,
+    application_command::CommandData, message_component::MessageComponentInteractionData,
+    Interaction, InteractionData, InteractionType,
 };
+use twilight_model::channel::message::MessageFlags;
 
 use super::EventContext;
-use crate::interactions::commands::CommandContext;
+use crate::features::{blacklist, emoji, payer_queue, raid, undo, verification};
+use crate::interactions::commands::local_guild::admin::config;
+use crate::interactions::commands::local_guild::move_message;
+use crate::interactions::commands::{help, CommandContext};
+use crate::interactions::InteractionContext;
 
 #[tracing::instrument(skip_all, fields(
     interaction.channel.id = ?interaction.channel.as_ref().map(|v| v.id),
@@ -13,19 +21,36 @@ use crate::interactions::commands::CommandContext;
     interaction.invoker = ?interaction.author_id(),
     interaction.is_guild = ?interaction.is_guild(),
     interaction.locale = ?interaction.locale,
+    trace_id = tracing::field::Empty,
 ))]
 pub async fn handle(ctx: &EventContext, interaction: Interaction) -> Result<()> {
+    // every log line and error of this interaction can be looked up
+    // with its trace ID (shown to users with developer mode on)
+    TraceId::new()
+        .scope(handle_interaction(ctx, interaction))
+        .await
+}
+
+async fn handle_interaction(ctx: &EventContext, interaction: Interaction) -> Result<()> {
     let Some(data) = &interaction.data else {
         warn!("got interaction with no data");
         return Ok(());
     };
 
+    if blacklist::on_interaction(ctx, &interaction).await {
+        return Ok(());
+    }
+
     let kind = interaction.kind;
     let result = match data {
         InteractionData::ApplicationCommand(data) => {
             let data = *data.clone();
             handle_command(ctx, data, interaction).await
         }
+        InteractionData::MessageComponent(data) => {
+            let data = MessageComponentInteractionData::clone(data);
+            handle_component(ctx, data, interaction).await
+        }
         _ => {
             warn!("got unimplemented {kind:?} interaction type");
             Ok(())
@@ -52,7 +77,9 @@ async fn handle_command(
 ) -> Result<()> {
     debug!("received command interaction");
 
-    let command_ctx = CommandContext::new(ctx.bot.clone(), ctx, data, &interaction);
+    let mut command_ctx = CommandContext::new(ctx.bot.clone(), ctx, data, &interaction);
+    crate::interactions::commands::resolve_alias(&mut command_ctx);
+
     match command_ctx.interaction.kind {
         InteractionType::ApplicationCommand => {
             let span = tracing::Span::current();
@@ -62,8 +89,7 @@ async fn handle_command(
                     tracing::field::display(command_ctx.command_name()),
                 );
             }
-            // we cannot guarantee that commands do run fast
-            command_ctx.defer(false).await?;
+            ctx.bot.metrics.record_command();
             crate::interactions::commands::handle(command_ctx).await?;
         }
         unknown => {
@@ -72,3 +98,53 @@ async fn handle_command(
     }
     Ok(())
 }
+
+#[tracing::instrument(skip_all, fields(
+    component.custom_id = %data.custom_id,
+    component.kind = ?data.component_type,
+))]
+async fn handle_component(
+    ctx: &EventContext,
+    data: MessageComponentInteractionData,
+    interaction: Interaction,
+) -> Result<()> {
+    debug!("received component interaction");
+
+    let prefix = data
+        .custom_id
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let component_ctx = InteractionContext::new(ctx.bot.clone(), ctx, data, &interaction);
+    let result = match prefix.as_str() {
+        config::CUSTOM_ID_PREFIX => config::on_page(&component_ctx).await,
+        emoji::CUSTOM_ID_PREFIX => emoji::on_review(&component_ctx).await,
+        help::CUSTOM_ID_PREFIX => help::on_page(&component_ctx).await,
+        move_message::CUSTOM_ID_PREFIX => move_message::on_select(&component_ctx).await,
+        payer_queue::CUSTOM_ID_PREFIX => payer_queue::on_action(&component_ctx).await,
+        raid::CUSTOM_ID_PREFIX => raid::on_end_lockdown(&component_ctx).await,
+        undo::CUSTOM_ID_PREFIX => undo::on_undo(&component_ctx).await,
+        verification::CUSTOM_ID_PREFIX => verification::on_verify(&component_ctx).await,
+        _ => {
+            warn!("got unknown component interaction");
+            Ok(())
+        }
+    };
+
+    let Err(error) = result else {
+        return Ok(());
+    };
+
+    // let the user know that something went wrong
+    let mut data =
+        crate::interactions::util::from_error(false, false, ctx.bot.is_sentry_enabled(), &error);
+    data.flags = Some(MessageFlags::EPHEMERAL);
+
+    component_ctx
+        .respond(data)
+        .await
+        .attach_printable("could not respond component while trying to send error message")?;
+
+    Err(error)
+}
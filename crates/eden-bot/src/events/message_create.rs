@@ -2,7 +2,7 @@ use eden_utils::Result;
 use tracing::trace;
 use twilight_model::channel::Message;
 
-use crate::features::father_belt;
+use crate::features::{anti_spam, father_belt};
 use crate::interactions::state::StatefulCommandTrigger;
 
 use super::EventContext;
@@ -31,6 +31,7 @@ pub async fn handle(ctx: &EventContext, message: Message) -> Result<()> {
         ));
 
     father_belt::on_message_create(ctx, &message).await;
+    anti_spam::on_message_create(ctx, &message).await;
 
     Ok(())
 }
@@ -7,9 +7,21 @@ mod ready;
 pub use self::context::*;
 
 use eden_utils::Result;
+#[cfg(debug_assertions)]
+use futures::future::BoxFuture;
 use tracing::{debug, warn};
 use twilight_gateway::Event;
 
+/// Handles a fake gateway event as if it is received from Discord.
+///
+/// The future is boxed since events can be simulated from commands which
+/// are handled from [`handle_event`] itself. The cache will not be updated
+/// with the simulated event to avoid storing fake data.
+#[cfg(debug_assertions)]
+pub fn handle_simulated_event(ctx: EventContext, event: Event) -> BoxFuture<'static, ()> {
+    Box::pin(handle_event(ctx, event))
+}
+
 #[tracing::instrument(skip_all, fields(
     ctx.latency = ?ctx.get_latency(),
     ctx.shard.id = %ctx.shard.id(),
@@ -19,17 +31,80 @@ use twilight_gateway::Event;
 pub async fn handle_event(ctx: EventContext, event: Event) {
     let event_kind = event.kind();
     let result: Result<()> = match event {
+        Event::ChannelCreate(..) => Ok(()),
+        Event::ChannelDelete(data) => {
+            if let Some(guild_id) = data.guild_id {
+                ctx.bot.invalidate_channel_permissions(guild_id, data.id);
+            }
+            Ok(())
+        }
+        Event::ChannelUpdate(data) => {
+            // permission overwrites of the channel may have changed
+            if let Some(guild_id) = data.guild_id {
+                ctx.bot.invalidate_channel_permissions(guild_id, data.id);
+            }
+            Ok(())
+        }
         Event::GuildCreate(guild) => self::guild_create::handle(&ctx, guild.0).await,
+        Event::GuildUpdate(data) => {
+            crate::features::guild_profile::on_guild_update(&ctx, &data.0).await;
+            Ok(())
+        }
+        Event::GuildScheduledEventCreate(data) => {
+            crate::features::scheduled_events::on_event_changed(&ctx, &data.0).await;
+            Ok(())
+        }
+        // reminders of deleted events are skipped once they're due
+        Event::GuildScheduledEventDelete(..) => Ok(()),
+        Event::GuildScheduledEventUpdate(data) => {
+            crate::features::scheduled_events::on_event_changed(&ctx, &data.0).await;
+            Ok(())
+        }
         Event::InteractionCreate(data) => self::interaction::handle(&ctx, data.0).await,
         Event::MessageCreate(data) => self::message_create::handle(&ctx, data.0).await,
         Event::MessageDelete(..) => Ok(()),
         Event::MessageDeleteBulk(..) => Ok(()),
-        Event::MemberUpdate(..) => Ok(()),
+        Event::MemberAdd(data) => {
+            crate::features::raid::on_member_add(&ctx, data.guild_id, &data.member).await;
+            crate::features::verification::on_member_add(&ctx, data.guild_id, &data.member).await;
+            crate::features::auto_role::on_member_add(&ctx, data.guild_id, &data.member).await;
+            Ok(())
+        }
+        Event::MemberUpdate(data) => {
+            // bot's roles may have changed, so are its permissions
+            let bot_id = ctx.bot.checked_application_id().map(|v| v.cast());
+            if bot_id == Some(data.user.id) {
+                ctx.bot.invalidate_permissions(data.guild_id);
+            }
+            Ok(())
+        }
+        // no features handle reactions yet but they can be simulated
+        // from `/admin simulate event` to test them.
+        Event::ReactionAdd(data) => {
+            debug!(
+                "member {} reacted to message {} with {:?}",
+                data.user_id, data.message_id, data.emoji
+            );
+            Ok(())
+        }
         Event::Ready(data) => self::ready::handle(&ctx, &data).await,
+        Event::RoleCreate(..) => Ok(()),
+        Event::RoleDelete(data) => {
+            ctx.bot.invalidate_permissions(data.guild_id);
+            Ok(())
+        }
+        Event::RoleUpdate(data) => {
+            ctx.bot.invalidate_permissions(data.guild_id);
+            Ok(())
+        }
         Event::Resumed => {
             debug!("successfully resumed gateway session");
             Ok(())
         }
+        Event::VoiceStateUpdate(data) => {
+            crate::features::voice_stats::on_voice_state_update(&ctx, &data.0);
+            Ok(())
+        }
         Event::GatewayClose(..) => Ok(()),
         _ => {
             warn!("received unimplemented {event_kind:?} event");
@@ -0,0 +1,252 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+use eden_settings::{ContentMode, Moderation};
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use itertools::Itertools;
+use tracing::{instrument, trace, warn};
+use twilight_model::channel::Message;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::{ChannelMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::events::EventContext;
+use crate::features::FeatureEvent;
+use crate::util::http::request_for_model;
+
+/// Detects members sending the same message over and over again
+/// within a certain period of time.
+///
+/// Depending on the configured [content mode](ContentMode), it keeps
+/// either the message content as is or its salted hash.
+pub struct DuplicateMessageDetector {
+    mode: ContentMode,
+    salt: Vec<u8>,
+    threshold: u32,
+    window: TimeDelta,
+    recent: DashMap<Id<UserMarker>, Vec<RecentMessage>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StoredContent {
+    Plaintext(String),
+    Hashed(Vec<u8>),
+}
+
+#[derive(Debug)]
+struct RecentMessage {
+    channel_id: Id<ChannelMarker>,
+    content: StoredContent,
+    sent_at: DateTime<Utc>,
+}
+
+impl DuplicateMessageDetector {
+    #[must_use]
+    pub fn new(settings: &Moderation) -> Self {
+        let salt = settings
+            .content_salt
+            .as_ref()
+            .map(|v| v.expose().as_bytes().to_vec())
+            .unwrap_or_else(|| rand::random::<[u8; 32]>().to_vec());
+
+        Self {
+            mode: settings.content_mode,
+            salt,
+            threshold: settings.duplicate_threshold,
+            window: settings.duplicate_window,
+            recent: DashMap::new(),
+        }
+    }
+
+    /// Records a new message from the author and checks whether the
+    /// author has sent the same message enough times to reach the
+    /// configured threshold.
+    ///
+    /// Recent messages from the author will be forgotten once it
+    /// reaches the threshold so the author won't be warned repeatedly.
+    pub fn record(
+        &self,
+        author_id: Id<UserMarker>,
+        channel_id: Id<ChannelMarker>,
+        content: &str,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if self.threshold == 0 || content.trim().is_empty() {
+            return false;
+        }
+
+        self.clear_expired(now);
+
+        let content = self.store_content(content);
+        let mut messages = self.recent.entry(author_id).or_default();
+        messages.push(RecentMessage {
+            channel_id,
+            content: content.clone(),
+            sent_at: now,
+        });
+
+        let duplicates = messages.iter().filter(|v| v.content == content).count();
+        let threshold = usize::try_from(self.threshold).unwrap_or(usize::MAX);
+        if duplicates < threshold {
+            return false;
+        }
+
+        let channels = messages.iter().map(|v| v.channel_id).unique().count();
+        trace!("detected {duplicates} duplicated message(s) across {channels} channel(s)");
+        messages.clear();
+
+        true
+    }
+
+    /// Forgets all recent messages that are outside the configured window.
+    pub fn clear_expired(&self, now: DateTime<Utc>) {
+        self.recent.retain(|_, messages| {
+            messages.retain(|v| now - v.sent_at <= self.window);
+            !messages.is_empty()
+        });
+    }
+
+    fn store_content(&self, content: &str) -> StoredContent {
+        // we don't want to differentiate messages with different
+        // casing and spacing from each other
+        let normalized = content.split_whitespace().join(" ").to_lowercase();
+        match self.mode {
+            ContentMode::Plaintext => StoredContent::Plaintext(normalized),
+            ContentMode::Hashed => {
+                let mut data = self.salt.clone();
+                data.extend_from_slice(normalized.as_bytes());
+                StoredContent::Hashed(eden_utils::hash::bytes::sha256(data))
+            }
+        }
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn on_message_create(ctx: &EventContext, message: &Message) {
+    if !ctx.bot.settings.features.anti_spam {
+        return;
+    }
+
+    let Some(guild_id) = message.guild_id else {
+        return;
+    };
+
+    let is_spamming = ctx.bot.anti_spam.record(
+        message.author.id,
+        message.channel_id,
+        &message.content,
+        Utc::now(),
+    );
+
+    if !is_spamming {
+        return;
+    }
+
+    ctx.bot.events.publish(FeatureEvent::ModerationAction {
+        guild_id,
+        user_id: message.author.id,
+    });
+
+    let needed =
+        Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::READ_MESSAGE_HISTORY;
+
+    match ctx
+        .bot
+        .preflight_permissions(guild_id, message.channel_id, needed)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            trace!("bot is lacking permissions to warn the user not to spam");
+            return;
+        }
+        Err(error) => {
+            warn!(%error, "could not check bot permissions before warning the user not to spam");
+            return;
+        }
+    }
+
+    trace!("warning the user not to spam");
+
+    #[allow(clippy::unwrap_used)]
+    let request = ctx
+        .bot
+        .http
+        .create_message(message.channel_id)
+        .content("Please stop sending the same message over and over again!")
+        .unwrap()
+        .reply(message.id);
+
+    if let Err(error) = request_for_model(&ctx.bot, request).await {
+        let error = error.anonymize();
+        let has_missing_access = error
+            .discord_http_error_info()
+            .map(|v| v.has_missing_access())
+            .unwrap_or_default();
+
+        if !has_missing_access {
+            warn!(%error, "could not warn the user for sending duplicated messages");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector(mode: ContentMode) -> DuplicateMessageDetector {
+        let settings = Moderation::builder()
+            .content_mode(mode)
+            .content_salt("test salt")
+            .duplicate_threshold(3)
+            .duplicate_window(TimeDelta::seconds(30))
+            .build();
+
+        DuplicateMessageDetector::new(&settings)
+    }
+
+    fn should_detect_duplicates(mode: ContentMode) {
+        let detector = detector(mode);
+        let author = Id::new(1);
+        let channel = Id::new(2);
+        let now = Utc::now();
+
+        assert!(!detector.record(author, channel, "hello", now));
+        assert!(!detector.record(author, channel, "HELLO  ", now));
+        assert!(detector.record(author, channel, "hello", now));
+
+        // it should be forgotten after it is detected
+        assert!(!detector.record(author, channel, "hello", now));
+    }
+
+    #[test]
+    fn should_detect_duplicates_in_plaintext_mode() {
+        should_detect_duplicates(ContentMode::Plaintext);
+    }
+
+    #[test]
+    fn should_detect_duplicates_in_hashed_mode() {
+        should_detect_duplicates(ContentMode::Hashed);
+    }
+
+    #[test]
+    fn should_not_store_plaintext_in_hashed_mode() {
+        let detector = detector(ContentMode::Hashed);
+        let content = detector.store_content("secret message");
+        assert!(matches!(content, StoredContent::Hashed(..)));
+    }
+
+    #[test]
+    fn should_forget_messages_outside_window() {
+        let detector = detector(ContentMode::Plaintext);
+        let author = Id::new(1);
+        let channel = Id::new(2);
+        let now = Utc::now();
+
+        assert!(!detector.record(author, channel, "hello", now));
+        assert!(!detector.record(author, channel, "hello", now));
+
+        let later = now + TimeDelta::minutes(1);
+        assert!(!detector.record(author, channel, "hello", later));
+        assert!(detector.recent.get(&author).is_some_and(|v| v.len() == 1));
+    }
+}
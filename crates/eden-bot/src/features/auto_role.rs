@@ -0,0 +1,328 @@
+use eden_schema::types::GuildSettings;
+use eden_tasks::Scheduled;
+use eden_utils::error::exts::*;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::Result;
+use std::fmt::Display;
+use tracing::{debug, instrument, trace, warn};
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
+use twilight_model::guild::{Member, Role};
+use twilight_model::id::marker::{GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::events::EventContext;
+use crate::interactions::embeds;
+use crate::tasks;
+use crate::util::http::{request_for_empty, request_for_list, request_for_model};
+use crate::Bot;
+
+/// Maximum amount of roles that can be given to new members automatically.
+pub const MAX_ROLES: usize = 10;
+
+/// Reasons why the bot cannot give a specific role to members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnassignableRole {
+    /// The role does not exist anymore.
+    Unknown,
+    /// The role is `@everyone` which every member already has.
+    Everyone,
+    /// The role is managed by an integration (bot or server boosting).
+    Managed,
+    /// The role is positioned at or above the bot's highest role.
+    AboveBot,
+    /// The role is positioned at or above the invoker's highest role.
+    AboveInvoker,
+}
+
+impl Display for UnassignableRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => f.write_str("the role does not exist anymore"),
+            Self::Everyone => f.write_str("every member already has the @everyone role"),
+            Self::Managed => f.write_str("the role is managed by an integration"),
+            Self::AboveBot => f.write_str("the role is higher than or equal to my highest role"),
+            Self::AboveInvoker => {
+                f.write_str("the role is higher than or equal to your highest role")
+            }
+        }
+    }
+}
+
+/// Checks whether the bot can give a role to members based on
+/// the guild's role hierarchy.
+fn check_assignable(
+    guild_id: Id<GuildMarker>,
+    role_id: Id<RoleMarker>,
+    role: Option<&Role>,
+    bot_position: i64,
+) -> Result<(), UnassignableRole> {
+    // @everyone role's ID is the same as the guild's ID
+    if role_id == guild_id.cast() {
+        return Err(UnassignableRole::Everyone);
+    }
+
+    let Some(role) = role else {
+        return Err(UnassignableRole::Unknown);
+    };
+
+    if role.managed {
+        Err(UnassignableRole::Managed)
+    } else if role.position >= bot_position {
+        Err(UnassignableRole::AboveBot)
+    } else {
+        Ok(())
+    }
+}
+
+/// Gets the position of the highest role from the given member roles.
+///
+/// It returns `0` (the position of `@everyone` role) if the member
+/// has no roles at all.
+fn highest_role_position(member_roles: &[Id<RoleMarker>], guild_roles: &[Role]) -> i64 {
+    guild_roles
+        .iter()
+        .filter(|v| member_roles.contains(&v.id))
+        .map(|v| v.position)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Fetches all roles from the guild and the position of the bot's highest role.
+async fn fetch_hierarchy(bot: &Bot, guild_id: Id<GuildMarker>) -> Result<(Vec<Role>, i64)> {
+    let roles = request_for_list(bot, bot.http.roles(guild_id))
+        .await
+        .attach_printable("could not get guild roles")?;
+
+    let bot_id = bot.application_id().cast::<UserMarker>();
+    let member_roles = if let Some(member) = bot.cache.member(guild_id, bot_id) {
+        trace!("cache hit, got member info from cache");
+        member.roles().to_vec()
+    } else {
+        trace!("cache miss, getting member info from Discord API");
+        request_for_model(bot, bot.http.guild_member(guild_id, bot_id))
+            .await?
+            .roles
+    };
+
+    let position = highest_role_position(&member_roles, &roles);
+    Ok((roles, position))
+}
+
+/// Checks whether the bot can give the specified role to members
+/// based on the guild's role hierarchy.
+pub async fn find_unassignable(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    role_id: Id<RoleMarker>,
+) -> Result<Option<UnassignableRole>> {
+    let (roles, bot_position) = fetch_hierarchy(bot, guild_id).await?;
+    let role = roles.iter().find(|v| v.id == role_id);
+    Ok(check_assignable(guild_id, role_id, role, bot_position).err())
+}
+
+/// Checks whether both the bot and the invoker with `invoker_roles`
+/// can give the specified role to members based on the guild's
+/// role hierarchy.
+///
+/// The invoker's roles are not checked if `is_owner` is `true`
+/// since the guild owner can give any roles.
+pub async fn find_unassignable_by(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    role_id: Id<RoleMarker>,
+    invoker_roles: &[Id<RoleMarker>],
+    is_owner: bool,
+) -> Result<Option<UnassignableRole>> {
+    let (roles, bot_position) = fetch_hierarchy(bot, guild_id).await?;
+    let role = roles.iter().find(|v| v.id == role_id);
+    if let Err(reason) = check_assignable(guild_id, role_id, role, bot_position) {
+        return Ok(Some(reason));
+    }
+
+    let invoker_position = highest_role_position(invoker_roles, &roles);
+    let above_invoker = role.is_some_and(|v| v.position >= invoker_position);
+    Ok((above_invoker && !is_owner).then_some(UnassignableRole::AboveInvoker))
+}
+
+/// Gives the configured auto roles to a new member in the local guild.
+///
+/// If verification is enabled, auto roles will be given after
+/// the member has been verified instead.
+#[instrument(skip_all, fields(%guild_id, member.id = %member.user.id))]
+pub async fn on_member_add(ctx: &EventContext, guild_id: Id<GuildMarker>, member: &Member) {
+    if member.user.bot || !ctx.bot.is_local_guild(&guild_id) {
+        return;
+    }
+
+    let result = async {
+        let settings = ctx.bot.guild_settings(guild_id).await?;
+
+        if settings.verification.is_active() {
+            trace!("verification is enabled, giving auto roles after verification");
+            return Ok(());
+        }
+
+        schedule(&ctx.bot, guild_id, member.user.id, &settings).await
+    }
+    .await;
+
+    if let Err(error) = result {
+        warn!(%error, "could not give auto roles to the new member");
+    }
+}
+
+/// Gives the configured auto roles to a member either immediately
+/// or after the configured delay.
+pub async fn schedule(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+    settings: &GuildSettings,
+) -> Result<()> {
+    let auto_role = &settings.auto_role;
+    if auto_role.role_ids.is_empty() {
+        trace!("no auto roles configured, skipping");
+        return Ok(());
+    }
+
+    match auto_role.delay_seconds.filter(|v| *v > 0) {
+        Some(seconds) => {
+            trace!("giving auto roles to member {user_id} in {seconds} second(s)");
+            let task = tasks::AssignAutoRoles { guild_id, user_id };
+            bot.queue
+                .schedule(task, Scheduled::in_seconds(i64::from(seconds)))
+                .await?;
+
+            Ok(())
+        }
+        None => assign(bot, guild_id, user_id, settings).await,
+    }
+}
+
+/// Gives all of the configured auto roles to a member.
+///
+/// Roles that the bot cannot give because of the role hierarchy
+/// or Discord errors will be reported to the member log channel.
+#[instrument(skip(bot, settings))]
+pub async fn assign(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+    settings: &GuildSettings,
+) -> Result<()> {
+    let role_ids = &settings.auto_role.role_ids;
+    if role_ids.is_empty() {
+        return Ok(());
+    }
+
+    let (roles, bot_position) = fetch_hierarchy(bot, guild_id).await?;
+    let mut failures = Vec::new();
+
+    for role_id in role_ids {
+        let role = roles.iter().find(|v| v.id == *role_id);
+        if let Err(reason) = check_assignable(guild_id, *role_id, role, bot_position) {
+            trace!("cannot give role {role_id}: {reason}");
+            failures.push(format!("{}: {reason}", role_id.mention()));
+            continue;
+        }
+
+        debug!("giving auto role {role_id} to member {user_id}");
+
+        #[allow(clippy::unwrap_used)]
+        let request = bot
+            .http
+            .add_guild_member_role(guild_id, user_id, *role_id)
+            .reason("Auto role")
+            .unwrap();
+
+        let result = request_for_empty(bot, request).await;
+        let has_left = result
+            .discord_http_error_info()
+            .and_then(|v| v.json_code())
+            .is_some_and(|v| v.is_unknown_resource());
+
+        if has_left {
+            trace!("member {user_id} has already left the guild");
+            return Ok(());
+        }
+
+        if let Err(error) = result {
+            let error = error.anonymize();
+            warn!(%error, "could not give auto role {role_id} to member {user_id}");
+            failures.push(format!("{}: {error}", role_id.mention()));
+        }
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    let embed = embeds::builders::error("Could not give auto roles", Some(chrono::Utc::now()))
+        .description(format!(
+            "I could not give these roles to {}:\n{}",
+            user_id.mention(),
+            failures.join("\n")
+        ))
+        .build();
+
+    crate::local_guild::channel::send_member_log(bot, settings, embed).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twilight_model::guild::{Permissions, RoleFlags};
+
+    fn role(id: u64, position: i64, managed: bool) -> Role {
+        Role {
+            color: 0,
+            hoist: false,
+            icon: None,
+            id: Id::new(id),
+            managed,
+            mentionable: false,
+            name: format!("role {id}"),
+            permissions: Permissions::empty(),
+            position,
+            flags: RoleFlags::empty(),
+            tags: None,
+            unicode_emoji: None,
+        }
+    }
+
+    #[test]
+    fn should_check_role_hierarchy() {
+        let guild_id = Id::new(1);
+        let roles = [role(2, 1, false), role(3, 5, false), role(4, 2, true)];
+        let bot_position = highest_role_position(&[Id::new(3)], &roles);
+        assert_eq!(bot_position, 5);
+
+        assert_eq!(
+            check_assignable(guild_id, Id::new(2), Some(&roles[0]), bot_position),
+            Ok(())
+        );
+        assert_eq!(
+            check_assignable(guild_id, Id::new(3), Some(&roles[1]), bot_position),
+            Err(UnassignableRole::AboveBot)
+        );
+        assert_eq!(
+            check_assignable(guild_id, Id::new(4), Some(&roles[2]), bot_position),
+            Err(UnassignableRole::Managed)
+        );
+        assert_eq!(
+            check_assignable(guild_id, Id::new(1), None, bot_position),
+            Err(UnassignableRole::Everyone)
+        );
+        assert_eq!(
+            check_assignable(guild_id, Id::new(5), None, bot_position),
+            Err(UnassignableRole::Unknown)
+        );
+    }
+
+    #[test]
+    fn highest_role_position_without_roles() {
+        assert_eq!(highest_role_position(&[], &[role(2, 3, false)]), 0);
+    }
+}
@@ -0,0 +1,132 @@
+use dashmap::DashSet;
+use eden_schema::types::{AllowedGuild, BlacklistedUser};
+use eden_utils::Result;
+use tokio::sync::OnceCell;
+use tracing::{debug, instrument, trace, warn};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::events::EventContext;
+use crate::interactions::InteractionContext;
+use crate::util::http::{request_for_empty, request_for_model};
+use crate::Bot;
+
+const BLACKLISTED_MSG: &str = "**You're not allowed to use Eden.**";
+
+/// Keeps blacklisted users in memory so Eden won't query the
+/// database every time it receives an interaction.
+///
+/// Blacklisted users are loaded from the database lazily
+/// once Eden receives its first interaction.
+#[derive(Debug, Default)]
+pub struct Blacklist {
+    loaded: OnceCell<()>,
+    users: DashSet<Id<UserMarker>>,
+}
+
+impl Blacklist {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether the user is blacklisted.
+    pub async fn contains(&self, bot: &Bot, user_id: Id<UserMarker>) -> Result<bool> {
+        self.loaded
+            .get_or_try_init(|| async {
+                trace!("loading blacklisted users");
+
+                let mut conn = bot.db_read().await?;
+                let users = BlacklistedUser::list(&mut conn).await?;
+                for user in users {
+                    self.users.insert(user.user_id);
+                }
+                Ok::<_, eden_utils::Error>(())
+            })
+            .await?;
+
+        Ok(self.users.contains(&user_id))
+    }
+
+    /// Remembers that the user has been blacklisted.
+    pub fn insert(&self, user_id: Id<UserMarker>) {
+        self.users.insert(user_id);
+    }
+
+    /// Remembers that the user has been removed from the blacklist.
+    pub fn remove(&self, user_id: Id<UserMarker>) {
+        self.users.remove(&user_id);
+    }
+}
+
+/// Whether the user owns the local guild. Only the owner
+/// can manage the blacklist and allowed guilds.
+pub async fn is_owner(bot: &Bot, user_id: Id<UserMarker>) -> Result<bool> {
+    let guild_id = bot.settings.bot.local_guild.id;
+    let cached_owner_id = bot.cache.guild(guild_id).map(|v| v.owner_id());
+    let owner_id = match cached_owner_id {
+        Some(owner_id) => owner_id,
+        None => {
+            request_for_model(bot, bot.http.guild(guild_id))
+                .await?
+                .owner_id
+        }
+    };
+    Ok(owner_id == user_id)
+}
+
+/// Ignores the interaction if its invoker is blacklisted. It
+/// returns true if the interaction has been handled.
+#[instrument(skip_all)]
+pub async fn on_interaction(ctx: &EventContext, interaction: &Interaction) -> bool {
+    let Some(author_id) = interaction.author_id() else {
+        return false;
+    };
+
+    match ctx.bot.blacklist.contains(&ctx.bot, author_id).await {
+        Ok(true) => {}
+        Ok(false) => return false,
+        Err(error) => {
+            warn!(%error, "could not check if the user is blacklisted");
+            return false;
+        }
+    }
+
+    debug!("ignoring interaction from blacklisted user {author_id}");
+
+    let data = InteractionResponseDataBuilder::new()
+        .content(BLACKLISTED_MSG)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    let ctx = InteractionContext::new(ctx.bot.clone(), ctx, (), interaction);
+    if let Err(error) = ctx.respond(data).await {
+        warn!(%error, "could not notify blacklisted user");
+    }
+
+    true
+}
+
+/// Leaves the guild if it is not allowed to stay there and
+/// `bot.leave_unallowed_guilds` is enabled.
+#[instrument(skip(bot))]
+pub async fn on_guild_create(bot: &Bot, guild_id: Id<GuildMarker>) -> Result<()> {
+    if !bot.settings.bot.leave_unallowed_guilds || bot.is_local_guild(&guild_id) {
+        return Ok(());
+    }
+
+    let mut conn = bot.db_read().await?;
+    if AllowedGuild::from_id(&mut conn, guild_id).await?.is_some() {
+        trace!("guild is allowed, staying");
+        return Ok(());
+    }
+    drop(conn);
+
+    warn!("leaving guild {guild_id} since it is not allowed");
+    request_for_empty(bot, bot.http.leave_guild(guild_id)).await?;
+
+    Ok(())
+}
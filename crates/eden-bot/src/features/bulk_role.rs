@@ -0,0 +1,508 @@
+use chrono::{DateTime, Utc};
+use eden_discord_types::choices::BulkRoleActionOption;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::time::Duration;
+use tracing::{debug, instrument, trace, warn};
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
+use twilight_model::channel::message::AllowedMentions;
+use twilight_model::guild::Member;
+use twilight_model::http::attachment::Attachment;
+use twilight_model::id::marker::{
+    ChannelMarker, GuildMarker, MessageMarker, RoleMarker, UserMarker,
+};
+use twilight_model::id::Id;
+
+use crate::features::auto_role;
+use crate::features::undo::{self, UndoAction};
+use crate::util::http::{request_for_empty, request_for_list, request_for_model};
+use crate::Bot;
+
+/// How many members are processed in a single run of the bulk role
+/// operation task before it continues in another run.
+///
+/// Every run has to finish before the task times out, so it is kept
+/// low enough considering [`REQUEST_DELAY`] between role changes.
+const BATCH_SIZE: u16 = 500;
+
+/// How many members have to be processed before the
+/// progress message gets updated.
+const PROGRESS_INTERVAL: usize = 25;
+
+/// Delay between every role change so bulk operations don't use up
+/// the entire rate limit of the guild and starve other features.
+const REQUEST_DELAY: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkRoleAction {
+    Add,
+    Remove,
+}
+
+impl BulkRoleAction {
+    /// Gets the action that reverts this action.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Add => Self::Remove,
+            Self::Remove => Self::Add,
+        }
+    }
+
+    /// Whether the member will be affected by this action.
+    #[must_use]
+    pub fn needs_change(self, role_id: Id<RoleMarker>, member_roles: &[Id<RoleMarker>]) -> bool {
+        let has_role = member_roles.contains(&role_id);
+        match self {
+            Self::Add => !has_role,
+            Self::Remove => has_role,
+        }
+    }
+
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Add => "Adding",
+            Self::Remove => "Removing",
+        }
+    }
+}
+
+impl From<BulkRoleActionOption> for BulkRoleAction {
+    fn from(value: BulkRoleActionOption) -> Self {
+        match value {
+            BulkRoleActionOption::Add => Self::Add,
+            BulkRoleActionOption::Remove => Self::Remove,
+        }
+    }
+}
+
+/// Filters which members should be affected by a bulk role operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BulkRoleFilter {
+    pub with_role: Option<Id<RoleMarker>>,
+    pub joined_after: Option<DateTime<Utc>>,
+    pub joined_before: Option<DateTime<Utc>>,
+}
+
+impl BulkRoleFilter {
+    #[must_use]
+    pub fn matches(&self, member_roles: &[Id<RoleMarker>], joined_at: DateTime<Utc>) -> bool {
+        let has_role = self.with_role.map_or(true, |v| member_roles.contains(&v));
+        let after = self.joined_after.map_or(true, |v| joined_at >= v);
+        let before = self.joined_before.map_or(true, |v| joined_at <= v);
+        has_role && after && before
+    }
+
+    fn matches_member(&self, member: &Member) -> bool {
+        let joined_at = DateTime::from_timestamp(member.joined_at.as_secs(), 0).unwrap_or_default();
+        self.matches(&member.roles, joined_at)
+    }
+}
+
+/// Role to be added to or removed from many members at once.
+///
+/// If `members` is set, only these members will be affected instead
+/// of fetching every member that matches the filter. This is used to
+/// roll back a previous bulk role operation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BulkRoleOperation {
+    pub guild_id: Id<GuildMarker>,
+    pub channel_id: Id<ChannelMarker>,
+    pub requested_by: Id<UserMarker>,
+    pub role_id: Id<RoleMarker>,
+    pub action: BulkRoleAction,
+    pub filter: BulkRoleFilter,
+    pub members: Option<Vec<Id<UserMarker>>>,
+    /// Progress of the operation from its previous runs.
+    #[serde(default)]
+    pub progress: Option<BulkRoleProgress>,
+}
+
+/// Progress of a bulk role operation carried over to its next run.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BulkRoleProgress {
+    pub message_id: Id<MessageMarker>,
+    /// Last member fetched from Discord if the targets are
+    /// found with the filter.
+    pub after: Option<Id<UserMarker>>,
+    pub processed: usize,
+    pub affected: Vec<Id<UserMarker>>,
+    pub failed: usize,
+}
+
+/// Performs a batch of a bulk role operation while reporting its
+/// progress to the channel where it was requested from.
+///
+/// It returns the operation to be performed in the next run if there
+/// are more members left to process, so large guilds don't make the
+/// task run longer than its timeout.
+///
+/// Once finished, members that are affected will be sent as a file
+/// so it can be rolled back manually and with `/admin undo`.
+#[instrument(skip_all, fields(
+    guild.id = %op.guild_id,
+    role.id = %op.role_id,
+    action = ?op.action,
+))]
+pub async fn perform(bot: &Bot, op: &BulkRoleOperation) -> Result<Option<BulkRoleOperation>> {
+    let mut op = op.clone();
+    let mut progress = match op.progress.take() {
+        Some(progress) => progress,
+        None => match start(bot, &op).await? {
+            Some(progress) => progress,
+            None => return Ok(None),
+        },
+    };
+
+    let (targets, done) = match op.members.as_mut() {
+        Some(members) => {
+            let rest = members.split_off(members.len().min(usize::from(BATCH_SIZE)));
+            let batch = std::mem::replace(members, rest);
+            let done = members.is_empty();
+            (batch, done)
+        }
+        None => {
+            let (targets, after) = find_targets(bot, &op, progress.after).await?;
+            let done = after.is_none();
+            progress.after = after;
+            (targets, done)
+        }
+    };
+    let total = op
+        .members
+        .as_ref()
+        .map(|v| progress.processed + targets.len() + v.len());
+
+    debug!(
+        "performing bulk role operation to {} member(s)",
+        targets.len()
+    );
+
+    let reason = format!("Bulk role operation requested by {}", op.requested_by);
+    for user_id in targets {
+        match change_role(bot, &op, user_id, &reason).await {
+            Ok(()) => progress.affected.push(user_id),
+            Err(error) => {
+                warn!(error = %error.anonymize(), "could not change role of member {user_id}");
+                progress.failed += 1;
+            }
+        }
+
+        progress.processed += 1;
+        if progress.processed % PROGRESS_INTERVAL == 0 {
+            update_progress(bot, &op, progress.message_id, progress.processed, total).await;
+        }
+        tokio::time::sleep(REQUEST_DELAY).await;
+    }
+
+    if done {
+        finish(bot, &op, progress).await?;
+        Ok(None)
+    } else {
+        debug!("continuing bulk role operation in the next run");
+        op.progress = Some(progress);
+        Ok(Some(op))
+    }
+}
+
+/// Checks if the role can still be changed and sends the progress
+/// message of the operation in its first run.
+///
+/// It returns `None` if the operation cannot be performed.
+async fn start(bot: &Bot, op: &BulkRoleOperation) -> Result<Option<BulkRoleProgress>> {
+    // roles may have been moved after the operation was requested
+    let unassignable = auto_role::find_unassignable(bot, op.guild_id, op.role_id).await?;
+    if let Some(reason) = unassignable {
+        warn!(%reason, "cannot perform bulk role operation");
+        send_unassignable(bot, op, &reason.to_string()).await?;
+        return Ok(None);
+    }
+
+    let total = op.members.as_ref().map(Vec::len);
+    Ok(Some(BulkRoleProgress {
+        message_id: send_progress(bot, op, 0, total).await?,
+        after: None,
+        processed: 0,
+        affected: Vec::new(),
+        failed: 0,
+    }))
+}
+
+async fn change_role(
+    bot: &Bot,
+    op: &BulkRoleOperation,
+    user_id: Id<UserMarker>,
+    reason: &str,
+) -> Result<()> {
+    if op.action == BulkRoleAction::Add {
+        let request = bot
+            .http
+            .add_guild_member_role(op.guild_id, user_id, op.role_id)
+            .reason(reason)
+            .into_typed_error()?;
+
+        request_for_empty(bot, request).await
+    } else {
+        let request = bot
+            .http
+            .remove_guild_member_role(op.guild_id, user_id, op.role_id)
+            .reason(reason)
+            .into_typed_error()?;
+
+        request_for_empty(bot, request).await
+    }
+}
+
+/// Reports the result of the operation after its last run and
+/// records it so it can be rolled back with `/admin undo`.
+async fn finish(bot: &Bot, op: &BulkRoleOperation, progress: BulkRoleProgress) -> Result<()> {
+    let processed = progress.processed;
+    update_progress(bot, op, progress.message_id, processed, Some(processed)).await;
+
+    let affected = progress.affected;
+    send_report(bot, op, &affected, progress.failed).await?;
+    if affected.is_empty() {
+        return Ok(());
+    }
+
+    let description = format!(
+        "{} {} for {} member(s)",
+        op.action.verb(),
+        op.role_id.mention(),
+        affected.len()
+    );
+    let rollback = BulkRoleOperation {
+        action: op.action.inverse(),
+        filter: BulkRoleFilter::default(),
+        members: Some(affected),
+        progress: None,
+        ..op.clone()
+    };
+    let action = UndoAction::BulkRole(Box::new(rollback));
+    let mut conn = bot.db_write().await?;
+    undo::record(
+        &mut conn,
+        op.guild_id,
+        op.requested_by,
+        &description,
+        &action,
+    )
+    .await?;
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    Ok(())
+}
+
+/// Fetches a page of members after `after` and finds members that
+/// match the filter and are going to be affected by the operation.
+///
+/// It also returns the last member of the page if there are more
+/// members to be fetched after it.
+async fn find_targets(
+    bot: &Bot,
+    op: &BulkRoleOperation,
+    after: Option<Id<UserMarker>>,
+) -> Result<(Vec<Id<UserMarker>>, Option<Id<UserMarker>>)> {
+    let mut request = bot
+        .http
+        .guild_members(op.guild_id)
+        .limit(BATCH_SIZE)
+        .into_typed_error()?;
+
+    if let Some(after) = after {
+        request = request.after(after);
+    }
+
+    trace!(?after, "fetching batch of guild members");
+    let members = request_for_list(bot, request)
+        .await
+        .attach_printable("could not fetch guild members")?;
+
+    let targets = members
+        .iter()
+        .filter(|v| op.action.needs_change(op.role_id, &v.roles))
+        .filter(|v| op.filter.matches_member(v))
+        .map(|v| v.user.id)
+        .collect();
+
+    let has_more = members.len() >= usize::from(BATCH_SIZE);
+    let after = members.last().map(|v| v.user.id).filter(|_| has_more);
+    Ok((targets, after))
+}
+
+fn progress_content(op: &BulkRoleOperation, processed: usize, total: Option<usize>) -> String {
+    let verb = op.action.verb();
+    let role = op.role_id.mention();
+    match total {
+        Some(total) => {
+            format!("**{verb} {role} for {total} member(s)**: {processed}/{total} processed")
+        }
+        None => format!("**{verb} {role}**: {processed} member(s) processed"),
+    }
+}
+
+async fn send_unassignable(bot: &Bot, op: &BulkRoleOperation, reason: &str) -> Result<()> {
+    let allowed_mentions = AllowedMentions::default();
+    let content = format!(
+        "**I cannot change {} of members** because {reason}.",
+        op.role_id.mention()
+    );
+    let request = bot
+        .http
+        .create_message(op.channel_id)
+        .allowed_mentions(Some(&allowed_mentions))
+        .content(&content)
+        .into_typed_error()?;
+
+    request_for_model(bot, request)
+        .await
+        .attach_printable("could not send bulk role operation error")?;
+
+    Ok(())
+}
+
+async fn send_progress(
+    bot: &Bot,
+    op: &BulkRoleOperation,
+    processed: usize,
+    total: Option<usize>,
+) -> Result<Id<MessageMarker>> {
+    let allowed_mentions = AllowedMentions::default();
+    let content = progress_content(op, processed, total);
+    let request = bot
+        .http
+        .create_message(op.channel_id)
+        .allowed_mentions(Some(&allowed_mentions))
+        .content(&content)
+        .into_typed_error()?;
+
+    let message = request_for_model(bot, request)
+        .await
+        .attach_printable("could not send bulk role operation progress")?;
+
+    Ok(message.id)
+}
+
+async fn update_progress(
+    bot: &Bot,
+    op: &BulkRoleOperation,
+    message_id: Id<MessageMarker>,
+    processed: usize,
+    total: Option<usize>,
+) {
+    let content = progress_content(op, processed, total);
+    let request = match bot
+        .http
+        .update_message(op.channel_id, message_id)
+        .content(Some(content.as_str()))
+    {
+        Ok(request) => request,
+        Err(error) => {
+            warn!(%error, "could not build bulk role operation progress update");
+            return;
+        }
+    };
+
+    // progress updates are not important enough to stop the operation
+    if let Err(error) = request_for_model(bot, request).await {
+        warn!(error = %error.anonymize(), "could not update bulk role operation progress");
+    }
+}
+
+/// Sends the list of affected members as a file that can be
+/// used to roll back the operation.
+async fn send_report(
+    bot: &Bot,
+    op: &BulkRoleOperation,
+    affected: &[Id<UserMarker>],
+    failed: usize,
+) -> Result<()> {
+    let mut artifact = String::new();
+    for user_id in affected {
+        let _ = writeln!(artifact, "{user_id}");
+    }
+
+    let filename = format!("bulk-role-{}-{}.txt", op.role_id, Utc::now().timestamp());
+    let attachments = vec![Attachment::from_bytes(filename, artifact.into_bytes(), 1)];
+
+    let mut content = format!(
+        "**Finished {} {}** for {} member(s) requested by {}.",
+        op.action.verb().to_lowercase(),
+        op.role_id.mention(),
+        affected.len(),
+        op.requested_by.mention()
+    );
+    if failed > 0 {
+        let _ = write!(
+            content,
+            "\nFailed to change the role of {failed} member(s)."
+        );
+    }
+    if !affected.is_empty() {
+        content.push_str("\nAffected members are attached. Use `/admin undo` to roll it back.");
+    }
+
+    let allowed_mentions = AllowedMentions::default();
+    let request = bot
+        .http
+        .create_message(op.channel_id)
+        .allowed_mentions(Some(&allowed_mentions))
+        .attachments(&attachments)
+        .into_typed_error()?
+        .content(&content)
+        .into_typed_error()?;
+
+    request_for_model(bot, request)
+        .await
+        .attach_printable("could not send bulk role operation report")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn should_only_change_members_that_need_it() {
+        let role_id = Id::new(1);
+        assert!(BulkRoleAction::Add.needs_change(role_id, &[]));
+        assert!(!BulkRoleAction::Add.needs_change(role_id, &[role_id]));
+        assert!(BulkRoleAction::Remove.needs_change(role_id, &[role_id]));
+        assert!(!BulkRoleAction::Remove.needs_change(role_id, &[]));
+        assert_eq!(BulkRoleAction::Add.inverse(), BulkRoleAction::Remove);
+    }
+
+    #[test]
+    fn should_match_filters() {
+        let now = Utc::now();
+        let with_role = Id::new(2);
+
+        let filter = BulkRoleFilter::default();
+        assert!(filter.matches(&[], now));
+
+        let filter = BulkRoleFilter {
+            with_role: Some(with_role),
+            joined_after: Some(now - TimeDelta::days(7)),
+            joined_before: None,
+        };
+        assert!(filter.matches(&[with_role], now - TimeDelta::days(1)));
+        assert!(!filter.matches(&[], now - TimeDelta::days(1)));
+        assert!(!filter.matches(&[with_role], now - TimeDelta::days(8)));
+
+        let filter = BulkRoleFilter {
+            joined_before: Some(now - TimeDelta::days(30)),
+            ..Default::default()
+        };
+        assert!(filter.matches(&[], now - TimeDelta::days(31)));
+        assert!(!filter.matches(&[], now));
+    }
+}
@@ -0,0 +1,621 @@
+use dashmap::DashMap;
+use eden_schema::types::CommandAlias;
+use eden_utils::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::result::Result as StdResult;
+use thiserror::Error;
+use tokio::sync::{Notify, OnceCell};
+use tracing::trace;
+use twilight_model::application::command::{
+    Command, CommandOption, CommandOptionChoiceValue, CommandOptionType, CommandType,
+};
+use twilight_model::application::interaction::application_command::{
+    CommandData, CommandDataOption, CommandOptionValue,
+};
+use twilight_model::id::Id;
+
+use crate::Bot;
+
+/// Maximum length of the name of a command.
+const MAX_NAME_LENGTH: usize = 32;
+
+/// Maximum length of the description of a command.
+const MAX_DESCRIPTION_LENGTH: usize = 100;
+
+/// Discord does not allow guilds to have more chat input commands
+/// than this, including the command aliases.
+const MAX_GUILD_COMMANDS: usize = 100;
+
+/// Problem with a command alias that prevents it from being added.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvalidAlias {
+    #[error("`{0}` is not a valid name. Use lowercase letters, numbers, `-` and `_` only")]
+    InvalidName(String),
+    #[error("`/{0}` is already a command")]
+    NameTaken(String),
+    #[error("there can only be up to {0} command aliases")]
+    TooManyAliases(usize),
+    #[error("`/{0}` does not exist or it has subcommands")]
+    UnknownCommand(String),
+    #[error("`{0}` is not an argument like `name:value`")]
+    MalformedArgument(String),
+    #[error("`/{command}` has no `{name}` option")]
+    UnknownOption { command: String, name: String },
+    #[error("`{value}` is not a valid value of `{name}`")]
+    InvalidValue { name: String, value: String },
+}
+
+/// Keeps the command aliases of the local guild in memory so Eden
+/// won't query the database every time it receives a command.
+///
+/// Command aliases are loaded from the database once the commands
+/// are registered to Discord.
+#[derive(Debug, Default)]
+pub struct CommandAliases {
+    aliases: DashMap<String, CommandAlias>,
+    changed: Notify,
+    loaded: OnceCell<()>,
+}
+
+impl CommandAliases {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the command aliases of the local guild if they're
+    /// not loaded yet.
+    pub async fn load(&self, bot: &Bot) -> Result<()> {
+        self.loaded
+            .get_or_try_init(|| async {
+                trace!("loading command aliases");
+
+                let mut conn = bot.db_read().await?;
+                let guild_id = bot.settings.bot.local_guild.id;
+                for alias in CommandAlias::list(&mut conn, guild_id).await? {
+                    self.aliases.insert(alias.name.clone(), alias);
+                }
+                Ok::<_, eden_utils::Error>(())
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<CommandAlias> {
+        self.aliases.get(name).map(|v| v.clone())
+    }
+
+    /// Gets every command alias sorted by name.
+    #[must_use]
+    pub fn list(&self) -> Vec<CommandAlias> {
+        let mut aliases = self.aliases.iter().map(|v| v.clone()).collect::<Vec<_>>();
+        aliases.sort_by(|a, b| a.name.cmp(&b.name));
+        aliases
+    }
+
+    pub fn insert(&self, alias: CommandAlias) {
+        self.aliases.insert(alias.name.clone(), alias);
+        self.changed.notify_one();
+    }
+
+    /// Replaces every command alias with the given aliases.
+    pub fn replace_all(&self, aliases: Vec<CommandAlias>) {
+        self.aliases.clear();
+        for alias in aliases {
+            self.aliases.insert(alias.name.clone(), alias);
+        }
+        self.changed.notify_one();
+    }
+
+    /// Forgets the command alias. It returns the removed alias if it exists.
+    pub fn remove(&self, name: &str) -> Option<CommandAlias> {
+        let removed = self.aliases.remove(name).map(|(_, v)| v);
+        if removed.is_some() {
+            self.changed.notify_one();
+        }
+        removed
+    }
+
+    /// Waits until any command alias is added or removed. Changes made
+    /// while nobody is waiting are still received by the next call.
+    pub async fn changed(&self) {
+        self.changed.notified().await;
+    }
+}
+
+/// Command or subcommand run by an alias.
+struct Target<'a> {
+    command: &'a Command,
+    /// Names of the subcommand group and subcommand after the
+    /// command's name, if there's any.
+    path: Vec<&'a str>,
+    options: &'a [CommandOption],
+}
+
+fn is_subcommand(option: &CommandOption) -> bool {
+    matches!(
+        option.kind,
+        CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+    )
+}
+
+/// Commands with subcommands cannot be run by themselves,
+/// so they cannot be aliased either.
+fn find_target<'a>(commands: &'a [Command], path: &str) -> Option<Target<'a>> {
+    let mut segments = path.split_whitespace();
+    let name = segments.next()?;
+    let command = commands
+        .iter()
+        .find(|v| v.kind == CommandType::ChatInput && v.name == name)?;
+
+    let mut target = Target {
+        command,
+        path: Vec::new(),
+        options: &command.options,
+    };
+
+    for segment in segments {
+        let option = target
+            .options
+            .iter()
+            .find(|v| is_subcommand(v) && v.name == segment)?;
+
+        target.path.push(&option.name);
+        target.options = option.options.as_deref().unwrap_or_default();
+    }
+
+    if target.options.iter().any(is_subcommand) {
+        return None;
+    }
+    Some(target)
+}
+
+/// Users, channels and roles can be typed as mentions or IDs.
+fn parse_id<T>(value: &str) -> Option<Id<T>> {
+    value
+        .trim_start_matches(['<', '@', '!', '&', '#'])
+        .trim_end_matches('>')
+        .parse()
+        .ok()
+}
+
+/// Parses a preset argument with the type of the option. Attachments
+/// cannot be preset since they have to be uploaded every time.
+fn parse_value(option: &CommandOption, value: &str) -> Option<CommandOptionValue> {
+    let parsed = match option.kind {
+        CommandOptionType::Boolean => CommandOptionValue::Boolean(value.parse().ok()?),
+        CommandOptionType::Channel => CommandOptionValue::Channel(parse_id(value)?),
+        CommandOptionType::Integer => CommandOptionValue::Integer(value.parse().ok()?),
+        CommandOptionType::Mentionable => CommandOptionValue::Mentionable(parse_id(value)?),
+        CommandOptionType::Number => {
+            let number = value.parse::<f64>().ok().filter(|v| v.is_finite())?;
+            CommandOptionValue::Number(number)
+        }
+        CommandOptionType::Role => CommandOptionValue::Role(parse_id(value)?),
+        CommandOptionType::String => CommandOptionValue::String(value.into()),
+        CommandOptionType::User => CommandOptionValue::User(parse_id(value)?),
+        _ => return None,
+    };
+
+    let choices = option.choices.as_deref().unwrap_or_default();
+    let is_choice = |choice: &CommandOptionChoiceValue| match (choice, &parsed) {
+        (CommandOptionChoiceValue::Integer(a), CommandOptionValue::Integer(b)) => a == b,
+        #[allow(clippy::float_cmp)]
+        (CommandOptionChoiceValue::Number(a), CommandOptionValue::Number(b)) => a == b,
+        (CommandOptionChoiceValue::String(a), CommandOptionValue::String(b)) => a == b,
+        _ => false,
+    };
+
+    if !choices.is_empty() && !choices.iter().any(|v| is_choice(&v.value)) {
+        return None;
+    }
+    Some(parsed)
+}
+
+/// Parses preset arguments typed like `amount:500 note:"monthly rent"`.
+pub fn parse_arguments(input: &str) -> StdResult<BTreeMap<String, String>, InvalidAlias> {
+    let mut arguments = BTreeMap::new();
+    let mut rest = input.trim_start();
+    while !rest.is_empty() {
+        let malformed = || {
+            let argument = rest.split_whitespace().next().unwrap_or_default();
+            InvalidAlias::MalformedArgument(argument.into())
+        };
+
+        let (name, value) = rest.split_once(':').ok_or_else(malformed)?;
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(malformed());
+        }
+
+        let (value, remaining) = if let Some(quoted) = value.strip_prefix('"') {
+            let end = quoted.find('"').ok_or_else(malformed)?;
+            (&quoted[..end], &quoted[end + 1..])
+        } else {
+            let end = value.find(char::is_whitespace).unwrap_or(value.len());
+            value.split_at(end)
+        };
+
+        arguments.insert(name.to_lowercase(), value.to_string());
+        rest = remaining.trim_start();
+    }
+    Ok(arguments)
+}
+
+/// How many command aliases can be registered in the local guild
+/// along with Eden's local guild commands (without any aliases).
+#[must_use]
+pub fn max_aliases(commands: &[Command]) -> usize {
+    let used = commands
+        .iter()
+        .filter(|v| v.kind == CommandType::ChatInput)
+        .count();
+
+    MAX_GUILD_COMMANDS.saturating_sub(used)
+}
+
+/// Checks whether the alias can be added given Eden's local guild
+/// commands (without any aliases), global commands and the existing
+/// command aliases.
+pub fn check(
+    name: &str,
+    command: &str,
+    arguments: &BTreeMap<String, String>,
+    commands: &[Command],
+    global_commands: &[Command],
+    aliases: &[CommandAlias],
+) -> StdResult<(), InvalidAlias> {
+    let is_valid_name = (1..=MAX_NAME_LENGTH).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+
+    if !is_valid_name {
+        return Err(InvalidAlias::InvalidName(name.into()));
+    }
+
+    let is_taken = commands
+        .iter()
+        .chain(global_commands)
+        .any(|v| v.kind == CommandType::ChatInput && v.name == name);
+
+    if is_taken {
+        return Err(InvalidAlias::NameTaken(name.into()));
+    }
+
+    // replacing an existing alias does not take another slot
+    let max = max_aliases(commands);
+    let others = aliases.iter().filter(|v| v.name != name).count();
+    if others >= max {
+        return Err(InvalidAlias::TooManyAliases(max));
+    }
+
+    let target = find_target(commands, command)
+        .ok_or_else(|| InvalidAlias::UnknownCommand(command.into()))?;
+
+    for (name, value) in arguments {
+        let option = target
+            .options
+            .iter()
+            .find(|v| v.name == *name)
+            .ok_or_else(|| InvalidAlias::UnknownOption {
+                command: command.into(),
+                name: name.clone(),
+            })?;
+
+        if parse_value(option, value).is_none() {
+            return Err(InvalidAlias::InvalidValue {
+                name: name.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Shows what the alias runs (like `/payer pay_bill method:paypal`).
+#[must_use]
+pub fn describe(alias: &CommandAlias) -> String {
+    let mut output = format!("/{}", alias.command);
+    for (name, value) in &alias.arguments {
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            let _ = write!(output, " {name}:\"{value}\"");
+        } else {
+            let _ = write!(output, " {name}:{value}");
+        }
+    }
+    output
+}
+
+/// Creates the command of the alias to be registered to Discord.
+/// Options that are not preset can still be filled by the invoker.
+///
+/// It returns `None` if the aliased command does not exist anymore.
+#[must_use]
+pub fn create_command(alias: &CommandAlias, commands: &[Command]) -> Option<Command> {
+    let target = find_target(commands, &alias.command)?;
+    let options = target
+        .options
+        .iter()
+        .filter(|v| !alias.arguments.contains_key(&v.name))
+        .cloned()
+        .collect();
+
+    let mut description = format!("Runs {}", describe(alias));
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        let (index, _) = description
+            .char_indices()
+            .nth(MAX_DESCRIPTION_LENGTH - 1)
+            .unwrap_or_default();
+
+        description.truncate(index);
+        description.push('…');
+    }
+
+    Some(Command {
+        application_id: None,
+        default_member_permissions: target.command.default_member_permissions,
+        dm_permission: Some(false),
+        description,
+        description_localizations: None,
+        guild_id: None,
+        id: None,
+        kind: CommandType::ChatInput,
+        name: alias.name.clone(),
+        name_localizations: None,
+        nsfw: target.command.nsfw,
+        options,
+        version: Id::new(1),
+    })
+}
+
+/// Turns the data of an invoked alias into the data of the aliased
+/// command as if the invoker used the aliased command with the
+/// preset arguments.
+///
+/// It returns `None` if the aliased command does not exist anymore
+/// or any of the preset arguments is no longer valid.
+#[must_use]
+pub fn resolve(
+    alias: &CommandAlias,
+    commands: &[Command],
+    data: &CommandData,
+) -> Option<CommandData> {
+    let target = find_target(commands, &alias.command)?;
+
+    let mut options = Vec::new();
+    for option in target.options {
+        if let Some(value) = alias.arguments.get(&option.name) {
+            options.push(CommandDataOption {
+                name: option.name.clone(),
+                value: parse_value(option, value)?,
+            });
+        } else if let Some(given) = data.options.iter().find(|v| v.name == option.name) {
+            options.push(given.clone());
+        }
+    }
+
+    // wrap the options from the innermost subcommand
+    let subcommand = target.path.len().saturating_sub(1);
+    for (index, name) in target.path.iter().enumerate().rev() {
+        let value = if index == subcommand {
+            CommandOptionValue::SubCommand(options)
+        } else {
+            CommandOptionValue::SubCommandGroup(options)
+        };
+        options = vec![CommandDataOption {
+            name: (*name).to_string(),
+            value,
+        }];
+    }
+
+    Some(CommandData {
+        name: target.command.name.clone(),
+        options,
+        ..data.clone()
+    })
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use eden_discord_types::commands::local_guild::{AdminCommand, PayerCommand};
+    use eden_discord_types::commands::Ping;
+    use twilight_interactions::command::CreateCommand;
+
+    fn commands() -> Vec<Command> {
+        vec![
+            AdminCommand::create_command().into(),
+            PayerCommand::create_command().into(),
+        ]
+    }
+
+    fn alias(command: &str, arguments: &str) -> CommandAlias {
+        named_alias("rent", command, arguments)
+    }
+
+    fn named_alias(name: &str, command: &str, arguments: &str) -> CommandAlias {
+        CommandAlias {
+            guild_id: Id::new(1),
+            name: name.into(),
+            created_at: Utc::now(),
+            created_by: Id::new(2),
+            command: command.into(),
+            arguments: parse_arguments(arguments).unwrap(),
+        }
+    }
+
+    fn data(name: &str, options: Vec<CommandDataOption>) -> CommandData {
+        CommandData {
+            guild_id: Some(Id::new(1)),
+            id: Id::new(3),
+            name: name.into(),
+            kind: CommandType::ChatInput,
+            options,
+            resolved: None,
+            target_id: None,
+        }
+    }
+
+    #[test]
+    fn should_parse_arguments() {
+        let arguments = parse_arguments(r#" method:paypal  Note:"monthly rent" empty:"""#).unwrap();
+        assert_eq!(arguments.len(), 3);
+        assert_eq!(arguments["method"], "paypal");
+        assert_eq!(arguments["note"], "monthly rent");
+        assert_eq!(arguments["empty"], "");
+
+        assert_eq!(
+            parse_arguments("method"),
+            Err(InvalidAlias::MalformedArgument("method".into()))
+        );
+        assert!(parse_arguments(r#"note:"unclosed"#).is_err());
+        assert!(parse_arguments("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_check_aliases() {
+        let commands = commands();
+        let global_commands = vec![Ping::create_command().into()];
+        let check_alias = |name: &str, command: &str, arguments: &str| {
+            check(
+                name,
+                command,
+                &parse_arguments(arguments).unwrap(),
+                &commands,
+                &global_commands,
+                &[],
+            )
+        };
+
+        assert_eq!(
+            check_alias("rent", "payer pay_bill", "method:paypal"),
+            Ok(())
+        );
+        assert_eq!(
+            check_alias("feature", "admin feature set", "percentage:50"),
+            Ok(())
+        );
+        assert!(matches!(
+            check_alias("Rent", "payer pay_bill", ""),
+            Err(InvalidAlias::InvalidName(_))
+        ));
+        assert!(matches!(
+            check_alias("payer", "payer pay_bill", ""),
+            Err(InvalidAlias::NameTaken(_))
+        ));
+        assert!(matches!(
+            check_alias("ping", "payer pay_bill", ""),
+            Err(InvalidAlias::NameTaken(_))
+        ));
+        assert!(matches!(
+            check_alias("rent", "payer", ""),
+            Err(InvalidAlias::UnknownCommand(_))
+        ));
+        assert!(matches!(
+            check_alias("rent", "payer pay", ""),
+            Err(InvalidAlias::UnknownCommand(_))
+        ));
+        assert!(matches!(
+            check_alias("rent", "payer pay_bill", "amount:500"),
+            Err(InvalidAlias::UnknownOption { .. })
+        ));
+        assert!(matches!(
+            check_alias("rent", "payer pay_bill", "method:cash"),
+            Err(InvalidAlias::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            check_alias("feature", "admin feature set", "percentage:half"),
+            Err(InvalidAlias::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn should_limit_aliases() {
+        let commands = commands();
+        let max = max_aliases(&commands);
+        assert_eq!(max, MAX_GUILD_COMMANDS - 2);
+
+        let aliases = (0..max)
+            .map(|i| named_alias(&format!("rent{i}"), "payer pay_bill", ""))
+            .collect::<Vec<_>>();
+
+        let arguments = BTreeMap::new();
+        assert_eq!(
+            check(
+                "rent",
+                "payer pay_bill",
+                &arguments,
+                &commands,
+                &[],
+                &aliases
+            ),
+            Err(InvalidAlias::TooManyAliases(max))
+        );
+        assert_eq!(
+            check(
+                "rent0",
+                "payer pay_bill",
+                &arguments,
+                &commands,
+                &[],
+                &aliases
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn should_create_alias_commands() {
+        let commands = commands();
+        let command = create_command(&alias("payer pay_bill", "method:paypal"), &commands).unwrap();
+        assert_eq!(command.name, "rent");
+        assert_eq!(command.description, "Runs /payer pay_bill method:paypal");
+        assert!(command.options.is_empty());
+
+        let command =
+            create_command(&alias("admin feature set", "percentage:50"), &commands).unwrap();
+        let options = command
+            .options
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(options, vec!["name", "role"]);
+
+        assert!(create_command(&alias("payer test", ""), &[]).is_none());
+    }
+
+    #[test]
+    fn should_resolve_aliases() {
+        let commands = commands();
+        let alias = alias("admin feature set", "percentage:50");
+        let given = CommandDataOption {
+            name: "name".into(),
+            value: CommandOptionValue::String("payer stats".into()),
+        };
+
+        let resolved = resolve(&alias, &commands, &data("rent", vec![given.clone()])).unwrap();
+        assert_eq!(resolved.name, "admin");
+        assert_eq!(resolved.id, Id::new(3));
+
+        let expected = vec![CommandDataOption {
+            name: "feature".into(),
+            value: CommandOptionValue::SubCommandGroup(vec![CommandDataOption {
+                name: "set".into(),
+                value: CommandOptionValue::SubCommand(vec![
+                    given,
+                    CommandDataOption {
+                        name: "percentage".into(),
+                        value: CommandOptionValue::Integer(50),
+                    },
+                ]),
+            }]),
+        }];
+        assert_eq!(resolved.options, expected);
+    }
+}
@@ -0,0 +1,326 @@
+use base64::Engine as _;
+use eden_schema::types::EmojiUpload;
+use eden_settings::{AlertClass, AlertSeverity};
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use std::fmt::Display;
+use tracing::{debug, instrument, warn};
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::channel::message::{AllowedMentions, MessageFlags};
+use twilight_model::channel::Attachment;
+use twilight_model::guild::{Emoji, Permissions};
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::InteractionResponseDataBuilder;
+use uuid::Uuid;
+
+use crate::interactions::InteractionContext;
+use crate::util::alerts::Alert;
+use crate::util::http::request_for_model;
+use crate::Bot;
+
+/// Prefix of all custom IDs of the emoji approval buttons.
+pub const CUSTOM_ID_PREFIX: &str = "emoji";
+
+/// Maximum size of an emoji image allowed by Discord.
+pub const MAX_IMAGE_SIZE: u64 = 256 * 1024;
+
+/// Image formats that Discord accepts for custom emojis.
+const ALLOWED_CONTENT_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Members with these permissions can upload emojis without
+/// approval and review pending emoji uploads.
+pub const REVIEWER_PERMISSIONS: Permissions =
+    Permissions::ADMINISTRATOR.union(Permissions::MANAGE_GUILD_EXPRESSIONS);
+
+const NOT_ALLOWED_MSG: &str = "**You're not allowed to review emoji uploads.**";
+const ALREADY_REVIEWED_MSG: &str = "**This emoji upload has already been reviewed.**";
+
+/// Reasons why an emoji cannot be uploaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidEmoji {
+    /// The name has characters other than letters, numbers and
+    /// underscores or it is not within 2 to 32 characters long.
+    Name,
+    /// The image is not a PNG, JPEG, GIF or WEBP image.
+    Format,
+    /// The image is larger than [`MAX_IMAGE_SIZE`].
+    TooLarge,
+}
+
+impl Display for InvalidEmoji {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name => f.write_str("the name must have 2 to 32 letters, numbers or underscores"),
+            Self::Format => f.write_str("the image must be a PNG, JPEG, GIF or WEBP image"),
+            Self::TooLarge => f.write_str("the image must not be larger than 256 KB"),
+        }
+    }
+}
+
+/// Checks whether the emoji can be uploaded to Discord.
+pub fn validate(name: &str, content_type: Option<&str>, size: u64) -> Result<(), InvalidEmoji> {
+    let is_valid_name = (2..=32).contains(&name.chars().count())
+        && name.chars().all(|v| v.is_ascii_alphanumeric() || v == '_');
+
+    if !is_valid_name {
+        return Err(InvalidEmoji::Name);
+    }
+
+    let is_allowed_format = content_type.is_some_and(|v| ALLOWED_CONTENT_TYPES.contains(&v));
+
+    if !is_allowed_format {
+        Err(InvalidEmoji::Format)
+    } else if size > MAX_IMAGE_SIZE {
+        Err(InvalidEmoji::TooLarge)
+    } else {
+        Ok(())
+    }
+}
+
+fn to_data_uri(content_type: &str, data: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    format!("data:{content_type};base64,{encoded}")
+}
+
+/// Downloads the emoji image from Discord as a data URI
+/// so it can be uploaded later.
+pub async fn download(image: &Attachment) -> Result<String> {
+    let response = reqwest::get(image.url.as_str())
+        .await
+        .into_typed_error()
+        .attach_printable("could not send request to Discord to download emoji image")?;
+
+    let data = response
+        .bytes()
+        .await
+        .into_typed_error()
+        .attach_printable("could not download emoji image data")?;
+
+    let content_type = image.content_type.as_deref().unwrap_or("image/png");
+    Ok(to_data_uri(content_type, &data))
+}
+
+/// Uploads the emoji from a data URI image to the guild.
+pub async fn upload(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    name: &str,
+    image: &str,
+    requested_by: Id<UserMarker>,
+) -> Result<Emoji> {
+    let reason = format!("Emoji upload requested by {requested_by}");
+    let request = bot
+        .http
+        .create_emoji(guild_id, name, image)
+        .reason(&reason)
+        .into_typed_error()?;
+
+    debug!("uploading emoji {name:?}");
+    request_for_model(bot, request)
+        .await
+        .attach_printable("could not upload emoji")
+}
+
+fn make_custom_id(upload_id: Uuid, approve: bool) -> String {
+    let action = if approve { "approve" } else { "reject" };
+    format!("{CUSTOM_ID_PREFIX}:{action}:{upload_id}")
+}
+
+/// Parses the custom ID of the emoji approval buttons and returns
+/// the upload's ID and whether it is approved.
+fn parse_custom_id(custom_id: &str) -> Option<(Uuid, bool)> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != CUSTOM_ID_PREFIX {
+        return None;
+    }
+
+    let approve = match parts.next()? {
+        "approve" => true,
+        "reject" => false,
+        _ => return None,
+    };
+    let upload_id = parts.next()?.parse().ok()?;
+    Some((upload_id, approve))
+}
+
+/// Asks the admins from the alert channel to approve or reject
+/// the emoji upload.
+pub async fn request_approval(bot: &Bot, upload: &EmojiUpload, image_url: &str) -> Result<()> {
+    let components = [Component::ActionRow(ActionRow {
+        components: vec![
+            button(
+                make_custom_id(upload.id, true),
+                "Approve",
+                ButtonStyle::Success,
+            ),
+            button(
+                make_custom_id(upload.id, false),
+                "Reject",
+                ButtonStyle::Danger,
+            ),
+        ],
+    })];
+
+    let content = format!(
+        "**{} wants to add an emoji named `{}`.**\n{image_url}",
+        upload.requested_by.mention(),
+        upload.name
+    );
+
+    let allowed_mentions = AllowedMentions::default();
+    let alert = Alert {
+        content: Some(&content),
+        components: &components,
+        allowed_mentions: Some(&allowed_mentions),
+        ..Alert::new(AlertClass::Moderation, AlertSeverity::Info)
+    };
+
+    debug!("sending emoji approval request");
+    bot.alerter.send(alert).await?;
+
+    Ok(())
+}
+
+fn button(custom_id: String, label: &str, style: ButtonStyle) -> Component {
+    Component::Button(Button {
+        custom_id: Some(custom_id),
+        disabled: false,
+        emoji: None,
+        label: Some(label.into()),
+        style,
+        url: None,
+    })
+}
+
+/// Approves or rejects an emoji upload after pressing one of the
+/// buttons from the emoji approval request.
+#[instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_review(ctx: &InteractionContext<MessageComponentInteractionData>) -> Result<()> {
+    let Some((upload_id, approve)) = parse_custom_id(&ctx.data.custom_id) else {
+        warn!("got invalid emoji upload custom id");
+        return Ok(());
+    };
+
+    let (Some(guild_id), Some(permissions)) = (
+        ctx.interaction.guild_id,
+        ctx.interaction.member.as_ref().and_then(|v| v.permissions),
+    ) else {
+        return respond_ephemeral(ctx, NOT_ALLOWED_MSG).await;
+    };
+
+    if !permissions.intersects(REVIEWER_PERMISSIONS) {
+        return respond_ephemeral(ctx, NOT_ALLOWED_MSG).await;
+    }
+
+    let user_id = ctx.invoker_id();
+    let mut conn = ctx.bot.db_write().await?;
+    let Some(upload) = EmojiUpload::review(&mut conn, upload_id, user_id, approve).await? else {
+        return respond_ephemeral(ctx, ALREADY_REVIEWED_MSG).await;
+    };
+
+    let content = if approve {
+        let Some(image) = upload.image.as_deref() else {
+            return respond_ephemeral(ctx, ALREADY_REVIEWED_MSG).await;
+        };
+
+        let emoji =
+            self::upload(&ctx.bot, guild_id, &upload.name, image, upload.requested_by).await?;
+        EmojiUpload::set_emoji_id(&mut conn, upload.id, emoji.id).await?;
+        format!(
+            "**Emoji {} requested by {} has been approved by {}.**",
+            emoji.id.mention(),
+            upload.requested_by.mention(),
+            user_id.mention()
+        )
+    } else {
+        debug!("rejected emoji upload {upload_id}");
+        format!(
+            "**Emoji `{}` requested by {} has been rejected by {}.**",
+            upload.name,
+            upload.requested_by.mention(),
+            user_id.mention()
+        )
+    };
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    let data = InteractionResponseDataBuilder::new()
+        .allowed_mentions(AllowedMentions::default())
+        .content(content)
+        .build();
+
+    ctx.respond(data).await?;
+
+    // the approval buttons are no longer needed
+    if let Some(message) = ctx.interaction.message.as_ref() {
+        #[allow(clippy::unwrap_used)]
+        let request = ctx
+            .bot
+            .http
+            .update_message(message.channel_id, message.id)
+            .components(Some(&[]))
+            .unwrap();
+
+        if let Err(error) = request_for_model(&ctx.bot, request).await {
+            warn!(error = %error.anonymize(), "could not remove emoji approval buttons");
+        }
+    }
+
+    Ok(())
+}
+
+async fn respond_ephemeral<T>(ctx: &InteractionContext<T>, content: &str) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_custom_ids() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_custom_id(&make_custom_id(id, true)), Some((id, true)));
+        assert_eq!(
+            parse_custom_id(&make_custom_id(id, false)),
+            Some((id, false))
+        );
+        assert_eq!(parse_custom_id(&format!("emoji:delete:{id}")), None);
+        assert_eq!(parse_custom_id(&format!("lockdown:{id}")), None);
+    }
+
+    #[test]
+    fn should_validate_emojis() {
+        let png = Some("image/png");
+        assert_eq!(validate("eden_logo", png, 1024), Ok(()));
+        assert_eq!(validate("e", png, 1024), Err(InvalidEmoji::Name));
+        assert_eq!(validate("eden logo", png, 1024), Err(InvalidEmoji::Name));
+
+        let result = validate("eden", Some("image/svg+xml"), 1024);
+        assert_eq!(result, Err(InvalidEmoji::Format));
+        assert_eq!(validate("eden", None, 1024), Err(InvalidEmoji::Format));
+
+        let result = validate("eden", Some("image/gif"), MAX_IMAGE_SIZE + 1);
+        assert_eq!(result, Err(InvalidEmoji::TooLarge));
+    }
+
+    #[test]
+    fn should_encode_data_uri() {
+        assert_eq!(
+            to_data_uri("image/png", b"eden"),
+            "data:image/png;base64,ZWRlbg=="
+        );
+    }
+}
@@ -1,30 +1,41 @@
+use eden_schema::types::GuildSettings;
 use eden_utils::{twilight::error::TwilightHttpErrorExt, Result};
-use regex::Regex;
-use std::sync::LazyLock;
 use tracing::{instrument, trace, warn};
 use twilight_mention::Mention;
 use twilight_model::channel::Message;
-use twilight_model::id::marker::UserMarker;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
 
 use crate::events::EventContext;
 use crate::util::http::request_for_model;
 
-#[instrument(skip_all)]
-pub async fn on_trigger(ctx: &EventContext, message: &Message) -> bool {
-    if message.guild_id.is_none() {
-        return false;
-    }
+/// Permissions Eden needs in the introduction channel to
+/// reply to introductions.
+pub const INTRODUCTION_PERMISSIONS: Permissions = Permissions::VIEW_CHANNEL
+    .union(Permissions::SEND_MESSAGES)
+    .union(Permissions::READ_MESSAGE_HISTORY);
+
+/// Maximum length of the name Eden says back to the user.
+const MAX_NAME_LENGTH: usize = 1500;
 
-    let Some((name, index)) = get_supposed_name(&message.content) else {
+#[instrument(skip_all)]
+pub async fn on_trigger(ctx: &EventContext, message: &Message, settings: &GuildSettings) -> bool {
+    let Some(guild_id) = message.guild_id else {
         return false;
     };
 
-    if !super::is_word_part_valid(&name, &message.content, index) {
+    if !should_reply(ctx, guild_id, message, settings) {
         return false;
     }
 
+    let Some(content) = render_introduction(&message.content, ctx.bot.application_id().cast())
+    else {
+        return false;
+    };
+
     trace!("relying back introduction message");
-    if let Err(error) = respond(ctx, &message, &name).await {
+    if let Err(error) = respond(ctx, message, &content).await {
         let has_missing_access = error
             .discord_http_error_info()
             .map(|v| v.has_missing_access())
@@ -38,185 +49,82 @@ pub async fn on_trigger(ctx: &EventContext, message: &Message) -> bool {
     true
 }
 
+/// Renders Eden's reply to an introduction message.
+///
+/// It returns `None` if no name can be detected from the message.
+//
 // We don't want to let Eden say "Hi <swear word>" when the user said that so.
 //
 // By the way, this is inspired by Dad Bot#2189 made by alekeagle
-#[tracing::instrument(skip_all)]
-async fn respond(ctx: &EventContext, message: &Message, name: &str) -> Result<()> {
+#[must_use]
+pub fn render_introduction(content: &str, bot_id: Id<UserMarker>) -> Option<String> {
+    let name = super::NAME_DETECTOR.detect(content)?;
+
     // We only limit up to 1500 characters unfortunately :)
-    let original_size = name.len();
-    let limit = original_size.clamp(1, 1500);
+    let limit = name
+        .char_indices()
+        .nth(MAX_NAME_LENGTH)
+        .map_or(name.len(), |(index, _)| index);
 
     // censor some profanity HAHAHAH
-    let mut name = super::init_censor!(&name[0..limit]).censor();
-    if name.len() != limit {
+    let is_truncated = limit < name.len();
+    let mut name = super::init_censor!(&name[..limit]).censor();
+    if is_truncated {
         name.push_str("...");
     }
 
-    let content = format!(
-        "Hi **{name}**, I'm {}!",
-        ctx.bot.application_id().cast::<UserMarker>().mention()
-    );
+    Some(format!("Hi **{name}**, I'm {}!", bot_id.mention()))
+}
+
+/// Whether introductions are enabled in the guild and the message
+/// is sent in the guild's introduction channel (if it is set).
+fn should_reply(
+    ctx: &EventContext,
+    guild_id: Id<GuildMarker>,
+    message: &Message,
+    settings: &GuildSettings,
+) -> bool {
+    if !ctx.bot.is_local_guild(&guild_id) {
+        return true;
+    }
 
+    let introductions = &settings.introductions;
+    introductions.enabled
+        && introductions
+            .channel_id
+            .map_or(true, |v| v == message.channel_id)
+}
+
+#[tracing::instrument(skip_all)]
+async fn respond(ctx: &EventContext, message: &Message, content: &str) -> Result<()> {
     let request = ctx
         .bot
         .http
         .create_message(message.channel_id)
-        .content(&content)
+        .content(content)
         .unwrap()
         .reply(message.id);
 
-    request_for_model(&ctx.bot.http, request).await?;
+    request_for_model(&ctx.bot, request).await?;
     Ok(())
 }
 
-// Bisaya and Filipino languages are not supported because of complexity
-// and Filipino do sometimes mix some words to make it understandable
-fn get_supposed_name(content: &str) -> Option<(String, usize)> {
-    // I am... My name is...
-    static I_AM: LazyLock<Regex> =
-        LazyLock::new(|| Regex::new(r"(^i|([^\w]i)|([\s]i))(('?m)|( am))").unwrap());
-
-    static MY_NAME_IS: LazyLock<Regex> = LazyLock::new(|| {
-        Regex::new(r"(^(my)|([^\w](my))|([\s](my))) ?name(('s)|( ?is))?").unwrap()
-    });
-
-    let lowercased_content = content.to_lowercase();
-    let index = I_AM
-        .find(&lowercased_content)
-        .or_else(|| MY_NAME_IS.find(&lowercased_content))?
-        .end();
-
-    // assuming that index is within the size of the string
-    let mut buffer = String::new();
-    let name = &content[index..].trim_start();
-
-    // strip any discord's markdown syntax. this will break the bot.
-    // repeat this until we have one event only!
-    let mut iters = 0;
-    loop {
-        let mut times = 0;
-        if times == 1 || iters > 500 {
-            break;
-        } else {
-            buffer.clear();
-        }
-        iters += 1;
-
-        let parser = pulldown_cmark::TextMergeStream::new(pulldown_cmark::Parser::new(name));
-        for event in parser {
-            match event {
-                pulldown_cmark::Event::Start(pulldown_cmark::Tag::Paragraph) => {}
-                pulldown_cmark::Event::End(pulldown_cmark::TagEnd::Paragraph) => {}
-                pulldown_cmark::Event::Code(data) => {
-                    times += 1;
-                    buffer.push_str(&data);
-                }
-                pulldown_cmark::Event::Text(text) => {
-                    times += 1;
-                    buffer.push_str(&text);
-                }
-                _ => {}
-            }
-        }
-    }
-
-    if !buffer.is_empty() {
-        Some((buffer, index))
-    } else {
-        None
-    }
-}
-
 #[cfg(test)]
-mod test {
+mod tests {
     use super::*;
 
-    use crate::features::father_belt::is_word_part_valid;
-    use twilight_model::id::Id;
-
+    #[allow(clippy::unwrap_used)]
     #[test]
-    fn test_issue_9_fix() {
-        let user_id = Id::<UserMarker>::new(1234567890);
-        let message = format!("I'm {}", user_id.mention());
-        let (name, index) = get_supposed_name(&message).unwrap();
-        assert!(!is_word_part_valid(&name, &message, index));
-
-        let message = "here is my face: https://example.com/image.png";
-        let (name, index) = get_supposed_name(message).unwrap();
-        assert!(!is_word_part_valid(&name, message, index));
-
-        let message = "https://example.com/image.png";
-        let (name, index) = get_supposed_name(message).unwrap();
-        assert!(!is_word_part_valid(&name, message, index));
-
-        let message = "I'm a guy";
-        let (name, index) = get_supposed_name(message).unwrap();
-        assert!(is_word_part_valid(&name, message, index));
-
-        // real world scenario, this is the exact link caused the issue #9
-        let message =
-            "https://media.discordapp.net/attachments/123/456/imagdse0.gif?ex=6&is=66&hm=4f9dd&";
-
-        let (name, index) = get_supposed_name(message).unwrap();
-        assert!(!is_word_part_valid(&name, message, index));
-    }
-
-    #[test]
-    fn test_find_name() {
-        assert_eq!(
-            get_supposed_name("My name is memothelemo").map(|(a, ..)| a),
-            Some("memothelemo".into())
-        );
-        assert_eq!(
-            get_supposed_name("my name is memothelemo").map(|(a, ..)| a),
-            Some("memothelemo".into())
-        );
-
-        assert_eq!(
-            get_supposed_name("My name ispop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-        assert_eq!(
-            get_supposed_name("My nameispop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-        assert_eq!(
-            get_supposed_name("Mynameispop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-
+    fn should_render_introductions() {
+        let bot_id = Id::new(1);
         assert_eq!(
-            get_supposed_name("my name ispop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-        assert_eq!(
-            get_supposed_name("my nameispop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-        assert_eq!(
-            get_supposed_name("mynameispop").map(|(a, ..)| a),
-            Some("pop".into())
+            render_introduction("I'm Eden", bot_id).as_deref(),
+            Some("Hi **Eden**, I'm <@1>!")
         );
+        assert_eq!(render_introduction("hello there", bot_id), None);
 
-        // contractions
-        assert_eq!(
-            get_supposed_name("my name'spop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-        assert_eq!(
-            get_supposed_name("my name'spop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-        assert_eq!(
-            get_supposed_name("myname'spop").map(|(a, ..)| a),
-            Some("pop".into())
-        );
-
-        // it should strip down markdown
-        assert_eq!(
-            get_supposed_name("my name is **MEMO**").map(|(a, ..)| a),
-            Some("MEMO".into())
-        );
+        let long_name = "a".repeat(MAX_NAME_LENGTH + 10);
+        let rendered = render_introduction(&format!("I'm {long_name}"), bot_id).unwrap();
+        assert!(rendered.contains(&format!("{}...", "a".repeat(MAX_NAME_LENGTH))));
     }
 }
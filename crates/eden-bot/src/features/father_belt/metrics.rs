@@ -0,0 +1,155 @@
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+
+/// Steps of the father belt pipeline that are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FatherBeltMetric {
+    /// A guild message is scanned by father belt.
+    MessageScanned,
+    /// A member is warned for swearing.
+    CensorshipTriggered,
+    /// A member is told to keep their voice down.
+    ScreamAlerted,
+    /// A member's introduction ("I'm ...") is replied to.
+    IntroductionHandled,
+}
+
+/// Snapshot of the father belt counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FatherBeltCounts {
+    pub messages_scanned: u64,
+    pub censorship_triggers: u64,
+    pub scream_alerts: u64,
+    pub introductions: u64,
+}
+
+impl FatherBeltCounts {
+    /// Gets the counters accumulated after the `previous` snapshot.
+    #[must_use]
+    pub fn since(&self, previous: &Self) -> Self {
+        Self {
+            messages_scanned: self
+                .messages_scanned
+                .saturating_sub(previous.messages_scanned),
+            censorship_triggers: self
+                .censorship_triggers
+                .saturating_sub(previous.censorship_triggers),
+            scream_alerts: self.scream_alerts.saturating_sub(previous.scream_alerts),
+            introductions: self.introductions.saturating_sub(previous.introductions),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    messages_scanned: AtomicU64,
+    censorship_triggers: AtomicU64,
+    scream_alerts: AtomicU64,
+    introductions: AtomicU64,
+}
+
+impl Counters {
+    fn get(&self, metric: FatherBeltMetric) -> &AtomicU64 {
+        match metric {
+            FatherBeltMetric::MessageScanned => &self.messages_scanned,
+            FatherBeltMetric::CensorshipTriggered => &self.censorship_triggers,
+            FatherBeltMetric::ScreamAlerted => &self.scream_alerts,
+            FatherBeltMetric::IntroductionHandled => &self.introductions,
+        }
+    }
+
+    fn snapshot(&self) -> FatherBeltCounts {
+        FatherBeltCounts {
+            messages_scanned: self.messages_scanned.load(Ordering::Relaxed),
+            censorship_triggers: self.censorship_triggers.load(Ordering::Relaxed),
+            scream_alerts: self.scream_alerts.load(Ordering::Relaxed),
+            introductions: self.introductions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Keeps track of how often each step of the father belt pipeline
+/// happens per guild, so its heuristics can be tuned with real data.
+#[derive(Debug, Default)]
+pub struct FatherBeltMetrics {
+    guilds: DashMap<Id<GuildMarker>, Counters>,
+}
+
+impl FatherBeltMetrics {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counters of a guild.
+    #[must_use]
+    pub fn guild(&self, guild_id: Id<GuildMarker>) -> FatherBeltCounts {
+        self.guilds
+            .get(&guild_id)
+            .map(|v| v.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Counters of every guild where father belt has scanned
+    /// at least one message.
+    #[must_use]
+    pub fn guilds(&self) -> Vec<(Id<GuildMarker>, FatherBeltCounts)> {
+        self.guilds
+            .iter()
+            .map(|v| (*v.key(), v.snapshot()))
+            .collect()
+    }
+
+    /// Sum of the counters across all guilds.
+    #[must_use]
+    pub fn total(&self) -> FatherBeltCounts {
+        self.guilds()
+            .into_iter()
+            .fold(FatherBeltCounts::default(), |acc, (_, v)| {
+                FatherBeltCounts {
+                    messages_scanned: acc.messages_scanned + v.messages_scanned,
+                    censorship_triggers: acc.censorship_triggers + v.censorship_triggers,
+                    scream_alerts: acc.scream_alerts + v.scream_alerts,
+                    introductions: acc.introductions + v.introductions,
+                }
+            })
+    }
+
+    pub(crate) fn record(&self, guild_id: Id<GuildMarker>, metric: FatherBeltMetric) {
+        self.guilds
+            .entry(guild_id)
+            .or_default()
+            .get(metric)
+            .fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_per_guild() {
+        let metrics = FatherBeltMetrics::new();
+        let a = Id::new(1);
+        let b = Id::new(2);
+        assert_eq!(metrics.guild(a), FatherBeltCounts::default());
+
+        metrics.record(a, FatherBeltMetric::MessageScanned);
+        metrics.record(a, FatherBeltMetric::MessageScanned);
+        metrics.record(a, FatherBeltMetric::ScreamAlerted);
+        metrics.record(b, FatherBeltMetric::MessageScanned);
+        metrics.record(b, FatherBeltMetric::IntroductionHandled);
+
+        assert_eq!(metrics.guild(a).messages_scanned, 2);
+        assert_eq!(metrics.guild(a).scream_alerts, 1);
+        assert_eq!(metrics.guild(b).introductions, 1);
+
+        let total = metrics.total();
+        assert_eq!(total.messages_scanned, 3);
+        assert_eq!(total.censorship_triggers, 0);
+        assert_eq!(metrics.guilds().len(), 2);
+    }
+}
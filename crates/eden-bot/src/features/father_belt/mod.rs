@@ -1,16 +1,30 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
 use eden_utils::twilight::error::TwilightHttpErrorExt;
-use regex::Regex;
 use rustrict::{Trie, Type};
 use std::sync::LazyLock;
-use tracing::{instrument, trace, warn};
+use std::time::Duration;
+use tracing::{debug, instrument, trace, warn};
 use twilight_model::channel::Message;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
 
 use crate::events::EventContext;
+use crate::features::FeatureEvent;
+use crate::util::event_bus::Subscription;
 use crate::util::http::request_for_model;
+use crate::Bot;
 
 mod introduce;
+mod metrics;
+mod name_detector;
 mod no_bad_words;
 
+pub use self::introduce::{render_introduction, INTRODUCTION_PERMISSIONS};
+pub use self::metrics::{FatherBeltCounts, FatherBeltMetric, FatherBeltMetrics};
+pub use self::name_detector::NameDetector;
+
 const RUSTRICT_CONFIGURED_TYPE: LazyLock<Type> =
     LazyLock::new(|| Type::INAPPROPRIATE | Type::EVASIVE | Type::OFFENSIVE | Type::SEVERE);
 
@@ -24,19 +38,157 @@ macro_rules! init_censor {
 }
 use init_censor;
 
+/// Detects names from introductions and filters out false
+/// positives like mentions and URLs.
+static NAME_DETECTOR: LazyLock<NameDetector> = LazyLock::new(NameDetector::new);
+
+/// How long father belt stops replying to a member after Eden
+/// took a moderation action against them.
+const SUPPRESSION_DURATION: TimeDelta = TimeDelta::minutes(5);
+
+/// How often expired reply suppressions are removed.
+const SUPPRESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Keeps track of members that father belt should not reply to
+/// for a while, so they won't get piled up with replies after
+/// getting moderated.
+#[derive(Debug, Default)]
+pub struct ReplySuppressions {
+    until: DashMap<(Id<GuildMarker>, Id<UserMarker>), DateTime<Utc>>,
+}
+
+impl ReplySuppressions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suppresses replies to the member of a guild until `duration`
+    /// has passed from `now`.
+    pub fn suppress(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        now: DateTime<Utc>,
+        duration: TimeDelta,
+    ) {
+        self.until.insert((guild_id, user_id), now + duration);
+    }
+
+    #[must_use]
+    pub fn is_suppressed(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        self.until
+            .get(&(guild_id, user_id))
+            .is_some_and(|until| *until > now)
+    }
+
+    /// Removes every suppression that has already expired by `now`.
+    pub fn sweep(&self, now: DateTime<Utc>) {
+        self.until.retain(|_, until| *until > now);
+    }
+}
+
+/// Listens for events from other features that father belt needs
+/// to react on until Eden shuts down.
+#[instrument(skip_all)]
+pub async fn listen(bot: Bot, mut events: Subscription<FeatureEvent>) {
+    let mut sweep = tokio::time::interval(SUPPRESSION_SWEEP_INTERVAL);
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Some(FeatureEvent::ModerationAction { guild_id, user_id }) => {
+                    debug!(%guild_id, %user_id, "suppressing replies after a moderation action");
+                    bot.father_belt
+                        .suppress(guild_id, user_id, Utc::now(), SUPPRESSION_DURATION);
+                }
+                Some(..) => {}
+                None => break,
+            },
+            _ = sweep.tick() => bot.father_belt.sweep(Utc::now()),
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn on_message_create(ctx: &EventContext, message: &Message) {
-    if self::introduce::on_trigger(ctx, message).await {
+    if !ctx.bot.settings.features.father_belt {
+        return;
+    }
+
+    if ctx.bot.quiet_hours.is_active() {
+        trace!("quiet hours are in effect");
         return;
     }
 
-    if self::no_bad_words::on_trigger(ctx, message).await {
+    let Some(guild_id) = message.guild_id else {
+        return;
+    };
+
+    if ctx
+        .bot
+        .father_belt
+        .is_suppressed(guild_id, message.author.id, Utc::now())
+    {
+        trace!("replies to the author are suppressed");
         return;
     }
 
-    // TODO: check channel permissions first before sending the message
-    if is_screaming(&message.content) && message.guild_id.is_some() {
+    let settings = match ctx.bot.guild_settings(guild_id).await {
+        Ok(settings) if settings.father_belt.enabled => settings,
+        Ok(..) => {
+            trace!("father belt is disabled in the guild");
+            return;
+        }
+        Err(error) => {
+            warn!(%error, "could not check if father belt is enabled in the guild");
+            return;
+        }
+    };
+
+    let metrics = &ctx.bot.father_belt_metrics;
+    metrics.record(guild_id, FatherBeltMetric::MessageScanned);
+
+    if ctx.bot.settings.features.introductions
+        && self::introduce::on_trigger(ctx, message, &settings).await
+    {
+        metrics.record(guild_id, FatherBeltMetric::IntroductionHandled);
+        return;
+    }
+
+    if self::no_bad_words::on_trigger(ctx, message, &settings).await {
+        metrics.record(guild_id, FatherBeltMetric::CensorshipTriggered);
+        return;
+    }
+
+    if is_screaming(&message.content) {
+        let needed = Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::READ_MESSAGE_HISTORY;
+
+        match ctx
+            .bot
+            .preflight_permissions(guild_id, message.channel_id, needed)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                trace!("bot is lacking permissions to alert the user not to scream");
+                return;
+            }
+            Err(error) => {
+                warn!(%error, "could not check bot permissions before alerting the user not to scream");
+                return;
+            }
+        }
+
         trace!("alerting the user not to scream");
+        metrics.record(guild_id, FatherBeltMetric::ScreamAlerted);
 
         let request = ctx
             .bot
@@ -46,7 +198,7 @@ pub async fn on_message_create(ctx: &EventContext, message: &Message) {
             .unwrap()
             .reply(message.id);
 
-        if let Err(error) = request_for_model(&ctx.bot.http, request).await {
+        if let Err(error) = request_for_model(&ctx.bot, request).await {
             let error = error.anonymize();
             let has_missing_access = error
                 .discord_http_error_info()
@@ -60,36 +212,6 @@ pub async fn on_message_create(ctx: &EventContext, message: &Message) {
     }
 }
 
-// From: https://github.com/memothelemo/eden/issues/9
-fn is_word_part_valid(processed: &str, original_content: &str, name_index: usize) -> bool {
-    static DISCORD_MENTION_TAG: LazyLock<Regex> =
-        LazyLock::new(|| Regex::new(r"<@[0-9]+>").unwrap());
-
-    if let Some(captures) = DISCORD_MENTION_TAG.captures(processed) {
-        // Do not return it as Some.
-        //
-        // Users will get advantage of the bug that allows the bot to ping the
-        // server administrator or any role and we don't want let it happend.
-        for id in 0..captures.len() {
-            if captures.get(id).is_some() {
-                return false;
-            }
-        }
-    }
-
-    // Checking if the finalized buffer comes from a URL part of the message.
-    // Related to issue #9.
-    let (left, right) = original_content.split_at(name_index);
-    let left = left.split_whitespace().last().unwrap_or("");
-    let right = right.split_whitespace().next().unwrap_or("");
-
-    let mut part = String::new();
-    part.push_str(left);
-    part.push_str(right);
-
-    url::Url::parse(&part).is_err()
-}
-
 // - Messages with only non-alphabetic characters are not considered as screaming
 // - Messages that can be considered as screaming if there are more than 2 consecutive
 //   uppercased words
@@ -162,6 +284,25 @@ pub fn install() {
 mod test {
     use super::*;
 
+    #[test]
+    fn should_expire_suppressions() {
+        let suppressions = ReplySuppressions::new();
+        let guild_id = Id::new(1);
+        let user_id = Id::new(2);
+        let now = Utc::now();
+
+        assert!(!suppressions.is_suppressed(guild_id, user_id, now));
+        suppressions.suppress(guild_id, user_id, now, SUPPRESSION_DURATION);
+        assert!(suppressions.is_suppressed(guild_id, user_id, now));
+        assert!(!suppressions.is_suppressed(Id::new(3), user_id, now));
+
+        let expired = now + SUPPRESSION_DURATION;
+        assert!(!suppressions.is_suppressed(guild_id, user_id, expired));
+
+        suppressions.sweep(expired);
+        assert!(suppressions.until.is_empty());
+    }
+
     #[test]
     fn test_is_screaming() {
         assert!(!is_screaming("I'm a cool guy"));
@@ -0,0 +1,213 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Detects the name of a member from messages where they introduce
+/// themselves ("I'm ..." or "My name is ...").
+///
+/// Names that are part of a mention or a URL are not detected
+/// (see issue #9) unless the URL's host is allowed. Names that
+/// contain any of the denied words are not detected either.
+#[derive(Debug, Clone, Default)]
+pub struct NameDetector {
+    allowed_hosts: Vec<String>,
+    denied_words: Vec<String>,
+}
+
+impl NameDetector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows names that are part of URLs from this host.
+    #[must_use]
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into().to_lowercase());
+        self
+    }
+
+    /// Rejects names that contain this word (case insensitive).
+    #[must_use]
+    pub fn deny_word(mut self, word: impl Into<String>) -> Self {
+        self.denied_words.push(word.into().to_lowercase());
+        self
+    }
+
+    /// Detects the name from the message content.
+    #[must_use]
+    pub fn detect(&self, content: &str) -> Option<String> {
+        let (name, index) = find_name(content)?;
+        self.is_valid(&name, content, index).then_some(name)
+    }
+
+    /// Checks whether the processed part of the content at `index`
+    /// is not a mention, a URL or a denied word.
+    #[must_use]
+    pub fn is_valid(&self, processed: &str, content: &str, index: usize) -> bool {
+        static DISCORD_MENTION_TAG: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"<@[0-9]+>").unwrap());
+
+        // Users will get advantage of the bug that allows the bot to ping the
+        // server administrator or any role and we don't want let it happen.
+        if DISCORD_MENTION_TAG.is_match(processed) {
+            return false;
+        }
+
+        let lowercased = processed.to_lowercase();
+        if self
+            .denied_words
+            .iter()
+            .any(|v| lowercased.contains(v.as_str()))
+        {
+            return false;
+        }
+
+        // Checking if the finalized buffer comes from a URL part of the message.
+        // Related to issue #9.
+        let (left, right) = content.split_at(index);
+        let left = left.split_whitespace().last().unwrap_or("");
+        let right = right.split_whitespace().next().unwrap_or("");
+
+        let mut part = String::new();
+        part.push_str(left);
+        part.push_str(right);
+
+        match url::Url::parse(&part) {
+            Ok(url) => url
+                .host_str()
+                .is_some_and(|host| self.allowed_hosts.iter().any(|v| v == host)),
+            Err(..) => true,
+        }
+    }
+}
+
+// Bisaya and Filipino languages are not supported because of complexity
+// and Filipino do sometimes mix some words to make it understandable
+fn find_name(content: &str) -> Option<(String, usize)> {
+    // I am... My name is...
+    static I_AM: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(^i|([^\w]i)|([\s]i))(('?m)|( am))").unwrap());
+
+    static MY_NAME_IS: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"(^(my)|([^\w](my))|([\s](my))) ?name(('s)|( ?is))?").unwrap()
+    });
+
+    let lowercased_content = content.to_lowercase();
+    let index = I_AM
+        .find(&lowercased_content)
+        .or_else(|| MY_NAME_IS.find(&lowercased_content))?
+        .end();
+
+    // assuming that index is within the size of the string
+    let mut buffer = String::new();
+    let name = &content[index..].trim_start();
+
+    // strip any discord's markdown syntax. this will break the bot.
+    // repeat this until we have one event only!
+    let mut iters = 0;
+    loop {
+        let mut times = 0;
+        if times == 1 || iters > 500 {
+            break;
+        } else {
+            buffer.clear();
+        }
+        iters += 1;
+
+        let parser = pulldown_cmark::TextMergeStream::new(pulldown_cmark::Parser::new(name));
+        for event in parser {
+            match event {
+                pulldown_cmark::Event::Start(pulldown_cmark::Tag::Paragraph) => {}
+                pulldown_cmark::Event::End(pulldown_cmark::TagEnd::Paragraph) => {}
+                pulldown_cmark::Event::Code(data) => {
+                    times += 1;
+                    buffer.push_str(&data);
+                }
+                pulldown_cmark::Event::Text(text) => {
+                    times += 1;
+                    buffer.push_str(&text);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !buffer.is_empty() {
+        Some((buffer, index))
+    } else {
+        None
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks every case from a corpus against the detector and
+    /// returns the cases that did not detect the expected name.
+    ///
+    /// Refer to `fixtures/names.txt` for the format of the corpus.
+    fn check_corpus(detector: &NameDetector, corpus: &str) -> Vec<String> {
+        let mut failures = Vec::new();
+        for line in corpus.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((message, expected)) = line.rsplit_once("=>") else {
+                failures.push(format!("invalid corpus line: {line:?}"));
+                continue;
+            };
+
+            let message = message.trim();
+            let expected = Some(expected.trim()).filter(|v| *v != "!");
+            let detected = detector.detect(message);
+            if detected.as_deref() != expected {
+                failures.push(format!(
+                    "{message:?}: expected {expected:?}, got {detected:?}"
+                ));
+            }
+        }
+        failures
+    }
+
+    #[test]
+    fn should_pass_corpus() {
+        let failures = check_corpus(&NameDetector::new(), include_str!("fixtures/names.txt"));
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    // From: https://github.com/memothelemo/eden/issues/9
+    #[test]
+    fn should_reject_names_from_mentions_and_urls() {
+        let detector = NameDetector::new();
+        let check = |message: &str| {
+            let (name, index) = find_name(message).unwrap();
+            detector.is_valid(&name, message, index)
+        };
+
+        assert!(!check("I'm <@1234567890>"));
+        assert!(!check("here is my face: https://example.com/image.png"));
+        assert!(!check("https://example.com/image.png"));
+        assert!(check("I'm a guy"));
+
+        // real world scenario, this is the exact link caused the issue #9
+        assert!(!check(
+            "https://media.discordapp.net/attachments/123/456/imagdse0.gif?ex=6&is=66&hm=4f9dd&"
+        ));
+    }
+
+    #[test]
+    fn should_allow_hosts_and_deny_words() {
+        let message = "check it out: https://eden.example/image.png";
+        assert_eq!(NameDetector::new().detect(message), None);
+
+        let detector = NameDetector::new().allow_host("eden.example");
+        assert_eq!(detector.detect(message).as_deref(), Some("age.png"));
+
+        let detector = NameDetector::new().deny_word("guy");
+        assert_eq!(detector.detect("I'm a GUY"), None);
+        assert_eq!(detector.detect("I'm a girl").as_deref(), Some("a girl"));
+    }
+}
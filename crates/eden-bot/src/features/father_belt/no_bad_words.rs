@@ -1,25 +1,45 @@
 use difference::{Changeset, Difference};
+use eden_schema::types::{GuildSettings, WordFilterAction};
+use eden_utils::error::exts::*;
 use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::Result;
 use itertools::Itertools;
 use rand::Rng;
 use rustrict::Type;
 use std::sync::LazyLock;
 use tokio::task::spawn_blocking;
 use tracing::{instrument, trace, warn};
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
 use twilight_model::channel::Message;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
 
 use crate::events::EventContext;
-use crate::util::http::request_for_model;
+use crate::features::{preferences, FeatureEvent};
+use crate::interactions::embeds;
+use crate::util::http::{request_for_empty, request_for_model};
+use crate::util::webhooks::{avatar_url, WebhookMessage};
+
+const DELETE_REASON: &str = "Message contains bad words";
+
+const DM_WARNING_MSG: &str =
+    "**Please do not swear in the server!** Your message contains these words: {BAD_WORDS}";
 
 #[instrument(skip_all)]
-pub async fn on_trigger(ctx: &EventContext, message: &Message) -> bool {
-    if message.guild_id.is_none() {
+pub async fn on_trigger(ctx: &EventContext, message: &Message, settings: &GuildSettings) -> bool {
+    let Some(guild_id) = message.guild_id else {
         return false;
-    }
+    };
 
     // It's a bit annoying to let the bot warn you every time you
     // said a swear word. Let's make it by chance!
-    if rand::random::<bool>() {
+    //
+    // This is decided before scanning the message since
+    // scanning is quite expensive.
+    let action = settings.word_filter.action;
+    if action == WordFilterAction::Reply && rand::random::<bool>() {
         return false;
     }
 
@@ -49,6 +69,15 @@ pub async fn on_trigger(ctx: &EventContext, message: &Message) -> bool {
         return false;
     }
 
+    // removing messages is a moderation action that other
+    // features may want to escalate or react on
+    if matches!(action, WordFilterAction::Delete | WordFilterAction::Repost) {
+        ctx.bot.events.publish(FeatureEvent::ModerationAction {
+            guild_id,
+            user_id: message.author.id,
+        });
+    }
+
     // render it letter by letter
     //
     // For example:
@@ -58,6 +87,35 @@ pub async fn on_trigger(ctx: &EventContext, message: &Message) -> bool {
         .map(|v| format!("`{}`", v.chars().join(" ")))
         .join(", ");
 
+    trace!(?action, "taking word filter action against the message");
+    let result = match action {
+        WordFilterAction::Reply => reply(ctx, message, warn_message, &bad_words).await,
+        WordFilterAction::Delete => delete(ctx, guild_id, message, false).await,
+        WordFilterAction::Repost => delete(ctx, guild_id, message, true).await,
+        WordFilterAction::DirectMessage => direct_message(ctx, message, &bad_words).await,
+        WordFilterAction::Log => log(ctx, settings, message, &bad_words).await,
+    };
+
+    if let Err(error) = result {
+        let has_missing_access = error
+            .discord_http_error_info()
+            .map(|v| v.has_missing_access())
+            .unwrap_or_default();
+
+        if !has_missing_access {
+            warn!(%error, "could not take {action:?} word filter action against the message");
+        }
+    }
+
+    true
+}
+
+async fn reply(
+    ctx: &EventContext,
+    message: &Message,
+    warn_message: &str,
+    bad_words: &str,
+) -> Result<()> {
     let preferred_name = message
         .member
         .as_ref()
@@ -66,29 +124,135 @@ pub async fn on_trigger(ctx: &EventContext, message: &Message) -> bool {
 
     let content = warn_message
         .replace("{USER_NAME}", preferred_name)
-        .replace("{BAD_WORDS}", &bad_words);
+        .replace("{BAD_WORDS}", bad_words);
 
     let request = ctx
         .bot
         .http
         .create_message(message.channel_id)
         .content(&content)
-        .unwrap()
+        .into_typed_error()?
         .reply(message.id);
 
     trace!("warning the user to not swear");
-    if let Err(error) = request_for_model(&ctx.bot.http, request).await {
-        let has_missing_access = error
-            .discord_http_error_info()
-            .map(|v| v.has_missing_access())
-            .unwrap_or_default();
+    request_for_model(&ctx.bot, request).await?;
+    Ok(())
+}
 
-        if !has_missing_access {
-            warn!(%error, "could not warn the user with message to not swear");
-        }
+/// Deletes the message and optionally re-posts it censored with
+/// a webhook imitating the author.
+///
+/// The censored copy is sent before the original message is deleted,
+/// so the message won't be lost if re-posting fails.
+async fn delete(
+    ctx: &EventContext,
+    guild_id: Id<GuildMarker>,
+    message: &Message,
+    repost: bool,
+) -> Result<()> {
+    let mut needed = Permissions::VIEW_CHANNEL | Permissions::MANAGE_MESSAGES;
+    if repost {
+        needed |= Permissions::MANAGE_WEBHOOKS;
     }
 
-    true
+    let is_permitted = ctx
+        .bot
+        .preflight_permissions(guild_id, message.channel_id, needed)
+        .await?;
+
+    if !is_permitted {
+        trace!("bot is lacking permissions to delete the message with bad words");
+        return Ok(());
+    }
+
+    if repost {
+        repost_censored(ctx, message).await?;
+    }
+
+    let request = ctx
+        .bot
+        .http
+        .delete_message(message.channel_id, message.id)
+        .reason(DELETE_REASON)
+        .into_typed_error()?;
+
+    trace!("deleting message with bad words");
+    request_for_empty(&ctx.bot, request)
+        .await
+        .attach_printable("could not delete message with bad words")?;
+
+    Ok(())
+}
+
+async fn repost_censored(ctx: &EventContext, message: &Message) -> Result<()> {
+    let author = &message.author;
+    let username = message
+        .member
+        .as_ref()
+        .and_then(|v| v.nick.as_deref())
+        .or(author.global_name.as_deref())
+        .unwrap_or(&author.name);
+
+    let avatar_url = avatar_url(author);
+    let content = censor(&message.content);
+    let webhook_message = WebhookMessage {
+        username,
+        avatar_url: Some(&avatar_url),
+        content: &content,
+        embeds: &[],
+        attachments: &[],
+    };
+
+    trace!("re-posting censored message");
+    ctx.bot
+        .webhooks
+        .send(message.channel_id, webhook_message)
+        .await
+        .attach_printable("could not re-post censored message with webhook")?;
+
+    Ok(())
+}
+
+async fn direct_message(ctx: &EventContext, message: &Message, bad_words: &str) -> Result<()> {
+    if !preferences::allows_dm_reminders(&ctx.bot, message.author.id).await? {
+        trace!("user opted out of DM reminders, skipping");
+        return Ok(());
+    }
+
+    let request = ctx.bot.http.create_private_channel(message.author.id);
+    let channel = request_for_model(&ctx.bot, request)
+        .await
+        .attach_printable("could not create DM channel with the user")?;
+
+    let content = DM_WARNING_MSG.replace("{BAD_WORDS}", bad_words);
+    let request = ctx
+        .bot
+        .http
+        .create_message(channel.id)
+        .content(&content)
+        .into_typed_error()?;
+
+    trace!("warning the user to not swear through DMs");
+    request_for_model(&ctx.bot, request).await?;
+    Ok(())
+}
+
+async fn log(
+    ctx: &EventContext,
+    settings: &GuildSettings,
+    message: &Message,
+    bad_words: &str,
+) -> Result<()> {
+    let embed = embeds::builders::with_emoji('🤬', "Message with bad words")
+        .description(format!(
+            "{} said {bad_words} in {}.",
+            message.author.id.mention(),
+            message.channel_id.mention()
+        ))
+        .build();
+
+    crate::local_guild::channel::send_member_log(&ctx.bot, settings, embed).await?;
+    Ok(())
 }
 
 const WARN_MESSAGES: &[&str] = &[
@@ -107,6 +271,13 @@ const WARN_MESSAGES: &[&str] = &[
 const NO_BAD_WORDS_FILTER: LazyLock<Type> =
     LazyLock::new(|| Type::OFFENSIVE | Type::PROFANE | Type::SEVERE);
 
+/// Censors all bad words from the content.
+fn censor(content: &str) -> String {
+    super::init_censor!(content)
+        .with_censor_threshold(*NO_BAD_WORDS_FILTER)
+        .censor()
+}
+
 fn process_bad_words(content: &str) -> Vec<String> {
     let mut bad_words = Vec::new();
 
@@ -118,7 +289,7 @@ fn process_bad_words(content: &str) -> Vec<String> {
             .with_censor_threshold(*NO_BAD_WORDS_FILTER)
             .censor();
 
-        if !super::is_word_part_valid(&original, original, 0) {
+        if !super::NAME_DETECTOR.is_valid(original, original, 0) {
             continue;
         }
 
@@ -152,6 +323,14 @@ mod tests {
         assert!(process_bad_words("No bad words here!").is_empty());
     }
 
+    #[test]
+    fn test_censor() {
+        let censored = censor("How fucking dare you!");
+        assert!(!censored.contains("fucking"));
+        assert!(censored.ends_with("dare you!"));
+        assert_eq!(censor("No bad words here!"), "No bad words here!");
+    }
+
     #[test]
     fn test_not_too_sensitive() {
         assert!(process_bad_words("I hate ginger").is_empty());
@@ -0,0 +1,261 @@
+use dashmap::DashMap;
+use eden_settings::FeatureRollout;
+use tokio::sync::Notify;
+use twilight_model::id::marker::{RoleMarker, UserMarker};
+use twilight_model::id::Id;
+
+/// Rollout rule of a command or feature that is not ready
+/// for everyone yet (canary rollout).
+///
+/// Gates are only kept in memory, so every gated command or feature
+/// is available to everyone again after Eden restarts unless it is
+/// gated from the `bot.feature_gates` setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureGate {
+    /// Name of the gated feature or command path (like `payer pay`).
+    pub name: String,
+    /// Percentage of users (from 0 to 100) that can use the feature.
+    pub percentage: u8,
+    /// Members with any of these roles can always use the feature.
+    pub roles: Vec<Id<RoleMarker>>,
+}
+
+impl From<&FeatureRollout> for FeatureGate {
+    fn from(value: &FeatureRollout) -> Self {
+        Self {
+            roles: value.roles.clone(),
+            ..Self::new(value.name.clone(), value.percentage)
+        }
+    }
+}
+
+impl FeatureGate {
+    #[must_use]
+    pub fn new(name: impl Into<String>, percentage: u8) -> Self {
+        Self {
+            name: name.into(),
+            percentage: percentage.min(100),
+            roles: Vec::new(),
+        }
+    }
+
+    /// Whether nobody can use the feature regardless of their roles.
+    #[must_use]
+    pub fn is_disabled(&self) -> bool {
+        self.percentage == 0 && self.roles.is_empty()
+    }
+
+    /// Whether the user can use the feature based on their roles
+    /// in the guild and their rollout bucket.
+    #[must_use]
+    pub fn is_enabled_for(&self, user_id: Id<UserMarker>, roles: &[Id<RoleMarker>]) -> bool {
+        if self.roles.iter().any(|v| roles.contains(v)) {
+            return true;
+        }
+        self.bucket(user_id) < self.percentage
+    }
+
+    /// Every user stays in the same bucket (from 0 to 99) per feature
+    /// so a feature won't randomly flip for them while rolling out.
+    fn bucket(&self, user_id: Id<UserMarker>) -> u8 {
+        let hash = eden_utils::hash::bytes::sha256(format!("{}:{user_id}", self.name));
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&hash[..8]);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let bucket = (u64::from_le_bytes(bytes) % 100) as u8;
+        bucket
+    }
+}
+
+/// Keeps every feature gate that can be changed at runtime
+/// with `/admin feature`.
+#[derive(Debug, Default)]
+pub struct FeatureGates {
+    gates: DashMap<String, FeatureGate>,
+    changed: Notify,
+}
+
+impl FeatureGates {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn from_settings(rollouts: &[FeatureRollout]) -> Self {
+        let gates = Self::new();
+        gates.apply_settings(&[], rollouts);
+        gates
+    }
+
+    /// Applies the changes of the `bot.feature_gates` setting. Gates
+    /// that are no longer in the setting are removed and gates that
+    /// are not changed in the setting are left as is.
+    pub fn apply_settings(&self, previous: &[FeatureRollout], current: &[FeatureRollout]) {
+        for rollout in previous {
+            if !current.iter().any(|v| v.name == rollout.name) {
+                self.remove(&rollout.name);
+            }
+        }
+
+        for rollout in current {
+            if !previous.contains(rollout) {
+                self.set(FeatureGate::from(rollout));
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<FeatureGate> {
+        self.gates.get(name).map(|v| v.clone())
+    }
+
+    /// Gets every feature gate sorted by name.
+    #[must_use]
+    pub fn list(&self) -> Vec<FeatureGate> {
+        let mut gates = self.gates.iter().map(|v| v.clone()).collect::<Vec<_>>();
+        gates.sort_by(|a, b| a.name.cmp(&b.name));
+        gates
+    }
+
+    pub fn set(&self, gate: FeatureGate) {
+        self.gates.insert(gate.name.clone(), gate);
+        self.changed.notify_one();
+    }
+
+    /// Removes the feature gate and makes the feature available to
+    /// everyone. It returns the removed gate if it exists.
+    pub fn remove(&self, name: &str) -> Option<FeatureGate> {
+        let removed = self.gates.remove(name).map(|(_, v)| v);
+        if removed.is_some() {
+            self.changed.notify_one();
+        }
+        removed
+    }
+
+    /// Waits until any feature gate is set or removed. Changes made
+    /// while nobody is waiting are still received by the next call.
+    pub async fn changed(&self) {
+        self.changed.notified().await;
+    }
+
+    /// Whether nobody can use the feature. Features without
+    /// any gate are available to everyone.
+    #[must_use]
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.gates.get(name).is_some_and(|v| v.is_disabled())
+    }
+
+    /// Whether the user can use the feature. Features without
+    /// any gate are available to everyone.
+    #[must_use]
+    pub fn is_enabled_for(
+        &self,
+        name: &str,
+        user_id: Id<UserMarker>,
+        roles: &[Id<RoleMarker>],
+    ) -> bool {
+        self.gates
+            .get(name)
+            .map_or(true, |v| v.is_enabled_for(user_id, roles))
+    }
+
+    /// Whether the user can use the command including its parent
+    /// commands. For example, `settings logs member` is gated if
+    /// either `settings`, `settings logs` or itself is gated.
+    #[must_use]
+    pub fn is_command_enabled_for(
+        &self,
+        path: &str,
+        user_id: Id<UserMarker>,
+        roles: &[Id<RoleMarker>],
+    ) -> bool {
+        if self.gates.is_empty() {
+            return true;
+        }
+
+        let parents = path.match_indices(' ').map(|(index, _)| &path[..index]);
+        parents
+            .chain(std::iter::once(path))
+            .all(|name| self.is_enabled_for(name, user_id, roles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_roll_out_by_percentage() {
+        let users = (1..=1000).map(Id::new).collect::<Vec<_>>();
+        let enabled = |gate: &FeatureGate| {
+            users
+                .iter()
+                .filter(|v| gate.is_enabled_for(**v, &[]))
+                .count()
+        };
+
+        assert_eq!(enabled(&FeatureGate::new("payer pay", 0)), 0);
+        assert_eq!(enabled(&FeatureGate::new("payer pay", 100)), users.len());
+
+        let half = enabled(&FeatureGate::new("payer pay", 50));
+        assert!((400..=600).contains(&half), "got {half} users");
+
+        // users must stay in the same bucket
+        let gate = FeatureGate::new("payer pay", 50);
+        assert_eq!(enabled(&gate), half);
+    }
+
+    #[test]
+    fn should_enable_for_role_holders() {
+        let role_id = Id::new(1);
+        let gate = FeatureGate {
+            roles: vec![role_id],
+            ..FeatureGate::new("emoji", 0)
+        };
+
+        assert!(gate.is_enabled_for(Id::new(1), &[role_id]));
+        assert!(!gate.is_enabled_for(Id::new(1), &[Id::new(2)]));
+    }
+
+    #[test]
+    fn should_gate_subcommands() {
+        let gates = FeatureGates::new();
+        let user_id = Id::new(1);
+        assert!(gates.is_command_enabled_for("settings logs member", user_id, &[]));
+
+        gates.set(FeatureGate::new("settings logs", 0));
+        assert!(!gates.is_command_enabled_for("settings logs member", user_id, &[]));
+        assert!(!gates.is_command_enabled_for("settings logs", user_id, &[]));
+        assert!(gates.is_command_enabled_for("settings", user_id, &[]));
+        assert!(gates.is_command_enabled_for("settings logsx", user_id, &[]));
+
+        assert!(gates.remove("settings logs").is_some());
+        assert!(gates.is_command_enabled_for("settings logs member", user_id, &[]));
+    }
+
+    #[test]
+    fn should_apply_settings_changes() {
+        let rollout = |name: &str, percentage| {
+            FeatureRollout::builder()
+                .name(name)
+                .percentage(percentage)
+                .build()
+        };
+
+        let previous = [rollout("emoji", 0), rollout("payer pay", 0)];
+        let gates = FeatureGates::from_settings(&previous);
+
+        // changed with `/admin feature` and left as is in the settings
+        gates.set(FeatureGate::new("emoji", 100));
+
+        let current = [rollout("emoji", 0), rollout("payer pay", 50)];
+        gates.apply_settings(&previous, &current);
+        assert_eq!(gates.get("emoji").map(|v| v.percentage), Some(100));
+        assert_eq!(gates.get("payer pay").map(|v| v.percentage), Some(50));
+
+        gates.apply_settings(&current, &current[1..]);
+        assert!(gates.get("emoji").is_none());
+    }
+}
@@ -0,0 +1,233 @@
+use dashmap::DashMap;
+use eden_schema::types::GuildProfileChange;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use tracing::{debug, instrument, trace, warn};
+use twilight_model::guild::{Guild, PartialGuild};
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+use twilight_model::util::ImageHash;
+use twilight_util::builder::embed::{EmbedFieldBuilder, ImageSource};
+
+use crate::events::EventContext;
+use crate::interactions::embeds;
+use crate::Bot;
+
+/// Parts of the guild's profile that are tracked for changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuildProfile {
+    pub name: String,
+    pub icon: Option<ImageHash>,
+    pub banner: Option<ImageHash>,
+    pub description: Option<String>,
+}
+
+impl GuildProfile {
+    #[must_use]
+    pub fn from_guild(guild: &Guild) -> Self {
+        Self {
+            name: guild.name.clone(),
+            icon: guild.icon,
+            banner: guild.banner,
+            description: guild.description.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn from_partial_guild(guild: &PartialGuild) -> Self {
+        Self {
+            name: guild.name.clone(),
+            icon: guild.icon,
+            banner: guild.banner,
+            description: guild.description.clone(),
+        }
+    }
+
+    /// Compares the profile against the newer one and returns
+    /// every field that has changed.
+    #[must_use]
+    pub fn diff(&self, new: &Self) -> Vec<ProfileChange> {
+        let mut changes = Vec::new();
+        if self.name != new.name {
+            changes.push(ProfileChange {
+                field: "name",
+                before: Some(self.name.clone()),
+                after: Some(new.name.clone()),
+            });
+        }
+        if self.icon != new.icon {
+            changes.push(ProfileChange {
+                field: "icon",
+                before: self.icon.map(|v| v.to_string()),
+                after: new.icon.map(|v| v.to_string()),
+            });
+        }
+        if self.banner != new.banner {
+            changes.push(ProfileChange {
+                field: "banner",
+                before: self.banner.map(|v| v.to_string()),
+                after: new.banner.map(|v| v.to_string()),
+            });
+        }
+        if self.description != new.description {
+            changes.push(ProfileChange {
+                field: "description",
+                before: self.description.clone(),
+                after: new.description.clone(),
+            });
+        }
+        changes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileChange {
+    pub field: &'static str,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Keeps the last known profile of every guild since `GUILD_UPDATE`
+/// events only contain the updated guild.
+#[derive(Debug, Default)]
+pub struct GuildProfiles {
+    profiles: DashMap<Id<GuildMarker>, GuildProfile>,
+}
+
+impl GuildProfiles {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn store(&self, guild_id: Id<GuildMarker>, profile: GuildProfile) {
+        self.profiles.insert(guild_id, profile);
+    }
+
+    /// Replaces the last known profile of the guild and returns
+    /// the changes made since then.
+    ///
+    /// It returns nothing if the guild's profile is not known yet.
+    #[must_use]
+    pub fn update(&self, guild_id: Id<GuildMarker>, profile: GuildProfile) -> Vec<ProfileChange> {
+        match self.profiles.insert(guild_id, profile.clone()) {
+            Some(old) => old.diff(&profile),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Logs changes of the local guild's profile to the server log channel.
+#[instrument(skip_all, fields(%guild.id))]
+pub async fn on_guild_update(ctx: &EventContext, guild: &PartialGuild) {
+    if !ctx.bot.is_local_guild(&guild.id) {
+        return;
+    }
+
+    let changes = ctx
+        .bot
+        .guild_profiles
+        .update(guild.id, GuildProfile::from_partial_guild(guild));
+
+    if changes.is_empty() {
+        trace!("no profile changes found, skipping");
+        return;
+    }
+
+    if let Err(error) = report(&ctx.bot, guild, &changes).await {
+        warn!(%error, "could not report guild profile changes");
+    }
+}
+
+async fn report(bot: &Bot, guild: &PartialGuild, changes: &[ProfileChange]) -> Result<()> {
+    debug!("recording {} guild profile change(s)", changes.len());
+
+    let mut conn = bot.db_write().await?;
+    for change in changes {
+        GuildProfileChange::record(
+            &mut conn,
+            guild.id,
+            change.field,
+            change.before.as_deref(),
+            change.after.as_deref(),
+        )
+        .await?;
+    }
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    let settings = bot.guild_settings(guild.id).await?;
+
+    let mut embed =
+        embeds::builders::with_emoji('📝', "Server profile updated").color(embeds::colors::GREEN);
+
+    for change in changes {
+        let before = change.before.as_deref().unwrap_or("*None*");
+        let after = change.after.as_deref().unwrap_or("*None*");
+        embed = embed.field(EmbedFieldBuilder::new(
+            capitalize(change.field),
+            format!("**Before**: {before}\n**After**: {after}"),
+        ));
+    }
+
+    if let Some(icon) = guild.icon {
+        let url = format!("https://cdn.discordapp.com/icons/{}/{icon}.png", guild.id);
+        match ImageSource::url(url) {
+            Ok(source) => embed = embed.thumbnail(source),
+            Err(error) => warn!(%error, "could not use guild icon as thumbnail"),
+        }
+    }
+
+    crate::local_guild::channel::send_server_log(bot, &settings, embed.build()).await?;
+    Ok(())
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> GuildProfile {
+        GuildProfile {
+            name: name.into(),
+            icon: None,
+            banner: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn should_diff_changed_fields_only() {
+        let old = profile("Eden");
+        assert!(old.diff(&old.clone()).is_empty());
+
+        let new = GuildProfile {
+            description: Some("A cozy place".into()),
+            ..profile("Eden City")
+        };
+        let changes = old.diff(&new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "name");
+        assert_eq!(changes[0].before.as_deref(), Some("Eden"));
+        assert_eq!(changes[1].field, "description");
+        assert_eq!(changes[1].before, None);
+    }
+
+    #[test]
+    fn should_only_report_known_profiles() {
+        let profiles = GuildProfiles::new();
+        let guild_id = Id::new(1);
+
+        assert!(profiles.update(guild_id, profile("Eden")).is_empty());
+        assert_eq!(profiles.update(guild_id, profile("Eden City")).len(), 1);
+    }
+}
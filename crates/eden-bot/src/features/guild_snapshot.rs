@@ -0,0 +1,87 @@
+use eden_schema::forms::{InsertCommandAliasForm, InsertGuildSnapshotForm};
+use eden_schema::types::{
+    CommandAlias, GuildSettings, GuildSnapshot, GuildSnapshotCommandAlias, GuildSnapshotData,
+};
+use eden_utils::Result;
+use tracing::{debug, instrument};
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+/// Takes a snapshot of the guild's data before a risky admin
+/// operation so it can be restored with `/admin restore-snapshot`.
+///
+/// It should be called within the same transaction as the operation
+/// so the snapshot is discarded if the operation fails.
+#[instrument(skip(conn))]
+pub async fn take(
+    conn: &mut sqlx::PgConnection,
+    guild_id: Id<GuildMarker>,
+    created_by: Id<UserMarker>,
+    reason: &str,
+) -> Result<GuildSnapshot> {
+    // this locks the row until the transaction ends
+    let settings = GuildSettings::upsert(conn, guild_id).await?;
+    let command_aliases = CommandAlias::list(conn, guild_id)
+        .await?
+        .into_iter()
+        .map(|alias| GuildSnapshotCommandAlias {
+            name: alias.name,
+            created_by: alias.created_by,
+            command: alias.command,
+            arguments: alias.arguments,
+        })
+        .collect();
+
+    let data = GuildSnapshotData {
+        settings: settings.data,
+        command_aliases,
+        ..Default::default()
+    };
+
+    let form = InsertGuildSnapshotForm::builder()
+        .guild_id(guild_id)
+        .created_by(created_by)
+        .reason(reason)
+        .data(&data)
+        .build();
+
+    let snapshot = GuildSnapshot::insert(conn, form).await?;
+    debug!("took guild snapshot {}", snapshot.id);
+
+    Ok(snapshot)
+}
+
+/// Replaces the guild's data with the data kept in the snapshot.
+///
+/// It returns the restored command aliases so they can be
+/// reloaded into [`Bot::command_aliases`](crate::Bot).
+#[instrument(skip_all, fields(snapshot.id = %snapshot.id))]
+pub async fn restore(
+    conn: &mut sqlx::PgConnection,
+    snapshot: &GuildSnapshot,
+) -> Result<Vec<CommandAlias>> {
+    let guild_id = snapshot.guild_id;
+    let data = &snapshot.data;
+
+    debug!("restoring guild settings");
+    GuildSettings::upsert(conn, guild_id).await?;
+    GuildSettings::update(conn, guild_id, &data.settings).await?;
+
+    debug!("restoring {} command alias(es)", data.command_aliases.len());
+    CommandAlias::delete_all(conn, guild_id).await?;
+
+    let mut aliases = Vec::with_capacity(data.command_aliases.len());
+    for alias in &data.command_aliases {
+        let form = InsertCommandAliasForm::builder()
+            .guild_id(guild_id)
+            .name(&alias.name)
+            .created_by(alias.created_by)
+            .command(&alias.command)
+            .arguments(alias.arguments.clone())
+            .build();
+
+        aliases.push(CommandAlias::upsert(conn, form).await?);
+    }
+
+    Ok(aliases)
+}
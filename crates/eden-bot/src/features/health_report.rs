@@ -0,0 +1,331 @@
+use chrono::{DateTime, Utc};
+use eden_settings::{AlertClass, AlertSeverity};
+use eden_utils::error::exts::*;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{debug, instrument};
+use twilight_model::channel::message::Embed;
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::features::father_belt::FatherBeltCounts;
+use crate::interactions::embeds;
+use crate::util::alerts::Alert;
+use crate::Bot;
+
+/// Counters of the bot's activity since it started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub commands_served: u64,
+    pub task_failures: u64,
+    pub shard_reconnects: u64,
+    pub interactions_cleared: u64,
+    pub db_acquires: u64,
+    pub db_acquire_wait_micros: u64,
+    pub slow_db_acquires: u64,
+    pub father_belt: FatherBeltCounts,
+}
+
+impl MetricsSnapshot {
+    /// Gets the counters accumulated after the `previous` snapshot.
+    #[must_use]
+    pub fn since(&self, previous: &Self) -> Self {
+        Self {
+            taken_at: self.taken_at,
+            commands_served: self
+                .commands_served
+                .saturating_sub(previous.commands_served),
+            task_failures: self.task_failures.saturating_sub(previous.task_failures),
+            shard_reconnects: self
+                .shard_reconnects
+                .saturating_sub(previous.shard_reconnects),
+            interactions_cleared: self
+                .interactions_cleared
+                .saturating_sub(previous.interactions_cleared),
+            db_acquires: self.db_acquires.saturating_sub(previous.db_acquires),
+            db_acquire_wait_micros: self
+                .db_acquire_wait_micros
+                .saturating_sub(previous.db_acquire_wait_micros),
+            slow_db_acquires: self
+                .slow_db_acquires
+                .saturating_sub(previous.slow_db_acquires),
+            father_belt: self.father_belt.since(&previous.father_belt),
+        }
+    }
+
+    /// Average time spent waiting for a database connection.
+    ///
+    /// It returns zero if no connections have been obtained.
+    #[must_use]
+    pub fn average_db_acquire_wait(&self) -> Duration {
+        self.db_acquire_wait_micros
+            .checked_div(self.db_acquires)
+            .map_or(Duration::ZERO, Duration::from_micros)
+    }
+}
+
+/// Keeps track of the bot's activity to be summarized in the
+/// periodic health report posted to the alert channel.
+#[derive(Debug)]
+pub struct BotMetrics {
+    started_at: DateTime<Utc>,
+    commands_served: AtomicU64,
+    shard_reconnects: AtomicU64,
+    interactions_cleared: AtomicU64,
+    db_acquires: AtomicU64,
+    db_acquire_wait_micros: AtomicU64,
+    slow_db_acquires: AtomicU64,
+    last_report: Mutex<Option<MetricsSnapshot>>,
+}
+
+impl BotMetrics {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            commands_served: AtomicU64::new(0),
+            shard_reconnects: AtomicU64::new(0),
+            interactions_cleared: AtomicU64::new(0),
+            db_acquires: AtomicU64::new(0),
+            db_acquire_wait_micros: AtomicU64::new(0),
+            slow_db_acquires: AtomicU64::new(0),
+            last_report: Mutex::new(None),
+        }
+    }
+
+    #[must_use]
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    pub fn record_command(&self) {
+        self.commands_served.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a shard that connected to the gateway
+    /// again after it got disconnected.
+    pub fn record_shard_reconnect(&self) {
+        self.shard_reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records stateful command interactions that have been
+    /// cleared after being inactive for too long.
+    pub fn record_interactions_cleared(&self, amount: u64) {
+        self.interactions_cleared
+            .fetch_add(amount, Ordering::Relaxed);
+    }
+
+    /// Records how long it took to obtain a connection from
+    /// the database pool.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn record_db_acquire(&self, waited: Duration, is_slow: bool) {
+        self.db_acquires.fetch_add(1, Ordering::Relaxed);
+        self.db_acquire_wait_micros
+            .fetch_add(waited.as_micros() as u64, Ordering::Relaxed);
+
+        if is_slow {
+            self.slow_db_acquires.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Takes a snapshot of the counters. Task failures and father belt
+    /// counters are counted somewhere else, so they have to be given
+    /// from there.
+    #[must_use]
+    pub fn snapshot(
+        &self,
+        task_failures: u64,
+        father_belt: FatherBeltCounts,
+        now: DateTime<Utc>,
+    ) -> MetricsSnapshot {
+        MetricsSnapshot {
+            taken_at: now,
+            commands_served: self.commands_served.load(Ordering::Relaxed),
+            task_failures,
+            shard_reconnects: self.shard_reconnects.load(Ordering::Relaxed),
+            interactions_cleared: self.interactions_cleared.load(Ordering::Relaxed),
+            db_acquires: self.db_acquires.load(Ordering::Relaxed),
+            db_acquire_wait_micros: self.db_acquire_wait_micros.load(Ordering::Relaxed),
+            slow_db_acquires: self.slow_db_acquires.load(Ordering::Relaxed),
+            father_belt,
+        }
+    }
+}
+
+impl Default for BotMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders the health report of the bot's activity from `since`.
+#[must_use]
+pub fn render(
+    started_at: DateTime<Utc>,
+    since: DateTime<Utc>,
+    activity: &MetricsSnapshot,
+    db_connections_in_use: u32,
+) -> Embed {
+    let description = format!(
+        "Summary of my activity since {}.",
+        discord_timestamp(since, TimestampStyle::LongDateTime)
+    );
+
+    embeds::builders::with_emoji('🩺', "Bot health report")
+        .description(description)
+        .field(
+            EmbedFieldBuilder::new(
+                "Online since",
+                discord_timestamp(started_at, TimestampStyle::Relative),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new("Commands served", activity.commands_served.to_string())
+                .inline(),
+        )
+        .field(EmbedFieldBuilder::new("Task failures", activity.task_failures.to_string()).inline())
+        .field(
+            EmbedFieldBuilder::new("Shard reconnects", activity.shard_reconnects.to_string())
+                .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(
+                "Inactive interactions cleared",
+                activity.interactions_cleared.to_string(),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(
+                "Database connections in use",
+                db_connections_in_use.to_string(),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(
+                "Average database wait",
+                format!("{} ms", activity.average_db_acquire_wait().as_millis()),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new("Slow database waits", activity.slow_db_acquires.to_string())
+                .inline(),
+        )
+        .field(EmbedFieldBuilder::new(
+            "Father belt",
+            render_father_belt(&activity.father_belt),
+        ))
+        .build()
+}
+
+fn render_father_belt(counts: &FatherBeltCounts) -> String {
+    format!(
+        "{} message(s) scanned, {} censored, {} scream alert(s), {} introduction(s)",
+        counts.messages_scanned,
+        counts.censorship_triggers,
+        counts.scream_alerts,
+        counts.introductions
+    )
+}
+
+/// Posts the health report of the bot's activity since the last
+/// report (or since the bot started) as an alert.
+#[instrument(skip_all)]
+pub async fn send(bot: &Bot, now: DateTime<Utc>) -> Result<()> {
+    let metrics = &bot.metrics;
+    let father_belt = bot.father_belt_metrics.total();
+    let current = metrics.snapshot(bot.queue.total_failures(), father_belt, now);
+
+    let mut last_report = metrics.last_report.lock().await;
+    let (since, activity) = match last_report.as_ref() {
+        Some(previous) => (previous.taken_at, current.since(previous)),
+        None => (metrics.started_at, current),
+    };
+
+    let embeds = [render(
+        metrics.started_at,
+        since,
+        &activity,
+        bot.db_connections_in_use(),
+    )];
+    let alert = Alert {
+        embeds: &embeds,
+        ..Alert::new(AlertClass::System, AlertSeverity::Info)
+    };
+
+    debug!("sending health report");
+    bot.alerter
+        .send(alert)
+        .await
+        .attach_printable("could not send health report")?;
+
+    *last_report = Some(current);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn should_count_activity_since_previous_snapshot() {
+        let metrics = BotMetrics::new();
+        let now = metrics.started_at();
+
+        metrics.record_command();
+        metrics.record_shard_reconnect();
+        metrics.record_interactions_cleared(1);
+        metrics.record_db_acquire(Duration::from_millis(2), false);
+
+        let father_belt = FatherBeltCounts {
+            messages_scanned: 10,
+            censorship_triggers: 1,
+            ..Default::default()
+        };
+        let previous = metrics.snapshot(2, father_belt, now);
+
+        metrics.record_command();
+        metrics.record_command();
+        metrics.record_interactions_cleared(3);
+        metrics.record_db_acquire(Duration::from_millis(10), false);
+        metrics.record_db_acquire(Duration::from_millis(20), true);
+        let father_belt = FatherBeltCounts {
+            messages_scanned: 25,
+            censorship_triggers: 1,
+            scream_alerts: 2,
+            introductions: 0,
+        };
+        let current = metrics.snapshot(5, father_belt, now + TimeDelta::weeks(1));
+
+        assert_eq!(
+            current.since(&previous),
+            MetricsSnapshot {
+                taken_at: now + TimeDelta::weeks(1),
+                commands_served: 2,
+                task_failures: 3,
+                shard_reconnects: 0,
+                interactions_cleared: 3,
+                db_acquires: 2,
+                db_acquire_wait_micros: 30_000,
+                slow_db_acquires: 1,
+                father_belt: FatherBeltCounts {
+                    messages_scanned: 15,
+                    censorship_triggers: 0,
+                    scream_alerts: 2,
+                    introductions: 0,
+                },
+            }
+        );
+        assert_eq!(
+            current.since(&previous).average_db_acquire_wait(),
+            Duration::from_millis(15)
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::Utc;
+use eden_schema::types::Instance;
+use eden_settings::Coordination;
+use eden_utils::{error::exts::*, Result};
+use sqlx::Connection;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Notify;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::Bot;
+
+/// Instances that missed this many heartbeats in a row are
+/// deleted from the `instances` table by the leader.
+const MISSED_HEARTBEATS_UNTIL_INACTIVE: i32 = 10;
+
+/// Whether this instance is the leader among the Eden instances
+/// sharing the same database.
+///
+/// Only the leader runs recurring tasks and registers commands. Every
+/// instance is the leader if `coordination.enabled` is turned off.
+#[derive(Debug)]
+pub struct Leadership {
+    id: Uuid,
+    name: String,
+    elected: Notify,
+    is_leader: AtomicBool,
+}
+
+impl Leadership {
+    #[must_use]
+    pub fn new(settings: &Coordination) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: settings.instance_name(),
+            elected: Notify::new(),
+            is_leader: AtomicBool::new(!settings.enabled),
+        }
+    }
+
+    /// ID of this instance in the `instances` table.
+    #[must_use]
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::Relaxed)
+    }
+
+    /// Waits until this instance becomes the leader. Elections made
+    /// while nobody is waiting are still received by the next call.
+    pub async fn elected(&self) {
+        self.elected.notified().await;
+    }
+
+    fn set_leader(&self, bot: &Bot, value: bool) {
+        let previous = self.is_leader.swap(value, Ordering::Relaxed);
+        bot.queue.set_runs_recurring_tasks(value);
+
+        if previous == value {
+            return;
+        }
+
+        if value {
+            info!("instance {:?} is now the leader", self.name);
+            self.elected.notify_one();
+        } else {
+            warn!("instance {:?} is no longer the leader", self.name);
+        }
+    }
+}
+
+/// Elects the leader with the other instances sharing the same database
+/// and keeps this instance's heartbeat until Eden shuts down.
+///
+/// The leader holds an advisory lock on its own connection, so the
+/// leadership is given up once the connection is closed (like if the
+/// leader crashed) and one of the followers takes over.
+#[instrument(skip_all)]
+pub async fn run(bot: Bot) {
+    let settings = &bot.settings.coordination;
+    if !settings.enabled {
+        return;
+    }
+
+    let interval = settings.heartbeat_interval.get();
+    let mut lock = None;
+    loop {
+        if let Err(error) = tick(&bot, &mut lock).await {
+            warn!(error = %error.anonymize(), "could not coordinate with other instances");
+        }
+
+        tokio::select! {
+            () = tokio::time::sleep(interval) => {}
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+    }
+
+    if let Err(error) = leave(&bot, lock).await {
+        warn!(error = %error.anonymize(), "could not leave the other instances");
+    }
+}
+
+async fn tick(bot: &Bot, lock: &mut Option<sqlx::PgConnection>) -> Result<()> {
+    let leadership = &bot.leadership;
+
+    // the connection holding the lock may be closed by the database
+    if let Some(conn) = lock.as_mut()
+        && conn.ping().await.is_err()
+    {
+        *lock = None;
+        leadership.set_leader(bot, false);
+    }
+
+    if lock.is_none() {
+        // the lock must not be returned to the pool with the connection
+        let mut conn = bot.db_read().await?.detach();
+        if Instance::try_lead(&mut conn).await? {
+            *lock = Some(conn);
+            leadership.set_leader(bot, true);
+        } else {
+            debug!("another instance is the leader");
+            leadership.set_leader(bot, false);
+        }
+    }
+
+    let now = Utc::now();
+    let is_leader = leadership.is_leader();
+
+    let mut conn = bot.db_write().await?;
+    Instance::heartbeat(&mut conn, leadership.id, &leadership.name, is_leader, now).await?;
+
+    if is_leader {
+        let interval = bot.settings.coordination.heartbeat_interval.to_time_delta();
+        let threshold = interval * MISSED_HEARTBEATS_UNTIL_INACTIVE;
+        let deleted = Instance::delete_inactive(&mut conn, now - threshold).await?;
+        if deleted > 0 {
+            debug!("deleted {deleted} inactive instance(s)");
+        }
+    }
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")
+}
+
+async fn leave(bot: &Bot, lock: Option<sqlx::PgConnection>) -> Result<()> {
+    if let Some(mut conn) = lock {
+        Instance::resign(&mut conn).await?;
+        debug!("gave up the leadership");
+    }
+
+    let mut conn = bot.db_read().await?;
+    Instance::delete(&mut conn, bot.leadership.id).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_lead_without_coordination() {
+        let leadership = Leadership::new(&Coordination::default());
+        assert!(leadership.is_leader());
+
+        let settings = Coordination::builder().enabled(true).build();
+        assert!(!Leadership::new(&settings).is_leader());
+    }
+}
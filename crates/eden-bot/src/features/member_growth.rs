@@ -0,0 +1,126 @@
+use chrono::Utc;
+use eden_schema::types::GuildMemberCount;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use std::fmt::Write as _;
+use tracing::{debug, instrument};
+
+use crate::interactions::response_cache::CachedCommand;
+use crate::util::http::request_for_model;
+use crate::Bot;
+
+/// Characters used to draw sparklines from the lowest to the highest value.
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Records today's member count of the local guild.
+///
+/// Recording it multiple times in a day keeps the most recent count.
+#[instrument(skip_all)]
+pub async fn record(bot: &Bot) -> Result<()> {
+    let guild_id = bot.settings.bot.local_guild.id;
+    let guild = request_for_model(bot, bot.http.guild(guild_id).with_counts(true))
+        .await
+        .attach_printable("could not get member count of the local guild")?;
+
+    let Some(members) = guild.approximate_member_count.or(guild.member_count) else {
+        debug!("Discord did not give the member count of the local guild");
+        return Ok(());
+    };
+
+    let mut conn = bot.db_write().await?;
+    let today = Utc::now().date_naive();
+    let members = i64::try_from(members).unwrap_or(i64::MAX);
+    GuildMemberCount::record(&mut conn, guild_id, today, members).await?;
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    debug!("recorded {members} member(s) of the local guild");
+    bot.response_cache.bust(CachedCommand::MemberGrowth);
+    Ok(())
+}
+
+/// Draws a sparkline out of `values` with one character per value.
+#[must_use]
+pub fn sparkline(values: &[i64]) -> String {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+
+    let range = max - min;
+    let last = SPARKS.len() - 1;
+    values
+        .iter()
+        .map(|value| {
+            if range == 0 {
+                return SPARKS[SPARKS.len() / 2];
+            }
+            // 7 is the index of the highest spark
+            let index = usize::try_from((value - min) * 7 / range).unwrap_or(last);
+            SPARKS[index.min(last)]
+        })
+        .collect()
+}
+
+/// Picks at most `limit` evenly spaced entries from `entries`,
+/// always including the first and the last entry (if `limit` allows).
+#[must_use]
+pub fn sample<T>(entries: &[T], limit: usize) -> Vec<&T> {
+    if entries.len() <= limit {
+        return entries.iter().collect();
+    }
+    if limit < 2 {
+        return entries.last().into_iter().collect();
+    }
+
+    let last = entries.len() - 1;
+    (0..limit)
+        .map(|i| &entries[i * last / (limit - 1)])
+        .collect()
+}
+
+/// Renders daily member counts as a CSV file.
+#[must_use]
+pub fn to_csv(counts: &[GuildMemberCount]) -> String {
+    let mut output = String::from("day,members\n");
+    for count in counts {
+        let _ = writeln!(output, "{},{}", count.day, count.members);
+    }
+    output
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use twilight_model::id::Id;
+
+    #[test]
+    fn should_draw_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[5, 5, 5]), "▅▅▅");
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▄█");
+        assert_eq!(sparkline(&[100, 93]), "█▁");
+    }
+
+    #[test]
+    fn should_sample_evenly() {
+        let entries = (0..10).collect::<Vec<_>>();
+        assert_eq!(sample(&entries, 20).len(), 10);
+        assert_eq!(sample(&entries, 4), vec![&0, &3, &6, &9]);
+        assert_eq!(sample(&entries, 2), vec![&0, &9]);
+    }
+
+    #[test]
+    fn should_render_csv() {
+        let counts = vec![GuildMemberCount {
+            guild_id: Id::new(1),
+            day: NaiveDate::from_ymd_opt(2024, 8, 26).unwrap(),
+            members: 120,
+        }];
+        assert_eq!(to_csv(&counts), "day,members\n2024-08-26,120\n");
+    }
+}
@@ -1 +1,73 @@
+use eden_tasks::queue_worker::TaskEvent;
+use twilight_gateway::ShardId;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::shard::OutageWindow;
+
+pub mod anti_spam;
+pub mod auto_role;
+pub mod blacklist;
+pub mod bulk_role;
+pub mod command_alias;
+pub mod emoji;
 pub mod father_belt;
+pub mod feature_gate;
+pub mod guild_profile;
+pub mod guild_snapshot;
+pub mod health_report;
+pub mod leader;
+pub mod member_growth;
+pub mod outage;
+pub mod payer_queue;
+pub mod payer_roles;
+pub mod preferences;
+pub mod presence;
+pub mod quiet_hours;
+pub mod raid;
+pub mod retention;
+pub mod scheduled_events;
+pub mod settings_reload;
+pub mod task_events;
+pub mod translation;
+pub mod undo;
+pub mod verification;
+pub mod voice_stats;
+
+/// Maximum amount of unreceived events kept per subscriber
+/// in [`Bot::events`](crate::Bot).
+pub const EVENT_BUS_CAPACITY: usize = 64;
+
+/// Events that features publish to signal each other through
+/// the bot's event bus.
+///
+/// Changes to Eden's internal state are published as well so tests
+/// can assert on what happened without looking into the internals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeatureEvent {
+    /// A command has finished running, whether it succeeded or not.
+    CommandCompleted {
+        /// Full path of the command (like `admin alias add`).
+        name: String,
+        guild_id: Option<Id<GuildMarker>>,
+        invoker_id: Id<UserMarker>,
+        success: bool,
+    },
+    /// Eden took a moderation action against a member.
+    ModerationAction {
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    },
+    /// Discord has recovered from an outage.
+    OutageRecovered(OutageWindow),
+    /// Reloaded settings have been applied.
+    SettingsReloaded {
+        /// Whether some of the changes are only applied after
+        /// Eden restarts.
+        requires_restart: bool,
+    },
+    /// A shard has connected (or reconnected) to the gateway.
+    ShardReady(ShardId),
+    /// The queue worker has finished performing a task.
+    TaskFinished(TaskEvent),
+}
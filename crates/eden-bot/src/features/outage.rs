@@ -0,0 +1,67 @@
+use eden_settings::{AlertClass, AlertSeverity};
+use eden_utils::error::exts::*;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::Result;
+use tracing::{debug, instrument, warn};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::features::voice_stats::format_duration;
+use crate::features::FeatureEvent;
+use crate::interactions::embeds;
+use crate::shard::OutageWindow;
+use crate::util::alerts::Alert;
+use crate::util::event_bus::Subscription;
+use crate::Bot;
+
+/// Listens for recovered Discord outages and posts one status
+/// message per outage to the alert channel until Eden shuts down.
+#[instrument(skip_all)]
+pub async fn listen(bot: Bot, mut events: Subscription<FeatureEvent>) {
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Some(FeatureEvent::OutageRecovered(window)) => {
+                    if let Err(error) = report_recovery(&bot, &window).await {
+                        warn!(error = %error.anonymize(), "could not report recovered outage");
+                    }
+                }
+                Some(..) => {}
+                None => break,
+            },
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+    }
+}
+
+async fn report_recovery(bot: &Bot, window: &OutageWindow) -> Result<()> {
+    let embed = embeds::builders::with_emoji('✅', "Discord has recovered from an outage")
+        .color(embeds::colors::GREEN)
+        .description(
+            "Eden may have missed some events or failed to respond to \
+            some interactions during the outage.",
+        )
+        .field(EmbedFieldBuilder::new(
+            "Started",
+            discord_timestamp(window.started_at, TimestampStyle::ShortDateTime),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "Ended",
+            discord_timestamp(window.ended_at, TimestampStyle::ShortDateTime),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "Duration",
+            format_duration(window.duration().num_seconds()),
+        ))
+        .build();
+
+    let embeds = [embed];
+    let alert = Alert {
+        embeds: &embeds,
+        ..Alert::new(AlertClass::System, AlertSeverity::Info)
+    };
+
+    debug!("sending outage recovery status");
+    bot.alerter.send(alert).await?;
+
+    Ok(())
+}
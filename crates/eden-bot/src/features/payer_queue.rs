@@ -0,0 +1,494 @@
+use chrono::{DateTime, Utc};
+use eden_schema::forms::InsertPayerForm;
+use eden_schema::types::{Payer, PayerApplication};
+use eden_utils::error::exts::*;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::Result;
+use std::fmt::Write;
+use tracing::{debug, instrument, trace, warn};
+use twilight_mention::Mention;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::channel::message::{AllowedMentions, MessageFlags};
+use twilight_model::guild::Permissions;
+use twilight_model::http::interaction::InteractionResponseData;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::embed::{EmbedFieldBuilder, EmbedFooterBuilder};
+use twilight_util::builder::InteractionResponseDataBuilder;
+use twilight_util::snowflake::Snowflake;
+use uuid::Uuid;
+
+use crate::features::preferences;
+use crate::interactions::{embeds, InteractionContext};
+use crate::util::http::request_for_model;
+use crate::Bot;
+
+/// Prefix of all custom IDs of the payer application queue buttons.
+pub const CUSTOM_ID_PREFIX: &str = "payer_queue";
+
+/// Deny reason given to applicants that are rejected from the queue.
+const DEFAULT_DENY_REASON: &str = "Rejected by the server administrators";
+
+const NOT_ALLOWED_MSG: &str = "**You're not allowed to review payer applications.**";
+const ALREADY_HANDLED_MSG: &str = "**This application has already been handled by another admin.**";
+const EMPTY_QUEUE_MSG: &str = "**There are no pending payer applications. 🎉**";
+
+/// Actions that can be taken on the pending application shown
+/// in the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Accept,
+    Reject,
+    Interview,
+}
+
+impl Action {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Accept => "accept",
+            Self::Reject => "reject",
+            Self::Interview => "interview",
+        }
+    }
+}
+
+/// What the pressed button of the queue wants to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueueButton {
+    /// Takes an action on the application then shows the
+    /// application at `page` afterwards.
+    Action {
+        action: Action,
+        application_id: Uuid,
+        page: usize,
+    },
+    /// Shows the application at `page`.
+    Page(usize),
+}
+
+fn make_action_custom_id(action: Action, application_id: Uuid, page: usize) -> String {
+    format!(
+        "{CUSTOM_ID_PREFIX}:{}:{application_id}:{page}",
+        action.name()
+    )
+}
+
+fn make_page_custom_id(page: usize) -> String {
+    format!("{CUSTOM_ID_PREFIX}:page:{page}")
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<QueueButton> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != CUSTOM_ID_PREFIX {
+        return None;
+    }
+
+    let action = match parts.next()? {
+        "accept" => Action::Accept,
+        "reject" => Action::Reject,
+        "interview" => Action::Interview,
+        "page" => return parts.next()?.parse().ok().map(QueueButton::Page),
+        _ => return None,
+    };
+
+    let application_id = parts.next()?.parse().ok()?;
+    let page = parts.next()?.parse().ok()?;
+    Some(QueueButton::Action {
+        action,
+        application_id,
+        page,
+    })
+}
+
+/// Renders the pending payer application at `page` of the queue
+/// along with the applicant's history and the review buttons.
+///
+/// Pending applications are sorted from the oldest to the newest
+/// so admins work through the queue in the same order. If `page`
+/// is out of bounds, the last pending application is shown.
+#[instrument(skip(bot))]
+pub async fn render(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    page: usize,
+) -> Result<InteractionResponseData> {
+    let mut conn = bot.db_read().await?;
+    let total =
+        usize::try_from(PayerApplication::pending_total(&mut conn).await?).unwrap_or_default();
+
+    if total == 0 {
+        return Ok(InteractionResponseDataBuilder::new()
+            .content(EMPTY_QUEUE_MSG)
+            .components(Vec::new())
+            .embeds(Vec::new())
+            .build());
+    }
+
+    let page = page.min(total - 1);
+    let offset = i64::try_from(page).unwrap_or(i64::MAX);
+    let Some(application) = PayerApplication::pending(&mut conn, offset, 1)
+        .await?
+        .into_iter()
+        .next()
+    else {
+        // somebody else emptied the queue before we could fetch it
+        return Ok(InteractionResponseDataBuilder::new()
+            .content(EMPTY_QUEUE_MSG)
+            .components(Vec::new())
+            .embeds(Vec::new())
+            .build());
+    };
+
+    let blacklisted = bot.blacklist.contains(bot, application.user_id).await?;
+    let joined_at = joined_at(bot, guild_id, application.user_id).await;
+
+    let relative = |dt| discord_timestamp(dt, TimestampStyle::Relative);
+    let created_at =
+        DateTime::from_timestamp_millis(application.user_id.timestamp()).unwrap_or_default();
+
+    let mut history = String::new();
+    let _ = writeln!(history, "Account created: {}", relative(created_at));
+    let joined_at = joined_at.map_or_else(|| String::from("*not a member*"), relative);
+    let _ = writeln!(history, "Joined the server: {joined_at}");
+    let _ = writeln!(history, "Applied: {}", relative(application.created_at));
+    if let Some(updated_at) = application.updated_at {
+        let _ = writeln!(history, "Last edited: {}", relative(updated_at));
+    }
+    if blacklisted {
+        history.push_str("**Blacklisted from using Eden**\n");
+    }
+
+    let interview = match (
+        application.interviewer_id,
+        application.interview_requested_at,
+    ) {
+        (Some(interviewer_id), Some(requested_at)) => format!(
+            "Requested by {} {}",
+            interviewer_id.mention(),
+            relative(requested_at)
+        ),
+        _ => String::from("Not requested"),
+    };
+
+    let footer = format!(
+        "Application {} of {} • ID: {}",
+        page + 1,
+        total,
+        application.id
+    );
+    let embed = embeds::builders::with_emoji('📝', "Pending payer application")
+        .description(format!(
+            "**{}** ({}) wants to be a monthly contributor.",
+            application.name,
+            application.user_id.mention()
+        ))
+        .field(EmbedFieldBuilder::new("Java username", &application.java_username).inline())
+        .field(
+            EmbedFieldBuilder::new(
+                "Bedrock username",
+                application.bedrock_username.as_deref().unwrap_or("*none*"),
+            )
+            .inline(),
+        )
+        .field(EmbedFieldBuilder::new("Answer", &application.answer))
+        .field(EmbedFieldBuilder::new("History", history))
+        .field(EmbedFieldBuilder::new("Interview", interview))
+        .footer(EmbedFooterBuilder::new(footer))
+        .build();
+
+    let components = vec![
+        Component::ActionRow(ActionRow {
+            components: vec![
+                action_button(
+                    &application,
+                    Action::Accept,
+                    page,
+                    "Accept",
+                    ButtonStyle::Success,
+                ),
+                action_button(
+                    &application,
+                    Action::Reject,
+                    page,
+                    "Reject",
+                    ButtonStyle::Danger,
+                ),
+                action_button(
+                    &application,
+                    Action::Interview,
+                    page,
+                    "Interview",
+                    ButtonStyle::Primary,
+                ),
+            ],
+        }),
+        Component::ActionRow(ActionRow {
+            components: vec![
+                page_button(page.saturating_sub(1), "Previous", page == 0),
+                page_button(page + 1, "Next", page + 1 >= total),
+            ],
+        }),
+    ];
+
+    Ok(InteractionResponseDataBuilder::new()
+        .allowed_mentions(AllowedMentions::default())
+        .content(String::new())
+        .embeds([embed])
+        .components(components)
+        .build())
+}
+
+/// Gets when the applicant joined the guild as a UNIX timestamp,
+/// if the applicant is still a member of the guild.
+async fn joined_at(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+) -> Option<DateTime<Utc>> {
+    match request_for_model(bot, bot.http.guild_member(guild_id, user_id)).await {
+        Ok(member) => member
+            .joined_at
+            .and_then(|v| DateTime::from_timestamp(v.as_secs(), 0)),
+        Err(error) => {
+            debug!(error = %error.anonymize(), "could not fetch applicant's member data");
+            None
+        }
+    }
+}
+
+fn action_button(
+    application: &PayerApplication,
+    action: Action,
+    page: usize,
+    label: &str,
+    style: ButtonStyle,
+) -> Component {
+    // an interview only needs to be requested once
+    let disabled = action == Action::Interview && application.interviewer_id.is_some();
+    Component::Button(Button {
+        custom_id: Some(make_action_custom_id(action, application.id, page)),
+        disabled,
+        emoji: None,
+        label: Some(label.into()),
+        style,
+        url: None,
+    })
+}
+
+fn page_button(page: usize, label: &str, disabled: bool) -> Component {
+    Component::Button(Button {
+        custom_id: Some(make_page_custom_id(page)),
+        disabled,
+        emoji: None,
+        label: Some(label.into()),
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
+
+/// Accepts, rejects or requests an interview for a payer application
+/// or moves to another page of the queue after pressing one of the
+/// buttons from `/payer admin queue`.
+///
+/// Every action only applies if the application has not been handled
+/// yet so multiple admins can work through the queue at the same time
+/// without processing the same application twice.
+#[instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_action(ctx: &InteractionContext<MessageComponentInteractionData>) -> Result<()> {
+    let Some(button) = parse_custom_id(&ctx.data.custom_id) else {
+        warn!("got invalid payer queue custom id");
+        return Ok(());
+    };
+
+    let (Some(guild_id), Some(permissions)) = (
+        ctx.interaction.guild_id,
+        ctx.interaction.member.as_ref().and_then(|v| v.permissions),
+    ) else {
+        return respond_ephemeral(ctx, NOT_ALLOWED_MSG).await;
+    };
+
+    if !permissions.contains(Permissions::ADMINISTRATOR) {
+        return respond_ephemeral(ctx, NOT_ALLOWED_MSG).await;
+    }
+
+    let (action, application_id, page) = match button {
+        QueueButton::Action {
+            action,
+            application_id,
+            page,
+        } => (action, application_id, page),
+        QueueButton::Page(page) => {
+            let data = render(&ctx.bot, guild_id, page).await?;
+            return ctx.update(data).await;
+        }
+    };
+
+    let admin_id = ctx.invoker_id();
+    let Some(application) = take_action(&ctx.bot, action, application_id, admin_id).await? else {
+        return respond_ephemeral(ctx, ALREADY_HANDLED_MSG).await;
+    };
+    debug!("{} payer application {application_id}", action.name());
+
+    notify_applicant(&ctx.bot, action, &application).await;
+
+    let mut data = render(&ctx.bot, guild_id, page).await?;
+    let status = match action {
+        Action::Accept => "accepted",
+        Action::Reject => "rejected",
+        Action::Interview => "requested an interview for",
+    };
+    let status = format!(
+        "**{} {status} {}'s application.**",
+        admin_id.mention(),
+        application.user_id.mention()
+    );
+    data.content = Some(match data.content.take().filter(|v| !v.is_empty()) {
+        Some(content) => format!("{status}\n{content}"),
+        None => status,
+    });
+
+    ctx.update(data).await
+}
+
+/// Applies `action` on the application if it is still pending. It
+/// returns `None` if the application is already handled by someone else.
+async fn take_action(
+    bot: &Bot,
+    action: Action,
+    application_id: Uuid,
+    admin_id: Id<UserMarker>,
+) -> Result<Option<PayerApplication>> {
+    let mut conn = bot.db_write().await?;
+    let application = match action {
+        Action::Accept => {
+            let Some(application) =
+                PayerApplication::review(&mut conn, application_id, true, None).await?
+            else {
+                return Ok(None);
+            };
+
+            let form = InsertPayerForm::builder()
+                .id(application.user_id)
+                .name(&application.name)
+                .java_username(&application.java_username)
+                .bedrock_username(application.bedrock_username.as_deref())
+                .build();
+
+            Payer::insert(&mut conn, form)
+                .await
+                .attach_printable("could not register accepted applicant as payer")?;
+
+            application
+        }
+        Action::Reject => {
+            let result = PayerApplication::review(
+                &mut conn,
+                application_id,
+                false,
+                Some(DEFAULT_DENY_REASON),
+            )
+            .await?;
+
+            let Some(application) = result else {
+                return Ok(None);
+            };
+            application
+        }
+        Action::Interview => {
+            let result =
+                PayerApplication::request_interview(&mut conn, application_id, admin_id).await?;
+
+            let Some(application) = result else {
+                return Ok(None);
+            };
+            application
+        }
+    };
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    Ok(Some(application))
+}
+
+/// Lets the applicant know what happened to their application.
+/// Failing to do so is not fatal as applicants can still check
+/// their application with `/payer app status`.
+async fn notify_applicant(bot: &Bot, action: Action, application: &PayerApplication) {
+    let content = match action {
+        Action::Accept => {
+            "**Your monthly contributor application has been accepted!** Welcome aboard! ❤️"
+        }
+        Action::Reject => {
+            "**Your monthly contributor application has been rejected.** You may check \
+            the reason by running `/payer app status`."
+        }
+        Action::Interview => {
+            "**The server administrators want to interview you about your monthly \
+            contributor application.** They will reach out to you soon."
+        }
+    };
+
+    let result = async {
+        if !preferences::allows_dm_reminders(bot, application.user_id).await? {
+            trace!("applicant opted out of DM reminders, skipping");
+            return Ok(());
+        }
+
+        let channel = request_for_model(bot, bot.http.create_private_channel(application.user_id))
+            .await
+            .attach_printable("could not create DM channel")?;
+
+        let request = bot
+            .http
+            .create_message(channel.id)
+            .content(content)
+            .into_typed_error()?;
+
+        request_for_model(bot, request).await.map(drop)
+    }
+    .await;
+
+    if let Err(error) = result {
+        warn!(error = %error.anonymize(), "could not notify applicant {}", application.user_id);
+    }
+}
+
+async fn respond_ephemeral<T>(ctx: &InteractionContext<T>, content: &str) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_custom_ids() {
+        let id = Uuid::new_v4();
+        for action in [Action::Accept, Action::Reject, Action::Interview] {
+            let expected = QueueButton::Action {
+                action,
+                application_id: id,
+                page: 3,
+            };
+            let custom_id = make_action_custom_id(action, id, 3);
+            assert_eq!(parse_custom_id(&custom_id), Some(expected));
+        }
+
+        assert_eq!(
+            parse_custom_id(&make_page_custom_id(2)),
+            Some(QueueButton::Page(2))
+        );
+        assert_eq!(parse_custom_id(&format!("payer_queue:delete:{id}:0")), None);
+        assert_eq!(parse_custom_id(&format!("payer_queue:accept:{id}")), None);
+        assert_eq!(parse_custom_id("help:2"), None);
+    }
+}
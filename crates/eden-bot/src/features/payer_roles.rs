@@ -0,0 +1,186 @@
+use chrono::{TimeDelta, Utc};
+use eden_schema::types::PayerStatusChange;
+use eden_utils::error::exts::*;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::Result;
+use sqlx::postgres::PgListener;
+use std::time::Duration;
+use tracing::{debug, instrument, trace, warn};
+use twilight_http::request::AuditLogReason;
+use twilight_model::id::marker::{GuildMarker, RoleMarker};
+use twilight_model::id::Id;
+
+use crate::features::auto_role;
+use crate::util::http::request_for_empty;
+use crate::Bot;
+
+/// Maximum amount of payer status changes applied at once.
+const BATCH_SIZE: i64 = 50;
+
+/// How long claimed changes are left alone before they can be claimed
+/// again, in case the process that claimed them has stopped.
+const CLAIM_TIMEOUT: TimeDelta = TimeDelta::minutes(5);
+
+/// How long to wait before receiving notifications again after
+/// the listener has lost its connection to the database.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Applies payer status changes as soon as the database records
+/// them until Eden shuts down.
+///
+/// Notifications may be missed while the listener is reconnecting,
+/// so [`SyncPayerRoles`](crate::tasks::SyncPayerRoles) applies the
+/// remaining changes periodically.
+#[instrument(skip_all)]
+pub async fn listen(bot: Bot) {
+    let mut listener = match PgListener::connect_with(&bot.pool).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            warn!(%error, "could not listen for payer status changes");
+            return;
+        }
+    };
+
+    if let Err(error) = listener.listen(PayerStatusChange::NOTIFY_CHANNEL).await {
+        warn!(%error, "could not listen for payer status changes");
+        return;
+    }
+
+    loop {
+        // changes may have been recorded while Eden is offline
+        if let Err(error) = apply_pending(&bot).await {
+            warn!(error = %error.anonymize(), "could not apply payer status changes");
+        }
+
+        tokio::select! {
+            result = listener.recv() => {
+                if let Err(error) = result {
+                    warn!(%error, "lost connection while listening for payer status changes");
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+    }
+}
+
+/// Gives or removes the payer role based on the pending payer
+/// status changes.
+///
+/// Changes are claimed and committed before the role is changed on
+/// Discord, so the database transaction is not kept open while waiting
+/// for Discord. Changes that failed to be applied are retried later,
+/// up to [`PayerStatusChange::MAX_ATTEMPTS`] times.
+#[instrument(skip_all)]
+pub async fn apply_pending(bot: &Bot) -> Result<()> {
+    let guild_id = bot.settings.bot.local_guild.id;
+    let settings = bot.guild_settings(guild_id).await?;
+
+    let role_id = settings.payers.role_id;
+    if let Some(role_id) = role_id
+        && let Some(reason) = auto_role::find_unassignable(bot, guild_id, role_id).await?
+    {
+        // changes are kept as is until the role hierarchy is fixed
+        warn!("cannot apply payer status changes: {reason}");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let mut conn = bot.db_write().await?;
+    let changes =
+        PayerStatusChange::claim_pending(&mut conn, BATCH_SIZE, now, now - CLAIM_TIMEOUT).await?;
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for (index, change) in changes.iter().enumerate() {
+        // only the latest change of the payer matters
+        let is_superseded = changes[index + 1..]
+            .iter()
+            .any(|v| v.payer_id == change.payer_id);
+
+        if let Some(role_id) = role_id
+            && !is_superseded
+            && let Err(error) = apply(bot, guild_id, role_id, change).await
+        {
+            warn!(
+                error = %error.anonymize(),
+                "could not apply payer status change {} (attempt {})",
+                change.id,
+                change.attempts + 1
+            );
+            failed.push(change.id);
+        }
+    }
+
+    let mut conn = bot.db_write().await?;
+    for change in &changes {
+        if failed.contains(&change.id) {
+            PayerStatusChange::record_failure(&mut conn, change.id).await?;
+        } else {
+            PayerStatusChange::mark_processed(&mut conn, change.id).await?;
+        }
+    }
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    debug!(
+        "applied {} payer status change(s)",
+        changes.len() - failed.len()
+    );
+    Ok(())
+}
+
+#[allow(clippy::unwrap_used)]
+#[instrument(skip(bot))]
+async fn apply(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    role_id: Id<RoleMarker>,
+    change: &PayerStatusChange,
+) -> Result<()> {
+    let user_id = change.payer_id;
+    let result = if change.status.is_in_good_standing() {
+        trace!("giving payer role to {user_id}");
+        let request = bot
+            .http
+            .add_guild_member_role(guild_id, user_id, role_id)
+            .reason("Payer is in good standing")
+            .unwrap();
+
+        request_for_empty(bot, request).await
+    } else {
+        trace!("removing payer role from {user_id}");
+        let request = bot
+            .http
+            .remove_guild_member_role(guild_id, user_id, role_id)
+            .reason("Payer has lapsed")
+            .unwrap();
+
+        request_for_empty(bot, request).await
+    };
+
+    // the payer may have left the guild
+    let has_left = result
+        .discord_http_error_info()
+        .and_then(|v| v.json_code())
+        .is_some_and(|v| v.is_unknown_resource());
+
+    if has_left {
+        trace!("payer {user_id} is not in the guild");
+        return Ok(());
+    }
+
+    result.attach_printable("could not apply payer role")?;
+    Ok(())
+}
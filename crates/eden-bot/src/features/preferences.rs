@@ -0,0 +1,108 @@
+use chrono::{FixedOffset, Offset, Utc};
+use eden_schema::types::{
+    DmRemindersOptOutPreference, LocalePreference, PreferenceKey, TimezonePreference,
+    UserPreferences,
+};
+use eden_utils::Result;
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+
+use crate::Bot;
+
+/// Whether the user allows the bot to send reminders and other
+/// non-essential messages through direct messages.
+///
+/// Features that DM users on their own (not as a response of
+/// the user's action) must check this first before sending one.
+/// Admin alerts are the exception since their recipients are
+/// configured in `alerts.<kind>.dm_user_ids` on purpose.
+pub async fn allows_dm_reminders(bot: &Bot, user_id: Id<UserMarker>) -> Result<bool> {
+    let mut conn = bot.db_read().await?;
+    let opted_out = UserPreferences::get::<DmRemindersOptOutPreference>(&mut conn, user_id).await?;
+    Ok(!opted_out)
+}
+
+/// Gets the locale the bot should use to talk to the user.
+///
+/// The stored locale preference takes priority over the locale of
+/// the user's Discord client (`client_locale`) if the user has set one.
+pub async fn locale(
+    bot: &Bot,
+    user_id: Id<UserMarker>,
+    client_locale: Option<&str>,
+) -> Result<String> {
+    let mut conn = bot.db_read().await?;
+    let stored = UserPreferences::find::<LocalePreference>(&mut conn, user_id).await?;
+    let locale = stored
+        .or_else(|| client_locale.map(String::from))
+        .unwrap_or_else(LocalePreference::default_value);
+
+    Ok(locale)
+}
+
+/// Gets the preferred timezone of the user. It falls back to
+/// UTC if the stored timezone is somehow invalid.
+pub async fn timezone(bot: &Bot, user_id: Id<UserMarker>) -> Result<FixedOffset> {
+    let mut conn = bot.db_read().await?;
+    let stored = UserPreferences::get::<TimezonePreference>(&mut conn, user_id).await?;
+    Ok(parse_timezone(&stored).unwrap_or_else(|| Utc.fix()))
+}
+
+/// Parses the user's preferred timezone as a UTC offset
+/// (like `+08:00`, `-0530` or `UTC`).
+#[must_use]
+pub fn parse_timezone(input: &str) -> Option<FixedOffset> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("utc") || input.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+    input.parse().ok()
+}
+
+/// Checks whether the locale looks like a valid language tag
+/// (like `en`, `en-US` or `zh-TW`).
+#[must_use]
+pub fn is_valid_locale(input: &str) -> bool {
+    let mut parts = input.split('-');
+    let Some(language) = parts.next() else {
+        return false;
+    };
+
+    let is_language_valid =
+        (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase());
+
+    let is_region_valid = match parts.next() {
+        Some(region) => {
+            (2..=3).contains(&region.len()) && region.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        None => true,
+    };
+
+    is_language_valid && is_region_valid && parts.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_timezones() {
+        assert_eq!(parse_timezone("UTC"), FixedOffset::east_opt(0));
+        assert_eq!(parse_timezone("+08:00"), FixedOffset::east_opt(8 * 3600));
+        assert_eq!(
+            parse_timezone("-05:30"),
+            FixedOffset::west_opt(5 * 3600 + 1800)
+        );
+        assert_eq!(parse_timezone("Asia/Manila"), None);
+    }
+
+    #[test]
+    fn should_validate_locales() {
+        assert!(is_valid_locale("en"));
+        assert!(is_valid_locale("en-US"));
+        assert!(is_valid_locale("fil"));
+        assert!(!is_valid_locale("EN"));
+        assert!(!is_valid_locale("en-US-x"));
+        assert!(!is_valid_locale("english"));
+    }
+}
@@ -0,0 +1,33 @@
+use eden_settings::SettingsWatcher;
+use tracing::{instrument, trace};
+
+use crate::Bot;
+
+/// Shows the activities from `bot.presence` in turns until Eden
+/// shuts down.
+///
+/// The presence is read from the latest settings every turn, so
+/// reloaded activities are rotated without restarting Eden.
+#[instrument(skip_all)]
+pub async fn rotate(bot: Bot, watcher: SettingsWatcher) {
+    let mut rotation = 0_usize;
+    loop {
+        let interval = watcher.current().bot.presence.rotation_interval.get();
+        tokio::select! {
+            () = tokio::time::sleep(interval) => {}
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+
+        let settings = watcher.current();
+        let presence = &settings.bot.presence;
+        if !presence.is_rotating() {
+            continue;
+        }
+
+        rotation = rotation.wrapping_add(1);
+        trace!("rotating presence activity ({rotation})");
+        bot.shard_manager
+            .set_presence(presence.to_payload_with(rotation))
+            .await;
+    }
+}
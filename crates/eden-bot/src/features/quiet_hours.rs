@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use eden_schema::types::{QuietHoursGuildSettings, QuietHoursOverwrite};
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, instrument, warn};
+use twilight_http::request::AuditLogReason;
+use twilight_model::channel::permission_overwrite::PermissionOverwriteType;
+use twilight_model::guild::Permissions;
+use twilight_model::http::permission_overwrite::PermissionOverwrite;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker};
+use twilight_model::id::Id;
+
+use crate::util::http::{request_for_empty, request_for_model};
+use crate::Bot;
+
+/// Permissions denied from the `@everyone` role in read-only
+/// channels during quiet hours.
+const RESTRICTED_PERMISSIONS: Permissions =
+    Permissions::SEND_MESSAGES.union(Permissions::SEND_MESSAGES_IN_THREADS);
+
+const QUIET_HOURS_REASON: &str = "Quiet hours started";
+const END_QUIET_HOURS_REASON: &str = "Quiet hours ended";
+
+/// Keeps track whether quiet hours are in effect in the local guild
+/// so features don't need to load the guild settings every time
+/// they want to send non-essential messages.
+#[derive(Debug, Default)]
+pub struct QuietHours {
+    active: AtomicBool,
+}
+
+impl QuietHours {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether Eden should not send non-essential messages
+    /// (like father belt replies) at the moment.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Updates the state from the local guild's quiet hours settings.
+    pub fn update(&self, settings: &QuietHoursGuildSettings, now: DateTime<Utc>) {
+        let active = settings.is_quiet_at(now);
+        self.active.store(active, Ordering::Relaxed);
+    }
+}
+
+/// Refreshes whether quiet hours are in effect in the local guild and
+/// restricts (or unrestricts) its read-only channels accordingly.
+#[instrument(skip(bot))]
+pub async fn apply(bot: &Bot, now: DateTime<Utc>) -> Result<()> {
+    let guild_id = bot.settings.bot.local_guild.id;
+
+    let settings = bot.guild_settings(guild_id).await?;
+
+    let settings = &settings.quiet_hours;
+    let was_restricted = bot.quiet_hours.is_active();
+    bot.quiet_hours.update(settings, now);
+
+    let restricted = bot.quiet_hours.is_active();
+    if restricted != was_restricted {
+        debug!(restricted, "quiet hours state changed");
+    }
+
+    for channel_id in &settings.read_only_channel_ids {
+        if let Err(error) = set_read_only(bot, guild_id, *channel_id, restricted).await {
+            warn!(%error, %channel_id, "could not update read-only channel for quiet hours");
+        }
+    }
+
+    Ok(())
+}
+
+/// Denies (or stops denying) `@everyone` from sending messages in the
+/// channel. It does nothing if the channel is already in that state.
+///
+/// The `@everyone` overwrite of the channel is saved before it is
+/// restricted, so exactly that overwrite is restored afterwards.
+#[instrument(skip(bot))]
+pub async fn set_read_only(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    channel_id: Id<ChannelMarker>,
+    restricted: bool,
+) -> Result<()> {
+    let mut conn = bot.db_read().await?;
+    let saved = QuietHoursOverwrite::from_channel(&mut conn, channel_id).await?;
+    drop(conn);
+
+    match (saved, restricted) {
+        (Some(..), true) | (None, false) => Ok(()),
+        (None, true) => restrict(bot, guild_id, channel_id).await,
+        (Some(saved), false) => unrestrict(bot, guild_id, saved).await,
+    }
+}
+
+#[allow(clippy::cast_possible_wrap, clippy::unwrap_used)]
+async fn restrict(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    channel_id: Id<ChannelMarker>,
+) -> Result<()> {
+    // @everyone role's ID is the same as the guild's ID
+    let everyone_id = guild_id.cast();
+
+    // the cached channel may be outdated and we don't want to
+    // restore the wrong overwrite after quiet hours.
+    let channel = request_for_model(bot, bot.http.channel(channel_id))
+        .await
+        .attach_printable("could not get the channel to restrict")?;
+
+    let original = channel
+        .permission_overwrites
+        .unwrap_or_default()
+        .into_iter()
+        .find(|v| v.id == everyone_id)
+        .map(|v| (v.allow, v.deny));
+
+    let mut conn = bot.db_write().await?;
+    let permissions = original.map(|(allow, deny)| (allow.bits() as i64, deny.bits() as i64));
+    QuietHoursOverwrite::save(&mut conn, guild_id, channel_id, permissions).await?;
+    conn.commit()
+        .await
+        .anonymize_error_into()
+        .attach_printable("could not commit database transaction")?;
+
+    debug!("denying @everyone from sending messages in read-only channel");
+
+    let (allow, deny) = original.unwrap_or((Permissions::empty(), Permissions::empty()));
+    let overwrite = PermissionOverwrite {
+        allow: Some(allow - RESTRICTED_PERMISSIONS),
+        deny: Some(deny | RESTRICTED_PERMISSIONS),
+        id: everyone_id,
+        kind: PermissionOverwriteType::Role,
+    };
+    let request = bot
+        .http
+        .update_channel_permission(channel_id, &overwrite)
+        .reason(QUIET_HOURS_REASON)
+        .unwrap();
+
+    let result = request_for_empty(bot, request)
+        .await
+        .attach_printable("could not update @everyone permissions of the channel");
+
+    // the channel is not restricted, so there's nothing to restore
+    if result.is_err() {
+        let mut conn = bot.db_read().await?;
+        QuietHoursOverwrite::delete(&mut conn, channel_id).await?;
+    }
+    result
+}
+
+#[allow(clippy::cast_sign_loss, clippy::unwrap_used)]
+async fn unrestrict(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    saved: QuietHoursOverwrite,
+) -> Result<()> {
+    let everyone_id = guild_id.cast();
+    let channel_id = saved.channel_id;
+
+    debug!("restoring @everyone permissions in read-only channel");
+    if let Some((allow, deny)) = saved.permissions {
+        let overwrite = PermissionOverwrite {
+            allow: Some(Permissions::from_bits_truncate(allow as u64)),
+            deny: Some(Permissions::from_bits_truncate(deny as u64)),
+            id: everyone_id,
+            kind: PermissionOverwriteType::Role,
+        };
+        let request = bot
+            .http
+            .update_channel_permission(channel_id, &overwrite)
+            .reason(END_QUIET_HOURS_REASON)
+            .unwrap();
+
+        request_for_empty(bot, request)
+            .await
+            .attach_printable("could not restore @everyone permissions of the channel")?;
+    } else {
+        let request = bot
+            .http
+            .delete_channel_permission(channel_id)
+            .role(everyone_id)
+            .reason(END_QUIET_HOURS_REASON)
+            .unwrap();
+
+        request_for_empty(bot, request)
+            .await
+            .attach_printable("could not remove @everyone overwrite of the channel")?;
+    }
+
+    let mut conn = bot.db_read().await?;
+    QuietHoursOverwrite::delete(&mut conn, channel_id).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    #[test]
+    fn should_update_state_from_settings() {
+        let settings = QuietHoursGuildSettings::builder()
+            .enabled(true)
+            .start(NaiveTime::from_hms_opt(0, 0, 0))
+            .end(NaiveTime::from_hms_opt(12, 0, 0))
+            .build();
+
+        let morning = DateTime::from_timestamp(3600, 0).unwrap_or_default();
+        let evening = DateTime::from_timestamp(3600 * 18, 0).unwrap_or_default();
+
+        let state = QuietHours::new();
+        state.update(&settings, morning);
+        assert!(state.is_active());
+        state.update(&settings, evening);
+        assert!(!state.is_active());
+    }
+}
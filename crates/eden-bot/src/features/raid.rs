@@ -0,0 +1,535 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+use eden_schema::forms::InsertRaidIncidentForm;
+use eden_schema::types::{LockdownMode, RaidIncident};
+use eden_settings::{AlertClass, AlertSeverity};
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use tracing::{debug, instrument, trace, warn};
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::{Guild, GuildFeature, Member, Permissions, VerificationLevel};
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+use twilight_util::builder::InteractionResponseDataBuilder;
+use uuid::Uuid;
+
+use crate::events::EventContext;
+use crate::interactions::{embeds, InteractionContext};
+use crate::util::alerts::{Alert, AlertIncident, IncidentStatus};
+use crate::util::http::request_for_model;
+use crate::Bot;
+
+/// Prefix of all custom IDs of the "End lockdown" buttons.
+pub const CUSTOM_ID_PREFIX: &str = "lockdown";
+
+/// Period of time where joins are counted to detect raids.
+const JOIN_WINDOW: TimeDelta = TimeDelta::minutes(1);
+
+/// Permissions removed from the `@everyone` role during lockdown.
+const RESTRICTED_PERMISSIONS: Permissions =
+    Permissions::SEND_MESSAGES.union(Permissions::SEND_MESSAGES_IN_THREADS);
+
+const LOCKDOWN_REASON: &str = "Raid detected";
+const END_LOCKDOWN_REASON: &str = "Lockdown ended";
+
+const NOT_ALLOWED_MSG: &str = "**You're not allowed to end the lockdown.**";
+const ALREADY_ENDED_MSG: &str = "**This lockdown has already ended.**";
+
+/// Keeps track of recent member joins per guild to detect raids.
+#[derive(Debug, Default)]
+pub struct JoinRateMonitor {
+    joins: DashMap<Id<GuildMarker>, VecDeque<DateTime<Utc>>>,
+}
+
+impl JoinRateMonitor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            joins: DashMap::new(),
+        }
+    }
+
+    /// Records a new member join in a guild. It returns the amount of
+    /// joins within the last minute if it reaches the `threshold`.
+    ///
+    /// Recent joins from the guild will be forgotten once it reaches
+    /// the threshold so the raid won't be detected repeatedly.
+    pub fn record(
+        &self,
+        guild_id: Id<GuildMarker>,
+        now: DateTime<Utc>,
+        threshold: u32,
+    ) -> Option<u32> {
+        if threshold == 0 {
+            return None;
+        }
+
+        let mut joins = self.joins.entry(guild_id).or_default();
+        while joins.front().is_some_and(|v| now - *v > JOIN_WINDOW) {
+            joins.pop_front();
+        }
+        joins.push_back(now);
+
+        let total = u32::try_from(joins.len()).unwrap_or(u32::MAX);
+        if total < threshold {
+            return None;
+        }
+
+        joins.clear();
+        Some(total)
+    }
+}
+
+fn make_custom_id(incident_id: Uuid) -> String {
+    format!("{CUSTOM_ID_PREFIX}:{incident_id}")
+}
+
+fn incident_key(incident_id: Uuid) -> String {
+    format!("raid:{incident_id}")
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<Uuid> {
+    let (prefix, id) = custom_id.split_once(':')?;
+    if prefix != CUSTOM_ID_PREFIX {
+        return None;
+    }
+    id.parse().ok()
+}
+
+/// Counts new members joined in the local guild and locks down
+/// the guild if it is considered as a raid.
+#[instrument(skip_all, fields(%guild_id, member.id = %member.user.id))]
+pub async fn on_member_add(ctx: &EventContext, guild_id: Id<GuildMarker>, member: &Member) {
+    if !ctx.bot.is_local_guild(&guild_id) {
+        return;
+    }
+
+    let result = async {
+        let settings = ctx.bot.guild_settings(guild_id).await?;
+
+        let raid = &settings.raid;
+        if !raid.enabled {
+            return Ok(());
+        }
+
+        let joins = ctx
+            .bot
+            .join_monitor
+            .record(guild_id, Utc::now(), raid.joins_per_minute);
+
+        if let Some(joins) = joins {
+            start_lockdown(&ctx.bot, guild_id, joins, raid.lockdown_mode).await?;
+        }
+
+        Ok::<_, eden_utils::Error>(())
+    }
+    .await;
+
+    if let Err(error) = result {
+        warn!(%error, "could not detect or lock down raid");
+    }
+}
+
+/// Locks down the guild, records the incident and alerts the
+/// admins with an "End lockdown" button.
+#[allow(clippy::cast_possible_wrap)]
+#[instrument(skip(bot))]
+async fn start_lockdown(
+    bot: &Bot,
+    guild_id: Id<GuildMarker>,
+    joins: u32,
+    mode: LockdownMode,
+) -> Result<()> {
+    let mut conn = bot.db_read().await?;
+    if RaidIncident::active(&mut conn, guild_id).await?.is_some() {
+        trace!("guild is already locked down, skipping");
+        return Ok(());
+    }
+    drop(conn);
+
+    warn!("detected raid with {joins} join(s) within a minute, locking down the guild");
+
+    let guild = request_for_model(bot, bot.http.guild(guild_id))
+        .await
+        .attach_printable("could not get guild info")?;
+
+    // only the changes made by the lockdown are recorded so
+    // anything else the admins changed in the meantime is kept.
+    let mut form = InsertRaidIncidentForm::builder()
+        .guild_id(guild_id)
+        .joins(i32::try_from(joins).unwrap_or(i32::MAX))
+        .build();
+
+    match mode {
+        LockdownMode::RestrictMessages => {
+            let removed = everyone_permissions(&guild) & RESTRICTED_PERMISSIONS;
+            if !removed.is_empty() {
+                form.removed_everyone_permissions = Some(removed.bits() as i64);
+            }
+        }
+        LockdownMode::RaiseVerification => {
+            if guild.verification_level != VerificationLevel::VeryHigh {
+                let level = i16::from(u8::from(guild.verification_level));
+                form.previous_verification_level = Some(level);
+            }
+        }
+        LockdownMode::PauseInvites => {
+            form.disabled_invites = !guild.features.contains(&GuildFeature::InvitesDisabled);
+        }
+    }
+
+    // the incident is recorded first so the lockdown can be ended
+    // even if the bot restarts right after locking down the guild.
+    let mut conn = bot.db_write().await?;
+    let incident = RaidIncident::insert(&mut conn, form).await?;
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    if let Err(error) = apply_lockdown(bot, &guild, &incident).await {
+        let mut conn = bot.db_write().await?;
+        RaidIncident::delete(&mut conn, incident.id).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        return Err(error);
+    }
+
+    send_alert(bot, &incident, mode).await
+}
+
+fn everyone_permissions(guild: &Guild) -> Permissions {
+    // @everyone role's ID is the same as the guild's ID
+    let everyone_id = guild.id.cast();
+    guild
+        .roles
+        .iter()
+        .find(|v| v.id == everyone_id)
+        .map(|v| v.permissions)
+        .unwrap_or_else(Permissions::empty)
+}
+
+/// Features of the guild with `INVITES_DISABLED` added or removed.
+fn features_with_invites_disabled(guild: &Guild, disabled: bool) -> Vec<Cow<'static, str>> {
+    let mut features = guild
+        .features
+        .iter()
+        .filter(|v| **v != GuildFeature::InvitesDisabled)
+        .map(|v| Cow::from(v.clone()))
+        .collect::<Vec<_>>();
+
+    if disabled {
+        features.push(Cow::from(GuildFeature::InvitesDisabled));
+    }
+    features
+}
+
+#[allow(clippy::cast_sign_loss)]
+async fn apply_lockdown(bot: &Bot, guild: &Guild, incident: &RaidIncident) -> Result<()> {
+    if let Some(removed) = incident.removed_everyone_permissions {
+        debug!("restricting @everyone from sending messages");
+
+        let removed = Permissions::from_bits_truncate(removed as u64);
+
+        #[allow(clippy::unwrap_used)]
+        let request = bot
+            .http
+            .update_role(guild.id, guild.id.cast())
+            .permissions(everyone_permissions(guild) - removed)
+            .reason(LOCKDOWN_REASON)
+            .unwrap();
+
+        request_for_model(bot, request)
+            .await
+            .attach_printable("could not restrict @everyone role")?;
+    }
+
+    if incident.previous_verification_level.is_some() {
+        debug!("raising verification level of the guild");
+
+        #[allow(clippy::unwrap_used)]
+        let request = bot
+            .http
+            .update_guild(guild.id)
+            .verification_level(Some(VerificationLevel::VeryHigh))
+            .reason(LOCKDOWN_REASON)
+            .unwrap();
+
+        request_for_model(bot, request)
+            .await
+            .attach_printable("could not raise verification level")?;
+    }
+
+    if incident.disabled_invites {
+        debug!("pausing invites of the guild");
+
+        let features = features_with_invites_disabled(guild, true);
+        let features = features.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+
+        #[allow(clippy::unwrap_used)]
+        let request = bot
+            .http
+            .update_guild(guild.id)
+            .features(&features)
+            .reason(LOCKDOWN_REASON)
+            .unwrap();
+
+        request_for_model(bot, request)
+            .await
+            .attach_printable("could not pause invites")?;
+    }
+
+    Ok(())
+}
+
+async fn send_alert(bot: &Bot, incident: &RaidIncident, mode: LockdownMode) -> Result<()> {
+    let action = match mode {
+        LockdownMode::RestrictMessages => "Members can no longer send messages",
+        LockdownMode::RaiseVerification => "Verification level has been raised to the highest",
+        LockdownMode::PauseInvites => "Invites are paused",
+    };
+
+    let embed = embeds::builders::error("Raid detected", Some(incident.created_at))
+        .description(format!(
+            "**{}** members joined within a minute so I locked down the server.\n\n{action} until the lockdown ends.",
+            incident.joins
+        ))
+        .build();
+
+    let components = [Component::ActionRow(ActionRow {
+        components: vec![Component::Button(Button {
+            custom_id: Some(make_custom_id(incident.id)),
+            disabled: false,
+            emoji: None,
+            label: Some("End lockdown".into()),
+            style: ButtonStyle::Danger,
+            url: None,
+        })],
+    })];
+
+    // the lockdown is resolved from the "End lockdown" button
+    let key = incident_key(incident.id);
+    let embeds = [embed];
+    let alert = Alert {
+        embeds: &embeds,
+        components: &components,
+        incident: Some(AlertIncident {
+            key: &key,
+            title: "Raid lockdown",
+            status: IncidentStatus::Ongoing,
+        }),
+        ..Alert::new(AlertClass::Raid, AlertSeverity::Critical)
+    };
+
+    debug!("sending raid alert");
+    bot.alerter.send(alert).await?;
+
+    Ok(())
+}
+
+/// Reverts the changes made from the lockdown. Only the changes made
+/// by the lockdown are reverted, anything else changed since then is
+/// left as is.
+#[allow(clippy::cast_sign_loss)]
+async fn restore(bot: &Bot, incident: &RaidIncident) -> Result<()> {
+    let guild = request_for_model(bot, bot.http.guild(incident.guild_id))
+        .await
+        .attach_printable("could not get guild info")?;
+
+    if let Some(removed) = incident.removed_everyone_permissions {
+        let removed = Permissions::from_bits_truncate(removed as u64);
+        let current = everyone_permissions(&guild);
+        if !current.contains(removed) {
+            debug!("restoring @everyone role permissions");
+
+            #[allow(clippy::unwrap_used)]
+            let request = bot
+                .http
+                .update_role(guild.id, guild.id.cast())
+                .permissions(current | removed)
+                .reason(END_LOCKDOWN_REASON)
+                .unwrap();
+
+            request_for_model(bot, request)
+                .await
+                .attach_printable("could not restore @everyone role")?;
+        }
+    }
+
+    // the verification level may have been changed by the admins
+    // during the lockdown, keep it if so.
+    if let Some(level) = incident.previous_verification_level
+        && guild.verification_level == VerificationLevel::VeryHigh
+    {
+        debug!("restoring verification level of the guild");
+
+        let level = VerificationLevel::from(u8::try_from(level).unwrap_or_default());
+
+        #[allow(clippy::unwrap_used)]
+        let request = bot
+            .http
+            .update_guild(guild.id)
+            .verification_level(Some(level))
+            .reason(END_LOCKDOWN_REASON)
+            .unwrap();
+
+        request_for_model(bot, request)
+            .await
+            .attach_printable("could not restore verification level")?;
+    }
+
+    if incident.disabled_invites && guild.features.contains(&GuildFeature::InvitesDisabled) {
+        debug!("resuming invites of the guild");
+
+        let features = features_with_invites_disabled(&guild, false);
+        let features = features.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+
+        #[allow(clippy::unwrap_used)]
+        let request = bot
+            .http
+            .update_guild(guild.id)
+            .features(&features)
+            .reason(END_LOCKDOWN_REASON)
+            .unwrap();
+
+        request_for_model(bot, request)
+            .await
+            .attach_printable("could not resume invites")?;
+    }
+
+    Ok(())
+}
+
+/// Ends the lockdown after pressing the "End lockdown" button
+/// from the raid alert.
+#[instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_end_lockdown(
+    ctx: &InteractionContext<MessageComponentInteractionData>,
+) -> Result<()> {
+    let Some(incident_id) = parse_custom_id(&ctx.data.custom_id) else {
+        warn!("got invalid lockdown custom id");
+        return Ok(());
+    };
+
+    let is_allowed = ctx
+        .interaction
+        .member
+        .as_ref()
+        .and_then(|v| v.permissions)
+        .is_some_and(|v| v.intersects(Permissions::ADMINISTRATOR | Permissions::MANAGE_GUILD));
+
+    if !is_allowed {
+        return respond_ephemeral(ctx, NOT_ALLOWED_MSG).await;
+    }
+
+    let user_id = ctx.invoker_id();
+    let mut conn = ctx.bot.db_read().await?;
+    let incident = RaidIncident::from_id(&mut conn, incident_id).await?;
+    drop(conn);
+
+    let Some(incident) = incident.filter(RaidIncident::is_active) else {
+        return respond_ephemeral(ctx, ALREADY_ENDED_MSG).await;
+    };
+
+    // restoring is safe to repeat if someone else ends it at the same time
+    debug!("ending lockdown of incident {incident_id}");
+    restore(&ctx.bot, &incident).await?;
+
+    let mut conn = ctx.bot.db_write().await?;
+    let ended = RaidIncident::end(&mut conn, incident_id, user_id).await?;
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    if ended.is_none() {
+        return respond_ephemeral(ctx, ALREADY_ENDED_MSG).await;
+    }
+
+    let data = InteractionResponseDataBuilder::new()
+        .content(format!("**Lockdown ended by {}.**", user_id.mention()))
+        .build();
+
+    ctx.respond(data).await?;
+    ctx.bot
+        .alerter
+        .resolve_incident(&incident_key(incident_id))
+        .await;
+
+    // the "End lockdown" button is no longer needed
+    if let Some(message) = ctx.interaction.message.as_ref() {
+        #[allow(clippy::unwrap_used)]
+        let request = ctx
+            .bot
+            .http
+            .update_message(message.channel_id, message.id)
+            .components(Some(&[]))
+            .unwrap();
+
+        if let Err(error) = request_for_model(&ctx.bot, request).await {
+            warn!(error = %error.anonymize(), "could not remove end lockdown button");
+        }
+    }
+
+    Ok(())
+}
+
+async fn respond_ephemeral<T>(ctx: &InteractionContext<T>, content: &str) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_custom_ids() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_custom_id(&make_custom_id(id)), Some(id));
+        assert_eq!(parse_custom_id(&format!("verification:{id}")), None);
+        assert_eq!(parse_custom_id("lockdown:abc"), None);
+    }
+
+    #[test]
+    fn should_detect_raids() {
+        let monitor = JoinRateMonitor::new();
+        let guild_id = Id::new(1);
+        let now = Utc::now();
+
+        assert_eq!(monitor.record(guild_id, now, 3), None);
+        assert_eq!(monitor.record(guild_id, now, 3), None);
+        assert_eq!(monitor.record(Id::new(2), now, 3), None);
+        assert_eq!(monitor.record(guild_id, now, 3), Some(3));
+
+        // it should be forgotten after it is detected
+        assert_eq!(monitor.record(guild_id, now, 3), None);
+    }
+
+    #[test]
+    fn should_forget_joins_outside_window() {
+        let monitor = JoinRateMonitor::new();
+        let guild_id = Id::new(1);
+        let now = Utc::now();
+
+        assert_eq!(monitor.record(guild_id, now, 3), None);
+        assert_eq!(monitor.record(guild_id, now, 3), None);
+
+        let later = now + TimeDelta::minutes(2);
+        assert_eq!(monitor.record(guild_id, later, 3), None);
+        assert_eq!(monitor.joins.get(&guild_id).map(|v| v.len()), Some(1));
+    }
+}
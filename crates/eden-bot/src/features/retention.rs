@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use eden_schema::types::{
+    EmojiUpload, GuildMemberCount, GuildProfileChange, GuildSnapshot, RaidIncident,
+    ScheduledEventReminder, VoiceStat,
+};
+use eden_settings::{AlertClass, AlertSeverity, RetentionCategory};
+use eden_tasks_schema::types::Task;
+use eden_utils::error::exts::*;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use tracing::{debug, info, instrument};
+use twilight_model::channel::message::Embed;
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::interactions::embeds;
+use crate::util::alerts::Alert;
+use crate::Bot;
+
+/// How many rows were deleted per category after pruning.
+pub type PruneReport = Vec<(RetentionCategory, u64)>;
+
+/// Deletes data older than the retention period of its category
+/// from the `retention` setting. Categories that are kept forever
+/// are not included in the report.
+#[instrument(skip_all)]
+pub async fn prune(bot: &Bot, now: DateTime<Utc>) -> Result<PruneReport> {
+    let retention = &bot.settings.retention;
+    let mut conn = bot.db_write().await?;
+
+    let mut report = PruneReport::new();
+    for category in RetentionCategory::ALL.iter().copied() {
+        let Some(period) = retention.get(category) else {
+            continue;
+        };
+
+        // durations that large mean the data should be kept forever anyway
+        let Some(before) = now.checked_sub_signed(period.to_time_delta()) else {
+            continue;
+        };
+
+        let deleted = prune_category(&mut conn, category, before)
+            .await
+            .attach_printable_lazy(|| format!("could not prune {}", category.name()))?;
+
+        debug!(
+            "deleted {deleted} row(s) of {} older than {period}",
+            category.name()
+        );
+        report.push((category, deleted));
+    }
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    Ok(report)
+}
+
+async fn prune_category(
+    conn: &mut sqlx::PgConnection,
+    category: RetentionCategory,
+    before: DateTime<Utc>,
+) -> Result<u64, QueryError> {
+    match category {
+        RetentionCategory::VoiceStats => VoiceStat::delete_before(conn, before.date_naive()).await,
+        RetentionCategory::ProfileChanges => GuildProfileChange::delete_before(conn, before).await,
+        RetentionCategory::RaidIncidents => RaidIncident::delete_ended_before(conn, before).await,
+        RetentionCategory::EmojiUploads => EmojiUpload::delete_reviewed_before(conn, before).await,
+        RetentionCategory::EventReminders => {
+            ScheduledEventReminder::delete_before(conn, before).await
+        }
+        RetentionCategory::TaskHistory => Task::delete_finished_before(conn, before).await,
+        RetentionCategory::GuildSnapshots => GuildSnapshot::delete_before(conn, before).await,
+        RetentionCategory::MemberCounts => {
+            GuildMemberCount::delete_before(conn, before.date_naive()).await
+        }
+    }
+}
+
+/// Renders how many rows were deleted per category.
+#[must_use]
+pub fn render(report: &PruneReport) -> Embed {
+    let total = report.iter().map(|(_, deleted)| deleted).sum::<u64>();
+    let mut builder = embeds::builders::with_emoji('🧹', "Old data pruned").description(format!(
+        "Deleted {total} row(s) older than their retention period."
+    ));
+
+    for (category, deleted) in report {
+        let name = format!("`{}`", category.name());
+        builder = builder.field(EmbedFieldBuilder::new(name, deleted.to_string()).inline());
+    }
+
+    builder.build()
+}
+
+/// Prunes old data and reports how many rows were deleted per
+/// category as an alert if anything has been deleted.
+#[instrument(skip_all)]
+pub async fn run(bot: &Bot, now: DateTime<Utc>) -> Result<()> {
+    let report = prune(bot, now).await?;
+    for (category, deleted) in &report {
+        info!("pruned {deleted} row(s) of {}", category.name());
+    }
+
+    if report.iter().all(|(_, deleted)| *deleted == 0) {
+        debug!("nothing has been pruned, skipping report");
+        return Ok(());
+    }
+
+    let embeds = [render(&report)];
+    let alert = Alert {
+        embeds: &embeds,
+        ..Alert::new(AlertClass::System, AlertSeverity::Info)
+    };
+
+    debug!("sending prune report");
+    bot.alerter
+        .send(alert)
+        .await
+        .attach_printable("could not send prune report")?;
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use eden_tasks::Scheduled;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use tracing::{debug, instrument, trace, warn};
+use twilight_mention::Mention;
+use twilight_model::channel::message::Embed;
+use twilight_model::guild::scheduled_event::{GuildScheduledEvent, Status};
+use twilight_model::guild::Guild;
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+use twilight_model::util::Timestamp;
+
+use crate::events::EventContext;
+use crate::interactions::embeds;
+use crate::tasks;
+use crate::Bot;
+
+/// Maximum amount of interested members mentioned in a reminder
+/// so the message does not exceed Discord's content length limit.
+pub const MAX_PINGED_USERS: usize = 50;
+
+/// How long before a scheduled event starts Eden reminds members about it.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventReminder {
+    DayBefore,
+    HourBefore,
+}
+
+impl EventReminder {
+    pub const ALL: [Self; 2] = [Self::DayBefore, Self::HourBefore];
+
+    #[must_use]
+    pub fn lead_time(self) -> TimeDelta {
+        match self {
+            Self::DayBefore => TimeDelta::days(1),
+            Self::HourBefore => TimeDelta::hours(1),
+        }
+    }
+
+    /// Gets [`EventReminder::lead_time`] in minutes as stored
+    /// in the database.
+    #[must_use]
+    pub fn lead_minutes(self) -> i32 {
+        match self {
+            Self::DayBefore => 24 * 60,
+            Self::HourBefore => 60,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::DayBefore => "Event starts tomorrow",
+            Self::HourBefore => "Event starts in an hour",
+        }
+    }
+}
+
+#[must_use]
+pub fn timestamp_to_dt(timestamp: Timestamp) -> DateTime<Utc> {
+    DateTime::from_timestamp(timestamp.as_secs(), 0).unwrap_or_default()
+}
+
+/// Gets the reminders of an event starting at `starts_at` along with
+/// when they're due. Reminders that are already past due are skipped.
+#[must_use]
+pub fn due_reminders(
+    starts_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Vec<(EventReminder, DateTime<Utc>)> {
+    EventReminder::ALL
+        .into_iter()
+        .map(|v| (v, starts_at - v.lead_time()))
+        .filter(|(_, due_at)| *due_at > now)
+        .collect()
+}
+
+/// Schedules reminders of a created or updated scheduled event
+/// in the local guild.
+///
+/// Reminders are scheduled every time the event is updated. Reminders of
+/// rescheduled, cancelled or deleted events are skipped once they're due
+/// and the same reminder is never posted twice.
+#[instrument(skip_all, fields(event.id = %event.id))]
+pub async fn on_event_changed(ctx: &EventContext, event: &GuildScheduledEvent) {
+    if !ctx.bot.is_local_guild(&event.guild_id) || event.status != Status::Scheduled {
+        return;
+    }
+
+    if let Err(error) = schedule_reminders(&ctx.bot, event, Utc::now()).await {
+        warn!(%error, "could not schedule reminders of scheduled event");
+    }
+}
+
+/// Schedules reminders of scheduled events that were created while
+/// Eden was offline once the local guild is loaded.
+///
+/// Reminders that are already scheduled are deduplicated by the queue.
+#[instrument(skip_all, fields(guild.id = %guild.id))]
+pub async fn on_guild_create(bot: &Bot, guild: &Guild) {
+    let now = Utc::now();
+    let events = guild
+        .guild_scheduled_events
+        .iter()
+        .filter(|v| v.status == Status::Scheduled);
+
+    for event in events {
+        if let Err(error) = schedule_reminders(bot, event, now).await {
+            warn!(%error, event.id = %event.id, "could not schedule reminders of scheduled event");
+        }
+    }
+}
+
+async fn schedule_reminders(
+    bot: &Bot,
+    event: &GuildScheduledEvent,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let starts_at = timestamp_to_dt(event.scheduled_start_time);
+    for (reminder, due_at) in due_reminders(starts_at, now) {
+        trace!("scheduling {reminder:?} reminder at {due_at}");
+
+        let task = tasks::SendEventReminder {
+            guild_id: event.guild_id,
+            event_id: event.id,
+            reminder,
+            starts_at,
+        };
+        bot.queue.schedule(task, Scheduled::At(due_at)).await?;
+    }
+
+    debug!("scheduled reminders of event {:?}", event.name);
+    Ok(())
+}
+
+/// Renders the reminder message of a scheduled event which mentions
+/// the given interested members.
+#[must_use]
+pub fn render_reminder(
+    event: &GuildScheduledEvent,
+    reminder: EventReminder,
+    interested: &[Id<UserMarker>],
+) -> (String, Embed) {
+    let starts_at = timestamp_to_dt(event.scheduled_start_time);
+
+    let mut description = format!(
+        "**{}** starts {} ({}).",
+        event.name,
+        discord_timestamp(starts_at, TimestampStyle::Relative),
+        discord_timestamp(starts_at, TimestampStyle::LongDateTime),
+    );
+
+    if let Some(channel_id) = event.channel_id {
+        let _ = write!(description, "\n**Where**: {}", channel_id.mention());
+    } else if let Some(location) = event
+        .entity_metadata
+        .as_ref()
+        .and_then(|v| v.location.as_deref())
+    {
+        let _ = write!(description, "\n**Where**: {location}");
+    }
+
+    if let Some(details) = event.description.as_deref().filter(|v| !v.is_empty()) {
+        let _ = write!(description, "\n\n{details}");
+    }
+
+    let embed = embeds::builders::with_emoji('📅', reminder.title())
+        .description(description)
+        .url(format!(
+            "https://discord.com/events/{}/{}",
+            event.guild_id, event.id
+        ))
+        .build();
+
+    let content = interested
+        .iter()
+        .map(|v| v.mention().to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (content, embed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 8, day, hour, 0, 0)
+            .single()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn should_get_due_reminders() {
+        let starts_at = at(25, 18);
+
+        let reminders = due_reminders(starts_at, at(20, 0));
+        assert_eq!(
+            reminders,
+            [
+                (EventReminder::DayBefore, at(24, 18)),
+                (EventReminder::HourBefore, at(25, 17)),
+            ]
+        );
+
+        // the event is created less than a day before it starts
+        let reminders = due_reminders(starts_at, at(25, 0));
+        assert_eq!(reminders, [(EventReminder::HourBefore, at(25, 17))]);
+
+        assert!(due_reminders(starts_at, at(25, 17)).is_empty());
+    }
+
+    #[test]
+    fn should_match_lead_time_in_minutes() {
+        for reminder in EventReminder::ALL {
+            assert_eq!(
+                i64::from(reminder.lead_minutes()),
+                reminder.lead_time().num_minutes()
+            );
+        }
+    }
+}
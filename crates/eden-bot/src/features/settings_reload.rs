@@ -0,0 +1,156 @@
+use eden_settings::{AlertClass, AlertSeverity, SettingsDiff, SettingsUpdate, SettingsWatcher};
+use eden_utils::Result;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, instrument, warn};
+
+use crate::features::FeatureEvent;
+use crate::interactions::embeds;
+use crate::util::alerts::Alert;
+use crate::Bot;
+
+/// Maximum length of the diff shown in the alert channel
+/// since embed descriptions are limited to 4096 characters.
+const MAX_DIFF_LENGTH: usize = 3800;
+
+const LIVE_CHANGES_NOTE: &str = "Changes to the presence, feature gates, log targets \
+    and span sampling are applied right away.";
+
+/// Applies the settings reloaded by the settings watcher and reports
+/// what has changed to the logs and the alert channel until Eden
+/// shuts down. Settings can also be reloaded right away whenever
+/// Eden receives `SIGHUP` (on Unix systems only).
+///
+/// Only changes to the presence and feature gates are applied by
+/// the bot (log targets and span sampling are applied by the logger).
+/// Other changes are only applied after Eden restarts.
+#[instrument(skip_all)]
+pub async fn listen(bot: Bot, watcher: SettingsWatcher) {
+    let mut updates = watcher.subscribe();
+    let mut sighup = install_sighup();
+
+    loop {
+        tokio::select! {
+            () = recv_sighup(&mut sighup) => watcher.reload(),
+            update = updates.recv() => match update {
+                Ok(update) => {
+                    if let Err(error) = apply(&bot, &update).await {
+                        warn!(%error, "could not apply reloaded settings");
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("missed {skipped} settings update(s)");
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+    }
+}
+
+async fn apply(bot: &Bot, update: &SettingsUpdate) -> Result<()> {
+    let diff = &update.diff;
+    if diff.requires_restart() {
+        warn!(%diff, "some settings changes require a restart");
+    } else {
+        info!(%diff, "settings have been changed");
+    }
+
+    let (previous, current) = (&update.previous.bot, &update.current.bot);
+    if previous.presence != current.presence {
+        debug!("applying presence from the settings");
+        bot.shard_manager
+            .set_presence(current.presence.to_payload())
+            .await;
+    }
+
+    if previous.feature_gates != current.feature_gates {
+        debug!("applying feature gates from the settings");
+        bot.feature_gates
+            .apply_settings(&previous.feature_gates, &current.feature_gates);
+    }
+
+    bot.events.publish(FeatureEvent::SettingsReloaded {
+        requires_restart: diff.requires_restart(),
+    });
+    report(bot, diff).await
+}
+
+#[cfg(target_family = "unix")]
+type Sighup = Option<tokio::signal::unix::Signal>;
+
+#[cfg(not(target_family = "unix"))]
+type Sighup = ();
+
+#[cfg(target_family = "unix")]
+fn install_sighup() -> Sighup {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::hangup()) {
+        Ok(sighup) => Some(sighup),
+        Err(error) => {
+            warn!(%error, "could not install SIGHUP handler. settings are only reloaded once the settings files are modified");
+            None
+        }
+    }
+}
+
+#[cfg(not(target_family = "unix"))]
+fn install_sighup() -> Sighup {}
+
+async fn recv_sighup(sighup: &mut Sighup) {
+    #[cfg(target_family = "unix")]
+    if let Some(sighup) = sighup {
+        if sighup.recv().await.is_some() {
+            return;
+        }
+    }
+    std::future::pending().await
+}
+
+async fn report(bot: &Bot, diff: &SettingsDiff) -> Result<()> {
+    let mut output = diff.to_string();
+    if output.len() > MAX_DIFF_LENGTH {
+        let mut end = MAX_DIFF_LENGTH;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+        output.push_str("\n...");
+    }
+
+    let embed = if diff.requires_restart() {
+        embeds::builders::with_emoji('⛔', "Restart required")
+            .color(embeds::colors::RED)
+            .description(format!(
+                "Some of the changes affect the shard topology or resources made \
+                on startup. **Restart Eden to apply them.** {LIVE_CHANGES_NOTE}\n\
+                ```\n{output}\n```"
+            ))
+    } else {
+        embeds::builders::with_emoji('⚙', "Settings changed")
+            .color(embeds::colors::GREEN)
+            .description(format!(
+                "{LIVE_CHANGES_NOTE} Other changes will be applied once Eden \
+                restarts.\n```\n{output}\n```"
+            ))
+    }
+    .build();
+
+    // some changes need the administrators to restart Eden
+    let severity = if diff.requires_restart() {
+        AlertSeverity::Warning
+    } else {
+        AlertSeverity::Info
+    };
+
+    let embeds = [embed];
+    let alert = Alert {
+        embeds: &embeds,
+        ..Alert::new(AlertClass::System, severity)
+    };
+
+    debug!("sending settings diff");
+    bot.alerter.send(alert).await?;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use eden_tasks::queue_worker::TaskEvent;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{instrument, warn};
+
+use crate::features::FeatureEvent;
+use crate::Bot;
+
+/// Publishes task events from the queue worker to the bot's event bus
+/// as [`FeatureEvent::TaskFinished`] until Eden shuts down.
+///
+/// Manually requeued tasks are not published since they're not
+/// performed yet.
+#[instrument(skip_all)]
+pub async fn forward(bot: Bot, mut events: broadcast::Receiver<TaskEvent>) {
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(TaskEvent::Requeued { .. }) => {}
+                Ok(event) => {
+                    bot.events.publish(FeatureEvent::TaskFinished(event));
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("missed {skipped} task event(s)");
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn should_forward_finished_tasks() {
+        let bot = crate::testkit::generate_fake_bot();
+        let mut recorder = crate::testkit::record_events(&bot);
+
+        let (sender, receiver) = broadcast::channel(4);
+        let kind = String::from("eden::tasks::register_commands");
+        let id = Uuid::new_v4();
+        sender
+            .send(TaskEvent::Requeued {
+                id,
+                kind: kind.clone(),
+            })
+            .unwrap();
+        sender
+            .send(TaskEvent::Completed {
+                id,
+                kind: kind.clone(),
+            })
+            .unwrap();
+
+        drop(sender);
+        forward(bot, receiver).await;
+
+        let expected = FeatureEvent::TaskFinished(TaskEvent::Completed { id, kind });
+        assert_eq!(recorder.drain(), &[expected]);
+    }
+}
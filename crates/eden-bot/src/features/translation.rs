@@ -0,0 +1,281 @@
+use eden_settings::Translation;
+use eden_utils::error::exts::*;
+use eden_utils::types::ProtectedString;
+use eden_utils::{Error, ErrorCategory, Result};
+use futures::future::BoxFuture;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Maximum amount of time to wait for the translation service.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Error)]
+#[error("could not translate message")]
+pub struct TranslateError;
+
+/// Message translated by [`Translator`].
+#[derive(Debug)]
+pub struct Translated {
+    pub text: String,
+    /// Language code of the original message as detected
+    /// by the translation service.
+    pub source_language: String,
+}
+
+/// Translates messages with the service configured
+/// in the `translation` setting.
+pub struct Translator {
+    backend: Box<dyn TranslationBackend>,
+    client: reqwest::Client,
+}
+
+/// Translation service that [`Translator`] sends messages to.
+trait TranslationBackend: Send + Sync {
+    /// Name of the service shown in logs.
+    fn name(&self) -> &'static str;
+
+    /// Translates the text to the language of a Discord locale.
+    fn translate<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        text: &'a str,
+        locale: &'a str,
+    ) -> BoxFuture<'a, Result<Translated, TranslateError>>;
+}
+
+impl Translator {
+    #[must_use]
+    pub fn from_settings(settings: &Translation) -> Self {
+        let backend: Box<dyn TranslationBackend> = match settings {
+            Translation::DeepL { api_key } => Box::new(DeepL {
+                api_key: api_key.clone(),
+            }),
+            Translation::LibreTranslate { url, api_key } => Box::new(LibreTranslate {
+                url: url.trim_end_matches('/').to_string(),
+                api_key: api_key.clone(),
+            }),
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Self { backend, client }
+    }
+
+    /// Translates the text to the language of a Discord locale
+    /// (like `en-US` or `ja`).
+    pub async fn translate(&self, text: &str, locale: &str) -> Result<Translated, TranslateError> {
+        self.backend.translate(&self.client, text, locale).await
+    }
+}
+
+impl Debug for Translator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Translator")
+            .field("backend", &self.backend.name())
+            .finish()
+    }
+}
+
+struct DeepL {
+    api_key: ProtectedString,
+}
+
+impl TranslationBackend for DeepL {
+    fn name(&self) -> &'static str {
+        "deepl"
+    }
+
+    fn translate<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        text: &'a str,
+        locale: &'a str,
+    ) -> BoxFuture<'a, Result<Translated, TranslateError>> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            text: [&'a str; 1],
+            target_lang: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            translations: Vec<ResponseTranslation>,
+        }
+
+        #[derive(Deserialize)]
+        struct ResponseTranslation {
+            text: String,
+            detected_source_language: String,
+        }
+
+        Box::pin(async move {
+            // keys of DeepL API Free accounts only work with the free API
+            let url = if self.api_key.expose().ends_with(":fx") {
+                "https://api-free.deepl.com/v2/translate"
+            } else {
+                "https://api.deepl.com/v2/translate"
+            };
+
+            let request = Request {
+                text: [text],
+                target_lang: deepl_language(locale),
+            };
+            let builder = client.post(url).header(
+                "Authorization",
+                format!("DeepL-Auth-Key {}", self.api_key.expose()),
+            );
+
+            let response: Response = send(builder, &request_body(&request)?).await?;
+            let translation = response.translations.into_iter().next().ok_or_else(|| {
+                Error::context(ErrorCategory::Unknown, TranslateError)
+                    .attach_printable("DeepL returned no translations")
+            })?;
+
+            Ok(Translated {
+                text: translation.text,
+                source_language: translation.detected_source_language,
+            })
+        })
+    }
+}
+
+struct LibreTranslate {
+    url: String,
+    api_key: Option<ProtectedString>,
+}
+
+impl TranslationBackend for LibreTranslate {
+    fn name(&self) -> &'static str {
+        "libretranslate"
+    }
+
+    fn translate<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        text: &'a str,
+        locale: &'a str,
+    ) -> BoxFuture<'a, Result<Translated, TranslateError>> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            q: &'a str,
+            source: &'static str,
+            target: String,
+            format: &'static str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            api_key: Option<&'a str>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            translated_text: String,
+            detected_language: Option<DetectedLanguage>,
+        }
+
+        #[derive(Deserialize)]
+        struct DetectedLanguage {
+            language: String,
+        }
+
+        Box::pin(async move {
+            let request = Request {
+                q: text,
+                source: "auto",
+                target: libre_translate_language(locale),
+                format: "text",
+                api_key: self.api_key.as_ref().map(ProtectedString::expose),
+            };
+
+            let builder = client.post(format!("{}/translate", self.url));
+            let response: Response = send(builder, &request_body(&request)?).await?;
+            Ok(Translated {
+                text: response.translated_text,
+                source_language: response
+                    .detected_language
+                    .map_or_else(|| String::from("unknown"), |v| v.language),
+            })
+        })
+    }
+}
+
+fn request_body(request: &impl Serialize) -> Result<Vec<u8>, TranslateError> {
+    serde_json::to_vec(request)
+        .into_typed_error()
+        .change_context(TranslateError)
+        .attach_printable("could not serialize translation request")
+}
+
+async fn send<T: DeserializeOwned>(
+    builder: reqwest::RequestBuilder,
+    body: &[u8],
+) -> Result<T, TranslateError> {
+    let data = builder
+        .header("Content-Type", "application/json")
+        .body(body.to_vec())
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .into_typed_error()
+        .change_context(TranslateError)
+        .attach_printable("could not send request to the translation service")?
+        .bytes()
+        .await
+        .into_typed_error()
+        .change_context(TranslateError)
+        .attach_printable("could not receive response from the translation service")?;
+
+    serde_json::from_slice(&data)
+        .into_typed_error()
+        .change_context(TranslateError)
+        .attach_printable("got invalid response from the translation service")
+}
+
+/// DeepL only tells apart the regional variants of English,
+/// Portuguese and Chinese.
+fn deepl_language(locale: &str) -> String {
+    match locale {
+        "en-US" | "en-GB" | "pt-BR" => locale.to_uppercase(),
+        "zh-CN" => "ZH-HANS".into(),
+        "zh-TW" => "ZH-HANT".into(),
+        "no" => "NB".into(),
+        _ => language_part(locale).to_uppercase(),
+    }
+}
+
+fn libre_translate_language(locale: &str) -> String {
+    match locale {
+        "zh-TW" => "zt".into(),
+        "no" => "nb".into(),
+        _ => language_part(locale).to_lowercase(),
+    }
+}
+
+fn language_part(locale: &str) -> &str {
+    locale
+        .split_once('-')
+        .map_or(locale, |(language, _)| language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_discord_locales() {
+        assert_eq!(deepl_language("en-US"), "EN-US");
+        assert_eq!(deepl_language("es-ES"), "ES");
+        assert_eq!(deepl_language("zh-TW"), "ZH-HANT");
+        assert_eq!(deepl_language("ja"), "JA");
+
+        assert_eq!(libre_translate_language("pt-BR"), "pt");
+        assert_eq!(libre_translate_language("zh-CN"), "zh");
+        assert_eq!(libre_translate_language("zh-TW"), "zt");
+        assert_eq!(libre_translate_language("no"), "nb");
+    }
+}
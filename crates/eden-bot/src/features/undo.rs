@@ -0,0 +1,303 @@
+use chrono::{TimeDelta, Utc};
+use eden_schema::forms::{InsertCommandAliasForm, InsertUndoEntryForm};
+use eden_schema::types::{
+    CommandAlias, GuildSettings, GuildSnapshot, GuildSnapshotCommandAlias, UndoEntry,
+};
+use eden_tasks::Scheduled;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, warn};
+use twilight_mention::Mention;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::InteractionResponseDataBuilder;
+use uuid::Uuid;
+
+use crate::features::bulk_role::BulkRoleOperation;
+use crate::features::guild_snapshot;
+use crate::interactions::InteractionContext;
+use crate::{tasks, Bot};
+
+/// Prefix of all custom IDs of the undo buttons from `/admin undo`.
+pub const CUSTOM_ID_PREFIX: &str = "undo";
+
+/// How long admin operations can be reverted after they are performed.
+pub const UNDO_WINDOW: TimeDelta = TimeDelta::minutes(30);
+
+const NOT_ALLOWED_MSG: &str = "**You're not allowed to undo admin operations.**";
+const EXPIRED_MSG: &str = "**This operation can no longer be undone.**";
+
+/// Inverse of an admin operation performed through Eden.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UndoAction {
+    /// Restores the guild settings before they were changed.
+    GuildSettings {
+        before: Box<GuildSettings>,
+        after: Box<GuildSettings>,
+    },
+    /// Performs the bulk role operation that reverts a previous
+    /// bulk role operation for the affected members only.
+    BulkRole(Box<BulkRoleOperation>),
+    /// Adds back a removed command alias.
+    CommandAlias(GuildSnapshotCommandAlias),
+    /// Restores the snapshot taken before restoring another snapshot.
+    RestoreSnapshot { restored_id: Uuid, backup_id: Uuid },
+}
+
+impl UndoAction {
+    /// Whether reverting this action changes the command aliases
+    /// of the guild, so they have to be reloaded.
+    #[must_use]
+    fn changes_aliases(&self) -> bool {
+        matches!(self, Self::CommandAlias(..) | Self::RestoreSnapshot { .. })
+    }
+
+    /// Whether reverting this action changes the data kept in
+    /// [guild snapshots](guild_snapshot).
+    #[must_use]
+    fn changes_guild_data(&self) -> bool {
+        !matches!(self, Self::BulkRole(..))
+    }
+}
+
+#[must_use]
+pub fn make_custom_id(entry_id: Uuid) -> String {
+    format!("{CUSTOM_ID_PREFIX}:{entry_id}")
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<Uuid> {
+    let (prefix, id) = custom_id.split_once(':')?;
+    if prefix != CUSTOM_ID_PREFIX {
+        return None;
+    }
+    id.parse().ok()
+}
+
+/// Remembers an admin operation so it can be reverted with `/admin undo`.
+///
+/// It should be called within the same transaction as the operation
+/// so the operation is not remembered if it fails.
+pub async fn record(
+    conn: &mut sqlx::PgConnection,
+    guild_id: Id<GuildMarker>,
+    actor_id: Id<UserMarker>,
+    description: &str,
+    action: &UndoAction,
+) -> Result<()> {
+    let action = serde_json::to_value(action)
+        .into_eden_error()
+        .attach_printable("could not serialize undo action")?;
+
+    let form = InsertUndoEntryForm::builder()
+        .guild_id(guild_id)
+        .actor_id(actor_id)
+        .description(description)
+        .action(&action)
+        .build();
+
+    UndoEntry::delete_before(conn, Utc::now() - UNDO_WINDOW).await?;
+    UndoEntry::insert(conn, form).await?;
+
+    Ok(())
+}
+
+/// Gets the recent admin operations of a guild, newest first.
+pub async fn list(bot: &Bot, guild_id: Id<GuildMarker>) -> Result<Vec<UndoEntry>> {
+    let mut conn = bot.db_read().await?;
+    let entries = UndoEntry::list(&mut conn, guild_id, Utc::now() - UNDO_WINDOW).await?;
+    Ok(entries)
+}
+
+/// Reverts an admin operation within the transaction. It returns
+/// `false` if it is not safe to revert because something has
+/// changed since the operation.
+#[instrument(skip_all)]
+async fn revert(
+    bot: &Bot,
+    conn: &mut sqlx::PgConnection,
+    guild_id: Id<GuildMarker>,
+    action: &UndoAction,
+) -> Result<bool> {
+    match action {
+        UndoAction::GuildSettings { before, after } => {
+            // this locks the row until the transaction ends
+            let current = GuildSettings::upsert(conn, guild_id).await?;
+            if current.data != **after {
+                debug!("guild settings were changed after the operation, not reverting");
+                return Ok(false);
+            }
+
+            debug!("restoring previous guild settings");
+            GuildSettings::update(conn, guild_id, before).await?;
+            Ok(true)
+        }
+        UndoAction::BulkRole(rollback) => {
+            debug!("scheduling bulk role operation to roll back");
+            let task = tasks::PerformBulkRoleOperation {
+                operation: rollback.as_ref().clone(),
+            };
+            bot.queue.schedule(task, Scheduled::now()).await?;
+            Ok(true)
+        }
+        UndoAction::CommandAlias(alias) => {
+            let exists = CommandAlias::list(conn, guild_id)
+                .await?
+                .iter()
+                .any(|v| v.name == alias.name);
+
+            if exists {
+                debug!("command alias was added again after the operation, not reverting");
+                return Ok(false);
+            }
+
+            debug!("adding back command alias {:?}", alias.name);
+            let form = InsertCommandAliasForm::builder()
+                .guild_id(guild_id)
+                .name(&alias.name)
+                .created_by(alias.created_by)
+                .command(&alias.command)
+                .arguments(alias.arguments.clone())
+                .build();
+
+            CommandAlias::upsert(conn, form).await?;
+            Ok(true)
+        }
+        UndoAction::RestoreSnapshot {
+            restored_id,
+            backup_id,
+        } => {
+            let restored = GuildSnapshot::from_id(conn, guild_id, *restored_id).await?;
+            let backup = GuildSnapshot::from_id(conn, guild_id, *backup_id).await?;
+            let (Some(restored), Some(backup)) = (restored, backup) else {
+                debug!("snapshots have expired, not reverting");
+                return Ok(false);
+            };
+
+            // this locks the row until the transaction ends
+            let current = GuildSettings::upsert(conn, guild_id).await?;
+            if current.data != restored.data.settings {
+                debug!("guild settings were changed after the operation, not reverting");
+                return Ok(false);
+            }
+
+            debug!("restoring snapshot taken before restoring another snapshot");
+            guild_snapshot::restore(conn, &backup).await?;
+            Ok(true)
+        }
+    }
+}
+
+/// Reverts an admin operation after pressing one of the
+/// undo buttons from `/admin undo`.
+#[instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_undo(ctx: &InteractionContext<MessageComponentInteractionData>) -> Result<()> {
+    let Some(entry_id) = parse_custom_id(&ctx.data.custom_id) else {
+        warn!("got invalid undo custom id");
+        return Ok(());
+    };
+
+    let is_allowed = ctx
+        .interaction
+        .member
+        .as_ref()
+        .and_then(|v| v.permissions)
+        .is_some_and(|v| v.contains(Permissions::ADMINISTRATOR));
+
+    let Some(guild_id) = ctx.interaction.guild_id.filter(|_| is_allowed) else {
+        return respond_ephemeral(ctx, NOT_ALLOWED_MSG.into()).await;
+    };
+
+    let since = Utc::now() - UNDO_WINDOW;
+    let mut conn = ctx.bot.db_write().await?;
+    let Some(entry) = UndoEntry::take(&mut conn, guild_id, entry_id, since).await? else {
+        return respond_ephemeral(ctx, EXPIRED_MSG.into()).await;
+    };
+
+    let action = match serde_json::from_value::<UndoAction>(entry.action.clone()) {
+        Ok(action) => action,
+        Err(error) => {
+            warn!(%error, "got invalid undo action of entry {}", entry.id);
+            return respond_ephemeral(ctx, EXPIRED_MSG.into()).await;
+        }
+    };
+
+    if action.changes_guild_data() {
+        let reason = format!("Before undoing \"{}\"", entry.description);
+        guild_snapshot::take(&mut conn, guild_id, ctx.invoker_id(), &reason).await?;
+    }
+
+    // the entry and the snapshot are discarded if it cannot be reverted yet
+    if !revert(&ctx.bot, &mut conn, guild_id, &action).await? {
+        drop(conn);
+
+        let content = format!(
+            "**Cannot undo \"{}\"** because it has been changed since then.",
+            entry.description
+        );
+        return respond_ephemeral(ctx, content).await;
+    }
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    ctx.bot.invalidate_guild_settings(guild_id);
+    if action.changes_aliases() {
+        let mut conn = ctx.bot.db_read().await?;
+        let aliases = CommandAlias::list(&mut conn, guild_id).await?;
+        ctx.bot.command_aliases.replace_all(aliases);
+    }
+
+    let data = InteractionResponseDataBuilder::new()
+        .content(format!(
+            "**Reverted \"{}\"** (undone by {}).",
+            entry.description,
+            ctx.invoker_id().mention()
+        ))
+        .build();
+
+    ctx.respond(data).await
+}
+
+async fn respond_ephemeral<T>(ctx: &InteractionContext<T>, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_custom_ids() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_custom_id(&make_custom_id(id)), Some(id));
+        assert_eq!(parse_custom_id("lockdown:abc"), None);
+    }
+
+    #[test]
+    fn should_serialize_actions() {
+        let action = UndoAction::RestoreSnapshot {
+            restored_id: Uuid::new_v4(),
+            backup_id: Uuid::new_v4(),
+        };
+
+        let value = serde_json::to_value(&action).unwrap();
+        assert_eq!(value["type"], "restore_snapshot");
+
+        let action = serde_json::from_value::<UndoAction>(value).unwrap();
+        assert!(action.changes_aliases());
+        assert!(action.changes_guild_data());
+    }
+}
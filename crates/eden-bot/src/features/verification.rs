@@ -0,0 +1,360 @@
+use eden_schema::types::{VerificationChallenge, VerificationMode};
+use eden_tasks::Scheduled;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use tracing::{debug, instrument, trace, warn};
+use twilight_mention::Mention;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::{Member, Permissions};
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::InteractionResponseDataBuilder;
+use uuid::Uuid;
+
+use crate::events::EventContext;
+use crate::interactions::{InteractionContext, LocalGuildContext};
+use crate::tasks;
+use crate::util::http::{request_for_empty, request_for_model};
+
+/// Prefix of all custom IDs of the verification prompt buttons.
+pub const CUSTOM_ID_PREFIX: &str = "verification";
+
+const CAPTCHA_CHOICES: usize = 4;
+
+const NOT_YOUR_PROMPT_MSG: &str = "**This verification prompt is not for you.**";
+const EXPIRED_MSG: &str =
+    "**This verification prompt has expired.** Please contact the server administrators.";
+const DISABLED_MSG: &str = "**Verification is currently disabled in this server.**";
+const WRONG_ANSWER_MSG: &str = "**Wrong code!** Please press the button with the code shown above.";
+const VERIFIED_MSG: &str = "**You're now verified!** Welcome to the server!";
+const FAILED_MSG: &str =
+    "**Sorry, I cannot verify you at the moment.** Please contact the server administrators.";
+
+/// Parsed custom ID of a pressed verification prompt button.
+#[derive(Debug, PartialEq, Eq)]
+struct PressedButton {
+    nonce: Uuid,
+    choice: String,
+}
+
+fn make_custom_id(nonce: Uuid, choice: &str) -> String {
+    format!("{CUSTOM_ID_PREFIX}:{nonce}:{choice}")
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<PressedButton> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != CUSTOM_ID_PREFIX {
+        return None;
+    }
+
+    let nonce = Uuid::parse_str(parts.next()?).ok()?;
+    let choice = parts.next()?.to_string();
+    Some(PressedButton { nonce, choice })
+}
+
+fn button(custom_id: String, label: String, style: ButtonStyle) -> Component {
+    Component::Button(Button {
+        custom_id: Some(custom_id),
+        disabled: false,
+        emoji: None,
+        label: Some(label),
+        style,
+        url: None,
+    })
+}
+
+/// Choices of a verification prompt and which one is correct.
+///
+/// The answer is only kept in the database, so it cannot be
+/// found from the custom IDs of the prompt's buttons.
+#[derive(Debug)]
+struct Challenge {
+    answer: String,
+    choices: Vec<String>,
+}
+
+impl Challenge {
+    const BUTTON_ANSWER: &'static str = "verify";
+
+    fn generate(mode: VerificationMode, rng: &mut impl Rng) -> Self {
+        match mode {
+            VerificationMode::Button => Self {
+                answer: Self::BUTTON_ANSWER.into(),
+                choices: vec![Self::BUTTON_ANSWER.into()],
+            },
+            VerificationMode::Captcha => {
+                let mut choices = Vec::with_capacity(CAPTCHA_CHOICES);
+                while choices.len() < CAPTCHA_CHOICES {
+                    let code = rng.gen_range(1000..=9999).to_string();
+                    if !choices.contains(&code) {
+                        choices.push(code);
+                    }
+                }
+
+                let answer = choices[0].clone();
+                choices.shuffle(rng);
+                Self { answer, choices }
+            }
+        }
+    }
+}
+
+/// Builds the content and buttons of the verification prompt
+/// for a new member depending on the verification mode.
+fn build_prompt(
+    user_id: Id<UserMarker>,
+    mode: VerificationMode,
+    nonce: Uuid,
+    challenge: &Challenge,
+) -> (String, Vec<Component>) {
+    let mention = user_id.mention();
+    match mode {
+        VerificationMode::Button => {
+            let content = format!(
+                "**Welcome {mention}!** Please press the button below to verify yourself and get access to the rest of the server."
+            );
+            let buttons = vec![button(
+                make_custom_id(nonce, Challenge::BUTTON_ANSWER),
+                "Verify".into(),
+                ButtonStyle::Success,
+            )];
+            (content, buttons)
+        }
+        VerificationMode::Captcha => {
+            let content = format!(
+                "**Welcome {mention}!** Please press the button with the code **{}** to verify yourself and get access to the rest of the server.",
+                challenge.answer
+            );
+
+            let buttons = challenge
+                .choices
+                .iter()
+                .map(|code| {
+                    button(
+                        make_custom_id(nonce, code),
+                        code.clone(),
+                        ButtonStyle::Secondary,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            (content, buttons)
+        }
+    }
+}
+
+/// Sends a verification prompt to a new member in the local guild
+/// and schedules to kick the member if it is still unverified
+/// after the configured period of time.
+#[instrument(skip_all, fields(%guild_id, member.id = %member.user.id))]
+pub async fn on_member_add(ctx: &EventContext, guild_id: Id<GuildMarker>, member: &Member) {
+    if member.user.bot || !ctx.bot.is_local_guild(&guild_id) {
+        return;
+    }
+
+    if let Err(error) = send_prompt(ctx, guild_id, member).await {
+        warn!(%error, "could not send verification prompt to the new member");
+    }
+}
+
+async fn send_prompt(ctx: &EventContext, guild_id: Id<GuildMarker>, member: &Member) -> Result<()> {
+    let settings = ctx.bot.guild_settings(guild_id).await?;
+
+    let verification = &settings.verification;
+    let Some(channel_id) = verification.channel_id.filter(|_| verification.is_active()) else {
+        trace!("verification is disabled, skipping");
+        return Ok(());
+    };
+
+    let needed = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+    if !ctx
+        .bot
+        .preflight_permissions(guild_id, channel_id, needed)
+        .await?
+    {
+        warn!("bot is lacking permissions to send verification prompts in {channel_id}");
+        return Ok(());
+    }
+
+    let user_id = member.user.id;
+    let challenge = Challenge::generate(verification.mode, &mut rand::thread_rng());
+
+    let mut conn = ctx.bot.db_write().await?;
+    let row =
+        VerificationChallenge::insert(&mut conn, guild_id, user_id, &challenge.answer).await?;
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    let (content, buttons) = build_prompt(user_id, verification.mode, row.nonce, &challenge);
+    let components = vec![Component::ActionRow(ActionRow {
+        components: buttons,
+    })];
+
+    debug!("sending verification prompt to member {user_id}");
+    let request = ctx
+        .bot
+        .http
+        .create_message(channel_id)
+        .content(&content)
+        .into_typed_error()
+        .anonymize_error()?
+        .components(&components)
+        .into_typed_error()
+        .anonymize_error()?;
+
+    request_for_model(&ctx.bot, request)
+        .await
+        .attach_printable("could not send verification prompt")?;
+
+    if let Some(minutes) = verification.kick_after_minutes {
+        let task = tasks::KickUnverifiedMember { guild_id, user_id };
+        ctx.bot
+            .queue
+            .schedule(task, Scheduled::in_minutes(i64::from(minutes)))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Verifies the member after pressing one of the verification
+/// prompt's buttons.
+#[instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_verify(ctx: &InteractionContext<MessageComponentInteractionData>) -> Result<()> {
+    let Some(pressed) = parse_custom_id(&ctx.data.custom_id) else {
+        warn!("got invalid verification custom id");
+        return Ok(());
+    };
+
+    let ctx = LocalGuildContext::from_ctx(ctx).await?;
+    let mut conn = ctx.bot.db_read().await?;
+    let challenge = VerificationChallenge::from_nonce(&mut conn, pressed.nonce).await?;
+    drop(conn);
+
+    let Some(challenge) = challenge.filter(|v| v.guild_id == ctx.guild_id) else {
+        return respond_ephemeral(ctx.inner, EXPIRED_MSG).await;
+    };
+
+    if ctx.author.id != challenge.user_id {
+        return respond_ephemeral(ctx.inner, NOT_YOUR_PROMPT_MSG).await;
+    }
+
+    let verification = &ctx.settings.verification;
+    let Some(role_id) = verification.role_id.filter(|_| verification.is_active()) else {
+        return respond_ephemeral(ctx.inner, DISABLED_MSG).await;
+    };
+
+    if pressed.choice != challenge.answer {
+        return respond_ephemeral(ctx.inner, WRONG_ANSWER_MSG).await;
+    }
+
+    debug!("verifying member {}", ctx.author.id);
+
+    let request = ctx
+        .bot
+        .http
+        .add_guild_member_role(ctx.guild_id, ctx.author.id, role_id);
+
+    let result = request_for_empty(&ctx.bot, request)
+        .await
+        .attach_printable("could not give verified role to member");
+
+    if result.is_err() {
+        respond_ephemeral(ctx.inner, FAILED_MSG).await?;
+    }
+    result?;
+
+    respond_ephemeral(ctx.inner, VERIFIED_MSG).await?;
+
+    let mut conn = ctx.bot.db_write().await?;
+    VerificationChallenge::delete_for_member(&mut conn, ctx.guild_id, ctx.author.id).await?;
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    // auto roles are withheld until the member is verified
+    let result =
+        crate::features::auto_role::schedule(&ctx.bot, ctx.guild_id, ctx.author.id, &ctx.settings)
+            .await;
+
+    if let Err(error) = result {
+        warn!(%error, "could not give auto roles to the verified member");
+    }
+
+    // the verification prompt is no longer needed
+    if let Some(message) = ctx.interaction.message.as_ref() {
+        let request = ctx.bot.http.delete_message(message.channel_id, message.id);
+        if let Err(error) = request_for_empty(&ctx.bot, request).await {
+            warn!(error = %error.anonymize(), "could not delete verification prompt");
+        }
+    }
+
+    Ok(())
+}
+
+async fn respond_ephemeral<T>(ctx: &InteractionContext<T>, content: &str) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn should_parse_custom_ids() {
+        let nonce = Uuid::new_v4();
+        assert_eq!(
+            parse_custom_id(&make_custom_id(nonce, "1234")),
+            Some(PressedButton {
+                nonce,
+                choice: "1234".into()
+            })
+        );
+        assert_eq!(parse_custom_id(&format!("payer:{nonce}:1234")), None);
+        assert_eq!(parse_custom_id("verification:abc:1234"), None);
+    }
+
+    #[test]
+    fn captcha_should_not_reveal_answer_in_custom_ids() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let challenge = Challenge::generate(VerificationMode::Captcha, &mut rng);
+        assert_eq!(challenge.choices.len(), CAPTCHA_CHOICES);
+        assert!(challenge.choices.contains(&challenge.answer));
+
+        let nonce = Uuid::new_v4();
+        let (_, buttons) = build_prompt(Id::new(1), VerificationMode::Captcha, nonce, &challenge);
+        let pressed = buttons
+            .iter()
+            .filter_map(|v| match v {
+                Component::Button(button) => button.custom_id.as_deref(),
+                _ => None,
+            })
+            .filter_map(parse_custom_id)
+            .collect::<Vec<_>>();
+
+        // every button only carries the nonce and its own code
+        assert_eq!(pressed.len(), CAPTCHA_CHOICES);
+        assert!(pressed.iter().all(|v| v.nonce == nonce));
+        assert_eq!(
+            pressed
+                .iter()
+                .filter(|v| v.choice == challenge.answer)
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use dashmap::DashMap;
+use eden_schema::types::VoiceStat;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use tracing::{debug, instrument, trace};
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_model::voice::VoiceState;
+
+use crate::events::EventContext;
+use crate::interactions::response_cache::CachedCommand;
+use crate::Bot;
+
+type SessionKey = (Id<GuildMarker>, Id<UserMarker>);
+type AggregateKey = (Id<GuildMarker>, Id<UserMarker>, NaiveDate);
+
+/// Keeps track of members currently in voice channels and their
+/// voice activity durations per day that are not saved into the
+/// database yet.
+#[derive(Debug, Default)]
+pub struct VoiceSessions {
+    /// When the session started or last checkpointed.
+    sessions: DashMap<SessionKey, DateTime<Utc>>,
+    pending: DashMap<AggregateKey, i64>,
+}
+
+impl VoiceSessions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new voice session of a member. Moving to another
+    /// voice channel continues the existing session.
+    pub fn join(&self, guild_id: Id<GuildMarker>, user_id: Id<UserMarker>, now: DateTime<Utc>) {
+        self.sessions.entry((guild_id, user_id)).or_insert(now);
+    }
+
+    /// Ends the voice session of a member (if there's any).
+    pub fn leave(&self, guild_id: Id<GuildMarker>, user_id: Id<UserMarker>, now: DateTime<Utc>) {
+        if let Some((_, started_at)) = self.sessions.remove(&(guild_id, user_id)) {
+            self.record(guild_id, user_id, started_at, now);
+        }
+    }
+
+    /// Moves the durations of all ongoing sessions up to `now` into
+    /// the pending daily aggregates.
+    pub fn checkpoint(&self, now: DateTime<Utc>) {
+        for mut entry in self.sessions.iter_mut() {
+            let (guild_id, user_id) = *entry.key();
+            self.record(guild_id, user_id, *entry.value(), now);
+            *entry.value_mut() = now;
+        }
+    }
+
+    #[must_use]
+    pub fn active_sessions(&self) -> usize {
+        self.sessions.len()
+    }
+
+    fn record(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) {
+        for (day, seconds) in split_by_day(start, end) {
+            *self.pending.entry((guild_id, user_id, day)).or_default() += seconds;
+        }
+    }
+
+    fn take_pending(&self) -> Vec<(AggregateKey, i64)> {
+        let keys = self.pending.iter().map(|v| *v.key()).collect::<Vec<_>>();
+        keys.into_iter()
+            .filter_map(|key| self.pending.remove(&key))
+            .collect()
+    }
+
+    fn restore_pending(&self, entries: Vec<(AggregateKey, i64)>) {
+        for (key, seconds) in entries {
+            *self.pending.entry(key).or_default() += seconds;
+        }
+    }
+}
+
+/// Splits the duration between `start` and `end` into seconds
+/// spent per day (in UTC).
+fn split_by_day(start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<(NaiveDate, i64)> {
+    let mut output = Vec::new();
+    let mut current = start;
+    while current < end {
+        let day = current.date_naive();
+        let next_day = day
+            .succ_opt()
+            .and_then(|v| v.and_hms_opt(0, 0, 0))
+            .map(|v| v.and_utc())
+            .unwrap_or(end);
+
+        let segment_end = next_day.min(end);
+        let seconds = (segment_end - current).num_seconds();
+        if seconds > 0 {
+            output.push((day, seconds));
+        }
+        current = segment_end;
+    }
+    output
+}
+
+#[instrument(skip_all, fields(
+    state.channel_id = ?state.channel_id,
+    state.guild_id = ?state.guild_id,
+    state.user_id = %state.user_id,
+))]
+pub fn on_voice_state_update(ctx: &EventContext, state: &VoiceState) {
+    let Some(guild_id) = state.guild_id else {
+        return;
+    };
+
+    let is_bot = state.member.as_ref().is_some_and(|v| v.user.bot);
+    if is_bot || !ctx.bot.is_local_guild(&guild_id) {
+        return;
+    }
+
+    let now = Utc::now();
+    if state.channel_id.is_some() {
+        trace!("member joined or moved voice channel");
+        ctx.bot.voice_sessions.join(guild_id, state.user_id, now);
+    } else {
+        trace!("member left voice channel");
+        ctx.bot.voice_sessions.leave(guild_id, state.user_id, now);
+    }
+}
+
+/// Saves all pending voice activity durations into the database.
+#[instrument(skip_all)]
+pub async fn flush(bot: &Bot) -> Result<()> {
+    bot.voice_sessions.checkpoint(Utc::now());
+
+    let pending = bot.voice_sessions.take_pending();
+    if pending.is_empty() {
+        trace!("no voice stats to flush");
+        return Ok(());
+    }
+
+    let result = save(bot, &pending).await;
+    if result.is_err() {
+        // we can try again later
+        bot.voice_sessions.restore_pending(pending);
+    } else {
+        debug!("flushed {} voice stat(s)", pending.len());
+        bot.response_cache.bust(CachedCommand::VoiceStats);
+    }
+
+    result
+}
+
+async fn save(bot: &Bot, entries: &[(AggregateKey, i64)]) -> Result<()> {
+    let mut conn = bot.db_write().await?;
+    for ((guild_id, user_id, day), seconds) in entries {
+        VoiceStat::add(&mut conn, *guild_id, *user_id, *day, *seconds).await?;
+    }
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    Ok(())
+}
+
+/// Formats voice activity duration into a human readable format.
+#[must_use]
+pub fn format_duration(seconds: i64) -> String {
+    let duration = TimeDelta::seconds(seconds);
+    let hours = duration.num_hours();
+    let minutes = duration.num_minutes() % 60;
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(day: u32, hour: u32, min: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2024, 8, day)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn should_split_durations_by_day() {
+        assert_eq!(
+            split_by_day(time(15, 23, 30), time(16, 0, 45)),
+            vec![
+                (time(15, 0, 0).date_naive(), 30 * 60),
+                (time(16, 0, 0).date_naive(), 45 * 60),
+            ]
+        );
+        assert_eq!(split_by_day(time(15, 1, 0), time(15, 1, 0)), vec![]);
+    }
+
+    #[test]
+    fn should_aggregate_sessions() {
+        let sessions = VoiceSessions::new();
+        let guild_id = Id::new(1);
+        let user_id = Id::new(2);
+
+        sessions.join(guild_id, user_id, time(15, 1, 0));
+        sessions.checkpoint(time(15, 1, 10));
+
+        // moving channels should not restart the session
+        sessions.join(guild_id, user_id, time(15, 1, 15));
+        sessions.leave(guild_id, user_id, time(15, 1, 30));
+
+        let pending = sessions.take_pending();
+        assert_eq!(
+            pending,
+            vec![((guild_id, user_id, time(15, 0, 0).date_naive()), 30 * 60)]
+        );
+        assert_eq!(sessions.active_sessions(), 0);
+    }
+
+    #[test]
+    fn format_durations() {
+        assert_eq!(format_duration(59), "0m");
+        assert_eq!(format_duration(3 * 3600 + 20 * 60), "3h 20m");
+    }
+}
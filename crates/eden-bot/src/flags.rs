@@ -1,19 +1,75 @@
+use eden_settings::CacheResource;
 use twilight_cache_inmemory::ResourceType;
 use twilight_gateway::{EventTypeFlags, Intents};
 
-pub const CACHE_RESOURCE_TYPES: ResourceType = ResourceType::GUILD
-    .union(ResourceType::USER)
-    .union(ResourceType::USER_CURRENT)
-    .union(ResourceType::CHANNEL);
+/// Converts cache resources from `bot.http.cache_resources`
+/// into resource type flags of the cache.
+#[must_use]
+pub fn cache_resource_types(resources: &[CacheResource]) -> ResourceType {
+    resources
+        .iter()
+        .fold(ResourceType::empty(), |flags, resource| {
+            flags.union(match resource {
+                CacheResource::Channel => ResourceType::CHANNEL,
+                CacheResource::Emoji => ResourceType::EMOJI,
+                CacheResource::Guild => ResourceType::GUILD,
+                CacheResource::GuildScheduledEvent => ResourceType::GUILD_SCHEDULED_EVENT,
+                CacheResource::Integration => ResourceType::INTEGRATION,
+                CacheResource::Member => ResourceType::MEMBER,
+                CacheResource::Message => ResourceType::MESSAGE,
+                CacheResource::Presence => ResourceType::PRESENCE,
+                CacheResource::Reaction => ResourceType::REACTION,
+                CacheResource::Role => ResourceType::ROLE,
+                CacheResource::StageInstance => ResourceType::STAGE_INSTANCE,
+                CacheResource::Sticker => ResourceType::STICKER,
+                CacheResource::User => ResourceType::USER,
+                CacheResource::UserCurrent => ResourceType::USER_CURRENT,
+                CacheResource::VoiceState => ResourceType::VOICE_STATE,
+            })
+        })
+}
 
 pub const INTENTS: Intents = Intents::GUILDS
     .union(Intents::DIRECT_MESSAGES)
     .union(Intents::GUILD_MEMBERS)
     .union(Intents::GUILD_MESSAGES)
+    .union(Intents::GUILD_SCHEDULED_EVENTS)
+    .union(Intents::GUILD_VOICE_STATES)
     .union(Intents::MESSAGE_CONTENT);
 
 pub const FILTERED_EVENT_TYPES: EventTypeFlags = EventTypeFlags::READY
     .union(EventTypeFlags::RESUMED)
     .union(EventTypeFlags::INTERACTION_CREATE)
+    .union(EventTypeFlags::CHANNEL_CREATE)
+    .union(EventTypeFlags::CHANNEL_DELETE)
+    .union(EventTypeFlags::CHANNEL_UPDATE)
     .union(EventTypeFlags::DIRECT_MESSAGES)
-    .union(EventTypeFlags::GUILD_CREATE);
+    .union(EventTypeFlags::GUILD_CREATE)
+    .union(EventTypeFlags::GUILD_UPDATE)
+    .union(EventTypeFlags::GUILD_SCHEDULED_EVENT_CREATE)
+    .union(EventTypeFlags::GUILD_SCHEDULED_EVENT_DELETE)
+    .union(EventTypeFlags::GUILD_SCHEDULED_EVENT_UPDATE)
+    .union(EventTypeFlags::MEMBER_ADD)
+    .union(EventTypeFlags::MEMBER_UPDATE)
+    .union(EventTypeFlags::ROLE_CREATE)
+    .union(EventTypeFlags::ROLE_DELETE)
+    .union(EventTypeFlags::ROLE_UPDATE)
+    .union(EventTypeFlags::VOICE_STATE_UPDATE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_cache_resources() {
+        assert_eq!(cache_resource_types(&[]), ResourceType::empty());
+        assert_eq!(
+            cache_resource_types(CacheResource::DEFAULT),
+            ResourceType::GUILD
+                | ResourceType::USER
+                | ResourceType::USER_CURRENT
+                | ResourceType::CHANNEL
+                | ResourceType::ROLE
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Display;
+use twilight_model::application::command::{Command, CommandOption, CommandType};
+
+/// Differences between the commands registered in Discord
+/// and the commands defined in Eden.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CommandsDiff {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl CommandsDiff {
+    #[must_use]
+    pub fn compute(existing: &[Command], desired: &[Command]) -> Self {
+        let find = |list: &[Command], target: &Command| {
+            list.iter()
+                .find(|v| v.name == target.name && v.kind == target.kind)
+                .cloned()
+        };
+
+        let mut diff = Self::default();
+        for command in desired {
+            match find(existing, command) {
+                Some(registered) if !is_same_command(&registered, command) => {
+                    diff.changed.push(display_name(command));
+                }
+                Some(..) => {}
+                None => diff.added.push(display_name(command)),
+            }
+        }
+
+        for command in existing {
+            if find(desired, command).is_none() {
+                diff.removed.push(display_name(command));
+            }
+        }
+
+        diff
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl Display for CommandsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "added: {:?}, changed: {:?}, removed: {:?}",
+            self.added, self.changed, self.removed
+        )
+    }
+}
+
+fn display_name(command: &Command) -> String {
+    match command.kind {
+        CommandType::ChatInput => format!("/{}", command.name),
+        _ => command.name.clone(),
+    }
+}
+
+/// Compares the command definitions only, ignoring any metadata
+/// given by Discord such as IDs and versions.
+pub(super) fn is_same_command(registered: &Command, desired: &Command) -> bool {
+    // Discord only keeps `dm_permission` for global commands
+    let same_dm_permission = registered.guild_id.is_some()
+        || registered.dm_permission.unwrap_or(true) == desired.dm_permission.unwrap_or(true);
+
+    same_dm_permission
+        && registered.kind == desired.kind
+        && registered.name == desired.name
+        && registered.description == desired.description
+        && registered.default_member_permissions == desired.default_member_permissions
+        && registered.nsfw.unwrap_or_default() == desired.nsfw.unwrap_or_default()
+        && registered
+            .name_localizations
+            .as_ref()
+            .filter(|v| !v.is_empty())
+            == desired
+                .name_localizations
+                .as_ref()
+                .filter(|v| !v.is_empty())
+        && registered
+            .description_localizations
+            .as_ref()
+            .filter(|v| !v.is_empty())
+            == desired
+                .description_localizations
+                .as_ref()
+                .filter(|v| !v.is_empty())
+        && normalize_options(&registered.options) == normalize_options(&desired.options)
+}
+
+/// Discord omits optional fields with default values from the registered
+/// commands, so both sides have to be normalized before comparing them.
+fn normalize_options(options: &[CommandOption]) -> Vec<CommandOption> {
+    options
+        .iter()
+        .cloned()
+        .map(|mut option| {
+            option.autocomplete = Some(option.autocomplete.unwrap_or_default());
+            option.required = Some(option.required.unwrap_or_default());
+            option.channel_types = option.channel_types.filter(|v| !v.is_empty());
+            option.choices = option.choices.filter(|v| !v.is_empty());
+            option.name_localizations = option.name_localizations.filter(|v| !v.is_empty());
+            option.description_localizations =
+                option.description_localizations.filter(|v| !v.is_empty());
+            option.options = option
+                .options
+                .as_deref()
+                .map(normalize_options)
+                .filter(|v| !v.is_empty());
+            option
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+
+    fn command(name: &str, description: &str) -> Command {
+        CommandBuilder::new(name, description, CommandType::ChatInput)
+            .option(StringBuilder::new("input", "Some input"))
+            .build()
+    }
+
+    #[test]
+    fn should_ignore_omitted_defaults() {
+        let desired = command("ping", "Pong!");
+
+        let mut registered = desired.clone();
+        registered.options[0].autocomplete = None;
+        registered.options[0].required = None;
+        registered.options[0].choices = Some(Vec::new());
+
+        let diff = CommandsDiff::compute(&[registered], &[desired]);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn should_compute_diff() {
+        let existing = [command("ping", "Pong!"), command("old", "Old command")];
+        let desired = [command("ping", "Pong?"), command("new", "New command")];
+
+        let diff = CommandsDiff::compute(&existing, &desired);
+        assert_eq!(
+            diff,
+            CommandsDiff {
+                added: vec!["/new".into()],
+                changed: vec!["/ping".into()],
+                removed: vec!["/old".into()],
+            }
+        );
+    }
+}
@@ -0,0 +1,485 @@
+use eden_discord_types::commands::{self, local_guild::HelpCommand};
+use eden_utils::Result;
+use std::fmt::Write as _;
+use tracing::{trace, warn};
+use twilight_interactions::command::CreateCommand;
+use twilight_model::application::command::{Command, CommandOption, CommandOptionType};
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::application::interaction::Interaction;
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::guild::Permissions;
+use twilight_model::http::interaction::InteractionResponseData;
+use twilight_util::builder::embed::{EmbedFieldBuilder, EmbedFooterBuilder};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{embeds, InteractionContext};
+use crate::Bot;
+
+/// Prefix of the custom ID of the page buttons.
+pub const CUSTOM_ID_PREFIX: &str = "help";
+
+const NO_COMMANDS_MSG: &str = "**There are no commands you can use at the moment.**";
+const SEE_ALL_COMMANDS_MSG: &str = "Use `/help` to see every command you can use.";
+
+/// Groups commands shown in `/help`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HelpCategory {
+    General,
+    Payer,
+    Moderation,
+    Administration,
+}
+
+impl HelpCategory {
+    const ALL: [Self; 4] = [
+        Self::General,
+        Self::Payer,
+        Self::Moderation,
+        Self::Administration,
+    ];
+
+    #[must_use]
+    pub fn emoji(self) -> char {
+        match self {
+            Self::General => '📖',
+            Self::Payer => '💸',
+            Self::Moderation => '🛡',
+            Self::Administration => '⚙',
+        }
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::General => "General",
+            Self::Payer => "Monthly contributors",
+            Self::Moderation => "Moderation",
+            Self::Administration => "Administration",
+        }
+    }
+}
+
+/// Extended information about a command shown in `/help`
+/// on top of its description from [`CreateCommand`].
+#[derive(Debug, Clone, Copy)]
+pub struct CommandHelp {
+    pub category: HelpCategory,
+    /// Longer explanation of what the command does.
+    pub details: Option<&'static str>,
+    /// Example usages of the command and its subcommands
+    /// (like `/payer register`).
+    pub examples: &'static [&'static str],
+    /// Permissions the invoker must have to see this command from
+    /// `/help`. It does not stop anyone from using the command.
+    pub permissions: Permissions,
+}
+
+impl CommandHelp {
+    #[must_use]
+    pub const fn new(category: HelpCategory) -> Self {
+        Self {
+            category,
+            details: None,
+            examples: &[],
+            permissions: Permissions::empty(),
+        }
+    }
+
+    #[must_use]
+    pub const fn details(mut self, details: &'static str) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    #[must_use]
+    pub const fn examples(mut self, examples: &'static [&'static str]) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    #[must_use]
+    pub const fn permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct HelpEntry {
+    command: Command,
+    help: CommandHelp,
+}
+
+fn all_entries() -> Vec<HelpEntry> {
+    macro_rules! entries {
+        [ $($command:ty),* $(,)? ] => {
+            vec![$( HelpEntry {
+                command: <$command as CreateCommand>::create_command().into(),
+                help: <$command as RunCommand>::HELP,
+            }, )*]
+        };
+    }
+
+    entries![
+        commands::local_guild::AdminCommand,
+        commands::local_guild::EmojiCommand,
+        commands::local_guild::HelpCommand,
+        commands::local_guild::PayerCommand,
+        commands::local_guild::PreferencesCommand,
+        commands::local_guild::RoleCommand,
+        commands::local_guild::SettingsCommand,
+        commands::local_guild::StatsCommand,
+        commands::Ping
+    ]
+}
+
+/// Gets every command the invoker can see and use.
+fn visible_entries(bot: &Bot, interaction: &Interaction) -> Vec<HelpEntry> {
+    let member = interaction.member.as_ref();
+    let permissions = member
+        .and_then(|v| v.permissions)
+        .unwrap_or_else(Permissions::empty);
+
+    let roles = member.map(|v| v.roles.as_slice()).unwrap_or_default();
+    let user_id = interaction.author_id();
+
+    all_entries()
+        .into_iter()
+        .filter(|entry| permissions.contains(entry.help.permissions))
+        .filter(|entry| {
+            user_id.map_or(true, |user_id| {
+                bot.feature_gates
+                    .is_command_enabled_for(&entry.command.name, user_id, roles)
+            })
+        })
+        .collect()
+}
+
+/// Groups commands by their category. Categories
+/// without any commands are excluded.
+fn pages(entries: &[HelpEntry]) -> Vec<(HelpCategory, Vec<&HelpEntry>)> {
+    HelpCategory::ALL
+        .into_iter()
+        .map(|category| {
+            let entries = entries
+                .iter()
+                .filter(|v| v.help.category == category)
+                .collect::<Vec<_>>();
+
+            (category, entries)
+        })
+        .filter(|(_, entries)| !entries.is_empty())
+        .collect()
+}
+
+/// Collects the full path and description of every subcommand.
+fn collect_subcommands<'a>(
+    parent: &str,
+    options: &'a [CommandOption],
+    output: &mut Vec<(String, &'a str)>,
+) {
+    for option in options {
+        let path = format!("{parent} {}", option.name);
+        match option.kind {
+            CommandOptionType::SubCommand => output.push((path, &option.description)),
+            CommandOptionType::SubCommandGroup => {
+                let options = option.options.as_deref().unwrap_or_default();
+                collect_subcommands(&path, options, output);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Finds the command (or subcommand) from a query like
+/// `/payer register`. It returns the normalized path, its
+/// description, options and the top-level command's entry.
+fn find_command<'a>(
+    entries: &'a [HelpEntry],
+    query: &str,
+) -> Option<(String, &'a str, &'a [CommandOption], &'a HelpEntry)> {
+    let query = query.trim().trim_start_matches('/').to_lowercase();
+    let mut segments = query.split_whitespace();
+
+    let name = segments.next()?;
+    let entry = entries.iter().find(|v| v.command.name == name)?;
+
+    let mut path = entry.command.name.clone();
+    let mut description = entry.command.description.as_str();
+    let mut options = entry.command.options.as_slice();
+    for segment in segments {
+        let option = options.iter().find(|v| {
+            v.name == segment
+                && matches!(
+                    v.kind,
+                    CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+                )
+        })?;
+
+        let _ = write!(path, " {}", option.name);
+        description = &option.description;
+        options = option.options.as_deref().unwrap_or_default();
+    }
+
+    Some((path, description, options, entry))
+}
+
+fn page_button(page: usize, label: &str, disabled: bool) -> Component {
+    Component::Button(Button {
+        custom_id: Some(format!("{CUSTOM_ID_PREFIX}:{page}")),
+        disabled,
+        emoji: None,
+        label: Some(label.into()),
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<usize> {
+    let (prefix, page) = custom_id.split_once(':')?;
+    if prefix != CUSTOM_ID_PREFIX {
+        return None;
+    }
+    page.parse().ok()
+}
+
+/// Renders one category of commands that the invoker can see.
+fn render_page(entries: &[HelpEntry], page: usize) -> InteractionResponseData {
+    let pages = pages(entries);
+    let Some((category, entries)) = pages.get(page).or_else(|| pages.last()) else {
+        return InteractionResponseDataBuilder::new()
+            .content(NO_COMMANDS_MSG)
+            .components(Vec::new())
+            .embeds(Vec::new())
+            .build();
+    };
+    let page = page.min(pages.len() - 1);
+
+    let mut description = String::new();
+    for entry in entries {
+        let command = &entry.command;
+        let _ = writeln!(
+            description,
+            "**/{}** - {}",
+            command.name, command.description
+        );
+
+        let mut subcommands = Vec::new();
+        collect_subcommands(&command.name, &command.options, &mut subcommands);
+        if !subcommands.is_empty() {
+            let names = subcommands
+                .iter()
+                .map(|(path, _)| format!("`/{path}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let _ = writeln!(description, "{names}");
+        }
+        description.push('\n');
+    }
+
+    let footer = format!(
+        "Page {} of {} • Use /help command:<name> to learn more about a command",
+        page + 1,
+        pages.len()
+    );
+    let embed = embeds::builders::with_emoji(category.emoji(), category.name())
+        .description(description)
+        .footer(EmbedFooterBuilder::new(footer))
+        .build();
+
+    let components = [Component::ActionRow(ActionRow {
+        components: vec![
+            page_button(page.saturating_sub(1), "Previous", page == 0),
+            page_button(page + 1, "Next", page + 1 >= pages.len()),
+        ],
+    })];
+
+    InteractionResponseDataBuilder::new()
+        .embeds([embed])
+        .components(components)
+        .build()
+}
+
+/// Renders the usage of a specific command or subcommand.
+fn render_command(
+    path: &str,
+    description: &str,
+    options: &[CommandOption],
+    entry: &HelpEntry,
+) -> InteractionResponseData {
+    let mut text = description.to_string();
+    let is_top_level = path == entry.command.name;
+    if is_top_level && let Some(details) = entry.help.details {
+        let _ = write!(text, "\n\n{details}");
+    }
+
+    let mut embed = embeds::builders::with_emoji(entry.help.category.emoji(), format!("/{path}"))
+        .description(text);
+
+    let mut subcommands = Vec::new();
+    collect_subcommands(path, options, &mut subcommands);
+
+    let mut usage = String::new();
+    if subcommands.is_empty() {
+        for option in options {
+            let required = if option.required.unwrap_or_default() {
+                " (required)"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                usage,
+                "`{}`{required} - {}",
+                option.name, option.description
+            );
+        }
+        if !usage.is_empty() {
+            embed = embed.field(EmbedFieldBuilder::new("Options", usage));
+        }
+    } else {
+        for (path, description) in subcommands {
+            let _ = writeln!(usage, "`/{path}` - {description}");
+        }
+        embed = embed.field(EmbedFieldBuilder::new("Subcommands", usage));
+    }
+
+    let prefix = format!("/{path}");
+    let examples = entry
+        .help
+        .examples
+        .iter()
+        .filter(|v| v.starts_with(&prefix))
+        .map(|v| format!("`{v}`"))
+        .collect::<Vec<_>>();
+
+    if !examples.is_empty() {
+        embed = embed.field(EmbedFieldBuilder::new("Examples", examples.join("\n")));
+    }
+
+    InteractionResponseDataBuilder::new()
+        .embeds([embed.build()])
+        .build()
+}
+
+impl RunCommand for HelpCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::General)
+        .examples(&["/help", "/help command:payer register"]);
+
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let entries = visible_entries(&ctx.bot, &ctx.interaction);
+
+        let Some(query) = self.command.as_deref() else {
+            return ctx.respond(render_page(&entries, 0)).await;
+        };
+
+        let Some((path, description, options, entry)) = find_command(&entries, query) else {
+            trace!("could not find command {query:?}");
+            let data = InteractionResponseDataBuilder::new()
+                .content(format!(
+                    "**Cannot find a command named `{query}`.** {SEE_ALL_COMMANDS_MSG}"
+                ))
+                .build();
+
+            return ctx.respond(data).await;
+        };
+
+        let data = render_command(&path, description, options, entry);
+        ctx.respond(data).await
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+/// Shows another page of commands after pressing the
+/// "Previous" or "Next" button from `/help`.
+#[tracing::instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_page(ctx: &InteractionContext<MessageComponentInteractionData>) -> Result<()> {
+    let Some(page) = parse_custom_id(&ctx.data.custom_id) else {
+        warn!("got invalid help custom id");
+        return Ok(());
+    };
+
+    let entries = visible_entries(&ctx.bot, &ctx.interaction);
+    ctx.update(render_page(&entries, page)).await
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<HelpEntry> {
+        all_entries()
+    }
+
+    #[test]
+    fn should_parse_custom_ids() {
+        assert_eq!(parse_custom_id("help:2"), Some(2));
+        assert_eq!(parse_custom_id("help:abc"), None);
+        assert_eq!(parse_custom_id("undo:2"), None);
+    }
+
+    #[test]
+    fn should_find_commands() {
+        let entries = entries();
+
+        let (path, _, options, entry) = find_command(&entries, "/Payer  register").unwrap();
+        assert_eq!(path, "payer register");
+        assert_eq!(entry.command.name, "payer");
+        assert!(options
+            .iter()
+            .all(|v| v.kind != CommandOptionType::SubCommand));
+
+        let (path, ..) = find_command(&entries, "admin feature").unwrap();
+        assert_eq!(path, "admin feature");
+
+        assert!(find_command(&entries, "admin unknown").is_none());
+        assert!(find_command(&entries, "").is_none());
+    }
+
+    #[test]
+    fn should_collect_subcommands() {
+        let command: Command = commands::local_guild::AdminCommand::create_command().into();
+        let mut subcommands = Vec::new();
+        collect_subcommands("admin", &command.options, &mut subcommands);
+
+        let paths = subcommands.iter().map(|v| v.0.as_str()).collect::<Vec<_>>();
+        assert!(paths.contains(&"admin undo"));
+        assert!(paths.contains(&"admin feature set"));
+        assert!(!paths.contains(&"admin feature"));
+    }
+
+    #[test]
+    fn should_group_pages_by_category() {
+        let entries = entries();
+        let pages = pages(&entries);
+        let categories = pages.iter().map(|v| v.0).collect::<Vec<_>>();
+        assert_eq!(categories, HelpCategory::ALL);
+
+        let entries = entries
+            .into_iter()
+            .filter(|v| v.help.category == HelpCategory::Payer)
+            .collect::<Vec<_>>();
+        assert_eq!(super::pages(&entries).len(), 1);
+    }
+
+    #[test]
+    fn should_have_examples_of_existing_commands() {
+        let entries = entries();
+        for entry in &entries {
+            for example in entry.help.examples {
+                let query = example.split(' ').take_while(|v| !v.contains(':'));
+                let query = query.collect::<Vec<_>>().join(" ");
+                assert!(
+                    find_command(&entries, &query).is_some(),
+                    "{example} does not exist"
+                );
+            }
+        }
+    }
+}
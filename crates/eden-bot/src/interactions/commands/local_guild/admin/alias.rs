@@ -0,0 +1,228 @@
+use eden_discord_types::commands::local_guild::{
+    AdminAliasAdd, AdminAliasCommand, AdminAliasList, AdminAliasRemove,
+};
+use eden_schema::forms::InsertCommandAliasForm;
+use eden_schema::types::{CommandAlias, GuildSnapshotCommandAlias};
+use eden_utils::{error::exts::*, Result};
+use itertools::Itertools;
+use tracing::debug;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::undo::{self, UndoAction};
+use crate::features::{command_alias, guild_snapshot};
+use crate::interactions::commands::{builtin_local_guild_commands, global_commands};
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for AdminAliasCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Add(cmd) => cmd.run(ctx).await,
+            Self::List(cmd) => cmd.run(ctx).await,
+            Self::Remove(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Add(cmd) => cmd.user_permissions(),
+            Self::List(cmd) => cmd.user_permissions(),
+            Self::Remove(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+/// Command paths are typed by hand so the leading slash, extra
+/// whitespaces and capital letters must not matter.
+fn normalize_command(command: &str) -> String {
+    command
+        .trim()
+        .trim_start_matches('/')
+        .split_whitespace()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().trim_start_matches('/').to_lowercase()
+}
+
+async fn reply<T>(ctx: &LocalGuildContext<'_, T>, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+async fn commit(conn: sqlx::Transaction<'_, sqlx::Postgres>) -> Result<()> {
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")
+}
+
+impl RunCommand for AdminAliasAdd {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let name = normalize_name(&self.name);
+        let command = normalize_command(&self.command);
+        let arguments = command_alias::parse_arguments(self.arguments.as_deref().unwrap_or(""))
+            .and_then(|arguments| {
+                let commands = builtin_local_guild_commands();
+                let global_commands = global_commands();
+                let aliases = ctx.bot.command_aliases.list();
+                command_alias::check(
+                    &name,
+                    &command,
+                    &arguments,
+                    &commands,
+                    &global_commands,
+                    &aliases,
+                )?;
+                Ok(arguments)
+            });
+
+        let arguments = match arguments {
+            Ok(arguments) => arguments,
+            Err(error) => return reply(&ctx, format!("**Cannot add `/{name}`:** {error}.")).await,
+        };
+
+        let form = InsertCommandAliasForm::builder()
+            .guild_id(ctx.guild_id)
+            .name(&name)
+            .created_by(ctx.invoker_id())
+            .command(&command)
+            .arguments(arguments)
+            .build();
+
+        let mut conn = ctx.bot.db_write().await?;
+        let replaces = ctx
+            .bot
+            .command_aliases
+            .list()
+            .iter()
+            .any(|v| v.name == name);
+        if replaces {
+            let reason = format!("Before replacing `/{name}`");
+            guild_snapshot::take(&mut conn, ctx.guild_id, ctx.author.id, &reason).await?;
+        }
+        let alias = CommandAlias::upsert(&mut conn, form).await?;
+        commit(conn).await?;
+
+        debug!(?alias, "added command alias");
+        let content = format!(
+            "**`/{name}` now runs `{}`.** It may take a few seconds to show up in Discord.",
+            command_alias::describe(&alias)
+        );
+        ctx.bot.command_aliases.insert(alias);
+
+        reply(&ctx, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminAliasList {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let mut description = ctx
+            .bot
+            .command_aliases
+            .list()
+            .iter()
+            .map(|v| format!("`/{}` → `{}`", v.name, command_alias::describe(v)))
+            .join("\n");
+
+        if description.is_empty() {
+            description.push_str("*No custom commands are added.*");
+        }
+
+        let embed = embeds::builders::with_emoji('🔀', "Custom commands")
+            .description(description)
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminAliasRemove {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let name = normalize_name(&self.name);
+        let mut conn = ctx.bot.db_write().await?;
+        let reason = format!("Before removing `/{name}`");
+        guild_snapshot::take(&mut conn, ctx.guild_id, ctx.author.id, &reason).await?;
+
+        // the snapshot is discarded if there is nothing to remove
+        let Some(removed) = CommandAlias::delete(&mut conn, ctx.guild_id, &name).await? else {
+            drop(conn);
+            return reply(&ctx, format!("**`/{name}` is not a custom command.**")).await;
+        };
+
+        let action = UndoAction::CommandAlias(GuildSnapshotCommandAlias {
+            name: removed.name,
+            created_by: removed.created_by,
+            command: removed.command,
+            arguments: removed.arguments,
+        });
+        let description = format!("Removed `/{name}`");
+        undo::record(
+            &mut conn,
+            ctx.guild_id,
+            ctx.author.id,
+            &description,
+            &action,
+        )
+        .await?;
+        commit(conn).await?;
+
+        debug!("removed command alias {name:?}");
+        ctx.bot.command_aliases.remove(&name);
+
+        reply(&ctx, format!("**Removed `/{name}`.**")).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_normalize_aliased_commands() {
+        assert_eq!(normalize_command(" /Payer   PAY_bill "), "payer pay_bill");
+        assert_eq!(normalize_command("ping"), "ping");
+        assert_eq!(normalize_name("/Rent"), "rent");
+    }
+}
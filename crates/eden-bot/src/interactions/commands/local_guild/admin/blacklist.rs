@@ -0,0 +1,314 @@
+use eden_discord_types::commands::local_guild::{
+    AdminBlacklistAdd, AdminBlacklistCommand, AdminBlacklistList, AdminBlacklistRemove,
+    AdminGuildsAllow, AdminGuildsCommand, AdminGuildsDisallow, AdminGuildsList,
+};
+use eden_schema::types::{AllowedGuild, BlacklistedUser};
+use eden_utils::{error::exts::*, Result};
+use std::fmt::Write as _;
+use tracing::debug;
+use twilight_mention::Mention;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::blacklist;
+use crate::interactions::embeds;
+
+const NOT_OWNER_MSG: &str =
+    "**Only the server owner can manage the blacklist and allowed servers.**";
+
+const INVALID_GUILD_ID_MSG: &str = "**Invalid server ID!** Please use the ID copied from Discord.";
+
+impl RunCommand for AdminBlacklistCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Add(cmd) => cmd.run(ctx).await,
+            Self::List(cmd) => cmd.run(ctx).await,
+            Self::Remove(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Add(cmd) => cmd.user_permissions(),
+            Self::List(cmd) => cmd.user_permissions(),
+            Self::Remove(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+impl RunCommand for AdminGuildsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Allow(cmd) => cmd.run(ctx).await,
+            Self::Disallow(cmd) => cmd.run(ctx).await,
+            Self::List(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Allow(cmd) => cmd.user_permissions(),
+            Self::Disallow(cmd) => cmd.user_permissions(),
+            Self::List(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+async fn reply(ctx: &CommandContext, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+/// Replies with [`NOT_OWNER_MSG`] if the invoker does not own the
+/// local guild. It returns true if the invoker owns the local guild.
+async fn check_owner(ctx: &CommandContext) -> Result<bool> {
+    if blacklist::is_owner(&ctx.bot, ctx.invoker_id()).await? {
+        return Ok(true);
+    }
+    reply(ctx, NOT_OWNER_MSG.into()).await?;
+    Ok(false)
+}
+
+fn parse_guild_id(input: &str) -> Option<Id<GuildMarker>> {
+    input.trim().parse().ok()
+}
+
+async fn commit(conn: sqlx::Transaction<'_, sqlx::Postgres>) -> Result<()> {
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")
+}
+
+impl RunCommand for AdminBlacklistAdd {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        if !check_owner(ctx).await? {
+            return Ok(());
+        }
+
+        let invoker_id = ctx.invoker_id();
+        if self.user == invoker_id {
+            return reply(ctx, "**You cannot blacklist yourself.**".into()).await;
+        }
+
+        debug!("blacklisting user {}", self.user);
+
+        let mut conn = ctx.bot.db_write().await?;
+        BlacklistedUser::upsert(&mut conn, self.user, invoker_id, self.reason.as_deref()).await?;
+        commit(conn).await?;
+        ctx.bot.blacklist.insert(self.user);
+
+        let content = format!("**Blacklisted {}.**", self.user.mention());
+        reply(ctx, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminBlacklistList {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        if !check_owner(ctx).await? {
+            return Ok(());
+        }
+
+        let mut conn = ctx.bot.db_read().await?;
+        let users = BlacklistedUser::list(&mut conn).await?;
+        drop(conn);
+
+        let mut description = String::new();
+        for user in &users {
+            let _ = write!(
+                description,
+                "- {} (`{}`)",
+                user.user_id.mention(),
+                user.user_id
+            );
+            if let Some(reason) = user.reason.as_deref() {
+                let _ = write!(description, ": {reason}");
+            }
+            description.push('\n');
+        }
+
+        if description.is_empty() {
+            description.push_str("*No users are blacklisted.*");
+        }
+
+        let embed = embeds::builders::with_emoji('⛔', "Blacklisted users")
+            .description(description)
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminBlacklistRemove {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        if !check_owner(ctx).await? {
+            return Ok(());
+        }
+
+        let mut conn = ctx.bot.db_write().await?;
+        let removed = BlacklistedUser::delete(&mut conn, self.user).await?;
+        commit(conn).await?;
+        ctx.bot.blacklist.remove(self.user);
+
+        let content = if removed {
+            debug!("removed user {} from the blacklist", self.user);
+            format!("**Removed {} from the blacklist.**", self.user.mention())
+        } else {
+            format!("{} is not blacklisted.", self.user.mention())
+        };
+        reply(ctx, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminGuildsAllow {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        if !check_owner(ctx).await? {
+            return Ok(());
+        }
+
+        let Some(guild_id) = parse_guild_id(&self.guild_id) else {
+            return reply(ctx, INVALID_GUILD_ID_MSG.into()).await;
+        };
+
+        let mut conn = ctx.bot.db_write().await?;
+        let inserted = AllowedGuild::insert(&mut conn, guild_id, ctx.invoker_id()).await?;
+        commit(conn).await?;
+
+        let content = if inserted.is_some() {
+            debug!("allowed guild {guild_id}");
+            format!("**Eden is now allowed to stay in server `{guild_id}`.**")
+        } else {
+            format!("Server `{guild_id}` is already allowed.")
+        };
+        reply(ctx, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminGuildsDisallow {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        if !check_owner(ctx).await? {
+            return Ok(());
+        }
+
+        let Some(guild_id) = parse_guild_id(&self.guild_id) else {
+            return reply(ctx, INVALID_GUILD_ID_MSG.into()).await;
+        };
+
+        let mut conn = ctx.bot.db_write().await?;
+        let removed = AllowedGuild::delete(&mut conn, guild_id).await?;
+        commit(conn).await?;
+
+        // Eden leaves the server once it connects to the gateway again
+        let content = if removed {
+            debug!("disallowed guild {guild_id}");
+            format!("**Eden is no longer allowed to stay in server `{guild_id}`.**")
+        } else {
+            format!("Server `{guild_id}` is not allowed in the first place.")
+        };
+        reply(ctx, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminGuildsList {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        if !check_owner(ctx).await? {
+            return Ok(());
+        }
+
+        let mut conn = ctx.bot.db_read().await?;
+        let guilds = AllowedGuild::list(&mut conn).await?;
+        drop(conn);
+
+        let mut description = String::new();
+        for guild in &guilds {
+            let name = ctx
+                .bot
+                .cache
+                .guild(guild.guild_id)
+                .map(|v| v.name().to_string());
+            let _ = write!(description, "- `{}`", guild.guild_id);
+            if let Some(name) = name {
+                let _ = write!(description, " ({name})");
+            }
+            description.push('\n');
+        }
+
+        if description.is_empty() {
+            description.push_str("*No other servers are allowed.*");
+        }
+
+        let embed = embeds::builders::with_emoji('🏠', "Allowed servers")
+            .description(description)
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_guild_ids() {
+        assert_eq!(parse_guild_id(" 123456789 "), Some(Id::new(123_456_789)));
+        assert_eq!(parse_guild_id("0"), None);
+        assert_eq!(parse_guild_id("eden"), None);
+    }
+}
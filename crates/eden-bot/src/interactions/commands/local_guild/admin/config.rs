@@ -0,0 +1,211 @@
+use eden_discord_types::commands::local_guild::{AdminConfigCommand, AdminConfigShow};
+use eden_settings::SettingsSnapshot;
+use eden_utils::Result;
+use std::fmt::Write as _;
+use tracing::warn;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_model::http::interaction::InteractionResponseData;
+use twilight_util::builder::embed::EmbedFooterBuilder;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::blacklist;
+use crate::interactions::{embeds, record_local_guild_ctx, InteractionContext, LocalGuildContext};
+
+/// Prefix of the custom ID of the page buttons.
+pub const CUSTOM_ID_PREFIX: &str = "config";
+
+/// Maximum amount of settings shown in a single page.
+const SETTINGS_PER_PAGE: usize = 20;
+
+/// Values longer than this are cut off so every page fits
+/// in the description of an embed.
+const MAX_VALUE_LENGTH: usize = 150;
+
+const NOT_OWNER_MSG: &str = "**Only the server owner can see the settings of Eden.**";
+
+impl RunCommand for AdminConfigCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Show(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Show(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+impl RunCommand for AdminConfigShow {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        // settings may reveal how Eden is deployed
+        if !blacklist::is_owner(&ctx.bot, ctx.author.id).await? {
+            let data = InteractionResponseDataBuilder::new()
+                .content(NOT_OWNER_MSG)
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        let snapshot = ctx.bot.settings.redacted_snapshot();
+        ctx.respond(render_page(&snapshot, 0)).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+/// Shows another page of settings after pressing the "Previous"
+/// or "Next" button from `/admin config show`.
+#[tracing::instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_page(ctx: &InteractionContext<MessageComponentInteractionData>) -> Result<()> {
+    let Some(page) = parse_custom_id(&ctx.data.custom_id) else {
+        warn!("got invalid config custom id");
+        return Ok(());
+    };
+
+    if !blacklist::is_owner(&ctx.bot, ctx.invoker_id()).await? {
+        warn!("got config page request from non-owner");
+        return Ok(());
+    }
+
+    let snapshot = ctx.bot.settings.redacted_snapshot();
+    ctx.update(render_page(&snapshot, page)).await
+}
+
+/// Splits the settings into pages where every page only has
+/// settings from the same group.
+fn pages(snapshot: &SettingsSnapshot) -> Vec<(&str, &[(String, String)])> {
+    snapshot
+        .groups
+        .iter()
+        .flat_map(|group| {
+            group
+                .values
+                .chunks(SETTINGS_PER_PAGE)
+                .map(|values| (group.name.as_str(), values))
+        })
+        .collect()
+}
+
+fn page_button(page: usize, label: &str, disabled: bool) -> Component {
+    Component::Button(Button {
+        custom_id: Some(format!("{CUSTOM_ID_PREFIX}:{page}")),
+        disabled,
+        emoji: None,
+        label: Some(label.into()),
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<usize> {
+    let (prefix, page) = custom_id.split_once(':')?;
+    if prefix != CUSTOM_ID_PREFIX {
+        return None;
+    }
+    page.parse().ok()
+}
+
+fn render_value(value: &str) -> String {
+    // backticks would break out of the inline code block
+    let mut value = value.replace('`', "'");
+    if let Some((index, _)) = value.char_indices().nth(MAX_VALUE_LENGTH) {
+        value.truncate(index);
+        value.push('…');
+    }
+    value
+}
+
+fn render_page(snapshot: &SettingsSnapshot, page: usize) -> InteractionResponseData {
+    let pages = pages(snapshot);
+    let page = page.min(pages.len().saturating_sub(1));
+
+    let mut description = String::new();
+    let mut title = "Settings";
+    if let Some((group, values)) = pages.get(page) {
+        title = *group;
+        for (key, value) in *values {
+            let _ = writeln!(description, "`{key}`: `{}`", render_value(value));
+        }
+    } else {
+        description.push_str("*No settings to show.*");
+    }
+
+    let footer = format!(
+        "Page {} of {} • Sensitive values are hidden",
+        page + 1,
+        pages.len().max(1)
+    );
+    let embed = embeds::builders::with_emoji('⚙', title)
+        .description(description)
+        .footer(EmbedFooterBuilder::new(footer))
+        .build();
+
+    let components = [Component::ActionRow(ActionRow {
+        components: vec![
+            page_button(page.saturating_sub(1), "Previous", page == 0),
+            page_button(page + 1, "Next", page + 1 >= pages.len()),
+        ],
+    })];
+
+    InteractionResponseDataBuilder::new()
+        .embeds([embed])
+        .components(components)
+        .flags(MessageFlags::EPHEMERAL)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eden_settings::SettingsGroup;
+
+    fn group(name: &str, amount: usize) -> SettingsGroup {
+        SettingsGroup {
+            name: name.into(),
+            values: (0..amount)
+                .map(|v| (format!("key_{v}"), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn should_split_groups_into_pages() {
+        let snapshot = SettingsSnapshot {
+            groups: vec![group("bot", SETTINGS_PER_PAGE + 1), group("database", 3)],
+        };
+
+        let pages = pages(&snapshot);
+        let names = pages.iter().map(|v| (v.0, v.1.len())).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![("bot", SETTINGS_PER_PAGE), ("bot", 1), ("database", 3)]
+        );
+        assert_eq!(parse_custom_id("config:2"), Some(2));
+        assert_eq!(parse_custom_id("help:2"), None);
+    }
+
+    #[test]
+    fn should_render_values_safely() {
+        assert_eq!(render_value("\"`eden`\""), "\"'eden'\"");
+
+        let long = "a".repeat(MAX_VALUE_LENGTH + 10);
+        assert_eq!(render_value(&long).chars().count(), MAX_VALUE_LENGTH + 1);
+    }
+}
@@ -0,0 +1,160 @@
+use eden_discord_types::commands::local_guild::{
+    AdminFeatureCommand, AdminFeatureList, AdminFeatureRemove, AdminFeatureSet,
+};
+use eden_utils::Result;
+use itertools::Itertools;
+use std::fmt::Write as _;
+use tracing::debug;
+use twilight_mention::Mention;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::feature_gate::FeatureGate;
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for AdminFeatureCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::List(cmd) => cmd.run(ctx).await,
+            Self::Remove(cmd) => cmd.run(ctx).await,
+            Self::Set(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::List(cmd) => cmd.user_permissions(),
+            Self::Remove(cmd) => cmd.user_permissions(),
+            Self::Set(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+/// Feature names are compared with the command path so extra
+/// whitespaces and capital letters must not matter.
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace().join(" ").to_lowercase()
+}
+
+fn describe(gate: &FeatureGate) -> String {
+    let mut output = format!("`{}`: {}% of members", gate.name, gate.percentage);
+    if !gate.roles.is_empty() {
+        let roles = gate.roles.iter().map(|v| v.mention()).join(", ");
+        let _ = write!(output, " and {roles}");
+    }
+    output
+}
+
+impl RunCommand for AdminFeatureList {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let mut description = ctx.bot.feature_gates.list().iter().map(describe).join("\n");
+
+        if description.is_empty() {
+            description.push_str("*No features are being rolled out.*");
+        }
+
+        let embed = embeds::builders::with_emoji('🚦', "Features being rolled out")
+            .description(description)
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminFeatureRemove {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let name = normalize_name(&self.name);
+        let content = if ctx.bot.feature_gates.remove(&name).is_some() {
+            debug!("removed feature gate {name:?}");
+            format!("**`{name}` is now available to everyone.**")
+        } else {
+            format!("**`{name}` is not being rolled out.**")
+        };
+
+        let data = InteractionResponseDataBuilder::new()
+            .content(content)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminFeatureSet {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let name = normalize_name(&self.name);
+        let percentage = u8::try_from(self.percentage.clamp(0, 100)).unwrap_or_default();
+
+        // keep the roles from before so admins can add roles one by one
+        let mut gate = FeatureGate {
+            percentage,
+            ..ctx
+                .bot
+                .feature_gates
+                .get(&name)
+                .unwrap_or_else(|| FeatureGate::new(&name, percentage))
+        };
+        if let Some(role_id) = self.role
+            && !gate.roles.contains(&role_id)
+        {
+            gate.roles.push(role_id);
+        }
+
+        debug!(?gate, "setting feature gate");
+        let content = format!("**Rolling out** {}", describe(&gate));
+        ctx.bot.feature_gates.set(gate);
+
+        let data = InteractionResponseDataBuilder::new()
+            .content(content)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_normalize_feature_names() {
+        assert_eq!(normalize_name("  Payer   PAY "), "payer pay");
+        assert_eq!(normalize_name("emoji"), "emoji");
+    }
+}
@@ -0,0 +1,181 @@
+use eden_discord_types::commands::local_guild::{
+    AdminLoggingCommand, AdminLoggingList, AdminLoggingRemove, AdminLoggingSet, AdminLoggingToggle,
+};
+use eden_utils::trace::sampling::SpanSampler;
+use eden_utils::Result;
+use itertools::Itertools;
+use tracing::debug;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::blacklist;
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+const NOT_OWNER_MSG: &str = "**Only the server owner can change how Eden records logs.**";
+
+impl RunCommand for AdminLoggingCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::List(cmd) => cmd.run(ctx).await,
+            Self::Remove(cmd) => cmd.run(ctx).await,
+            Self::Set(cmd) => cmd.run(ctx).await,
+            Self::Toggle(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::List(cmd) => cmd.user_permissions(),
+            Self::Remove(cmd) => cmd.user_permissions(),
+            Self::Set(cmd) => cmd.user_permissions(),
+            Self::Toggle(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+async fn reply(ctx: &CommandContext, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+/// Changes to logging apply to the entire instance of Eden,
+/// not only this server.
+async fn check_owner(ctx: &CommandContext) -> Result<bool> {
+    if blacklist::is_owner(&ctx.bot, ctx.invoker_id()).await? {
+        return Ok(true);
+    }
+    reply(ctx, NOT_OWNER_MSG.into()).await?;
+    Ok(false)
+}
+
+fn describe(span: &str, percentage: f64) -> String {
+    format!("`{span}`: {percentage}% of spans")
+}
+
+impl RunCommand for AdminLoggingList {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !check_owner(ctx.inner).await? {
+            return Ok(());
+        }
+
+        let sampler = SpanSampler::global();
+        let mut description = sampler
+            .rules()
+            .iter()
+            .map(|(span, percentage)| describe(span, *percentage))
+            .join("\n");
+
+        if description.is_empty() {
+            description.push_str("*No spans are being sampled.*");
+        } else if !sampler.is_enabled() {
+            description.insert_str(0, "**Sampling is turned off.** Every span is recorded.\n");
+        }
+
+        let embed = embeds::builders::with_emoji('🪵', "Sampled spans")
+            .description(description)
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminLoggingRemove {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !check_owner(ctx.inner).await? {
+            return Ok(());
+        }
+
+        let span = self.span.trim();
+        let content = if SpanSampler::global().remove(span) {
+            debug!("removed sampling of span {span:?}");
+            format!("**Every span of `{span}` is now recorded.**")
+        } else {
+            format!("**`{span}` is not being sampled.**")
+        };
+
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminLoggingSet {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !check_owner(ctx.inner).await? {
+            return Ok(());
+        }
+
+        let span = self.span.trim();
+        let percentage = self.percentage.clamp(0., 100.);
+
+        debug!("sampling {percentage}% of span {span:?}");
+        SpanSampler::global().set(span, percentage);
+
+        let content = format!("**Recording** {}", describe(span, percentage));
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminLoggingToggle {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !check_owner(ctx.inner).await? {
+            return Ok(());
+        }
+
+        let state = if self.enabled { "on" } else { "off" };
+        debug!("turning span sampling {state}");
+        SpanSampler::global().set_enabled(self.enabled);
+
+        let content = if self.enabled {
+            "**Span sampling is turned on.**"
+        } else {
+            "**Span sampling is turned off.** Every span is recorded."
+        };
+        reply(ctx.inner, content.into()).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
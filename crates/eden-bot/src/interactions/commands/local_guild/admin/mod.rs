@@ -0,0 +1,133 @@
+use crate::interactions::commands::{CommandContext, CommandHelp, HelpCategory, RunCommand};
+use crate::interactions::validation::Validator;
+use eden_discord_types::commands::local_guild::AdminCommand;
+use eden_utils::Result;
+use twilight_model::guild::Permissions;
+
+mod alias;
+mod blacklist;
+pub mod config;
+mod feature;
+mod logging;
+mod queue;
+mod roles;
+mod shard;
+#[cfg(debug_assertions)]
+mod simulate;
+mod snapshot;
+mod undo;
+
+impl RunCommand for AdminCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::Administration)
+        .permissions(Permissions::ADMINISTRATOR)
+        .details("Most of these commands require you to be registered as an admin in Eden.")
+        .examples(&["/admin feature list", "/admin undo"]);
+
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Alias(cmd) => cmd.run(ctx).await,
+            Self::Blacklist(cmd) => cmd.run(ctx).await,
+            Self::Config(cmd) => cmd.run(ctx).await,
+            Self::Feature(cmd) => cmd.run(ctx).await,
+            Self::Guilds(cmd) => cmd.run(ctx).await,
+            Self::Logging(cmd) => cmd.run(ctx).await,
+            Self::Queue(cmd) => cmd.run(ctx).await,
+            Self::RestoreSnapshot(cmd) => cmd.run(ctx).await,
+            Self::Roles(cmd) => cmd.run(ctx).await,
+            Self::Shard(cmd) => cmd.run(ctx).await,
+            #[cfg(debug_assertions)]
+            Self::Simulate(cmd) => cmd.run(ctx).await,
+            Self::Undo(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Alias(cmd) => cmd.user_permissions(),
+            Self::Blacklist(cmd) => cmd.user_permissions(),
+            Self::Config(cmd) => cmd.user_permissions(),
+            Self::Feature(cmd) => cmd.user_permissions(),
+            Self::Guilds(cmd) => cmd.user_permissions(),
+            Self::Logging(cmd) => cmd.user_permissions(),
+            Self::Queue(cmd) => cmd.user_permissions(),
+            Self::RestoreSnapshot(cmd) => cmd.user_permissions(),
+            Self::Roles(cmd) => cmd.user_permissions(),
+            Self::Shard(cmd) => cmd.user_permissions(),
+            #[cfg(debug_assertions)]
+            Self::Simulate(cmd) => cmd.user_permissions(),
+            Self::Undo(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Alias(cmd) => cmd.guild_permissions(),
+            Self::Blacklist(cmd) => cmd.guild_permissions(),
+            Self::Config(cmd) => cmd.guild_permissions(),
+            Self::Feature(cmd) => cmd.guild_permissions(),
+            Self::Guilds(cmd) => cmd.guild_permissions(),
+            Self::Logging(cmd) => cmd.guild_permissions(),
+            Self::Queue(cmd) => cmd.guild_permissions(),
+            Self::RestoreSnapshot(cmd) => cmd.guild_permissions(),
+            Self::Roles(cmd) => cmd.guild_permissions(),
+            Self::Shard(cmd) => cmd.guild_permissions(),
+            #[cfg(debug_assertions)]
+            Self::Simulate(cmd) => cmd.guild_permissions(),
+            Self::Undo(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn channel_permissions(&self) -> Permissions {
+        match self {
+            Self::Alias(cmd) => cmd.channel_permissions(),
+            Self::Blacklist(cmd) => cmd.channel_permissions(),
+            Self::Config(cmd) => cmd.channel_permissions(),
+            Self::Feature(cmd) => cmd.channel_permissions(),
+            Self::Guilds(cmd) => cmd.channel_permissions(),
+            Self::Logging(cmd) => cmd.channel_permissions(),
+            Self::Queue(cmd) => cmd.channel_permissions(),
+            Self::RestoreSnapshot(cmd) => cmd.channel_permissions(),
+            Self::Roles(cmd) => cmd.channel_permissions(),
+            Self::Shard(cmd) => cmd.channel_permissions(),
+            #[cfg(debug_assertions)]
+            Self::Simulate(cmd) => cmd.channel_permissions(),
+            Self::Undo(cmd) => cmd.channel_permissions(),
+        }
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        match self {
+            Self::Alias(cmd) => cmd.validate(validator),
+            Self::Blacklist(cmd) => cmd.validate(validator),
+            Self::Config(cmd) => cmd.validate(validator),
+            Self::Feature(cmd) => cmd.validate(validator),
+            Self::Guilds(cmd) => cmd.validate(validator),
+            Self::Logging(cmd) => cmd.validate(validator),
+            Self::Queue(cmd) => cmd.validate(validator),
+            Self::RestoreSnapshot(cmd) => cmd.validate(validator),
+            Self::Roles(cmd) => cmd.validate(validator),
+            Self::Shard(cmd) => cmd.validate(validator),
+            #[cfg(debug_assertions)]
+            Self::Simulate(cmd) => cmd.validate(validator),
+            Self::Undo(cmd) => cmd.validate(validator),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        match self {
+            Self::Alias(cmd) => cmd.is_ephemeral(),
+            Self::Blacklist(cmd) => cmd.is_ephemeral(),
+            Self::Config(cmd) => cmd.is_ephemeral(),
+            Self::Feature(cmd) => cmd.is_ephemeral(),
+            Self::Guilds(cmd) => cmd.is_ephemeral(),
+            Self::Logging(cmd) => cmd.is_ephemeral(),
+            Self::Queue(cmd) => cmd.is_ephemeral(),
+            Self::RestoreSnapshot(cmd) => cmd.is_ephemeral(),
+            Self::Roles(cmd) => cmd.is_ephemeral(),
+            Self::Shard(cmd) => cmd.is_ephemeral(),
+            #[cfg(debug_assertions)]
+            Self::Simulate(cmd) => cmd.is_ephemeral(),
+            Self::Undo(cmd) => cmd.is_ephemeral(),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use eden_discord_types::commands::local_guild::{
+    AdminQueueCommand, AdminQueuePause, AdminQueueResume,
+};
+use eden_utils::Result;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::blacklist;
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+const NOT_OWNER_MSG: &str = "**Only the server owner can control the task queue.**";
+
+impl RunCommand for AdminQueueCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Pause(cmd) => cmd.run(ctx).await,
+            Self::Resume(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Pause(cmd) => cmd.user_permissions(),
+            Self::Resume(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+async fn reply(ctx: &CommandContext, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+/// The task queue is shared by the entire instance of Eden,
+/// not only this server.
+async fn check_owner(ctx: &CommandContext) -> Result<bool> {
+    if blacklist::is_owner(&ctx.bot, ctx.invoker_id()).await? {
+        return Ok(true);
+    }
+    reply(ctx, NOT_OWNER_MSG.into()).await?;
+    Ok(false)
+}
+
+impl RunCommand for AdminQueuePause {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !check_owner(ctx.inner).await? {
+            return Ok(());
+        }
+
+        let content = if ctx.bot.queue.pause() {
+            format!(
+                "**Paused the task queue.** {} running task(s) will be allowed to finish.",
+                ctx.bot.queue.running_tasks()
+            )
+        } else {
+            "**The task queue is already paused.**".into()
+        };
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AdminQueueResume {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !check_owner(ctx.inner).await? {
+            return Ok(());
+        }
+
+        let content = if ctx.bot.queue.resume() {
+            "**Resumed the task queue.**"
+        } else {
+            "**The task queue is not paused.**"
+        };
+        reply(ctx.inner, content.into()).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::Utc;
+use eden_discord_types::commands::local_guild::{AdminRolesBulk, AdminRolesCommand};
+use eden_tasks::Scheduled;
+use eden_utils::time::parse_time_delta;
+use eden_utils::Result;
+use tracing::debug;
+use twilight_mention::Mention;
+use twilight_model::channel::message::AllowedMentions;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::bulk_role::{BulkRoleAction, BulkRoleFilter, BulkRoleOperation};
+use crate::features::{auto_role, blacklist};
+use crate::interactions::validation::{Validator, DURATION_FORMAT};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+use crate::tasks;
+
+impl RunCommand for AdminRolesCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Bulk(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Bulk(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Bulk(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn channel_permissions(&self) -> Permissions {
+        match self {
+            Self::Bulk(cmd) => cmd.channel_permissions(),
+        }
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        match self {
+            Self::Bulk(cmd) => cmd.validate(validator),
+        }
+    }
+}
+
+impl RunCommand for AdminRolesBulk {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let now = Utc::now();
+        let mut filter = BulkRoleFilter {
+            with_role: self.with_role,
+            ..Default::default()
+        };
+
+        // durations are already checked in `validate`
+        for (input, joined) in [
+            (&self.joined_within, &mut filter.joined_after),
+            (&self.joined_before, &mut filter.joined_before),
+        ] {
+            *joined = input.as_deref().and_then(parse_time_delta).map(|v| now - v);
+        }
+
+        let is_owner = blacklist::is_owner(&ctx.bot, ctx.author.id).await?;
+        let unassignable = auto_role::find_unassignable_by(
+            &ctx.bot,
+            ctx.guild_id,
+            self.role,
+            &ctx.member.roles,
+            is_owner,
+        )
+        .await?;
+        if let Some(reason) = unassignable {
+            let data = InteractionResponseDataBuilder::new()
+                .allowed_mentions(AllowedMentions::default())
+                .content(format!(
+                    "**I cannot change {} of members** because {reason}.",
+                    self.role.mention()
+                ))
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        let operation = BulkRoleOperation {
+            guild_id: ctx.guild_id,
+            channel_id: ctx.channel_id,
+            requested_by: ctx.author.id,
+            role_id: self.role,
+            action: BulkRoleAction::from(self.action),
+            filter,
+            members: None,
+            progress: None,
+        };
+
+        debug!(?operation, "queueing bulk role operation");
+        let task = tasks::PerformBulkRoleOperation { operation };
+        ctx.bot.queue.schedule(task, Scheduled::now()).await?;
+
+        let data = InteractionResponseDataBuilder::new()
+            .allowed_mentions(AllowedMentions::default())
+            .content(format!(
+                "**Queued bulk role operation for {}.** Its progress will be reported here.",
+                self.role.mention()
+            ))
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+
+    fn channel_permissions(&self) -> Permissions {
+        Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::ATTACH_FILES
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        for (argument, input) in [
+            ("joined_within", &self.joined_within),
+            ("joined_before", &self.joined_before),
+        ] {
+            if let Some(input) = input {
+                validator.parse(argument, input, parse_time_delta, DURATION_FORMAT);
+            }
+        }
+    }
+}
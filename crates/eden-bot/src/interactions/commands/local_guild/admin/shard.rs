@@ -0,0 +1,87 @@
+use eden_discord_types::commands::local_guild::AdminShard;
+use eden_utils::Result;
+use fancy_duration::FancyDuration;
+use std::time::Duration;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::embed::EmbedFieldBuilder;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+const NOT_SERVED_MSG: &str =
+    "**This server is not served by any of the shards running in this instance of Eden.**";
+
+impl RunCommand for AdminShard {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let manager = &ctx.bot.shard_manager;
+        let Some(shard) = manager.shard_for_guild(ctx.guild_id).await else {
+            let data = InteractionResponseDataBuilder::new()
+                .content(NOT_SERVED_MSG)
+                .build();
+
+            return ctx.respond(data).await;
+        };
+
+        let id = shard.id();
+        let status = shard.status().await;
+        let (average, recent, heartbeats) = {
+            let latency = shard.latency().await;
+            (
+                latency.average(),
+                latency.recent().first().copied(),
+                latency.heartbeats(),
+            )
+        };
+        let metrics = shard.metrics();
+
+        let embed = embeds::builders::with_emoji('🛰', format!("Shard {}", id.number()))
+            .description(format!(
+                "This server is served by shard **{}** out of {} shard(s).",
+                id.number(),
+                id.total()
+            ))
+            .field(EmbedFieldBuilder::new("Status", format!("`{status:?}`")))
+            .field(EmbedFieldBuilder::new("Average latency", display_latency(average)).inline())
+            .field(EmbedFieldBuilder::new("Recent latency", display_latency(recent)).inline())
+            .field(EmbedFieldBuilder::new("Heartbeats", heartbeats.to_string()).inline())
+            .field(EmbedFieldBuilder::new("Events received", metrics.events().to_string()).inline())
+            .field(
+                EmbedFieldBuilder::new("Payloads received", metrics.payloads().to_string())
+                    .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    "Average payload size",
+                    format!("{} bytes", metrics.average_bytes()),
+                )
+                .inline(),
+            )
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+fn display_latency(latency: Option<Duration>) -> String {
+    latency.map_or_else(
+        || String::from("*not measured yet*"),
+        |v| FancyDuration(v).truncate(1).to_string(),
+    )
+}
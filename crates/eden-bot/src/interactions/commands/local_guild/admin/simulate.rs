@@ -0,0 +1,101 @@
+use eden_discord_types::choices::SimulatedEventOption;
+use eden_discord_types::commands::local_guild::{AdminSimulateCommand, AdminSimulateEvent};
+use eden_utils::Result;
+use tracing::debug;
+use twilight_gateway::Event;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::events::EventContext;
+use crate::features::blacklist;
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+const NOT_OWNER_MSG: &str = "**Only the server owner can simulate events.**";
+
+/// Deserializes the JSON payload of a gateway event of a specific kind.
+fn parse_event(kind: SimulatedEventOption, json: &str) -> serde_json::Result<Event> {
+    let event = match kind {
+        SimulatedEventOption::MemberAdd => Event::MemberAdd(serde_json::from_str(json)?),
+        SimulatedEventOption::MessageCreate => Event::MessageCreate(serde_json::from_str(json)?),
+        SimulatedEventOption::ReactionAdd => Event::ReactionAdd(serde_json::from_str(json)?),
+        SimulatedEventOption::VoiceStateUpdate => {
+            Event::VoiceStateUpdate(serde_json::from_str(json)?)
+        }
+    };
+    Ok(event)
+}
+
+async fn reply(ctx: &CommandContext, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+impl RunCommand for AdminSimulateCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Event(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Event(cmd) => cmd.user_permissions(),
+        }
+    }
+}
+
+impl RunCommand for AdminSimulateEvent {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !blacklist::is_owner(&ctx.bot, ctx.author.id).await? {
+            return reply(ctx.inner, NOT_OWNER_MSG.into()).await;
+        }
+
+        let event = match parse_event(self.kind, &self.json) {
+            Ok(event) => event,
+            Err(error) => {
+                let content = format!("**Invalid {:?} payload!** {error}", self.kind);
+                return reply(ctx.inner, content).await;
+            }
+        };
+
+        debug!("simulating {:?} event", event.kind());
+
+        let event_ctx = EventContext {
+            bot: ctx.bot.clone(),
+            latency: ctx.shard.latency().await.clone(),
+            shard: ctx.shard.clone(),
+        };
+        crate::events::handle_simulated_event(event_ctx, event).await;
+
+        let content = format!(
+            "**Simulated {:?} event.** Check the logs to see how it is handled.",
+            self.kind
+        );
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reject_invalid_payloads() {
+        assert!(parse_event(SimulatedEventOption::MemberAdd, "{}").is_err());
+        assert!(parse_event(SimulatedEventOption::ReactionAdd, "not json").is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use eden_discord_types::commands::local_guild::AdminRestoreSnapshot;
+use eden_schema::types::GuildSnapshot;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::{error::exts::*, Result};
+use std::fmt::Write as _;
+use tracing::debug;
+use twilight_mention::Mention;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+use uuid::Uuid;
+
+use super::{CommandContext, RunCommand};
+use crate::features::guild_snapshot;
+use crate::features::undo::{self, UndoAction};
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+/// Maximum amount of snapshots listed if no snapshot is given.
+const LIST_LIMIT: i64 = 10;
+
+impl RunCommand for AdminRestoreSnapshot {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(id) = self.snapshot.as_deref() else {
+            return list(&ctx).await;
+        };
+
+        let Ok(id) = Uuid::parse_str(id.trim()) else {
+            return reply(&ctx, format!("**`{id}` is not a valid snapshot ID.**")).await;
+        };
+
+        let mut conn = ctx.bot.db_write().await?;
+        let Some(snapshot) = GuildSnapshot::from_id(&mut conn, ctx.guild_id, id).await? else {
+            let content = format!("**Snapshot `{id}` does not exist or has expired.**");
+            return reply(&ctx, content).await;
+        };
+
+        // restoring is a risky operation by itself
+        let reason = format!("Before restoring snapshot {id}");
+        let backup = guild_snapshot::take(&mut conn, ctx.guild_id, ctx.author.id, &reason).await?;
+        let aliases = guild_snapshot::restore(&mut conn, &snapshot).await?;
+
+        let action = UndoAction::RestoreSnapshot {
+            restored_id: snapshot.id,
+            backup_id: backup.id,
+        };
+        let description = format!("Restored snapshot `{id}`");
+        undo::record(
+            &mut conn,
+            ctx.guild_id,
+            ctx.author.id,
+            &description,
+            &action,
+        )
+        .await?;
+
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        debug!("restored guild snapshot {id}");
+        ctx.bot.invalidate_guild_settings(ctx.guild_id);
+        ctx.bot.command_aliases.replace_all(aliases);
+
+        let content = format!(
+            "**Restored snapshot `{id}`** taken {}.\n\
+            The data before restoring is kept in snapshot `{}`.",
+            discord_timestamp(snapshot.created_at, TimestampStyle::Relative),
+            backup.id
+        );
+        reply(&ctx, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
+
+async fn list<T>(ctx: &LocalGuildContext<'_, T>) -> Result<()> {
+    let mut conn = ctx.bot.db_read().await?;
+    let snapshots = GuildSnapshot::list(&mut conn, ctx.guild_id, LIST_LIMIT).await?;
+
+    let mut description = String::new();
+    for snapshot in &snapshots {
+        let _ = writeln!(
+            description,
+            "`{}`: {} by {} ({})",
+            snapshot.id,
+            snapshot.reason,
+            snapshot.created_by.mention(),
+            discord_timestamp(snapshot.created_at, TimestampStyle::Relative)
+        );
+    }
+
+    if description.is_empty() {
+        description.push_str("*No snapshots have been taken recently.*");
+    }
+
+    let embed = embeds::builders::with_emoji('🗃', "Recent snapshots")
+        .description(description)
+        .build();
+
+    let data = InteractionResponseDataBuilder::new()
+        .embeds(vec![embed])
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+async fn reply<T>(ctx: &LocalGuildContext<'_, T>, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
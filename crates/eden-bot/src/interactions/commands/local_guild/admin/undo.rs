@@ -0,0 +1,77 @@
+use eden_discord_types::commands::local_guild::AdminUndo;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::Result;
+use std::fmt::Write as _;
+use twilight_mention::Mention;
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::undo;
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+/// Maximum amount of buttons allowed in a single action row.
+const BUTTONS_PER_ROW: usize = 5;
+
+impl RunCommand for AdminUndo {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let entries = undo::list(&ctx.bot, ctx.guild_id).await?;
+
+        let mut description = String::new();
+        let mut buttons = Vec::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let _ = writeln!(
+                description,
+                "**{}.** {} by {} ({})",
+                index + 1,
+                entry.description,
+                entry.actor_id.mention(),
+                discord_timestamp(entry.created_at, TimestampStyle::Relative)
+            );
+
+            buttons.push(Component::Button(Button {
+                custom_id: Some(undo::make_custom_id(entry.id)),
+                disabled: false,
+                emoji: None,
+                label: Some(format!("Undo #{}", index + 1)),
+                style: ButtonStyle::Secondary,
+                url: None,
+            }));
+        }
+
+        if description.is_empty() {
+            description.push_str("*No recent admin operations to undo.*");
+        }
+
+        let embed = embeds::builders::with_emoji('↩', "Recent admin operations")
+            .description(description)
+            .build();
+
+        let components = buttons
+            .chunks(BUTTONS_PER_ROW)
+            .map(|v| {
+                Component::ActionRow(ActionRow {
+                    components: v.to_vec(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .components(components)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
@@ -0,0 +1,101 @@
+use eden_discord_types::commands::local_guild::{EmojiAdd, EmojiCommand};
+use eden_schema::forms::InsertEmojiUploadForm;
+use eden_schema::types::EmojiUpload;
+use eden_utils::{error::exts::*, Result};
+use tracing::debug;
+use twilight_mention::Mention;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::features::emoji;
+use crate::interactions::commands::{CommandContext, CommandHelp, HelpCategory, RunCommand};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+const PENDING_MSG: &str =
+    "**Your emoji has been sent to the admins for approval.** It will be added once approved.";
+
+impl RunCommand for EmojiCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::General)
+        .details("Uploaded emojis may need to be approved by the administrators first.")
+        .examples(&["/emoji add name:eden_wave"]);
+
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Add(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Add(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Add(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+impl RunCommand for EmojiAdd {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let content_type = self.image.content_type.as_deref();
+        if let Err(reason) = emoji::validate(&self.name, content_type, self.image.size) {
+            let data = InteractionResponseDataBuilder::new()
+                .content(format!("**I cannot add this emoji** because {reason}."))
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        let image = emoji::download(&self.image).await?;
+        let form = InsertEmojiUploadForm::builder()
+            .guild_id(ctx.guild_id)
+            .requested_by(ctx.author.id)
+            .name(&self.name)
+            .image(&image)
+            .build();
+
+        let mut conn = ctx.bot.db_write().await?;
+        let upload = EmojiUpload::insert(&mut conn, form).await?;
+
+        let is_reviewer = ctx
+            .member
+            .permissions
+            .is_some_and(|v| v.intersects(emoji::REVIEWER_PERMISSIONS));
+
+        let content = if ctx.settings.emoji.require_approval && !is_reviewer {
+            debug!("emoji upload {} requires approval", upload.id);
+            emoji::request_approval(&ctx.bot, &upload, &self.image.url).await?;
+            PENDING_MSG.to_string()
+        } else {
+            // the invoker is allowed to upload emojis by themselves
+            EmojiUpload::review(&mut conn, upload.id, ctx.author.id, true).await?;
+
+            let emoji =
+                emoji::upload(&ctx.bot, ctx.guild_id, &self.name, &image, ctx.author.id).await?;
+
+            EmojiUpload::set_emoji_id(&mut conn, upload.id, emoji.id).await?;
+            format!("**Added emoji {}.**", emoji.id.mention())
+        };
+
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        let data = InteractionResponseDataBuilder::new()
+            .content(content)
+            .build();
+        ctx.respond(data).await
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_GUILD_EXPRESSIONS
+    }
+}
@@ -1,2 +1,9 @@
+pub mod admin;
+mod emoji;
+pub mod move_message;
 mod payer;
+mod preferences;
+mod role;
 mod settings;
+mod stats;
+pub mod translate_message;
@@ -0,0 +1,333 @@
+use eden_utils::error::{GuildErrorCategory, UserErrorCategory};
+use eden_utils::{error::exts::*, Error, ErrorCategory, Result};
+use thiserror::Error;
+use tracing::{debug, info, trace};
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::{
+    ActionRow, Component, SelectMenu, SelectMenuType,
+};
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::channel::{ChannelType, Message};
+use twilight_model::guild::Permissions;
+use twilight_model::http::attachment::Attachment;
+use twilight_model::id::marker::{ChannelMarker, MessageMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::interactions::commands::CommandContext;
+use crate::interactions::tags::LackingPermissionsTag;
+use crate::interactions::{InteractionContext, LocalGuildContext};
+use crate::util::http::{request_for_empty, request_for_model};
+use crate::util::webhooks::{avatar_url, WebhookMessage};
+use crate::Bot;
+
+/// Prefix of the custom ID of the channel select menu.
+pub const CUSTOM_ID_PREFIX: &str = "move_message";
+
+const SELECT_CHANNEL_MSG: &str = "**Where do you want to move this message?**";
+const SAME_CHANNEL_MSG: &str = "**This message is already in that channel.**";
+
+const USER_REQUIRED: Permissions = Permissions::MANAGE_MESSAGES;
+const USER_TARGET_CHANNEL_REQUIRED: Permissions =
+    Permissions::VIEW_CHANNEL.union(Permissions::SEND_MESSAGES);
+const SOURCE_CHANNEL_REQUIRED: Permissions = Permissions::VIEW_CHANNEL
+    .union(Permissions::READ_MESSAGE_HISTORY)
+    .union(Permissions::MANAGE_MESSAGES);
+const TARGET_CHANNEL_REQUIRED: Permissions =
+    Permissions::VIEW_CHANNEL.union(Permissions::MANAGE_WEBHOOKS);
+
+/// Attachments are re-uploaded since their URLs will no longer
+/// work once the original message is deleted.
+const ATTACHMENTS_REQUIRED: Permissions = Permissions::ATTACH_FILES;
+
+#[derive(Debug, Error)]
+#[error("user lacked permissions to move messages")]
+struct LackingUserPermissions;
+
+#[derive(Debug, Error)]
+#[error("bot lacked channel permissions to move messages")]
+struct LackingBotPermissions;
+
+#[derive(Debug, Error)]
+#[error("could not find the message to move")]
+struct MissingTargetMessage;
+
+fn make_custom_id(channel_id: Id<ChannelMarker>, message_id: Id<MessageMarker>) -> String {
+    format!("{CUSTOM_ID_PREFIX}:{channel_id}:{message_id}")
+}
+
+fn parse_custom_id(custom_id: &str) -> Option<(Id<ChannelMarker>, Id<MessageMarker>)> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != CUSTOM_ID_PREFIX {
+        return None;
+    }
+
+    let channel_id = parts.next()?.parse().ok()?;
+    let message_id = parts.next()?.parse().ok()?;
+    Some((channel_id, message_id))
+}
+
+/// Checks whether the invoker is allowed to move messages in the
+/// current channel. Discord already gives us the invoker's permissions
+/// in the channel where the interaction was invoked.
+fn check_user_permissions<T>(ctx: &LocalGuildContext<'_, T>) -> Result<()> {
+    let permissions = ctx.member.permissions.unwrap_or_else(Permissions::empty);
+    if permissions.contains(USER_REQUIRED) {
+        return Ok(());
+    }
+
+    Err(Error::context_anonymize(
+        ErrorCategory::User(UserErrorCategory::MissingPermissions),
+        LackingUserPermissions,
+    ))
+    .attach(LackingPermissionsTag::new(permissions, USER_REQUIRED))
+}
+
+/// Checks whether the invoker is allowed to send messages in the
+/// selected channel. Discord gives us the invoker's permissions in
+/// the selected channel along with the select menu interaction.
+fn check_user_target_permissions(
+    ctx: &LocalGuildContext<'_, MessageComponentInteractionData>,
+    channel_id: Id<ChannelMarker>,
+    has_attachments: bool,
+) -> Result<()> {
+    let mut required = USER_TARGET_CHANNEL_REQUIRED;
+    if has_attachments {
+        required |= ATTACHMENTS_REQUIRED;
+    }
+
+    let permissions = ctx
+        .data
+        .resolved
+        .as_ref()
+        .and_then(|v| v.channels.get(&channel_id))
+        .map_or_else(Permissions::empty, |v| v.permissions);
+
+    if permissions.contains(required) {
+        return Ok(());
+    }
+
+    Err(Error::context_anonymize(
+        ErrorCategory::User(UserErrorCategory::MissingPermissions),
+        LackingUserPermissions,
+    ))
+    .attach(LackingPermissionsTag::new(permissions, required))
+}
+
+async fn check_bot_permissions(
+    ctx: &LocalGuildContext<'_, MessageComponentInteractionData>,
+    channel_id: Id<ChannelMarker>,
+    required: Permissions,
+) -> Result<()> {
+    let permissions = ctx
+        .bot
+        .permissions_in(ctx.guild_id, channel_id, true)
+        .await?;
+    let current = permissions.channel.unwrap_or(permissions.guild);
+    if current.contains(required) {
+        return Ok(());
+    }
+
+    let tag = LackingPermissionsTag::new(current, required);
+    Err(Error::context_anonymize(
+        ErrorCategory::Guild(GuildErrorCategory::MissingChannelPermissions(
+            tag.calculated(),
+        )),
+        LackingBotPermissions,
+    ))
+    .attach(tag)
+}
+
+/// Runs the "Move to..." message command by letting the invoker
+/// choose which channel the message should be moved into.
+#[tracing::instrument(skip_all)]
+pub async fn run(ctx: &CommandContext) -> Result<()> {
+    let ctx = LocalGuildContext::from_ctx(ctx).await?;
+    check_user_permissions(&ctx)?;
+
+    let Some(message_id) = ctx.data.target_id.map(|v| v.cast::<MessageMarker>()) else {
+        return Err(Error::context_anonymize(
+            ErrorCategory::Unknown,
+            MissingTargetMessage,
+        ));
+    };
+
+    trace!("asking the invoker where to move message {message_id}");
+    let select_menu = Component::SelectMenu(SelectMenu {
+        channel_types: Some(vec![ChannelType::GuildText, ChannelType::GuildAnnouncement]),
+        custom_id: make_custom_id(ctx.channel_id, message_id),
+        disabled: false,
+        kind: SelectMenuType::Channel,
+        max_values: Some(1),
+        min_values: Some(1),
+        options: None,
+        placeholder: Some("Select a channel".into()),
+    });
+
+    let data = InteractionResponseDataBuilder::new()
+        .content(SELECT_CHANNEL_MSG)
+        .components(vec![Component::ActionRow(ActionRow {
+            components: vec![select_menu],
+        })])
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+/// Moves the message after the invoker has selected the channel
+/// where the message should be moved into.
+#[tracing::instrument(skip_all, fields(custom_id = %ctx.data.custom_id))]
+pub async fn on_select(ctx: &InteractionContext<MessageComponentInteractionData>) -> Result<()> {
+    let Some((source_id, message_id)) = parse_custom_id(&ctx.data.custom_id) else {
+        return Err(Error::context_anonymize(
+            ErrorCategory::Unknown,
+            MissingTargetMessage,
+        ))
+        .attach_printable("got invalid custom id");
+    };
+
+    let Some(target_id) = ctx
+        .data
+        .values
+        .first()
+        .and_then(|v| v.parse::<Id<ChannelMarker>>().ok())
+    else {
+        return Err(Error::context_anonymize(
+            ErrorCategory::Unknown,
+            MissingTargetMessage,
+        ))
+        .attach_printable("got no selected channel");
+    };
+
+    let ctx = LocalGuildContext::from_ctx(ctx).await?;
+    check_user_permissions(&ctx)?;
+
+    if source_id == target_id {
+        return respond_ephemeral(ctx.inner, SAME_CHANNEL_MSG.into()).await;
+    }
+
+    check_bot_permissions(&ctx, source_id, SOURCE_CHANNEL_REQUIRED).await?;
+
+    let bot = &ctx.bot;
+    let message = request_for_model(bot, bot.http.message(source_id, message_id))
+        .await
+        .attach_printable("could not get message to move")?;
+
+    let has_attachments = !message.attachments.is_empty();
+    check_user_target_permissions(&ctx, target_id, has_attachments)?;
+
+    let mut target_required = TARGET_CHANNEL_REQUIRED;
+    if has_attachments {
+        target_required |= ATTACHMENTS_REQUIRED;
+    }
+    check_bot_permissions(&ctx, target_id, target_required).await?;
+
+    debug!("moving message {message_id} from {source_id} to {target_id}");
+    repost_message(bot, target_id, &message).await?;
+
+    let reason = format!(
+        "Moved to #{target_id} by {} ({})",
+        ctx.author.name, ctx.author.id
+    );
+
+    #[allow(clippy::unwrap_used)]
+    let request = bot
+        .http
+        .delete_message(source_id, message_id)
+        .reason(&reason)
+        .unwrap();
+
+    request_for_empty(bot, request)
+        .await
+        .attach_printable("could not delete original message")?;
+
+    info!(
+        moved.by = %ctx.author.id,
+        moved.author = %message.author.id,
+        moved.from = %source_id,
+        moved.to = %target_id,
+        "moved message {message_id}"
+    );
+
+    let content = format!("**Moved message to {}!**", target_id.mention());
+    respond_ephemeral(ctx.inner, content).await
+}
+
+/// Re-posts the message into another channel with a managed webhook
+/// imitating the original author's name and avatar.
+async fn repost_message(bot: &Bot, channel_id: Id<ChannelMarker>, message: &Message) -> Result<()> {
+    let attachments = download_attachments(message).await?;
+
+    let author = &message.author;
+    let avatar_url = avatar_url(author);
+    let message = WebhookMessage {
+        username: author.global_name.as_deref().unwrap_or(&author.name),
+        avatar_url: Some(&avatar_url),
+        content: &message.content,
+        embeds: &message.embeds,
+        attachments: &attachments,
+    };
+
+    bot.webhooks
+        .send(channel_id, message)
+        .await
+        .attach_printable("could not re-post message with webhook")?;
+
+    Ok(())
+}
+
+/// Downloads the message's attachments so they can be uploaded
+/// again with the re-posted message.
+async fn download_attachments(message: &Message) -> Result<Vec<Attachment>> {
+    let mut attachments = Vec::with_capacity(message.attachments.len());
+    for (id, attachment) in (1..).zip(&message.attachments) {
+        trace!("downloading attachment {}", attachment.id);
+
+        let response = reqwest::get(attachment.url.as_str())
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .into_typed_error()
+            .attach_printable("could not send request to Discord to download attachment")?;
+
+        let data = response
+            .bytes()
+            .await
+            .into_typed_error()
+            .attach_printable("could not download attachment data")?;
+
+        let mut file = Attachment::from_bytes(attachment.filename.clone(), data.into(), id);
+        file.description.clone_from(&attachment.description);
+        attachments.push(file);
+    }
+
+    Ok(attachments)
+}
+
+async fn respond_ephemeral<T>(ctx: &InteractionContext<T>, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_custom_ids() {
+        let channel_id = Id::new(1);
+        let message_id = Id::new(2);
+        assert_eq!(
+            parse_custom_id(&make_custom_id(channel_id, message_id)),
+            Some((channel_id, message_id))
+        );
+        assert_eq!(parse_custom_id("verification:1:2"), None);
+        assert_eq!(parse_custom_id("move_message:1"), None);
+    }
+}
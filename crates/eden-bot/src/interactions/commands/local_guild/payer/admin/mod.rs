@@ -0,0 +1,37 @@
+use crate::interactions::commands::{CommandContext, RunCommand};
+use eden_discord_types::commands::local_guild::PayerAdminCommand;
+use twilight_model::guild::Permissions;
+
+mod queue;
+
+impl RunCommand for PayerAdminCommand {
+    async fn run(&self, ctx: &CommandContext) -> eden_utils::Result<()> {
+        match self {
+            Self::Queue(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Queue(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Queue(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn channel_permissions(&self) -> Permissions {
+        match self {
+            Self::Queue(cmd) => cmd.channel_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        match self {
+            Self::Queue(cmd) => cmd.is_ephemeral(),
+        }
+    }
+}
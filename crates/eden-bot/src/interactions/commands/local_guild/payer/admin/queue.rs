@@ -0,0 +1,28 @@
+use eden_discord_types::commands::local_guild::PayerAdminQueue;
+use eden_utils::Result;
+use twilight_model::guild::Permissions;
+
+use crate::features::payer_queue;
+use crate::interactions::{
+    commands::{CommandContext, RunCommand},
+    record_local_guild_ctx, LocalGuildContext,
+};
+
+impl RunCommand for PayerAdminQueue {
+    #[tracing::instrument(skip_all, fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let data = payer_queue::render(&ctx.bot, ctx.guild_id, 0).await?;
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
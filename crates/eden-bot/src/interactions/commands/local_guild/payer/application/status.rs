@@ -37,35 +37,37 @@ impl RunCommand for PayerApplicationStatus {
         };
 
         let mut content = String::from("**Status**: ");
-        let mut footer = String::from("Updated: ");
-
-        let embed = embeds::builders::with_emoji('📋', "Application Status");
+        let mut embed = embeds::builders::with_emoji('📋', "Application Status");
         let result = get_application_result(&application);
 
-        // we need to let the user know that the time zone is in UTC
+        // Discord does not render timestamp markups in embed footers, so
+        // the embed's timestamp is used instead since relative times written
+        // in the footer will be out of date after the response is sent.
         trace!(?result, "got payer application result");
-        match result {
+        let footer = match result {
             ApplicationResult::Pending => {
-                write!(&mut footer, "None").into_typed_error()?;
                 writeln!(&mut content, "🕑 Pending").into_typed_error()?;
                 writeln!(&mut content).into_typed_error()?;
                 writeln!(&mut content, "{PENDING_MESSAGE}").into_typed_error()?;
+                "Not reviewed yet"
             }
             ApplicationResult::Passed { updated } => {
-                write!(&mut footer, "{} (UTC)", updated.to_rfc2822()).into_typed_error()?;
+                embed = embeds::builders::with_timestamp(embed, updated);
                 writeln!(&mut content, "✅ Approved").into_typed_error()?;
                 writeln!(&mut content).into_typed_error()?;
                 write!(&mut content, "{APPROVED_MESSAGE}").into_typed_error()?;
+                "Updated"
             }
             ApplicationResult::Failed { reason, updated } => {
-                write!(&mut footer, "{} (UTC)", updated.to_rfc2822()).into_typed_error()?;
+                embed = embeds::builders::with_timestamp(embed, updated);
 
                 let message = REJECTION_MESSAGE.replace("{INSERT_MESSAGE}", &reason.into_inner());
                 writeln!(&mut content, "❌ Rejected").into_typed_error()?;
                 writeln!(&mut content).into_typed_error()?;
                 write!(&mut content, "{message}").into_typed_error()?;
+                "Updated"
             }
-        }
+        };
 
         let embed = embed
             .description(content)
@@ -1,45 +1,70 @@
-use crate::interactions::commands::{CommandContext, RunCommand};
+use crate::interactions::commands::{CommandContext, CommandHelp, HelpCategory, RunCommand};
 use eden_discord_types::commands::local_guild::PayerCommand;
 use twilight_model::guild::Permissions;
 
+mod admin;
 mod application;
 mod pay_bill;
 mod register;
+mod stats;
 
 impl RunCommand for PayerCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::Payer)
+        .details("Monthly contributors share the cost of the server's subscriptions.")
+        .examples(&["/payer register", "/payer pay_bill", "/payer stats"]);
+
     async fn run(&self, ctx: &CommandContext) -> eden_utils::Result<()> {
         match self {
+            Self::Admin(cmd) => cmd.run(ctx).await,
             Self::Application(cmd) => cmd.run(ctx).await,
             Self::PayBill(cmd) => cmd.run(ctx).await,
             Self::Register(cmd) => cmd.run(ctx).await,
+            Self::Stats(cmd) => cmd.run(ctx).await,
             Self::Test(..) => ctx.unimplemented_cmd(),
         }
     }
 
     fn guild_permissions(&self) -> Permissions {
         match self {
+            Self::Admin(cmd) => cmd.guild_permissions(),
             Self::Application(cmd) => cmd.guild_permissions(),
             Self::PayBill(cmd) => cmd.guild_permissions(),
             Self::Register(cmd) => cmd.guild_permissions(),
+            Self::Stats(cmd) => cmd.guild_permissions(),
             Self::Test(..) => Permissions::empty(),
         }
     }
 
     fn user_permissions(&self) -> Permissions {
         match self {
+            Self::Admin(cmd) => cmd.user_permissions(),
             Self::Application(cmd) => cmd.user_permissions(),
             Self::PayBill(cmd) => cmd.user_permissions(),
             Self::Register(cmd) => cmd.user_permissions(),
+            Self::Stats(cmd) => cmd.user_permissions(),
             Self::Test(..) => Permissions::empty(),
         }
     }
 
     fn channel_permissions(&self) -> Permissions {
         match self {
+            Self::Admin(cmd) => cmd.channel_permissions(),
             Self::Application(cmd) => cmd.channel_permissions(),
             Self::PayBill(cmd) => cmd.channel_permissions(),
             Self::Register(cmd) => cmd.channel_permissions(),
+            Self::Stats(cmd) => cmd.channel_permissions(),
             Self::Test(..) => Permissions::empty(),
         }
     }
+
+    fn is_ephemeral(&self) -> bool {
+        match self {
+            Self::Admin(cmd) => cmd.is_ephemeral(),
+            Self::Application(cmd) => cmd.is_ephemeral(),
+            Self::PayBill(cmd) => cmd.is_ephemeral(),
+            Self::Register(cmd) => cmd.is_ephemeral(),
+            Self::Stats(cmd) => cmd.is_ephemeral(),
+            Self::Test(..) => false,
+        }
+    }
 }
@@ -17,12 +17,10 @@ impl RunCommand for PayerPayBill {
         record_local_guild_ctx!(ctx);
 
         // create DM channel
-        let dm_channel_id = request_for_model(
-            &ctx.bot.http,
-            ctx.bot.http.create_private_channel(ctx.author.id),
-        )
-        .await?
-        .id;
+        let dm_channel_id =
+            request_for_model(&ctx.bot, ctx.bot.http.create_private_channel(ctx.author.id))
+                .await?
+                .id;
 
         // then, create a message prompting the user to upload or put your reference number and stuff
         let message = match self.method {
@@ -39,7 +37,7 @@ impl RunCommand for PayerPayBill {
             .content(&message)
             .unwrap();
 
-        request_for_model(&ctx.bot.http, result).await?;
+        request_for_model(&ctx.bot, result).await?;
 
         let state = PayerPayBillState::new(ctx.author.id, dm_channel_id, self.method);
         let command = StatefulCommand::PayerPayBill(state);
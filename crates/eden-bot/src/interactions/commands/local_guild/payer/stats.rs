@@ -0,0 +1,99 @@
+use chrono::Utc;
+use eden_discord_types::commands::local_guild::PayerStats;
+use eden_schema::types::{PayerContributionStat, PublicPayerStatsPreference, UserPreferences};
+use eden_utils::Result;
+use std::fmt::Write as _;
+use twilight_mention::Mention;
+use twilight_model::channel::message::MessageFlags;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::response_cache::{CacheKey, CachedCommand};
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+const LEADERBOARD_LIMIT: i64 = 10;
+
+/// Formats the totals of every currency the payer has paid
+/// with since they cannot be added together.
+fn format_total(stat: &PayerContributionStat) -> String {
+    if stat.totals.is_empty() {
+        return "0".into();
+    }
+
+    stat.totals
+        .iter()
+        .map(|(currency, total)| format!("{total} {currency}"))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+impl RunCommand for PayerStats {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        // the response includes the invoker's own stats
+        let invoker_id = ctx.author.id.to_string();
+        let key = CacheKey::new(CachedCommand::PayerStats, ctx.guild_id, invoker_id);
+        let cache = &ctx.bot.response_cache;
+        if let Some(data) = cache.get(&key, Utc::now()) {
+            return ctx.respond(data).await;
+        }
+
+        let mut conn = ctx.bot.db_read().await?;
+        let top = PayerContributionStat::public_top(&mut conn, LEADERBOARD_LIMIT).await?;
+        let own = PayerContributionStat::from_payer(&mut conn, ctx.author.id).await?;
+        let public =
+            UserPreferences::get::<PublicPayerStatsPreference>(&mut conn, ctx.author.id).await?;
+        drop(conn);
+
+        let mut description = String::new();
+        for (rank, stat) in top.iter().enumerate() {
+            let _ = writeln!(
+                description,
+                "**{}.** {} - {} ({} bill(s), {} month streak)",
+                rank + 1,
+                stat.payer_id.mention(),
+                format_total(stat),
+                stat.paid_bills,
+                stat.current_streak
+            );
+        }
+
+        if description.is_empty() {
+            description.push_str("*No contributors have shared their stats yet.*");
+        }
+
+        if let Some(stat) = own.as_ref() {
+            let visibility = if public { "public" } else { "private" };
+            let _ = write!(
+                description,
+                "\n**Your contributions** ({visibility})\n\
+                **Total paid**: {}\n\
+                **Paid bills**: {}\n\
+                **Current streak**: {}\n\
+                **Longest streak**: {}",
+                format_total(stat),
+                stat.paid_bills,
+                stat.current_streak,
+                stat.longest_streak
+            );
+        }
+
+        let embed = embeds::builders::with_emoji('💸', "Top contributors")
+            .description(description)
+            .build();
+
+        let mut data = InteractionResponseDataBuilder::new().embeds(vec![embed]);
+
+        // only the invoker should see their own stats if they did not opt in
+        if own.is_some() && !public {
+            data = data.flags(MessageFlags::EPHEMERAL);
+        }
+
+        let data = data.build();
+        cache.insert(key, &data, Utc::now());
+        ctx.respond(data).await
+    }
+}
@@ -0,0 +1,183 @@
+use eden_discord_types::commands::local_guild::{
+    PreferencesCommand, PreferencesDmReminders, PreferencesLocale, PreferencesPublicPayerStats,
+    PreferencesTimezone, PreferencesView,
+};
+use eden_schema::types::{
+    DmRemindersOptOutPreference, LocalePreference, PreferenceKey, PublicPayerStatsPreference,
+    TimezonePreference, UserPreferences,
+};
+use eden_utils::{error::exts::*, Result};
+use std::fmt::Debug;
+use tracing::trace;
+use twilight_model::channel::message::MessageFlags;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::features::preferences;
+use crate::interactions::commands::{CommandContext, CommandHelp, HelpCategory, RunCommand};
+use crate::interactions::embeds;
+use crate::interactions::response_cache::CachedCommand;
+
+impl RunCommand for PreferencesCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::General).examples(&[
+        "/preferences view",
+        "/preferences timezone set:+08:00",
+        "/preferences locale",
+    ]);
+
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::DmReminders(cmd) => cmd.run(ctx).await,
+            Self::Locale(cmd) => cmd.run(ctx).await,
+            Self::PublicPayerStats(cmd) => cmd.run(ctx).await,
+            Self::Timezone(cmd) => cmd.run(ctx).await,
+            Self::View(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+async fn reply(ctx: &CommandContext, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+/// Modifies the invoker's preference if `overwrite` is specified,
+/// otherwise it replies with the current value.
+async fn modify_or_get<K: PreferenceKey>(
+    ctx: &CommandContext,
+    name: &str,
+    overwrite: Option<K::Value>,
+) -> Result<()> {
+    let invoker_id = ctx.invoker_id();
+    let Some(overwrite) = overwrite else {
+        trace!("getting {:?} preference for user {invoker_id}", K::KEY);
+
+        let mut conn = ctx.bot.db_read().await?;
+        let value = UserPreferences::get::<K>(&mut conn, invoker_id).await?;
+        return reply(ctx, format_value(name, &value, false)).await;
+    };
+
+    trace!("overriding {:?} preference for user {invoker_id}", K::KEY);
+
+    let mut conn = ctx.bot.db_write().await?;
+    UserPreferences::set::<K>(&mut conn, invoker_id, &overwrite).await?;
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    reply(ctx, format_value(name, &overwrite, true)).await
+}
+
+fn format_value(name: &str, value: &impl Debug, changed: bool) -> String {
+    if changed {
+        format!("**Changed \"{name}\" to**: `{value:?}`")
+    } else {
+        format!("**{name}**: `{value:?}`")
+    }
+}
+
+impl RunCommand for PreferencesDmReminders {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        // the stored preference is an opt-out, so it has to be inverted
+        let overwrite = self.set.map(|v| !v);
+        if overwrite.is_some() {
+            return modify_or_get::<DmRemindersOptOutPreference>(
+                ctx,
+                "Opted out of DM reminders",
+                overwrite,
+            )
+            .await;
+        }
+
+        let allowed = preferences::allows_dm_reminders(&ctx.bot, ctx.invoker_id()).await?;
+        reply(ctx, format_value("Receive DM reminders", &allowed, false)).await
+    }
+}
+
+impl RunCommand for PreferencesLocale {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let overwrite = self.set.as_deref().map(str::trim);
+        if let Some(locale) = overwrite
+            && !preferences::is_valid_locale(locale)
+        {
+            let content = "**Invalid locale!** Please use something like `en`, `en-US` or `ja`.";
+            return reply(ctx, content.into()).await;
+        }
+
+        modify_or_get::<LocalePreference>(ctx, "Locale", overwrite.map(String::from)).await
+    }
+}
+
+impl RunCommand for PreferencesPublicPayerStats {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        modify_or_get::<PublicPayerStatsPreference>(ctx, "Public payer stats", self.set).await?;
+
+        // the user may be on (or off) the leaderboard now
+        if self.set.is_some() {
+            ctx.bot.response_cache.bust(CachedCommand::PayerStats);
+        }
+        Ok(())
+    }
+}
+
+impl RunCommand for PreferencesTimezone {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let overwrite = match self.set.as_deref() {
+            Some(input) => match preferences::parse_timezone(input) {
+                Some(offset) => Some(offset.to_string()),
+                None => {
+                    let content =
+                        "**Invalid timezone!** Please use a UTC offset like `+08:00` or `-05:00`.";
+                    return reply(ctx, content.into()).await;
+                }
+            },
+            None => None,
+        };
+
+        modify_or_get::<TimezonePreference>(ctx, "Timezone", overwrite).await
+    }
+}
+
+impl RunCommand for PreferencesView {
+    #[tracing::instrument(skip(ctx))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let mut conn = ctx.bot.db_read().await?;
+        let preferences = UserPreferences::get_all(&mut conn, ctx.invoker_id()).await?;
+        drop(conn);
+
+        let dm_reminders = if preferences.dm_reminders_opt_out {
+            "Disabled"
+        } else {
+            "Enabled"
+        };
+
+        let public_payer_stats = if preferences.public_payer_stats {
+            "Public"
+        } else {
+            "Private"
+        };
+
+        let embed = embeds::builders::with_emoji('⚙', "Your preferences")
+            .description(format!(
+                "**Locale**: `{}`\n**Timezone**: `UTC{}`\n**DM reminders**: {dm_reminders}\n\
+                **Payer stats**: {public_payer_stats}",
+                preferences.locale, preferences.timezone
+            ))
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+}
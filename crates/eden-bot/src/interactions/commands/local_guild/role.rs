@@ -0,0 +1,253 @@
+use chrono::{TimeDelta, Utc};
+use eden_discord_types::commands::local_guild::{RoleCommand, RoleGrantTemp, RoleTempList};
+use eden_schema::forms::InsertTempRoleGrantForm;
+use eden_schema::types::TempRoleGrant;
+use eden_tasks::Scheduled;
+use eden_utils::time::{discord_timestamp, parse_time_delta, TimestampStyle};
+use eden_utils::{error::exts::*, Result};
+use std::fmt::Write as _;
+use tracing::debug;
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
+use twilight_model::channel::message::AllowedMentions;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::features::{auto_role, blacklist};
+use crate::interactions::commands::{CommandContext, CommandHelp, HelpCategory, RunCommand};
+use crate::interactions::validation::{Validator, ViolationKind, DURATION_FORMAT};
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+use crate::tasks;
+use crate::util::http::request_for_empty;
+
+/// Maximum amount of active grants shown from `/role temp-list`.
+const LIST_LIMIT: usize = 25;
+
+impl RunCommand for RoleCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::Moderation)
+        .permissions(Permissions::MANAGE_ROLES)
+        .examples(&[
+            "/role grant-temp user:@Eden role:@Supporter duration:30d",
+            "/role temp-list",
+        ]);
+
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::GrantTemp(cmd) => cmd.run(ctx).await,
+            Self::TempList(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::GrantTemp(cmd) => cmd.user_permissions(),
+            Self::TempList(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::GrantTemp(cmd) => cmd.guild_permissions(),
+            Self::TempList(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        match self {
+            Self::GrantTemp(cmd) => cmd.validate(validator),
+            Self::TempList(cmd) => cmd.validate(validator),
+        }
+    }
+}
+
+impl RunCommand for RoleGrantTemp {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        // the duration is already checked in `validate`
+        let duration = parse_time_delta(&self.duration).unwrap_or_else(TimeDelta::zero);
+
+        let is_owner = blacklist::is_owner(&ctx.bot, ctx.author.id).await?;
+        let unassignable = auto_role::find_unassignable_by(
+            &ctx.bot,
+            ctx.guild_id,
+            self.role,
+            &ctx.member.roles,
+            is_owner,
+        )
+        .await?;
+
+        if let Some(reason) = unassignable {
+            let data = InteractionResponseDataBuilder::new()
+                .allowed_mentions(AllowedMentions::default())
+                .content(format!(
+                    "**I cannot give {} to {}** because {reason}.",
+                    self.role.mention(),
+                    self.user.mention()
+                ))
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        let mut conn = ctx.bot.db_write().await?;
+        let active = TempRoleGrant::active(&mut conn, ctx.guild_id, self.user, self.role).await?;
+
+        // the role must not be removed once it expires if the member
+        // already had it before, only active grants can be extended.
+        let has_role = ctx
+            .data
+            .resolved
+            .as_ref()
+            .and_then(|v| v.members.get(&self.user))
+            .is_some_and(|v| v.roles.contains(&self.role));
+
+        if has_role && active.is_none() {
+            drop(conn);
+
+            let data = InteractionResponseDataBuilder::new()
+                .allowed_mentions(AllowedMentions::default())
+                .content(format!(
+                    "**{} already has {}.**",
+                    self.user.mention(),
+                    self.role.mention()
+                ))
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        let expires_at = Utc::now() + duration;
+        debug!(
+            "granting temporary role {} to member {} until {expires_at}",
+            self.role, self.user
+        );
+
+        let form = InsertTempRoleGrantForm::builder()
+            .guild_id(ctx.guild_id)
+            .user_id(self.user)
+            .role_id(self.role)
+            .granted_by(ctx.author.id)
+            .expires_at(expires_at)
+            .build();
+
+        let grant = TempRoleGrant::grant(&mut conn, form).await?;
+
+        let task = tasks::RemoveTempRole { grant_id: grant.id };
+        ctx.bot
+            .queue
+            .schedule(task, Scheduled::At(grant.expires_at))
+            .await?;
+
+        // the grant is recorded first so the role will be
+        // removed even if the bot restarts after giving it
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        if !has_role {
+            #[allow(clippy::unwrap_used)]
+            let request = ctx
+                .bot
+                .http
+                .add_guild_member_role(ctx.guild_id, self.user, self.role)
+                .reason("Temporary role granted")
+                .unwrap();
+
+            if let Err(error) = request_for_empty(&ctx.bot, request).await {
+                let mut conn = ctx.bot.db_write().await?;
+                TempRoleGrant::mark_removed(&mut conn, grant.id).await?;
+                conn.commit()
+                    .await
+                    .into_eden_error()
+                    .attach_printable("could not commit transaction")?;
+
+                return Err(error).attach_printable("could not give temporary role to member");
+            }
+        }
+
+        let data = InteractionResponseDataBuilder::new()
+            .allowed_mentions(AllowedMentions::default())
+            .content(format!(
+                "**Gave {} to {}** until {} ({}).",
+                self.role.mention(),
+                self.user.mention(),
+                discord_timestamp(grant.expires_at, TimestampStyle::LongDateTime),
+                discord_timestamp(grant.expires_at, TimestampStyle::Relative)
+            ))
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        let duration = validator.parse(
+            "duration",
+            &self.duration,
+            parse_time_delta,
+            DURATION_FORMAT,
+        );
+        if let Some(duration) = duration {
+            validator.check(
+                "duration",
+                duration > TimeDelta::zero(),
+                ViolationKind::NotPositive,
+            );
+        }
+    }
+}
+
+impl RunCommand for RoleTempList {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let mut conn = ctx.bot.db_read().await?;
+        let grants = TempRoleGrant::active_list(&mut conn, ctx.guild_id).await?;
+
+        let mut description = String::new();
+        for grant in grants.iter().take(LIST_LIMIT) {
+            let _ = writeln!(
+                description,
+                "- {} has {} (expires {})",
+                grant.user_id.mention(),
+                grant.role_id.mention(),
+                discord_timestamp(grant.expires_at, TimestampStyle::Relative)
+            );
+        }
+
+        if grants.len() > LIST_LIMIT {
+            let _ = writeln!(description, "*...and {} more*", grants.len() - LIST_LIMIT);
+        }
+
+        if description.is_empty() {
+            description.push_str("*No active temporary roles.*");
+        }
+
+        let embed = embeds::builders::with_emoji('⏳', "Active temporary roles")
+            .description(description)
+            .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+}
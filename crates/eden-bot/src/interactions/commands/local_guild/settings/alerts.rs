@@ -0,0 +1,86 @@
+use eden_discord_types::commands::local_guild::{AlertsSettingsChannel, AlertsSettingsCommand};
+use eden_utils::Result;
+use tracing::trace;
+use twilight_mention::Mention;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+/// Permissions Eden needs in the alert channel to send alerts.
+const ALERT_PERMISSIONS: Permissions = Permissions::VIEW_CHANNEL
+    .union(Permissions::SEND_MESSAGES)
+    .union(Permissions::EMBED_LINKS)
+    .union(Permissions::ATTACH_FILES);
+
+impl RunCommand for AlertsSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Channel(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+impl RunCommand for AlertsSettingsChannel {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Alert channel";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(channel_id) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx
+                .settings
+                .alerts
+                .channel_id
+                .unwrap_or(ctx.bot.settings.bot.local_guild.alert_channel_id);
+
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        let can_send = ctx
+            .bot
+            .preflight_permissions(ctx.guild_id, channel_id, ALERT_PERMISSIONS)
+            .await?;
+
+        if !can_send {
+            let data = InteractionResponseDataBuilder::new()
+                .content(format!(
+                    "**I cannot send alerts to {}** because I need `View Channel`, \
+                    `Send Messages`, `Embed Links` and `Attach Files` permissions there.",
+                    channel_id.mention()
+                ))
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        trace!("overriding {NAME:?} to {channel_id:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.alerts.channel_id = Some(channel_id);
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, channel_id).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
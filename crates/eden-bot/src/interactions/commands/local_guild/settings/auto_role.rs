@@ -0,0 +1,195 @@
+use eden_discord_types::commands::local_guild::{
+    AutoRoleSettingsAdd, AutoRoleSettingsCommand, AutoRoleSettingsDelay, AutoRoleSettingsList,
+    AutoRoleSettingsRemove,
+};
+use eden_utils::Result;
+use tracing::trace;
+use twilight_mention::Mention;
+use twilight_model::channel::message::AllowedMentions;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::auto_role::{self, UnassignableRole};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for AutoRoleSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Add(cmd) => cmd.run(ctx).await,
+            Self::Delay(cmd) => cmd.run(ctx).await,
+            Self::List(cmd) => cmd.run(ctx).await,
+            Self::Remove(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Add(cmd) => cmd.user_permissions(),
+            Self::Delay(cmd) => cmd.user_permissions(),
+            Self::List(cmd) => cmd.user_permissions(),
+            Self::Remove(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Add(cmd) => cmd.guild_permissions(),
+            Self::Delay(cmd) => cmd.guild_permissions(),
+            Self::List(cmd) => cmd.guild_permissions(),
+            Self::Remove(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+/// Replies with a message without pinging any of the mentioned roles.
+async fn reply(ctx: &CommandContext, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .allowed_mentions(AllowedMentions::default())
+        .build();
+
+    ctx.respond(data).await
+}
+
+impl RunCommand for AutoRoleSettingsAdd {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let role_ids = &ctx.settings.auto_role.role_ids;
+        if role_ids.contains(&self.role) {
+            let content = format!("{} is already one of the auto roles.", self.role.mention());
+            return reply(ctx.inner, content).await;
+        }
+
+        if role_ids.len() >= auto_role::MAX_ROLES {
+            let content = format!(
+                "**Cannot add more auto roles!** Only up to {} roles can be given to new members.",
+                auto_role::MAX_ROLES
+            );
+            return reply(ctx.inner, content).await;
+        }
+
+        let unassignable = auto_role::find_unassignable(&ctx.bot, ctx.guild_id, self.role).await?;
+        if let Some(reason) = unassignable {
+            let hint = match reason {
+                UnassignableRole::AboveBot => {
+                    "\n\nPlease move my highest role above that role and try again."
+                }
+                _ => "",
+            };
+            let content = format!(
+                "**I cannot give {} to new members** because {reason}.{hint}",
+                self.role.mention()
+            );
+            return reply(ctx.inner, content).await;
+        }
+
+        trace!("adding auto role {}", self.role);
+
+        let mut form = ctx.settings.data.clone();
+        form.auto_role.role_ids.push(self.role);
+        super::save_settings(&ctx, "Auto roles", &form).await?;
+
+        let content = format!("**Added {} to the auto roles.**", self.role.mention());
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+}
+
+impl RunCommand for AutoRoleSettingsDelay {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Auto role delay (seconds)";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(seconds) = self.seconds else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.auto_role.delay_seconds;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        // zero seconds means roles will be given immediately
+        let value = u32::try_from(seconds).ok().filter(|v| *v > 0);
+        trace!("overriding {NAME:?} to {value:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.auto_role.delay_seconds = value;
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, value).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AutoRoleSettingsList {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let auto_role = &ctx.settings.auto_role;
+        if auto_role.role_ids.is_empty() {
+            return reply(ctx.inner, "**There are no auto roles yet.**".into()).await;
+        }
+
+        let roles = auto_role
+            .role_ids
+            .iter()
+            .map(|v| format!("- {}", v.mention()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let delay = match auto_role.delay_seconds {
+            Some(seconds) => format!("after {seconds} second(s)"),
+            None => "immediately".into(),
+        };
+
+        let content = format!("**Auto roles** (given {delay}):\n{roles}");
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for AutoRoleSettingsRemove {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if !ctx.settings.auto_role.role_ids.contains(&self.role) {
+            let content = format!("{} is not one of the auto roles.", self.role.mention());
+            return reply(ctx.inner, content).await;
+        }
+
+        trace!("removing auto role {}", self.role);
+
+        let mut form = ctx.settings.data.clone();
+        form.auto_role.role_ids.retain(|v| *v != self.role);
+        super::save_settings(&ctx, "Auto roles", &form).await?;
+
+        let content = format!("**Removed {} from the auto roles.**", self.role.mention());
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
@@ -0,0 +1,56 @@
+use eden_discord_types::commands::local_guild::{EmojiSettingsApproval, EmojiSettingsCommand};
+use eden_utils::Result;
+use tracing::trace;
+use twilight_model::guild::Permissions;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for EmojiSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Approval(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Approval(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Approval(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+impl RunCommand for EmojiSettingsApproval {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Emoji uploads require approval";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(require_approval) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.emoji.require_approval;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {require_approval:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.emoji.require_approval = require_approval;
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, require_approval).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
@@ -0,0 +1,58 @@
+use eden_discord_types::commands::local_guild::{
+    FatherBeltSettingsCommand, FatherBeltSettingsEnabled,
+};
+use eden_utils::Result;
+use tracing::trace;
+use twilight_model::guild::Permissions;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for FatherBeltSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Enabled(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Enabled(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Enabled(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+impl RunCommand for FatherBeltSettingsEnabled {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Father belt enabled";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(enabled) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.father_belt.enabled;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {enabled:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.father_belt.enabled = enabled;
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, enabled).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
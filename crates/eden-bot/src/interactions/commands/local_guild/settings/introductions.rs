@@ -0,0 +1,175 @@
+use eden_discord_types::commands::local_guild::{
+    IntroductionsSettingsChannel, IntroductionsSettingsCommand, IntroductionsSettingsEnabled,
+    IntroductionsSettingsPreview,
+};
+use eden_utils::Result;
+use std::fmt::Write as _;
+use tracing::trace;
+use twilight_mention::Mention;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::father_belt::{render_introduction, INTRODUCTION_PERMISSIONS};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+const MISSING_PERMISSIONS_MSG: &str =
+    "I need `View Channel`, `Send Messages` and `Read Message History` permissions there";
+
+impl RunCommand for IntroductionsSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Channel(cmd) => cmd.run(ctx).await,
+            Self::Enabled(cmd) => cmd.run(ctx).await,
+            Self::Preview(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.user_permissions(),
+            Self::Enabled(cmd) => cmd.user_permissions(),
+            Self::Preview(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.guild_permissions(),
+            Self::Enabled(cmd) => cmd.guild_permissions(),
+            Self::Preview(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        match self {
+            Self::Channel(cmd) => cmd.is_ephemeral(),
+            Self::Enabled(cmd) => cmd.is_ephemeral(),
+            Self::Preview(cmd) => cmd.is_ephemeral(),
+        }
+    }
+}
+
+impl RunCommand for IntroductionsSettingsChannel {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Introduction channel";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(channel_id) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.introductions.channel_id;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        let can_reply = ctx
+            .bot
+            .preflight_permissions(ctx.guild_id, channel_id, INTRODUCTION_PERMISSIONS)
+            .await?;
+
+        if !can_reply {
+            let data = InteractionResponseDataBuilder::new()
+                .content(format!(
+                    "**I cannot use {} for introductions** because {MISSING_PERMISSIONS_MSG}.",
+                    channel_id.mention()
+                ))
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        trace!("overriding {NAME:?} to {channel_id:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.introductions.channel_id = Some(channel_id);
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, Some(channel_id)).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for IntroductionsSettingsEnabled {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Introductions enabled";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(enabled) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.introductions.enabled;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {enabled:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.introductions.enabled = enabled;
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, enabled).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for IntroductionsSettingsPreview {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let rendered = render_introduction(&self.sample, ctx.bot.application_id().cast());
+        let mut content = match rendered {
+            Some(rendered) => format!("**Eden will reply with**:\n{rendered}"),
+            None => "**Eden will not reply** because no name can be found.".to_string(),
+        };
+
+        if !ctx.settings.introductions.enabled || !ctx.settings.father_belt.enabled {
+            content.push_str("\n\n**Warning**: Introductions are currently disabled.");
+        }
+
+        // admins should know if Eden cannot reply in the configured channel
+        if let Some(channel_id) = ctx.settings.introductions.channel_id {
+            let can_reply = ctx
+                .bot
+                .preflight_permissions(ctx.guild_id, channel_id, INTRODUCTION_PERMISSIONS)
+                .await?;
+
+            if !can_reply {
+                let _ = write!(
+                    content,
+                    "\n\n**Warning**: I cannot reply in {} because {MISSING_PERMISSIONS_MSG}.",
+                    channel_id.mention()
+                );
+            }
+        }
+
+        let data = InteractionResponseDataBuilder::new()
+            .content(content)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn is_ephemeral(&self) -> bool {
+        true
+    }
+}
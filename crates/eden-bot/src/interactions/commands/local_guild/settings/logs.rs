@@ -0,0 +1,114 @@
+use eden_discord_types::commands::local_guild::{
+    LogsSettingsCommand, LogsSettingsMember, LogsSettingsServer,
+};
+use eden_utils::Result;
+use tracing::trace;
+use twilight_model::channel::ChannelType;
+use twilight_model::guild::Permissions;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::validation::Validator;
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+/// Channels where Eden can send logs to.
+const LOG_CHANNEL_TYPES: &[ChannelType] = &[ChannelType::GuildText, ChannelType::GuildAnnouncement];
+
+impl RunCommand for LogsSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Member(cmd) => cmd.run(ctx).await,
+            Self::Server(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Member(cmd) => cmd.user_permissions(),
+            Self::Server(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Member(cmd) => cmd.guild_permissions(),
+            Self::Server(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        match self {
+            Self::Member(cmd) => cmd.validate(validator),
+            Self::Server(cmd) => cmd.validate(validator),
+        }
+    }
+}
+
+impl RunCommand for LogsSettingsMember {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Member log channel";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(channel_id) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.logs.member_channel_id;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {channel_id:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.logs.member_channel_id = Some(channel_id);
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, Some(channel_id)).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        if let Some(channel_id) = self.set {
+            validator.channel_type("set", channel_id, LOG_CHANNEL_TYPES);
+        }
+    }
+}
+
+impl RunCommand for LogsSettingsServer {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Server log channel";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(channel_id) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.logs.server_channel_id;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {channel_id:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.logs.server_channel_id = Some(channel_id);
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, Some(channel_id)).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        if let Some(channel_id) = self.set {
+            validator.channel_type("set", channel_id, LOG_CHANNEL_TYPES);
+        }
+    }
+}
@@ -1,34 +1,177 @@
-use crate::interactions::commands::{CommandContext, RunCommand};
+use crate::features::guild_snapshot;
+use crate::features::undo::{self, UndoAction};
+use crate::interactions::commands::{CommandContext, CommandHelp, HelpCategory, RunCommand};
+use crate::interactions::validation::Validator;
+use crate::interactions::LocalGuildContext;
 use eden_discord_types::commands::local_guild::SettingsCommand;
-use eden_utils::Result;
+use eden_schema::types::GuildSettings;
+use eden_utils::{error::exts::*, Result};
 use std::fmt::Debug;
 use twilight_model::guild::Permissions;
 use twilight_util::builder::InteractionResponseDataBuilder;
 
+mod alerts;
+mod auto_role;
+mod emoji;
+mod father_belt;
+mod introductions;
+mod logs;
 mod payer;
+mod quiet_hours;
+mod raid;
+mod scheduled_events;
+mod translation;
 mod user;
+mod verification;
+mod word_filter;
 
 impl RunCommand for SettingsCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::Administration)
+        .permissions(Permissions::ADMINISTRATOR)
+        .examples(&[
+            "/settings quiet-hours schedule start:22:00 end:06:00",
+            "/settings wordfilter",
+            "/settings fatherbelt enabled set:False",
+        ]);
+
     async fn run(&self, ctx: &CommandContext) -> Result<()> {
         match self {
+            Self::Alerts(cmd) => cmd.run(ctx).await,
+            Self::AutoRole(cmd) => cmd.run(ctx).await,
+            Self::Emoji(cmd) => cmd.run(ctx).await,
+            Self::Events(cmd) => cmd.run(ctx).await,
+            Self::FatherBelt(cmd) => cmd.run(ctx).await,
+            Self::Introductions(cmd) => cmd.run(ctx).await,
+            Self::Logs(cmd) => cmd.run(ctx).await,
             Self::Payer(cmd) => cmd.run(ctx).await,
+            Self::QuietHours(cmd) => cmd.run(ctx).await,
+            Self::Raid(cmd) => cmd.run(ctx).await,
+            Self::Translation(cmd) => cmd.run(ctx).await,
             Self::User(cmd) => cmd.run(ctx).await,
+            Self::Verification(cmd) => cmd.run(ctx).await,
+            Self::WordFilter(cmd) => cmd.run(ctx).await,
         }
     }
 
     fn guild_permissions(&self) -> Permissions {
         match self {
+            Self::Alerts(cmd) => cmd.guild_permissions(),
+            Self::AutoRole(cmd) => cmd.guild_permissions(),
+            Self::Emoji(cmd) => cmd.guild_permissions(),
+            Self::Events(cmd) => cmd.guild_permissions(),
+            Self::FatherBelt(cmd) => cmd.guild_permissions(),
+            Self::Introductions(cmd) => cmd.guild_permissions(),
+            Self::Logs(cmd) => cmd.guild_permissions(),
             Self::Payer(cmd) => cmd.guild_permissions(),
+            Self::QuietHours(cmd) => cmd.guild_permissions(),
+            Self::Raid(cmd) => cmd.guild_permissions(),
+            Self::Translation(cmd) => cmd.guild_permissions(),
             Self::User(cmd) => cmd.guild_permissions(),
+            Self::Verification(cmd) => cmd.guild_permissions(),
+            Self::WordFilter(cmd) => cmd.guild_permissions(),
         }
     }
 
     fn user_permissions(&self) -> Permissions {
         match self {
+            Self::Alerts(cmd) => cmd.user_permissions(),
+            Self::AutoRole(cmd) => cmd.user_permissions(),
+            Self::Emoji(cmd) => cmd.user_permissions(),
+            Self::Events(cmd) => cmd.user_permissions(),
+            Self::FatherBelt(cmd) => cmd.user_permissions(),
+            Self::Introductions(cmd) => cmd.user_permissions(),
+            Self::Logs(cmd) => cmd.user_permissions(),
             Self::Payer(cmd) => cmd.user_permissions(),
+            Self::QuietHours(cmd) => cmd.user_permissions(),
+            Self::Raid(cmd) => cmd.user_permissions(),
+            Self::Translation(cmd) => cmd.user_permissions(),
             Self::User(cmd) => cmd.user_permissions(),
+            Self::Verification(cmd) => cmd.user_permissions(),
+            Self::WordFilter(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        match self {
+            Self::Alerts(cmd) => cmd.validate(validator),
+            Self::AutoRole(cmd) => cmd.validate(validator),
+            Self::Emoji(cmd) => cmd.validate(validator),
+            Self::Events(cmd) => cmd.validate(validator),
+            Self::FatherBelt(cmd) => cmd.validate(validator),
+            Self::Introductions(cmd) => cmd.validate(validator),
+            Self::Logs(cmd) => cmd.validate(validator),
+            Self::Payer(cmd) => cmd.validate(validator),
+            Self::QuietHours(cmd) => cmd.validate(validator),
+            Self::Raid(cmd) => cmd.validate(validator),
+            Self::Translation(cmd) => cmd.validate(validator),
+            Self::User(cmd) => cmd.validate(validator),
+            Self::Verification(cmd) => cmd.validate(validator),
+            Self::WordFilter(cmd) => cmd.validate(validator),
         }
     }
+
+    fn is_ephemeral(&self) -> bool {
+        match self {
+            Self::Alerts(cmd) => cmd.is_ephemeral(),
+            Self::AutoRole(cmd) => cmd.is_ephemeral(),
+            Self::Emoji(cmd) => cmd.is_ephemeral(),
+            Self::Events(cmd) => cmd.is_ephemeral(),
+            Self::FatherBelt(cmd) => cmd.is_ephemeral(),
+            Self::Introductions(cmd) => cmd.is_ephemeral(),
+            Self::Logs(cmd) => cmd.is_ephemeral(),
+            Self::Payer(cmd) => cmd.is_ephemeral(),
+            Self::QuietHours(cmd) => cmd.is_ephemeral(),
+            Self::Raid(cmd) => cmd.is_ephemeral(),
+            Self::Translation(cmd) => cmd.is_ephemeral(),
+            Self::User(cmd) => cmd.is_ephemeral(),
+            Self::Verification(cmd) => cmd.is_ephemeral(),
+            Self::WordFilter(cmd) => cmd.is_ephemeral(),
+        }
+    }
+}
+
+/// Saves the modified local guild settings and remembers the previous
+/// settings so the change can be reverted with `/admin undo`.
+///
+/// A [snapshot](guild_snapshot) is taken before the settings are changed.
+pub async fn save_settings<T>(
+    ctx: &LocalGuildContext<'_, T>,
+    name: &str,
+    form: &GuildSettings,
+) -> Result<()> {
+    let mut conn = ctx.bot.db_write().await?;
+
+    // this locks the row until the transaction ends
+    let before = GuildSettings::upsert(&mut conn, ctx.guild_id).await?.data;
+    if before != *form {
+        let reason = format!("Before changing \"{name}\"");
+        guild_snapshot::take(&mut conn, ctx.guild_id, ctx.author.id, &reason).await?;
+    }
+    GuildSettings::update(&mut conn, ctx.guild_id, form).await?;
+
+    if before != *form {
+        let action = UndoAction::GuildSettings {
+            before: Box::new(before),
+            after: Box::new(form.clone()),
+        };
+        let description = format!("Changed \"{name}\"");
+        undo::record(
+            &mut conn,
+            ctx.guild_id,
+            ctx.author.id,
+            &description,
+            &action,
+        )
+        .await?;
+    }
+
+    conn.commit()
+        .await
+        .into_eden_error()
+        .attach_printable("could not commit transaction")?;
+
+    ctx.bot.invalidate_guild_settings(ctx.guild_id);
+    Ok(())
 }
 
 pub async fn reply_with_changed_value(
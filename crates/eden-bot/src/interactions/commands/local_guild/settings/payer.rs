@@ -1,30 +1,41 @@
 use eden_discord_types::commands::local_guild::{
-    PayerSettingsAllowSelfRegistration, PayerSettingsCommand,
+    PayerSettingsAllowSelfRegistration, PayerSettingsCommand, PayerSettingsRole,
 };
-use eden_schema::types::GuildSettings;
-use eden_utils::{error::exts::*, Result};
-use tracing::trace;
+use eden_schema::types::PayerStatusChange;
+use eden_tasks::Scheduled;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use tracing::{debug, trace};
+use twilight_mention::Mention;
+use twilight_model::channel::message::AllowedMentions;
 use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
 
 use super::{CommandContext, RunCommand};
+use crate::features::auto_role::{self, UnassignableRole};
+use crate::features::bulk_role::{BulkRoleAction, BulkRoleFilter, BulkRoleOperation};
 use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+use crate::tasks;
 
 impl RunCommand for PayerSettingsCommand {
     async fn run(&self, ctx: &CommandContext) -> Result<()> {
         match self {
             Self::AllowSelfRegistration(cmd) => cmd.run(ctx).await,
+            Self::Role(cmd) => cmd.run(ctx).await,
         }
     }
 
     fn user_permissions(&self) -> Permissions {
         match self {
             Self::AllowSelfRegistration(cmd) => cmd.user_permissions(),
+            Self::Role(cmd) => cmd.user_permissions(),
         }
     }
 
     fn guild_permissions(&self) -> Permissions {
         match self {
             Self::AllowSelfRegistration(cmd) => cmd.guild_permissions(),
+            Self::Role(cmd) => cmd.guild_permissions(),
         }
     }
 }
@@ -38,15 +49,10 @@ impl RunCommand for PayerSettingsAllowSelfRegistration {
         if let Some(overwrite) = self.set {
             trace!("overriding `allow_self_registration` to {overwrite}");
 
-            let mut conn = ctx.bot.db_write().await?;
             let mut form = ctx.settings.data.clone();
             form.payers.allow_self_register = overwrite;
 
-            GuildSettings::update(&mut conn, ctx.guild_id, &form).await?;
-            conn.commit()
-                .await
-                .into_eden_error()
-                .attach_printable("could not commit transaction")?;
+            super::save_settings(&ctx, "Allow self registration", &form).await?;
 
             super::reply_with_changed_value(&ctx, "Allow self registration", overwrite).await
         } else {
@@ -64,3 +70,81 @@ impl RunCommand for PayerSettingsAllowSelfRegistration {
         Permissions::ADMINISTRATOR
     }
 }
+
+impl RunCommand for PayerSettingsRole {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Payer role";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(role_id) = self.set else {
+            trace!("getting {NAME:?} value");
+            return super::reply_with_output(ctx.inner, NAME, ctx.settings.payers.role_id).await;
+        };
+
+        let unassignable = auto_role::find_unassignable(&ctx.bot, ctx.guild_id, role_id).await?;
+        if let Some(reason) = unassignable {
+            let hint = match reason {
+                UnassignableRole::AboveBot => {
+                    "\n\nPlease move my highest role above that role and try again."
+                }
+                _ => "",
+            };
+            let data = InteractionResponseDataBuilder::new()
+                .content(format!(
+                    "**I cannot give {} to payers** because {reason}.{hint}",
+                    role_id.mention()
+                ))
+                .allowed_mentions(AllowedMentions::default())
+                .build();
+
+            return ctx.respond(data).await;
+        }
+
+        trace!("overriding {NAME:?} to {role_id:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.payers.role_id = Some(role_id);
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        // existing payers have to be given the new role as well
+        let mut conn = ctx.bot.db_write().await?;
+        let changes = PayerStatusChange::resync_all(&mut conn).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        debug!("applying payer role to {changes} payer(s)");
+
+        // payers must not keep the previous payer role
+        if let Some(previous) = ctx.settings.payers.role_id.filter(|v| *v != role_id) {
+            let operation = BulkRoleOperation {
+                guild_id: ctx.guild_id,
+                channel_id: ctx.channel_id,
+                requested_by: ctx.author.id,
+                role_id: previous,
+                action: BulkRoleAction::Remove,
+                filter: BulkRoleFilter::default(),
+                members: None,
+                progress: None,
+            };
+
+            debug!("removing previous payer role {previous}");
+            let task = tasks::PerformBulkRoleOperation { operation };
+            ctx.bot.queue.schedule(task, Scheduled::now()).await?;
+        }
+
+        super::reply_with_changed_value(&ctx, NAME, role_id).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+}
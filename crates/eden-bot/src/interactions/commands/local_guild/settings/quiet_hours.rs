@@ -0,0 +1,263 @@
+use chrono::{NaiveTime, Utc};
+use eden_discord_types::commands::local_guild::{
+    QuietHoursSettingsCommand, QuietHoursSettingsEnabled, QuietHoursSettingsReadOnly,
+    QuietHoursSettingsSchedule,
+};
+use eden_schema::types::QuietHoursGuildSettings;
+use eden_utils::Result;
+use tracing::trace;
+use twilight_mention::Mention;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::features::{preferences, quiet_hours};
+use crate::interactions::validation::Validator;
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+const TIME_FORMAT: &str = "`22:00` or `06:30`";
+const TIMEZONE_FORMAT: &str = "`+08:00` or `-05:00`";
+
+impl RunCommand for QuietHoursSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Enabled(cmd) => cmd.run(ctx).await,
+            Self::ReadOnly(cmd) => cmd.run(ctx).await,
+            Self::Schedule(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Enabled(cmd) => cmd.user_permissions(),
+            Self::ReadOnly(cmd) => cmd.user_permissions(),
+            Self::Schedule(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Enabled(cmd) => cmd.guild_permissions(),
+            Self::ReadOnly(cmd) => cmd.guild_permissions(),
+            Self::Schedule(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        match self {
+            Self::Enabled(cmd) => cmd.validate(validator),
+            Self::ReadOnly(cmd) => cmd.validate(validator),
+            Self::Schedule(cmd) => cmd.validate(validator),
+        }
+    }
+}
+
+async fn reply(ctx: &CommandContext, content: String) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .build();
+
+    ctx.respond(data).await
+}
+
+/// Renders the quiet hours schedule (like `22:00 - 06:00 (+08:00)`).
+fn format_schedule(settings: &QuietHoursGuildSettings) -> String {
+    let format = |time: Option<NaiveTime>| match time {
+        Some(time) => time.format("%H:%M").to_string(),
+        None => "--:--".into(),
+    };
+    format!(
+        "{} - {} ({})",
+        format(settings.start),
+        format(settings.end),
+        settings.utc_offset()
+    )
+}
+
+fn parse_time(input: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(input.trim(), "%H:%M").ok()
+}
+
+impl RunCommand for QuietHoursSettingsEnabled {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Quiet hours enabled";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(enabled) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.quiet_hours.enabled;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {enabled:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.quiet_hours.enabled = enabled;
+        super::save_settings(&ctx, NAME, &form).await?;
+        ctx.bot.quiet_hours.update(&form.quiet_hours, Utc::now());
+
+        super::reply_with_changed_value(&ctx, NAME, enabled).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for QuietHoursSettingsReadOnly {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let channel_ids = &ctx.settings.quiet_hours.read_only_channel_ids;
+        let is_read_only = channel_ids.contains(&self.channel);
+        let mention = self.channel.mention();
+
+        let Some(read_only) = self.set else {
+            let content = if is_read_only {
+                format!("**{mention} becomes read-only during quiet hours.**")
+            } else {
+                format!("**{mention} does not become read-only during quiet hours.**")
+            };
+            return reply(ctx.inner, content).await;
+        };
+
+        if read_only == is_read_only {
+            let content = format!("**Nothing has changed for {mention}.**");
+            return reply(ctx.inner, content).await;
+        }
+
+        trace!(
+            "setting read-only for channel {} to {read_only}",
+            self.channel
+        );
+
+        let mut form = ctx.settings.data.clone();
+        if read_only {
+            form.quiet_hours.read_only_channel_ids.push(self.channel);
+        } else {
+            form.quiet_hours
+                .read_only_channel_ids
+                .retain(|v| *v != self.channel);
+        }
+        super::save_settings(&ctx, "Read-only channels during quiet hours", &form).await?;
+
+        // the recurring task won't touch this channel anymore, so
+        // we need to lift the restriction by ourselves.
+        if !read_only {
+            quiet_hours::set_read_only(&ctx.bot, ctx.guild_id, self.channel, false).await?;
+        }
+
+        let content = if read_only {
+            format!("**{mention} will become read-only during quiet hours.**")
+        } else {
+            format!("**{mention} will no longer become read-only during quiet hours.**")
+        };
+        reply(ctx.inner, content).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_CHANNELS | Permissions::MANAGE_ROLES
+    }
+}
+
+impl RunCommand for QuietHoursSettingsSchedule {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Quiet hours schedule";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        if self.start.is_none() && self.end.is_none() && self.timezone.is_none() {
+            trace!("getting {NAME:?} value");
+            let value = format_schedule(&ctx.settings.quiet_hours);
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        }
+
+        // arguments are already checked in `validate`
+        let mut form = ctx.settings.data.clone();
+        for (input, time) in [
+            (self.start.as_deref(), &mut form.quiet_hours.start),
+            (self.end.as_deref(), &mut form.quiet_hours.end),
+        ] {
+            if let Some(parsed) = input.and_then(parse_time) {
+                *time = Some(parsed);
+            }
+        }
+
+        // the invoker's timezone is used if the schedule is set for
+        // the first time without specifying the timezone.
+        let is_new =
+            ctx.settings.quiet_hours.start.is_none() && ctx.settings.quiet_hours.end.is_none();
+        let offset = match self.timezone.as_deref() {
+            Some(input) => preferences::parse_timezone(input),
+            None if is_new => Some(preferences::timezone(&ctx.bot, ctx.author.id).await?),
+            None => None,
+        };
+
+        if let Some(offset) = offset {
+            form.quiet_hours.utc_offset_minutes = offset.local_minus_utc() / 60;
+        }
+
+        let value = format_schedule(&form.quiet_hours);
+        trace!("overriding {NAME:?} to {value:?}");
+
+        super::save_settings(&ctx, NAME, &form).await?;
+        ctx.bot.quiet_hours.update(&form.quiet_hours, Utc::now());
+
+        super::reply_with_changed_value(&ctx, NAME, value).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        for (argument, input) in [("start", &self.start), ("end", &self.end)] {
+            if let Some(input) = input {
+                validator.parse(argument, input, parse_time, TIME_FORMAT);
+            }
+        }
+
+        if let Some(input) = &self.timezone {
+            validator.parse(
+                "timezone",
+                input,
+                preferences::parse_timezone,
+                TIMEZONE_FORMAT,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_times() {
+        assert_eq!(parse_time("22:00"), NaiveTime::from_hms_opt(22, 0, 0));
+        assert_eq!(parse_time(" 6:30"), NaiveTime::from_hms_opt(6, 30, 0));
+        assert_eq!(parse_time("24:00"), None);
+        assert_eq!(parse_time("10pm"), None);
+    }
+
+    #[test]
+    fn should_format_schedule() {
+        let settings = QuietHoursGuildSettings::builder()
+            .start(NaiveTime::from_hms_opt(22, 0, 0))
+            .utc_offset_minutes(8 * 60)
+            .build();
+
+        assert_eq!(format_schedule(&settings), "22:00 - --:-- (+08:00)");
+    }
+}
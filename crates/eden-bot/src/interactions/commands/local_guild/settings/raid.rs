@@ -0,0 +1,132 @@
+use eden_discord_types::choices::LockdownModeOption;
+use eden_discord_types::commands::local_guild::{
+    RaidSettingsCommand, RaidSettingsEnabled, RaidSettingsMode, RaidSettingsThreshold,
+};
+use eden_schema::types::{LockdownMode, RaidGuildSettings};
+use eden_utils::Result;
+use std::fmt::Debug;
+use tracing::trace;
+use twilight_model::guild::Permissions;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for RaidSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Enabled(cmd) => cmd.run(ctx).await,
+            Self::Mode(cmd) => cmd.run(ctx).await,
+            Self::Threshold(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Enabled(cmd) => cmd.user_permissions(),
+            Self::Mode(cmd) => cmd.user_permissions(),
+            Self::Threshold(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Enabled(cmd) => cmd.guild_permissions(),
+            Self::Mode(cmd) => cmd.guild_permissions(),
+            Self::Threshold(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+/// Modifies the local guild's raid settings if `overwrite`
+/// is specified, otherwise it replies with the current value.
+async fn modify_or_get<V: Debug>(
+    ctx: &CommandContext,
+    name: &str,
+    overwrite: Option<V>,
+    get: impl FnOnce(&RaidGuildSettings) -> V,
+    set: impl FnOnce(&mut RaidGuildSettings, V),
+) -> Result<()> {
+    let ctx = LocalGuildContext::from_ctx(ctx).await?;
+    record_local_guild_ctx!(ctx);
+
+    let Some(overwrite) = overwrite else {
+        trace!("getting {name:?} value");
+        let value = get(&ctx.settings.raid);
+        return super::reply_with_output(ctx.inner, name, value).await;
+    };
+
+    trace!("overriding {name:?} to {overwrite:?}");
+
+    let mut form = ctx.settings.data.clone();
+    set(&mut form.raid, overwrite);
+
+    let value = get(&form.raid);
+    super::save_settings(&ctx, name, &form).await?;
+
+    super::reply_with_changed_value(&ctx, name, value).await
+}
+
+impl RunCommand for RaidSettingsEnabled {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        modify_or_get(
+            ctx,
+            "Raid detection enabled",
+            self.set,
+            |v| v.enabled,
+            |v, value| v.enabled = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_GUILD | Permissions::MANAGE_ROLES
+    }
+}
+
+impl RunCommand for RaidSettingsMode {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let overwrite = self.set.map(|v| match v {
+            LockdownModeOption::RestrictMessages => LockdownMode::RestrictMessages,
+            LockdownModeOption::RaiseVerification => LockdownMode::RaiseVerification,
+            LockdownModeOption::PauseInvites => LockdownMode::PauseInvites,
+        });
+
+        modify_or_get(
+            ctx,
+            "Lockdown mode",
+            overwrite,
+            |v| v.lockdown_mode,
+            |v, value| v.lockdown_mode = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for RaidSettingsThreshold {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let overwrite = self.joins_per_minute.and_then(|v| u32::try_from(v).ok());
+        modify_or_get(
+            ctx,
+            "Raid threshold (joins per minute)",
+            overwrite,
+            |v| v.joins_per_minute,
+            |v, value| v.joins_per_minute = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
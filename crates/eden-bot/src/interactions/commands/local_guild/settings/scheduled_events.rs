@@ -0,0 +1,114 @@
+use eden_discord_types::commands::local_guild::{
+    ScheduledEventsSettingsChannel, ScheduledEventsSettingsCommand, ScheduledEventsSettingsPing,
+};
+use eden_utils::Result;
+use tracing::trace;
+use twilight_model::channel::ChannelType;
+use twilight_model::guild::Permissions;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::validation::Validator;
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+/// Channels where Eden can post scheduled event reminders to.
+const REMINDER_CHANNEL_TYPES: &[ChannelType] =
+    &[ChannelType::GuildText, ChannelType::GuildAnnouncement];
+
+impl RunCommand for ScheduledEventsSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Channel(cmd) => cmd.run(ctx).await,
+            Self::Ping(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.user_permissions(),
+            Self::Ping(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.guild_permissions(),
+            Self::Ping(cmd) => cmd.guild_permissions(),
+        }
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        match self {
+            Self::Channel(cmd) => cmd.validate(validator),
+            Self::Ping(cmd) => cmd.validate(validator),
+        }
+    }
+}
+
+impl RunCommand for ScheduledEventsSettingsChannel {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Scheduled event reminder channel";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        // `reset` takes priority over `set` so reminders can be turned off
+        let channel_id = if self.reset == Some(true) {
+            None
+        } else if let Some(channel_id) = self.set {
+            Some(channel_id)
+        } else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.scheduled_events.channel_id;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {channel_id:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.scheduled_events.channel_id = channel_id;
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, channel_id).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn validate(&self, validator: &mut Validator<'_>) {
+        if let Some(channel_id) = self.set {
+            validator.channel_type("set", channel_id, REMINDER_CHANNEL_TYPES);
+        }
+    }
+}
+
+impl RunCommand for ScheduledEventsSettingsPing {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Mention interested members in reminders";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(ping_interested) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.scheduled_events.ping_interested;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        trace!("overriding {NAME:?} to {ping_interested:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.scheduled_events.ping_interested = ping_interested;
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, ping_interested).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
@@ -0,0 +1,215 @@
+use eden_discord_types::choices::VerificationModeOption;
+use eden_discord_types::commands::local_guild::{
+    VerificationSettingsChannel, VerificationSettingsCommand, VerificationSettingsEnabled,
+    VerificationSettingsKickAfter, VerificationSettingsMode, VerificationSettingsReset,
+    VerificationSettingsRole,
+};
+use eden_schema::types::{VerificationGuildSettings, VerificationMode};
+use eden_utils::Result;
+use std::fmt::Debug;
+use tracing::trace;
+use twilight_model::guild::Permissions;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for VerificationSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Channel(cmd) => cmd.run(ctx).await,
+            Self::Enabled(cmd) => cmd.run(ctx).await,
+            Self::KickAfter(cmd) => cmd.run(ctx).await,
+            Self::Mode(cmd) => cmd.run(ctx).await,
+            Self::Reset(cmd) => cmd.run(ctx).await,
+            Self::Role(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.user_permissions(),
+            Self::Enabled(cmd) => cmd.user_permissions(),
+            Self::KickAfter(cmd) => cmd.user_permissions(),
+            Self::Mode(cmd) => cmd.user_permissions(),
+            Self::Reset(cmd) => cmd.user_permissions(),
+            Self::Role(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Channel(cmd) => cmd.guild_permissions(),
+            Self::Enabled(cmd) => cmd.guild_permissions(),
+            Self::KickAfter(cmd) => cmd.guild_permissions(),
+            Self::Mode(cmd) => cmd.guild_permissions(),
+            Self::Reset(cmd) => cmd.guild_permissions(),
+            Self::Role(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+/// Modifies the local guild's verification settings if `overwrite`
+/// is specified, otherwise it replies with the current value.
+async fn modify_or_get<V: Debug>(
+    ctx: &CommandContext,
+    name: &str,
+    overwrite: Option<V>,
+    get: impl FnOnce(&VerificationGuildSettings) -> V,
+    set: impl FnOnce(&mut VerificationGuildSettings, V),
+) -> Result<()> {
+    let ctx = LocalGuildContext::from_ctx(ctx).await?;
+    record_local_guild_ctx!(ctx);
+
+    let Some(overwrite) = overwrite else {
+        trace!("getting {name:?} value");
+        let value = get(&ctx.settings.verification);
+        return super::reply_with_output(ctx.inner, name, value).await;
+    };
+
+    trace!("overriding {name:?} to {overwrite:?}");
+
+    let mut form = ctx.settings.data.clone();
+    set(&mut form.verification, overwrite);
+
+    let value = get(&form.verification);
+    super::save_settings(&ctx, name, &form).await?;
+
+    super::reply_with_changed_value(&ctx, name, value).await
+}
+
+impl RunCommand for VerificationSettingsChannel {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        modify_or_get(
+            ctx,
+            "Verification channel",
+            self.set.map(Some),
+            |v| v.channel_id,
+            |v, value| v.channel_id = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for VerificationSettingsEnabled {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        modify_or_get(
+            ctx,
+            "Verification enabled",
+            self.set,
+            |v| v.enabled,
+            |v, value| v.enabled = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+}
+
+impl RunCommand for VerificationSettingsKickAfter {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        // zero minutes means the member will never be kicked
+        let overwrite = self
+            .minutes
+            .map(|v| u32::try_from(v).ok().filter(|v| *v > 0));
+
+        modify_or_get(
+            ctx,
+            "Kick unverified members after (minutes)",
+            overwrite,
+            |v| v.kick_after_minutes,
+            |v, value| v.kick_after_minutes = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::KICK_MEMBERS
+    }
+}
+
+impl RunCommand for VerificationSettingsMode {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let overwrite = self.set.map(|v| match v {
+            VerificationModeOption::Button => VerificationMode::Button,
+            VerificationModeOption::Captcha => VerificationMode::Captcha,
+        });
+
+        modify_or_get(
+            ctx,
+            "Verification mode",
+            overwrite,
+            |v| v.mode,
+            |v, value| v.mode = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for VerificationSettingsReset {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        trace!("resetting verification settings");
+
+        let mut form = ctx.settings.data.clone();
+        form.verification = VerificationGuildSettings::default();
+        super::save_settings(&ctx, "Verification settings", &form).await?;
+
+        let data = InteractionResponseDataBuilder::new()
+            .content("**Verification is turned off and all of its settings are unset.**")
+            .build();
+
+        ctx.respond(data).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
+
+impl RunCommand for VerificationSettingsRole {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        modify_or_get(
+            ctx,
+            "Verified role",
+            self.set.map(Some),
+            |v| v.role_id,
+            |v, value| v.role_id = value,
+        )
+        .await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        Permissions::MANAGE_ROLES
+    }
+}
@@ -0,0 +1,67 @@
+use eden_discord_types::choices::WordFilterActionOption;
+use eden_discord_types::commands::local_guild::{
+    WordFilterSettingsAction, WordFilterSettingsCommand,
+};
+use eden_schema::types::WordFilterAction;
+use eden_utils::Result;
+use tracing::trace;
+use twilight_model::guild::Permissions;
+
+use super::{CommandContext, RunCommand};
+use crate::interactions::{record_local_guild_ctx, LocalGuildContext};
+
+impl RunCommand for WordFilterSettingsCommand {
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Action(cmd) => cmd.run(ctx).await,
+        }
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        match self {
+            Self::Action(cmd) => cmd.user_permissions(),
+        }
+    }
+
+    fn guild_permissions(&self) -> Permissions {
+        match self {
+            Self::Action(cmd) => cmd.guild_permissions(),
+        }
+    }
+}
+
+impl RunCommand for WordFilterSettingsAction {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        const NAME: &str = "Word filter action";
+
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let Some(action) = self.set else {
+            trace!("getting {NAME:?} value");
+            let value = ctx.settings.word_filter.action;
+            return super::reply_with_output(ctx.inner, NAME, value).await;
+        };
+
+        let action = match action {
+            WordFilterActionOption::Reply => WordFilterAction::Reply,
+            WordFilterActionOption::Delete => WordFilterAction::Delete,
+            WordFilterActionOption::Repost => WordFilterAction::Repost,
+            WordFilterActionOption::DirectMessage => WordFilterAction::DirectMessage,
+            WordFilterActionOption::Log => WordFilterAction::Log,
+        };
+        trace!("overriding {NAME:?} to {action:?}");
+
+        let mut form = ctx.settings.data.clone();
+        form.word_filter.action = action;
+
+        super::save_settings(&ctx, NAME, &form).await?;
+
+        super::reply_with_changed_value(&ctx, NAME, action).await
+    }
+
+    fn user_permissions(&self) -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::{TimeDelta, Utc};
+use eden_discord_types::choices::GrowthRangeOption;
+use eden_discord_types::commands::local_guild::{StatsCommand, StatsGrowth, StatsVoice};
+use eden_schema::types::{GuildMemberCount, VoiceStat};
+use eden_utils::error::UserErrorCategory;
+use eden_utils::{error::exts::*, Error, ErrorCategory, Result};
+use std::fmt::Write as _;
+use thiserror::Error;
+use twilight_mention::Mention;
+use twilight_model::guild::Permissions;
+use twilight_model::http::attachment::Attachment;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::features::{member_growth, voice_stats};
+use crate::interactions::commands::{CommandContext, CommandHelp, HelpCategory, RunCommand};
+use crate::interactions::response_cache::{CacheKey, CachedCommand};
+use crate::interactions::tags::LackingPermissionsTag;
+use crate::interactions::{embeds, record_local_guild_ctx, LocalGuildContext};
+
+const DEFAULT_DAYS: i64 = 7;
+const LEADERBOARD_LIMIT: i64 = 10;
+const DEFAULT_GROWTH_RANGE: GrowthRangeOption = GrowthRangeOption::Month;
+const GROWTH_TABLE_ROWS: usize = 10;
+const EXPORT_REQUIRED: Permissions = Permissions::ADMINISTRATOR;
+
+#[derive(Debug, Error)]
+#[error("user lacked permissions to export member counts")]
+struct LackingExportPermissions;
+
+impl RunCommand for StatsCommand {
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::General).examples(&[
+        "/stats growth range:Last 90 days",
+        "/stats voice",
+        "/stats voice days:30",
+    ]);
+
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        match self {
+            Self::Growth(cmd) => cmd.run(ctx).await,
+            Self::Voice(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+impl RunCommand for StatsGrowth {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let export = self.export.unwrap_or_default();
+        if export {
+            let permissions = ctx.member.permissions.unwrap_or_else(Permissions::empty);
+            if !permissions.contains(EXPORT_REQUIRED) {
+                return Err(Error::context_anonymize(
+                    ErrorCategory::User(UserErrorCategory::MissingPermissions),
+                    LackingExportPermissions,
+                ))
+                .attach(LackingPermissionsTag::new(permissions, EXPORT_REQUIRED));
+            }
+        }
+
+        let days = self.range.unwrap_or(DEFAULT_GROWTH_RANGE).days();
+        let key = CacheKey::new(CachedCommand::MemberGrowth, ctx.guild_id, days.to_string());
+
+        let cache = &ctx.bot.response_cache;
+        if !export {
+            if let Some(data) = cache.get(&key, Utc::now()) {
+                return ctx.respond(data).await;
+            }
+        }
+
+        let since = (Utc::now() - TimeDelta::days(days - 1)).date_naive();
+
+        let mut conn = ctx.bot.db_read().await?;
+        let counts = GuildMemberCount::since(&mut conn, ctx.guild_id, since).await?;
+
+        let description = match (counts.first(), counts.last()) {
+            (Some(first), Some(last)) => {
+                let members = counts.iter().map(|v| v.members).collect::<Vec<_>>();
+                let mut description = format!(
+                    "**{}** → **{}** member(s) ({:+})\n`{}`\n```\n",
+                    first.members,
+                    last.members,
+                    last.members - first.members,
+                    member_growth::sparkline(&members)
+                );
+                for count in member_growth::sample(&counts, GROWTH_TABLE_ROWS) {
+                    let _ = writeln!(description, "{}  {:>8}", count.day, count.members);
+                }
+                description.push_str("```");
+                description
+            }
+            _ => String::from("*No member counts recorded yet.*"),
+        };
+
+        let embed =
+            embeds::builders::with_emoji('📈', format!("Member growth in the last {days} day(s)"))
+                .description(description)
+                .build();
+
+        let mut data = InteractionResponseDataBuilder::new().embeds(vec![embed]);
+        if export {
+            let filename = format!("member-growth-{}.csv", Utc::now().date_naive());
+            let csv = member_growth::to_csv(&counts);
+            data = data.attachments(vec![Attachment::from_bytes(filename, csv.into_bytes(), 1)]);
+        }
+
+        let data = data.build();
+        if !export {
+            cache.insert(key, &data, Utc::now());
+        }
+        ctx.respond(data).await
+    }
+}
+
+impl RunCommand for StatsVoice {
+    #[tracing::instrument(skip(ctx), fields(ctx = tracing::field::Empty))]
+    async fn run(&self, ctx: &CommandContext) -> Result<()> {
+        let ctx = LocalGuildContext::from_ctx(ctx).await?;
+        record_local_guild_ctx!(ctx);
+
+        let days = self.days.unwrap_or(DEFAULT_DAYS);
+        let key = CacheKey::new(CachedCommand::VoiceStats, ctx.guild_id, days.to_string());
+
+        let cache = &ctx.bot.response_cache;
+        if let Some(data) = cache.get(&key, Utc::now()) {
+            return ctx.respond(data).await;
+        }
+
+        // include recent voice activities as well
+        voice_stats::flush(&ctx.bot).await?;
+
+        let since = (Utc::now() - TimeDelta::days(days - 1)).date_naive();
+
+        let mut conn = ctx.bot.db_read().await?;
+        let entries = VoiceStat::top(&mut conn, ctx.guild_id, since, LEADERBOARD_LIMIT).await?;
+
+        let mut description = String::new();
+        for (rank, entry) in entries.iter().enumerate() {
+            let _ = writeln!(
+                description,
+                "**{}.** {} - {}",
+                rank + 1,
+                entry.user_id.mention(),
+                voice_stats::format_duration(entry.seconds)
+            );
+        }
+
+        if description.is_empty() {
+            description.push_str("*No voice activity yet.*");
+        }
+
+        let embed = embeds::builders::with_emoji(
+            '🎙',
+            format!("Most voice active members in the last {days} day(s)"),
+        )
+        .description(description)
+        .build();
+
+        let data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .build();
+
+        cache.insert(key, &data, Utc::now());
+        ctx.respond(data).await
+    }
+}
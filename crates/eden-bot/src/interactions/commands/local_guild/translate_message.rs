@@ -0,0 +1,143 @@
+use chrono::TimeDelta;
+use eden_utils::error::UserErrorCategory;
+use eden_utils::{error::exts::*, Error, ErrorCategory, Result};
+use thiserror::Error;
+use tracing::{trace, warn};
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::id::marker::MessageMarker;
+use twilight_util::builder::embed::EmbedFooterBuilder;
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::features::preferences;
+use crate::interactions::commands::CommandContext;
+use crate::interactions::cooldowns::{self, CooldownStatus};
+use crate::interactions::{embeds, LocalGuildContext};
+
+/// Maximum length of the description of an embed.
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// How long users have to wait before translating another message,
+/// since translation services are rate limited and may charge per
+/// translated character.
+const TRANSLATION_COOLDOWN: TimeDelta = TimeDelta::seconds(10);
+const COOLDOWN_BUCKET: &str = "translate message";
+
+const NOT_ENABLED_MSG: &str = "**Message translation is not enabled in this server.**";
+const NO_CONTENT_MSG: &str = "**This message has no text to translate.**";
+
+#[derive(Debug, Error)]
+#[error("could not find the message to translate")]
+struct MissingTargetMessage;
+
+#[derive(Debug, Error)]
+#[error("user is on cooldown to translate messages")]
+struct TranslationOnCooldown;
+
+/// Runs the "Translate message" message command by translating the
+/// message into the invoker's language. Only the invoker can see
+/// the translation.
+#[tracing::instrument(skip_all)]
+pub async fn run(ctx: &CommandContext) -> Result<()> {
+    let ctx = LocalGuildContext::from_ctx(ctx).await?;
+
+    let settings = ctx.bot.guild_settings(ctx.guild_id).await?;
+    let translator = ctx.bot.translator.as_ref();
+    let Some(translator) = translator.filter(|_| settings.translation.enabled) else {
+        return respond_ephemeral(&ctx, NOT_ENABLED_MSG).await;
+    };
+
+    let message = ctx
+        .data
+        .target_id
+        .map(|v| v.cast::<MessageMarker>())
+        .and_then(|id| ctx.data.resolved.as_ref()?.messages.get(&id));
+
+    let Some(message) = message else {
+        return Err(Error::context_anonymize(
+            ErrorCategory::Unknown,
+            MissingTargetMessage,
+        ));
+    };
+
+    if message.content.trim().is_empty() {
+        return respond_ephemeral(&ctx, NO_CONTENT_MSG).await;
+    }
+
+    let client_locale = ctx.interaction.locale.as_deref();
+    let locale = preferences::locale(&ctx.bot, ctx.invoker_id(), client_locale).await?;
+    trace!("translating message {} into {locale}", message.id);
+
+    let invoker_id = ctx.invoker_id();
+    let cooldown = TRANSLATION_COOLDOWN;
+    let expires_at =
+        match cooldowns::try_start(&ctx.bot, invoker_id, COOLDOWN_BUCKET, cooldown).await? {
+            CooldownStatus::Started { expires_at } => expires_at,
+            CooldownStatus::Active { expires_at } => {
+                trace!("invoker is still on translation cooldown until {expires_at}");
+                return Err(Error::context_anonymize(
+                    ErrorCategory::User(UserErrorCategory::OnCooldown(expires_at)),
+                    TranslationOnCooldown,
+                ));
+            }
+        };
+
+    let result = translator
+        .translate(&message.content, &locale)
+        .await
+        .anonymize_error();
+
+    // the user should not wait if the translation service failed
+    if result.is_err() {
+        let refunded =
+            cooldowns::refund(&ctx.bot, invoker_id, COOLDOWN_BUCKET, cooldown, expires_at).await;
+        if let Err(error) = refunded {
+            warn!(%error, "could not refund translation cooldown");
+        }
+    }
+    let translated = result?;
+
+    let embed = embeds::builders::with_emoji('🌐', "Translation")
+        .description(truncate(translated.text))
+        .footer(EmbedFooterBuilder::new(format!(
+            "Translated from {}",
+            translated.source_language
+        )))
+        .build();
+
+    let data = InteractionResponseDataBuilder::new()
+        .embeds([embed])
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+fn truncate(mut text: String) -> String {
+    if let Some((index, _)) = text.char_indices().nth(MAX_DESCRIPTION_LENGTH - 1) {
+        text.truncate(index);
+        text.push('…');
+    }
+    text
+}
+
+async fn respond_ephemeral<T>(ctx: &LocalGuildContext<'_, T>, content: &str) -> Result<()> {
+    let data = InteractionResponseDataBuilder::new()
+        .content(content)
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    ctx.respond(data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_fit_translations_in_embeds() {
+        assert_eq!(truncate("hola".into()), "hola");
+
+        let long = "a".repeat(MAX_DESCRIPTION_LENGTH + 10);
+        assert_eq!(truncate(long).chars().count(), MAX_DESCRIPTION_LENGTH);
+    }
+}
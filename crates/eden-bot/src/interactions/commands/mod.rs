@@ -1,28 +1,47 @@
 use eden_discord_types::commands;
 use eden_schema::types::{Admin, User};
+use eden_utils::error::tags::TelemetryTags;
 use eden_utils::error::{GuildErrorCategory, UserErrorCategory};
 use eden_utils::twilight::error::TwilightHttpErrorExt;
 use eden_utils::{error::exts::*, Error, ErrorCategory, Result};
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, info, trace, warn};
 use twilight_interactions::command::{CommandInputData, CommandModel, CreateCommand};
+use twilight_model::application::command::{Command, CommandType};
 use twilight_model::application::interaction::application_command::CommandData;
 use twilight_model::guild::Permissions;
-use twilight_model::id::marker::UserMarker;
-use twilight_util::permission_calculator::PermissionCalculator;
+use twilight_util::builder::InteractionResponseDataBuilder;
 
 use crate::errors::RegisterCommandsError;
+use crate::features::{command_alias, preferences, FeatureEvent};
+use crate::interactions::cooldowns::{self, CooldownStatus};
 use crate::interactions::tags::{CheckPermsInvokerTag, LackingPermissionsTag};
+use crate::interactions::util::CommandTimedOut;
+use crate::interactions::validation::Validator;
 use crate::interactions::LocalGuildContext;
-use crate::util::http::request_for_model;
-use crate::Bot;
+use crate::util::http::request_for_list;
+use crate::{Bot, BotPermissions};
+
+use self::diff::CommandsDiff;
 
 mod context;
-mod local_guild;
+mod diff;
+pub mod help;
+pub mod local_guild;
 mod ping;
+pub mod sync;
 
 pub use self::context::*;
+pub use self::help::{CommandHelp, HelpCategory};
+
+/// Commands that cannot be gated with `/admin feature` so admins
+/// cannot lock themselves out from managing feature gates.
+const UNGATED_COMMAND_PREFIX: &str = "admin feature";
+
+const NOT_ROLLED_OUT_MSG: &str =
+    "**This command is not available for you yet.** Please try again later.";
 
 #[allow(async_fn_in_trait)]
 pub trait RunCommand: CreateCommand + CommandModel + Debug {
@@ -55,10 +74,30 @@ pub trait RunCommand: CreateCommand + CommandModel + Debug {
     fn channel_permissions(&self) -> Permissions {
         Permissions::empty()
     }
+
+    /// Checks the parsed arguments of this command before running it.
+    ///
+    /// Every constraint violated is shown to the invoker at once
+    /// so commands don't need to reply with their own errors.
+    fn validate(&self, _validator: &mut Validator<'_>) {}
+
+    /// Whether the response of this command should only be seen
+    /// by the invoker, like responses exposing settings or previews
+    /// to other members.
+    fn is_ephemeral(&self) -> bool {
+        false
+    }
+
+    /// Extended information about this command shown in `/help`.
+    ///
+    /// Only top-level commands are shown in `/help`, so this
+    /// is ignored for subcommands.
+    const HELP: CommandHelp = CommandHelp::new(HelpCategory::General);
 }
 
 pub async fn handle(ctx: CommandContext) -> Result<()> {
     debug!("received command: {:?}", ctx.data.name);
+    let started_at = Instant::now();
 
     macro_rules! match_commands {
         ($ctx:expr, $data:expr, [ $($command:ty),* $(,)? ]) => (match $ctx.data.name.as_str() {
@@ -69,20 +108,56 @@ pub async fn handle(ctx: CommandContext) -> Result<()> {
 
     let input: CommandInputData<'_> = ctx.data.clone().into();
     let name = ctx.command_name();
-    let result = match_commands!(
-        ctx,
-        input,
-        [
-            commands::local_guild::PayerCommand,
-            commands::local_guild::SettingsCommand,
-            commands::Ping
-        ]
-    );
+    let future = async {
+        if ctx.data.kind == CommandType::Message {
+            return handle_message_command(&ctx).await;
+        }
+        match_commands!(
+            ctx,
+            input,
+            [
+                commands::local_guild::AdminCommand,
+                commands::local_guild::EmojiCommand,
+                commands::local_guild::HelpCommand,
+                commands::local_guild::PayerCommand,
+                commands::local_guild::PreferencesCommand,
+                commands::local_guild::RoleCommand,
+                commands::local_guild::SettingsCommand,
+                commands::local_guild::StatsCommand,
+                commands::Ping
+            ]
+        )
+    };
+
+    // Handlers stuck on something (like a hanging HTTP request) are
+    // cancelled so the invoker isn't left with an unanswered command.
+    let execution_timeout = ctx.bot.settings.bot.commands.execution_timeout;
+    let result = if execution_timeout.is_zero() {
+        future.await
+    } else {
+        tokio::time::timeout(execution_timeout.get(), future)
+            .await
+            .unwrap_or_else(|_| {
+                Err(Error::context_anonymize(
+                    ErrorCategory::Unknown,
+                    CommandTimedOut(name.clone()),
+                ))
+                .attach_printable(format!("command took longer than {execution_timeout}"))
+            })
+    };
+
+    ctx.bot.events.publish(FeatureEvent::CommandCompleted {
+        name: name.clone(),
+        guild_id: ctx.interaction.guild_id,
+        invoker_id: ctx.invoker_id(),
+        success: result.is_ok(),
+    });
 
     let Err(error) = result else {
         trace!("successfully ran command {name:?}");
         return Ok(());
     };
+    let error = error.attach(telemetry_tags(&ctx, &name, started_at.elapsed()));
 
     let is_admin = error
         .get_attached_any::<CheckPermsInvokerTag>()
@@ -99,8 +174,10 @@ pub async fn handle(ctx: CommandContext) -> Result<()> {
         &error,
     );
 
-    // log error messages for non-user errors.
-    if !error.get_category().is_user_error() && !ctx.bot.is_sentry_enabled() {
+    // log error messages for non-user errors. errors are expected
+    // while Discord is having an outage so they're not logged.
+    let is_degraded = ctx.bot.shard_manager.outage().is_degraded();
+    if !error.get_category().is_user_error() && !ctx.bot.is_sentry_enabled() && !is_degraded {
         warn!(%error, "failed to run command {name:?}");
     }
 
@@ -111,46 +188,164 @@ pub async fn handle(ctx: CommandContext) -> Result<()> {
     Ok(())
 }
 
+/// Structured tags attached to every command error so errors
+/// can be filtered by command from logs and Sentry.
+fn telemetry_tags(ctx: &CommandContext, path: &str, elapsed: Duration) -> TelemetryTags {
+    let mut tags = TelemetryTags::new()
+        .with("command", &ctx.data.name)
+        .with("command.path", path)
+        .with("duration_ms", elapsed.as_millis())
+        .with("shard.id", ctx.shard.id().number());
+
+    if let Some(guild_id) = ctx.interaction.guild_id {
+        tags = tags.with_hashed("guild.hash", guild_id);
+    }
+
+    tags
+}
+
 pub async fn register(bot: &Bot) -> Result<(), RegisterCommandsError> {
+    let interaction = bot.interaction();
+
+    let mut global_commands = global_commands();
+    for command in &mut global_commands {
+        eden_discord_types::i18n::localize(command, None);
+    }
+
+    self::sync::load_command_aliases(bot).await;
+    let local_guild_commands = local_guild_commands(bot);
+    let local_guild_id = bot.settings.bot.local_guild.id;
+
+    // Localizations must be fetched too, otherwise every localized
+    // command would always be seen as changed.
+    let request = interaction.global_commands().with_localizations(true);
+    let existing = request_for_list(bot, request)
+        .await
+        .change_context(RegisterCommandsError)
+        .attach_printable("could not get registered global commands")?;
+
+    let diff = CommandsDiff::compute(&existing, &global_commands);
+    if diff.is_empty() {
+        debug!("global commands are up to date");
+    } else {
+        info!(%diff, "setting global commands with {} command group(s)", global_commands.len());
+        interaction
+            .set_global_commands(&global_commands)
+            .await
+            .into_typed_error()
+            .change_context(RegisterCommandsError)?;
+    }
+
+    let request = interaction
+        .guild_commands(local_guild_id)
+        .with_localizations(true);
+    let existing = request_for_list(bot, request)
+        .await
+        .change_context(RegisterCommandsError)
+        .attach_printable("could not get registered local guild commands")?;
+
+    let diff = CommandsDiff::compute(&existing, &local_guild_commands);
+    if diff.is_empty() {
+        debug!("guild ({local_guild_id}) commands are up to date");
+    } else {
+        info!(
+            %diff,
+            "setting guild ({local_guild_id}) commands with {} command group(s)",
+            local_guild_commands.len()
+        );
+        interaction
+            .set_guild_commands(local_guild_id, &local_guild_commands)
+            .await
+            .into_typed_error()
+            .change_context(RegisterCommandsError)?;
+    }
+
+    Ok(())
+}
+
+/// Builds every local guild command to be registered, leaving out
+/// commands that nobody can use because of their feature gates.
+///
+/// Command aliases are registered as commands of their own.
+fn local_guild_commands(bot: &Bot) -> Vec<Command> {
+    let mut list = builtin_local_guild_commands();
+    let locales = bot.settings.bot.local_guild.command_locales.as_deref();
+    for command in &mut list {
+        eden_discord_types::i18n::localize(command, locales);
+    }
+
+    self::sync::remove_disabled(&bot.feature_gates, &mut list);
+
+    // aliases over the limit would make Discord reject every command
+    let global_commands = global_commands();
+    let aliases = bot
+        .command_aliases
+        .list()
+        .into_iter()
+        .filter(|alias| {
+            !list
+                .iter()
+                .chain(&global_commands)
+                .any(|v| v.name == alias.name)
+        })
+        .filter_map(|alias| command_alias::create_command(&alias, &list))
+        .take(command_alias::max_aliases(&list))
+        .collect::<Vec<_>>();
+
+    list.extend(aliases);
+    list
+}
+
+/// Builds the global commands made by Eden without localizations.
+pub fn global_commands() -> Vec<Command> {
+    use eden_discord_types::commands;
+    vec![commands::Ping::create_command().into()]
+}
+
+/// Builds the local guild commands made by Eden without
+/// the command aliases and localizations.
+pub fn builtin_local_guild_commands() -> Vec<Command> {
     use eden_discord_types::commands;
     macro_rules! create_cmds {
         [ $($command:ty),* $(,)? ] => {
             vec!{$( <$command as CreateCommand>::create_command().into(), )*}
         };
     }
-    let interaction = bot.interaction();
 
-    let global_commands = create_cmds![commands::Ping];
-    let local_guild_commands = create_cmds![
+    let mut list: Vec<Command> = create_cmds![
+        commands::local_guild::AdminCommand,
+        commands::local_guild::EmojiCommand,
+        commands::local_guild::HelpCommand,
         commands::local_guild::PayerCommand,
-        commands::local_guild::SettingsCommand
+        commands::local_guild::PreferencesCommand,
+        commands::local_guild::RoleCommand,
+        commands::local_guild::SettingsCommand,
+        commands::local_guild::StatsCommand
     ];
+    list.push(commands::local_guild::MoveMessageCommand::create_command());
+    list.push(commands::local_guild::TranslateMessageCommand::create_command());
+    list
+}
 
-    let total_groups = global_commands.len() + local_guild_commands.len();
-    let local_guild_id = bot.settings.bot.local_guild.id;
-
-    debug!(
-        "setting global commands with {} command group(s)",
-        global_commands.len()
-    );
-    interaction
-        .set_global_commands(&global_commands)
-        .await
-        .into_typed_error()
-        .change_context(RegisterCommandsError)?;
+/// Replaces the data of an invoked command alias with the data
+/// of the command it runs, so the aliased command is handled
+/// like it is invoked directly.
+pub fn resolve_alias(ctx: &mut CommandContext) {
+    if ctx.data.kind != CommandType::ChatInput {
+        return;
+    }
 
-    debug!(
-        "setting guild ({local_guild_id}) commands with {} command group(s)",
-        local_guild_commands.len()
-    );
-    interaction
-        .set_guild_commands(local_guild_id, &local_guild_commands)
-        .await
-        .into_typed_error()
-        .change_context(RegisterCommandsError)?;
+    let Some(alias) = ctx.bot.command_aliases.get(&ctx.data.name) else {
+        return;
+    };
 
-    info!("registered {total_groups} command group(s)");
-    Ok(())
+    let commands = builtin_local_guild_commands();
+    if let Some(data) = command_alias::resolve(&alias, &commands, &ctx.data) {
+        debug!("resolved alias {:?} to {:?}", alias.name, alias.command);
+        ctx.data = data;
+    } else {
+        warn!("command alias {:?} runs an invalid command", alias.name);
+    }
 }
 
 #[derive(Debug, Error)]
@@ -165,66 +360,9 @@ enum LackingBotPermissions {
 #[error("user lacked permissions to use the command {0:?}")]
 struct LackingUserPermissions(String);
 
-#[tracing::instrument(skip_all)]
-async fn fetch_guild_and_channel_permissions(
-    ctx: &LocalGuildContext<'_, CommandData>,
-    needs_channel_info: bool,
-) -> Result<(Permissions, Option<Permissions>)> {
-    let cache = &ctx.bot.cache;
-    let bot_id = ctx.bot.application_id().cast::<UserMarker>();
-
-    let guild = request_for_model(&ctx.bot.http, ctx.bot.http.guild(ctx.guild_id)).await?;
-    let everyone_role = crate::util::get_everyone_role(&guild)
-        .map(|v| v.permissions)
-        .unwrap_or_else(Permissions::empty);
-
-    // Consider trying from cache first?
-    let member_roles = if let Some(member) = cache.member(ctx.guild_id, bot_id) {
-        trace!("cache hit, got member info from cache");
-        member.roles().to_vec()
-    } else {
-        trace!("cache miss, getting member info from Discord API");
-        request_for_model(
-            &ctx.bot.http,
-            ctx.bot.http.guild_member(ctx.guild_id, bot_id),
-        )
-        .await?
-        .roles
-    };
-
-    let mut channel_kind = None;
-    let mut overwrites = None;
-
-    if let Some(channel) = cache.channel(ctx.channel_id) {
-        trace!("cache hit, got channel info from cache");
-
-        let overwrites_data = channel.permission_overwrites.clone().unwrap_or_default();
-        channel_kind = Some(channel.kind);
-        overwrites = Some(overwrites_data);
-    } else if needs_channel_info {
-        // do not request for channels stuff if it is not really required anyways.
-        trace!("cache miss, getting channel info from Discord API");
-
-        let channel =
-            request_for_model(&ctx.bot.http, ctx.bot.http.channel(ctx.channel_id)).await?;
-
-        channel_kind = Some(channel.kind);
-        overwrites = channel.permission_overwrites;
-    } else {
-        trace!("cache miss, not getting channel info from Discord API");
-    }
-
-    let member_roles = crate::util::get_member_role_perms(&member_roles, &guild.roles);
-    trace!(?member_roles, ?everyone_role);
-    let calculator = PermissionCalculator::new(ctx.guild_id, bot_id, everyone_role, &member_roles);
-
-    let guild = calculator.root();
-    let channel = channel_kind
-        .zip(overwrites)
-        .map(|(channel_kind, overwrites)| calculator.in_channel(channel_kind, &overwrites));
-
-    Ok((guild, channel))
-}
+#[derive(Debug, Error)]
+#[error("user is on cooldown to use the command {0:?}")]
+struct CommandOnCooldown(String);
 
 #[allow(clippy::unwrap_used)]
 #[tracing::instrument(skip_all, fields(
@@ -247,7 +385,10 @@ async fn check_bot_guild_permissions<T: CommandModel + RunCommand>(
     }
 
     trace!("fetching bot's guild and channel permissions");
-    let result = fetch_guild_and_channel_permissions(ctx, !channel_required.is_empty()).await;
+    let result = ctx
+        .bot
+        .permissions_in(ctx.guild_id, ctx.channel_id, !channel_required.is_empty())
+        .await;
     if let Some(info) = result.discord_http_error_info() {
         // somehow the API cannot give channel info apparently
         if info.has_missing_access() && !channel_required.is_empty() {
@@ -261,7 +402,10 @@ async fn check_bot_guild_permissions<T: CommandModel + RunCommand>(
         }
     }
 
-    let (current_guild_permissions, current_channel_permissions) = result?;
+    let BotPermissions {
+        guild: current_guild_permissions,
+        channel: current_channel_permissions,
+    } = result?;
     let span = tracing::Span::current();
     if !span.is_disabled() {
         span.record(
@@ -354,6 +498,41 @@ async fn check_user_guild_permissions<T: CommandModel + RunCommand>(
     }
 }
 
+/// Message commands (context menu commands) cannot be parsed with
+/// [`CommandModel`], so they are handled separately.
+async fn handle_message_command(ctx: &CommandContext) -> Result<()> {
+    // message commands are meant to be seen by the invoker only
+    ctx.defer(true).await?;
+    match ctx.data.name.as_str() {
+        commands::local_guild::MoveMessageCommand::NAME => {
+            self::local_guild::move_message::run(ctx).await
+        }
+        commands::local_guild::TranslateMessageCommand::NAME => {
+            self::local_guild::translate_message::run(ctx).await
+        }
+        _ => ctx.unimplemented_cmd(),
+    }
+}
+
+/// Whether the command is rolled out to the invoker with `/admin feature`.
+fn is_rolled_out(ctx: &CommandContext) -> bool {
+    let path = ctx.command_name();
+    if path.starts_with(UNGATED_COMMAND_PREFIX) {
+        return true;
+    }
+
+    let roles = ctx
+        .interaction
+        .member
+        .as_ref()
+        .map(|v| v.roles.as_slice())
+        .unwrap_or_default();
+
+    ctx.bot
+        .feature_gates
+        .is_command_enabled_for(&path, ctx.invoker_id(), roles)
+}
+
 async fn handle_command<'a, T: CommandModel + RunCommand>(
     ctx: &CommandContext,
     data: CommandInputData<'a>,
@@ -365,6 +544,18 @@ async fn handle_command<'a, T: CommandModel + RunCommand>(
             format!("could not parse {:?} command from interaction", T::NAME)
         })?;
 
+    // we cannot guarantee that commands do run fast
+    ctx.defer(command.is_ephemeral()).await?;
+
+    if !is_rolled_out(ctx) {
+        trace!("command is not rolled out to the invoker yet");
+        let data = InteractionResponseDataBuilder::new()
+            .content(NOT_ROLLED_OUT_MSG)
+            .build();
+
+        return ctx.respond(data).await;
+    }
+
     let guild_ctx = LocalGuildContext::from_ctx(ctx).await.ok();
     if let Some(ctx) = guild_ctx {
         let permissions = ctx.member.permissions.unwrap_or_else(Permissions::empty);
@@ -381,5 +572,41 @@ async fn handle_command<'a, T: CommandModel + RunCommand>(
             .attach(tag)?;
     }
 
-    command.run(ctx).await
+    let mut validator = Validator::new(ctx.data.resolved.as_ref());
+    command.validate(&mut validator);
+    if !validator.violations().is_empty() {
+        let client_locale = ctx.interaction.locale.as_deref();
+        let locale = preferences::locale(&ctx.bot, ctx.invoker_id(), client_locale).await?;
+        validator.finish(ctx.command_name(), Some(&locale))?;
+    }
+
+    let path = ctx.command_name();
+    let rules = &ctx.bot.settings.bot.commands.cooldowns;
+    let Some(rule) = cooldowns::find_rule(rules, &path) else {
+        return command.run(ctx).await;
+    };
+
+    let duration = rule.duration.to_time_delta();
+    let bucket = rule.command.as_str();
+    let expires_at =
+        match cooldowns::try_start(&ctx.bot, ctx.invoker_id(), bucket, duration).await? {
+            CooldownStatus::Started { expires_at } => expires_at,
+            CooldownStatus::Active { expires_at } => {
+                trace!("invoker is still on cooldown until {expires_at}");
+                return Err(Error::context_anonymize(
+                    ErrorCategory::User(UserErrorCategory::OnCooldown(expires_at)),
+                    CommandOnCooldown(path),
+                ));
+            }
+        };
+
+    let result = command.run(ctx).await;
+    if result.is_err() {
+        let invoker_id = ctx.invoker_id();
+        let refunded = cooldowns::refund(&ctx.bot, invoker_id, bucket, duration, expires_at).await;
+        if let Err(error) = refunded {
+            warn!(%error, "could not refund cooldown of command {path:?}");
+        }
+    }
+    result
 }
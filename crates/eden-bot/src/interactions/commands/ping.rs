@@ -7,10 +7,13 @@ use tracing::trace;
 use twilight_model::channel::message::Embed;
 use twilight_util::builder::InteractionResponseDataBuilder;
 
-use super::{CommandContext, RunCommand};
+use super::{CommandContext, CommandHelp, HelpCategory, RunCommand};
 use crate::interactions::embeds;
 
 impl RunCommand for Ping {
+    const HELP: CommandHelp =
+        CommandHelp::new(HelpCategory::General).examples(&["/ping", "/ping show_latency:True"]);
+
     #[tracing::instrument(skip(ctx))]
     async fn run(&self, ctx: &CommandContext) -> Result<()> {
         let mut content = "**:ping_pong:  Pong!**".to_string();
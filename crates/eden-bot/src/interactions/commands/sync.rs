@@ -0,0 +1,320 @@
+use eden_utils::error::{exts::*, Context};
+use eden_utils::Result;
+use std::result::Result as StdResult;
+use std::time::Duration;
+use tracing::{debug, info, instrument, warn};
+use twilight_http::client::InteractionClient;
+use twilight_model::application::command::{
+    Command, CommandOption, CommandOptionType, CommandType,
+};
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+
+use crate::errors::RegisterCommandsError;
+use crate::features::feature_gate::FeatureGates;
+use crate::util::http::{request_for_empty, request_for_list, request_for_model};
+use crate::Bot;
+
+use super::diff::{self, CommandsDiff};
+use super::UNGATED_COMMAND_PREFIX;
+
+/// How long to wait for feature gates or command aliases to stop changing
+/// before updating the registered commands, so changing several of them
+/// in a row only sends requests to Discord once.
+const DEBOUNCE: Duration = Duration::from_secs(10);
+
+/// Keeps the registered local guild commands in sync with the feature
+/// gates and command aliases until Eden shuts down. Commands are also
+/// updated once this instance becomes the leader.
+///
+/// It is expected to be spawned after the commands are registered.
+#[instrument(skip_all)]
+pub async fn listen(bot: Bot) {
+    loop {
+        tokio::select! {
+            () = bot.feature_gates.changed() => {}
+            () = bot.command_aliases.changed() => {}
+            () = bot.leadership.elected() => {}
+            _ = eden_utils::shutdown::graceful() => break,
+        }
+
+        loop {
+            tokio::select! {
+                () = bot.feature_gates.changed() => {}
+                () = bot.command_aliases.changed() => {}
+                () = tokio::time::sleep(DEBOUNCE) => break,
+            }
+        }
+
+        // only the leader registers commands
+        if !bot.leadership.is_leader() {
+            debug!("skipping command updates since this instance is not the leader");
+            continue;
+        }
+
+        if let Err(error) = reconcile(&bot).await {
+            warn!(error = %error.anonymize(), "could not update registered commands");
+        }
+    }
+}
+
+/// Updates the registered local guild commands one by one and only
+/// the ones that are different from Eden's commands, unlike
+/// [`register`](super::register) which replaces all of them at once.
+#[instrument(skip_all)]
+pub async fn reconcile(bot: &Bot) -> Result<(), RegisterCommandsError> {
+    let interaction = bot.interaction();
+    let guild_id = bot.settings.bot.local_guild.id;
+    load_command_aliases(bot).await;
+
+    let desired = super::local_guild_commands(bot);
+
+    // localizations are compared with the desired commands as well
+    let request = interaction
+        .guild_commands(guild_id)
+        .with_localizations(true);
+
+    let existing = request_for_list(bot, request)
+        .await
+        .change_context(RegisterCommandsError)
+        .attach_printable("could not get registered local guild commands")?;
+
+    let diff = CommandsDiff::compute(&existing, &desired);
+    if diff.is_empty() {
+        debug!("guild ({guild_id}) commands are up to date");
+        return Ok(());
+    }
+    info!(%diff, "updating guild ({guild_id}) commands");
+
+    for command in &desired {
+        let registered = existing
+            .iter()
+            .find(|v| v.name == command.name && v.kind == command.kind);
+
+        if registered.is_some_and(|v| diff::is_same_command(v, command)) {
+            continue;
+        }
+
+        // Discord replaces the command if it has the same name and type
+        upsert_guild_command(bot, &interaction, guild_id, command)
+            .await
+            .attach_printable_lazy(|| format!("could not update command {:?}", command.name))?;
+    }
+
+    for command in &existing {
+        if desired
+            .iter()
+            .any(|v| v.name == command.name && v.kind == command.kind)
+        {
+            continue;
+        }
+
+        let Some(command_id) = command.id else {
+            continue;
+        };
+        request_for_empty(bot, interaction.delete_guild_command(guild_id, command_id))
+            .await
+            .change_context(RegisterCommandsError)
+            .attach_printable_lazy(|| format!("could not delete command {:?}", command.name))?;
+    }
+
+    Ok(())
+}
+
+/// Command aliases that cannot be loaded are left unregistered
+/// rather than failing to register every other command.
+pub(super) async fn load_command_aliases(bot: &Bot) {
+    if let Err(error) = bot.command_aliases.load(bot).await {
+        warn!(error = %error.anonymize(), "could not load command aliases");
+    }
+}
+
+async fn upsert_guild_command(
+    bot: &Bot,
+    interaction: &InteractionClient<'_>,
+    guild_id: Id<GuildMarker>,
+    command: &Command,
+) -> Result<(), RegisterCommandsError> {
+    let request = interaction.create_guild_command(guild_id);
+    let result = if command.kind == CommandType::Message {
+        let mut request =
+            validated(request.message(&command.name))?.nsfw(command.nsfw.unwrap_or_default());
+
+        if let Some(permissions) = command.default_member_permissions {
+            request = request.default_member_permissions(permissions);
+        }
+        if let Some(localizations) = command.name_localizations.as_ref() {
+            request = validated(request.name_localizations(localizations))?;
+        }
+        request_for_model(bot, request).await
+    } else {
+        let mut request = validated(request.chat_input(&command.name, &command.description))?;
+        request = validated(request.command_options(&command.options))?
+            .nsfw(command.nsfw.unwrap_or_default());
+
+        if let Some(permissions) = command.default_member_permissions {
+            request = request.default_member_permissions(permissions);
+        }
+        if let Some(localizations) = command.name_localizations.as_ref() {
+            request = validated(request.name_localizations(localizations))?;
+        }
+        if let Some(localizations) = command.description_localizations.as_ref() {
+            request = validated(request.description_localizations(localizations))?;
+        }
+        request_for_model(bot, request).await
+    };
+
+    result.map(|_| ()).change_context(RegisterCommandsError)
+}
+
+fn validated<T, C: Context>(result: StdResult<T, C>) -> Result<T, RegisterCommandsError> {
+    result
+        .into_typed_error()
+        .change_context(RegisterCommandsError)
+}
+
+/// Removes commands and subcommands that nobody can use because of
+/// their feature gates, so members won't see them until they are
+/// rolled out. Commands under `/admin feature` are always kept.
+pub fn remove_disabled(gates: &FeatureGates, commands: &mut Vec<Command>) {
+    commands.retain_mut(|command| {
+        // only slash commands are gated by their path
+        if command.kind != CommandType::ChatInput {
+            return true;
+        }
+
+        let disabled = gates.is_disabled(&command.name);
+        !is_removable(&command.name, disabled)
+            && retain_options(gates, &command.name, disabled, &mut command.options)
+    });
+}
+
+/// Removes the disabled subcommands of a command. It returns whether
+/// the command can still be used since commands with subcommands
+/// cannot be used by themselves.
+fn retain_options(
+    gates: &FeatureGates,
+    path: &str,
+    parent_disabled: bool,
+    options: &mut Vec<CommandOption>,
+) -> bool {
+    let is_subcommand = |option: &CommandOption| {
+        matches!(
+            option.kind,
+            CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+        )
+    };
+
+    let had_subcommands = options.iter().any(is_subcommand);
+    options.retain_mut(|option| {
+        if !is_subcommand(option) {
+            return true;
+        }
+
+        let path = format!("{path} {}", option.name);
+        let disabled = parent_disabled || gates.is_disabled(&path);
+        if is_removable(&path, disabled) {
+            return false;
+        }
+
+        option.options.as_mut().map_or(true, |options| {
+            retain_options(gates, &path, disabled, options)
+        })
+    });
+
+    !had_subcommands || options.iter().any(is_subcommand)
+}
+
+fn is_removable(path: &str, disabled: bool) -> bool {
+    let is_ungated = path.starts_with(UNGATED_COMMAND_PREFIX)
+        || UNGATED_COMMAND_PREFIX.starts_with(&format!("{path} "));
+
+    disabled && !is_ungated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::feature_gate::FeatureGate;
+    use twilight_util::builder::command::{
+        CommandBuilder, StringBuilder, SubCommandBuilder, SubCommandGroupBuilder,
+    };
+
+    fn commands() -> Vec<Command> {
+        let admin = CommandBuilder::new("admin", "Admin", CommandType::ChatInput)
+            .option(
+                SubCommandGroupBuilder::new("feature", "Feature").subcommands([
+                    SubCommandBuilder::new("set", "Set"),
+                    SubCommandBuilder::new("remove", "Remove"),
+                ]),
+            )
+            .option(SubCommandBuilder::new("shard", "Shard"))
+            .build();
+
+        let settings = CommandBuilder::new("settings", "Settings", CommandType::ChatInput)
+            .option(SubCommandGroupBuilder::new("logs", "Logs").subcommands([
+                SubCommandBuilder::new("member", "Member"),
+                SubCommandBuilder::new("message", "Message"),
+            ]))
+            .option(SubCommandBuilder::new("emoji", "Emoji"))
+            .build();
+
+        let emoji = CommandBuilder::new("emoji", "Emoji", CommandType::ChatInput)
+            .option(StringBuilder::new("name", "Name"))
+            .build();
+
+        vec![admin, settings, emoji]
+    }
+
+    fn paths(commands: &[Command]) -> Vec<String> {
+        fn walk(path: &str, options: &[CommandOption], paths: &mut Vec<String>) {
+            for option in options {
+                if let Some(options) = option.options.as_deref() {
+                    let path = format!("{path} {}", option.name);
+                    paths.push(path.clone());
+                    walk(&path, options, paths);
+                } else if option.kind == CommandOptionType::SubCommand {
+                    paths.push(format!("{path} {}", option.name));
+                }
+            }
+        }
+
+        let mut paths = Vec::new();
+        for command in commands {
+            paths.push(command.name.clone());
+            walk(&command.name, &command.options, &mut paths);
+        }
+        paths
+    }
+
+    #[test]
+    fn should_remove_disabled_commands() {
+        let gates = FeatureGates::new();
+        gates.set(FeatureGate::new("admin", 0));
+        gates.set(FeatureGate::new("settings logs member", 0));
+        gates.set(FeatureGate::new("settings logs message", 0));
+        gates.set(FeatureGate {
+            roles: vec![Id::new(1)],
+            ..FeatureGate::new("emoji", 0)
+        });
+
+        let mut commands = commands();
+        remove_disabled(&gates, &mut commands);
+        assert_eq!(
+            paths(&commands),
+            vec![
+                "admin",
+                "admin feature",
+                "admin feature set",
+                "admin feature remove",
+                "settings",
+                "settings emoji",
+                "emoji",
+            ]
+        );
+
+        gates.set(FeatureGate::new("settings emoji", 0));
+        remove_disabled(&gates, &mut commands);
+        assert_eq!(paths(&commands)[4..], ["emoji"]);
+    }
+}
@@ -1,7 +1,14 @@
 pub const ERROR_OCCURRED_MESSAGE: &str = "🔴  **Error occurred!**";
 pub const INTERNAL_MSG: &str = "There's something wrong with while I am processing your command.\n\nPlease contact @memothelemo to be able assist the problem.";
 pub const INTERNAL_DB_MSG: &str = "There's something wrong when accessing your data.\n\nPlease contact @memothelemo to be able assist the problem.";
+pub const DISCORD_CANNOT_DM_MSG: &str = "I cannot send direct messages to the user. Please make sure direct messages from server members are enabled and try again.";
+pub const DISCORD_MISSING_PERMS_MSG: &str = "I do not have enough permissions to perform this action.\n\nPlease inform the server administrators about this error.";
+pub const DISCORD_UNKNOWN_RESOURCE_MSG: &str =
+    "The channel, message, role or member I am trying to access does not exist anymore.";
+pub const DISCORD_LIMIT_REACHED_MSG: &str = "I cannot perform this action because Discord's limit has been reached.\n\nPlease inform the server administrators about this error.";
+pub const TIMED_OUT_MSG: &str = "Sorry, I took too long to finish your command so I had to stop it.\n\nPlease try again in a moment. If it keeps happening, please contact @memothelemo.";
 pub const NOT_ALLOWED_MSG: &str = "You're not allowed to access this command!";
+pub const ON_COOLDOWN_MSG: &str = "You can use this command again {expires_at}.";
 
 pub const MISSING_GUILD_PERMS_MSG: &str = "I cannot run this command because I do not have the following permissions in this server:\n```{missing_permissions}```\n{footer}";
 pub const MISSING_CHANNEL_PERMS_MSG: &str = "I cannot run this command because I do not have the following permissions in this channel you're in:\n```{missing_permissions}```\n{footer}";
@@ -1,4 +1,4 @@
-use eden_schema::types::{GuildSettings, GuildSettingsRow};
+use eden_schema::types::GuildSettingsRow;
 use eden_utils::error::GuildErrorCategory;
 use eden_utils::{Error, ErrorCategory, Result};
 use std::fmt::Debug;
@@ -82,8 +82,7 @@ impl<'a, T> LocalGuildContext<'a, T> {
             ));
         };
 
-        let mut conn = ctx.bot.db_read().await?;
-        let settings = GuildSettings::upsert(&mut conn, *guild_id).await?;
+        let settings = ctx.bot.guild_settings(*guild_id).await?;
         trace!(?settings, "got local guild settings");
 
         Ok(Self {
@@ -104,11 +103,9 @@ impl<'a, T> LocalGuildContext<'a, T> {
         }
 
         // TODO: Find a way to reduce this request
-        let guild = crate::util::http::request_for_model(
-            &self.bot.http,
-            self.bot.http.guild(self.guild_id),
-        )
-        .await?;
+        let guild =
+            crate::util::http::request_for_model(&self.bot, self.bot.http.guild(self.guild_id))
+                .await?;
 
         let everyone_role = crate::util::get_everyone_role(&guild)
             .map(|v| v.permissions)
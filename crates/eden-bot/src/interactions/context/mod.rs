@@ -1,7 +1,8 @@
+use chrono::{DateTime, TimeDelta, Utc};
 use eden_utils::error::exts::{AnonymizedResultExt, IntoTypedError, ResultExt};
 use eden_utils::Result;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tracing::Span;
+use tracing::{debug, Span};
 use twilight_model::channel::message::{AllowedMentions, Embed, MessageFlags};
 use twilight_model::http::interaction::{
     InteractionResponse, InteractionResponseData, InteractionResponseType,
@@ -9,14 +10,26 @@ use twilight_model::http::interaction::{
 use twilight_model::id::marker::{ChannelMarker, UserMarker};
 use twilight_model::{application::interaction::Interaction, id::Id};
 use twilight_util::builder::InteractionResponseDataBuilder;
+use twilight_util::snowflake::Snowflake;
 
 use crate::events::EventContext;
 use crate::shard::ShardHandle;
+use crate::util::http::request_for_model;
 use crate::Bot;
 
 mod local_guild;
 pub use self::local_guild::*;
 
+/// How long Discord allows the bot to respond or follow up an
+/// interaction with its token after the interaction is created.
+const INTERACTION_TOKEN_LIFETIME: TimeDelta = TimeDelta::minutes(15);
+
+/// Checks whether an interaction token issued at `issued_at` is
+/// no longer usable at `now`.
+fn is_token_expired_at(issued_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - issued_at >= INTERACTION_TOKEN_LIFETIME
+}
+
 #[derive(Debug)]
 pub struct InteractionContext<T> {
     pub bot: Bot,
@@ -26,6 +39,7 @@ pub struct InteractionContext<T> {
     pub shard: ShardHandle,
 
     responded: AtomicBool,
+    token_issued_at: DateTime<Utc>,
 }
 
 impl<T> InteractionContext<T> {
@@ -33,6 +47,12 @@ impl<T> InteractionContext<T> {
         let Some(ref channel) = interaction.channel else {
             panic!("Ping interactions are not allowed to be used for creating contexts");
         };
+
+        // Interaction IDs are snowflakes, so we can get when the interaction
+        // (including its token) is created from Discord.
+        let token_issued_at =
+            DateTime::from_timestamp_millis(interaction.id.timestamp()).unwrap_or_else(Utc::now);
+
         Self {
             bot,
             channel_id: channel.id,
@@ -40,6 +60,7 @@ impl<T> InteractionContext<T> {
             interaction: interaction.clone(),
             shard: ctx.shard.clone(),
             responded: AtomicBool::new(false),
+            token_issued_at,
         }
     }
 
@@ -76,6 +97,31 @@ impl<T> InteractionContext<T> {
             .attach_printable("could not respond with message")
     }
 
+    /// Edits the message where the interaction's component is attached
+    /// to. It only works for message component interactions.
+    pub async fn update(&self, data: InteractionResponseData) -> Result<()> {
+        let kind = InteractionResponseType::UpdateMessage;
+        self.send_response(Some(data), kind)
+            .await
+            .attach_printable("could not update message")
+    }
+
+    /// Gets when the interaction token is issued by Discord.
+    #[must_use]
+    pub fn token_issued_at(&self) -> DateTime<Utc> {
+        self.token_issued_at
+    }
+
+    /// Whether the interaction token has expired and it can no longer
+    /// be used to respond or follow up the interaction.
+    ///
+    /// Responses sent after the token has expired will be sent as
+    /// a normal message in the interaction's channel instead.
+    #[must_use]
+    pub fn is_token_expired(&self) -> bool {
+        is_token_expired_at(self.token_issued_at, Utc::now())
+    }
+
     /// Gets the invoker's user id
     #[allow(clippy::expect_used)]
     #[must_use]
@@ -100,6 +146,10 @@ impl<T> InteractionContext<T> {
         data: Option<InteractionResponseData>,
         kind: InteractionResponseType,
     ) -> Result<()> {
+        if self.is_token_expired() {
+            return self.send_late_response(data).await;
+        }
+
         let http = self.bot.interaction();
         let responded_earlier = self.responded.load(Ordering::Relaxed);
 
@@ -175,4 +225,99 @@ impl<T> InteractionContext<T> {
             Ok(())
         }
     }
+
+    /// Sends the response as a normal message in the interaction's
+    /// channel since the interaction token has already expired.
+    ///
+    /// Ephemeral responses are sent to the invoker's DM channel instead
+    /// since channel messages cannot be ephemeral.
+    #[tracing::instrument(skip_all)]
+    async fn send_late_response(&self, data: Option<InteractionResponseData>) -> Result<()> {
+        debug!("interaction token has expired, sending response as a channel message");
+
+        // deferred responses without data have nothing to send anyway
+        let Some(data) = data else {
+            return Ok(());
+        };
+
+        let flags = data.flags.unwrap_or_else(MessageFlags::empty);
+        let channel_id = if flags.contains(MessageFlags::EPHEMERAL) {
+            debug!("response is ephemeral, sending it to the invoker's DM channel");
+            let request = self.bot.http.create_private_channel(self.invoker_id());
+            request_for_model(&self.bot, request)
+                .await
+                .attach_printable("could not create DM channel for the invoker")?
+                .id
+        } else {
+            self.channel_id
+        };
+
+        let mut request = self.bot.http.create_message(channel_id);
+        let flags = flags.difference(MessageFlags::EPHEMERAL);
+        if !flags.is_empty() {
+            request = request.flags(flags);
+        }
+
+        if let Some(mentions) = &data.allowed_mentions {
+            request = request.allowed_mentions(Some(mentions));
+        }
+
+        if let Some(attachments) = &data.attachments {
+            request = request
+                .attachments(attachments)
+                .into_typed_error()
+                .anonymize_error()?;
+        }
+
+        if let Some(components) = &data.components {
+            request = request
+                .components(components)
+                .into_typed_error()
+                .anonymize_error()?;
+        }
+
+        if let Some(content) = &data.content {
+            request = request
+                .content(content)
+                .into_typed_error()
+                .anonymize_error()?;
+        }
+
+        if let Some(embeds) = &data.embeds {
+            request = request
+                .embeds(embeds)
+                .into_typed_error()
+                .anonymize_error()?;
+        }
+
+        if let Some(tts) = data.tts {
+            request = request.tts(tts);
+        }
+
+        request_for_model(&self.bot, request)
+            .await
+            .attach_printable("could not send late response as a channel message")?;
+
+        self.responded.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_expire_token_after_lifetime() {
+        let issued_at = Utc::now();
+        assert!(!is_token_expired_at(issued_at, issued_at));
+        assert!(!is_token_expired_at(
+            issued_at,
+            issued_at + TimeDelta::minutes(14)
+        ));
+        assert!(is_token_expired_at(
+            issued_at,
+            issued_at + INTERACTION_TOKEN_LIFETIME
+        ));
+    }
 }
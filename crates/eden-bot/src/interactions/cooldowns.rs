@@ -0,0 +1,330 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::mapref::entry::Entry;
+use dashmap::{DashMap, DashSet};
+use eden_schema::types::CommandCooldown;
+use eden_settings::{CommandCooldownRule, Commands};
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use tracing::{debug, trace};
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+
+use crate::Bot;
+
+type CooldownKey = (Id<UserMarker>, String);
+
+/// Keeps track of users' command cooldowns per bucket.
+///
+/// Cooldowns that last at least `bot.commands.persist_cooldowns_after`
+/// are also stored in the database and loaded lazily per user on their
+/// first use, so they won't reset after Eden restarts.
+#[derive(Debug)]
+pub struct CommandCooldowns {
+    expiries: DashMap<CooldownKey, DateTime<Utc>>,
+    loaded_users: DashSet<Id<UserMarker>>,
+    persist_after: Option<TimeDelta>,
+}
+
+impl CommandCooldowns {
+    #[must_use]
+    pub fn new(settings: &Commands) -> Self {
+        Self {
+            expiries: DashMap::new(),
+            loaded_users: DashSet::new(),
+            persist_after: settings
+                .persist_cooldowns
+                .then(|| settings.persist_cooldowns_after.to_time_delta()),
+        }
+    }
+
+    /// Gets when the user's cooldown of a bucket expires if it is
+    /// still active.
+    #[must_use]
+    pub fn expires_at(
+        &self,
+        user_id: Id<UserMarker>,
+        bucket: &str,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        self.expiries
+            .get(&(user_id, bucket.to_string()))
+            .map(|v| *v)
+            .filter(|expires_at| *expires_at > now)
+    }
+
+    /// Starts the user's cooldown of a bucket until `expires_at`.
+    pub fn start(&self, user_id: Id<UserMarker>, bucket: &str, expires_at: DateTime<Utc>) {
+        self.expiries
+            .insert((user_id, bucket.to_string()), expires_at);
+    }
+
+    /// Starts the user's cooldown of a bucket until `expires_at` only
+    /// if the user is not on cooldown of the bucket by `now`.
+    ///
+    /// It returns when the active cooldown expires if the user is
+    /// still on cooldown.
+    pub fn reserve(
+        &self,
+        user_id: Id<UserMarker>,
+        bucket: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DateTime<Utc>> {
+        match self.expiries.entry((user_id, bucket.to_string())) {
+            Entry::Occupied(entry) if *entry.get() > now => Err(*entry.get()),
+            Entry::Occupied(mut entry) => {
+                entry.insert(expires_at);
+                Ok(())
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(expires_at);
+                Ok(())
+            }
+        }
+    }
+
+    /// Cancels the user's cooldown of a bucket if it is still the
+    /// one that expires at `expires_at`.
+    pub fn release(&self, user_id: Id<UserMarker>, bucket: &str, expires_at: DateTime<Utc>) {
+        self.expiries
+            .remove_if(&(user_id, bucket.to_string()), |_, v| *v == expires_at);
+    }
+
+    /// Forgets all cooldowns that are already expired.
+    pub fn clear_expired(&self, now: DateTime<Utc>) {
+        self.expiries.retain(|_, expires_at| *expires_at > now);
+    }
+
+    /// Whether the cooldown is long enough to be stored in the database.
+    #[must_use]
+    pub fn should_persist(&self, duration: TimeDelta) -> bool {
+        self.persist_after
+            .is_some_and(|threshold| duration >= threshold)
+    }
+
+    fn merge_loaded(&self, user_id: Id<UserMarker>, cooldowns: Vec<CommandCooldown>) {
+        for cooldown in cooldowns {
+            self.expiries
+                .entry((user_id, cooldown.bucket))
+                .and_modify(|v| *v = (*v).max(cooldown.expires_at))
+                .or_insert(cooldown.expires_at);
+        }
+        self.loaded_users.insert(user_id);
+    }
+}
+
+/// Finds the cooldown rule of a command path.
+///
+/// Rules of subcommands take priority over rules of their
+/// command groups.
+#[must_use]
+pub fn find_rule<'a>(
+    rules: &'a [CommandCooldownRule],
+    path: &str,
+) -> Option<&'a CommandCooldownRule> {
+    rules
+        .iter()
+        .filter(|rule| {
+            path == rule.command
+                || path
+                    .strip_prefix(rule.command.as_str())
+                    .is_some_and(|rest| rest.starts_with(' '))
+        })
+        .max_by_key(|rule| rule.command.len())
+}
+
+/// Outcome of [`try_start`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CooldownStatus {
+    /// The cooldown of the bucket has started and it can be
+    /// refunded with [`refund`] until it expires.
+    Started { expires_at: DateTime<Utc> },
+    /// The user is still on cooldown of the bucket.
+    Active { expires_at: DateTime<Utc> },
+}
+
+/// Checks if the user is on cooldown of the bucket. If not, the
+/// cooldown of the bucket will start for the given duration.
+///
+/// The cooldown is reserved atomically, so concurrent uses of the
+/// same bucket cannot both pass through.
+pub async fn try_start(
+    bot: &Bot,
+    user_id: Id<UserMarker>,
+    bucket: &str,
+    duration: TimeDelta,
+) -> Result<CooldownStatus> {
+    let cooldowns = &bot.cooldowns;
+    let persist = cooldowns.should_persist(duration);
+
+    if persist && !cooldowns.loaded_users.contains(&user_id) {
+        trace!("loading persisted command cooldowns of the user");
+        let mut conn = bot.db_read().await?;
+        let loaded = CommandCooldown::active_list(&mut conn, user_id, Utc::now()).await?;
+        cooldowns.merge_loaded(user_id, loaded);
+    }
+
+    let now = Utc::now();
+    let expires_at = now + duration;
+    if let Err(expires_at) = cooldowns.reserve(user_id, bucket, now, expires_at) {
+        return Ok(CooldownStatus::Active { expires_at });
+    }
+
+    if persist {
+        debug!(?bucket, %expires_at, "storing command cooldown");
+        let result = async {
+            let mut conn = bot.db_write().await?;
+            CommandCooldown::upsert(&mut conn, user_id, bucket, expires_at).await?;
+            conn.commit()
+                .await
+                .into_eden_error()
+                .attach_printable("could not commit transaction")
+        }
+        .await;
+
+        if let Err(error) = result {
+            cooldowns.release(user_id, bucket, expires_at);
+            return Err(error);
+        }
+    }
+
+    Ok(CooldownStatus::Started { expires_at })
+}
+
+/// Cancels the cooldown started from [`try_start`] with the same
+/// `duration` so the user can use the command again right away.
+pub async fn refund(
+    bot: &Bot,
+    user_id: Id<UserMarker>,
+    bucket: &str,
+    duration: TimeDelta,
+    expires_at: DateTime<Utc>,
+) -> Result<()> {
+    debug!(?bucket, "refunding command cooldown");
+
+    let cooldowns = &bot.cooldowns;
+    cooldowns.release(user_id, bucket, expires_at);
+
+    if cooldowns.should_persist(duration) {
+        let mut conn = bot.db_write().await?;
+        CommandCooldown::delete(&mut conn, user_id, bucket, expires_at).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eden_utils::types::HumanDuration;
+
+    fn setup(persist_cooldowns: bool) -> CommandCooldowns {
+        let settings = Commands::builder()
+            .persist_cooldowns(persist_cooldowns)
+            .persist_cooldowns_after(HumanDuration::from_mins(60))
+            .build();
+
+        CommandCooldowns::new(&settings)
+    }
+
+    #[test]
+    fn should_expire_cooldowns() {
+        let cooldowns = setup(true);
+        let user_id = Id::new(1);
+        let now = Utc::now();
+
+        assert_eq!(cooldowns.expires_at(user_id, "daily", now), None);
+
+        let expires_at = now + TimeDelta::minutes(1);
+        cooldowns.start(user_id, "daily", expires_at);
+        assert_eq!(
+            cooldowns.expires_at(user_id, "daily", now),
+            Some(expires_at)
+        );
+        assert_eq!(cooldowns.expires_at(user_id, "weekly", now), None);
+        assert_eq!(cooldowns.expires_at(user_id, "daily", expires_at), None);
+
+        cooldowns.clear_expired(expires_at);
+        assert!(cooldowns.expiries.is_empty());
+    }
+
+    #[test]
+    fn should_reserve_cooldowns_once() {
+        let cooldowns = setup(true);
+        let user_id = Id::new(1);
+        let now = Utc::now();
+        let expires_at = now + TimeDelta::minutes(1);
+
+        assert_eq!(cooldowns.reserve(user_id, "daily", now, expires_at), Ok(()));
+        assert_eq!(
+            cooldowns.reserve(user_id, "daily", now, now + TimeDelta::minutes(2)),
+            Err(expires_at)
+        );
+
+        // releasing a cooldown that was replaced should do nothing
+        cooldowns.release(user_id, "daily", now);
+        assert_eq!(
+            cooldowns.expires_at(user_id, "daily", now),
+            Some(expires_at)
+        );
+
+        cooldowns.release(user_id, "daily", expires_at);
+        assert_eq!(cooldowns.expires_at(user_id, "daily", now), None);
+    }
+
+    #[test]
+    fn should_find_most_specific_rule() {
+        let rules = vec![
+            CommandCooldownRule::builder()
+                .command("payer")
+                .duration(HumanDuration::from_mins(1))
+                .build(),
+            CommandCooldownRule::builder()
+                .command("payer register")
+                .duration(HumanDuration::from_days(1))
+                .build(),
+        ];
+
+        let find = |path| find_rule(&rules, path).map(|v| v.command.as_str());
+        assert_eq!(find("payer register"), Some("payer register"));
+        assert_eq!(find("payer stats"), Some("payer"));
+        assert_eq!(find("payer"), Some("payer"));
+        assert_eq!(find("payers"), None);
+        assert_eq!(find("ping"), None);
+    }
+
+    #[test]
+    fn should_persist_long_cooldowns_only() {
+        let cooldowns = setup(true);
+        assert!(!cooldowns.should_persist(TimeDelta::minutes(5)));
+        assert!(cooldowns.should_persist(TimeDelta::days(1)));
+
+        let cooldowns = setup(false);
+        assert!(!cooldowns.should_persist(TimeDelta::days(1)));
+    }
+
+    #[test]
+    fn should_keep_latest_expiry_when_loaded() {
+        let cooldowns = setup(true);
+        let user_id = Id::new(1);
+        let now = Utc::now();
+
+        let latest = now + TimeDelta::hours(2);
+        cooldowns.start(user_id, "daily", latest);
+        cooldowns.merge_loaded(
+            user_id,
+            vec![CommandCooldown {
+                user_id,
+                bucket: "daily".into(),
+                expires_at: now + TimeDelta::hours(1),
+            }],
+        );
+
+        assert_eq!(cooldowns.expires_at(user_id, "daily", now), Some(latest));
+        assert!(cooldowns.loaded_users.contains(&user_id));
+    }
+}
@@ -15,21 +15,28 @@ pub fn error(title: impl Display, emitted_at: Option<DateTime<Utc>>) -> EmbedBui
         .title(format!("❌  {title}"))
         .color(super::colors::RED);
 
-    // twilight uses 'time' while Eden uses 'chrono'
     if let Some(emitted_at) = emitted_at {
-        match Timestamp::from_secs(emitted_at.timestamp()) {
-            Ok(timestamp) => {
-                builder = builder.timestamp(timestamp);
-            }
-            Err(error) => {
-                warn!(%error, "could not convert chrono timestamp time to twilight's timestamp");
-            }
-        }
+        builder = with_timestamp(builder, emitted_at);
     }
 
     builder
 }
 
+/// Sets the timestamp of the embed which Discord renders next to the
+/// footer in the viewer's time zone, so it won't go out of date unlike
+/// relative times written in the footer.
+#[must_use]
+pub fn with_timestamp(builder: EmbedBuilder, at: DateTime<Utc>) -> EmbedBuilder {
+    // twilight uses 'time' while Eden uses 'chrono'
+    match Timestamp::from_secs(at.timestamp()) {
+        Ok(timestamp) => builder.timestamp(timestamp),
+        Err(error) => {
+            warn!(%error, "could not convert chrono timestamp time to twilight's timestamp");
+            builder
+        }
+    }
+}
+
 #[must_use]
 pub fn success(title: impl Display) -> EmbedBuilder {
     EmbedBuilder::new()
@@ -2,9 +2,12 @@ mod context;
 
 pub mod commands;
 pub mod consts;
+pub mod cooldowns;
 pub mod embeds;
+pub mod response_cache;
 pub mod state;
 pub mod tags;
 pub mod util;
+pub mod validation;
 
 pub use self::context::*;
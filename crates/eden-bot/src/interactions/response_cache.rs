@@ -0,0 +1,166 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+use eden_settings::Commands;
+use tracing::trace;
+use twilight_model::http::interaction::InteractionResponseData;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+
+/// Commands where their responses can be cached.
+///
+/// Each of these has its own set of writes that make
+/// their cached responses outdated.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum CachedCommand {
+    /// `/stats growth`. Outdated once member counts are recorded.
+    MemberGrowth,
+    /// `/payer stats`. Outdated once contribution stats are
+    /// refreshed or a user changes their stats visibility.
+    PayerStats,
+    /// `/stats voice`. Outdated once voice stats are flushed.
+    VoiceStats,
+}
+
+/// Identifies a cached command response.
+///
+/// `args` must include everything that makes the response different
+/// (including the invoker if the response is personalized).
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CacheKey {
+    command: CachedCommand,
+    guild_id: Id<GuildMarker>,
+    args: String,
+}
+
+impl CacheKey {
+    #[must_use]
+    pub fn new(command: CachedCommand, guild_id: Id<GuildMarker>, args: impl Into<String>) -> Self {
+        Self {
+            command,
+            guild_id,
+            args: args.into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    data: InteractionResponseData,
+    expires_at: DateTime<Utc>,
+}
+
+/// Keeps responses of expensive read-only commands for a short
+/// while so popular commands won't hit the database every time.
+#[derive(Debug)]
+pub struct ResponseCache {
+    entries: DashMap<CacheKey, CacheEntry>,
+    ttl: Option<TimeDelta>,
+}
+
+impl ResponseCache {
+    #[must_use]
+    pub fn new(settings: &Commands) -> Self {
+        let ttl = settings.response_cache_ttl.to_time_delta();
+        Self {
+            entries: DashMap::new(),
+            ttl: (ttl > TimeDelta::zero()).then_some(ttl),
+        }
+    }
+
+    /// Gets the cached response if it has not expired yet.
+    #[must_use]
+    pub fn get(&self, key: &CacheKey, now: DateTime<Utc>) -> Option<InteractionResponseData> {
+        let entry = self.entries.get(key)?;
+        if entry.expires_at > now {
+            trace!(?key, "got cached command response");
+            return Some(entry.data.clone());
+        }
+        drop(entry);
+
+        self.entries.remove(key);
+        None
+    }
+
+    /// Caches the command response. It does nothing if
+    /// response caching is disabled.
+    pub fn insert(&self, key: CacheKey, data: &InteractionResponseData, now: DateTime<Utc>) {
+        let Some(ttl) = self.ttl else {
+            return;
+        };
+
+        // entries may pile up from users who ran the command once
+        self.entries.retain(|_, entry| entry.expires_at > now);
+
+        let entry = CacheEntry {
+            data: data.clone(),
+            expires_at: now + ttl,
+        };
+        self.entries.insert(key, entry);
+    }
+
+    /// Forgets all cached responses of the command
+    /// because they're already outdated.
+    pub fn bust(&self, command: CachedCommand) {
+        trace!(?command, "busting cached command responses");
+        self.entries.retain(|key, _| key.command != command);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eden_utils::types::HumanDuration;
+
+    fn setup(ttl: HumanDuration) -> ResponseCache {
+        let settings = Commands::builder().response_cache_ttl(ttl).build();
+        ResponseCache::new(&settings)
+    }
+
+    fn response(content: &str) -> InteractionResponseData {
+        InteractionResponseData {
+            content: Some(content.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_expire_cached_responses() {
+        let cache = setup(HumanDuration::from_secs(30));
+        let key =
+            |guild_id, args| CacheKey::new(CachedCommand::VoiceStats, Id::new(guild_id), args);
+        let now = Utc::now();
+
+        cache.insert(key(1, "7"), &response("foo"), now);
+        assert_eq!(cache.get(&key(1, "7"), now), Some(response("foo")));
+        assert_eq!(cache.get(&key(1, "30"), now), None);
+        assert_eq!(cache.get(&key(2, "7"), now), None);
+
+        let later = now + TimeDelta::seconds(30);
+        assert_eq!(cache.get(&key(1, "7"), later), None);
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn should_bust_cached_responses_of_the_command() {
+        let cache = setup(HumanDuration::from_secs(30));
+        let voice = CacheKey::new(CachedCommand::VoiceStats, Id::new(1), "");
+        let payer = CacheKey::new(CachedCommand::PayerStats, Id::new(1), "");
+        let now = Utc::now();
+
+        cache.insert(voice.clone(), &response("foo"), now);
+        cache.insert(payer.clone(), &response("bar"), now);
+        cache.bust(CachedCommand::VoiceStats);
+
+        assert_eq!(cache.get(&voice, now), None);
+        assert!(cache.get(&payer, now).is_some());
+    }
+
+    #[test]
+    fn should_not_cache_if_disabled() {
+        let cache = setup(HumanDuration::from_secs(0));
+        let key = CacheKey::new(CachedCommand::PayerStats, Id::new(1), "");
+
+        cache.insert(key, &response("foo"), Utc::now());
+        assert!(cache.entries.is_empty());
+    }
+}
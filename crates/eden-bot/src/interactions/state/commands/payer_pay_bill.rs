@@ -65,7 +65,7 @@ impl AnyStatefulCommand for PayerPayBillState {
 
         // Read the message perhaps :)
         let request = bot.http.message(self.dm_channel_id, message_id);
-        let result = request_for_model(&bot.http, request).await;
+        let result = request_for_model(bot, request).await;
         let message = match result {
             Ok(n) => n,
             Err(error) => {
@@ -133,7 +133,7 @@ impl AnyStatefulCommand for PayerPayBillState {
             .content(CANCELLED_PAYMENT_MSG)
             .unwrap();
 
-        request_for_model(&bot.http, request).await?;
+        request_for_model(bot, request).await?;
         Ok(())
     }
 }
@@ -167,7 +167,7 @@ impl PayerPayBillState {
             request = request.reply(last_user_message_id);
         }
 
-        request_for_model(&bot.http, request).await?;
+        request_for_model(bot, request).await?;
         Ok(())
     }
 }
@@ -59,33 +59,32 @@ impl CommandStates {
 
     /// Clears out any inactive stateful commands as long as they reached
     /// the minimum timeout threshold (found in `bot.commands.inactivity_timeout`).
+    ///
+    /// Their timeout handlers are run in the background and they will be
+    /// waited until they're done when [`CommandStates::shutdown`] is called.
+    ///
+    /// It returns the amount of stateful commands that have been cleared.
     #[tracing::instrument(skip_all)]
-    pub async fn clear_inactive(&self) {
+    pub async fn clear_inactive(&self) -> usize {
         trace!("clearing all inactive stateful command interactions");
 
         let mut deletes = Vec::new();
         let now = Utc::now();
 
         for entry in self.0.items.iter() {
-            let id = *entry.key();
-            let value = entry.value();
-
-            let command = value.lock().await;
+            let command = entry.value().lock().await;
             let difference = (now - command.last_used_at).abs();
-            if difference < self.0.timeout {
-                continue;
-            }
-
-            let bot = self.0.bot.get();
-            if let Err(error) = command.data.on_timed_out(&bot).await {
-                warn!(%error, "could not process `on_timed_out` for stateful command interaction {id}");
+            if difference >= self.0.timeout {
+                deletes.push(*entry.key());
             }
-            deletes.push(id);
         }
 
-        let deleted = deletes.len();
+        let mut deleted = 0;
         for id in deletes {
-            self.0.items.remove(&id);
+            if let Some((id, command)) = self.0.items.remove(&id) {
+                self.spawn_timeout_handler(id, command);
+                deleted += 1;
+            }
         }
 
         if deleted > 0 {
@@ -93,6 +92,7 @@ impl CommandStates {
         } else {
             trace!("cleared 0 inactive stateful command interaction");
         }
+        deleted
     }
 
     /// Triggers all current stateful commands.
@@ -111,16 +111,26 @@ impl CommandStates {
             let id = *entry.key();
             let value = entry.value().clone();
             let this = self.clone();
-            tokio::spawn(async move {
+            self.0.futures.spawn(async move {
                 this.trigger_command(id, value, trigger).await;
             });
         }
     }
 
+    /// Times out every remaining stateful command and waits for all
+    /// of their pending triggers and timeout handlers to finish.
     #[tracing::instrument(skip_all)]
     pub async fn shutdown(&self) {
         self.0.futures.close();
 
+        let remaining = self.0.items.iter().map(|v| *v.key()).collect::<Vec<_>>();
+
+        for id in remaining {
+            if let Some((id, command)) = self.0.items.remove(&id) {
+                self.spawn_timeout_handler(id, command);
+            }
+        }
+
         let futures = self.0.futures.len();
         if futures == 0 {
             return;
@@ -130,6 +140,20 @@ impl CommandStates {
         self.0.futures.wait().await;
     }
 
+    fn spawn_timeout_handler(
+        &self,
+        id: Id<InteractionMarker>,
+        command: Arc<Mutex<CommandStateInfo>>,
+    ) {
+        let bot = self.0.bot.get();
+        self.0.futures.spawn(async move {
+            let command = command.lock().await;
+            if let Err(error) = command.data.on_timed_out(&bot).await {
+                warn!(%error, "could not process `on_timed_out` for stateful command interaction {id}");
+            }
+        });
+    }
+
     #[tracing::instrument(skip_all, fields(
         command.data = tracing::field::Empty,
         command.interaction.id = %id,
@@ -68,6 +68,7 @@ const fn get_missing_permissions(input: Permissions, requirements: Permissions)
 pub fn install_hook() {
     LackingPermissionsTag::install_hook();
     CheckPermsInvokerTag::install_hook();
+    super::validation::InvalidArgumentsTag::install_hook();
 }
 
 #[cfg(test)]
@@ -1,7 +1,12 @@
 use crate::interactions::consts;
+use crate::interactions::validation::InvalidArgumentsTag;
 use eden_utils::error::{exts::*, UserErrorCategory};
 use eden_utils::error::{ErrorCategory, GuildErrorCategory};
 use eden_utils::sql::SqlErrorExt;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::trace::TraceId;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::twilight::tags::DiscordHttpErrorInfo;
 use itertools::Itertools;
 use thiserror::Error;
 use twilight_model::{channel::message::Embed, http::interaction::InteractionResponseData};
@@ -12,7 +17,14 @@ use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder
 #[error("command {0:?} is not implemented")]
 pub struct UnknownCommandError(pub(super) String);
 
+#[derive(Debug, Error)]
+#[error("command {0:?} took too long to run")]
+pub struct CommandTimedOut(pub(super) String);
+
 /// Builds interaction response data based on [`eden_utils::Error`].
+///
+/// Users with developer mode on will see the entire error along with
+/// the trace ID of the interaction to look up its logs.
 pub fn from_error(
     admin_mode: bool,
     developer_mode: bool,
@@ -70,18 +82,43 @@ pub fn from_error(
             }
         },
         ErrorCategory::User(category) => match category {
+            UserErrorCategory::InvalidArguments => error
+                .get_attached_any::<InvalidArgumentsTag>()
+                .next()
+                .map(InvalidArgumentsTag::render)
+                .unwrap_or_else(|| {
+                    super::embeds::builders::error("Invalid arguments!", None).build()
+                }),
             UserErrorCategory::MissingPermissions => {
                 super::embeds::builders::error("Access denied", None)
                     .description(consts::NOT_ALLOWED_MSG)
                     .build()
             }
+            UserErrorCategory::OnCooldown(expires_at) => {
+                let expires_at = discord_timestamp(*expires_at, TimestampStyle::Relative);
+                let message = consts::ON_COOLDOWN_MSG.replace("{expires_at}", &expires_at);
+
+                super::embeds::builders::with_emoji('⏳', "Slow down!")
+                    .description(message)
+                    .build()
+            }
         },
         ErrorCategory::Unknown => {
-            // unknown is a bit vague
-            let msg = if error.is_pool_error() {
-                consts::INTERNAL_DB_MSG
-            } else {
-                consts::INTERNAL_MSG
+            let is_timed_out = error.downcast_ref_any::<CommandTimedOut>().is_some();
+
+            // unknown is a bit vague, Discord may tell us why it failed
+            let json_code = error
+                .discord_http_error_info()
+                .and_then(DiscordHttpErrorInfo::json_code);
+
+            let msg = match json_code {
+                Some(code) if code.is_cannot_dm() => consts::DISCORD_CANNOT_DM_MSG,
+                Some(code) if code.is_missing_permissions() => consts::DISCORD_MISSING_PERMS_MSG,
+                Some(code) if code.is_unknown_resource() => consts::DISCORD_UNKNOWN_RESOURCE_MSG,
+                Some(code) if code.is_limit_reached() => consts::DISCORD_LIMIT_REACHED_MSG,
+                _ if is_timed_out => consts::TIMED_OUT_MSG,
+                _ if error.is_pool_error() => consts::INTERNAL_DB_MSG,
+                _ => consts::INTERNAL_MSG,
             };
 
             let footer = if is_sentry_enabled {
@@ -91,8 +128,12 @@ pub fn from_error(
                 None
             };
 
-            let mut builder =
-                super::embeds::builders::error("Something went wrong!", None).description(msg);
+            let builder = if is_timed_out {
+                super::embeds::builders::with_emoji('⌛', "That took too long!")
+            } else {
+                super::embeds::builders::error("Something went wrong!", None)
+            };
+            let mut builder = builder.description(msg);
 
             if let Some(footer) = footer {
                 builder = builder.footer(footer);
@@ -112,11 +153,20 @@ fn render_error_embeds(
     embeds: &mut Vec<Embed>,
     is_sentry_enabled: bool,
 ) {
-    let footer = if !error.get_category().is_user_error() && is_sentry_enabled {
+    // these IDs tie the error to its logs and Sentry event
+    let mut ids = Vec::new();
+    if let Some(trace_id) = TraceId::current() {
+        ids.push(format!("Trace ID: {trace_id}"));
+    }
+    if !error.get_category().is_user_error() && is_sentry_enabled {
         let sentry_event_id = eden_utils::sentry::capture_error_with_id(error);
-        Some(EmbedFooterBuilder::new(format!("Error ID: {sentry_event_id}")).build())
-    } else {
+        ids.push(format!("Error ID: {sentry_event_id}"));
+    }
+
+    let footer = if ids.is_empty() {
         None
+    } else {
+        Some(EmbedFooterBuilder::new(ids.join(" • ")).build())
     };
 
     // Output includes some of ANSI escape sequences since tracing_error
@@ -0,0 +1,314 @@
+//! Validation of command arguments after they're parsed.
+//!
+//! Commands declare constraints of their arguments with
+//! [`RunCommand::validate`] and every violated constraint is shown
+//! to the invoker at once in a single (localized) error embed.
+//!
+//! [`RunCommand::validate`]: crate::interactions::commands::RunCommand::validate
+use eden_discord_types::i18n;
+use eden_utils::error::{exts::*, UserErrorCategory};
+use eden_utils::{Error, ErrorCategory, Result};
+use serde::{ser::SerializeMap, Serialize};
+use std::fmt::Write as _;
+use thiserror::Error;
+use twilight_model::application::interaction::InteractionDataResolved;
+use twilight_model::channel::message::Embed;
+use twilight_model::channel::ChannelType;
+use twilight_model::id::marker::ChannelMarker;
+use twilight_model::id::Id;
+
+use super::embeds;
+
+/// Sample of durations accepted by [`parse_time_delta`].
+///
+/// [`parse_time_delta`]: eden_utils::time::parse_time_delta
+pub const DURATION_FORMAT: &str = "`30d`, `1w 2d` or `12h`";
+
+#[derive(Debug, Error)]
+#[error("user gave invalid arguments to the command {0:?}")]
+pub struct InvalidArguments(pub String);
+
+/// A constraint that an argument of the command does not satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The number must be greater than zero.
+    NotPositive,
+    /// The number must be within `min` and `max` (inclusive).
+    OutOfRange { min: i64, max: i64 },
+    /// The argument cannot be parsed. `expected` is a sample
+    /// of what the argument should look like.
+    Malformed { expected: &'static str },
+    /// The channel must be one of the listed channel types.
+    ChannelType { allowed: &'static [ChannelType] },
+}
+
+/// An argument that does not satisfy one of its constraints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub argument: &'static str,
+    pub kind: ViolationKind,
+}
+
+impl Violation {
+    /// Renders the violation in the locale (falls back to English).
+    fn render(&self, locale: Option<&str>) -> String {
+        let argument = self.argument;
+        match &self.kind {
+            ViolationKind::NotPositive => i18n::message(
+                locale,
+                "validation.not_positive",
+                "`{argument}` must be greater than zero",
+                &[("argument", argument)],
+            ),
+            ViolationKind::OutOfRange { min, max } => i18n::message(
+                locale,
+                "validation.out_of_range",
+                "`{argument}` must be between {min} and {max}",
+                &[
+                    ("argument", argument),
+                    ("min", &min.to_string()),
+                    ("max", &max.to_string()),
+                ],
+            ),
+            ViolationKind::Malformed { expected } => i18n::message(
+                locale,
+                "validation.malformed",
+                "`{argument}` is invalid (it should look like {expected})",
+                &[("argument", argument), ("expected", expected)],
+            ),
+            ViolationKind::ChannelType { allowed } => {
+                let allowed = allowed
+                    .iter()
+                    .map(|v| channel_type_name(*v))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                i18n::message(
+                    locale,
+                    "validation.channel_type",
+                    "`{argument}` must be a {allowed} channel",
+                    &[("argument", argument), ("allowed", &allowed)],
+                )
+            }
+        }
+    }
+}
+
+fn channel_type_name(kind: ChannelType) -> &'static str {
+    match kind {
+        ChannelType::GuildText => "text",
+        ChannelType::GuildVoice => "voice",
+        ChannelType::GuildCategory => "category",
+        ChannelType::GuildAnnouncement => "announcement",
+        ChannelType::GuildStageVoice => "stage",
+        ChannelType::GuildForum => "forum",
+        ChannelType::AnnouncementThread
+        | ChannelType::PublicThread
+        | ChannelType::PrivateThread => "thread",
+        _ => "other",
+    }
+}
+
+/// Collects every violated constraint of the command's arguments.
+#[derive(Debug)]
+pub struct Validator<'a> {
+    resolved: Option<&'a InteractionDataResolved>,
+    violations: Vec<Violation>,
+}
+
+impl<'a> Validator<'a> {
+    #[must_use]
+    pub fn new(resolved: Option<&'a InteractionDataResolved>) -> Self {
+        Self {
+            resolved,
+            violations: Vec::new(),
+        }
+    }
+
+    /// Adds a violation if `is_valid` is false.
+    pub fn check(&mut self, argument: &'static str, is_valid: bool, kind: ViolationKind) {
+        if !is_valid {
+            self.violations.push(Violation { argument, kind });
+        }
+    }
+
+    pub fn in_range(&mut self, argument: &'static str, value: i64, min: i64, max: i64) {
+        let kind = ViolationKind::OutOfRange { min, max };
+        self.check(argument, (min..=max).contains(&value), kind);
+    }
+
+    /// Checks whether the argument can be parsed with `parser`.
+    ///
+    /// It returns the parsed value so other constraints
+    /// can be checked against it.
+    pub fn parse<T>(
+        &mut self,
+        argument: &'static str,
+        input: &str,
+        parser: impl FnOnce(&str) -> Option<T>,
+        expected: &'static str,
+    ) -> Option<T> {
+        let value = parser(input);
+        self.check(
+            argument,
+            value.is_some(),
+            ViolationKind::Malformed { expected },
+        );
+        value
+    }
+
+    /// Checks whether the channel is one of the allowed channel types.
+    ///
+    /// Channels that are not resolved by Discord are ignored.
+    pub fn channel_type(
+        &mut self,
+        argument: &'static str,
+        channel_id: Id<ChannelMarker>,
+        allowed: &'static [ChannelType],
+    ) {
+        let kind = self
+            .resolved
+            .and_then(|v| v.channels.get(&channel_id))
+            .map(|v| v.kind);
+
+        if let Some(kind) = kind {
+            let is_valid = allowed.contains(&kind);
+            self.check(argument, is_valid, ViolationKind::ChannelType { allowed });
+        }
+    }
+
+    #[must_use]
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Fails with [`UserErrorCategory::InvalidArguments`] if any of
+    /// the constraints are violated.
+    pub fn finish(self, command: String, locale: Option<&str>) -> Result<()> {
+        if self.violations.is_empty() {
+            return Ok(());
+        }
+
+        let tag = InvalidArgumentsTag {
+            violations: self.violations,
+            locale: locale.map(String::from),
+        };
+
+        Err(Error::context_anonymize(
+            ErrorCategory::User(UserErrorCategory::InvalidArguments),
+            InvalidArguments(command),
+        ))
+        .attach(tag)
+    }
+}
+
+/// Violations of the command's arguments along with the
+/// invoker's locale to render them with.
+#[derive(Debug, Clone)]
+pub struct InvalidArgumentsTag {
+    pub violations: Vec<Violation>,
+    pub locale: Option<String>,
+}
+
+impl InvalidArgumentsTag {
+    /// Renders every violation in the invoker's language.
+    #[must_use]
+    pub fn render(&self) -> Embed {
+        let locale = self.locale.as_deref();
+        let title = i18n::message(locale, "validation.title", "Invalid arguments!", &[]);
+        let footer = i18n::message(
+            locale,
+            "validation.footer",
+            "Please fix these and try again.",
+            &[],
+        );
+
+        let mut description = String::new();
+        for violation in &self.violations {
+            let _ = writeln!(description, "- {}", violation.render(locale));
+        }
+        let _ = write!(description, "\n{footer}");
+
+        embeds::builders::error(&title, None)
+            .description(description)
+            .build()
+    }
+
+    pub(crate) fn install_hook() {
+        Error::install_serde_hook::<Self>();
+        Error::install_hook::<Self>(|this, ctx| {
+            for violation in &this.violations {
+                ctx.push_body(format!("{}: {:?}", violation.argument, violation.kind));
+            }
+        });
+    }
+}
+
+impl Serialize for InvalidArgumentsTag {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let arguments = self
+            .violations
+            .iter()
+            .map(|v| v.argument)
+            .collect::<Vec<_>>();
+
+        // this is to differentiate various attachments
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("_type", "INVALID_ARGUMENTS")?;
+        map.serialize_entry("arguments", &arguments)?;
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_collect_every_violation() {
+        let mut validator = Validator::new(None);
+        validator.check("amount", false, ViolationKind::NotPositive);
+        validator.check("price", true, ViolationKind::NotPositive);
+        validator.in_range("days", 91, 1, 90);
+        let parsed = validator.parse("time", "10pm", |v| v.parse::<u32>().ok(), "`22:00`");
+        assert_eq!(parsed, None);
+
+        let arguments = validator
+            .violations()
+            .iter()
+            .map(|v| v.argument)
+            .collect::<Vec<_>>();
+
+        assert_eq!(arguments, ["amount", "days", "time"]);
+        assert!(validator.finish("test".into(), None).is_err());
+    }
+
+    #[test]
+    fn should_pass_without_violations() {
+        let mut validator = Validator::new(None);
+        validator.in_range("amount", 10, 1, 100);
+        validator.channel_type("channel", Id::new(1), &[ChannelType::GuildText]);
+        assert!(validator.finish("test".into(), None).is_ok());
+    }
+
+    #[test]
+    fn should_render_in_invoker_language() {
+        let violation = Violation {
+            argument: "days",
+            kind: ViolationKind::OutOfRange { min: 1, max: 90 },
+        };
+
+        assert_eq!(
+            violation.render(Some("pt-BR")),
+            "`days` deve estar entre 1 e 90"
+        );
+        assert_eq!(
+            violation.render(Some("ja")),
+            "`days` must be between 1 and 90"
+        );
+        assert_eq!(violation.render(None), "`days` must be between 1 and 90");
+    }
+}
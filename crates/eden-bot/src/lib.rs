@@ -1,23 +1,26 @@
 #![feature(let_chains, new_uninit)]
 mod context;
+#[cfg(target_family = "unix")]
+mod control;
 mod events;
 mod flags;
 mod interactions;
 mod local_guild;
+mod startup;
 mod suggestions;
-#[cfg(test)]
-mod tests;
 
 pub mod errors;
 pub mod features;
 pub mod shard;
 pub mod tasks;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
 pub mod util;
 
-pub use self::context::{Bot, BotRef};
+pub use self::context::{Bot, BotPermissions, BotRef};
 
 use self::errors::{MigrateError, StartBotError};
-use eden_settings::Settings;
+use eden_settings::{Settings, SettingsWatcher};
 use eden_tasks::Scheduled;
 use eden_utils::{error::exts::*, shutdown::ShutdownMode, Result};
 use std::time::Duration;
@@ -26,15 +29,67 @@ use tokio::sync::Mutex;
 use tracing::{debug, info, trace, warn};
 
 #[tracing::instrument(skip_all, name = "start_bot")]
-pub async fn start(settings: Arc<Settings>) -> Result<(), StartBotError> {
+pub async fn start(settings: Arc<Settings>, watcher: SettingsWatcher) -> Result<(), StartBotError> {
     self::features::father_belt::install();
 
-    let bot = Bot::new(settings);
+    let bot = Bot::new(settings).change_context(StartBotError)?;
+
+    // Eden may start before the database does (like in docker-compose)
+    self::startup::wait_for_dependencies(&bot)
+        .await
+        .change_context(StartBotError)?;
+
     // Run migrations first before starting the bot process entirely
     perform_database_migrations(&bot)
         .await
         .change_context(StartBotError)?;
 
+    // followers may share the database with the leader instance
+    eden_utils::tokio::spawn(
+        "eden_bot::features::leader::run",
+        self::features::leader::run(bot.clone()),
+    );
+    if bot.settings.coordination.enabled {
+        let name = bot.leadership.name();
+        info!("coordinating with other instances as {name:?}");
+    }
+
+    // listen for events from other features before the shards start
+    eden_utils::tokio::spawn(
+        "eden_bot::features::father_belt::listen",
+        self::features::father_belt::listen(bot.clone(), bot.events.subscribe()),
+    );
+    eden_utils::tokio::spawn(
+        "eden_bot::features::outage::listen",
+        self::features::outage::listen(bot.clone(), bot.events.subscribe()),
+    );
+    eden_utils::tokio::spawn(
+        "eden_bot::features::payer_roles::listen",
+        self::features::payer_roles::listen(bot.clone()),
+    );
+    eden_utils::tokio::spawn(
+        "eden_bot::features::presence::rotate",
+        self::features::presence::rotate(bot.clone(), watcher.clone()),
+    );
+    eden_utils::tokio::spawn(
+        "eden_bot::features::task_events::forward",
+        self::features::task_events::forward(bot.clone(), bot.queue.subscribe_events()),
+    );
+    eden_utils::tokio::spawn(
+        "eden_bot::features::settings_reload::listen",
+        self::features::settings_reload::listen(bot.clone(), watcher),
+    );
+    #[cfg(target_family = "unix")]
+    eden_utils::tokio::spawn(
+        "eden_bot::control::serve",
+        self::control::serve(bot.clone()),
+    );
+
+    bot.shard_manager
+        .fetch_recommended(&bot)
+        .await
+        .change_context(StartBotError)?;
+
     bot.shard_manager.start_all();
 
     let bot_tx = bot.clone();
@@ -62,8 +117,14 @@ pub async fn start(settings: Arc<Settings>) -> Result<(), StartBotError> {
             return result;
         }
 
-        // register commands
-        if let Err(error) = crate::interactions::commands::register(&bot).await {
+        // register commands. followers register them once they're elected
+        let result = if bot.leadership.is_leader() {
+            crate::interactions::commands::register(&bot).await
+        } else {
+            debug!("skipping command registration since this instance is not the leader");
+            Ok(())
+        };
+        if let Err(error) = result {
             warn!(error = %error.anonymize(), "failed to register Eden commands. scheduling to register commands later");
 
             let result = bot
@@ -76,6 +137,12 @@ pub async fn start(settings: Arc<Settings>) -> Result<(), StartBotError> {
             }
         }
 
+        // feature gates and elections may change the commands after this point
+        eden_utils::tokio::spawn(
+            "eden_bot::interactions::commands::sync::listen",
+            crate::interactions::commands::sync::listen(bot.clone()),
+        );
+
         eden_utils::shutdown::graceful().await;
         bot.shard_manager.shutdown_all();
         bot.shard_manager
@@ -84,6 +151,9 @@ pub async fn start(settings: Arc<Settings>) -> Result<(), StartBotError> {
             })
             .await;
 
+        // let pending stateful commands know that Eden is shutting down
+        bot.command_state.shutdown().await;
+
         Ok::<_, eden_utils::Error<StartBotError>>(())
     });
 
@@ -113,6 +183,16 @@ async fn perform_database_migrations(bot: &Bot) -> Result<(), MigrateError> {
     info!("performing database migrations. this may take a while...");
 
     let now = Instant::now();
+    if let Some(schema) = bot.settings.database.schema.as_deref() {
+        // tables are created in the first schema of `search_path`
+        sqlx::query(&format!("CREATE SCHEMA IF NOT EXISTS {schema}"))
+            .execute(&bot.pool)
+            .await
+            .into_typed_error()
+            .change_context(MigrateError)
+            .attach_printable_lazy(|| format!("could not create schema {schema:?}"))?;
+    }
+
     eden_schema::MIGRATOR
         .run(&bot.pool)
         .await
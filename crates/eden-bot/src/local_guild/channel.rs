@@ -1,13 +1,21 @@
+use eden_schema::types::GuildSettings;
+use eden_settings::{AlertClass, AlertSeverity};
+use eden_tasks::TaskHealthEvent;
 use eden_utils::error::exts::ResultExt;
 use eden_utils::Result;
 use tracing::{debug, trace};
+use twilight_model::channel::message::Embed;
 use twilight_model::channel::ChannelType;
 use twilight_model::guild::{Guild, Permissions};
 use twilight_model::id::marker::{ChannelMarker, UserMarker};
 use twilight_model::id::Id;
 use twilight_util::permission_calculator::PermissionCalculator;
 
-use crate::errors::SendWelcomeMessageError;
+use crate::errors::{
+    SendAlertError, SendMemberLogError, SendServerLogError, SendWelcomeMessageError,
+};
+use crate::interactions::embeds;
+use crate::util::alerts::{Alert, AlertIncident, IncidentStatus};
 use crate::Bot;
 
 /// Attempts to find sendable channels for the bot to send a message with.
@@ -64,7 +72,7 @@ pub async fn send_welcome_message(bot: &Bot, guild: &Guild) -> Result<(), SendWe
 
             // Try to create a DM channel for the guild owner
             let dm_channel = crate::util::http::request_for_model(
-                &bot.http,
+                bot,
                 bot.http.create_private_channel(guild.owner_id),
             )
             .await
@@ -88,7 +96,7 @@ pub async fn send_welcome_message(bot: &Bot, guild: &Guild) -> Result<(), SendWe
         .content(MESSAGE)
         .expect("unexpected error while trying to set the message content");
 
-    crate::util::http::request_for_model(&bot.http, request)
+    crate::util::http::request_for_model(bot, request)
         .await
         .change_context(SendWelcomeMessageError)
         .attach_printable_lazy(|| format!("failed to send welcome message to channel {channel}"))
@@ -97,3 +105,115 @@ pub async fn send_welcome_message(bot: &Bot, guild: &Guild) -> Result<(), SendWe
     debug!("sent welcome message to channel {channel}");
     Ok(())
 }
+
+/// Sends a consolidated alert whenever a task type repeatedly
+/// fails or recovered from its failures.
+#[tracing::instrument(skip_all, fields(
+    task.kind = %event.kind(),
+    task.streak = %event.streak(),
+))]
+pub async fn send_task_health_alert(
+    bot: &Bot,
+    event: &TaskHealthEvent,
+) -> Result<(), SendAlertError> {
+    let (severity, embed) = match event {
+        TaskHealthEvent::Failing { kind, streak } => {
+            let embed = embeds::builders::error("Background task is failing", Some(chrono::Utc::now()))
+                .description(format!(
+                    "Task `{kind}` has failed **{streak}** time(s) in a row.\n\nPlease check the logs for more information. I will let you know once it recovers."
+                ))
+                .build();
+
+            (AlertSeverity::Warning, embed)
+        }
+        TaskHealthEvent::Recovered { kind, streak } => {
+            let embed = embeds::builders::success("Background task recovered")
+                .description(format!(
+                    "Task `{kind}` has completed successfully after failing **{streak}** time(s) in a row."
+                ))
+                .build();
+
+            (AlertSeverity::Info, embed)
+        }
+    };
+
+    // alerts of the task type are grouped until it recovers
+    let key = format!("task:{}", event.kind());
+    let title = format!("Failures of task `{}`", event.kind());
+    let status = match event {
+        TaskHealthEvent::Failing { .. } => IncidentStatus::Ongoing,
+        TaskHealthEvent::Recovered { .. } => IncidentStatus::Resolved,
+    };
+
+    let embeds = [embed];
+    let alert = Alert {
+        embeds: &embeds,
+        incident: Some(AlertIncident {
+            key: &key,
+            title: &title,
+            status,
+        }),
+        ..Alert::new(AlertClass::TaskFailures, severity)
+    };
+
+    debug!("sending task health alert");
+    bot.alerter.send(alert).await
+}
+
+/// Sends a log to the member log channel of the guild if it is configured.
+#[allow(clippy::expect_used)]
+#[tracing::instrument(skip_all)]
+pub async fn send_member_log(
+    bot: &Bot,
+    settings: &GuildSettings,
+    embed: Embed,
+) -> Result<(), SendMemberLogError> {
+    let Some(channel_id) = settings.logs.member_channel_id else {
+        trace!("member log channel is not configured, skipping");
+        return Ok(());
+    };
+
+    let embeds = [embed];
+    let request = bot
+        .http
+        .create_message(channel_id)
+        .embeds(&embeds)
+        .expect("unexpected error while trying to set the message embeds");
+
+    debug!("sending log to the member log channel");
+    crate::util::http::request_for_model(bot, request)
+        .await
+        .change_context(SendMemberLogError)
+        .attach_printable_lazy(|| format!("with member log channel: {channel_id}"))?;
+
+    Ok(())
+}
+
+/// Sends a log to the server log channel of the guild if it is configured.
+#[allow(clippy::expect_used)]
+#[tracing::instrument(skip_all)]
+pub async fn send_server_log(
+    bot: &Bot,
+    settings: &GuildSettings,
+    embed: Embed,
+) -> Result<(), SendServerLogError> {
+    let Some(channel_id) = settings.logs.server_channel_id else {
+        trace!("server log channel is not configured, skipping");
+        return Ok(());
+    };
+
+    let embeds = [embed];
+    let request = bot
+        .http
+        .create_message(channel_id)
+        .embeds(&embeds)
+        .expect("unexpected error while trying to set the message embeds");
+
+    debug!("sending log to the server log channel");
+    crate::util::http::request_for_model(bot, request)
+        .await
+        .change_context(SendServerLogError)
+        .attach_printable_lazy(|| format!("with server log channel: {channel_id}"))?;
+
+    Ok(())
+}
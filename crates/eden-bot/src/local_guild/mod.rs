@@ -38,7 +38,7 @@ pub async fn update_admins(bot: &Bot, guild: &Guild) -> Result<(), UpdateLocalGu
         }
 
         trace!(?after, "fetching batch of guild members");
-        let members = crate::util::http::request_for_list(&bot.http, request)
+        let members = crate::util::http::request_for_list(bot, request)
             .await
             .change_context(UpdateLocalGuildAdminsError)
             .attach_printable("failed to fetch all guild members")?;
@@ -129,10 +129,12 @@ pub async fn setup(bot: &Bot, guild: &Guild) -> Result<(), SetupLocalGuildError>
     }
 
     // Check if alert_channel_id exists, otherwise warn the user
-    let alert_channel_exists = guild
-        .channels
-        .iter()
-        .any(|v| v.id == bot.settings.bot.local_guild.alert_channel_id);
+    let alert_channel_id = settings
+        .alerts
+        .channel_id
+        .unwrap_or(bot.settings.bot.local_guild.alert_channel_id);
+
+    let alert_channel_exists = guild.channels.iter().any(|v| v.id == alert_channel_id);
 
     if !alert_channel_exists {
         warn!("Eden detects that your configured alert channel does not exists and it may not work as intended!\n\n{}", crate::suggestions::NO_ALERT_CHANNEL_ID.as_str());
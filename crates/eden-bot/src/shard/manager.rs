@@ -1,26 +1,42 @@
-use eden_settings::Settings;
+use eden_settings::{Settings, Sharding};
+use eden_utils::error::exts::*;
 use eden_utils::Result;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as Sender};
 use tokio::sync::Mutex;
-use tracing::{debug, info, trace};
-use twilight_gateway::queue::{LocalQueue, Queue};
+use tracing::{debug, info, trace, warn};
 use twilight_gateway::ShardId;
+use twilight_model::gateway::payload::outgoing::update_presence::UpdatePresencePayload;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
 
 use super::observer::{ShardObserver, ShardObserverMessage};
-use super::ShardHandle;
-use crate::BotRef;
+use super::{GatewayPayloadMetrics, IdentifyQueue, OutageDetector, ShardHandle};
+use crate::util::http::request_for_model;
+use crate::{Bot, BotRef};
+
+/// Gets the ID of the shard that receives events from a given guild.
+///
+/// Refer to Discord's documentation for the formula used at:
+/// https://discord.com/developers/docs/topics/gateway#sharding-sharding-formula
+fn shard_id_for_guild(guild_id: Id<GuildMarker>, total: u64) -> ShardId {
+    let id = (guild_id.get() >> 22) % total;
+    ShardId::new(id, total)
+}
 
 #[derive(Debug)]
 pub struct ShardManager {
     pub(crate) connected: AtomicU64,
-    pub(crate) queue: Arc<dyn Queue>,
+    pub(crate) queue: Arc<IdentifyQueue>,
     pub(crate) fatal_error: AtomicBool,
+    pub(crate) payload_metrics: GatewayPayloadMetrics,
+    pub(crate) outage: OutageDetector,
 
     observer: Sender<ShardObserverMessage>,
     notify_rx: Arc<Mutex<Receiver<ShardManagerNotification>>>,
+    presence: Mutex<UpdatePresencePayload>,
     shards: Arc<Mutex<HashMap<ShardId, ShardHandle>>>,
 
     /// First shard to initialize.
@@ -38,19 +54,24 @@ impl ShardManager {
         let (notify_tx, notify_rx) = mpsc::unbounded_channel();
         let notify_rx = Arc::new(Mutex::new(notify_rx));
 
+        let queue = IdentifyQueue::new(settings.bot.gateway.queue_url.as_deref());
         let shards = Arc::new(Mutex::new(HashMap::new()));
         let manager = Arc::new(Self {
             connected: AtomicU64::new(0),
-            queue: Arc::new(LocalQueue::new()),
+            queue: Arc::new(queue),
             fatal_error: AtomicBool::new(false),
+            payload_metrics: GatewayPayloadMetrics::default(),
+            outage: OutageDetector::default(),
 
             observer: observer_tx,
             notify_rx,
+            presence: Mutex::new(settings.bot.presence.to_payload()),
             shards: shards.clone(),
 
             first: AtomicU64::new(settings.bot.sharding.first()),
-            size: AtomicU64::new(settings.bot.sharding.size()),
-            total: AtomicU64::new(settings.bot.sharding.total()),
+            // resolved with `fetch_recommended` if sharding is set to `auto`
+            size: AtomicU64::new(settings.bot.sharding.size().unwrap_or_default()),
+            total: AtomicU64::new(settings.bot.sharding.total().unwrap_or_default()),
         });
 
         let observer = ShardObserver::new(
@@ -75,6 +96,18 @@ impl ShardManager {
         self.connected.load(Ordering::Relaxed)
     }
 
+    /// Gets the metrics of the payloads received from the gateway.
+    #[must_use]
+    pub fn payload_metrics(&self) -> &GatewayPayloadMetrics {
+        &self.payload_metrics
+    }
+
+    /// Gets the detector of Discord outages across all shards.
+    #[must_use]
+    pub fn outage(&self) -> &OutageDetector {
+        &self.outage
+    }
+
     #[must_use]
     pub fn has_fatal_error(&self) -> bool {
         self.fatal_error.load(Ordering::Relaxed)
@@ -86,6 +119,11 @@ impl ShardManager {
         self.total.load(Ordering::Relaxed)
     }
 
+    /// Gets the presence that shards will identify with.
+    pub async fn presence(&self) -> UpdatePresencePayload {
+        self.presence.lock().await.clone()
+    }
+
     /// Gets the [`ShardHandle`] from a given shard ID.
     ///
     /// Read more about [`ShardHandle`] to know the details of it.
@@ -100,6 +138,16 @@ impl ShardManager {
         self.shards.lock().await.values().cloned().collect()
     }
 
+    /// Gets the [`ShardHandle`] of the shard that receives events
+    /// from a given guild.
+    ///
+    /// It returns `None` if that shard is not initialized by this
+    /// instance of Eden (like if it is handled by other instances).
+    pub async fn shard_for_guild(&self, guild_id: Id<GuildMarker>) -> Option<ShardHandle> {
+        let total = self.total.load(Ordering::Relaxed);
+        self.shard(shard_id_for_guild(guild_id, total)).await
+    }
+
     /// Gets all initialized shards by their [shard ID](ShardId).
     pub async fn initialized_shards(&self) -> Vec<ShardId> {
         self.shards.lock().await.keys().copied().collect()
@@ -107,6 +155,42 @@ impl ShardManager {
 }
 
 impl ShardManager {
+    /// Asks Discord for the recommended amount of shards and how many
+    /// shards can identify at once if sharding is set to `auto`.
+    ///
+    /// It must be called before starting all shards.
+    #[tracing::instrument(skip_all)]
+    pub async fn fetch_recommended(&self, bot: &Bot) -> Result<()> {
+        if !matches!(bot.settings.bot.sharding, Sharding::Auto) {
+            return Ok(());
+        }
+
+        let info = request_for_model(bot, bot.http.gateway().authed())
+            .await
+            .attach_printable("could not get recommended sharding from Discord")?;
+
+        let limit = &info.session_start_limit;
+        let max_concurrency = limit.max_concurrency.max(1);
+        info!(
+            "using {} shard(s) recommended by Discord (max concurrency: {max_concurrency})",
+            info.shards
+        );
+
+        if u64::from(limit.remaining) < info.shards {
+            warn!(
+                "only {} session start(s) are remaining until {}ms later; some shards may fail to start",
+                limit.remaining, limit.reset_after
+            );
+        }
+
+        self.first.store(0, Ordering::Relaxed);
+        self.size.store(info.shards, Ordering::Relaxed);
+        self.total.store(info.shards, Ordering::Relaxed);
+        self.queue.set_max_concurrency(usize::from(max_concurrency));
+
+        Ok(())
+    }
+
     pub fn abort(&self) {
         drop(self.observer.send(ShardObserverMessage::Abort));
     }
@@ -126,6 +210,15 @@ impl ShardManager {
         }
     }
 
+    /// Replaces the presence of all shards including the shards
+    /// that will be started or restarted later.
+    pub async fn set_presence(&self, presence: UpdatePresencePayload) {
+        *self.presence.lock().await = presence.clone();
+        for shard in self.shards().await {
+            shard.replace_presence(presence.clone());
+        }
+    }
+
     pub fn shutdown_all(&self) {
         drop(self.observer.send(ShardObserverMessage::Shutdown));
     }
@@ -274,3 +367,15 @@ pub(crate) enum ShardManagerNotification {
         total: u64,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_shard_for_guild() {
+        let guild_id = Id::new(197_038_439_483_310_086);
+        assert_eq!(shard_id_for_guild(guild_id, 1), ShardId::new(0, 1));
+        assert_eq!(shard_id_for_guild(guild_id, 4), ShardId::new(2, 4));
+    }
+}
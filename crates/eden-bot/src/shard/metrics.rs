@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Keeps track of the (decompressed) sizes of every payload and
+/// the number of events received from the gateway across all shards
+/// or from a single shard.
+#[derive(Debug, Default)]
+pub struct GatewayPayloadMetrics {
+    events: AtomicU64,
+    payloads: AtomicU64,
+    total_bytes: AtomicU64,
+    largest_bytes: AtomicU64,
+}
+
+impl GatewayPayloadMetrics {
+    /// Total number of events received from the gateway excluding
+    /// payloads that Eden does not handle (like heartbeat ACKs).
+    #[must_use]
+    pub fn events(&self) -> u64 {
+        self.events.load(Ordering::Relaxed)
+    }
+
+    /// Total number of payloads received from the gateway.
+    #[must_use]
+    pub fn payloads(&self) -> u64 {
+        self.payloads.load(Ordering::Relaxed)
+    }
+
+    /// Total size of all payloads received from the gateway in bytes.
+    #[must_use]
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Size of the largest payload received from the gateway in bytes.
+    #[must_use]
+    pub fn largest_bytes(&self) -> u64 {
+        self.largest_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Average size of the payloads received from the gateway in bytes.
+    ///
+    /// It returns `0` if there are no payloads received yet.
+    #[must_use]
+    pub fn average_bytes(&self) -> u64 {
+        self.total_bytes()
+            .checked_div(self.payloads())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn record(&self, bytes: usize) {
+        let bytes = bytes as u64;
+        self.payloads.fetch_add(1, Ordering::Relaxed);
+        self.total_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.largest_bytes.fetch_max(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_event(&self) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_payload_sizes() {
+        let metrics = GatewayPayloadMetrics::default();
+        assert_eq!(metrics.average_bytes(), 0);
+
+        metrics.record(100);
+        metrics.record(300);
+        metrics.record_event();
+
+        assert_eq!(metrics.events(), 1);
+        assert_eq!(metrics.payloads(), 2);
+        assert_eq!(metrics.total_bytes(), 400);
+        assert_eq!(metrics.largest_bytes(), 300);
+        assert_eq!(metrics.average_bytes(), 200);
+    }
+}
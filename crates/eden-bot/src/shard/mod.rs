@@ -1,9 +1,15 @@
 // This sharding architecture is inspired from serenity.
 mod manager;
+mod metrics;
 mod observer;
+mod outage;
+mod queue;
 mod runner;
 
 pub use self::manager::ShardManager;
+pub use self::metrics::GatewayPayloadMetrics;
+pub use self::outage::{OutageDetector, OutageWindow};
+pub use self::queue::{BucketQueue, IdentifyQueue, RemoteQueue};
 pub use self::runner::ShardHandle;
 pub use twilight_model::gateway::presence::{
     Activity, ActivityAssets, ActivityButton, ActivityEmoji, ActivityFlags, ActivityParty,
@@ -115,13 +115,15 @@ impl ShardObserver {
 impl ShardObserver {
     async fn start(&mut self, id: ShardId) {
         let token = self.settings.bot.token.expose().to_string();
+        let presence = self.manager.presence().await;
         let config = twilight_gateway::Config::builder(token, flags::INTENTS)
             .event_types(flags::FILTERED_EVENT_TYPES)
+            .large_threshold(self.settings.bot.gateway.large_threshold)
+            .presence(presence.clone())
             .queue(self.manager.queue.clone())
             .build();
 
         let shard = Shard::with_config(id, config);
-        let presence = self.settings.bot.presence.clone();
         let (runner, handle) = ShardRunner::new(
             self.bot.clone(),
             self.manager.clone(),
@@ -0,0 +1,183 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::sync::atomic::{AtomicI64, AtomicU32, Ordering};
+use tracing::{info, warn};
+
+/// How many consecutive gateway and HTTP failures across all shards
+/// are needed before Eden assumes that Discord is having an outage.
+pub const FAILURE_THRESHOLD: u32 = 5;
+
+/// Timestamp (in milliseconds) stored if there's no ongoing outage.
+const NO_OUTAGE: i64 = i64::MIN;
+
+/// Period of time where Discord is assumed to be having an outage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutageWindow {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl OutageWindow {
+    #[must_use]
+    pub fn duration(&self) -> TimeDelta {
+        self.ended_at - self.started_at
+    }
+}
+
+/// Detects Discord outages from consecutive gateway and HTTP
+/// failures across all shards.
+///
+/// While an outage is ongoing, Eden stays in a quiet degraded state
+/// where expected failures are not logged as warnings and every
+/// Sentry event is tagged with when the outage started.
+#[derive(Debug)]
+pub struct OutageDetector {
+    consecutive_failures: AtomicU32,
+    started_at: AtomicI64,
+    threshold: u32,
+}
+
+impl OutageDetector {
+    #[must_use]
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            consecutive_failures: AtomicU32::new(0),
+            started_at: AtomicI64::new(NO_OUTAGE),
+            threshold,
+        }
+    }
+
+    /// Whether Discord is assumed to be having an outage.
+    #[must_use]
+    pub fn is_degraded(&self) -> bool {
+        self.started_at().is_some()
+    }
+
+    /// Gets when the ongoing outage started.
+    #[must_use]
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        match self.started_at.load(Ordering::Relaxed) {
+            NO_OUTAGE => None,
+            millis => DateTime::from_timestamp_millis(millis),
+        }
+    }
+
+    /// Records a failed gateway or HTTP request.
+    ///
+    /// It returns `true` if this failure started an outage.
+    pub fn record_failure(&self, now: DateTime<Utc>) -> bool {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures < self.threshold {
+            return false;
+        }
+
+        let started = self
+            .started_at
+            .compare_exchange(
+                NO_OUTAGE,
+                now.timestamp_millis(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok();
+
+        if started {
+            warn!("got {failures} consecutive Discord failures. Discord may be having an outage");
+            // outages affect the entire process, not only the thread
+            // that happens to record this failure
+            sentry::Hub::main().configure_scope(|scope| {
+                scope.set_tag("outage", "true");
+                scope.set_tag("outage.started_at", now.to_rfc3339());
+            });
+        }
+        started
+    }
+
+    /// Records a successful gateway or HTTP request.
+    ///
+    /// It returns the outage window if this success ended an outage.
+    pub fn record_success(&self, now: DateTime<Utc>) -> Option<OutageWindow> {
+        // avoid contention since this is called for every gateway event
+        if self.consecutive_failures.load(Ordering::Relaxed) == 0 && !self.is_degraded() {
+            return None;
+        }
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+
+        let started_at = match self.started_at.swap(NO_OUTAGE, Ordering::Relaxed) {
+            NO_OUTAGE => return None,
+            millis => DateTime::from_timestamp_millis(millis)?,
+        };
+
+        let window = OutageWindow {
+            started_at,
+            ended_at: now,
+        };
+        info!(duration = ?window.duration(), "Discord has recovered from an outage");
+        sentry::Hub::main().configure_scope(|scope| {
+            scope.remove_tag("outage");
+            scope.remove_tag("outage.started_at");
+        });
+
+        Some(window)
+    }
+}
+
+impl Default for OutageDetector {
+    fn default() -> Self {
+        Self::new(FAILURE_THRESHOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_detect_outages_after_threshold() {
+        let detector = OutageDetector::new(3);
+        let now = Utc::now();
+
+        assert!(!detector.record_failure(now));
+        assert!(!detector.record_failure(now));
+        assert!(!detector.is_degraded());
+
+        assert!(detector.record_failure(now));
+        assert!(!detector.record_failure(now));
+        assert_eq!(
+            detector.started_at(),
+            DateTime::from_timestamp_millis(now.timestamp_millis())
+        );
+    }
+
+    #[test]
+    fn should_reset_failures_on_success() {
+        let detector = OutageDetector::new(2);
+        let now = Utc::now();
+
+        assert!(!detector.record_failure(now));
+        assert_eq!(detector.record_success(now), None);
+        assert!(!detector.record_failure(now));
+        assert!(!detector.is_degraded());
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn should_report_window_once_recovered() {
+        let detector = OutageDetector::new(1);
+        let started_at = DateTime::from_timestamp_millis(Utc::now().timestamp_millis()).unwrap();
+        let ended_at = started_at + TimeDelta::minutes(10);
+
+        assert!(detector.record_failure(started_at));
+
+        let window = detector.record_success(ended_at);
+        assert_eq!(
+            window,
+            Some(OutageWindow {
+                started_at,
+                ended_at
+            })
+        );
+        assert_eq!(window.map(|v| v.duration()), Some(TimeDelta::minutes(10)));
+        assert_eq!(detector.record_success(ended_at), None);
+        assert!(!detector.is_degraded());
+    }
+}
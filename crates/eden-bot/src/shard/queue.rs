@@ -0,0 +1,226 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tracing::warn;
+use twilight_gateway::queue::Queue;
+use url::Url;
+
+/// How long a shard has to wait after another shard from the same
+/// bucket has identified. Discord allows one identify per bucket every
+/// 5 seconds, so a second is added to spare.
+const IDENTIFY_INTERVAL: Duration = Duration::from_secs(6);
+
+/// How long a shard has to wait before asking the remote queue again
+/// after the remote queue could not be reached.
+const REMOTE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Queue that every shard of this instance of Eden waits on
+/// before identifying with the Discord gateway.
+#[derive(Debug)]
+pub enum IdentifyQueue {
+    Local(BucketQueue),
+    Remote(RemoteQueue),
+}
+
+impl IdentifyQueue {
+    /// Uses the remote queue at `queue_url` if it is set and valid,
+    /// otherwise it uses a queue local to this instance.
+    #[must_use]
+    pub fn new(queue_url: Option<&str>) -> Self {
+        let Some(queue_url) = queue_url else {
+            return Self::Local(BucketQueue::new(1));
+        };
+
+        match RemoteQueue::new(queue_url) {
+            Ok(queue) => Self::Remote(queue),
+            Err(error) => {
+                warn!(%error, "`bot.gateway.queue_url` is invalid; using local identify queue");
+                Self::Local(BucketQueue::new(1))
+            }
+        }
+    }
+
+    /// Sets how many shards can identify at once. It does nothing
+    /// for remote queues since they are the ones keeping track of it.
+    pub fn set_max_concurrency(&self, max_concurrency: usize) {
+        if let Self::Local(queue) = self {
+            queue.set_max_concurrency(max_concurrency);
+        }
+    }
+}
+
+impl Queue for IdentifyQueue {
+    fn request<'a>(&'a self, shard_id: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        match self {
+            Self::Local(queue) => queue.request(shard_id),
+            Self::Remote(queue) => queue.request(shard_id),
+        }
+    }
+}
+
+/// Queue of shards waiting to identify with the Discord gateway.
+///
+/// Shards are put into buckets by `shard_id % max_concurrency` and each
+/// bucket lets one shard identify at a time, so bots allowed to identify
+/// multiple shards at once (large bots) can start their shards faster.
+///
+/// Refer to Discord's documentation for more details at:
+/// https://discord.com/developers/docs/topics/gateway#sharding-max-concurrency
+#[derive(Debug)]
+pub struct BucketQueue {
+    buckets: RwLock<Vec<Arc<Mutex<Option<Instant>>>>>,
+}
+
+impl BucketQueue {
+    #[must_use]
+    pub fn new(max_concurrency: usize) -> Self {
+        let queue = Self {
+            buckets: RwLock::new(Vec::new()),
+        };
+        queue.set_max_concurrency(max_concurrency);
+        queue
+    }
+
+    /// Replaces the buckets with `max_concurrency` buckets. It is
+    /// expected to be called before any shard has started.
+    #[allow(clippy::unwrap_used)]
+    pub fn set_max_concurrency(&self, max_concurrency: usize) {
+        let buckets = (0..max_concurrency.max(1))
+            .map(|_| Arc::new(Mutex::new(None)))
+            .collect();
+
+        *self.buckets.write().unwrap() = buckets;
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[must_use]
+    pub fn max_concurrency(&self) -> usize {
+        self.buckets.read().unwrap().len()
+    }
+
+    #[allow(clippy::unwrap_used, clippy::cast_possible_truncation)]
+    fn bucket(&self, shard_id: u64) -> Arc<Mutex<Option<Instant>>> {
+        let buckets = self.buckets.read().unwrap();
+        let index = (shard_id % buckets.len() as u64) as usize;
+        buckets[index].clone()
+    }
+}
+
+impl Queue for BucketQueue {
+    fn request<'a>(&'a self, [id, _]: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        let bucket = self.bucket(id);
+        Box::pin(async move {
+            // the bucket stays locked until the shard can identify
+            let mut last_identified = bucket.lock().await;
+            if let Some(last_identified) = *last_identified {
+                tokio::time::sleep_until(last_identified + IDENTIFY_INTERVAL).await;
+            }
+            *last_identified = Some(Instant::now());
+        })
+    }
+}
+
+/// Queue shared by multiple instances of Eden through an HTTP server
+/// compatible with twilight's gateway queue, so instances sharding
+/// across processes don't identify more shards than Discord allows.
+///
+/// The server responds to `GET {url}?shard={id}` once the shard
+/// is allowed to identify.
+#[derive(Debug)]
+pub struct RemoteQueue {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl RemoteQueue {
+    pub fn new(url: &str) -> Result<Self, url::ParseError> {
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url: Url::parse(url)?,
+        })
+    }
+
+    fn request_url(&self, shard_id: u64) -> Url {
+        let mut url = self.url.clone();
+        url.query_pairs_mut()
+            .append_pair("shard", &shard_id.to_string());
+        url
+    }
+}
+
+impl Queue for RemoteQueue {
+    fn request<'a>(&'a self, [id, _]: [u64; 2]) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        let url = self.request_url(id);
+        Box::pin(async move {
+            // identifying without the queue's permission may get every
+            // instance sharing the queue rate limited by Discord
+            loop {
+                let result = self
+                    .client
+                    .get(url.clone())
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status);
+
+                match result {
+                    Ok(..) => break,
+                    Err(error) => {
+                        warn!(%error, "could not request remote identify queue for shard {id}");
+                        tokio::time::sleep(REMOTE_RETRY_INTERVAL).await;
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_put_shards_into_buckets() {
+        let queue = BucketQueue::new(2);
+        assert!(Arc::ptr_eq(&queue.bucket(0), &queue.bucket(2)));
+        assert!(Arc::ptr_eq(&queue.bucket(1), &queue.bucket(3)));
+        assert!(!Arc::ptr_eq(&queue.bucket(0), &queue.bucket(1)));
+    }
+
+    #[tokio::test]
+    async fn should_identify_buckets_concurrently() {
+        let queue = BucketQueue::new(2);
+        let started = Instant::now();
+
+        // shards 0 and 1 are in different buckets
+        queue.request([0, 2]).await;
+        queue.request([1, 2]).await;
+        assert!(started.elapsed() < IDENTIFY_INTERVAL);
+    }
+
+    #[test]
+    fn should_request_remote_queue_with_shard_id() {
+        let queue = RemoteQueue::new("http://localhost:7000").unwrap();
+        assert_eq!(
+            queue.request_url(3).as_str(),
+            "http://localhost:7000/?shard=3"
+        );
+
+        assert!(RemoteQueue::new("localhost:7000/queue").is_err());
+        assert!(matches!(
+            IdentifyQueue::new(Some("not a url")),
+            IdentifyQueue::Local(..)
+        ));
+    }
+
+    #[test]
+    fn should_have_at_least_one_bucket() {
+        let queue = BucketQueue::new(0);
+        assert_eq!(queue.max_concurrency(), 1);
+
+        queue.set_max_concurrency(16);
+        assert_eq!(queue.max_concurrency(), 16);
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::Utc;
 use eden_utils::error::exts::{AnyErrorExt, ErrorExt};
 use eden_utils::{Error, ErrorCategory};
 use std::sync::Arc;
@@ -6,15 +7,18 @@ use tokio::sync::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as
 use tokio::sync::{Mutex, MutexGuard};
 use tokio_util::task::TaskTracker;
 use tracing::{debug, trace, warn, Instrument, Span};
-use twilight_gateway::error::ReceiveMessageErrorType;
-use twilight_gateway::{CloseFrame, ConnectionStatus, Event, EventType, Latency, Shard, ShardId};
+use twilight_gateway::error::{ReceiveMessageError, ReceiveMessageErrorType};
+use twilight_gateway::{
+    CloseFrame, ConnectionStatus, Event, EventType, Latency, Message, Shard, ShardId,
+};
 use twilight_model::gateway::payload::outgoing::update_presence::UpdatePresencePayload;
 use twilight_model::gateway::payload::outgoing::UpdatePresence;
 use twilight_model::gateway::presence::{Activity, Status};
 
 use super::observer::ShardNotification;
-use super::{PresenceData, ShardManager};
+use super::{GatewayPayloadMetrics, PresenceData, ShardManager};
 use crate::events::EventContext;
+use crate::features::FeatureEvent;
 use crate::BotRef;
 
 pub struct ShardRunner {
@@ -28,6 +32,7 @@ pub struct ShardRunner {
 
     ///////////////////////////////////////////////
     id: ShardId,
+    has_connected: bool,
     presence: UpdatePresencePayload,
     last_status: ConnectionStatus,
     shard: Shard,
@@ -40,7 +45,7 @@ impl ShardRunner {
         bot: BotRef,
         manager: Arc<ShardManager>,
         observer: Sender<ShardNotification>,
-        presence: Option<UpdatePresencePayload>,
+        presence: UpdatePresencePayload,
         shard: Shard,
     ) -> (Self, ShardHandle) {
         let (tx, rx) = mpsc::unbounded_channel();
@@ -48,6 +53,7 @@ impl ShardRunner {
         let handle = ShardHandle {
             id: shard.id(),
             latency: Arc::new(Mutex::new(shard.latency().clone())),
+            metrics: Arc::new(GatewayPayloadMetrics::default()),
             runner_tx: tx,
             status: Arc::new(Mutex::new(shard.status().clone())),
         };
@@ -61,8 +67,9 @@ impl ShardRunner {
             tasks: TaskTracker::new(),
 
             id: shard.id(),
+            has_connected: false,
             last_status: shard.status().clone(),
-            presence: presence.unwrap_or_else(|| PresenceData::default().into()),
+            presence,
             shard,
         };
 
@@ -116,16 +123,23 @@ impl ShardRunner {
             let bot = self.bot.get();
             if matches!(event.kind(), EventType::Ready | EventType::Resumed) {
                 debug!("shard {} is ready", self.id);
+                if self.has_connected {
+                    bot.metrics.record_shard_reconnect();
+                }
+                self.has_connected = true;
+
                 if let Err(error) = self.observer.send(ShardNotification::Connected(self.id)) {
                     warn!(%error, "could not notify shard observer that the shard {} is connected to the gateway", self.id);
                 }
                 // update their presence while it is ready
                 self.update_presence().await;
+                bot.events.publish(FeatureEvent::ShardReady(self.id));
             }
 
             if let Event::Ready(data) = &event {
                 bot.override_application_id(data.application.id);
             }
+            bot.cache.update(&event);
             trace!("received event {:?}", event.kind());
 
             let span = Span::current();
@@ -142,13 +156,22 @@ impl ShardRunner {
     async fn next_action(&mut self) -> ShardAction {
         use futures::future::{select, Either::*};
 
-        let next_event = Box::pin(self.shard.next_event());
+        let metrics = [&self.manager.payload_metrics, &*self.handle.metrics];
+        let next_event = Box::pin(next_event(&mut self.shard, metrics));
         let runner = Box::pin(self.runner_rx.recv());
 
         match select(next_event, runner).await {
-            Left((Ok(event), ..)) => ShardAction::NewEvent(event),
+            Left((Ok(event), ..)) => {
+                if let Some(window) = self.manager.outage.record_success(Utc::now()) {
+                    self.bot
+                        .get()
+                        .events
+                        .publish(FeatureEvent::OutageRecovered(window));
+                }
+                ShardAction::NewEvent(event)
+            }
             Left((Err(source), ..)) => {
-                log_shard_error!(source);
+                log_shard_error!(source, self.manager.outage.is_degraded());
                 if source.is_fatal() {
                     self.manager.shutdown_all();
 
@@ -159,6 +182,7 @@ impl ShardRunner {
 
                     ShardAction::Shutdown(ShutdownReason::FatalError(source))
                 } else {
+                    self.manager.outage.record_failure(Utc::now());
                     ShardAction::Continue
                 }
             }
@@ -179,6 +203,10 @@ impl ShardRunner {
                 self.presence.status = presence.status;
                 ShardAction::UpdatePresence
             }
+            Right((Some(ShardRunnerMessage::ReplacePresence(presence)), ..)) => {
+                self.presence = presence;
+                ShardAction::UpdatePresence
+            }
             Right((Some(ShardRunnerMessage::SetStatus(status)), ..)) => {
                 self.presence.status = status;
                 ShardAction::UpdatePresence
@@ -253,6 +281,30 @@ impl ShardRunner {
     }
 }
 
+/// Receives the next event from the gateway like [`Shard::next_event`]
+/// but it records the size of every payload and every event received
+/// to all of the given metrics.
+async fn next_event(
+    shard: &mut Shard,
+    metrics: [&GatewayPayloadMetrics; 2],
+) -> Result<Event, ReceiveMessageError> {
+    loop {
+        match shard.next_message().await? {
+            Message::Close(frame) => return Ok(Event::GatewayClose(frame)),
+            Message::Text(json) => {
+                metrics.iter().for_each(|v| v.record(json.len()));
+                trace!(payload.size = json.len(), "received gateway payload");
+
+                let event_types = shard.config().event_types();
+                if let Some(event) = twilight_gateway::parse(json, event_types)? {
+                    metrics.iter().for_each(|v| v.record_event());
+                    return Ok(event.into());
+                }
+            }
+        }
+    }
+}
+
 impl ShardRunner {
     async fn handle_new_status(&self, status: &ConnectionStatus) {
         if !status.is_disconnected() {
@@ -279,8 +331,14 @@ impl ShardRunner {
 
 macro_rules! log_shard_error {
     ($source:expr) => {
+        log_shard_error!($source, false)
+    };
+    ($source:expr, $is_degraded:expr) => {
         if $source.is_fatal() {
             tracing::error!(error = %$source, "got shard fatal error");
+        } else if $is_degraded {
+            // Discord is having an outage, we don't want to flood the logs
+            tracing::debug!(error = %$source, "got shard error");
         } else {
             tracing::warn!(error = %$source, "got shard error");
         }
@@ -299,6 +357,7 @@ pub struct ShardHandle {
     status: Arc<Mutex<ConnectionStatus>>,
 
     pub(super) latency: Arc<Mutex<Latency>>,
+    pub(super) metrics: Arc<GatewayPayloadMetrics>,
     pub(super) runner_tx: Sender<ShardRunnerMessage>,
 }
 
@@ -321,6 +380,12 @@ impl ShardHandle {
         self.status.lock().await.clone()
     }
 
+    /// Payloads and events received by this shard only.
+    #[must_use]
+    pub fn metrics(&self) -> &GatewayPayloadMetrics {
+        &self.metrics
+    }
+
     pub fn abort(&self) {
         self.send_to_shard(ShardRunnerMessage::Abort);
     }
@@ -336,6 +401,11 @@ impl ShardHandle {
         self.send_to_shard(ShardRunnerMessage::SetPresence(presence));
     }
 
+    /// Replaces the presence entirely.
+    pub fn replace_presence(&self, presence: UpdatePresencePayload) {
+        self.send_to_shard(ShardRunnerMessage::ReplacePresence(presence));
+    }
+
     pub fn set_status(&self, mut status: Status) {
         if status == Status::Offline {
             status = Status::Invisible;
@@ -355,6 +425,22 @@ impl ShardHandle {
     }
 }
 
+#[cfg(any(test, feature = "testkit"))]
+impl ShardHandle {
+    /// Creates a handle of a shard that is never run, so messages
+    /// sent to it are dropped.
+    pub fn detached(shard: &Shard) -> Self {
+        let (runner_tx, _) = mpsc::unbounded_channel();
+        Self {
+            id: shard.id(),
+            latency: Arc::new(Mutex::new(shard.latency().clone())),
+            metrics: Arc::new(GatewayPayloadMetrics::default()),
+            runner_tx,
+            status: Arc::new(Mutex::new(shard.status().clone())),
+        }
+    }
+}
+
 /// Messages that can be sent from the shard manager to a shard.
 #[derive(Debug)]
 pub enum ShardRunnerMessage {
@@ -369,6 +455,9 @@ pub enum ShardRunnerMessage {
     SetActivites(Vec<Activity>),
     /// Indicates request to a shard to update their presence entirely.
     SetPresence(PresenceData),
+    /// Indicates request to a shard to replace their presence with
+    /// the payload.
+    ReplacePresence(UpdatePresencePayload),
     /// Indicates request to a shard to change their presence status.
     SetStatus(Status),
 }
@@ -380,6 +469,7 @@ impl ShardRunnerMessage {
             Self::Shutdown => "shutdown",
             Self::SetActivites(..) => "set_activity",
             Self::SetPresence(..) => "set_presence",
+            Self::ReplacePresence(..) => "replace_presence",
             Self::SetStatus(..) => "set_status",
         }
     }
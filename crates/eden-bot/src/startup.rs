@@ -0,0 +1,115 @@
+use eden_utils::error::exts::*;
+use eden_utils::{Error, ErrorCategory, Result};
+use sqlx::{ConnectOptions, Connection};
+use std::result::Result as StdResult;
+use std::time::Instant;
+use tracing::{debug, info, warn};
+use twilight_http::error::ErrorType;
+
+use crate::errors::WaitForDependenciesError;
+use crate::Bot;
+
+/// Service that Eden needs to be available before it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dependency {
+    Database,
+    DiscordApi,
+}
+
+impl Dependency {
+    /// Every dependency in the order they're waited for.
+    const ALL: &'static [Self] = &[Self::Database, Self::DiscordApi];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Database => "database",
+            Self::DiscordApi => "Discord API",
+        }
+    }
+
+    /// Checks once whether the dependency is available.
+    /// It returns why if it is not available.
+    async fn check(self, bot: &Bot) -> StdResult<(), String> {
+        match self {
+            Self::Database => {
+                let options = bot.settings.database.as_postgres_connect_options();
+                let conn = options.connect().await.map_err(|v| v.to_string())?;
+                if let Err(error) = conn.close().await {
+                    debug!(%error, "could not close database connection gracefully");
+                }
+                Ok(())
+            }
+            Self::DiscordApi => match bot.http.gateway().await {
+                Ok(..) => Ok(()),
+                // Discord has responded, so it is reachable anyway
+                Err(error) if matches!(error.kind(), ErrorType::Response { .. }) => Ok(()),
+                Err(error) => Err(error.to_string()),
+            },
+        }
+    }
+}
+
+/// Waits for the database to accept connections and for the Discord
+/// API to be reachable before Eden runs migrations and connects shards.
+///
+/// It fails if any of them is still not available after
+/// `startup.timeout` has elapsed.
+#[tracing::instrument(skip_all)]
+pub async fn wait_for_dependencies(bot: &Bot) -> Result<(), WaitForDependenciesError> {
+    let settings = &bot.settings.startup;
+    if !settings.wait_for_dependencies {
+        debug!("startup gate is turned off, not waiting for dependencies");
+        return Ok(());
+    }
+
+    let started_at = Instant::now();
+    let deadline = started_at + settings.timeout.get();
+    for dependency in Dependency::ALL.iter().copied() {
+        wait_for(bot, dependency, started_at, deadline).await?;
+    }
+
+    debug!(elapsed = ?started_at.elapsed(), "every dependency is available");
+    Ok(())
+}
+
+async fn wait_for(
+    bot: &Bot,
+    dependency: Dependency,
+    started_at: Instant,
+    deadline: Instant,
+) -> Result<(), WaitForDependenciesError> {
+    let interval = bot.settings.startup.retry_interval.get();
+    let name = dependency.name();
+
+    let mut attempts = 1;
+    loop {
+        let check = dependency.check(bot);
+        let reason = match tokio::time::timeout_at(deadline.into(), check).await {
+            Ok(Ok(())) if attempts > 1 => {
+                info!("{name} is now available after {attempts} attempt(s)");
+                return Ok(());
+            }
+            Ok(Ok(())) => {
+                debug!("{name} is available");
+                return Ok(());
+            }
+            Ok(Err(reason)) => reason,
+            Err(..) => "timed out".into(),
+        };
+
+        let elapsed = started_at.elapsed();
+        if Instant::now() + interval >= deadline {
+            warn!("gave up waiting for {name} after {elapsed:.0?}: {reason}");
+            return Err(Error::context(
+                ErrorCategory::Unknown,
+                WaitForDependenciesError,
+            ))
+            .attach_printable(format!("{name} is not available: {reason}"))
+            .attach_printable(format!("waited for {elapsed:.0?} ({attempts} attempt(s))"));
+        }
+
+        info!("waiting for {name} (attempt {attempts}, {elapsed:.0?} elapsed): {reason}");
+        tokio::time::sleep(interval).await;
+        attempts += 1;
+    }
+}
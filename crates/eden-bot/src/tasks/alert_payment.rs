@@ -1,4 +1,5 @@
 use eden_discord_types::choices::PaymentMethodOption;
+use eden_settings::{AlertClass, AlertSeverity};
 use eden_tasks::prelude::*;
 use eden_utils::{
     error::exts::{IntoTypedError, ResultExt},
@@ -17,6 +18,7 @@ use twilight_model::{
     },
 };
 
+use crate::util::alerts::Alert;
 use crate::{util::http::request_for_model, BotRef};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -51,34 +53,34 @@ impl Task for AlertPayment {
         let filename = format!("payment_for_{}.{}", self.biller_id, self.payment_image_ext);
         let attachments = vec![Attachment::from_bytes(filename, data.into(), 1)];
 
-        trace!("relying payment image to the alert channel");
+        trace!("relying payment image to the admins");
 
-        let alert_channel_id = bot.settings.bot.local_guild.alert_channel_id;
         let content = format!(
             "**{}'s payment with {:?} as their payment method**",
             self.biller_id.mention(),
             self.payment_method
         );
-        let request = bot
-            .http
-            .create_message(alert_channel_id)
-            .attachments(&attachments)
-            .unwrap()
-            .content(&content)
-            .unwrap();
-
-        let result = request_for_model(&bot.http, request)
-            .await
-            .attach_printable("failed to send message to the alert channel");
+        let alert = Alert {
+            content: Some(&content),
+            attachments: &attachments,
+            ..Alert::new(AlertClass::Billing, AlertSeverity::Info)
+        };
+
+        let result = bot.alerter.send(alert).await;
+
+        // retryable errors like outages will be retried at a later time
+        let is_fatal = result
+            .discord_http_error_info()
+            .is_some_and(|info| !info.is_retryable());
 
-        if result.discord_http_error_info().is_some() {
+        if is_fatal {
             let request = bot
                 .http
                 .create_message(self.biller_dm_channel_id)
                 .content(OOPS_MSG)
                 .unwrap();
 
-            request_for_model(&bot.http, request)
+            request_for_model(&bot, request)
                 .await
                 .attach_printable("failed to send error message to the biller")?;
 
@@ -0,0 +1,30 @@
+use chrono::Utc;
+use eden_tasks::prelude::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::features::quiet_hours;
+use crate::BotRef;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ApplyQuietHours;
+
+#[async_trait]
+impl Task for ApplyQuietHours {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+        quiet_hours::apply(&bot, Utc::now()).await?;
+        Ok(TaskResult::Completed)
+    }
+
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::interval(TimeDelta::minutes(1))
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::apply_quiet_hours"
+    }
+}
@@ -0,0 +1,37 @@
+use eden_tasks::prelude::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::BotRef;
+
+/// Gives the configured auto roles to a new member after
+/// the configured delay has passed.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AssignAutoRoles {
+    pub guild_id: Id<GuildMarker>,
+    pub user_id: Id<UserMarker>,
+}
+
+#[async_trait]
+impl Task for AssignAutoRoles {
+    type State = BotRef;
+
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+
+        let settings = bot.guild_settings(self.guild_id).await?;
+
+        crate::features::auto_role::assign(&bot, self.guild_id, self.user_id, &settings).await?;
+        Ok(TaskResult::Completed)
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::assign_auto_roles"
+    }
+
+    fn priority() -> TaskPriority {
+        TaskPriority::Low
+    }
+}
@@ -0,0 +1,40 @@
+use eden_tasks::prelude::*;
+use eden_tasks::Scheduled;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::features::bulk_role::BulkRoleOperation;
+use crate::BotRef;
+
+/// Adds or removes a role from many members at once
+/// requested from `/admin roles bulk`.
+///
+/// Members are processed in batches, each batch in a run of its own.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PerformBulkRoleOperation {
+    pub operation: BulkRoleOperation,
+}
+
+#[async_trait]
+impl Task for PerformBulkRoleOperation {
+    type State = BotRef;
+
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+        let next = crate::features::bulk_role::perform(&bot, &self.operation).await?;
+        if let Some(operation) = next {
+            let task = PerformBulkRoleOperation { operation };
+            bot.queue.schedule(task, Scheduled::now()).await?;
+        }
+
+        Ok(TaskResult::Completed)
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::perform_bulk_role_operation"
+    }
+
+    fn priority() -> TaskPriority {
+        TaskPriority::Low
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::Utc;
+use eden_schema::types::CommandCooldown;
+use eden_tasks::prelude::*;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::BotRef;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ClearExpiredCooldowns;
+
+#[async_trait]
+impl Task for ClearExpiredCooldowns {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+        let now = Utc::now();
+        bot.cooldowns.clear_expired(now);
+
+        let mut conn = bot.db_write().await?;
+        let deleted = CommandCooldown::delete_expired(&mut conn, now).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        debug!("deleted {deleted} expired command cooldown(s)");
+        Ok(TaskResult::Completed)
+    }
+
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::interval(TimeDelta::hours(1))
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::clear_expired_cooldowns"
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::BotRef;
 
+/// Clears stateful command interactions that have been inactive
+/// for longer than `bot.commands.inactivity_timeout`.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ClearInactiveInteractionStates;
 
@@ -14,7 +16,8 @@ impl Task for ClearInactiveInteractionStates {
     #[tracing::instrument(skip_all)]
     async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
         let bot = state.get();
-        bot.command_state.clear_inactive().await;
+        let cleared = bot.command_state.clear_inactive().await;
+        bot.metrics.record_interactions_cleared(cleared as u64);
 
         Ok(TaskResult::Completed)
     }
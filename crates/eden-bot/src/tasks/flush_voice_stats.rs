@@ -0,0 +1,29 @@
+use eden_tasks::prelude::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::BotRef;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FlushVoiceStats;
+
+#[async_trait]
+impl Task for FlushVoiceStats {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+        crate::features::voice_stats::flush(&bot).await?;
+
+        Ok(TaskResult::Completed)
+    }
+
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::interval(TimeDelta::minutes(5))
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::flush_voice_stats"
+    }
+}
@@ -0,0 +1,98 @@
+use eden_schema::types::VerificationChallenge;
+use eden_tasks::prelude::*;
+use eden_utils::error::exts::*;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+use twilight_http::request::AuditLogReason;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::util::http::{request_for_empty, request_for_model};
+use crate::{Bot, BotRef};
+
+/// Kicks a member from the guild if the member is still not verified
+/// after the configured period of time.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct KickUnverifiedMember {
+    pub guild_id: Id<GuildMarker>,
+    pub user_id: Id<UserMarker>,
+}
+
+impl KickUnverifiedMember {
+    /// Deletes the member's verification prompt answers since
+    /// the member is no longer in the guild.
+    async fn forget_challenges(&self, bot: &Bot) -> Result<()> {
+        let mut conn = bot.db_write().await?;
+        VerificationChallenge::delete_for_member(&mut conn, self.guild_id, self.user_id).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Task for KickUnverifiedMember {
+    type State = BotRef;
+
+    #[allow(clippy::unwrap_used)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+
+        let settings = bot.guild_settings(self.guild_id).await?;
+
+        let verification = &settings.verification;
+        let Some(role_id) = verification.role_id.filter(|_| verification.is_active()) else {
+            trace!("verification is disabled, skipping");
+            return Ok(TaskResult::Completed);
+        };
+
+        let request = bot.http.guild_member(self.guild_id, self.user_id);
+        let result = request_for_model(&bot, request).await;
+
+        // the member may have left the guild already
+        let has_left = result
+            .discord_http_error_info()
+            .and_then(|v| v.json_code())
+            .is_some_and(|v| v.is_unknown_resource());
+
+        if has_left {
+            trace!("member {} has already left the guild", self.user_id);
+            self.forget_challenges(&bot).await?;
+            return Ok(TaskResult::Completed);
+        }
+
+        let member = result?;
+        if member.roles.contains(&role_id) {
+            trace!("member {} is already verified", self.user_id);
+            return Ok(TaskResult::Completed);
+        }
+
+        debug!("kicking unverified member {}", self.user_id);
+
+        let request = bot
+            .http
+            .remove_guild_member(self.guild_id, self.user_id)
+            .reason("Did not complete verification in time")
+            .unwrap();
+
+        request_for_empty(&bot, request)
+            .await
+            .attach_printable("could not kick unverified member")?;
+
+        self.forget_challenges(&bot).await?;
+        Ok(TaskResult::Completed)
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::kick_unverified_member"
+    }
+
+    fn priority() -> TaskPriority {
+        TaskPriority::Low
+    }
+}
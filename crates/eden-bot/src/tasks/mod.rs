@@ -1,20 +1,77 @@
+use eden_utils::error::exts::*;
+use tracing::warn;
+
 use crate::context::BotQueue;
 
 mod alert_payment;
+mod apply_quiet_hours;
+mod assign_auto_roles;
+mod bulk_role_operation;
+mod clear_expired_cooldowns;
 mod clear_inactive_interaction_states;
+mod flush_voice_stats;
+mod kick_unverified_member;
+mod prune_expired_data;
+mod record_member_count;
+mod refresh_payer_stats;
 mod register_commands;
+mod remove_temp_role;
+mod send_event_reminder;
+mod send_health_report;
 mod setup_local_guild;
+mod sync_payer_roles;
 
 pub use self::alert_payment::*;
+pub use self::apply_quiet_hours::*;
+pub use self::assign_auto_roles::*;
+pub use self::bulk_role_operation::*;
+pub use self::clear_expired_cooldowns::*;
 pub use self::clear_inactive_interaction_states::*;
+pub use self::flush_voice_stats::*;
+pub use self::kick_unverified_member::*;
+pub use self::prune_expired_data::*;
+pub use self::record_member_count::*;
+pub use self::refresh_payer_stats::*;
 pub use self::register_commands::*;
+pub use self::remove_temp_role::*;
+pub use self::send_event_reminder::*;
+pub use self::send_health_report::*;
 pub use self::setup_local_guild::*;
+pub use self::sync_payer_roles::*;
 
 #[must_use]
 pub(crate) fn register_all_tasks(queue: BotQueue) -> BotQueue {
     queue
+        .on_task_health(|bot, event| {
+            Box::pin(async move {
+                let bot = bot.get();
+                let result =
+                    crate::local_guild::channel::send_task_health_alert(&bot, &event).await;
+
+                if let Err(error) = result {
+                    warn!(
+                        error = %error.anonymize(),
+                        "could not alert task health for {:?}",
+                        event.kind()
+                    );
+                }
+            })
+        })
         .register_task::<AlertPayment>()
+        .register_task::<ApplyQuietHours>()
+        .register_task::<AssignAutoRoles>()
+        .register_task::<PerformBulkRoleOperation>()
+        .register_task::<ClearExpiredCooldowns>()
         .register_task::<ClearInactiveInteractionStates>()
+        .register_task::<FlushVoiceStats>()
+        .register_task::<KickUnverifiedMember>()
+        .register_task::<PruneExpiredData>()
+        .register_task::<RecordMemberCount>()
+        .register_task::<RefreshPayerStats>()
         .register_task::<RegisterCommands>()
+        .register_task::<RemoveTempRole>()
+        .register_task::<SendEventReminder>()
+        .register_task::<SendHealthReport>()
         .register_task::<SetupLocalGuild>()
+        .register_task::<SyncPayerRoles>()
 }
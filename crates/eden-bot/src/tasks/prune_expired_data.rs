@@ -0,0 +1,33 @@
+use chrono::Utc;
+use eden_tasks::prelude::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::features::retention;
+use crate::BotRef;
+
+/// Deletes data generated by Eden that is older than its retention
+/// period from the `retention` setting every night (in UTC).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PruneExpiredData;
+
+#[async_trait]
+impl Task for PruneExpiredData {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+        retention::run(&bot, Utc::now()).await?;
+        Ok(TaskResult::Completed)
+    }
+
+    #[allow(clippy::expect_used)]
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::cron("0 0 0 * * *").expect("invalid cron expression")
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::prune_expired_data"
+    }
+}
@@ -0,0 +1,33 @@
+use eden_tasks::prelude::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::BotRef;
+
+/// Records the member count of the local guild for `/stats growth`.
+///
+/// It runs every hour so the count of each day is still recorded
+/// even if Eden restarts often.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecordMemberCount;
+
+#[async_trait]
+impl Task for RecordMemberCount {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+        crate::features::member_growth::record(&bot).await?;
+
+        Ok(TaskResult::Completed)
+    }
+
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::interval(TimeDelta::hours(1))
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::record_member_count"
+    }
+}
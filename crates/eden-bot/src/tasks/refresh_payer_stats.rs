@@ -0,0 +1,43 @@
+use chrono::Utc;
+use eden_schema::types::PayerContributionStat;
+use eden_tasks::prelude::*;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::interactions::response_cache::CachedCommand;
+use crate::BotRef;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RefreshPayerStats;
+
+#[async_trait]
+impl Task for RefreshPayerStats {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+
+        let mut conn = bot.db_write().await?;
+        let today = Utc::now().date_naive();
+        let refreshed = PayerContributionStat::refresh_all(&mut conn, today).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        debug!("refreshed contribution stats of {refreshed} payer(s)");
+        bot.response_cache.bust(CachedCommand::PayerStats);
+        Ok(TaskResult::Completed)
+    }
+
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::interval(TimeDelta::hours(1))
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::refresh_payer_stats"
+    }
+}
@@ -13,6 +13,13 @@ impl Task for RegisterCommands {
 
     async fn perform(&self, _ctx: &TaskRunContext, bot: Self::State) -> Result<TaskResult> {
         let bot = bot.get();
+
+        // any instance may pick up this task but only the leader
+        // registers commands
+        if !bot.leadership.is_leader() {
+            return Ok(TaskResult::RetryIn(TimeDelta::minutes(1)));
+        }
+
         crate::interactions::commands::register(&bot)
             .await
             .change_context(RegisterCommandsError)?;
@@ -20,6 +27,11 @@ impl Task for RegisterCommands {
         Ok(TaskResult::Completed)
     }
 
+    // there's only one set of commands to register
+    fn dedup_key(&self) -> Option<String> {
+        Some("all".into())
+    }
+
     fn kind() -> &'static str {
         "eden::tasks::register_commands"
     }
@@ -0,0 +1,90 @@
+use chrono::Utc;
+use eden_schema::types::TempRoleGrant;
+use eden_tasks::prelude::*;
+use eden_utils::error::exts::*;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+use twilight_http::request::AuditLogReason;
+use uuid::Uuid;
+
+use crate::util::http::request_for_empty;
+use crate::BotRef;
+
+/// Removes a temporarily granted role from a member once it expires.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RemoveTempRole {
+    pub grant_id: Uuid,
+}
+
+#[async_trait]
+impl Task for RemoveTempRole {
+    type State = BotRef;
+
+    #[allow(clippy::unwrap_used)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+
+        let mut conn = bot.db_write().await?;
+        let Some(grant) = TempRoleGrant::from_id(&mut conn, self.grant_id).await? else {
+            trace!("temporary role grant {} does not exist", self.grant_id);
+            return Ok(TaskResult::Completed);
+        };
+
+        if grant.removed_at.is_some() {
+            trace!("temporary role is already removed");
+            return Ok(TaskResult::Completed);
+        }
+
+        // the grant may have been extended and another task is
+        // scheduled to remove the role later
+        if grant.expires_at > Utc::now() {
+            trace!(
+                "temporary role grant has been extended until {}",
+                grant.expires_at
+            );
+            return Ok(TaskResult::Completed);
+        }
+
+        debug!(
+            "removing expired temporary role {} from member {}",
+            grant.role_id, grant.user_id
+        );
+
+        let request = bot
+            .http
+            .remove_guild_member_role(grant.guild_id, grant.user_id, grant.role_id)
+            .reason("Temporary role expired")
+            .unwrap();
+
+        // the member may have left or the role may have been deleted already
+        let result = request_for_empty(&bot, request).await;
+        let is_unknown = result
+            .discord_http_error_info()
+            .and_then(|v| v.json_code())
+            .is_some_and(|v| v.is_unknown_resource());
+
+        if is_unknown {
+            trace!("member or role does not exist anymore");
+        } else {
+            result.attach_printable("could not remove expired temporary role")?;
+        }
+
+        TempRoleGrant::mark_removed(&mut conn, grant.id).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        Ok(TaskResult::Completed)
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::remove_temp_role"
+    }
+
+    fn priority() -> TaskPriority {
+        TaskPriority::Low
+    }
+}
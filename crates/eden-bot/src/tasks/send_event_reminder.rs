@@ -0,0 +1,154 @@
+use chrono::{DateTime, Utc};
+use eden_schema::types::ScheduledEventReminder;
+use eden_tasks::prelude::*;
+use eden_utils::error::exts::*;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+use twilight_model::channel::message::AllowedMentions;
+use twilight_model::guild::scheduled_event::Status;
+use twilight_model::id::marker::{GuildMarker, ScheduledEventMarker};
+use twilight_model::id::Id;
+
+use crate::features::scheduled_events::{self, EventReminder};
+use crate::util::http::{request_for_list, request_for_model};
+use crate::BotRef;
+
+/// Posts a reminder of an upcoming guild scheduled event to
+/// the configured reminder channel.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SendEventReminder {
+    pub guild_id: Id<GuildMarker>,
+    pub event_id: Id<ScheduledEventMarker>,
+    pub reminder: EventReminder,
+    /// When the event starts at the time the reminder is scheduled.
+    pub starts_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl Task for SendEventReminder {
+    type State = BotRef;
+
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+
+        let settings = bot.guild_settings(self.guild_id).await?;
+
+        let Some(channel_id) = settings.scheduled_events.channel_id else {
+            trace!("scheduled event reminders are disabled, skipping");
+            return Ok(TaskResult::Completed);
+        };
+
+        let request = bot.http.guild_scheduled_event(self.guild_id, self.event_id);
+        let result = request_for_model(&bot, request).await;
+
+        let is_deleted = result
+            .discord_http_error_info()
+            .and_then(|v| v.json_code())
+            .is_some_and(|v| v.is_unknown_resource());
+
+        if is_deleted {
+            trace!("scheduled event {} has been deleted", self.event_id);
+            return Ok(TaskResult::Completed);
+        }
+
+        let event = result?;
+        if event.status != Status::Scheduled {
+            trace!("scheduled event has already started or been cancelled");
+            return Ok(TaskResult::Completed);
+        }
+
+        // another reminder is scheduled if the event is rescheduled
+        if scheduled_events::timestamp_to_dt(event.scheduled_start_time) != self.starts_at {
+            trace!("scheduled event has been rescheduled");
+            return Ok(TaskResult::Completed);
+        }
+
+        let mut conn = bot.db_write().await?;
+        let lead_minutes = self.reminder.lead_minutes();
+        let marked = ScheduledEventReminder::mark_sent(
+            &mut conn,
+            self.guild_id,
+            self.event_id,
+            lead_minutes,
+            self.starts_at,
+        )
+        .await?;
+
+        if marked.is_none() {
+            trace!("{:?} reminder has already been posted", self.reminder);
+            return Ok(TaskResult::Completed);
+        }
+
+        let interested = if settings.scheduled_events.ping_interested {
+            let request = bot
+                .http
+                .guild_scheduled_event_users(self.guild_id, self.event_id);
+
+            request_for_list(&bot, request)
+                .await
+                .attach_printable("could not get interested members of scheduled event")?
+                .into_iter()
+                .map(|v| v.user.id)
+                .take(scheduled_events::MAX_PINGED_USERS)
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        let (content, embed) =
+            scheduled_events::render_reminder(&event, self.reminder, &interested);
+
+        // only interested members are allowed to be mentioned
+        let allowed_mentions = AllowedMentions {
+            users: interested,
+            ..Default::default()
+        };
+
+        debug!(
+            "posting {:?} reminder of event {:?}",
+            self.reminder, event.name
+        );
+
+        let embeds = [embed];
+        let request = bot
+            .http
+            .create_message(channel_id)
+            .allowed_mentions(Some(&allowed_mentions))
+            .content(&content)
+            .into_typed_error()?
+            .embeds(&embeds)
+            .into_typed_error()?;
+
+        request_for_model(&bot, request)
+            .await
+            .attach_printable("could not post scheduled event reminder")?;
+
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        Ok(TaskResult::Completed)
+    }
+
+    /// Reminders of the same event and start time are scheduled once
+    /// even if the event is seen again at startup or updated.
+    fn dedup_key(&self) -> Option<String> {
+        Some(format!(
+            "{}:{}:{}",
+            self.event_id,
+            self.reminder.lead_minutes(),
+            self.starts_at.timestamp()
+        ))
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::send_event_reminder"
+    }
+
+    fn priority() -> TaskPriority {
+        TaskPriority::Low
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::Utc;
+use eden_tasks::prelude::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::features::health_report;
+use crate::BotRef;
+
+/// Posts a summary of the bot's activity to the alert channel weekly
+/// for operators who don't collect metrics with external tools.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SendHealthReport;
+
+#[async_trait]
+impl Task for SendHealthReport {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+        health_report::send(&bot, Utc::now()).await?;
+        Ok(TaskResult::Completed)
+    }
+
+    // every Monday at midnight (UTC) so restarts don't push the
+    // report back by another week
+    #[allow(clippy::expect_used)]
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::cron("0 0 0 * * Mon").expect("invalid cron expression")
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::send_health_report"
+    }
+}
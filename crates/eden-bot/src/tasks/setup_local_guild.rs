@@ -19,7 +19,7 @@ impl Task for SetupLocalGuild {
         let local_guild_id = bot.settings.bot.local_guild.id;
 
         debug!("fetching guild information for local guild {local_guild_id}");
-        let guild = crate::util::http::request_for_model(&bot.http, bot.http.guild(local_guild_id))
+        let guild = crate::util::http::request_for_model(&bot, bot.http.guild(local_guild_id))
             .await
             .change_context(SetupLocalGuildError)
             .attach_printable_lazy(|| format!("could not request guild data for {local_guild_id}"))
@@ -0,0 +1,44 @@
+use eden_schema::types::PayerStatusChange;
+use eden_tasks::prelude::*;
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::features::payer_roles;
+use crate::BotRef;
+
+/// Recomputes the status of every payer (since bills may become overdue
+/// at any time) and applies the payer status changes left unapplied.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SyncPayerRoles;
+
+#[async_trait]
+impl Task for SyncPayerRoles {
+    type State = BotRef;
+
+    #[tracing::instrument(skip_all)]
+    async fn perform(&self, _ctx: &TaskRunContext, state: Self::State) -> Result<TaskResult> {
+        let bot = state.get();
+
+        let mut conn = bot.db_write().await?;
+        let changed = PayerStatusChange::refresh_all(&mut conn).await?;
+        conn.commit()
+            .await
+            .into_eden_error()
+            .attach_printable("could not commit transaction")?;
+
+        debug!("status of {changed} payer(s) has changed");
+        payer_roles::apply_pending(&bot).await?;
+
+        Ok(TaskResult::Completed)
+    }
+
+    fn trigger() -> TaskTrigger {
+        TaskTrigger::interval(TimeDelta::minutes(15))
+    }
+
+    fn kind() -> &'static str {
+        "eden::tasks::sync_payer_roles"
+    }
+}
@@ -0,0 +1,145 @@
+//! Helpers to construct Eden's bot and contexts in tests without
+//! connecting to Discord or the database.
+//!
+//! It is only available in tests or with the `testkit` feature enabled.
+//! Use the `eden-testkit` crate to test Eden from other crates.
+#![allow(clippy::unwrap_used)]
+use eden_settings::{Bot, Database, LocalGuild, Settings};
+use eden_utils::error::exts::*;
+use eden_utils::types::Sensitive;
+use serde_json::json;
+use std::sync::Arc;
+use twilight_gateway::{Intents, Shard, ShardId};
+use twilight_model::application::interaction::application_command::CommandData;
+use twilight_model::application::interaction::Interaction;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::features::FeatureEvent;
+use crate::shard::ShardHandle;
+use crate::util::event_bus::Recorder;
+
+pub use crate::events::EventContext;
+pub use crate::interactions::commands::CommandContext;
+
+/// ID of the local guild in [fake settings](generate_fake_settings).
+pub const FAKE_GUILD_ID: Id<GuildMarker> = Id::new(273534239310479360);
+
+/// ID of the channel where fake interactions are invoked.
+pub const FAKE_CHANNEL_ID: Id<ChannelMarker> = Id::new(273534239310479361);
+
+/// ID of the user who invokes fake interactions.
+pub const FAKE_USER_ID: Id<UserMarker> = Id::new(273534239310479362);
+
+pub fn generate_real_settings() -> Settings {
+    match Settings::from_env() {
+        Ok(n) => n,
+        Err(error) => {
+            eden_utils::Error::init();
+            let error = error
+                .anonymize()
+                .attach(crate::suggestions::DEV_ENV_NOT_SET_UP);
+
+            panic!("Cannot load settings: {error}");
+        }
+    }
+}
+
+pub fn generate_fake_settings() -> Settings {
+    Settings::builder()
+        .bot(
+            Bot::builder()
+                .local_guild(
+                    LocalGuild::builder()
+                        .id(FAKE_GUILD_ID)
+                        .alert_channel_id(FAKE_CHANNEL_ID)
+                        .build(),
+                )
+                .token("a test token")
+                .anonymization_key("a test secret")
+                .build(),
+        )
+        .database(
+            Database::builder()
+                .url(Sensitive::new("postgres://test".try_into().unwrap()))
+                .build(),
+        )
+        .build()
+}
+
+/// Creates a bot from [fake settings](generate_fake_settings). It does
+/// not connect to the database until a query is made.
+pub fn generate_fake_bot() -> crate::Bot {
+    crate::Bot::new(Arc::new(generate_fake_settings())).unwrap()
+}
+
+/// Records every [event](FeatureEvent) published by the bot after this call
+/// so tests can assert on what happened.
+pub fn record_events(bot: &crate::Bot) -> Recorder<FeatureEvent> {
+    bot.events.record()
+}
+
+/// Creates an event context with a shard that never connects
+/// to the Discord gateway.
+pub fn generate_fake_event_context(bot: crate::Bot) -> EventContext {
+    let token = bot.settings.bot.token.expose().to_string();
+    let shard = Shard::new(ShardId::ONE, token, Intents::empty());
+
+    EventContext {
+        bot,
+        latency: shard.latency().clone(),
+        shard: ShardHandle::detached(&shard),
+    }
+}
+
+/// Creates the data of a chat input command without any options.
+pub fn generate_fake_command_data(name: &str) -> CommandData {
+    serde_json::from_value(json!({
+        "id": "1",
+        "name": name,
+        "type": 1,
+    }))
+    .unwrap()
+}
+
+/// Creates a command interaction invoked by [`FAKE_USER_ID`] in
+/// [`FAKE_CHANNEL_ID`] of the local guild.
+pub fn generate_fake_command_interaction(data: CommandData) -> Interaction {
+    serde_json::from_value(json!({
+        "id": "1200000000000000000",
+        "application_id": "1",
+        "type": 2,
+        "token": "a test interaction token",
+        "version": 1,
+        "channel": {
+            "id": FAKE_CHANNEL_ID.to_string(),
+            "type": 0,
+        },
+        "guild_id": FAKE_GUILD_ID.to_string(),
+        "locale": "en-US",
+        "member": {
+            "deaf": false,
+            "mute": false,
+            "flags": 0,
+            "joined_at": "2024-01-01T00:00:00.000000+00:00",
+            "permissions": "0",
+            "roles": [],
+            "user": {
+                "id": FAKE_USER_ID.to_string(),
+                "username": "tester",
+                "discriminator": "0",
+                "avatar": null,
+            },
+        },
+        "data": data,
+    }))
+    .unwrap()
+}
+
+/// Creates a command context from a [fake interaction] of the command.
+///
+/// [fake interaction]: generate_fake_command_interaction
+pub fn generate_fake_command_context(ctx: &EventContext, data: CommandData) -> CommandContext {
+    let interaction = generate_fake_command_interaction(data.clone());
+    CommandContext::new(ctx.bot.clone(), ctx, data, &interaction)
+}
@@ -0,0 +1,429 @@
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use eden_settings::{AlertClass, AlertSeverity};
+use eden_utils::error::exts::*;
+use eden_utils::time::{discord_timestamp, TimestampStyle};
+use eden_utils::Result;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use tracing::{debug, trace, warn};
+use twilight_model::channel::message::{AllowedMentions, Component, Embed, Message};
+use twilight_model::channel::thread::AutoArchiveDuration;
+use twilight_model::http::attachment::Attachment;
+use twilight_model::id::marker::{ChannelMarker, MessageMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::errors::SendAlertError;
+use crate::interactions::embeds;
+use crate::util::http::request_for_model;
+use crate::{Bot, BotRef};
+
+/// Discord does not allow thread names longer than this.
+const MAX_THREAD_NAME_LENGTH: usize = 100;
+
+/// Alert to be sent to wherever alerts of its class are routed.
+#[derive(Debug, Clone, Copy)]
+pub struct Alert<'a> {
+    pub class: AlertClass,
+    pub severity: AlertSeverity,
+    pub content: Option<&'a str>,
+    pub embeds: &'a [Embed],
+    /// Components are only sent to channels since their
+    /// interactions are handled in the local guild.
+    pub components: &'a [Component],
+    pub attachments: &'a [Attachment],
+    pub allowed_mentions: Option<&'a AllowedMentions>,
+    /// Incident this alert belongs to, if any.
+    pub incident: Option<AlertIncident<'a>>,
+}
+
+impl Alert<'_> {
+    #[must_use]
+    pub const fn new(class: AlertClass, severity: AlertSeverity) -> Self {
+        Self {
+            class,
+            severity,
+            content: None,
+            embeds: &[],
+            components: &[],
+            attachments: &[],
+            allowed_mentions: None,
+            incident: None,
+        }
+    }
+}
+
+/// Groups related alerts together into a thread in the alert channel
+/// if `local_guild.alerts.incident_threads` is enabled.
+#[derive(Debug, Clone, Copy)]
+pub struct AlertIncident<'a> {
+    /// Alerts with the same key belong to the same incident.
+    pub key: &'a str,
+    /// Shown in the summary message and as the name of the thread.
+    pub title: &'a str,
+    pub status: IncidentStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncidentStatus {
+    Ongoing,
+    /// The incident thread is closed after sending this alert.
+    Resolved,
+}
+
+/// Thread where the alerts of an ongoing incident are posted.
+#[derive(Debug, Clone)]
+struct IncidentThread {
+    channel_id: Id<ChannelMarker>,
+    summary_id: Id<MessageMarker>,
+    thread_id: Id<ChannelMarker>,
+    title: String,
+    started_at: DateTime<Utc>,
+    alerts: u32,
+}
+
+/// Thread of an incident, if it has one. It is locked while alerts of
+/// the incident are posted so they end up in the same thread.
+type IncidentSlot = Arc<Mutex<Option<IncidentThread>>>;
+
+/// Sends alerts to the channels and DMs configured from the
+/// `local_guild.alerts` setting.
+///
+/// Incident threads are only kept in memory, so alerts of incidents
+/// started before Eden restarts are posted in the channel instead.
+pub struct Alerter {
+    bot: BotRef,
+    incidents: DashMap<String, IncidentSlot>,
+}
+
+impl Alerter {
+    #[must_use]
+    pub fn new(bot: BotRef) -> Self {
+        Self {
+            bot,
+            incidents: DashMap::new(),
+        }
+    }
+
+    /// Sends an alert to its routed channel and DMs. Alerts less
+    /// severe than the route's minimum severity are skipped.
+    ///
+    /// Failing to send the alert to someone's DMs is only logged
+    /// since the alert channel is where alerts are mainly seen.
+    #[tracing::instrument(skip_all, fields(
+        alert.class = ?alert.class,
+        alert.severity = ?alert.severity,
+    ))]
+    pub async fn send(&self, alert: Alert<'_>) -> Result<(), SendAlertError> {
+        let bot = self.bot.get();
+        let alert_channel_id = bot.alert_channel_id().await;
+        let routing = &bot.settings.bot.local_guild.alerts;
+        let targets = routing.resolve(alert_channel_id, alert.class, alert.severity);
+
+        if targets.is_empty() {
+            // routes with `dm_only` turned on may not have anyone to DM
+            let is_skipped = routing
+                .get(alert.class)
+                .is_some_and(|v| alert.severity < v.min_severity);
+
+            if is_skipped {
+                trace!("alert is less severe than its route, skipping");
+            } else {
+                warn!("alert has no destination; set a channel or users to DM in its route");
+            }
+            return Ok(());
+        }
+
+        let result = match (targets.channel_id, alert.incident) {
+            (Some(channel_id), Some(incident)) if routing.incident_threads => self
+                .post_incident(&bot, channel_id, incident, &alert)
+                .await
+                .change_context(SendAlertError)
+                .attach_printable_lazy(|| format!("with alert channel: {channel_id}"))
+                .attach_printable_lazy(|| format!("with incident: {}", incident.key)),
+            (Some(channel_id), _) => {
+                debug!("sending alert to channel {channel_id}");
+                post(&bot, channel_id, &alert, true)
+                    .await
+                    .map(|_| ())
+                    .change_context(SendAlertError)
+                    .attach_printable_lazy(|| format!("with alert channel: {channel_id}"))
+            }
+            (None, _) => Ok(()),
+        };
+
+        let mut is_delivered = targets.channel_id.is_some() && result.is_ok();
+        for user_id in targets.user_ids {
+            match send_dm(&bot, *user_id, &alert).await {
+                Ok(()) => is_delivered = true,
+                Err(error) => {
+                    warn!(error = %error.anonymize(), "could not send alert to {user_id}'s DMs");
+                }
+            }
+        }
+
+        if !is_delivered && targets.channel_id.is_none() {
+            warn!("alert could not be sent to anyone's DMs");
+        }
+
+        result
+    }
+
+    /// Marks an ongoing incident as resolved without sending an alert,
+    /// for incidents resolved by the administrators from the thread.
+    #[tracing::instrument(skip(self))]
+    pub async fn resolve_incident(&self, key: &str) {
+        let (slot, mut current) = self.lock_incident(key).await;
+        let thread = current.take();
+        self.incidents.remove_if(key, |_, v| Arc::ptr_eq(v, &slot));
+        drop(current);
+
+        let Some(thread) = thread else {
+            trace!("incident has no thread, skipping");
+            return;
+        };
+
+        let bot = self.bot.get();
+        if let Err(error) = update_summary(&bot, &thread).await {
+            warn!(error = %error.anonymize(), "could not update incident summary");
+        }
+    }
+
+    /// Posts the alert into the thread of its incident. The thread is
+    /// created along with its summary message if the incident is new.
+    async fn post_incident(
+        &self,
+        bot: &Bot,
+        channel_id: Id<ChannelMarker>,
+        incident: AlertIncident<'_>,
+        alert: &Alert<'_>,
+    ) -> Result<()> {
+        let (slot, mut current) = self.lock_incident(incident.key).await;
+        let result = post_to_thread(bot, channel_id, incident, alert, &mut current).await;
+        if current.is_none() {
+            self.incidents
+                .remove_if(incident.key, |_, v| Arc::ptr_eq(v, &slot));
+        }
+        result
+    }
+
+    /// Locks the incident so its alerts are posted one at a time,
+    /// otherwise alerts sent at the same time may create a thread each.
+    async fn lock_incident(
+        &self,
+        key: &str,
+    ) -> (IncidentSlot, OwnedMutexGuard<Option<IncidentThread>>) {
+        loop {
+            let slot = Arc::clone(&self.incidents.entry(key.to_string()).or_default());
+            let guard = Arc::clone(&slot).lock_owned().await;
+
+            // the incident may have been resolved while waiting for the lock
+            let is_current = self
+                .incidents
+                .get(key)
+                .is_some_and(|v| Arc::ptr_eq(v.value(), &slot));
+
+            if is_current {
+                return (slot, guard);
+            }
+        }
+    }
+}
+
+async fn post_to_thread(
+    bot: &Bot,
+    channel_id: Id<ChannelMarker>,
+    incident: AlertIncident<'_>,
+    alert: &Alert<'_>,
+    current: &mut Option<IncidentThread>,
+) -> Result<()> {
+    let existing = current.take().filter(|v| v.channel_id == channel_id);
+    let mut thread = match existing {
+        Some(thread) => thread,
+        None if incident.status == IncidentStatus::Resolved => {
+            trace!("incident has no thread, sending alert to channel {channel_id}");
+            return post(bot, channel_id, alert, true).await.map(|_| ());
+        }
+        None => match open_thread(bot, channel_id, incident).await {
+            Ok(thread) => thread,
+            Err(error) => {
+                warn!(error = %error.anonymize(), "could not create incident thread");
+                return post(bot, channel_id, alert, true).await.map(|_| ());
+            }
+        },
+    };
+
+    debug!("sending alert to incident thread {}", thread.thread_id);
+    if let Err(error) = post(bot, thread.thread_id, alert, true).await {
+        *current = Some(thread);
+        return Err(error);
+    }
+    thread.alerts += 1;
+
+    if incident.status == IncidentStatus::Resolved {
+        update_summary(bot, &thread)
+            .await
+            .attach_printable("could not update incident summary")?;
+    } else {
+        *current = Some(thread);
+    }
+
+    Ok(())
+}
+
+async fn open_thread(
+    bot: &Bot,
+    channel_id: Id<ChannelMarker>,
+    incident: AlertIncident<'_>,
+) -> Result<IncidentThread> {
+    let started_at = Utc::now();
+    let embeds = [render_summary(incident.title, started_at, None)];
+
+    debug!("sending incident summary to channel {channel_id}");
+    let request = bot
+        .http
+        .create_message(channel_id)
+        .embeds(&embeds)
+        .into_typed_error()?;
+
+    let summary = request_for_model(bot, request)
+        .await
+        .attach_printable("could not send incident summary")?;
+
+    let name = thread_name(incident.title);
+    let request = bot
+        .http
+        .create_thread_from_message(channel_id, summary.id, &name)
+        .into_typed_error()?
+        .auto_archive_duration(AutoArchiveDuration::Day);
+
+    let thread = request_for_model(bot, request)
+        .await
+        .attach_printable("could not create incident thread")?;
+
+    Ok(IncidentThread {
+        channel_id,
+        summary_id: summary.id,
+        thread_id: thread.id,
+        title: incident.title.to_string(),
+        started_at,
+        alerts: 0,
+    })
+}
+
+/// Updates the summary message of an incident once it is resolved.
+async fn update_summary(bot: &Bot, thread: &IncidentThread) -> Result<()> {
+    let embeds = [render_summary(
+        &thread.title,
+        thread.started_at,
+        Some(thread.alerts),
+    )];
+
+    let request = bot
+        .http
+        .update_message(thread.channel_id, thread.summary_id)
+        .embeds(Some(&embeds))
+        .into_typed_error()?;
+
+    request_for_model(bot, request).await?;
+    Ok(())
+}
+
+/// Renders the summary message of an incident. `resolved` is the amount
+/// of alerts posted in its thread if the incident has been resolved.
+fn render_summary(title: &str, started_at: DateTime<Utc>, resolved: Option<u32>) -> Embed {
+    let started = discord_timestamp(started_at, TimestampStyle::Relative);
+    let Some(alerts) = resolved else {
+        return embeds::builders::error(title, None)
+            .description("This incident is ongoing. Alerts about it are posted in the thread.")
+            .field(EmbedFieldBuilder::new("Status", "Ongoing").inline())
+            .field(EmbedFieldBuilder::new("Started", started).inline())
+            .build();
+    };
+
+    let resolved = discord_timestamp(Utc::now(), TimestampStyle::Relative);
+    embeds::builders::success(title)
+        .description("This incident has been resolved.")
+        .field(EmbedFieldBuilder::new("Status", "Resolved").inline())
+        .field(EmbedFieldBuilder::new("Started", started).inline())
+        .field(EmbedFieldBuilder::new("Resolved", resolved).inline())
+        .field(EmbedFieldBuilder::new("Alerts", alerts.to_string()).inline())
+        .build()
+}
+
+fn thread_name(title: &str) -> String {
+    match title.char_indices().nth(MAX_THREAD_NAME_LENGTH) {
+        Some((index, _)) => title[..index].to_string(),
+        None => title.to_string(),
+    }
+}
+
+async fn send_dm(bot: &Bot, user_id: Id<UserMarker>, alert: &Alert<'_>) -> Result<()> {
+    let channel = request_for_model(bot, bot.http.create_private_channel(user_id))
+        .await
+        .attach_printable("could not create DM channel")?;
+
+    debug!("sending alert to {user_id}'s DMs");
+    post(bot, channel.id, alert, false).await?;
+    Ok(())
+}
+
+async fn post(
+    bot: &Bot,
+    channel_id: Id<ChannelMarker>,
+    alert: &Alert<'_>,
+    with_components: bool,
+) -> Result<Message> {
+    let mut request = bot.http.create_message(channel_id);
+    if let Some(allowed_mentions) = alert.allowed_mentions {
+        request = request.allowed_mentions(Some(allowed_mentions));
+    }
+    if let Some(content) = alert.content {
+        request = request.content(content).into_typed_error()?;
+    }
+    if !alert.embeds.is_empty() {
+        request = request.embeds(alert.embeds).into_typed_error()?;
+    }
+    if !alert.attachments.is_empty() {
+        request = request.attachments(alert.attachments).into_typed_error()?;
+    }
+    if with_components && !alert.components.is_empty() {
+        request = request.components(alert.components).into_typed_error()?;
+    }
+
+    request_for_model(bot, request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_fit_thread_names() {
+        assert_eq!(thread_name("Raid detected"), "Raid detected");
+
+        let long = "a".repeat(MAX_THREAD_NAME_LENGTH + 10);
+        assert_eq!(thread_name(&long).len(), MAX_THREAD_NAME_LENGTH);
+    }
+
+    #[tokio::test]
+    async fn should_lock_incidents_one_at_a_time() {
+        let bot = crate::testkit::generate_fake_bot();
+        let alerter = &bot.alerter;
+
+        let (_, guard) = alerter.lock_incident("raid:1").await;
+        let timeout = std::time::Duration::from_millis(50);
+        let waiting = tokio::time::timeout(timeout, alerter.lock_incident("raid:1")).await;
+        assert!(waiting.is_err());
+
+        let other = tokio::time::timeout(timeout, alerter.lock_incident("raid:2")).await;
+        assert!(other.is_ok());
+        drop(other);
+        drop(guard);
+
+        alerter.resolve_incident("raid:1").await;
+        alerter.resolve_incident("raid:2").await;
+        assert!(alerter.incidents.is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::info;
+use twilight_http::request::{Method, Request};
+
+#[derive(Debug, Error)]
+#[error("could not record skipped Discord mutation")]
+pub struct RecordDryRunError;
+
+/// Discord mutation that is skipped because dry run mode is enabled.
+#[derive(Debug, Serialize)]
+pub struct DryRunEntry {
+    pub method: String,
+    /// Kind of route requested with its IDs and tokens left out.
+    pub route: String,
+    pub body: Option<String>,
+    pub skipped_at: DateTime<Utc>,
+}
+
+impl DryRunEntry {
+    #[must_use]
+    pub fn from_request(request: &Request) -> Self {
+        Self {
+            method: request.method().to_http().to_string(),
+            route: route_kind(request.path()),
+            body: request
+                .body()
+                .map(|v| String::from_utf8_lossy(v).into_owned()),
+            skipped_at: Utc::now(),
+        }
+    }
+}
+
+/// Turns the request path into the kind of route requested.
+///
+/// Paths of webhook and interaction routes contain their tokens,
+/// so they should not be logged or recorded as is.
+fn route_kind(path: &str) -> String {
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let mut segments = path.split('/').collect::<Vec<_>>();
+    let has_token = matches!(segments.first(), Some(&("webhooks" | "interactions")));
+    for (index, segment) in segments.iter_mut().enumerate() {
+        if has_token && index == 2 {
+            *segment = "{token}";
+        } else if !segment.is_empty() && segment.bytes().all(|v| v.is_ascii_digit()) {
+            *segment = "{id}";
+        }
+    }
+    segments.join("/")
+}
+
+/// Records all outgoing Discord mutations if dry run mode is enabled.
+pub struct DryRunSink {
+    lock: Mutex<()>,
+    output: Option<PathBuf>,
+}
+
+impl DryRunSink {
+    #[must_use]
+    pub fn new(output: Option<PathBuf>) -> Self {
+        Self {
+            lock: Mutex::new(()),
+            output,
+        }
+    }
+
+    /// Whether the request mutates anything from Discord and it
+    /// should be skipped in dry run mode.
+    #[must_use]
+    pub fn is_mutation(request: &Request) -> bool {
+        !matches!(request.method(), Method::Get)
+    }
+
+    /// Logs and records the skipped Discord mutation to the output
+    /// file (if it is configured).
+    pub async fn record(&self, entry: &DryRunEntry) -> Result<(), RecordDryRunError> {
+        info!(
+            dry_run.method = %entry.method,
+            dry_run.route = %entry.route,
+            dry_run.body = ?entry.body,
+            "skipped Discord mutation because dry run mode is enabled"
+        );
+
+        let Some(output) = self.output.as_ref() else {
+            return Ok(());
+        };
+
+        let mut line = serde_json::to_string(entry)
+            .into_typed_error()
+            .change_context(RecordDryRunError)
+            .attach_printable("could not serialize dry run entry")?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(output)
+            .await
+            .into_typed_error()
+            .change_context(RecordDryRunError)
+            .attach_printable_lazy(|| format!("could not open {}", output.display()))?;
+
+        file.write_all(line.as_bytes())
+            .await
+            .into_typed_error()
+            .change_context(RecordDryRunError)
+            .attach_printable_lazy(|| format!("could not write to {}", output.display()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use twilight_http::request::Request;
+    use twilight_http::routing::Route;
+
+    #[test]
+    fn should_only_consider_non_get_requests_as_mutations() {
+        let get = Request::from_route(&Route::GetGuild {
+            guild_id: 1,
+            with_counts: false,
+        });
+        assert!(!DryRunSink::is_mutation(&get));
+
+        let post = Request::from_route(&Route::CreateMessage { channel_id: 1 });
+        assert!(DryRunSink::is_mutation(&post));
+    }
+
+    #[test]
+    fn should_leave_out_ids_and_tokens_from_route_kind() {
+        assert_eq!(
+            route_kind("channels/123/messages"),
+            "channels/{id}/messages"
+        );
+        assert_eq!(
+            route_kind("webhooks/123/secret-token?wait=true"),
+            "webhooks/{id}/{token}"
+        );
+        assert_eq!(
+            route_kind("interactions/123/secret-token/callback"),
+            "interactions/{id}/{token}/callback"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt::Debug;
+use std::time::Duration;
+use tokio::sync::broadcast::{
+    self,
+    error::{RecvError, TryRecvError},
+};
+use tracing::warn;
+
+/// Bounded in-process event bus that lets Eden's features signal
+/// each other without relying on shared statics.
+///
+/// Publishing never blocks. Subscribers that cannot keep up will
+/// miss the oldest events once the bus reaches its capacity.
+pub struct EventBus<E> {
+    sender: broadcast::Sender<E>,
+}
+
+impl<E: Clone + Debug> EventBus<E> {
+    /// Creates a new event bus that can hold up to `capacity`
+    /// unreceived events per subscriber.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Publishes an event to every subscriber.
+    ///
+    /// It returns the number of subscribers that will receive the event.
+    pub fn publish(&self, event: E) -> usize {
+        self.sender.send(event).unwrap_or_default()
+    }
+
+    /// Subscribes to every event published after this call.
+    #[must_use]
+    pub fn subscribe(&self) -> Subscription<E> {
+        Subscription {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// Subscribes to every event published after this call and keeps
+    /// them to be looked up later.
+    #[must_use]
+    pub fn record(&self) -> Recorder<E> {
+        Recorder {
+            events: Vec::new(),
+            subscription: self.subscribe(),
+        }
+    }
+
+    #[must_use]
+    pub fn subscribers(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl<E> Debug for EventBus<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscribers", &self.sender.receiver_count())
+            .finish()
+    }
+}
+
+/// Receives events published from an [`EventBus`].
+#[derive(Debug)]
+pub struct Subscription<E> {
+    receiver: broadcast::Receiver<E>,
+}
+
+impl<E: Clone + Debug> Subscription<E> {
+    /// Waits for the next published event.
+    ///
+    /// It returns `None` if the event bus is dropped.
+    pub async fn recv(&mut self) -> Option<E> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("subscriber lagged behind, skipped {skipped} event(s)");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Receives the next published event without waiting.
+    ///
+    /// It returns `None` if there are no events left to receive.
+    pub fn try_recv(&mut self) -> Option<E> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => return Some(event),
+                Err(TryRecvError::Lagged(skipped)) => {
+                    warn!("subscriber lagged behind, skipped {skipped} event(s)");
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Keeps every event published from an [`EventBus`] since it
+/// is [created](EventBus::record).
+///
+/// It is meant for tests (and plugins) to assert on what happened
+/// inside Eden without looking into the internals of its features.
+#[derive(Debug)]
+pub struct Recorder<E> {
+    events: Vec<E>,
+    subscription: Subscription<E>,
+}
+
+impl<E: Clone + Debug> Recorder<E> {
+    /// Events recorded so far, from the oldest to the newest.
+    ///
+    /// Events that are published but not received yet are only
+    /// recorded after [`Recorder::drain`] or [`Recorder::wait_for`]
+    /// is called.
+    #[must_use]
+    pub fn events(&self) -> &[E] {
+        &self.events
+    }
+
+    /// Records every event published so far without waiting and
+    /// returns all recorded events.
+    pub fn drain(&mut self) -> &[E] {
+        while let Some(event) = self.subscription.try_recv() {
+            self.events.push(event);
+        }
+        &self.events
+    }
+
+    /// Waits until an event that matches the predicate is published
+    /// (or has been recorded already).
+    ///
+    /// It returns `None` if no matching event is published within
+    /// the timeout or the event bus is dropped.
+    pub async fn wait_for<F>(&mut self, timeout: Duration, mut predicate: F) -> Option<E>
+    where
+        F: FnMut(&E) -> bool,
+    {
+        if let Some(event) = self.drain().iter().find(|v| predicate(v)) {
+            return Some(event.clone());
+        }
+
+        let future = async {
+            while let Some(event) = self.subscription.recv().await {
+                self.events.push(event.clone());
+                if predicate(&event) {
+                    return Some(event);
+                }
+            }
+            None
+        };
+        tokio::time::timeout(timeout, future).await.ok().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_deliver_to_every_subscriber() {
+        let bus = EventBus::new(4);
+        assert_eq!(bus.publish(1), 0);
+
+        let mut a = bus.subscribe();
+        let mut b = bus.subscribe();
+        assert_eq!(bus.publish(2), 2);
+
+        assert_eq!(a.recv().await, Some(2));
+        assert_eq!(b.recv().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn should_skip_oldest_events_if_lagged() {
+        let bus = EventBus::new(2);
+        let mut subscription = bus.subscribe();
+        for event in 0..4 {
+            bus.publish(event);
+        }
+
+        assert_eq!(subscription.recv().await, Some(2));
+        assert_eq!(subscription.recv().await, Some(3));
+
+        drop(bus);
+        assert_eq!(subscription.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn should_record_events() {
+        let bus = EventBus::new(4);
+        bus.publish(1);
+
+        let mut recorder = bus.record();
+        bus.publish(2);
+        bus.publish(3);
+        assert!(recorder.events().is_empty());
+        assert_eq!(recorder.drain(), &[2, 3]);
+
+        let timeout = Duration::from_millis(100);
+        assert_eq!(recorder.wait_for(timeout, |v| *v == 2).await, Some(2));
+        assert_eq!(recorder.wait_for(timeout, |v| *v == 4).await, None);
+
+        bus.publish(4);
+        bus.publish(5);
+        assert_eq!(recorder.wait_for(timeout, |v| *v == 4).await, Some(4));
+        assert_eq!(recorder.events(), &[2, 3, 4]);
+    }
+}
@@ -1,14 +1,20 @@
+use chrono::Utc;
 use eden_utils::error::{exts::*, Result};
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::twilight::tags::DiscordHttpErrorInfo;
 use futures::{FutureExt, TryFutureExt};
 use serde::de::DeserializeOwned;
 use std::future::IntoFuture;
 use std::result::Result as StdResult;
-use tracing::trace;
-use twilight_http::request::TryIntoRequest;
-use twilight_http::response::marker::ListBody;
+use tracing::{trace, warn};
+use twilight_http::request::{Request, TryIntoRequest};
+use twilight_http::response::marker::{EmptyBody, ListBody};
 
 use crate::errors::tags::RequestHttpTag;
 use crate::errors::RequestHttpError;
+use crate::features::FeatureEvent;
+use crate::util::dry_run::{DryRunEntry, DryRunSink};
+use crate::Bot;
 
 /// Simplifies fetching request and transforming [`twilight_http::Error`]
 /// into [Eden's error type](eden_utils::Error).
@@ -33,7 +39,7 @@ use crate::errors::RequestHttpError;
 ///     .limit(500)
 ///     .unwrap();
 ///
-/// let response = request_for_list(&bot.http, request).await?;
+/// let response = request_for_list(&bot, request).await?;
 /// ```
 #[tracing::instrument(skip_all, fields(
     method = tracing::field::Empty,
@@ -44,11 +50,14 @@ pub async fn request_for_list<
     T: IntoFuture<Output = StdResult<twilight_http::Response<ListBody<M>>, twilight_http::Error>>
         + TryIntoRequest,
 >(
-    client: &twilight_http::Client,
+    bot: &Bot,
     request: T,
 ) -> Result<Vec<M>, RequestHttpError> {
     let request = request.try_into_request().unwrap();
     let tag = RequestHttpTag::new(request.method(), request.path());
+    if skip_if_dry_run(bot, &request).await {
+        return Ok(Vec::new());
+    }
 
     let span = tracing::Span::current();
     if !span.is_disabled() {
@@ -59,15 +68,17 @@ pub async fn request_for_list<
     }
 
     trace!("fetching request for list");
-    let list = client
+    let result = bot
+        .http
         .request::<Vec<M>>(request)
         .map(|v| v.into_eden_error().anonymize_error())
         .and_then(|v| v.model().map(|v| v.into_typed_error().anonymize_error()))
         .await
         .change_context(RequestHttpError)
-        .attach(tag)?;
+        .attach(tag);
 
-    Ok(list)
+    track_outage(bot, &result);
+    result
 }
 
 /// Simplifies getting the response model data from a request data
@@ -81,11 +92,14 @@ pub async fn request_for_model<
     T: IntoFuture<Output = StdResult<twilight_http::Response<M>, twilight_http::Error>>
         + TryIntoRequest,
 >(
-    client: &twilight_http::Client,
+    bot: &Bot,
     request: T,
 ) -> Result<M, RequestHttpError> {
     let request = request.try_into_request().unwrap();
     let tag = RequestHttpTag::new(request.method(), request.path());
+    if skip_if_dry_run(bot, &request).await {
+        return synthetic_model(&request).attach(tag);
+    }
 
     let span = tracing::Span::current();
     if !span.is_disabled() {
@@ -96,13 +110,106 @@ pub async fn request_for_model<
     }
 
     trace!("fetching request for model");
-    let response = client
+    let result = bot
+        .http
         .request::<M>(request)
         .map(|v| v.into_eden_error().anonymize_error())
         .and_then(|v| v.model().map(|v| v.into_typed_error().anonymize_error()))
         .await
         .change_context(RequestHttpError)
-        .attach(tag)?;
+        .attach(tag);
+
+    track_outage(bot, &result);
+    result
+}
+
+/// Simplifies sending a request that does not expect any response
+/// body and transforming its error into [Eden's error type](eden_utils::Error).
+#[tracing::instrument(skip_all, fields(
+    method = tracing::field::Empty,
+    path = tracing::field::Empty,
+))]
+pub async fn request_for_empty<
+    T: IntoFuture<Output = StdResult<twilight_http::Response<EmptyBody>, twilight_http::Error>>
+        + TryIntoRequest,
+>(
+    bot: &Bot,
+    request: T,
+) -> Result<(), RequestHttpError> {
+    let request = request.try_into_request().unwrap();
+    let tag = RequestHttpTag::new(request.method(), request.path());
+    if skip_if_dry_run(bot, &request).await {
+        return Ok(());
+    }
+
+    let span = tracing::Span::current();
+    if !span.is_disabled() {
+        let method = request.method().to_http();
+        let path = request.path().to_string();
+        span.record("request.method", tracing::field::display(&method));
+        span.record("request.path", tracing::field::display(&path));
+    }
+
+    trace!("fetching request with empty response");
+    let result = bot
+        .http
+        .request::<EmptyBody>(request)
+        .map(|v| v.into_eden_error().anonymize_error())
+        .await
+        .change_context(RequestHttpError)
+        .attach(tag)
+        .map(|_| ());
+
+    track_outage(bot, &result);
+    result
+}
+
+/// Lets the outage detector know whether the request failed
+/// because Discord is having an outage or it went through.
+fn track_outage<T>(bot: &Bot, result: &Result<T, RequestHttpError>) {
+    let outage = bot.shard_manager.outage();
+    match result.discord_http_error_info() {
+        Some(DiscordHttpErrorInfo::Outage | DiscordHttpErrorInfo::TimedOut) => {
+            outage.record_failure(Utc::now());
+        }
+        // other errors are caused by Eden itself, not Discord
+        Some(..) => {}
+        None if result.is_ok() => {
+            if let Some(window) = outage.record_success(Utc::now()) {
+                bot.events.publish(FeatureEvent::OutageRecovered(window));
+            }
+        }
+        None => {}
+    }
+}
+
+/// Records the request and returns `true` if it mutates anything
+/// from Discord while dry run mode is enabled, meaning it should
+/// not be executed.
+async fn skip_if_dry_run(bot: &Bot, request: &Request) -> bool {
+    let Some(sink) = bot.dry_run_sink() else {
+        return false;
+    };
+
+    if !DryRunSink::is_mutation(request) {
+        return false;
+    }
 
-    Ok(response)
+    let entry = DryRunEntry::from_request(request);
+    if let Err(error) = sink.record(&entry).await {
+        warn!(error = %error.anonymize(), "could not record skipped Discord mutation");
+    }
+
+    true
+}
+
+/// Makes up the response model of a skipped request in dry run mode
+/// from its body, since most mutations respond with what was sent.
+fn synthetic_model<M: DeserializeOwned>(request: &Request) -> Result<M, RequestHttpError> {
+    let body = request.body().unwrap_or(b"{}".as_slice());
+    serde_json::from_slice(body)
+        .into_typed_error()
+        .change_context(RequestHttpError)
+        .attach_printable("request is skipped because dry run mode is enabled")
+        .attach_printable("could not make up the response from the request body")
 }
@@ -2,7 +2,11 @@ use twilight_model::guild::{Guild, Permissions, Role};
 use twilight_model::id::marker::RoleMarker;
 use twilight_model::id::Id;
 
+pub mod alerts;
+pub mod dry_run;
+pub mod event_bus;
 pub mod http;
+pub mod webhooks;
 
 /// Gets the @everyone role from a guild.
 pub fn get_everyone_role(guild: &Guild) -> Option<&Role> {
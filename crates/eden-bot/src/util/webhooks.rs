@@ -0,0 +1,249 @@
+use dashmap::DashMap;
+use eden_utils::error::exts::*;
+use eden_utils::twilight::codes::DiscordJsonErrorCode;
+use eden_utils::twilight::error::TwilightHttpErrorExt;
+use eden_utils::{Error, ErrorCategory, Result};
+use thiserror::Error;
+use tracing::{debug, trace};
+use twilight_http::request::AuditLogReason;
+use twilight_model::channel::message::{AllowedMentions, Embed};
+use twilight_model::channel::{Message, Webhook};
+use twilight_model::http::attachment::Attachment;
+use twilight_model::id::marker::{ChannelMarker, UserMarker, WebhookMarker};
+use twilight_model::id::Id;
+use twilight_model::user::User;
+
+use crate::util::http::{request_for_empty, request_for_list, request_for_model};
+use crate::BotRef;
+
+#[derive(Debug, Error)]
+#[error("managed webhook has no token")]
+pub struct MissingWebhookToken;
+
+/// Name of all webhooks managed by Eden.
+pub const MANAGED_WEBHOOK_NAME: &str = "Eden";
+
+/// Webhook created and managed by Eden in a specific channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedWebhook {
+    pub id: Id<WebhookMarker>,
+    pub token: String,
+}
+
+/// Message to be sent through a managed webhook with custom
+/// author name and avatar.
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookMessage<'a> {
+    pub username: &'a str,
+    pub avatar_url: Option<&'a str>,
+    pub content: &'a str,
+    pub embeds: &'a [Embed],
+    pub attachments: &'a [Attachment],
+}
+
+/// Gets the avatar URL of a user so webhook messages can imitate them.
+#[must_use]
+pub fn avatar_url(user: &User) -> String {
+    match user.avatar {
+        Some(hash) => format!("https://cdn.discordapp.com/avatars/{}/{hash}.png", user.id),
+        None => {
+            let index = (user.id.get() >> 22) % 6;
+            format!("https://cdn.discordapp.com/embed/avatars/{index}.png")
+        }
+    }
+}
+
+/// Lazily creates and caches one managed webhook per channel so
+/// features can send messages with custom author display.
+pub struct WebhookManager {
+    bot: BotRef,
+    cache: DashMap<Id<ChannelMarker>, ManagedWebhook>,
+}
+
+impl WebhookManager {
+    #[must_use]
+    pub fn new(bot: BotRef) -> Self {
+        Self {
+            bot,
+            cache: DashMap::new(),
+        }
+    }
+
+    /// Gets the managed webhook of a specific channel. It will be
+    /// created if the channel has no managed webhook yet.
+    #[tracing::instrument(skip(self))]
+    pub async fn get(&self, channel_id: Id<ChannelMarker>) -> Result<ManagedWebhook> {
+        if let Some(webhook) = self.cache.get(&channel_id) {
+            trace!("cache hit, got managed webhook from cache");
+            return Ok(webhook.clone());
+        }
+
+        trace!("cache miss, finding managed webhook from Discord API");
+        let webhook = match self.find_existing(channel_id).await? {
+            Some(webhook) => webhook,
+            None => self.create(channel_id).await?,
+        };
+
+        self.cache.insert(channel_id, webhook.clone());
+        Ok(webhook)
+    }
+
+    /// Sends a message through the managed webhook of a specific channel.
+    ///
+    /// If the cached webhook has been deleted by someone else, it will
+    /// create a new one and send the message again.
+    #[tracing::instrument(skip(self, message))]
+    pub async fn send(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message: WebhookMessage<'_>,
+    ) -> Result<Message> {
+        let webhook = self.get(channel_id).await?;
+        let result = self.execute(&webhook, message).await;
+
+        let is_deleted = result
+            .discord_http_error_info()
+            .and_then(|v| v.json_code())
+            .is_some_and(|v| v == DiscordJsonErrorCode::UnknownWebhook);
+
+        if !is_deleted {
+            return result;
+        }
+
+        debug!("managed webhook for {channel_id} was deleted, creating a new one");
+        self.forget(channel_id);
+
+        let webhook = self.get(channel_id).await?;
+        self.execute(&webhook, message).await
+    }
+
+    /// Replaces the managed webhook of a specific channel with a new
+    /// one, invalidating the old webhook's token.
+    #[tracing::instrument(skip(self))]
+    pub async fn rotate(&self, channel_id: Id<ChannelMarker>) -> Result<ManagedWebhook> {
+        debug!("rotating managed webhook for {channel_id}");
+        self.cleanup(channel_id).await?;
+
+        let webhook = self.create(channel_id).await?;
+        self.cache.insert(channel_id, webhook.clone());
+        Ok(webhook)
+    }
+
+    /// Deletes all managed webhooks in a specific channel.
+    #[tracing::instrument(skip(self))]
+    pub async fn cleanup(&self, channel_id: Id<ChannelMarker>) -> Result<()> {
+        self.forget(channel_id);
+
+        let bot = self.bot.get();
+        let bot_id = bot.application_id().cast::<UserMarker>();
+        let webhooks = request_for_list(&bot, bot.http.channel_webhooks(channel_id))
+            .await
+            .attach_printable("could not get channel webhooks")?;
+
+        for webhook in webhooks.iter().filter(|v| is_managed(v, bot_id)) {
+            trace!("deleting managed webhook {}", webhook.id);
+
+            #[allow(clippy::unwrap_used)]
+            let request = bot
+                .http
+                .delete_webhook(webhook.id)
+                .reason("Cleaning up managed webhook")
+                .unwrap();
+
+            request_for_empty(&bot, request)
+                .await
+                .attach_printable("could not delete managed webhook")?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the cached managed webhook of a specific channel.
+    pub fn forget(&self, channel_id: Id<ChannelMarker>) {
+        self.cache.remove(&channel_id);
+    }
+
+    async fn execute(
+        &self,
+        webhook: &ManagedWebhook,
+        message: WebhookMessage<'_>,
+    ) -> Result<Message> {
+        let bot = self.bot.get();
+
+        // we don't want to ping anyone mentioned in the message
+        let allowed_mentions = AllowedMentions::default();
+        let mut request = bot
+            .http
+            .execute_webhook(webhook.id, &webhook.token)
+            .allowed_mentions(Some(&allowed_mentions))
+            .content(message.content)
+            .into_typed_error()
+            .anonymize_error()?
+            .embeds(message.embeds)
+            .into_typed_error()
+            .anonymize_error()?
+            .attachments(message.attachments)
+            .into_typed_error()
+            .anonymize_error()?
+            .username(message.username)
+            .into_typed_error()
+            .anonymize_error()?;
+
+        if let Some(avatar_url) = message.avatar_url {
+            request = request.avatar_url(avatar_url);
+        }
+
+        let message = request_for_model(&bot, request.wait())
+            .await
+            .attach_printable("could not send message with managed webhook")?;
+
+        Ok(message)
+    }
+
+    async fn find_existing(&self, channel_id: Id<ChannelMarker>) -> Result<Option<ManagedWebhook>> {
+        let bot = self.bot.get();
+        let bot_id = bot.application_id().cast::<UserMarker>();
+        let webhooks = request_for_list(&bot, bot.http.channel_webhooks(channel_id))
+            .await
+            .attach_printable("could not get channel webhooks")?;
+
+        let webhook = webhooks
+            .into_iter()
+            .filter(|v| is_managed(v, bot_id))
+            .find_map(|v| v.token.map(|token| ManagedWebhook { id: v.id, token }));
+
+        Ok(webhook)
+    }
+
+    async fn create(&self, channel_id: Id<ChannelMarker>) -> Result<ManagedWebhook> {
+        debug!("creating managed webhook for {channel_id}");
+
+        let bot = self.bot.get();
+
+        #[allow(clippy::unwrap_used)]
+        let request = bot
+            .http
+            .create_webhook(channel_id, MANAGED_WEBHOOK_NAME)
+            .unwrap();
+
+        let webhook = request_for_model(&bot, request)
+            .await
+            .attach_printable("could not create managed webhook")?;
+
+        // webhooks created by bots should always have their tokens
+        let Some(token) = webhook.token else {
+            return Err(Error::context(ErrorCategory::Unknown, MissingWebhookToken))
+                .attach_printable(format!("created webhook {} has no token", webhook.id));
+        };
+
+        Ok(ManagedWebhook {
+            id: webhook.id,
+            token,
+        })
+    }
+}
+
+fn is_managed(webhook: &Webhook, bot_id: Id<UserMarker>) -> bool {
+    let is_owned = webhook.user.as_ref().is_some_and(|v| v.id == bot_id);
+    is_owned && webhook.name.as_deref() == Some(MANAGED_WEBHOOK_NAME)
+}
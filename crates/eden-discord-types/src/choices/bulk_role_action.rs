@@ -0,0 +1,9 @@
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CommandOption, CreateOption)]
+pub enum BulkRoleActionOption {
+    #[option(name = "Add", value = "add")]
+    Add,
+    #[option(name = "Remove", value = "remove")]
+    Remove,
+}
@@ -0,0 +1,26 @@
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CommandOption, CreateOption)]
+pub enum GrowthRangeOption {
+    #[option(name = "Last 7 days", value = "week")]
+    Week,
+    #[option(name = "Last 30 days", value = "month")]
+    Month,
+    #[option(name = "Last 90 days", value = "quarter")]
+    Quarter,
+    #[option(name = "Last 365 days", value = "year")]
+    Year,
+}
+
+impl GrowthRangeOption {
+    /// Number of days covered by this range.
+    #[must_use]
+    pub const fn days(self) -> i64 {
+        match self {
+            Self::Week => 7,
+            Self::Month => 30,
+            Self::Quarter => 90,
+            Self::Year => 365,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CommandOption, CreateOption)]
+pub enum LockdownModeOption {
+    #[option(name = "Restrict messages", value = "restrict_messages")]
+    RestrictMessages,
+    #[option(name = "Raise verification level", value = "raise_verification")]
+    RaiseVerification,
+    #[option(name = "Pause invites", value = "pause_invites")]
+    PauseInvites,
+}
@@ -1,2 +1,15 @@
+mod bulk_role_action;
+mod growth_range;
+mod lockdown_mode;
 mod payment_method;
+mod simulated_event;
+mod verification_mode;
+mod word_filter_action;
+
+pub use self::bulk_role_action::*;
+pub use self::growth_range::*;
+pub use self::lockdown_mode::*;
 pub use self::payment_method::*;
+pub use self::simulated_event::*;
+pub use self::verification_mode::*;
+pub use self::word_filter_action::*;
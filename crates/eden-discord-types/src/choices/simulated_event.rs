@@ -0,0 +1,13 @@
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CommandOption, CreateOption)]
+pub enum SimulatedEventOption {
+    #[option(name = "MemberAdd", value = "member_add")]
+    MemberAdd,
+    #[option(name = "MessageCreate", value = "message_create")]
+    MessageCreate,
+    #[option(name = "ReactionAdd", value = "reaction_add")]
+    ReactionAdd,
+    #[option(name = "VoiceStateUpdate", value = "voice_state_update")]
+    VoiceStateUpdate,
+}
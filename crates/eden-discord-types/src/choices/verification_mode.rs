@@ -0,0 +1,9 @@
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CommandOption, CreateOption)]
+pub enum VerificationModeOption {
+    #[option(name = "Button", value = "button")]
+    Button,
+    #[option(name = "Captcha", value = "captcha")]
+    Captcha,
+}
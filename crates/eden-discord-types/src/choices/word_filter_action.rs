@@ -0,0 +1,15 @@
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CommandOption, CreateOption)]
+pub enum WordFilterActionOption {
+    #[option(name = "Reply with a warning", value = "reply")]
+    Reply,
+    #[option(name = "Delete the message", value = "delete")]
+    Delete,
+    #[option(name = "Re-post the message censored", value = "repost")]
+    Repost,
+    #[option(name = "Warn the author through DMs", value = "direct_message")]
+    DirectMessage,
+    #[option(name = "Log to the member log channel", value = "log")]
+    Log,
+}
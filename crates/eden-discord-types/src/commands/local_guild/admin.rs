@@ -0,0 +1,438 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::{RoleMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::choices::{BulkRoleActionOption, SimulatedEventOption};
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "admin",
+    desc = "Commands for server administrators",
+    dm_permission = false
+)]
+pub enum AdminCommand {
+    #[command(name = "alias")]
+    Alias(AdminAliasCommand),
+    #[command(name = "blacklist")]
+    Blacklist(AdminBlacklistCommand),
+    #[command(name = "config")]
+    Config(AdminConfigCommand),
+    #[command(name = "feature")]
+    Feature(AdminFeatureCommand),
+    #[command(name = "guilds")]
+    Guilds(AdminGuildsCommand),
+    #[command(name = "logging")]
+    Logging(AdminLoggingCommand),
+    #[command(name = "queue")]
+    Queue(AdminQueueCommand),
+    #[command(name = "restore-snapshot")]
+    RestoreSnapshot(AdminRestoreSnapshot),
+    #[command(name = "roles")]
+    Roles(AdminRolesCommand),
+    #[command(name = "shard")]
+    Shard(AdminShard),
+    // simulated events may trigger real actions from fake data,
+    // so it is only registered in development builds
+    #[cfg(debug_assertions)]
+    #[command(name = "simulate")]
+    Simulate(AdminSimulateCommand),
+    #[command(name = "undo")]
+    Undo(AdminUndo),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "alias",
+    desc = "Commands to manage custom commands that run other commands",
+    dm_permission = false
+)]
+pub enum AdminAliasCommand {
+    #[command(name = "add")]
+    Add(AdminAliasAdd),
+    #[command(name = "list")]
+    List(AdminAliasList),
+    #[command(name = "remove")]
+    Remove(AdminAliasRemove),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "add",
+    desc = "Adds or replaces a custom command that runs another command",
+    dm_permission = false
+)]
+pub struct AdminAliasAdd {
+    /// Name of the custom command (like "rent")
+    #[command(min_length = 1, max_length = 32)]
+    pub name: String,
+    /// Command to run (like "payer pay_bill")
+    #[command(min_length = 1, max_length = 100)]
+    pub command: String,
+    /// Preset arguments of the command (like "amount:500")
+    #[command(max_length = 500)]
+    pub arguments: Option<String>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "list",
+    desc = "Lists every custom command of this server",
+    dm_permission = false
+)]
+pub struct AdminAliasList;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "remove",
+    desc = "Removes a custom command",
+    dm_permission = false
+)]
+pub struct AdminAliasRemove {
+    /// Name of the custom command
+    #[command(min_length = 1, max_length = 32)]
+    pub name: String,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "blacklist",
+    desc = "Commands to manage users whose interactions are ignored (server owner only)",
+    dm_permission = false
+)]
+pub enum AdminBlacklistCommand {
+    #[command(name = "add")]
+    Add(AdminBlacklistAdd),
+    #[command(name = "list")]
+    List(AdminBlacklistList),
+    #[command(name = "remove")]
+    Remove(AdminBlacklistRemove),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "add",
+    desc = "Makes Eden ignore every interaction from the user",
+    dm_permission = false
+)]
+pub struct AdminBlacklistAdd {
+    /// User to be blacklisted
+    pub user: Id<UserMarker>,
+    /// Why the user is blacklisted
+    #[command(max_length = 500)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "list",
+    desc = "Lists every blacklisted user",
+    dm_permission = false
+)]
+pub struct AdminBlacklistList;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "remove",
+    desc = "Removes the user from the blacklist",
+    dm_permission = false
+)]
+pub struct AdminBlacklistRemove {
+    /// User to be removed from the blacklist
+    pub user: Id<UserMarker>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "config",
+    desc = "Commands to inspect the settings of this instance of Eden (server owner only)",
+    dm_permission = false
+)]
+pub enum AdminConfigCommand {
+    #[command(name = "show")]
+    Show(AdminConfigShow),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "show",
+    desc = "Shows the settings Eden has loaded with sensitive values hidden",
+    dm_permission = false
+)]
+pub struct AdminConfigShow;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "feature",
+    desc = "Commands to roll out commands and features to some members only",
+    dm_permission = false
+)]
+pub enum AdminFeatureCommand {
+    #[command(name = "list")]
+    List(AdminFeatureList),
+    #[command(name = "remove")]
+    Remove(AdminFeatureRemove),
+    #[command(name = "set")]
+    Set(AdminFeatureSet),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "list",
+    desc = "Lists every feature that is being rolled out",
+    dm_permission = false
+)]
+pub struct AdminFeatureList;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "remove",
+    desc = "Makes the feature available to everyone",
+    dm_permission = false
+)]
+pub struct AdminFeatureRemove {
+    /// Name of the feature or command (like "payer pay")
+    #[command(min_length = 1, max_length = 100)]
+    pub name: String,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "set",
+    desc = "Rolls out the feature to a percentage of members or role holders",
+    dm_permission = false
+)]
+pub struct AdminFeatureSet {
+    /// Name of the feature or command (like "payer pay")
+    #[command(min_length = 1, max_length = 100)]
+    pub name: String,
+    /// Percentage of members that can use the feature
+    #[command(min_value = 0, max_value = 100)]
+    pub percentage: i64,
+    /// Members with this role can always use the feature
+    pub role: Option<Id<RoleMarker>>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "logging",
+    desc = "Commands to change how Eden records logs (server owner only)",
+    dm_permission = false
+)]
+pub enum AdminLoggingCommand {
+    #[command(name = "list")]
+    List(AdminLoggingList),
+    #[command(name = "remove")]
+    Remove(AdminLoggingRemove),
+    #[command(name = "set")]
+    Set(AdminLoggingSet),
+    #[command(name = "toggle")]
+    Toggle(AdminLoggingToggle),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "list",
+    desc = "Lists every span that is being sampled",
+    dm_permission = false
+)]
+pub struct AdminLoggingList;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "remove",
+    desc = "Records every span of the given path again",
+    dm_permission = false
+)]
+pub struct AdminLoggingRemove {
+    /// Full path or target of the span
+    #[command(min_length = 1, max_length = 200)]
+    pub span: String,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "set",
+    desc = "Records only a percentage of spans of the given path",
+    dm_permission = false
+)]
+pub struct AdminLoggingSet {
+    /// Full path or target of the span (like "eden_bot::events::message_create::handle")
+    #[command(min_length = 1, max_length = 200)]
+    pub span: String,
+    /// Percentage of spans to be recorded
+    #[command(min_value = 0.0, max_value = 100.0)]
+    pub percentage: f64,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "toggle",
+    desc = "Turns span sampling on or off",
+    dm_permission = false
+)]
+pub struct AdminLoggingToggle {
+    /// Whether spans are sampled. Every span is recorded if it is off
+    pub enabled: bool,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "queue",
+    desc = "Commands to control Eden's task queue (server owner only)",
+    dm_permission = false
+)]
+pub enum AdminQueueCommand {
+    #[command(name = "pause")]
+    Pause(AdminQueuePause),
+    #[command(name = "resume")]
+    Resume(AdminQueueResume),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "pause",
+    desc = "Stops running new tasks while letting running tasks finish",
+    dm_permission = false
+)]
+pub struct AdminQueuePause;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "resume",
+    desc = "Starts running new tasks again",
+    dm_permission = false
+)]
+pub struct AdminQueueResume;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "restore-snapshot",
+    desc = "Restores server data from a snapshot taken before a risky operation",
+    dm_permission = false
+)]
+pub struct AdminRestoreSnapshot {
+    /// ID of the snapshot to restore. Recent snapshots are listed if not set
+    #[command(min_length = 36, max_length = 36)]
+    pub snapshot: Option<String>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "guilds",
+    desc = "Commands to manage servers where Eden is allowed to stay (server owner only)",
+    dm_permission = false
+)]
+pub enum AdminGuildsCommand {
+    #[command(name = "allow")]
+    Allow(AdminGuildsAllow),
+    #[command(name = "disallow")]
+    Disallow(AdminGuildsDisallow),
+    #[command(name = "list")]
+    List(AdminGuildsList),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "allow",
+    desc = "Allows Eden to stay in the server",
+    dm_permission = false
+)]
+pub struct AdminGuildsAllow {
+    /// ID of the server to be allowed
+    #[command(min_length = 1, max_length = 20)]
+    pub guild_id: String,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "disallow",
+    desc = "Stops allowing Eden to stay in the server",
+    dm_permission = false
+)]
+pub struct AdminGuildsDisallow {
+    /// ID of the server to be disallowed
+    #[command(min_length = 1, max_length = 20)]
+    pub guild_id: String,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "list",
+    desc = "Lists every server where Eden is allowed to stay",
+    dm_permission = false
+)]
+pub struct AdminGuildsList;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "roles",
+    desc = "Commands to manage roles of many members at once",
+    dm_permission = false
+)]
+pub enum AdminRolesCommand {
+    #[command(name = "bulk")]
+    Bulk(AdminRolesBulk),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "bulk",
+    desc = "Adds or removes a role from every member matching the filters",
+    dm_permission = false
+)]
+pub struct AdminRolesBulk {
+    /// Whether to add or remove the role
+    pub action: BulkRoleActionOption,
+    /// Role to be added to or removed from members
+    pub role: Id<RoleMarker>,
+    /// Only affect members who have this role
+    pub with_role: Option<Id<RoleMarker>>,
+    /// Only affect members who joined within this period (e.g. 7d, 1w 2d)
+    #[command(max_length = 30)]
+    pub joined_within: Option<String>,
+    /// Only affect members who joined more than this long ago (e.g. 30d)
+    #[command(max_length = 30)]
+    pub joined_before: Option<String>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "simulate",
+    desc = "Commands to test Eden's features locally (development builds only)",
+    dm_permission = false
+)]
+pub enum AdminSimulateCommand {
+    #[command(name = "event")]
+    Event(AdminSimulateEvent),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "event",
+    desc = "Injects a fake gateway event to Eden's event handlers",
+    dm_permission = false
+)]
+pub struct AdminSimulateEvent {
+    /// Kind of gateway event to simulate
+    pub kind: SimulatedEventOption,
+
+    /// Payload of the event in JSON (as it would be sent from Discord)
+    #[command(max_length = 6000)]
+    pub json: String,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "shard",
+    desc = "Shows which shard serves this server along with its latency and event counters",
+    dm_permission = false
+)]
+pub struct AdminShard;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "undo",
+    desc = "Lists recent admin operations that can be reverted",
+    dm_permission = false
+)]
+pub struct AdminUndo;
@@ -0,0 +1,27 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::channel::Attachment;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "emoji",
+    desc = "Commands to manage emojis in this server",
+    dm_permission = false
+)]
+pub enum EmojiCommand {
+    #[command(name = "add")]
+    Add(EmojiAdd),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "add",
+    desc = "Uploads a new emoji to this server",
+    dm_permission = false
+)]
+pub struct EmojiAdd {
+    /// Name of the emoji (letters, numbers and underscores only)
+    #[command(min_length = 2, max_length = 32)]
+    pub name: String,
+    /// Image of the emoji (PNG, JPEG, GIF or WEBP up to 256 KB)
+    pub image: Attachment,
+}
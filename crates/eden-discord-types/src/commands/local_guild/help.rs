@@ -0,0 +1,13 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "help",
+    desc = "Shows every command you can use or how to use a specific command",
+    dm_permission = false
+)]
+pub struct HelpCommand {
+    /// Name of the command to explain (e.g. `payer register`)
+    #[command(max_length = 100)]
+    pub command: Option<String>,
+}
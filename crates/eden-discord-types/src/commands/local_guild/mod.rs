@@ -1,5 +1,21 @@
+mod admin;
+mod emoji;
+mod help;
+mod move_message;
 mod payer;
+mod preferences;
+mod role;
 mod settings;
+mod stats;
+mod translate_message;
 
+pub use self::admin::*;
+pub use self::emoji::*;
+pub use self::help::*;
+pub use self::move_message::*;
 pub use self::payer::*;
+pub use self::preferences::*;
+pub use self::role::*;
 pub use self::settings::*;
+pub use self::stats::*;
+pub use self::translate_message::*;
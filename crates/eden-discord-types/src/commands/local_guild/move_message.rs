@@ -0,0 +1,35 @@
+use twilight_model::application::command::{Command, CommandType};
+use twilight_model::guild::Permissions;
+use twilight_model::id::Id;
+
+/// Message context menu command that moves a message into
+/// another channel.
+///
+/// Unlike other commands, message commands have no options so it
+/// cannot be derived with [`CreateCommand`](twilight_interactions::command::CreateCommand).
+#[derive(Debug)]
+pub struct MoveMessageCommand;
+
+impl MoveMessageCommand {
+    pub const NAME: &'static str = "Move to...";
+
+    #[must_use]
+    pub fn create_command() -> Command {
+        Command {
+            application_id: None,
+            default_member_permissions: Some(Permissions::MANAGE_MESSAGES),
+            dm_permission: Some(false),
+            // message commands must have an empty description
+            description: String::new(),
+            description_localizations: None,
+            guild_id: None,
+            id: None,
+            kind: CommandType::Message,
+            name: Self::NAME.into(),
+            name_localizations: None,
+            nsfw: None,
+            options: Vec::new(),
+            version: Id::new(1),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::guild::Permissions;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "admin",
+    desc = "Commands for administrators to manage monthly contributors",
+    dm_permission = false
+)]
+pub enum PayerAdminCommand {
+    #[command(name = "queue")]
+    Queue(PayerAdminQueue),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "queue",
+    desc = "Reviews pending monthly contributor applications one by one",
+    dm_permission = false,
+    default_permissions = "PayerAdminQueue::required_permissions"
+)]
+pub struct PayerAdminQueue;
+
+impl PayerAdminQueue {
+    fn required_permissions() -> Permissions {
+        Permissions::ADMINISTRATOR
+    }
+}
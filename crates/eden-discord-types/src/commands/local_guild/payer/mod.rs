@@ -3,7 +3,9 @@ use twilight_interactions::command::{CommandModel, CreateCommand};
 
 use crate::choices::PaymentMethodOption;
 
+mod admin;
 mod application;
+pub use self::admin::*;
 pub use self::application::*;
 
 #[derive(Debug, CreateCommand, CommandModel)]
@@ -13,12 +15,16 @@ pub use self::application::*;
     dm_permission = false
 )]
 pub enum PayerCommand {
+    #[command(name = "admin")]
+    Admin(PayerAdminCommand),
     #[command(name = "app")]
     Application(PayerApplicationCommand),
     #[command(name = "pay_bill")]
     PayBill(PayerPayBill),
     #[command(name = "register")]
     Register(PayerRegister),
+    #[command(name = "stats")]
+    Stats(PayerStats),
     #[command(name = "test")]
     Test(PayerTest),
 }
@@ -55,6 +61,14 @@ pub struct PayerRegister {
     pub reason: Option<Sensitive<String>>,
 }
 
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "stats",
+    desc = "Shows contribution totals and streaks of monthly contributors",
+    dm_permission = false
+)]
+pub struct PayerStats;
+
 #[derive(Debug, CreateCommand, CommandModel)]
 #[command(name = "test", desc = "Just a testing command", dm_permission = false)]
 pub struct PayerTest {
@@ -0,0 +1,74 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "preferences",
+    desc = "Commands to manage your personal preferences",
+    dm_permission = false
+)]
+pub enum PreferencesCommand {
+    #[command(name = "dm_reminders")]
+    DmReminders(PreferencesDmReminders),
+    #[command(name = "locale")]
+    Locale(PreferencesLocale),
+    #[command(name = "public_payer_stats")]
+    PublicPayerStats(PreferencesPublicPayerStats),
+    #[command(name = "timezone")]
+    Timezone(PreferencesTimezone),
+    #[command(name = "view")]
+    View(PreferencesView),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "dm_reminders",
+    desc = "Modifies or gets whether you receive reminders through direct messages",
+    dm_permission = false
+)]
+pub struct PreferencesDmReminders {
+    /// Whether to receive reminders through direct messages
+    pub set: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "locale",
+    desc = "Modifies or gets your preferred locale",
+    dm_permission = false
+)]
+pub struct PreferencesLocale {
+    /// Preferred locale (e.g. en-US, fil, ja)
+    #[command(min_length = 2, max_length = 10)]
+    pub set: Option<String>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "public_payer_stats",
+    desc = "Modifies or gets whether your contribution stats are shown publicly",
+    dm_permission = false
+)]
+pub struct PreferencesPublicPayerStats {
+    /// Whether to show your contribution stats in /payer stats
+    pub set: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "timezone",
+    desc = "Modifies or gets your preferred timezone",
+    dm_permission = false
+)]
+pub struct PreferencesTimezone {
+    /// Preferred timezone as a UTC offset (e.g. +08:00, -05:00)
+    #[command(min_length = 3, max_length = 6)]
+    pub set: Option<String>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "view",
+    desc = "Shows all of your preferences",
+    dm_permission = false
+)]
+pub struct PreferencesView;
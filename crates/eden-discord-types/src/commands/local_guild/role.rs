@@ -0,0 +1,40 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::{RoleMarker, UserMarker};
+use twilight_model::id::Id;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "role",
+    desc = "Commands to manage roles of members in this server",
+    dm_permission = false
+)]
+pub enum RoleCommand {
+    #[command(name = "grant-temp")]
+    GrantTemp(RoleGrantTemp),
+    #[command(name = "temp-list")]
+    TempList(RoleTempList),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "grant-temp",
+    desc = "Gives a role to a member until it expires",
+    dm_permission = false
+)]
+pub struct RoleGrantTemp {
+    /// Member to give the role to
+    pub user: Id<UserMarker>,
+    /// Role to be given temporarily
+    pub role: Id<RoleMarker>,
+    /// How long the member will have the role (e.g. 30d, 1w 2d, 12h)
+    #[command(max_length = 30)]
+    pub duration: String,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "temp-list",
+    desc = "Lists all active temporary roles given to members",
+    dm_permission = false
+)]
+pub struct RoleTempList;
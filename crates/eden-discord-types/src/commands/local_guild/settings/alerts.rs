@@ -0,0 +1,25 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::ChannelMarker;
+use twilight_model::id::Id;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "alerts",
+    desc = "Commands to manage where Eden alerts the administrators",
+    dm_permission = false
+)]
+pub enum AlertsSettingsCommand {
+    #[command(name = "channel")]
+    Channel(AlertsSettingsChannel),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "channel",
+    desc = "Modifies or gets the channel where Eden alerts the administrators",
+    dm_permission = false
+)]
+pub struct AlertsSettingsChannel {
+    /// Channel where alerts will be sent
+    pub set: Option<Id<ChannelMarker>>,
+}
@@ -0,0 +1,62 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::RoleMarker;
+use twilight_model::id::Id;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "autorole",
+    desc = "Commands to manage roles given to new members automatically",
+    dm_permission = false
+)]
+pub enum AutoRoleSettingsCommand {
+    #[command(name = "add")]
+    Add(AutoRoleSettingsAdd),
+    #[command(name = "delay")]
+    Delay(AutoRoleSettingsDelay),
+    #[command(name = "list")]
+    List(AutoRoleSettingsList),
+    #[command(name = "remove")]
+    Remove(AutoRoleSettingsRemove),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "add",
+    desc = "Adds a role to be given to new members",
+    dm_permission = false
+)]
+pub struct AutoRoleSettingsAdd {
+    /// Role to be given to new members
+    pub role: Id<RoleMarker>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "delay",
+    desc = "Modifies or gets how long to wait before giving roles to new members",
+    dm_permission = false
+)]
+pub struct AutoRoleSettingsDelay {
+    /// Seconds to wait before giving roles (0 to give immediately)
+    #[command(min_value = 0, max_value = 86400)]
+    pub seconds: Option<i64>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "list",
+    desc = "Lists all roles given to new members",
+    dm_permission = false
+)]
+pub struct AutoRoleSettingsList;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "remove",
+    desc = "Removes a role given to new members",
+    dm_permission = false
+)]
+pub struct AutoRoleSettingsRemove {
+    /// Role to be removed
+    pub role: Id<RoleMarker>,
+}
@@ -0,0 +1,23 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "emoji",
+    desc = "Commands to manage how emojis are uploaded with Eden",
+    dm_permission = false
+)]
+pub enum EmojiSettingsCommand {
+    #[command(name = "approval")]
+    Approval(EmojiSettingsApproval),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "approval",
+    desc = "Modifies or gets 'Emoji uploads require approval' option",
+    dm_permission = false
+)]
+pub struct EmojiSettingsApproval {
+    /// Whether uploaded emojis must be approved by admins first
+    pub set: Option<bool>,
+}
@@ -0,0 +1,23 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "fatherbelt",
+    desc = "Commands to manage father belt in this server",
+    dm_permission = false
+)]
+pub enum FatherBeltSettingsCommand {
+    #[command(name = "enabled")]
+    Enabled(FatherBeltSettingsEnabled),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "enabled",
+    desc = "Modifies or gets 'Father belt enabled' option",
+    dm_permission = false
+)]
+pub struct FatherBeltSettingsEnabled {
+    /// Whether Eden should reply to introductions, bad words and screaming messages
+    pub set: Option<bool>,
+}
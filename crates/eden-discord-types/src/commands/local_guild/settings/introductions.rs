@@ -0,0 +1,52 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::ChannelMarker;
+use twilight_model::id::Id;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "introductions",
+    desc = "Commands to manage how Eden replies to member introductions",
+    dm_permission = false
+)]
+pub enum IntroductionsSettingsCommand {
+    #[command(name = "channel")]
+    Channel(IntroductionsSettingsChannel),
+    #[command(name = "enabled")]
+    Enabled(IntroductionsSettingsEnabled),
+    #[command(name = "preview")]
+    Preview(IntroductionsSettingsPreview),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "channel",
+    desc = "Modifies or gets the channel where members introduce themselves",
+    dm_permission = false
+)]
+pub struct IntroductionsSettingsChannel {
+    /// Channel where members introduce themselves
+    pub set: Option<Id<ChannelMarker>>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "enabled",
+    desc = "Modifies or gets 'Introductions enabled' option",
+    dm_permission = false
+)]
+pub struct IntroductionsSettingsEnabled {
+    /// Whether Eden should reply to member introductions
+    pub set: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "preview",
+    desc = "Previews how Eden replies to an introduction message",
+    dm_permission = false
+)]
+pub struct IntroductionsSettingsPreview {
+    /// Sample introduction message (like "I'm Eden")
+    #[command(min_length = 1, max_length = 2000)]
+    pub sample: String,
+}
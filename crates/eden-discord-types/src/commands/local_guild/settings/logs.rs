@@ -0,0 +1,38 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::ChannelMarker;
+use twilight_model::id::Id;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "logs",
+    desc = "Commands to manage where Eden logs events in this server",
+    dm_permission = false
+)]
+pub enum LogsSettingsCommand {
+    #[command(name = "member")]
+    Member(LogsSettingsMember),
+    #[command(name = "server")]
+    Server(LogsSettingsServer),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "member",
+    desc = "Modifies or gets the channel where member events are logged",
+    dm_permission = false
+)]
+pub struct LogsSettingsMember {
+    /// Channel where member events are logged
+    pub set: Option<Id<ChannelMarker>>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "server",
+    desc = "Modifies or gets the channel where server profile changes are logged",
+    dm_permission = false
+)]
+pub struct LogsSettingsServer {
+    /// Channel where server profile changes are logged
+    pub set: Option<Id<ChannelMarker>>,
+}
@@ -1,10 +1,34 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
 
+mod alerts;
+mod auto_role;
+mod emoji;
+mod father_belt;
+mod introductions;
+mod logs;
 mod payer;
+mod quiet_hours;
+mod raid;
+mod scheduled_events;
+mod translation;
 mod user;
+mod verification;
+mod word_filter;
 
+pub use self::alerts::*;
+pub use self::auto_role::*;
+pub use self::emoji::*;
+pub use self::father_belt::*;
+pub use self::introductions::*;
+pub use self::logs::*;
 pub use self::payer::*;
+pub use self::quiet_hours::*;
+pub use self::raid::*;
+pub use self::scheduled_events::*;
+pub use self::translation::*;
 pub use self::user::*;
+pub use self::verification::*;
+pub use self::word_filter::*;
 
 #[derive(Debug, CreateCommand, CommandModel)]
 #[command(
@@ -13,8 +37,32 @@ pub use self::user::*;
     dm_permission = false
 )]
 pub enum SettingsCommand {
+    #[command(name = "alerts")]
+    Alerts(AlertsSettingsCommand),
+    #[command(name = "autorole")]
+    AutoRole(AutoRoleSettingsCommand),
+    #[command(name = "emoji")]
+    Emoji(EmojiSettingsCommand),
+    #[command(name = "events")]
+    Events(ScheduledEventsSettingsCommand),
+    #[command(name = "fatherbelt")]
+    FatherBelt(FatherBeltSettingsCommand),
+    #[command(name = "introductions")]
+    Introductions(IntroductionsSettingsCommand),
+    #[command(name = "logs")]
+    Logs(LogsSettingsCommand),
     #[command(name = "payer")]
     Payer(PayerSettingsCommand),
+    #[command(name = "quiet-hours")]
+    QuietHours(QuietHoursSettingsCommand),
+    #[command(name = "raid")]
+    Raid(RaidSettingsCommand),
+    #[command(name = "translation")]
+    Translation(TranslationSettingsCommand),
     #[command(name = "user")]
     User(UserSettingsCommand),
+    #[command(name = "verification")]
+    Verification(VerificationSettingsCommand),
+    #[command(name = "wordfilter")]
+    WordFilter(WordFilterSettingsCommand),
 }
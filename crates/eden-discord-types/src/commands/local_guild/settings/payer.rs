@@ -1,4 +1,6 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::RoleMarker;
+use twilight_model::id::Id;
 
 #[derive(Debug, CreateCommand, CommandModel)]
 #[command(
@@ -9,6 +11,8 @@ use twilight_interactions::command::{CommandModel, CreateCommand};
 pub enum PayerSettingsCommand {
     #[command(name = "allow_self_registration")]
     AllowSelfRegistration(PayerSettingsAllowSelfRegistration),
+    #[command(name = "role")]
+    Role(PayerSettingsRole),
 }
 
 #[derive(Debug, CreateCommand, CommandModel)]
@@ -22,3 +26,14 @@ pub struct PayerSettingsAllowSelfRegistration {
     /// without admin approval
     pub set: Option<bool>,
 }
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "role",
+    desc = "Modifies or gets the role given to monthly contributors in good standing",
+    dm_permission = false
+)]
+pub struct PayerSettingsRole {
+    /// Role to be given to monthly contributors who are not behind their payments
+    pub set: Option<Id<RoleMarker>>,
+}
@@ -0,0 +1,60 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::ChannelMarker;
+use twilight_model::id::Id;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "quiet-hours",
+    desc = "Commands to manage when Eden should stay quiet in this server",
+    dm_permission = false
+)]
+pub enum QuietHoursSettingsCommand {
+    #[command(name = "enabled")]
+    Enabled(QuietHoursSettingsEnabled),
+    #[command(name = "readonly")]
+    ReadOnly(QuietHoursSettingsReadOnly),
+    #[command(name = "schedule")]
+    Schedule(QuietHoursSettingsSchedule),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "enabled",
+    desc = "Modifies or gets 'Quiet hours enabled' option",
+    dm_permission = false
+)]
+pub struct QuietHoursSettingsEnabled {
+    /// Whether Eden should stay quiet during quiet hours
+    pub set: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "readonly",
+    desc = "Modifies or gets whether a channel becomes read-only during quiet hours",
+    dm_permission = false
+)]
+pub struct QuietHoursSettingsReadOnly {
+    /// Channel to be modified
+    pub channel: Id<ChannelMarker>,
+    /// Whether members cannot send messages in that channel during quiet hours
+    pub set: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "schedule",
+    desc = "Modifies or gets when quiet hours start and end every day",
+    dm_permission = false
+)]
+pub struct QuietHoursSettingsSchedule {
+    /// When quiet hours start in 24-hour format (like "22:00")
+    #[command(min_length = 4, max_length = 5)]
+    pub start: Option<String>,
+    /// When quiet hours end in 24-hour format (like "06:30")
+    #[command(min_length = 4, max_length = 5)]
+    pub end: Option<String>,
+    /// UTC offset of the server's local time (like "+08:00" or "UTC")
+    #[command(max_length = 10)]
+    pub timezone: Option<String>,
+}
@@ -0,0 +1,52 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::choices::LockdownModeOption;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "raid",
+    desc = "Commands to manage raid detection and lockdown",
+    dm_permission = false
+)]
+pub enum RaidSettingsCommand {
+    #[command(name = "enabled")]
+    Enabled(RaidSettingsEnabled),
+    #[command(name = "mode")]
+    Mode(RaidSettingsMode),
+    #[command(name = "threshold")]
+    Threshold(RaidSettingsThreshold),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "enabled",
+    desc = "Modifies or gets 'Raid detection enabled' option",
+    dm_permission = false
+)]
+pub struct RaidSettingsEnabled {
+    /// Whether the server will be locked down once a raid is detected
+    pub set: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "mode",
+    desc = "Modifies or gets how the server will be locked down",
+    dm_permission = false
+)]
+pub struct RaidSettingsMode {
+    /// Action to take once a raid is detected
+    pub set: Option<LockdownModeOption>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "threshold",
+    desc = "Modifies or gets how many joins per minute is considered a raid",
+    dm_permission = false
+)]
+pub struct RaidSettingsThreshold {
+    /// Amount of members joined within a minute
+    #[command(min_value = 2, max_value = 1000)]
+    pub joins_per_minute: Option<i64>,
+}
@@ -0,0 +1,40 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::ChannelMarker;
+use twilight_model::id::Id;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "events",
+    desc = "Commands to manage reminders of upcoming scheduled events",
+    dm_permission = false
+)]
+pub enum ScheduledEventsSettingsCommand {
+    #[command(name = "channel")]
+    Channel(ScheduledEventsSettingsChannel),
+    #[command(name = "ping")]
+    Ping(ScheduledEventsSettingsPing),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "channel",
+    desc = "Modifies or gets the channel where scheduled event reminders are posted",
+    dm_permission = false
+)]
+pub struct ScheduledEventsSettingsChannel {
+    /// Channel where scheduled event reminders are posted
+    pub set: Option<Id<ChannelMarker>>,
+    /// Stops posting scheduled event reminders
+    pub reset: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "ping",
+    desc = "Modifies or gets 'Mention interested members in reminders' option",
+    dm_permission = false
+)]
+pub struct ScheduledEventsSettingsPing {
+    /// Whether reminders should mention members interested in the event
+    pub set: Option<bool>,
+}
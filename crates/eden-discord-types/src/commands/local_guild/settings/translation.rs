@@ -0,0 +1,23 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "translation",
+    desc = "Commands to manage message translation in this server",
+    dm_permission = false
+)]
+pub enum TranslationSettingsCommand {
+    #[command(name = "enabled")]
+    Enabled(TranslationSettingsEnabled),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "enabled",
+    desc = "Modifies or gets 'Message translation enabled' option",
+    dm_permission = false
+)]
+pub struct TranslationSettingsEnabled {
+    /// Whether members can translate messages with 'Translate message'
+    pub set: Option<bool>,
+}
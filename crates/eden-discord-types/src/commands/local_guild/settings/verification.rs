@@ -0,0 +1,90 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::marker::{ChannelMarker, RoleMarker};
+use twilight_model::id::Id;
+
+use crate::choices::VerificationModeOption;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "verification",
+    desc = "Commands to manage verification of new members",
+    dm_permission = false
+)]
+pub enum VerificationSettingsCommand {
+    #[command(name = "channel")]
+    Channel(VerificationSettingsChannel),
+    #[command(name = "enabled")]
+    Enabled(VerificationSettingsEnabled),
+    #[command(name = "kick_after")]
+    KickAfter(VerificationSettingsKickAfter),
+    #[command(name = "mode")]
+    Mode(VerificationSettingsMode),
+    #[command(name = "reset")]
+    Reset(VerificationSettingsReset),
+    #[command(name = "role")]
+    Role(VerificationSettingsRole),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "channel",
+    desc = "Modifies or gets the channel where verification prompts are sent",
+    dm_permission = false
+)]
+pub struct VerificationSettingsChannel {
+    /// Channel where new members verify themselves
+    pub set: Option<Id<ChannelMarker>>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "enabled",
+    desc = "Modifies or gets 'Verification enabled' option",
+    dm_permission = false
+)]
+pub struct VerificationSettingsEnabled {
+    /// Whether new members must verify themselves first
+    pub set: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "kick_after",
+    desc = "Modifies or gets how long unverified members will be kicked",
+    dm_permission = false
+)]
+pub struct VerificationSettingsKickAfter {
+    /// Minutes before kicking unverified members (0 to disable)
+    #[command(min_value = 0, max_value = 10080)]
+    pub minutes: Option<i64>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "mode",
+    desc = "Modifies or gets how new members verify themselves",
+    dm_permission = false
+)]
+pub struct VerificationSettingsMode {
+    /// Verification method for new members
+    pub set: Option<VerificationModeOption>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "reset",
+    desc = "Turns off verification and unsets all of its settings",
+    dm_permission = false
+)]
+pub struct VerificationSettingsReset;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "role",
+    desc = "Modifies or gets the role given to verified members",
+    dm_permission = false
+)]
+pub struct VerificationSettingsRole {
+    /// Role given to members once they're verified
+    pub set: Option<Id<RoleMarker>>,
+}
@@ -0,0 +1,25 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::choices::WordFilterActionOption;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "wordfilter",
+    desc = "Commands to manage how messages with bad words are handled",
+    dm_permission = false
+)]
+pub enum WordFilterSettingsCommand {
+    #[command(name = "action")]
+    Action(WordFilterSettingsAction),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "action",
+    desc = "Modifies or gets what to do with messages that contain bad words",
+    dm_permission = false
+)]
+pub struct WordFilterSettingsAction {
+    /// Action to take once a message with bad words is found
+    pub set: Option<WordFilterActionOption>,
+}
@@ -0,0 +1,41 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::choices::GrowthRangeOption;
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "stats",
+    desc = "Commands to view activity statistics of this server",
+    dm_permission = false
+)]
+pub enum StatsCommand {
+    #[command(name = "growth")]
+    Growth(StatsGrowth),
+    #[command(name = "voice")]
+    Voice(StatsVoice),
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "growth",
+    desc = "Shows how the number of members of this server changed over time",
+    dm_permission = false
+)]
+pub struct StatsGrowth {
+    /// Period to look back (defaults to the last 30 days)
+    pub range: Option<GrowthRangeOption>,
+    /// Attaches the daily member counts as a CSV file (administrators only)
+    pub export: Option<bool>,
+}
+
+#[derive(Debug, CreateCommand, CommandModel)]
+#[command(
+    name = "voice",
+    desc = "Shows the most voice active members over a period",
+    dm_permission = false
+)]
+pub struct StatsVoice {
+    /// Number of days to look back (defaults to 7 days)
+    #[command(min_value = 1, max_value = 90)]
+    pub days: Option<i64>,
+}
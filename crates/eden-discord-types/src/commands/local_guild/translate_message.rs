@@ -0,0 +1,31 @@
+use twilight_model::application::command::{Command, CommandType};
+use twilight_model::id::Id;
+
+/// Message context menu command that translates a message into
+/// the invoker's language.
+#[derive(Debug)]
+pub struct TranslateMessageCommand;
+
+impl TranslateMessageCommand {
+    pub const NAME: &'static str = "Translate message";
+
+    #[must_use]
+    pub fn create_command() -> Command {
+        Command {
+            application_id: None,
+            default_member_permissions: None,
+            dm_permission: Some(false),
+            // message commands must have an empty description
+            description: String::new(),
+            description_localizations: None,
+            guild_id: None,
+            id: None,
+            kind: CommandType::Message,
+            name: Self::NAME.into(),
+            name_localizations: None,
+            nsfw: None,
+            options: Vec::new(),
+            version: Id::new(1),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Translations of Eden's command names, descriptions and messages.
+//!
+//! Translations are stored per locale in the `locales` directory of
+//! this crate as JSON files. Every translation is keyed by the path of
+//! the command or option (like `payer stats`) and is applied with
+//! [`localize`] before the commands are registered to Discord.
+//!
+//! Messages sent by Eden are keyed by their name (like
+//! `validation.title`) and are translated with [`message`].
+//!
+//! To add a new locale, add its file in the `locales` directory and
+//! include it in [`LOCALE_FILES`].
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+use twilight_model::application::command::{Command, CommandOption};
+
+/// Translated name and/or description of a command or option.
+#[derive(Debug, Deserialize)]
+pub struct Translation {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Translations of a single Discord locale.
+#[derive(Debug, Deserialize)]
+pub struct Catalog {
+    /// Discord locale of the translations (like `es-ES`).
+    ///
+    /// Refer to the list of supported locales at:
+    /// https://discord.com/developers/docs/reference#locales
+    #[serde(skip)]
+    pub locale: &'static str,
+    /// Translations keyed by the path of the command or option
+    /// separated by spaces.
+    #[serde(default)]
+    pub commands: BTreeMap<String, Translation>,
+    /// Translated messages keyed by their name. Placeholders
+    /// (like `{argument}`) are filled in by [`message`].
+    #[serde(default)]
+    pub messages: BTreeMap<String, String>,
+}
+
+impl Catalog {
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<&Translation> {
+        self.commands.get(path)
+    }
+}
+
+/// Finds the translations of a locale (like `pt-BR`).
+///
+/// Locales from other regions of the same language (like `pt`
+/// or `pt-PT`) fall back to the translations of that language.
+#[must_use]
+pub fn catalog(locale: &str) -> Option<&'static Catalog> {
+    let language = |locale: &str| locale.split('-').next().unwrap_or_default().to_lowercase();
+
+    let catalogs = catalogs();
+    catalogs
+        .iter()
+        .find(|v| v.locale.eq_ignore_ascii_case(locale))
+        .or_else(|| {
+            catalogs
+                .iter()
+                .find(|v| language(v.locale) == language(locale))
+        })
+}
+
+/// Translates a message into the locale and fills in its
+/// placeholders (like `{argument}`) with `args`.
+///
+/// `fallback` (in English) is used if the locale is not set or
+/// the message is not translated yet.
+#[must_use]
+pub fn message(locale: Option<&str>, key: &str, fallback: &str, args: &[(&str, &str)]) -> String {
+    let template = locale
+        .and_then(catalog)
+        .and_then(|v| v.messages.get(key))
+        .map_or(fallback, String::as_str);
+
+    let mut output = template.to_string();
+    for (name, value) in args {
+        output = output.replace(&format!("{{{name}}}"), value);
+    }
+    output
+}
+
+/// Embedded translation files of every supported Discord locale.
+const LOCALE_FILES: &[(&str, &str)] = &[
+    ("es-ES", include_str!("../locales/es-ES.json")),
+    ("pt-BR", include_str!("../locales/pt-BR.json")),
+];
+
+/// All of the available translations of Eden's commands.
+///
+/// The translation files are parsed once on first use.
+pub fn catalogs() -> &'static [Catalog] {
+    static CATALOGS: OnceLock<Vec<Catalog>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        LOCALE_FILES
+            .iter()
+            .map(|(locale, source)| {
+                let mut catalog: Catalog = serde_json::from_str(source)
+                    .unwrap_or_else(|e| panic!("invalid translations of {locale}: {e}"));
+
+                catalog.locale = locale;
+                catalog
+            })
+            .collect()
+    })
+}
+
+/// Fills in the name and description localizations of a command and
+/// all of its options from the translations of the given locales.
+///
+/// Translations of every locale in [`catalogs`] will be used if
+/// `locales` is set to `None`.
+pub fn localize(command: &mut Command, locales: Option<&[String]>) {
+    let catalogs = catalogs()
+        .iter()
+        .filter(|v| locales.map_or(true, |l| l.iter().any(|l| l == v.locale)))
+        .collect::<Vec<_>>();
+
+    let path = command.name.clone();
+    let (names, descriptions) = collect(&catalogs, &path);
+    command.name_localizations = names;
+    command.description_localizations = descriptions;
+
+    for option in &mut command.options {
+        localize_option(&catalogs, &path, option);
+    }
+}
+
+fn localize_option(catalogs: &[&Catalog], parent: &str, option: &mut CommandOption) {
+    let path = format!("{parent} {}", option.name);
+    let (names, descriptions) = collect(catalogs, &path);
+    option.name_localizations = names;
+    option.description_localizations = descriptions;
+
+    for option in option.options.iter_mut().flatten() {
+        localize_option(catalogs, &path, option);
+    }
+}
+
+type Localizations = Option<HashMap<String, String>>;
+
+fn collect(catalogs: &[&Catalog], path: &str) -> (Localizations, Localizations) {
+    let mut names = HashMap::new();
+    let mut descriptions = HashMap::new();
+    for catalog in catalogs {
+        let Some(translation) = catalog.get(path) else {
+            continue;
+        };
+        if let Some(name) = &translation.name {
+            names.insert(catalog.locale.to_string(), name.clone());
+        }
+        if let Some(description) = &translation.description {
+            descriptions.insert(catalog.locale.to_string(), description.clone());
+        }
+    }
+
+    let names = Some(names).filter(|v| !v.is_empty());
+    let descriptions = Some(descriptions).filter(|v| !v.is_empty());
+    (names, descriptions)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{self, local_guild};
+    use twilight_interactions::command::CreateCommand;
+    use twilight_model::application::command::CommandType;
+
+    fn all_commands() -> Vec<Command> {
+        vec![
+            commands::Ping::create_command().into(),
+            local_guild::AdminCommand::create_command().into(),
+            local_guild::HelpCommand::create_command().into(),
+            local_guild::PayerCommand::create_command().into(),
+            local_guild::PreferencesCommand::create_command().into(),
+            local_guild::RoleCommand::create_command().into(),
+            local_guild::SettingsCommand::create_command().into(),
+            local_guild::StatsCommand::create_command().into(),
+            local_guild::MoveMessageCommand::create_command(),
+            local_guild::TranslateMessageCommand::create_command(),
+        ]
+    }
+
+    fn find_kind(commands: &[Command], path: &str) -> Option<CommandType> {
+        let mut segments = path.split(' ');
+        let name = segments.next()?;
+        let command = commands.iter().find(|v| v.name == name)?;
+
+        let mut options = command.options.as_slice();
+        for segment in segments {
+            let option = options.iter().find(|v| v.name == segment)?;
+            options = option.options.as_deref().unwrap_or_default();
+        }
+        Some(command.kind)
+    }
+
+    #[test]
+    fn should_have_valid_translations() {
+        let commands = all_commands();
+        for catalog in catalogs() {
+            for (path, translation) in &catalog.commands {
+                let kind = find_kind(&commands, path)
+                    .unwrap_or_else(|| panic!("{path} ({}) does not exist", catalog.locale));
+
+                if let Some(name) = &translation.name {
+                    assert!(
+                        (1..=32).contains(&name.chars().count()),
+                        "{name} is too long"
+                    );
+                    if kind == CommandType::ChatInput {
+                        assert!(
+                            name.chars()
+                                .all(|c| !c.is_whitespace() && !c.is_uppercase()),
+                            "{name} is not a valid chat input command name"
+                        );
+                    }
+                }
+
+                if let Some(description) = &translation.description {
+                    assert!(kind == CommandType::ChatInput);
+                    assert!(
+                        description.chars().count() <= 100,
+                        "{description} is too long"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_translate_messages() {
+        let args = [("argument", "days"), ("min", "1"), ("max", "90")];
+        let fallback = "`{argument}` must be between {min} and {max}";
+        assert_eq!(
+            message(Some("pt"), "validation.out_of_range", fallback, &args),
+            "`days` deve estar entre 1 e 90"
+        );
+        assert_eq!(
+            message(Some("ja"), "validation.out_of_range", fallback, &args),
+            "`days` must be between 1 and 90"
+        );
+        assert_eq!(message(None, "validation.unknown", "Hello!", &[]), "Hello!");
+        assert_eq!(catalog("es-MX").map(|v| v.locale), Some("es-ES"));
+    }
+
+    #[test]
+    fn should_localize_commands() {
+        let mut command: Command = local_guild::PayerCommand::create_command().into();
+        localize(&mut command, Some(&["pt-BR".into()]));
+
+        let names = command.name_localizations.unwrap();
+        assert_eq!(names.get("pt-BR").map(String::as_str), Some("contribuinte"));
+        assert!(!names.contains_key("es-ES"));
+
+        let stats = command.options.iter().find(|v| v.name == "stats").unwrap();
+        assert!(stats.description_localizations.is_some());
+
+        let mut command: Command = local_guild::PayerCommand::create_command().into();
+        localize(&mut command, Some(&[]));
+        assert!(command.name_localizations.is_none());
+    }
+}
@@ -1,2 +1,3 @@
 pub mod choices;
 pub mod commands;
+pub mod i18n;
@@ -0,0 +1,14 @@
+use std::collections::BTreeMap;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use typed_builder::TypedBuilder;
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct InsertCommandAliasForm<'a> {
+    pub guild_id: Id<GuildMarker>,
+    pub name: &'a str,
+    pub created_by: Id<UserMarker>,
+    pub command: &'a str,
+    #[builder(default)]
+    pub arguments: BTreeMap<String, String>,
+}
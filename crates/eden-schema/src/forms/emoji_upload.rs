@@ -0,0 +1,11 @@
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use typed_builder::TypedBuilder;
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct InsertEmojiUploadForm<'a> {
+    pub guild_id: Id<GuildMarker>,
+    pub requested_by: Id<UserMarker>,
+    pub name: &'a str,
+    pub image: &'a str,
+}
@@ -0,0 +1,13 @@
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use typed_builder::TypedBuilder;
+
+use crate::types::GuildSnapshotData;
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct InsertGuildSnapshotForm<'a> {
+    pub guild_id: Id<GuildMarker>,
+    pub created_by: Id<UserMarker>,
+    pub reason: &'a str,
+    pub data: &'a GuildSnapshotData,
+}
@@ -1,15 +1,27 @@
 mod admin;
 mod bill;
+mod command_alias;
+mod emoji_upload;
+mod guild_snapshot;
 mod identity;
 mod payer;
 mod payer_application;
 mod payment;
+mod raid_incident;
+mod temp_role_grant;
+mod undo_entry;
 mod user;
 
 pub use self::admin::{InsertAdminForm, UpdateAdminForm};
 pub use self::bill::{InsertBillForm, UpdateBillForm};
+pub use self::command_alias::InsertCommandAliasForm;
+pub use self::emoji_upload::InsertEmojiUploadForm;
+pub use self::guild_snapshot::InsertGuildSnapshotForm;
 pub use self::identity::InsertIdentityForm;
 pub use self::payer::{InsertPayerForm, UpdatePayerForm};
 pub use self::payer_application::{InsertPayerApplicationForm, UpdatePayerApplicationForm};
 pub use self::payment::{InsertPaymentForm, UpdatePaymentForm};
+pub use self::raid_incident::InsertRaidIncidentForm;
+pub use self::temp_role_grant::InsertTempRoleGrantForm;
+pub use self::undo_entry::InsertUndoEntryForm;
 pub use self::user::UpdateUserForm;
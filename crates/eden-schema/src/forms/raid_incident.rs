@@ -0,0 +1,14 @@
+use twilight_model::id::{marker::GuildMarker, Id};
+use typed_builder::TypedBuilder;
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct InsertRaidIncidentForm {
+    pub guild_id: Id<GuildMarker>,
+    pub joins: i32,
+    #[builder(default)]
+    pub removed_everyone_permissions: Option<i64>,
+    #[builder(default)]
+    pub previous_verification_level: Option<i16>,
+    #[builder(default)]
+    pub disabled_invites: bool,
+}
@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use twilight_model::id::marker::{GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+use typed_builder::TypedBuilder;
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct InsertTempRoleGrantForm {
+    pub guild_id: Id<GuildMarker>,
+    pub user_id: Id<UserMarker>,
+    pub role_id: Id<RoleMarker>,
+    pub granted_by: Id<UserMarker>,
+    pub expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,12 @@
+use serde_json::Value;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use typed_builder::TypedBuilder;
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct InsertUndoEntryForm<'a> {
+    pub guild_id: Id<GuildMarker>,
+    pub actor_id: Id<UserMarker>,
+    pub description: &'a str,
+    pub action: &'a Value,
+}
@@ -0,0 +1,173 @@
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::types::{AllowedGuild, BlacklistedUser};
+
+impl BlacklistedUser {
+    pub async fn list(conn: &mut sqlx::PgConnection) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM blacklisted_users ORDER BY created_at ASC")
+            .fetch_all(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get blacklisted users")
+    }
+
+    /// Blacklists the user. If the user is already blacklisted,
+    /// the reason will be replaced instead.
+    pub async fn upsert(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+        blacklisted_by: Id<UserMarker>,
+        reason: Option<&str>,
+    ) -> Result<Self, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO blacklisted_users(user_id, blacklisted_by, reason)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id)
+                DO UPDATE SET blacklisted_by = EXCLUDED.blacklisted_by,
+                    reason = EXCLUDED.reason
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(user_id))
+        .bind(SqlSnowflake::new(blacklisted_by))
+        .bind(reason)
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not blacklist user")
+    }
+
+    /// Removes the user from the blacklist. It returns false
+    /// if the user is not blacklisted.
+    pub async fn delete(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+    ) -> Result<bool, QueryError> {
+        sqlx::query(r"DELETE FROM blacklisted_users WHERE user_id = $1")
+            .bind(SqlSnowflake::new(user_id))
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not remove user from the blacklist")
+            .map(|v| v.rows_affected() > 0)
+    }
+}
+
+impl AllowedGuild {
+    pub async fn from_id(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM allowed_guilds WHERE guild_id = $1")
+            .bind(SqlSnowflake::new(guild_id))
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get allowed guild from id")
+    }
+
+    pub async fn list(conn: &mut sqlx::PgConnection) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM allowed_guilds ORDER BY created_at ASC")
+            .fetch_all(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get allowed guilds")
+    }
+
+    /// Allows Eden to stay in the guild. It returns `None`
+    /// if the guild is already allowed.
+    pub async fn insert(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        allowed_by: Id<UserMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO allowed_guilds(guild_id, allowed_by)
+            VALUES ($1, $2)
+            ON CONFLICT (guild_id) DO NOTHING
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(SqlSnowflake::new(allowed_by))
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not allow guild")
+    }
+
+    /// Removes the guild from the allowed guilds. It returns
+    /// false if the guild is not allowed in the first place.
+    pub async fn delete(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<bool, QueryError> {
+        sqlx::query(r"DELETE FROM allowed_guilds WHERE guild_id = $1")
+            .bind(SqlSnowflake::new(guild_id))
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not remove guild from the allowed guilds")
+            .map(|v| v.rows_affected() > 0)
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_blacklist_users(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let user_id = Id::new(1);
+        let owner_id = Id::new(2);
+
+        BlacklistedUser::upsert(&mut conn, user_id, owner_id, None).await?;
+
+        // it should replace the reason of the existing entry
+        let entry = BlacklistedUser::upsert(&mut conn, user_id, owner_id, Some("spam")).await?;
+        assert_eq!(entry.reason.as_deref(), Some("spam"));
+
+        let list = BlacklistedUser::list(&mut conn).await?;
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].user_id, user_id);
+
+        assert!(BlacklistedUser::delete(&mut conn, user_id).await?);
+        assert!(!BlacklistedUser::delete(&mut conn, user_id).await?);
+        assert!(BlacklistedUser::list(&mut conn).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_allowed_guilds(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let guild_id = Id::new(1);
+        let owner_id = Id::new(2);
+
+        let inserted = AllowedGuild::insert(&mut conn, guild_id, owner_id).await?;
+        assert!(inserted.is_some());
+
+        // it should not be allowed twice
+        let inserted = AllowedGuild::insert(&mut conn, guild_id, owner_id).await?;
+        assert!(inserted.is_none());
+
+        assert!(AllowedGuild::from_id(&mut conn, guild_id).await?.is_some());
+
+        assert!(AllowedGuild::delete(&mut conn, guild_id).await?);
+        assert!(AllowedGuild::from_id(&mut conn, guild_id).await?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+
+use crate::forms::InsertCommandAliasForm;
+use crate::types::CommandAlias;
+
+impl CommandAlias {
+    /// Gets every command alias of a guild sorted by name.
+    pub async fn list(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM command_aliases
+            WHERE guild_id = $1
+            ORDER BY name ASC",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get command aliases")
+    }
+
+    /// Creates the command alias. If the guild has an alias with
+    /// the same name, it will be replaced instead.
+    pub async fn upsert(
+        conn: &mut sqlx::PgConnection,
+        form: InsertCommandAliasForm<'_>,
+    ) -> Result<Self, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO command_aliases(guild_id, name, created_by, command, arguments)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (guild_id, name)
+                DO UPDATE SET created_by = EXCLUDED.created_by,
+                    command = EXCLUDED.command,
+                    arguments = EXCLUDED.arguments
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(form.name)
+        .bind(SqlSnowflake::new(form.created_by))
+        .bind(form.command)
+        .bind(sqlx::types::Json(&form.arguments))
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not save command alias")
+    }
+
+    /// Deletes the command alias and returns what has been deleted.
+    /// It returns `None` if the guild has no alias with that name.
+    pub async fn delete(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        name: &str,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"DELETE FROM command_aliases WHERE guild_id = $1 AND name = $2
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(name)
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not delete command alias")
+    }
+
+    /// Deletes every command alias of a guild and returns how many
+    /// were deleted.
+    pub async fn delete_all(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM command_aliases WHERE guild_id = $1")
+            .bind(SqlSnowflake::new(guild_id))
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete command aliases")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_command_aliases(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let guild_id = Id::new(1);
+
+        let form = InsertCommandAliasForm::builder()
+            .guild_id(guild_id)
+            .name("rent")
+            .created_by(Id::new(2))
+            .command("payer pay_bill")
+            .build();
+        CommandAlias::upsert(&mut conn, form).await?;
+
+        // it should replace the existing alias
+        let arguments = BTreeMap::from([("amount".to_string(), "500".to_string())]);
+        let form = InsertCommandAliasForm::builder()
+            .guild_id(guild_id)
+            .name("rent")
+            .created_by(Id::new(3))
+            .command("payer pay_bill")
+            .arguments(arguments.clone())
+            .build();
+        let alias = CommandAlias::upsert(&mut conn, form).await?;
+        assert_eq!(alias.created_by, Id::new(3));
+        assert_eq!(alias.arguments, arguments);
+
+        let list = CommandAlias::list(&mut conn, guild_id).await?;
+        assert_eq!(list, vec![alias.clone()]);
+        assert!(CommandAlias::list(&mut conn, Id::new(2)).await?.is_empty());
+
+        let deleted = CommandAlias::delete(&mut conn, guild_id, "rent").await?;
+        assert_eq!(deleted, Some(alias));
+        assert!(CommandAlias::delete(&mut conn, guild_id, "rent")
+            .await?
+            .is_none());
+
+        let form = InsertCommandAliasForm::builder()
+            .guild_id(guild_id)
+            .name("rent")
+            .created_by(Id::new(2))
+            .command("payer pay_bill")
+            .build();
+        CommandAlias::upsert(&mut conn, form).await?;
+        assert_eq!(CommandAlias::delete_all(&mut conn, guild_id).await?, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+
+use crate::types::CommandCooldown;
+
+impl CommandCooldown {
+    /// Gets all cooldowns of a user that are not expired yet.
+    pub async fn active_list(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM command_cooldowns
+            WHERE user_id = $1 AND expires_at > $2",
+        )
+        .bind(SqlSnowflake::new(user_id))
+        .bind(now.naive_utc())
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get active command cooldowns")
+    }
+
+    /// Stores the cooldown of a user's command bucket, replacing
+    /// its previous expiry if there's any.
+    pub async fn upsert(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+        bucket: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), QueryError> {
+        sqlx::query(
+            r"INSERT INTO command_cooldowns(user_id, bucket, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, bucket)
+                DO UPDATE SET expires_at = EXCLUDED.expires_at",
+        )
+        .bind(SqlSnowflake::new(user_id))
+        .bind(bucket)
+        .bind(expires_at.naive_utc())
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable_lazy(|| format!("could not store command cooldown {bucket:?}"))?;
+
+        Ok(())
+    }
+
+    /// Deletes the cooldown of a user's command bucket if it does
+    /// not expire later than `expires_at`.
+    pub async fn delete(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+        bucket: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), QueryError> {
+        sqlx::query(
+            r"DELETE FROM command_cooldowns
+            WHERE user_id = $1 AND bucket = $2 AND expires_at <= $3",
+        )
+        .bind(SqlSnowflake::new(user_id))
+        .bind(bucket)
+        .bind(expires_at.naive_utc())
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable_lazy(|| format!("could not delete command cooldown {bucket:?}"))?;
+
+        Ok(())
+    }
+
+    /// Deletes all expired cooldowns and returns how many were deleted.
+    pub async fn delete_expired(
+        conn: &mut sqlx::PgConnection,
+        now: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM command_cooldowns WHERE expires_at <= $1")
+            .bind(now.naive_utc())
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete expired command cooldowns")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_upsert_and_active_list(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let user_id = Id::new(1);
+        let now = Utc::now();
+
+        CommandCooldown::upsert(&mut conn, user_id, "daily", now + TimeDelta::hours(1)).await?;
+        CommandCooldown::upsert(&mut conn, user_id, "weekly", now - TimeDelta::hours(1)).await?;
+
+        // it should replace the expiry of the existing cooldown
+        let expires_at = now + TimeDelta::days(1);
+        CommandCooldown::upsert(&mut conn, user_id, "daily", expires_at).await?;
+
+        let list = CommandCooldown::active_list(&mut conn, user_id, now).await?;
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].bucket, "daily");
+        assert_eq!(list[0].expires_at.timestamp(), expires_at.timestamp());
+
+        let deleted = CommandCooldown::delete_expired(&mut conn, now).await?;
+        assert_eq!(deleted, 1);
+
+        CommandCooldown::delete(&mut conn, user_id, "daily", expires_at).await?;
+        let list = CommandCooldown::active_list(&mut conn, user_id, now).await?;
+        assert!(list.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{EmojiMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+use crate::forms::InsertEmojiUploadForm;
+use crate::types::EmojiUpload;
+
+impl EmojiUpload {
+    pub async fn from_id(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM emoji_uploads WHERE id = $1 LIMIT 1")
+            .bind(id)
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get emoji upload from id")
+    }
+}
+
+impl EmojiUpload {
+    pub async fn insert(
+        conn: &mut sqlx::PgConnection,
+        form: InsertEmojiUploadForm<'_>,
+    ) -> Result<Self, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO emoji_uploads(guild_id, requested_by, name, image)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(SqlSnowflake::new(form.requested_by))
+        .bind(form.name)
+        .bind(form.image)
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not insert emoji upload")
+    }
+
+    /// Approves or rejects a pending emoji upload. Its image will be
+    /// cleared from the database since it is no longer needed.
+    ///
+    /// It returns the upload before it is reviewed, or `None` if it
+    /// does not exist or it has already been reviewed.
+    pub async fn review(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+        reviewed_by: Id<UserMarker>,
+        approved: bool,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"UPDATE emoji_uploads AS new
+            SET approved = $3, reviewed_by = $2, image = NULL
+            FROM emoji_uploads AS old
+            WHERE new.id = $1 AND old.id = new.id AND new.approved IS NULL
+            RETURNING old.*",
+        )
+        .bind(id)
+        .bind(SqlSnowflake::new(reviewed_by))
+        .bind(approved)
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not review emoji upload")
+    }
+
+    /// Remembers which emoji is uploaded from an approved upload.
+    pub async fn set_emoji_id(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+        emoji_id: Id<EmojiMarker>,
+    ) -> Result<(), QueryError> {
+        sqlx::query(r"UPDATE emoji_uploads SET emoji_id = $2 WHERE id = $1")
+            .bind(id)
+            .bind(SqlSnowflake::new(emoji_id))
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not set emoji id of emoji upload")?;
+
+        Ok(())
+    }
+
+    /// Deletes emoji uploads reviewed before `before` and returns how
+    /// many were deleted. Pending uploads are kept until reviewed.
+    pub async fn delete_reviewed_before(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(
+            r"DELETE FROM emoji_uploads
+            WHERE approved IS NOT NULL
+                AND COALESCE(updated_at, created_at) < $1",
+        )
+        .bind(before.naive_utc())
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not delete old emoji uploads")
+        .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_review(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let form = InsertEmojiUploadForm::builder()
+            .guild_id(Id::new(1))
+            .requested_by(Id::new(2))
+            .name("eden")
+            .image("data:image/png;base64,AAAA")
+            .build();
+
+        let upload = EmojiUpload::insert(&mut conn, form).await?;
+        assert!(upload.is_pending());
+
+        let reviewer_id = Id::new(3);
+        let reviewed = EmojiUpload::review(&mut conn, upload.id, reviewer_id, true)
+            .await?
+            .unwrap();
+
+        // the image is needed to upload the emoji after approval
+        assert_eq!(
+            reviewed.image.as_deref(),
+            Some("data:image/png;base64,AAAA")
+        );
+
+        // it should not be reviewed twice
+        let result = EmojiUpload::review(&mut conn, upload.id, reviewer_id, false).await?;
+        assert!(result.is_none());
+
+        EmojiUpload::set_emoji_id(&mut conn, upload.id, Id::new(4)).await?;
+
+        let upload = EmojiUpload::from_id(&mut conn, upload.id).await?.unwrap();
+        assert_eq!(upload.approved, Some(true));
+        assert_eq!(upload.reviewed_by, Some(reviewer_id));
+        assert_eq!(upload.emoji_id, Some(Id::new(4)));
+        assert_eq!(upload.image, None);
+
+        Ok(())
+    }
+}
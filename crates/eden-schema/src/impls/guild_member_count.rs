@@ -0,0 +1,160 @@
+use chrono::NaiveDate;
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+
+use crate::types::GuildMemberCount;
+
+impl GuildMemberCount {
+    /// Records the number of members of a guild in a specific day.
+    ///
+    /// Recording it again in the same day replaces the earlier count.
+    pub async fn record(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        day: NaiveDate,
+        members: i64,
+    ) -> Result<Self, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO guild_member_counts(guild_id, day, members)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (guild_id, day)
+                DO UPDATE SET members = EXCLUDED.members
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(day)
+        .bind(members)
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not record guild member count")
+    }
+
+    /// Gets the daily member counts of a guild since `since`,
+    /// from the oldest to the most recent day.
+    pub async fn since(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        since: NaiveDate,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM guild_member_counts
+            WHERE guild_id = $1 AND day >= $2
+            ORDER BY day",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(since)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get guild member counts")
+    }
+
+    /// Deletes member counts of every guild recorded before `day`
+    /// and returns how many were deleted.
+    pub async fn delete_before(
+        conn: &mut sqlx::PgConnection,
+        day: NaiveDate,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM guild_member_counts WHERE day < $1")
+            .bind(day)
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete old guild member counts")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_record(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let day = NaiveDate::from_ymd_opt(2024, 8, 26).unwrap();
+
+        GuildMemberCount::record(&mut conn, guild_id, day, 100)
+            .await
+            .anonymize_error()?;
+
+        let count = GuildMemberCount::record(&mut conn, guild_id, day, 105)
+            .await
+            .anonymize_error()?;
+
+        assert_eq!(count.members, 105);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_since(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let old_day = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let day = NaiveDate::from_ymd_opt(2024, 8, 25).unwrap();
+        let next_day = NaiveDate::from_ymd_opt(2024, 8, 26).unwrap();
+
+        for (day, members) in [(next_day, 110), (old_day, 50), (day, 100)] {
+            GuildMemberCount::record(&mut conn, guild_id, day, members)
+                .await
+                .anonymize_error()?;
+        }
+
+        // from other guilds
+        GuildMemberCount::record(&mut conn, Id::new(2), day, 1)
+            .await
+            .anonymize_error()?;
+
+        let counts = GuildMemberCount::since(&mut conn, guild_id, day)
+            .await
+            .anonymize_error()?;
+
+        let counts = counts
+            .iter()
+            .map(|v| (v.day, v.members))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![(day, 100), (next_day, 110)]);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_delete_before(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let old_day = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let day = NaiveDate::from_ymd_opt(2024, 8, 25).unwrap();
+
+        for (day, members) in [(old_day, 50), (day, 100)] {
+            GuildMemberCount::record(&mut conn, guild_id, day, members)
+                .await
+                .anonymize_error()?;
+        }
+
+        let deleted = GuildMemberCount::delete_before(&mut conn, day)
+            .await
+            .anonymize_error()?;
+        assert_eq!(deleted, 1);
+
+        let counts = GuildMemberCount::since(&mut conn, guild_id, old_day)
+            .await
+            .anonymize_error()?;
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].members, 100);
+
+        Ok(())
+    }
+}
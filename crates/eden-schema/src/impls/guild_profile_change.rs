@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+
+use crate::types::GuildProfileChange;
+
+impl GuildProfileChange {
+    /// Maximum amount of profile changes kept per guild.
+    pub const MAX_HISTORY: i64 = 50;
+
+    /// Records a change of the guild's profile. Older changes will be
+    /// deleted if there are more than [`MAX_HISTORY`](Self::MAX_HISTORY)
+    /// changes recorded in the guild.
+    pub async fn record(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        field: &str,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> Result<Self, QueryError> {
+        let change = sqlx::query_as::<_, Self>(
+            r#"INSERT INTO guild_profile_changes(guild_id, field, "before", "after")
+            VALUES ($1, $2, $3, $4)
+            RETURNING *"#,
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(field)
+        .bind(before)
+        .bind(after)
+        .fetch_one(&mut *conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not record guild profile change")?;
+
+        sqlx::query(
+            r"DELETE FROM guild_profile_changes
+            WHERE guild_id = $1 AND id NOT IN (
+                SELECT id FROM guild_profile_changes
+                WHERE guild_id = $1
+                ORDER BY created_at DESC
+                LIMIT $2
+            )",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(Self::MAX_HISTORY)
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not delete old guild profile changes")?;
+
+        Ok(change)
+    }
+
+    /// Gets the recent profile changes of a guild, newest first.
+    pub async fn history(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        limit: i64,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM guild_profile_changes
+            WHERE guild_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get guild profile history")
+    }
+
+    /// Deletes profile changes recorded before `before` and returns
+    /// how many were deleted.
+    pub async fn delete_before(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM guild_profile_changes WHERE created_at < $1")
+            .bind(before.naive_utc())
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete old guild profile changes")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_record_and_history(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let guild_id = Id::new(1);
+
+        GuildProfileChange::record(&mut conn, guild_id, "name", Some("Old"), Some("New")).await?;
+        GuildProfileChange::record(&mut conn, guild_id, "icon", Some("abc"), None).await?;
+
+        let history = GuildProfileChange::history(&mut conn, guild_id, 10).await?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].field, "icon");
+        assert_eq!(history[0].after, None);
+        assert_eq!(history[1].before.as_deref(), Some("Old"));
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_prune_old_changes(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let guild_id = Id::new(1);
+
+        for index in 0..=GuildProfileChange::MAX_HISTORY {
+            let name = index.to_string();
+            GuildProfileChange::record(&mut conn, guild_id, "name", None, Some(&name)).await?;
+        }
+
+        let history = GuildProfileChange::history(&mut conn, guild_id, 100).await?;
+        assert_eq!(history.len(), GuildProfileChange::MAX_HISTORY as usize);
+
+        Ok(())
+    }
+}
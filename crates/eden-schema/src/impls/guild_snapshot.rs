@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+use crate::forms::InsertGuildSnapshotForm;
+use crate::types::GuildSnapshot;
+
+impl GuildSnapshot {
+    /// Maximum amount of snapshots kept per guild.
+    pub const MAX_PER_GUILD: i64 = 25;
+
+    /// Saves a snapshot of a guild. Older snapshots will be deleted
+    /// if there are more than [`MAX_PER_GUILD`](Self::MAX_PER_GUILD)
+    /// snapshots saved in the guild.
+    pub async fn insert(
+        conn: &mut sqlx::PgConnection,
+        form: InsertGuildSnapshotForm<'_>,
+    ) -> Result<Self, QueryError> {
+        let snapshot = sqlx::query_as::<_, Self>(
+            r"INSERT INTO guild_snapshots(guild_id, created_by, reason, data)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(SqlSnowflake::new(form.created_by))
+        .bind(form.reason)
+        .bind(sqlx::types::Json(form.data))
+        .fetch_one(&mut *conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not insert guild snapshot")?;
+
+        sqlx::query(
+            r"DELETE FROM guild_snapshots
+            WHERE guild_id = $1 AND id NOT IN (
+                SELECT id FROM guild_snapshots
+                WHERE guild_id = $1
+                ORDER BY created_at DESC
+                LIMIT $2
+            )",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(Self::MAX_PER_GUILD)
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not delete old guild snapshots")?;
+
+        Ok(snapshot)
+    }
+
+    /// Gets a snapshot of a guild from its ID.
+    pub async fn from_id(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        id: Uuid,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM guild_snapshots WHERE guild_id = $1 AND id = $2")
+            .bind(SqlSnowflake::new(guild_id))
+            .bind(id)
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get guild snapshot from id")
+    }
+
+    /// Gets the recent snapshots of a guild, newest first.
+    pub async fn list(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        limit: i64,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM guild_snapshots
+            WHERE guild_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get guild snapshots")
+    }
+
+    /// Deletes snapshots taken before `before` and returns
+    /// how many were deleted.
+    pub async fn delete_before(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM guild_snapshots WHERE created_at < $1")
+            .bind(before.naive_utc())
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete old guild snapshots")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GuildSettings, GuildSnapshotData, PayerGuildSettings};
+    use chrono::TimeDelta;
+
+    fn form(data: &GuildSnapshotData) -> InsertGuildSnapshotForm<'_> {
+        InsertGuildSnapshotForm::builder()
+            .guild_id(Id::new(1))
+            .created_by(Id::new(2))
+            .reason("test")
+            .data(data)
+            .build()
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_insert_and_list(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let data = GuildSnapshotData {
+            settings: GuildSettings::builder()
+                .payers(
+                    PayerGuildSettings::builder()
+                        .allow_self_register(false)
+                        .build(),
+                )
+                .build(),
+            ..Default::default()
+        };
+
+        let snapshot = GuildSnapshot::insert(&mut conn, form(&data)).await?;
+        assert_eq!(snapshot.data, data);
+
+        let found = GuildSnapshot::from_id(&mut conn, Id::new(1), snapshot.id).await?;
+        assert_eq!(found, Some(snapshot.clone()));
+        assert!(GuildSnapshot::from_id(&mut conn, Id::new(2), snapshot.id)
+            .await?
+            .is_none());
+
+        for _ in 0..GuildSnapshot::MAX_PER_GUILD {
+            GuildSnapshot::insert(&mut conn, form(&data)).await?;
+        }
+        let list = GuildSnapshot::list(&mut conn, Id::new(1), 100).await?;
+        assert_eq!(list.len(), GuildSnapshot::MAX_PER_GUILD as usize);
+
+        let deleted =
+            GuildSnapshot::delete_before(&mut conn, Utc::now() + TimeDelta::minutes(1)).await?;
+        assert_eq!(deleted, GuildSnapshot::MAX_PER_GUILD as u64);
+
+        Ok(())
+    }
+}
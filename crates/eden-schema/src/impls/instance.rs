@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use uuid::Uuid;
+
+use crate::types::Instance;
+
+/// Key of the advisory lock held by the leader instance
+/// (`eden` in ASCII).
+const LEADER_LOCK_KEY: i64 = 0x6564_656e;
+
+impl Instance {
+    /// Gets every instance seen since the given time sorted
+    /// by when they're started.
+    pub async fn list_active(
+        conn: &mut sqlx::PgConnection,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM instances
+            WHERE last_seen_at >= $1
+            ORDER BY started_at ASC",
+        )
+        .bind(since.naive_utc())
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get active instances")
+    }
+
+    /// Records that the instance is still alive and whether
+    /// it is the leader.
+    pub async fn heartbeat(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+        name: &str,
+        is_leader: bool,
+        now: DateTime<Utc>,
+    ) -> Result<(), QueryError> {
+        sqlx::query(
+            r"INSERT INTO instances(id, name, last_seen_at, is_leader)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (id)
+                DO UPDATE SET last_seen_at = EXCLUDED.last_seen_at,
+                    is_leader = EXCLUDED.is_leader",
+        )
+        .bind(id)
+        .bind(name)
+        .bind(now.naive_utc())
+        .bind(is_leader)
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not record instance heartbeat")?;
+
+        Ok(())
+    }
+
+    pub async fn delete(conn: &mut sqlx::PgConnection, id: Uuid) -> Result<(), QueryError> {
+        sqlx::query(r"DELETE FROM instances WHERE id = $1")
+            .bind(id)
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete instance")?;
+
+        Ok(())
+    }
+
+    /// Deletes instances that are not seen since the given time
+    /// and returns how many were deleted.
+    pub async fn delete_inactive(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM instances WHERE last_seen_at < $1")
+            .bind(before.naive_utc())
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete inactive instances")
+            .map(|v| v.rows_affected())
+    }
+
+    /// Attempts to become the leader. It returns false if another
+    /// instance is the leader.
+    ///
+    /// The leadership is held until [`Instance::resign`] is called or
+    /// the connection is closed, so the connection must not be returned
+    /// to the pool while it is the leader.
+    pub async fn try_lead(conn: &mut sqlx::PgConnection) -> Result<bool, QueryError> {
+        sqlx::query_scalar::<_, bool>(r"SELECT pg_try_advisory_lock($1)")
+            .bind(LEADER_LOCK_KEY)
+            .fetch_one(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not acquire leader lock")
+    }
+
+    /// Gives up the leadership held by the connection. It returns
+    /// false if the connection is not holding it.
+    pub async fn resign(conn: &mut sqlx::PgConnection) -> Result<bool, QueryError> {
+        sqlx::query_scalar::<_, bool>(r"SELECT pg_advisory_unlock($1)")
+            .bind(LEADER_LOCK_KEY)
+            .fetch_one(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not release leader lock")
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_leader_election(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut first = pool.acquire().await.anonymize_error_into()?;
+        let mut second = pool.acquire().await.anonymize_error_into()?;
+
+        assert!(Instance::try_lead(&mut first).await?);
+        assert!(!Instance::try_lead(&mut second).await?);
+
+        assert!(!Instance::resign(&mut second).await?);
+        assert!(Instance::resign(&mut first).await?);
+        assert!(Instance::try_lead(&mut second).await?);
+        assert!(Instance::resign(&mut second).await?);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_heartbeat(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let now = Utc::now();
+
+        let leader = Uuid::new_v4();
+        let follower = Uuid::new_v4();
+        Instance::heartbeat(&mut conn, leader, "leader", false, now).await?;
+        Instance::heartbeat(&mut conn, leader, "leader", true, now).await?;
+        let long_ago = now - TimeDelta::hours(1);
+        Instance::heartbeat(&mut conn, follower, "follower", false, long_ago).await?;
+
+        let list = Instance::list_active(&mut conn, now - TimeDelta::minutes(1)).await?;
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].id, leader);
+        assert!(list[0].is_leader);
+
+        let deleted = Instance::delete_inactive(&mut conn, now - TimeDelta::minutes(1)).await?;
+        assert_eq!(deleted, 1);
+
+        Instance::delete(&mut conn, leader).await?;
+        assert!(Instance::list_active(&mut conn, now - TimeDelta::hours(2))
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+}
@@ -1,8 +1,26 @@
 mod admin;
 mod bill;
+mod blacklist;
+mod command_alias;
+mod command_cooldown;
+mod emoji_upload;
+mod guild_member_count;
+mod guild_profile_change;
 mod guild_settings;
+mod guild_snapshot;
 mod identity;
+mod instance;
 mod payer;
 mod payer_application;
+mod payer_contribution_stat;
+mod payer_status_change;
 mod payment;
+mod quiet_hours_overwrite;
+mod raid_incident;
+mod scheduled_event_reminder;
+mod temp_role_grant;
+mod undo_entry;
 mod user;
+mod user_preference;
+mod verification_challenge;
+mod voice_stat;
@@ -35,6 +35,38 @@ impl PayerApplication {
             .change_context(QueryError)
             .attach_printable("could not get payer application from user's id")
     }
+
+    /// Gets pending applications from the oldest to the newest.
+    pub async fn pending(
+        conn: &mut sqlx::PgConnection,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM payer_applications
+            WHERE accepted IS NULL
+            ORDER BY created_at ASC
+            OFFSET $1 LIMIT $2",
+        )
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get pending payer applications")
+    }
+
+    pub async fn pending_total(conn: &mut sqlx::PgConnection) -> Result<i64, QueryError> {
+        sqlx::query_scalar::<_, i64>(
+            r"SELECT COUNT(*) FROM payer_applications WHERE accepted IS NULL",
+        )
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not count pending payer applications")
+    }
 }
 
 impl PayerApplication {
@@ -97,13 +129,70 @@ impl PayerApplication {
         .change_context(QueryError)
         .attach_printable("could not update payer application")
     }
+
+    /// Accepts or rejects a pending application.
+    ///
+    /// It returns `None` if the application does not exist or it has
+    /// already been reviewed, so admins reviewing applications at the
+    /// same time cannot review the same application twice.
+    pub async fn review(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+        accepted: bool,
+        deny_reason: Option<&str>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"UPDATE payer_applications
+            SET accepted = $2,
+                deny_reason = $3
+            WHERE id = $1 AND accepted IS NULL
+            RETURNING *",
+        )
+        .bind(id)
+        .bind(accepted)
+        .bind(deny_reason)
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not review payer application")
+    }
+
+    /// Remembers that an admin has asked the applicant of a pending
+    /// application for an interview.
+    ///
+    /// It returns `None` if the application does not exist, it has
+    /// already been reviewed or another admin has already asked for
+    /// an interview.
+    pub async fn request_interview(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+        interviewer_id: Id<UserMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"UPDATE payer_applications
+            SET interviewer_id = $2,
+                interview_requested_at = (now() at TIME ZONE ('utc'))
+            WHERE id = $1 AND accepted IS NULL AND interviewer_id IS NULL
+            RETURNING *",
+        )
+        .bind(id)
+        .bind(SqlSnowflake::new(interviewer_id))
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not request interview for payer application")
+    }
 }
 
 #[allow(clippy::unwrap_used, clippy::unreadable_literal)]
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::forms::InsertPayerForm;
     use crate::test_utils;
+    use crate::types::Payer;
 
     #[sqlx::test(migrator = "crate::MIGRATOR")]
     async fn test_delete(pool: sqlx::PgPool) -> eden_utils::Result<()> {
@@ -177,6 +266,48 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_review(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let application = test_utils::generate_payer_application(&mut conn).await?;
+        assert_eq!(PayerApplication::pending_total(&mut conn).await?, 1);
+
+        let interviewer_id = Id::new(1);
+        let result = PayerApplication::request_interview(&mut conn, application.id, interviewer_id)
+            .await?
+            .unwrap();
+        assert_eq!(result.interviewer_id, Some(interviewer_id));
+        assert!(result.interview_requested_at.is_some());
+
+        // only one admin can ask for an interview
+        let result =
+            PayerApplication::request_interview(&mut conn, application.id, Id::new(2)).await?;
+        assert!(result.is_none());
+
+        let result = PayerApplication::review(&mut conn, application.id, true, None)
+            .await?
+            .unwrap();
+        assert_eq!(result.accepted, Some(true));
+
+        // it should not be reviewed twice
+        let result =
+            PayerApplication::review(&mut conn, application.id, false, Some("Too late")).await?;
+        assert!(result.is_none());
+
+        let pending = PayerApplication::pending(&mut conn, 0, 10).await?;
+        assert!(pending.is_empty());
+
+        // accepted applicants can be registered with their own usernames
+        let form = InsertPayerForm::builder()
+            .id(application.user_id)
+            .name(&application.name)
+            .java_username(&application.java_username)
+            .build();
+        Payer::insert(&mut conn, form).await?;
+
+        Ok(())
+    }
+
     #[sqlx::test(migrator = "crate::MIGRATOR")]
     async fn test_insert_with_bedrock_username(pool: sqlx::PgPool) -> eden_utils::Result<()> {
         let mut conn = pool.acquire().await.anonymize_error_into()?;
@@ -0,0 +1,302 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use rust_decimal::Decimal;
+use sqlx::types::Json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+
+use crate::types::{PayerContributionStat, PreferenceKey, PublicPayerStatsPreference};
+
+#[derive(Debug)]
+struct BillSummary {
+    id: i64,
+    deadline: NaiveDate,
+    currency: String,
+    price: Decimal,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Aggregate {
+    paid_bills: i32,
+    totals: BTreeMap<String, Decimal>,
+    current_streak: i32,
+    longest_streak: i32,
+}
+
+/// Aggregates contributions of a payer from bills sorted by their deadline.
+///
+/// Bills before the payer has registered are not counted against their
+/// streak and neither are unpaid bills which are not overdue yet.
+fn aggregate(
+    registered_at: NaiveDate,
+    bills: &[BillSummary],
+    paid: Option<&HashSet<i64>>,
+    today: NaiveDate,
+) -> Aggregate {
+    let mut output = Aggregate::default();
+    for bill in bills {
+        if paid.is_some_and(|v| v.contains(&bill.id)) {
+            output.paid_bills += 1;
+            *output.totals.entry(bill.currency.clone()).or_default() += bill.price;
+            output.current_streak += 1;
+            output.longest_streak = output.longest_streak.max(output.current_streak);
+        } else if bill.deadline >= registered_at && bill.deadline < today {
+            output.current_streak = 0;
+        }
+    }
+    output
+}
+
+impl PayerContributionStat {
+    pub async fn from_payer(
+        conn: &mut sqlx::PgConnection,
+        payer_id: Id<UserMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM payer_contribution_stats WHERE payer_id = $1")
+            .bind(SqlSnowflake::new(payer_id))
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get payer contribution stats")
+    }
+
+    /// Gets the top contributors who allowed their stats to be shown publicly.
+    pub async fn public_top(
+        conn: &mut sqlx::PgConnection,
+        limit: i64,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT stats.* FROM payer_contribution_stats stats
+            INNER JOIN user_preferences pref
+                ON pref.user_id = stats.payer_id AND pref.key = $1
+            WHERE pref.value = 'true'::jsonb AND stats.paid_bills > 0
+            ORDER BY stats.paid_bills DESC, stats.current_streak DESC
+            LIMIT $2",
+        )
+        .bind(PublicPayerStatsPreference::KEY)
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get top public payer contribution stats")
+    }
+
+    /// Recomputes the contribution stats of every payer.
+    ///
+    /// This goes through every bill and successful payment so it should
+    /// only be called periodically instead of per command invocation.
+    pub async fn refresh_all(
+        conn: &mut sqlx::PgConnection,
+        today: NaiveDate,
+    ) -> Result<u64, QueryError> {
+        let payers = sqlx::query_as::<_, (SqlSnowflake<UserMarker>, NaiveDateTime)>(
+            r"SELECT id, created_at FROM payers",
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get payers")?;
+
+        let bills = sqlx::query_as::<_, (i64, NaiveDate, String, Decimal)>(
+            r"SELECT id, deadline, currency, price FROM bills ORDER BY deadline, id",
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get bills")?
+        .into_iter()
+        .map(|(id, deadline, currency, price)| BillSummary {
+            id,
+            deadline,
+            currency,
+            price,
+        })
+        .collect::<Vec<_>>();
+
+        let payments = sqlx::query_as::<_, (SqlSnowflake<UserMarker>, i64)>(
+            r"SELECT payer_id, bill_id FROM payments
+            WHERE data->'status'->>'type' = 'success'",
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get successful payments")?;
+
+        let mut paid = HashMap::<Id<UserMarker>, HashSet<i64>>::new();
+        for (payer_id, bill_id) in payments {
+            paid.entry(payer_id.into()).or_default().insert(bill_id);
+        }
+
+        let mut refreshed = 0;
+        for (payer_id, created_at) in payers {
+            let payer_id: Id<UserMarker> = payer_id.into();
+            let stats = aggregate(created_at.date(), &bills, paid.get(&payer_id), today);
+
+            sqlx::query(
+                r"INSERT INTO payer_contribution_stats(
+                    payer_id, paid_bills, totals, current_streak, longest_streak
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (payer_id)
+                    DO UPDATE SET paid_bills = EXCLUDED.paid_bills,
+                        totals = EXCLUDED.totals,
+                        current_streak = EXCLUDED.current_streak,
+                        longest_streak = EXCLUDED.longest_streak,
+                        refreshed_at = (now() at TIME ZONE ('utc'))",
+            )
+            .bind(SqlSnowflake::new(payer_id))
+            .bind(stats.paid_bills)
+            .bind(Json(stats.totals))
+            .bind(stats.current_streak)
+            .bind(stats.longest_streak)
+            .execute(&mut *conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable_lazy(|| {
+                format!("could not refresh contribution stats of payer {payer_id}")
+            })?;
+
+            refreshed += 1;
+        }
+
+        Ok(refreshed)
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forms::UpdatePaymentForm;
+    use crate::payment::{PaymentData, PaymentStatus};
+    use crate::test_utils;
+    use crate::types::{Payment, UserPreferences};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    fn bill(id: i64, deadline: NaiveDate) -> BillSummary {
+        BillSummary {
+            id,
+            deadline,
+            currency: "PHP".into(),
+            price: Decimal::from(20),
+        }
+    }
+
+    #[test]
+    fn should_group_totals_by_currency() {
+        let bills = [
+            bill(1, date(1, 10)),
+            BillSummary {
+                currency: "USD".into(),
+                price: Decimal::from(5),
+                ..bill(2, date(2, 10))
+            },
+            bill(3, date(3, 10)),
+        ];
+
+        let paid = HashSet::from([1, 2, 3]);
+        let output = aggregate(date(1, 1), &bills, Some(&paid), date(4, 1));
+        assert_eq!(
+            output.totals,
+            BTreeMap::from([
+                ("PHP".into(), Decimal::from(40)),
+                ("USD".into(), Decimal::from(5)),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_aggregate_streaks() {
+        let bills = [
+            bill(1, date(1, 10)),
+            bill(2, date(2, 10)),
+            bill(3, date(3, 10)),
+            bill(4, date(4, 10)),
+            bill(5, date(5, 10)),
+        ];
+
+        // missed the 3rd bill and the 5th bill is not overdue yet
+        let paid = HashSet::from([1, 2, 4]);
+        let output = aggregate(date(1, 1), &bills, Some(&paid), date(5, 1));
+        assert_eq!(
+            output,
+            Aggregate {
+                paid_bills: 3,
+                totals: BTreeMap::from([("PHP".into(), Decimal::from(60))]),
+                current_streak: 1,
+                longest_streak: 2,
+            }
+        );
+
+        // bills before registering should not break the streak
+        let paid = HashSet::from([3, 4]);
+        let output = aggregate(date(2, 15), &bills, Some(&paid), date(5, 1));
+        assert_eq!(output.current_streak, 2);
+
+        let output = aggregate(date(1, 1), &bills, None, date(6, 1));
+        assert_eq!(output, Aggregate::default());
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_refresh_all(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let payer = test_utils::generate_payer(&mut conn).await?;
+        let bill = test_utils::generate_bill(&mut conn).await?;
+        let payment = test_utils::generate_payment(&mut conn, bill.id, payer.id).await?;
+
+        let form = UpdatePaymentForm::builder()
+            .data(PaymentData {
+                status: PaymentStatus::Success,
+                ..payment.data
+            })
+            .build();
+
+        Payment::update(&mut conn, payment.id, form)
+            .await
+            .anonymize_error()?;
+
+        let refreshed = PayerContributionStat::refresh_all(&mut conn, date(8, 19))
+            .await
+            .anonymize_error()?;
+        assert_eq!(refreshed, 1);
+
+        let stats = PayerContributionStat::from_payer(&mut conn, payer.id)
+            .await
+            .anonymize_error()?
+            .unwrap();
+
+        assert_eq!(stats.paid_bills, 1);
+        assert_eq!(stats.totals, BTreeMap::from([("PHP".into(), bill.price)]));
+        assert_eq!(stats.current_streak, 1);
+
+        // it should not be shown publicly unless the payer allowed it
+        let top = PayerContributionStat::public_top(&mut conn, 10)
+            .await
+            .anonymize_error()?;
+        assert!(top.is_empty());
+
+        UserPreferences::set::<PublicPayerStatsPreference>(&mut conn, payer.id, &true)
+            .await
+            .anonymize_error()?;
+
+        let top = PayerContributionStat::public_top(&mut conn, 10)
+            .await
+            .anonymize_error()?;
+        assert_eq!(top, vec![stats]);
+
+        Ok(())
+    }
+}
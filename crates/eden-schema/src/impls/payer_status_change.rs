@@ -0,0 +1,266 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+
+use crate::types::PayerStatusChange;
+
+impl PayerStatusChange {
+    /// Postgres channel notified whenever a payer status change is recorded.
+    pub const NOTIFY_CHANNEL: &'static str = "payer_status_changes";
+
+    /// Changes that failed to be applied this many times are left
+    /// unprocessed and not retried anymore.
+    pub const MAX_ATTEMPTS: i32 = 5;
+
+    /// Gets the oldest unprocessed changes that can still be retried
+    /// and locks them until the transaction ends.
+    ///
+    /// Changes locked by other transactions are skipped so multiple
+    /// processes won't apply the same change at the same time.
+    pub async fn lock_pending(
+        conn: &mut sqlx::PgConnection,
+        limit: i64,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM payer_status_changes
+            WHERE processed_at IS NULL AND attempts < $1
+            ORDER BY id ASC
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED",
+        )
+        .bind(Self::MAX_ATTEMPTS)
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get pending payer status changes")
+    }
+
+    /// Claims the oldest unprocessed changes that can still be retried,
+    /// oldest first, so they can be applied outside of the transaction.
+    ///
+    /// Changes claimed by other processes are skipped unless they were
+    /// claimed before `stale_before` (like if the process has crashed).
+    pub async fn claim_pending(
+        conn: &mut sqlx::PgConnection,
+        limit: i64,
+        now: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<Vec<Self>, QueryError> {
+        let mut changes = sqlx::query_as::<_, Self>(
+            r"UPDATE payer_status_changes
+            SET claimed_at = $1
+            WHERE id IN (
+                SELECT id FROM payer_status_changes
+                WHERE processed_at IS NULL AND attempts < $2
+                    AND (claimed_at IS NULL OR claimed_at < $3)
+                ORDER BY id ASC
+                LIMIT $4
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *",
+        )
+        .bind(now.naive_utc())
+        .bind(Self::MAX_ATTEMPTS)
+        .bind(stale_before.naive_utc())
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not claim pending payer status changes")?;
+
+        changes.sort_by_key(|v| v.id);
+        Ok(changes)
+    }
+}
+
+impl PayerStatusChange {
+    pub async fn mark_processed(conn: &mut sqlx::PgConnection, id: i64) -> Result<(), QueryError> {
+        sqlx::query(
+            r"UPDATE payer_status_changes
+            SET processed_at = (now() at TIME ZONE ('utc'))
+            WHERE id = $1",
+        )
+        .bind(id)
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not mark payer status change as processed")?;
+
+        Ok(())
+    }
+
+    /// Records a failed attempt to apply the change so it
+    /// can be claimed and retried later.
+    pub async fn record_failure(conn: &mut sqlx::PgConnection, id: i64) -> Result<(), QueryError> {
+        sqlx::query(
+            r"UPDATE payer_status_changes
+            SET attempts = attempts + 1, claimed_at = NULL
+            WHERE id = $1",
+        )
+        .bind(id)
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not record failed payer status change")?;
+
+        Ok(())
+    }
+
+    /// Recomputes the status of every payer since bills may become
+    /// overdue without any changes to the database.
+    ///
+    /// It returns the amount of payers with changed status.
+    pub async fn refresh_all(conn: &mut sqlx::PgConnection) -> Result<i64, QueryError> {
+        sqlx::query_scalar::<_, i64>(r"SELECT COUNT(*) FROM payers WHERE refresh_payer_status(id)")
+            .fetch_one(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not refresh status of all payers")
+    }
+
+    /// Records the current status of every payer as a change so it
+    /// will be applied again (like after the payer role has changed).
+    ///
+    /// It returns the amount of recorded changes.
+    pub async fn resync_all(conn: &mut sqlx::PgConnection) -> Result<u64, QueryError> {
+        let changes = sqlx::query(
+            r"INSERT INTO payer_status_changes (payer_id, previous_status, status)
+            SELECT id, status, status FROM payers",
+        )
+        .execute(&mut *conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not resync status of all payers")?
+        .rows_affected();
+
+        // listeners are only notified once the transaction is committed
+        sqlx::query(r"SELECT pg_notify($1, '')")
+            .bind(Self::NOTIFY_CHANNEL)
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not notify resynced payer status changes")?;
+
+        Ok(changes)
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forms::{InsertBillForm, UpdatePaymentForm};
+    use crate::payment::{PaymentData, PaymentStatus};
+    use crate::test_utils;
+    use crate::types::{Bill, Payer, PayerStatus, Payment};
+    use chrono::{TimeDelta, Utc};
+    use rust_decimal::Decimal;
+    use twilight_model::id::Id;
+
+    async fn latest_status(conn: &mut sqlx::PgConnection) -> eden_utils::Result<PayerStatus> {
+        let changes = PayerStatusChange::lock_pending(conn, 100).await?;
+        Ok(changes.last().unwrap().status)
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_status_changes(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        // bills due before the payer has registered are not counted
+        test_utils::generate_bill(&mut conn).await?;
+        let payer = test_utils::generate_payer(&mut conn).await?;
+        assert_eq!(payer.status, PayerStatus::Settled);
+
+        let changes = PayerStatusChange::lock_pending(&mut conn, 100).await?;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].previous_status, None);
+
+        let form = InsertBillForm::builder()
+            .created_by(Id::new(123456))
+            .currency("PHP")
+            .deadline(Utc::now().date_naive() + TimeDelta::days(7))
+            .price(Decimal::from(20))
+            .build();
+
+        let bill = Bill::insert(&mut conn, form).await.anonymize_error()?;
+        assert_eq!(latest_status(&mut conn).await?, PayerStatus::Active);
+
+        let payment = test_utils::generate_payment(&mut conn, bill.id, payer.id).await?;
+        let data = PaymentData {
+            status: PaymentStatus::Success,
+            ..payment.data
+        };
+        let form = UpdatePaymentForm::builder().data(data).build();
+        Payment::update(&mut conn, payment.id, form).await?;
+        assert_eq!(latest_status(&mut conn).await?, PayerStatus::Settled);
+
+        // nothing has changed since then
+        assert_eq!(PayerStatusChange::refresh_all(&mut conn).await?, 0);
+
+        let payer = Payer::from_id(&mut conn, payer.id).await?.unwrap();
+        assert_eq!(payer.status, PayerStatus::Settled);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_mark_processed(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        test_utils::generate_payer(&mut conn).await?;
+
+        let changes = PayerStatusChange::lock_pending(&mut conn, 100).await?;
+        for _ in 0..PayerStatusChange::MAX_ATTEMPTS {
+            PayerStatusChange::record_failure(&mut conn, changes[0].id).await?;
+        }
+        assert!(PayerStatusChange::lock_pending(&mut conn, 100)
+            .await?
+            .is_empty());
+
+        assert_eq!(PayerStatusChange::resync_all(&mut conn).await?, 1);
+        let changes = PayerStatusChange::lock_pending(&mut conn, 100).await?;
+        assert_eq!(changes.len(), 1);
+
+        PayerStatusChange::mark_processed(&mut conn, changes[0].id).await?;
+        assert!(PayerStatusChange::lock_pending(&mut conn, 100)
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_claim_pending(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        test_utils::generate_payer(&mut conn).await?;
+
+        let now = Utc::now();
+        let stale_before = now - TimeDelta::minutes(5);
+        let changes = PayerStatusChange::claim_pending(&mut conn, 100, now, stale_before).await?;
+        assert_eq!(changes.len(), 1);
+
+        // claimed changes are not claimed again until they become stale
+        let claimed = PayerStatusChange::claim_pending(&mut conn, 100, now, stale_before).await?;
+        assert!(claimed.is_empty());
+
+        let later = now + TimeDelta::minutes(10);
+        let stale_before = later - TimeDelta::minutes(5);
+        let claimed = PayerStatusChange::claim_pending(&mut conn, 100, later, stale_before).await?;
+        assert_eq!(claimed.len(), 1);
+
+        // failed changes can be claimed again right away
+        PayerStatusChange::record_failure(&mut conn, changes[0].id).await?;
+        let claimed = PayerStatusChange::claim_pending(&mut conn, 100, later, later).await?;
+        assert_eq!(claimed.len(), 1);
+
+        Ok(())
+    }
+}
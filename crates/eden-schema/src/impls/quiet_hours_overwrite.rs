@@ -0,0 +1,98 @@
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker};
+use twilight_model::id::Id;
+
+use crate::types::QuietHoursOverwrite;
+
+impl QuietHoursOverwrite {
+    pub async fn from_channel(
+        conn: &mut sqlx::PgConnection,
+        channel_id: Id<ChannelMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM quiet_hours_overwrites WHERE channel_id = $1")
+            .bind(SqlSnowflake::new(channel_id))
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get quiet hours overwrite from channel id")
+    }
+
+    /// Saves the overwrite of the channel before quiet hours started.
+    ///
+    /// The overwrite that has been saved first is kept if the channel
+    /// already has one, so the original overwrite is not replaced
+    /// with the restricted one.
+    pub async fn save(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+        permissions: Option<(i64, i64)>,
+    ) -> Result<Self, QueryError> {
+        let (allow, deny) = permissions.unzip();
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO quiet_hours_overwrites(channel_id, guild_id, allow, deny)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (channel_id)
+                DO UPDATE SET channel_id = quiet_hours_overwrites.channel_id
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(channel_id))
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(allow)
+        .bind(deny)
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not save quiet hours overwrite")
+    }
+
+    pub async fn delete(
+        conn: &mut sqlx::PgConnection,
+        channel_id: Id<ChannelMarker>,
+    ) -> Result<(), QueryError> {
+        sqlx::query(r"DELETE FROM quiet_hours_overwrites WHERE channel_id = $1")
+            .bind(SqlSnowflake::new(channel_id))
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete quiet hours overwrite")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_save_keeps_original(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let guild_id = Id::new(1);
+        let channel_id = Id::new(2);
+
+        let saved = QuietHoursOverwrite::save(&mut conn, guild_id, channel_id, None).await?;
+        assert_eq!(saved.permissions, None);
+
+        // restricted overwrite must not replace the original one
+        let saved =
+            QuietHoursOverwrite::save(&mut conn, guild_id, channel_id, Some((0, 2048))).await?;
+        assert_eq!(saved.permissions, None);
+
+        QuietHoursOverwrite::delete(&mut conn, channel_id).await?;
+        let saved = QuietHoursOverwrite::from_channel(&mut conn, channel_id).await?;
+        assert!(saved.is_none());
+
+        let saved =
+            QuietHoursOverwrite::save(&mut conn, guild_id, channel_id, Some((1024, 0))).await?;
+        assert_eq!(saved.permissions, Some((1024, 0)));
+
+        Ok(())
+    }
+}
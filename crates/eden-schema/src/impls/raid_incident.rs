@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+use crate::forms::InsertRaidIncidentForm;
+use crate::types::RaidIncident;
+
+impl RaidIncident {
+    pub async fn from_id(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM raid_incidents WHERE id = $1 LIMIT 1")
+            .bind(id)
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get raid incident from id")
+    }
+
+    /// Gets the raid incident with an active lockdown in a guild.
+    pub async fn active(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM raid_incidents
+            WHERE guild_id = $1 AND ended_at IS NULL
+            LIMIT 1",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get active raid incident")
+    }
+}
+
+impl RaidIncident {
+    pub async fn insert(
+        conn: &mut sqlx::PgConnection,
+        form: InsertRaidIncidentForm,
+    ) -> Result<Self, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO raid_incidents(
+                guild_id, joins, removed_everyone_permissions,
+                previous_verification_level, disabled_invites
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(form.joins)
+        .bind(form.removed_everyone_permissions)
+        .bind(form.previous_verification_level)
+        .bind(form.disabled_invites)
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not insert raid incident")
+    }
+
+    /// Ends the lockdown of a raid incident.
+    ///
+    /// It returns `None` if the incident does not exist or
+    /// its lockdown has already ended.
+    pub async fn end(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+        ended_by: Id<UserMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"UPDATE raid_incidents
+            SET ended_at = (now() at TIME ZONE ('utc')),
+                ended_by = $2
+            WHERE id = $1 AND ended_at IS NULL
+            RETURNING *",
+        )
+        .bind(id)
+        .bind(SqlSnowflake::new(ended_by))
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not end raid incident")
+    }
+
+    /// Deletes a raid incident. It is used if the lockdown
+    /// could not be applied to the guild.
+    pub async fn delete(conn: &mut sqlx::PgConnection, id: Uuid) -> Result<(), QueryError> {
+        sqlx::query(r"DELETE FROM raid_incidents WHERE id = $1")
+            .bind(id)
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete raid incident")?;
+
+        Ok(())
+    }
+
+    /// Deletes raid incidents whose lockdown has ended before `before`
+    /// and returns how many were deleted. Active lockdowns are kept
+    /// since they are needed to restore the guild.
+    pub async fn delete_ended_before(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM raid_incidents WHERE ended_at < $1")
+            .bind(before.naive_utc())
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete old raid incidents")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    fn form(guild_id: Id<GuildMarker>) -> InsertRaidIncidentForm {
+        InsertRaidIncidentForm::builder()
+            .guild_id(guild_id)
+            .joins(15)
+            .removed_everyone_permissions(Some(2048))
+            .build()
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_insert(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let incident = RaidIncident::insert(&mut conn, form(guild_id)).await?;
+        assert!(incident.is_active());
+        assert_eq!(incident.joins, 15);
+        assert_eq!(incident.removed_everyone_permissions, Some(2048));
+        assert_eq!(incident.previous_verification_level, None);
+        assert!(!incident.disabled_invites);
+
+        // there should be only one active lockdown per guild
+        assert!(RaidIncident::insert(&mut conn, form(guild_id))
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_end(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let user_id = Id::new(2);
+        let incident = RaidIncident::insert(&mut conn, form(guild_id)).await?;
+
+        let result = RaidIncident::active(&mut conn, guild_id).await?;
+        assert_eq!(result.map(|v| v.id), Some(incident.id));
+
+        let ended = RaidIncident::end(&mut conn, incident.id, user_id)
+            .await?
+            .unwrap();
+
+        assert!(!ended.is_active());
+        assert_eq!(ended.ended_by, Some(user_id));
+
+        // it should not end the same lockdown twice
+        let result = RaidIncident::end(&mut conn, incident.id, user_id).await?;
+        assert!(result.is_none());
+
+        let result = RaidIncident::active(&mut conn, guild_id).await?;
+        assert!(result.is_none());
+
+        let result = RaidIncident::from_id(&mut conn, incident.id).await?;
+        assert!(result.is_some());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_delete_ended_before(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let ended = RaidIncident::insert(&mut conn, form(Id::new(1))).await?;
+        RaidIncident::end(&mut conn, ended.id, Id::new(3)).await?;
+        let active = RaidIncident::insert(&mut conn, form(Id::new(2))).await?;
+
+        let before = Utc::now() + TimeDelta::minutes(1);
+        let deleted = RaidIncident::delete_ended_before(&mut conn, before).await?;
+        assert_eq!(deleted, 1);
+
+        // active lockdowns must be kept until they end
+        let result = RaidIncident::from_id(&mut conn, active.id).await?;
+        assert!(result.is_some());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{GuildMarker, ScheduledEventMarker};
+use twilight_model::id::Id;
+
+use crate::types::ScheduledEventReminder;
+
+impl ScheduledEventReminder {
+    /// Marks the reminder of the event as posted. It returns `None`
+    /// if the reminder has already been posted.
+    pub async fn mark_sent(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        event_id: Id<ScheduledEventMarker>,
+        lead_minutes: i32,
+        starts_at: DateTime<Utc>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO scheduled_event_reminders(guild_id, event_id, lead_minutes, starts_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (event_id, lead_minutes, starts_at) DO NOTHING
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(SqlSnowflake::new(event_id))
+        .bind(lead_minutes)
+        .bind(starts_at.naive_utc())
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not mark scheduled event reminder as sent")
+    }
+
+    /// Deletes reminders of events that started before `before` and
+    /// returns how many were deleted.
+    pub async fn delete_before(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM scheduled_event_reminders WHERE starts_at < $1")
+            .bind(before.naive_utc())
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete old scheduled event reminders")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_mark_sent(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let guild_id = Id::new(1);
+        let event_id = Id::new(2);
+        let starts_at = DateTime::from_timestamp(1_724_400_000, 0).unwrap();
+
+        let sent =
+            ScheduledEventReminder::mark_sent(&mut conn, guild_id, event_id, 60, starts_at).await?;
+        assert!(sent.is_some());
+
+        // the same reminder should not be posted twice
+        let sent =
+            ScheduledEventReminder::mark_sent(&mut conn, guild_id, event_id, 60, starts_at).await?;
+        assert!(sent.is_none());
+
+        // other reminders of the same event are not affected
+        let sent =
+            ScheduledEventReminder::mark_sent(&mut conn, guild_id, event_id, 1440, starts_at)
+                .await?;
+        assert!(sent.is_some());
+
+        // the event is rescheduled
+        let starts_at = starts_at + TimeDelta::hours(2);
+        let sent =
+            ScheduledEventReminder::mark_sent(&mut conn, guild_id, event_id, 60, starts_at).await?;
+        assert!(sent.is_some());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+use crate::forms::InsertTempRoleGrantForm;
+use crate::types::TempRoleGrant;
+
+impl TempRoleGrant {
+    pub async fn from_id(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM temp_role_grants WHERE id = $1 LIMIT 1")
+            .bind(id)
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get temporary role grant from id")
+    }
+
+    /// Gets the active temporary grant of a role given to a member.
+    pub async fn active(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        role_id: Id<RoleMarker>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM temp_role_grants
+            WHERE guild_id = $1 AND user_id = $2 AND role_id = $3
+                AND removed_at IS NULL
+            LIMIT 1",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(SqlSnowflake::new(user_id))
+        .bind(SqlSnowflake::new(role_id))
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get active temporary role grant")
+    }
+
+    /// Gets all active temporary role grants in a guild sorted
+    /// by which grant expires first.
+    pub async fn active_list(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM temp_role_grants
+            WHERE guild_id = $1 AND removed_at IS NULL
+            ORDER BY expires_at ASC",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get active temporary role grants")
+    }
+}
+
+impl TempRoleGrant {
+    /// Records a temporary role grant. If the member has an active
+    /// grant of the same role, its expiry will be replaced instead.
+    pub async fn grant(
+        conn: &mut sqlx::PgConnection,
+        form: InsertTempRoleGrantForm,
+    ) -> Result<Self, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO temp_role_grants(guild_id, user_id, role_id, granted_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (guild_id, user_id, role_id) WHERE removed_at IS NULL
+                DO UPDATE SET granted_by = EXCLUDED.granted_by,
+                    expires_at = EXCLUDED.expires_at
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(SqlSnowflake::new(form.user_id))
+        .bind(SqlSnowflake::new(form.role_id))
+        .bind(SqlSnowflake::new(form.granted_by))
+        .bind(form.expires_at.naive_utc())
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not insert temporary role grant")
+    }
+
+    /// Marks the temporary role grant as removed.
+    ///
+    /// It returns `None` if the grant does not exist or it is
+    /// already removed.
+    pub async fn mark_removed(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"UPDATE temp_role_grants
+            SET removed_at = (now() at TIME ZONE ('utc'))
+            WHERE id = $1 AND removed_at IS NULL
+            RETURNING *",
+        )
+        .bind(id)
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not mark temporary role grant as removed")
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeDelta, Utc};
+
+    fn form(expires_at: DateTime<Utc>) -> InsertTempRoleGrantForm {
+        InsertTempRoleGrantForm::builder()
+            .guild_id(Id::new(1))
+            .user_id(Id::new(2))
+            .role_id(Id::new(3))
+            .granted_by(Id::new(4))
+            .expires_at(expires_at)
+            .build()
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_grant(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let now = Utc::now();
+        let first = TempRoleGrant::grant(&mut conn, form(now + TimeDelta::days(1))).await?;
+        assert!(first.removed_at.is_none());
+
+        // granting the same role again should extend the active grant
+        let expires_at = now + TimeDelta::days(7);
+        let second = TempRoleGrant::grant(&mut conn, form(expires_at)).await?;
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.expires_at.timestamp(), expires_at.timestamp());
+
+        let list = TempRoleGrant::active_list(&mut conn, Id::new(1)).await?;
+        assert_eq!(list.len(), 1);
+
+        let active = TempRoleGrant::active(&mut conn, Id::new(1), Id::new(2), Id::new(3)).await?;
+        assert_eq!(active.map(|v| v.id), Some(first.id));
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_mark_removed(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let grant = TempRoleGrant::grant(&mut conn, form(Utc::now())).await?;
+        let removed = TempRoleGrant::mark_removed(&mut conn, grant.id)
+            .await?
+            .unwrap();
+        assert!(removed.removed_at.is_some());
+
+        let result = TempRoleGrant::mark_removed(&mut conn, grant.id).await?;
+        assert!(result.is_none());
+
+        let list = TempRoleGrant::active_list(&mut conn, Id::new(1)).await?;
+        assert!(list.is_empty());
+
+        // it should be able to grant the same role again once it is removed
+        let regranted = TempRoleGrant::grant(&mut conn, form(Utc::now())).await?;
+        assert_ne!(grant.id, regranted.id);
+
+        let result = TempRoleGrant::from_id(&mut conn, grant.id).await?;
+        assert!(result.is_some());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+use crate::forms::InsertUndoEntryForm;
+use crate::types::UndoEntry;
+
+impl UndoEntry {
+    /// Maximum amount of admin operations remembered per guild.
+    pub const MAX_PER_GUILD: i64 = 10;
+
+    /// Remembers an admin operation. The oldest operations will be
+    /// forgotten if there are more than [`MAX_PER_GUILD`](Self::MAX_PER_GUILD)
+    /// operations remembered in the guild.
+    pub async fn insert(
+        conn: &mut sqlx::PgConnection,
+        form: InsertUndoEntryForm<'_>,
+    ) -> Result<Self, QueryError> {
+        let entry = sqlx::query_as::<_, Self>(
+            r"INSERT INTO undo_entries(guild_id, actor_id, description, action)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(SqlSnowflake::new(form.actor_id))
+        .bind(form.description)
+        .bind(sqlx::types::Json(form.action))
+        .fetch_one(&mut *conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not insert undo entry")?;
+
+        sqlx::query(
+            r"DELETE FROM undo_entries
+            WHERE guild_id = $1 AND id NOT IN (
+                SELECT id FROM undo_entries
+                WHERE guild_id = $1
+                ORDER BY created_at DESC
+                LIMIT $2
+            )",
+        )
+        .bind(SqlSnowflake::new(form.guild_id))
+        .bind(Self::MAX_PER_GUILD)
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not delete old undo entries")?;
+
+        Ok(entry)
+    }
+
+    /// Gets the admin operations of a guild performed after `since`,
+    /// newest first.
+    pub async fn list(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"SELECT * FROM undo_entries
+            WHERE guild_id = $1 AND created_at >= $2
+            ORDER BY created_at DESC",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(since.naive_utc())
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get undo entries")
+    }
+
+    /// Removes an admin operation performed after `since` so it
+    /// cannot be reverted twice.
+    ///
+    /// It returns `None` if it does not exist, it is performed
+    /// before `since` or it has been taken already.
+    pub async fn take(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"DELETE FROM undo_entries
+            WHERE guild_id = $1 AND id = $2 AND created_at >= $3
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(id)
+        .bind(since.naive_utc())
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not take undo entry")
+    }
+
+    /// Deletes admin operations performed before `before` and
+    /// returns how many were deleted.
+    pub async fn delete_before(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM undo_entries WHERE created_at < $1")
+            .bind(before.naive_utc())
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete old undo entries")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use serde_json::{json, Value};
+
+    fn form(action: &Value) -> InsertUndoEntryForm<'_> {
+        InsertUndoEntryForm::builder()
+            .guild_id(Id::new(1))
+            .actor_id(Id::new(2))
+            .description("test")
+            .action(action)
+            .build()
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_insert_and_take(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let action = json!({ "type": "test" });
+        let since = Utc::now() - TimeDelta::minutes(1);
+
+        let entry = UndoEntry::insert(&mut conn, form(&action)).await?;
+        assert_eq!(entry.action, action);
+
+        let list = UndoEntry::list(&mut conn, Id::new(1), since).await?;
+        assert_eq!(list, vec![entry.clone()]);
+        assert!(UndoEntry::list(&mut conn, Id::new(2), since)
+            .await?
+            .is_empty());
+
+        // it should not be taken from other guilds or twice
+        assert!(UndoEntry::take(&mut conn, Id::new(2), entry.id, since)
+            .await?
+            .is_none());
+        let taken = UndoEntry::take(&mut conn, Id::new(1), entry.id, since).await?;
+        assert_eq!(taken, Some(entry.clone()));
+        assert!(UndoEntry::take(&mut conn, Id::new(1), entry.id, since)
+            .await?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_limit_and_expire(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let action = json!({ "type": "test" });
+        let since = Utc::now() - TimeDelta::minutes(1);
+
+        for _ in 0..=UndoEntry::MAX_PER_GUILD {
+            UndoEntry::insert(&mut conn, form(&action)).await?;
+        }
+        let list = UndoEntry::list(&mut conn, Id::new(1), since).await?;
+        assert_eq!(list.len(), UndoEntry::MAX_PER_GUILD as usize);
+
+        let later = Utc::now() + TimeDelta::minutes(1);
+        assert!(UndoEntry::list(&mut conn, Id::new(1), later)
+            .await?
+            .is_empty());
+
+        let deleted = UndoEntry::delete_before(&mut conn, later).await?;
+        assert_eq!(deleted, UndoEntry::MAX_PER_GUILD as u64);
+
+        Ok(())
+    }
+}
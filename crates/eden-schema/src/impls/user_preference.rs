@@ -0,0 +1,185 @@
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use serde_json::Value;
+use sqlx::types::Json;
+use tracing::warn;
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+
+use crate::types::{
+    DmRemindersOptOutPreference, LocalePreference, PreferenceKey, PublicPayerStatsPreference,
+    TimezonePreference, UserPreferences,
+};
+
+/// Deserializes the stored preference value. It falls back to the
+/// default value if the stored value is somehow invalid.
+fn decode<K: PreferenceKey>(value: Option<Value>) -> K::Value {
+    let Some(value) = value else {
+        return K::default_value();
+    };
+
+    serde_json::from_value::<K::Value>(value).unwrap_or_else(|error| {
+        warn!(%error, "got invalid user preference value for {:?}", K::KEY);
+        K::default_value()
+    })
+}
+
+fn take(rows: &mut Vec<(String, Json<Value>)>, key: &str) -> Option<Value> {
+    let index = rows.iter().position(|(k, _)| k == key)?;
+    let (_, Json(value)) = rows.swap_remove(index);
+    Some(value)
+}
+
+impl UserPreferences {
+    /// Gets a specific preference of a user.
+    pub async fn get<K: PreferenceKey>(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+    ) -> Result<K::Value, QueryError> {
+        let value = Self::find::<K>(conn, user_id).await?;
+        Ok(value.unwrap_or_else(K::default_value))
+    }
+
+    /// Gets a specific preference of a user. It returns `None`
+    /// if the user has not set it yet.
+    pub async fn find<K: PreferenceKey>(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+    ) -> Result<Option<K::Value>, QueryError> {
+        let value = sqlx::query_scalar::<_, Json<Value>>(
+            r"SELECT value FROM user_preferences
+            WHERE user_id = $1 AND key = $2
+            LIMIT 1",
+        )
+        .bind(SqlSnowflake::new(user_id))
+        .bind(K::KEY)
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable_lazy(|| format!("could not get user preference {:?}", K::KEY))?;
+
+        Ok(value.map(|v| decode::<K>(Some(v.0))))
+    }
+
+    /// Gets all known preferences of a user.
+    pub async fn get_all(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+    ) -> Result<Self, QueryError> {
+        let mut rows = sqlx::query_as::<_, (String, Json<Value>)>(
+            r"SELECT key, value FROM user_preferences WHERE user_id = $1",
+        )
+        .bind(SqlSnowflake::new(user_id))
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get user preferences")?;
+
+        let locale = take(&mut rows, LocalePreference::KEY);
+        let dm_reminders_opt_out = take(&mut rows, DmRemindersOptOutPreference::KEY);
+        let timezone = take(&mut rows, TimezonePreference::KEY);
+        let public_payer_stats = take(&mut rows, PublicPayerStatsPreference::KEY);
+
+        Ok(Self {
+            locale: decode::<LocalePreference>(locale),
+            dm_reminders_opt_out: decode::<DmRemindersOptOutPreference>(dm_reminders_opt_out),
+            timezone: decode::<TimezonePreference>(timezone),
+            public_payer_stats: decode::<PublicPayerStatsPreference>(public_payer_stats),
+        })
+    }
+
+    /// Sets a specific preference of a user.
+    pub async fn set<K: PreferenceKey>(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+        value: &K::Value,
+    ) -> Result<(), QueryError> {
+        sqlx::query(
+            r"INSERT INTO user_preferences(user_id, key, value)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, key)
+                DO UPDATE SET value = EXCLUDED.value",
+        )
+        .bind(SqlSnowflake::new(user_id))
+        .bind(K::KEY)
+        .bind(Json(value))
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable_lazy(|| format!("could not set user preference {:?}", K::KEY))?;
+
+        Ok(())
+    }
+
+    /// Resets a specific preference of a user back to its default value.
+    pub async fn reset<K: PreferenceKey>(
+        conn: &mut sqlx::PgConnection,
+        user_id: Id<UserMarker>,
+    ) -> Result<(), QueryError> {
+        sqlx::query(r"DELETE FROM user_preferences WHERE user_id = $1 AND key = $2")
+            .bind(SqlSnowflake::new(user_id))
+            .bind(K::KEY)
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable_lazy(|| format!("could not reset user preference {:?}", K::KEY))?;
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_get_and_set(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let user_id = Id::new(1);
+
+        let value = UserPreferences::get::<DmRemindersOptOutPreference>(&mut conn, user_id).await?;
+        assert!(!value);
+
+        UserPreferences::set::<DmRemindersOptOutPreference>(&mut conn, user_id, &true).await?;
+        let value = UserPreferences::get::<DmRemindersOptOutPreference>(&mut conn, user_id).await?;
+        assert!(value);
+
+        UserPreferences::set::<TimezonePreference>(&mut conn, user_id, &"+08:00".into()).await?;
+        let all = UserPreferences::get_all(&mut conn, user_id).await?;
+        assert_eq!(
+            all,
+            UserPreferences {
+                locale: LocalePreference::default_value(),
+                dm_reminders_opt_out: true,
+                timezone: "+08:00".into(),
+                public_payer_stats: false,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_reset(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let user_id = Id::new(1);
+
+        UserPreferences::set::<LocalePreference>(&mut conn, user_id, &"fr".into()).await?;
+        UserPreferences::reset::<LocalePreference>(&mut conn, user_id).await?;
+
+        let value = UserPreferences::get::<LocalePreference>(&mut conn, user_id).await?;
+        assert_eq!(value, LocalePreference::default_value());
+
+        let value = UserPreferences::find::<LocalePreference>(&mut conn, user_id).await?;
+        assert_eq!(value, None);
+
+        Ok(())
+    }
+}
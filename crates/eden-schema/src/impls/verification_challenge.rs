@@ -0,0 +1,99 @@
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+use crate::types::VerificationChallenge;
+
+impl VerificationChallenge {
+    /// Saves the expected answer of a member's verification prompt.
+    ///
+    /// Previous challenges of the member are deleted, so only
+    /// the latest verification prompt can be answered.
+    pub async fn insert(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        answer: &str,
+    ) -> Result<Self, QueryError> {
+        Self::delete_for_member(&mut *conn, guild_id, user_id).await?;
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO verification_challenges(guild_id, user_id, answer)
+            VALUES ($1, $2, $3)
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(SqlSnowflake::new(user_id))
+        .bind(answer)
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not insert verification challenge")
+    }
+
+    pub async fn from_nonce(
+        conn: &mut sqlx::PgConnection,
+        nonce: Uuid,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(r"SELECT * FROM verification_challenges WHERE nonce = $1")
+            .bind(nonce)
+            .fetch_optional(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not get verification challenge from nonce")
+    }
+
+    /// Deletes all challenges of a member once the member is
+    /// verified or kicked from the guild.
+    pub async fn delete_for_member(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM verification_challenges WHERE guild_id = $1 AND user_id = $2")
+            .bind(SqlSnowflake::new(guild_id))
+            .bind(SqlSnowflake::new(user_id))
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete verification challenges of member")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_only_keep_latest_challenge(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let guild_id = Id::new(1);
+        let user_id = Id::new(2);
+
+        let old = VerificationChallenge::insert(&mut conn, guild_id, user_id, "1234").await?;
+        let new = VerificationChallenge::insert(&mut conn, guild_id, user_id, "5678").await?;
+
+        assert!(VerificationChallenge::from_nonce(&mut conn, old.nonce)
+            .await?
+            .is_none());
+
+        let challenge = VerificationChallenge::from_nonce(&mut conn, new.nonce)
+            .await?
+            .unwrap();
+        assert_eq!(challenge.answer, "5678");
+
+        let deleted =
+            VerificationChallenge::delete_for_member(&mut conn, guild_id, user_id).await?;
+        assert_eq!(deleted, 1);
+
+        Ok(())
+    }
+}
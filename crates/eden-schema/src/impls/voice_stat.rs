@@ -0,0 +1,176 @@
+use chrono::NaiveDate;
+use eden_utils::error::exts::*;
+use eden_utils::sql::util::SqlSnowflake;
+use eden_utils::sql::QueryError;
+use eden_utils::Result;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::types::{VoiceLeaderboardEntry, VoiceStat};
+
+impl VoiceStat {
+    /// Adds voice activity duration (in seconds) of a member in a specific day.
+    pub async fn add(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        day: NaiveDate,
+        seconds: i64,
+    ) -> Result<Self, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"INSERT INTO voice_stats(guild_id, user_id, day, seconds)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (guild_id, user_id, day)
+                DO UPDATE SET seconds = voice_stats.seconds + EXCLUDED.seconds
+            RETURNING *",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(SqlSnowflake::new(user_id))
+        .bind(day)
+        .bind(seconds)
+        .fetch_one(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not add voice stat")
+    }
+
+    /// Gets the most voice active members in a guild since `since`.
+    pub async fn top(
+        conn: &mut sqlx::PgConnection,
+        guild_id: Id<GuildMarker>,
+        since: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<VoiceLeaderboardEntry>, QueryError> {
+        sqlx::query_as::<_, VoiceLeaderboardEntry>(
+            r"SELECT user_id, SUM(seconds)::BIGINT AS seconds
+            FROM voice_stats
+            WHERE guild_id = $1 AND day >= $2
+            GROUP BY user_id
+            ORDER BY seconds DESC
+            LIMIT $3",
+        )
+        .bind(SqlSnowflake::new(guild_id))
+        .bind(since)
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not get top voice active members")
+    }
+
+    /// Deletes voice stats recorded before `day` and returns
+    /// how many were deleted.
+    pub async fn delete_before(
+        conn: &mut sqlx::PgConnection,
+        day: NaiveDate,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(r"DELETE FROM voice_stats WHERE day < $1")
+            .bind(day)
+            .execute(conn)
+            .await
+            .into_eden_error()
+            .change_context(QueryError)
+            .attach_printable("could not delete old voice stats")
+            .map(|v| v.rows_affected())
+    }
+}
+
+#[allow(clippy::unwrap_used, clippy::unreadable_literal)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_add(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let user_id = Id::new(2);
+        let day = NaiveDate::from_ymd_opt(2024, 8, 15).unwrap();
+
+        VoiceStat::add(&mut conn, guild_id, user_id, day, 60)
+            .await
+            .anonymize_error()?;
+
+        let stat = VoiceStat::add(&mut conn, guild_id, user_id, day, 30)
+            .await
+            .anonymize_error()?;
+
+        assert_eq!(stat.seconds, 90);
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_top(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let old_day = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let day = NaiveDate::from_ymd_opt(2024, 8, 15).unwrap();
+
+        VoiceStat::add(&mut conn, guild_id, Id::new(2), day, 60)
+            .await
+            .anonymize_error()?;
+
+        VoiceStat::add(&mut conn, guild_id, Id::new(3), day, 120)
+            .await
+            .anonymize_error()?;
+
+        // this should not be included
+        VoiceStat::add(&mut conn, guild_id, Id::new(2), old_day, 1000)
+            .await
+            .anonymize_error()?;
+
+        let entries = VoiceStat::top(&mut conn, guild_id, day, 10)
+            .await
+            .anonymize_error()?;
+
+        assert_eq!(
+            entries,
+            vec![
+                VoiceLeaderboardEntry {
+                    user_id: Id::new(3),
+                    seconds: 120
+                },
+                VoiceLeaderboardEntry {
+                    user_id: Id::new(2),
+                    seconds: 60
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_delete_before(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let guild_id = Id::new(1);
+        let old_day = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let day = NaiveDate::from_ymd_opt(2024, 8, 15).unwrap();
+
+        VoiceStat::add(&mut conn, guild_id, Id::new(2), old_day, 60)
+            .await
+            .anonymize_error()?;
+
+        VoiceStat::add(&mut conn, guild_id, Id::new(2), day, 60)
+            .await
+            .anonymize_error()?;
+
+        let deleted = VoiceStat::delete_before(&mut conn, day)
+            .await
+            .anonymize_error()?;
+        assert_eq!(deleted, 1);
+
+        let entries = VoiceStat::top(&mut conn, guild_id, old_day, 10)
+            .await
+            .anonymize_error()?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].seconds, 60);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+/// User whose interactions are ignored by Eden.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlacklistedUser {
+    pub user_id: Id<UserMarker>,
+    pub created_at: DateTime<Utc>,
+    pub blacklisted_by: Id<UserMarker>,
+    pub reason: Option<String>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for BlacklistedUser {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let user_id = row.try_get::<SqlSnowflake<UserMarker>, _>("user_id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let blacklisted_by = row.try_get::<SqlSnowflake<UserMarker>, _>("blacklisted_by")?;
+        let reason = row.try_get("reason")?;
+
+        Ok(Self {
+            user_id: user_id.into(),
+            created_at: naive_to_dt(created_at),
+            blacklisted_by: blacklisted_by.into(),
+            reason,
+        })
+    }
+}
+
+/// Guild other than the local guild where Eden is allowed to stay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowedGuild {
+    pub guild_id: Id<GuildMarker>,
+    pub created_at: DateTime<Utc>,
+    pub allowed_by: Id<UserMarker>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for AllowedGuild {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let allowed_by = row.try_get::<SqlSnowflake<UserMarker>, _>("allowed_by")?;
+
+        Ok(Self {
+            guild_id: guild_id.into(),
+            created_at: naive_to_dt(created_at),
+            allowed_by: allowed_by.into(),
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use std::collections::BTreeMap;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+/// Custom command of a guild that runs an existing command
+/// with preset arguments (like `/rent` for `/payer pay_bill`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandAlias {
+    pub guild_id: Id<GuildMarker>,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Id<UserMarker>,
+    /// Path of the aliased command (like `payer pay_bill`).
+    pub command: String,
+    /// Preset arguments keyed by option name. Values are kept as
+    /// they're typed and parsed once the alias is used.
+    pub arguments: BTreeMap<String, String>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for CommandAlias {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let name = row.try_get("name")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let created_by = row.try_get::<SqlSnowflake<UserMarker>, _>("created_by")?;
+        let command = row.try_get("command")?;
+        let arguments =
+            row.try_get::<sqlx::types::Json<BTreeMap<String, String>>, _>("arguments")?;
+
+        Ok(Self {
+            guild_id: guild_id.into(),
+            name,
+            created_at: naive_to_dt(created_at),
+            created_by: created_by.into(),
+            command,
+            arguments: arguments.0,
+        })
+    }
+}
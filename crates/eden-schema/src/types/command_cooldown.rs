@@ -0,0 +1,28 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+
+/// Cooldown of a command bucket persisted so it won't be
+/// reset after Eden restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandCooldown {
+    pub user_id: Id<UserMarker>,
+    pub bucket: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for CommandCooldown {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let user_id = row.try_get::<SqlSnowflake<UserMarker>, _>("user_id")?;
+        let bucket = row.try_get("bucket")?;
+        let expires_at = row.try_get::<NaiveDateTime, _>("expires_at")?;
+
+        Ok(Self {
+            user_id: user_id.into(),
+            bucket,
+            expires_at: naive_to_dt(expires_at),
+        })
+    }
+}
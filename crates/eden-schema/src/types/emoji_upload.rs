@@ -0,0 +1,65 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::{EmojiMarker, GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+/// Custom emoji requested to be uploaded to a guild through Eden.
+///
+/// Every upload is kept even after it is reviewed so admins
+/// can find out who added which emoji.
+#[derive(Debug, Clone)]
+pub struct EmojiUpload {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub guild_id: Id<GuildMarker>,
+    pub requested_by: Id<UserMarker>,
+    pub name: String,
+    /// Image data URI of the emoji.
+    ///
+    /// It is `None` once the upload has been reviewed.
+    pub image: Option<String>,
+    /// It is `None` if the upload is still waiting for approval.
+    pub approved: Option<bool>,
+    pub reviewed_by: Option<Id<UserMarker>>,
+    /// ID of the emoji once it is uploaded to the guild.
+    pub emoji_id: Option<Id<EmojiMarker>>,
+}
+
+impl EmojiUpload {
+    /// Whether the upload is still waiting for approval.
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.approved.is_none()
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for EmojiUpload {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let updated_at = row.try_get::<Option<NaiveDateTime>, _>("updated_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let requested_by = row.try_get::<SqlSnowflake<UserMarker>, _>("requested_by")?;
+        let name = row.try_get("name")?;
+        let image = row.try_get("image")?;
+        let approved = row.try_get("approved")?;
+        let reviewed_by = row.try_get::<Option<SqlSnowflake<UserMarker>>, _>("reviewed_by")?;
+        let emoji_id = row.try_get::<Option<SqlSnowflake<EmojiMarker>>, _>("emoji_id")?;
+
+        Ok(Self {
+            id,
+            created_at: naive_to_dt(created_at),
+            updated_at: updated_at.map(naive_to_dt),
+            guild_id: guild_id.into(),
+            requested_by: requested_by.into(),
+            name,
+            image,
+            approved,
+            reviewed_by: reviewed_by.map(Into::into),
+            emoji_id: emoji_id.map(Into::into),
+        })
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDate;
+use eden_utils::sql::util::SqlSnowflake;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+
+/// Number of members a guild has at the end of a single day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuildMemberCount {
+    pub guild_id: Id<GuildMarker>,
+    pub day: NaiveDate,
+    pub members: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for GuildMemberCount {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let day = row.try_get("day")?;
+        let members = row.try_get("members")?;
+
+        Ok(Self {
+            guild_id: guild_id.into(),
+            day,
+            members,
+        })
+    }
+}
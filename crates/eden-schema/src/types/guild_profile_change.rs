@@ -0,0 +1,38 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+/// Change of a guild's profile (like its name or icon).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuildProfileChange {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub guild_id: Id<GuildMarker>,
+    /// Which part of the profile has changed (like `name` or `icon`).
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for GuildProfileChange {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let field = row.try_get("field")?;
+        let before = row.try_get("before")?;
+        let after = row.try_get("after")?;
+
+        Ok(Self {
+            id,
+            created_at: naive_to_dt(created_at),
+            guild_id: guild_id.into(),
+            field,
+            before,
+            after,
+        })
+    }
+}
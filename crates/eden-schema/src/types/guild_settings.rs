@@ -1,12 +1,13 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, NaiveTime, Offset, Utc};
 use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::ops::Deref;
-use twilight_model::id::{marker::GuildMarker, Id};
+use twilight_model::id::marker::{ChannelMarker, GuildMarker, RoleMarker};
+use twilight_model::id::Id;
 use typed_builder::TypedBuilder;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GuildSettingsRow {
     pub id: Id<GuildMarker>,
     pub created_at: DateTime<Utc>,
@@ -59,6 +60,30 @@ pub struct GuildSettings {
     pub version: GuildSettingsVersion,
     #[builder(default)]
     pub payers: PayerGuildSettings,
+    #[builder(default)]
+    pub verification: VerificationGuildSettings,
+    #[builder(default)]
+    pub auto_role: AutoRoleGuildSettings,
+    #[builder(default)]
+    pub logs: LogsGuildSettings,
+    #[builder(default)]
+    pub raid: RaidGuildSettings,
+    #[builder(default)]
+    pub emoji: EmojiGuildSettings,
+    #[builder(default)]
+    pub word_filter: WordFilterGuildSettings,
+    #[builder(default)]
+    pub introductions: IntroductionsGuildSettings,
+    #[builder(default)]
+    pub quiet_hours: QuietHoursGuildSettings,
+    #[builder(default)]
+    pub scheduled_events: ScheduledEventsGuildSettings,
+    #[builder(default)]
+    pub father_belt: FatherBeltGuildSettings,
+    #[builder(default)]
+    pub alerts: AlertsGuildSettings,
+    #[builder(default)]
+    pub translation: TranslationGuildSettings,
 }
 
 impl Default for GuildSettings {
@@ -66,6 +91,18 @@ impl Default for GuildSettings {
         Self {
             version: GuildSettingsVersion::V1,
             payers: PayerGuildSettings::default(),
+            verification: VerificationGuildSettings::default(),
+            auto_role: AutoRoleGuildSettings::default(),
+            logs: LogsGuildSettings::default(),
+            raid: RaidGuildSettings::default(),
+            emoji: EmojiGuildSettings::default(),
+            word_filter: WordFilterGuildSettings::default(),
+            introductions: IntroductionsGuildSettings::default(),
+            quiet_hours: QuietHoursGuildSettings::default(),
+            scheduled_events: ScheduledEventsGuildSettings::default(),
+            father_belt: FatherBeltGuildSettings::default(),
+            alerts: AlertsGuildSettings::default(),
+            translation: TranslationGuildSettings::default(),
         }
     }
 }
@@ -75,12 +112,358 @@ impl Default for GuildSettings {
 pub struct PayerGuildSettings {
     #[builder(default = false)]
     pub allow_self_register: bool,
+    /// Role given to payers in good standing and removed
+    /// from payers who have lapsed.
+    #[builder(default)]
+    pub role_id: Option<Id<RoleMarker>>,
 }
 
 impl Default for PayerGuildSettings {
     fn default() -> Self {
         Self {
             allow_self_register: true,
+            role_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationMode {
+    /// New members only need to press the verify button.
+    #[default]
+    Button,
+    /// New members need to press the button with the code
+    /// shown from the verification prompt.
+    Captcha,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct VerificationGuildSettings {
+    /// Whether new members must verify themselves first before
+    /// they can access the rest of the guild.
+    #[builder(default = false)]
+    pub enabled: bool,
+    /// Channel where verification prompts will be sent.
+    #[builder(default)]
+    pub channel_id: Option<Id<ChannelMarker>>,
+    /// Role that will be given to members once they're verified.
+    #[builder(default)]
+    pub role_id: Option<Id<RoleMarker>>,
+    #[builder(default)]
+    pub mode: VerificationMode,
+    /// How long (in minutes) unverified members will be kicked
+    /// after they joined the guild. It is disabled if not set.
+    #[builder(default)]
+    pub kick_after_minutes: Option<u32>,
+}
+
+impl VerificationGuildSettings {
+    /// Whether the verification gate is enabled and all of its
+    /// required options are set.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.channel_id.is_some() && self.role_id.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct AutoRoleGuildSettings {
+    /// Roles that will be given to new members automatically.
+    #[builder(default)]
+    pub role_ids: Vec<Id<RoleMarker>>,
+    /// How long (in seconds) to wait before giving roles to new
+    /// members. It is useful to dodge raid joins.
+    #[builder(default)]
+    pub delay_seconds: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct LogsGuildSettings {
+    /// Channel where member related events and failures are logged.
+    #[builder(default)]
+    pub member_channel_id: Option<Id<ChannelMarker>>,
+    /// Channel where changes of the server's profile (like its
+    /// name or icon) are logged.
+    #[builder(default)]
+    pub server_channel_id: Option<Id<ChannelMarker>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LockdownMode {
+    /// Members (except admins) cannot send messages by removing
+    /// `Send Messages` permission from the `@everyone` role.
+    #[default]
+    RestrictMessages,
+    /// Raises the guild's verification level to the highest level.
+    RaiseVerification,
+    /// Pauses invites of the guild so no one can join with them.
+    PauseInvites,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct RaidGuildSettings {
+    /// Whether the bot should detect raids and lock down the guild
+    /// automatically if a raid is detected.
+    #[builder(default = false)]
+    pub enabled: bool,
+    /// Amount of members joined within a minute to consider it as a raid.
+    #[builder(default = 10)]
+    pub joins_per_minute: u32,
+    #[builder(default)]
+    pub lockdown_mode: LockdownMode,
+}
+
+impl Default for RaidGuildSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            joins_per_minute: 10,
+            lockdown_mode: LockdownMode::default(),
         }
     }
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct EmojiGuildSettings {
+    /// Whether emojis uploaded with `/emoji add` by members without
+    /// the `Manage Expressions` permission must be approved by admins.
+    #[builder(default = true)]
+    pub require_approval: bool,
+}
+
+impl Default for EmojiGuildSettings {
+    fn default() -> Self {
+        Self {
+            require_approval: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WordFilterAction {
+    /// Replies to the message with a warning (by chance).
+    #[default]
+    Reply,
+    /// Deletes the message.
+    Delete,
+    /// Deletes the message and re-posts it censored with a webhook
+    /// imitating the author.
+    Repost,
+    /// Sends a warning to the author through direct messages.
+    DirectMessage,
+    /// Only logs the message to the member log channel.
+    Log,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct WordFilterGuildSettings {
+    /// What to do with messages that contain bad words.
+    #[builder(default)]
+    pub action: WordFilterAction,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct IntroductionsGuildSettings {
+    /// Whether Eden should reply to member introductions.
+    #[builder(default = true)]
+    pub enabled: bool,
+    /// Channel where members introduce themselves. Eden replies to
+    /// introductions from any channel if it is not set.
+    #[builder(default)]
+    pub channel_id: Option<Id<ChannelMarker>>,
+}
+
+impl Default for IntroductionsGuildSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            channel_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct QuietHoursGuildSettings {
+    /// Whether Eden should stop sending non-essential messages
+    /// (like father belt replies) during quiet hours.
+    #[builder(default = false)]
+    pub enabled: bool,
+    /// When quiet hours start in the guild's local time.
+    #[builder(default)]
+    pub start: Option<NaiveTime>,
+    /// When quiet hours end in the guild's local time. Quiet hours
+    /// continue until the next day if it is earlier than `start`.
+    #[builder(default)]
+    pub end: Option<NaiveTime>,
+    /// UTC offset (in minutes) of the guild's local time.
+    #[builder(default)]
+    pub utc_offset_minutes: i32,
+    /// Channels where members cannot send messages during quiet hours.
+    ///
+    /// These channels are expected to allow members to send messages
+    /// outside quiet hours, since Eden removes the restriction once
+    /// quiet hours are over.
+    #[builder(default)]
+    pub read_only_channel_ids: Vec<Id<ChannelMarker>>,
+}
+
+impl QuietHoursGuildSettings {
+    /// Gets the UTC offset of the guild's local time.
+    ///
+    /// It falls back to UTC if the stored offset is out of range.
+    #[must_use]
+    pub fn utc_offset(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.utc_offset_minutes.saturating_mul(60))
+            .unwrap_or_else(|| Utc.fix())
+    }
+
+    /// Whether quiet hours are in effect at the given time.
+    #[must_use]
+    pub fn is_quiet_at(&self, now: DateTime<Utc>) -> bool {
+        let (true, Some(start), Some(end)) = (self.enabled, self.start, self.end) else {
+            return false;
+        };
+
+        let time = now.with_timezone(&self.utc_offset()).time();
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct ScheduledEventsGuildSettings {
+    /// Channel where reminders of upcoming scheduled events are
+    /// posted. Reminders are disabled if it is not set.
+    #[builder(default)]
+    pub channel_id: Option<Id<ChannelMarker>>,
+    /// Whether reminders should mention members who are
+    /// interested in the event.
+    #[builder(default = false)]
+    pub ping_interested: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct FatherBeltGuildSettings {
+    /// Whether father belt (replies to introductions, bad words
+    /// and screaming messages) is enabled.
+    #[builder(default = true)]
+    pub enabled: bool,
+}
+
+impl Default for FatherBeltGuildSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct AlertsGuildSettings {
+    /// Channel where Eden alerts the administrators. It overrides
+    /// the `bot.local_guild.alert_channel_id` setting if it is set.
+    #[builder(default)]
+    pub channel_id: Option<Id<ChannelMarker>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, TypedBuilder)]
+#[serde(default)]
+pub struct TranslationGuildSettings {
+    /// Whether members can translate messages with the
+    /// "Translate message" command.
+    #[builder(default)]
+    pub enabled: bool,
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn time(hour: u32, min: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(hour, min, 0)
+    }
+
+    fn at(hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, hour, min, 0)
+            .single()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn should_enable_features_for_existing_settings() {
+        let settings = serde_json::from_str::<GuildSettings>(
+            r#"{"_v":"v1","introductions":{"channel_id":"1234"}}"#,
+        )
+        .unwrap();
+
+        assert!(settings.father_belt.enabled);
+        assert!(settings.introductions.enabled);
+        assert_eq!(settings.introductions.channel_id, Some(Id::new(1234)));
+        assert_eq!(settings.alerts.channel_id, None);
+    }
+
+    #[test]
+    fn should_check_quiet_hours_within_the_same_day() {
+        let settings = QuietHoursGuildSettings::builder()
+            .enabled(true)
+            .start(time(13, 0))
+            .end(time(15, 30))
+            .build();
+
+        assert!(!settings.is_quiet_at(at(12, 59)));
+        assert!(settings.is_quiet_at(at(13, 0)));
+        assert!(settings.is_quiet_at(at(15, 29)));
+        assert!(!settings.is_quiet_at(at(15, 30)));
+    }
+
+    #[test]
+    fn should_check_quiet_hours_overnight_with_offset() {
+        // 22:00 to 06:00 in UTC+08:00
+        let settings = QuietHoursGuildSettings::builder()
+            .enabled(true)
+            .start(time(22, 0))
+            .end(time(6, 0))
+            .utc_offset_minutes(8 * 60)
+            .build();
+
+        assert!(!settings.is_quiet_at(at(13, 59)));
+        assert!(settings.is_quiet_at(at(14, 0)));
+        assert!(settings.is_quiet_at(at(21, 59)));
+        assert!(!settings.is_quiet_at(at(22, 0)));
+    }
+
+    #[test]
+    fn should_not_be_quiet_if_disabled_or_incomplete() {
+        let mut settings = QuietHoursGuildSettings::builder()
+            .start(time(0, 0))
+            .end(time(23, 59))
+            .build();
+        assert!(!settings.is_quiet_at(at(12, 0)));
+
+        settings.enabled = true;
+        assert!(settings.is_quiet_at(at(12, 0)));
+
+        settings.end = settings.start;
+        assert!(!settings.is_quiet_at(at(12, 0)));
+
+        settings.end = None;
+        assert!(!settings.is_quiet_at(at(12, 0)));
+    }
+}
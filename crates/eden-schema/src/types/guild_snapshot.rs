@@ -0,0 +1,70 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::BTreeMap;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+use super::GuildSettings;
+
+/// Copy of a guild's data taken before a risky admin operation
+/// so it can be restored with `/admin restore-snapshot`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuildSnapshot {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub guild_id: Id<GuildMarker>,
+    pub created_by: Id<UserMarker>,
+    /// Which operation the snapshot was taken before.
+    pub reason: String,
+    pub data: GuildSnapshotData,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for GuildSnapshot {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let created_by = row.try_get::<SqlSnowflake<UserMarker>, _>("created_by")?;
+        let reason = row.try_get("reason")?;
+        let data = row.try_get::<sqlx::types::Json<GuildSnapshotData>, _>("data")?;
+
+        Ok(Self {
+            id,
+            created_at: naive_to_dt(created_at),
+            guild_id: guild_id.into(),
+            created_by: created_by.into(),
+            reason,
+            data: data.0,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GuildSnapshotVersion {
+    #[default]
+    V1,
+}
+
+/// Guild data kept in a snapshot.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GuildSnapshotData {
+    #[serde(rename = "_v")]
+    pub version: GuildSnapshotVersion,
+    pub settings: GuildSettings,
+    pub command_aliases: Vec<GuildSnapshotCommandAlias>,
+}
+
+/// Command alias kept in a snapshot.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct GuildSnapshotCommandAlias {
+    pub name: String,
+    pub created_by: Id<UserMarker>,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: BTreeMap<String, String>,
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::naive_to_dt;
+use sqlx::Row;
+use uuid::Uuid;
+
+/// Eden process connected to the database. Multiple instances
+/// can share the same database but only the leader runs recurring
+/// tasks and registers commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instance {
+    pub id: Uuid,
+    pub name: String,
+    pub started_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub is_leader: bool,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for Instance {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let name = row.try_get("name")?;
+        let started_at = row.try_get::<NaiveDateTime, _>("started_at")?;
+        let last_seen_at = row.try_get::<NaiveDateTime, _>("last_seen_at")?;
+        let is_leader = row.try_get("is_leader")?;
+
+        Ok(Self {
+            id,
+            name,
+            started_at: naive_to_dt(started_at),
+            last_seen_at: naive_to_dt(last_seen_at),
+            is_leader,
+        })
+    }
+}
@@ -1,19 +1,59 @@
 mod admin;
 mod bill;
+mod blacklist;
+mod command_alias;
+mod command_cooldown;
+mod emoji_upload;
+mod guild_member_count;
+mod guild_profile_change;
 mod guild_settings;
+mod guild_snapshot;
 mod identity;
+mod instance;
 mod payer;
 mod payer_application;
+mod payer_contribution_stat;
+mod payer_status_change;
 mod payment;
+mod quiet_hours_overwrite;
+mod raid_incident;
+mod scheduled_event_reminder;
+mod temp_role_grant;
+mod undo_entry;
 mod user;
+mod user_preference;
+mod verification_challenge;
+mod voice_stat;
 
 pub use self::admin::*;
 pub use self::bill::*;
+pub use self::blacklist::*;
+pub use self::command_alias::*;
+pub use self::command_cooldown::*;
+pub use self::emoji_upload::*;
+pub use self::guild_member_count::*;
+pub use self::guild_profile_change::*;
 pub use self::guild_settings::{
-    GuildSettings, GuildSettingsRow, GuildSettingsVersion, PayerGuildSettings,
+    AlertsGuildSettings, AutoRoleGuildSettings, EmojiGuildSettings, FatherBeltGuildSettings,
+    GuildSettings, GuildSettingsRow, GuildSettingsVersion, IntroductionsGuildSettings,
+    LockdownMode, LogsGuildSettings, PayerGuildSettings, QuietHoursGuildSettings,
+    RaidGuildSettings, ScheduledEventsGuildSettings, TranslationGuildSettings,
+    VerificationGuildSettings, VerificationMode, WordFilterAction, WordFilterGuildSettings,
 };
+pub use self::guild_snapshot::*;
 pub use self::identity::*;
+pub use self::instance::*;
 pub use self::payer::*;
 pub use self::payer_application::*;
+pub use self::payer_contribution_stat::*;
+pub use self::payer_status_change::*;
 pub use self::payment::*;
+pub use self::quiet_hours_overwrite::*;
+pub use self::raid_incident::*;
+pub use self::scheduled_event_reminder::*;
+pub use self::temp_role_grant::*;
+pub use self::undo_entry::*;
 pub use self::user::*;
+pub use self::user_preference::*;
+pub use self::verification_challenge::*;
+pub use self::voice_stat::*;
@@ -1,6 +1,9 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use serde::{Deserialize, Serialize};
 use sqlx::Row;
+use std::str::FromStr;
+use thiserror::Error;
 use twilight_model::id::{marker::UserMarker, Id};
 
 #[derive(Debug, Clone)]
@@ -8,20 +11,82 @@ pub struct Payer {
     pub id: Id<UserMarker>,
     pub created_at: DateTime<Utc>,
     pub name: String,
+    pub status: PayerStatus,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// Whether a payer is up to date with their bills. Only bills
+/// due after the payer has registered are counted.
+///
+/// It is computed by the database whenever bills or payments change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayerStatus {
+    /// Some bills are not paid yet but none of them is overdue.
+    Active,
+    /// Every bill is paid.
+    Settled,
+    /// Some bills are not paid after their deadline.
+    Lapsed,
+}
+
+impl PayerStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Settled => "settled",
+            Self::Lapsed => "lapsed",
+        }
+    }
+
+    /// Whether the payer is in good standing (not lapsed).
+    #[must_use]
+    pub const fn is_in_good_standing(self) -> bool {
+        !matches!(self, Self::Lapsed)
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("unknown payer status {0:?}")]
+pub struct UnknownPayerStatus(String);
+
+impl FromStr for PayerStatus {
+    type Err = UnknownPayerStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(Self::Active),
+            "settled" => Ok(Self::Settled),
+            "lapsed" => Ok(Self::Lapsed),
+            _ => Err(UnknownPayerStatus(s.to_string())),
+        }
+    }
+}
+
+impl PayerStatus {
+    pub(crate) fn decode(row: &sqlx::postgres::PgRow, column: &str) -> Result<Self, sqlx::Error> {
+        let value = row.try_get::<String, _>(column)?;
+        value.parse().map_err(|e| sqlx::Error::ColumnDecode {
+            index: column.into(),
+            source: Box::new(e),
+        })
+    }
+}
+
 impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for Payer {
     fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
         let id = row.try_get::<SqlSnowflake<UserMarker>, _>("id")?;
         let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
         let updated_at = row.try_get::<Option<NaiveDateTime>, _>("updated_at")?;
         let name = row.try_get("name")?;
+        let status = PayerStatus::decode(row, "status")?;
 
         Ok(Self {
             id: id.into(),
             created_at: naive_to_dt(created_at),
             name,
+            status,
             updated_at: updated_at.map(naive_to_dt),
         })
     }
@@ -16,6 +16,16 @@ pub struct PayerApplication {
     pub accepted: Option<bool>,
     pub answer: String,
     pub deny_reason: Option<String>,
+    pub interviewer_id: Option<Id<UserMarker>>,
+    pub interview_requested_at: Option<DateTime<Utc>>,
+}
+
+impl PayerApplication {
+    /// Whether the application is still waiting to be reviewed.
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.accepted.is_none()
+    }
 }
 
 impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for PayerApplication {
@@ -30,6 +40,10 @@ impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for PayerApplication {
         let accepted = row.try_get("accepted")?;
         let answer = row.try_get("answer")?;
         let deny_reason = row.try_get("deny_reason")?;
+        let interviewer_id =
+            row.try_get::<Option<SqlSnowflake<UserMarker>>, _>("interviewer_id")?;
+        let interview_requested_at =
+            row.try_get::<Option<NaiveDateTime>, _>("interview_requested_at")?;
 
         Ok(Self {
             id,
@@ -42,6 +56,8 @@ impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for PayerApplication {
             accepted,
             answer,
             deny_reason,
+            interviewer_id: interviewer_id.map(Into::into),
+            interview_requested_at: interview_requested_at.map(naive_to_dt),
         })
     }
 }
@@ -0,0 +1,46 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use twilight_model::id::marker::UserMarker;
+use twilight_model::id::Id;
+
+/// Cached aggregate of a payer's contributions to the server.
+///
+/// These are refreshed periodically, so they may not reflect
+/// recently settled payments right away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayerContributionStat {
+    pub payer_id: Id<UserMarker>,
+    /// Amount of bills the payer has successfully paid.
+    pub paid_bills: i32,
+    /// Total amount paid from all successfully paid bills
+    /// grouped by their currency.
+    pub totals: BTreeMap<String, Decimal>,
+    /// Amount of consecutive bills paid up to the latest bill.
+    pub current_streak: i32,
+    pub longest_streak: i32,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for PayerContributionStat {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        let payer_id = row.try_get::<SqlSnowflake<UserMarker>, _>("payer_id")?;
+        let paid_bills = row.try_get("paid_bills")?;
+        let totals = row.try_get::<sqlx::types::Json<BTreeMap<String, Decimal>>, _>("totals")?;
+        let current_streak = row.try_get("current_streak")?;
+        let longest_streak = row.try_get("longest_streak")?;
+        let refreshed_at = row.try_get::<NaiveDateTime, _>("refreshed_at")?;
+
+        Ok(Self {
+            payer_id: payer_id.into(),
+            paid_bills,
+            totals: totals.0,
+            current_streak,
+            longest_streak,
+            refreshed_at: naive_to_dt(refreshed_at),
+        })
+    }
+}
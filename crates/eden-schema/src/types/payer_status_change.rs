@@ -0,0 +1,46 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::{marker::UserMarker, Id};
+
+use super::PayerStatus;
+
+/// Status transition of a payer recorded by the database, waiting
+/// to be applied by Eden (like giving or removing the payer role).
+#[derive(Debug, Clone)]
+pub struct PayerStatusChange {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub payer_id: Id<UserMarker>,
+    /// It is `None` if the payer has just registered.
+    pub previous_status: Option<PayerStatus>,
+    pub status: PayerStatus,
+    /// How many times Eden has failed to apply this change.
+    pub attempts: i32,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for PayerStatusChange {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let payer_id = row.try_get::<SqlSnowflake<UserMarker>, _>("payer_id")?;
+        let previous_status = match row.try_get::<Option<String>, _>("previous_status")? {
+            Some(..) => Some(PayerStatus::decode(row, "previous_status")?),
+            None => None,
+        };
+        let status = PayerStatus::decode(row, "status")?;
+        let attempts = row.try_get("attempts")?;
+        let processed_at = row.try_get::<Option<NaiveDateTime>, _>("processed_at")?;
+
+        Ok(Self {
+            id,
+            created_at: naive_to_dt(created_at),
+            payer_id: payer_id.into(),
+            previous_status,
+            status,
+            attempts,
+            processed_at: processed_at.map(naive_to_dt),
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker};
+use twilight_model::id::Id;
+
+/// Permissions of the `@everyone` overwrite in a read-only channel
+/// before quiet hours started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuietHoursOverwrite {
+    pub channel_id: Id<ChannelMarker>,
+    pub guild_id: Id<GuildMarker>,
+    pub created_at: DateTime<Utc>,
+    /// Allowed and denied permissions of the overwrite.
+    ///
+    /// It is `None` if the channel has no overwrite for `@everyone`.
+    pub permissions: Option<(i64, i64)>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for QuietHoursOverwrite {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let channel_id = row.try_get::<SqlSnowflake<ChannelMarker>, _>("channel_id")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let allow = row.try_get::<Option<i64>, _>("allow")?;
+        let deny = row.try_get::<Option<i64>, _>("deny")?;
+
+        Ok(Self {
+            channel_id: channel_id.into(),
+            guild_id: guild_id.into(),
+            created_at: naive_to_dt(created_at),
+            permissions: allow.zip(deny),
+        })
+    }
+}
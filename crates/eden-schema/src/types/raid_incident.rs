@@ -0,0 +1,62 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+/// Detected raid in a guild and the lockdown made because of it.
+#[derive(Debug, Clone)]
+pub struct RaidIncident {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub guild_id: Id<GuildMarker>,
+    /// Amount of members joined within a minute when the raid is detected.
+    pub joins: i32,
+    /// Permissions removed from the `@everyone` role during the lockdown.
+    ///
+    /// It is `None` if it is not modified during the lockdown.
+    pub removed_everyone_permissions: Option<i64>,
+    /// Verification level of the guild before the lockdown.
+    ///
+    /// It is `None` if it is not modified during the lockdown.
+    pub previous_verification_level: Option<i16>,
+    /// Whether invites of the guild are paused during the lockdown.
+    pub disabled_invites: bool,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub ended_by: Option<Id<UserMarker>>,
+}
+
+impl RaidIncident {
+    /// Whether the lockdown from this incident is still active.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for RaidIncident {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let joins = row.try_get("joins")?;
+        let removed_everyone_permissions = row.try_get("removed_everyone_permissions")?;
+        let previous_verification_level = row.try_get("previous_verification_level")?;
+        let disabled_invites = row.try_get("disabled_invites")?;
+        let ended_at = row.try_get::<Option<NaiveDateTime>, _>("ended_at")?;
+        let ended_by = row.try_get::<Option<SqlSnowflake<UserMarker>>, _>("ended_by")?;
+
+        Ok(Self {
+            id,
+            created_at: naive_to_dt(created_at),
+            guild_id: guild_id.into(),
+            joins,
+            removed_everyone_permissions,
+            previous_verification_level,
+            disabled_invites,
+            ended_at: ended_at.map(naive_to_dt),
+            ended_by: ended_by.map(Into::into),
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::{GuildMarker, ScheduledEventMarker};
+use twilight_model::id::Id;
+
+/// Reminder of a guild scheduled event that has been posted.
+///
+/// It prevents the same reminder from being posted more than once
+/// since reminders are scheduled every time the event is updated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledEventReminder {
+    pub event_id: Id<ScheduledEventMarker>,
+    /// How long (in minutes) before the event starts the
+    /// reminder is posted.
+    pub lead_minutes: i32,
+    pub starts_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub guild_id: Id<GuildMarker>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for ScheduledEventReminder {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let event_id = row.try_get::<SqlSnowflake<ScheduledEventMarker>, _>("event_id")?;
+        let lead_minutes = row.try_get("lead_minutes")?;
+        let starts_at = row.try_get::<NaiveDateTime, _>("starts_at")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+
+        Ok(Self {
+            event_id: event_id.into(),
+            lead_minutes,
+            starts_at: naive_to_dt(starts_at),
+            created_at: naive_to_dt(created_at),
+            guild_id: guild_id.into(),
+        })
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::{GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+/// Role temporarily granted to a member until it expires.
+#[derive(Debug, Clone)]
+pub struct TempRoleGrant {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub guild_id: Id<GuildMarker>,
+    pub user_id: Id<UserMarker>,
+    pub role_id: Id<RoleMarker>,
+    pub granted_by: Id<UserMarker>,
+    pub expires_at: DateTime<Utc>,
+    /// When the role is removed from the member.
+    ///
+    /// It is `None` if the grant is still active.
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for TempRoleGrant {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let updated_at = row.try_get::<Option<NaiveDateTime>, _>("updated_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let user_id = row.try_get::<SqlSnowflake<UserMarker>, _>("user_id")?;
+        let role_id = row.try_get::<SqlSnowflake<RoleMarker>, _>("role_id")?;
+        let granted_by = row.try_get::<SqlSnowflake<UserMarker>, _>("granted_by")?;
+        let expires_at = row.try_get::<NaiveDateTime, _>("expires_at")?;
+        let removed_at = row.try_get::<Option<NaiveDateTime>, _>("removed_at")?;
+
+        Ok(Self {
+            id,
+            created_at: naive_to_dt(created_at),
+            updated_at: updated_at.map(naive_to_dt),
+            guild_id: guild_id.into(),
+            user_id: user_id.into(),
+            role_id: role_id.into(),
+            granted_by: granted_by.into(),
+            expires_at: naive_to_dt(expires_at),
+            removed_at: removed_at.map(naive_to_dt),
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use serde_json::Value;
+use sqlx::Row;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+/// Recent admin operation that can be reverted with `/admin undo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoEntry {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub guild_id: Id<GuildMarker>,
+    pub actor_id: Id<UserMarker>,
+    pub description: String,
+    /// How to revert the operation. Its structure is up to the bot.
+    pub action: Value,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for UndoEntry {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let id = row.try_get("id")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let actor_id = row.try_get::<SqlSnowflake<UserMarker>, _>("actor_id")?;
+        let description = row.try_get("description")?;
+        let action = row.try_get::<sqlx::types::Json<Value>, _>("action")?;
+
+        Ok(Self {
+            id,
+            created_at: naive_to_dt(created_at),
+            guild_id: guild_id.into(),
+            actor_id: actor_id.into(),
+            description,
+            action: action.0,
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// Typed key of a user preference stored in the `user_preferences` table.
+pub trait PreferenceKey {
+    /// Key of the preference stored in the database.
+    const KEY: &'static str;
+
+    type Value: Debug + Serialize + DeserializeOwned + Send + Sync;
+
+    /// Value of the preference if the user has not set it yet.
+    fn default_value() -> Self::Value;
+}
+
+/// Preferred locale of the user (e.g. `en-US`).
+pub struct LocalePreference;
+
+impl PreferenceKey for LocalePreference {
+    const KEY: &'static str = "locale";
+    type Value = String;
+
+    fn default_value() -> Self::Value {
+        "en-US".into()
+    }
+}
+
+/// Whether the user does not want to receive reminders and
+/// other non-essential messages through direct messages.
+pub struct DmRemindersOptOutPreference;
+
+impl PreferenceKey for DmRemindersOptOutPreference {
+    const KEY: &'static str = "dm_reminders_opt_out";
+    type Value = bool;
+
+    fn default_value() -> Self::Value {
+        false
+    }
+}
+
+/// Preferred timezone of the user as a UTC offset (e.g. `+08:00`).
+pub struct TimezonePreference;
+
+impl PreferenceKey for TimezonePreference {
+    const KEY: &'static str = "timezone";
+    type Value = String;
+
+    fn default_value() -> Self::Value {
+        "+00:00".into()
+    }
+}
+
+/// Whether the user allows their payer contribution stats to be
+/// publicly shown in `/payer stats`.
+pub struct PublicPayerStatsPreference;
+
+impl PreferenceKey for PublicPayerStatsPreference {
+    const KEY: &'static str = "public_payer_stats";
+    type Value = bool;
+
+    fn default_value() -> Self::Value {
+        false
+    }
+}
+
+/// All known preferences of a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserPreferences {
+    pub locale: String,
+    pub dm_reminders_opt_out: bool,
+    pub timezone: String,
+    pub public_payer_stats: bool,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        Self {
+            locale: LocalePreference::default_value(),
+            dm_reminders_opt_out: DmRemindersOptOutPreference::default_value(),
+            timezone: TimezonePreference::default_value(),
+            public_payer_stats: PublicPayerStatsPreference::default_value(),
+        }
+    }
+}
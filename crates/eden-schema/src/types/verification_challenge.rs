@@ -0,0 +1,34 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eden_utils::sql::util::{naive_to_dt, SqlSnowflake};
+use sqlx::Row;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+use uuid::Uuid;
+
+/// Expected answer of a verification prompt sent to a new member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationChallenge {
+    pub nonce: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub guild_id: Id<GuildMarker>,
+    pub user_id: Id<UserMarker>,
+    pub answer: String,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for VerificationChallenge {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        let nonce = row.try_get("nonce")?;
+        let created_at = row.try_get::<NaiveDateTime, _>("created_at")?;
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let user_id = row.try_get::<SqlSnowflake<UserMarker>, _>("user_id")?;
+        let answer = row.try_get("answer")?;
+
+        Ok(Self {
+            nonce,
+            created_at: naive_to_dt(created_at),
+            guild_id: guild_id.into(),
+            user_id: user_id.into(),
+            answer,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use eden_utils::sql::util::SqlSnowflake;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+use twilight_model::id::Id;
+
+/// Total time spent by a member in voice channels in a single day.
+#[derive(Debug, Clone)]
+pub struct VoiceStat {
+    pub guild_id: Id<GuildMarker>,
+    pub user_id: Id<UserMarker>,
+    pub day: NaiveDate,
+    pub seconds: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for VoiceStat {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        let guild_id = row.try_get::<SqlSnowflake<GuildMarker>, _>("guild_id")?;
+        let user_id = row.try_get::<SqlSnowflake<UserMarker>, _>("user_id")?;
+        let day = row.try_get("day")?;
+        let seconds = row.try_get("seconds")?;
+
+        Ok(Self {
+            guild_id: guild_id.into(),
+            user_id: user_id.into(),
+            day,
+            seconds,
+        })
+    }
+}
+
+/// Total time spent by a member in voice channels over a period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceLeaderboardEntry {
+    pub user_id: Id<UserMarker>,
+    pub seconds: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for VoiceLeaderboardEntry {
+    fn from_row(row: &'r sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        let user_id = row.try_get::<SqlSnowflake<UserMarker>, _>("user_id")?;
+        let seconds = row.try_get("seconds")?;
+
+        Ok(Self {
+            user_id: user_id.into(),
+            seconds,
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use doku::Document;
+use serde::{Deserialize, Serialize};
+use twilight_model::id::marker::{ChannelMarker, UserMarker};
+use twilight_model::id::Id;
+use typed_builder::TypedBuilder;
+
+/// Kinds of events Eden alerts the administrators about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertClass {
+    /// Payment processes that need to be reviewed by the administrators.
+    Billing,
+    /// Moderation requests like emoji approval requests.
+    Moderation,
+    /// Raids detected in the local guild/server.
+    Raid,
+    /// Background tasks that keep failing or recovered from failing.
+    TaskFailures,
+    /// Everything else about Eden itself like outages, settings reloads
+    /// and health reports.
+    System,
+}
+
+/// How important an alert is. Alerts less severe than the route's
+/// `min_severity` are not sent at all.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Default, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct AlertRouting {
+    /// Whether related alerts (like a failing background task until it
+    /// recovers or a raid until its lockdown ends) should be grouped
+    /// into a thread per incident in the alert channel.
+    ///
+    /// The first alert of the incident is posted as a summary message
+    /// with the thread created from it, and the summary gets updated
+    /// once the incident is resolved. Eden needs the `Create Public Threads`
+    /// and `Send Messages in Threads` permissions to do this.
+    ///
+    /// The default value is false if not set.
+    #[builder(default)]
+    #[doku(example = "false")]
+    pub incident_threads: bool,
+
+    /// Where alerts of payment processes should be sent.
+    #[builder(default, setter(strip_option))]
+    pub billing: Option<AlertRoute>,
+
+    /// Where alerts of moderation requests (like emoji approval
+    /// requests) should be sent.
+    #[builder(default, setter(strip_option))]
+    pub moderation: Option<AlertRoute>,
+
+    /// Where alerts of detected raids should be sent.
+    #[builder(default, setter(strip_option))]
+    pub raid: Option<AlertRoute>,
+
+    /// Where alerts of failing background tasks should be sent.
+    #[builder(default, setter(strip_option))]
+    pub task_failures: Option<AlertRoute>,
+
+    /// Where alerts about Eden itself (outages, settings reloads and
+    /// health reports) should be sent.
+    #[builder(default, setter(strip_option))]
+    pub system: Option<AlertRoute>,
+}
+
+impl AlertRouting {
+    #[must_use]
+    pub fn get(&self, class: AlertClass) -> Option<&AlertRoute> {
+        match class {
+            AlertClass::Billing => self.billing.as_ref(),
+            AlertClass::Moderation => self.moderation.as_ref(),
+            AlertClass::Raid => self.raid.as_ref(),
+            AlertClass::TaskFailures => self.task_failures.as_ref(),
+            AlertClass::System => self.system.as_ref(),
+        }
+    }
+
+    /// Resolves where an alert of `class` with `severity` should be
+    /// sent to. Alerts of classes without a route are sent to
+    /// `alert_channel_id` regardless of their severity.
+    #[must_use]
+    pub fn resolve(
+        &self,
+        alert_channel_id: Id<ChannelMarker>,
+        class: AlertClass,
+        severity: AlertSeverity,
+    ) -> AlertTargets<'_> {
+        let Some(route) = self.get(class) else {
+            return AlertTargets {
+                channel_id: Some(alert_channel_id),
+                user_ids: &[],
+            };
+        };
+
+        if severity < route.min_severity {
+            return AlertTargets {
+                channel_id: None,
+                user_ids: &[],
+            };
+        }
+
+        let channel_id = if route.dm_only {
+            None
+        } else {
+            Some(route.channel_id.unwrap_or(alert_channel_id))
+        };
+
+        AlertTargets {
+            channel_id,
+            user_ids: &route.dm_user_ids,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct AlertRoute {
+    /// Channel where alerts of this kind should be sent.
+    ///
+    /// If it is not set, alerts will be sent to `alert_channel_id`.
+    #[builder(default, setter(strip_option))]
+    #[doku(as = "String", example = "<insert me>")]
+    pub channel_id: Option<Id<ChannelMarker>>,
+
+    /// Users who should receive alerts of this kind through DMs.
+    ///
+    /// Interactive alerts (like raid alerts) can only be acted upon
+    /// from the channel.
+    #[builder(default)]
+    #[doku(as = "Vec<String>", example = "<insert me>")]
+    pub dm_user_ids: Vec<Id<UserMarker>>,
+
+    /// Whether alerts of this kind should be sent only through DMs
+    /// to the users from `dm_user_ids`.
+    ///
+    /// The default value is false if not set.
+    #[builder(default)]
+    #[doku(example = "false")]
+    pub dm_only: bool,
+
+    /// The least severe alerts of this kind to be sent.
+    ///
+    /// There are three severities to choose (from least to most severe):
+    /// - `info` - Eden lets the administrators know something happened.
+    /// - `warning` - something needs the administrators' attention.
+    /// - `critical` - something needs to be dealt with right away.
+    ///
+    /// The default value is `info` if not set.
+    #[builder(default)]
+    #[doku(as = "String", example = "info")]
+    pub min_severity: AlertSeverity,
+}
+
+/// Where an alert should be sent to, resolved from [`AlertRouting`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlertTargets<'a> {
+    pub channel_id: Option<Id<ChannelMarker>>,
+    pub user_ids: &'a [Id<UserMarker>],
+}
+
+impl AlertTargets<'_> {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.channel_id.is_none() && self.user_ids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALERT_CHANNEL_ID: Id<ChannelMarker> = Id::new(1);
+    const RAID_CHANNEL_ID: Id<ChannelMarker> = Id::new(2);
+    const ADMIN_ID: Id<UserMarker> = Id::new(3);
+
+    fn routing() -> AlertRouting {
+        AlertRouting::builder()
+            .raid(
+                AlertRoute::builder()
+                    .channel_id(RAID_CHANNEL_ID)
+                    .dm_user_ids(vec![ADMIN_ID])
+                    .build(),
+            )
+            .task_failures(
+                AlertRoute::builder()
+                    .min_severity(AlertSeverity::Warning)
+                    .build(),
+            )
+            .billing(
+                AlertRoute::builder()
+                    .dm_user_ids(vec![ADMIN_ID])
+                    .dm_only(true)
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn should_fallback_to_alert_channel() {
+        let routing = routing();
+        let targets = routing.resolve(ALERT_CHANNEL_ID, AlertClass::System, AlertSeverity::Info);
+        assert_eq!(targets.channel_id, Some(ALERT_CHANNEL_ID));
+        assert!(targets.user_ids.is_empty());
+
+        let targets = routing.resolve(
+            ALERT_CHANNEL_ID,
+            AlertClass::TaskFailures,
+            AlertSeverity::Critical,
+        );
+        assert_eq!(targets.channel_id, Some(ALERT_CHANNEL_ID));
+    }
+
+    #[test]
+    fn should_route_to_configured_targets() {
+        let routing = routing();
+        let targets = routing.resolve(ALERT_CHANNEL_ID, AlertClass::Raid, AlertSeverity::Info);
+        assert_eq!(targets.channel_id, Some(RAID_CHANNEL_ID));
+        assert_eq!(targets.user_ids, [ADMIN_ID]);
+
+        let targets = routing.resolve(ALERT_CHANNEL_ID, AlertClass::Billing, AlertSeverity::Info);
+        assert_eq!(targets.channel_id, None);
+        assert_eq!(targets.user_ids, [ADMIN_ID]);
+    }
+
+    #[test]
+    fn should_skip_alerts_below_min_severity() {
+        let routing = routing();
+        let targets = routing.resolve(
+            ALERT_CHANNEL_ID,
+            AlertClass::TaskFailures,
+            AlertSeverity::Info,
+        );
+        assert!(targets.is_empty());
+    }
+}
@@ -1,29 +1,102 @@
 use doku::Document;
 use eden_tasks::prelude::TimeDelta;
 use eden_utils::error::exts::ErrorExt;
-use eden_utils::types::{ProtectedString, Sensitive};
+use eden_utils::types::{HumanDuration, ProtectedString, Sensitive};
 use eden_utils::{Error, ErrorCategory, Result};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::num::NonZeroU64;
-use std::time::Duration;
-use twilight_model::gateway::payload::outgoing::update_presence::UpdatePresencePayload;
-use twilight_model::id::marker::{ChannelMarker, GuildMarker};
+use std::path::PathBuf;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker, RoleMarker};
 use twilight_model::id::Id;
 use typed_builder::TypedBuilder;
 
-use crate::SettingsLoadError;
+use crate::{AlertRouting, Presence, SettingsLoadError};
 
 #[derive(Debug, Deserialize, Document, Serialize, TypedBuilder)]
 pub struct Bot {
+    /// Secret used to anonymize identifiable values (like user and guild
+    /// IDs) consistently in logs, metrics and Sentry events.
+    ///
+    /// **DO NOT SHARE THIS SECRET TO ANYONE!** Anyone who knows it can
+    /// find out which IDs the anonymized values belong to.
+    ///
+    /// Changing it makes every anonymized value different from before
+    /// so anonymized values cannot be correlated with the old ones anymore.
+    ///
+    /// It must be set to a long random secret that is different
+    /// from the bot token.
+    #[builder(setter(into))]
+    #[doku(as = "String", example = "<insert secret here>")]
+    pub anonymization_key: ProtectedString,
+
     /// Parameters for configuring what Eden should behave when
     /// dealing with its commands to any users.
     #[builder(default)]
     #[serde(default)]
     pub commands: Commands,
 
+    /// Path to the local control socket (a Unix domain socket) where
+    /// administrators can inspect and manage queued tasks with JSON lines.
+    ///
+    /// The socket is only accessible to users who have access to the file
+    /// itself, so make sure to put it somewhere only trusted users can reach.
+    ///
+    /// If it is not set, the control socket will be disabled.
+    #[builder(default)]
+    #[doku(as = "String", example = "eden.sock")]
+    #[serde(default)]
+    pub control_socket: Option<PathBuf>,
+
+    /// Whether Eden should run in dry run mode.
+    ///
+    /// In dry run mode, all outgoing Discord mutations (sending messages,
+    /// changing roles, sending DMs and many more) will be logged and recorded
+    /// to `dry_run_output` instead of being executed. Skipped mutations are
+    /// treated as if they succeeded, and responses to commands are still
+    /// sent so commands can be used as usual.
+    ///
+    /// This is useful for staging environments to safely rehearse Eden's
+    /// features with production data.
+    ///
+    /// The default value is false if not set.
+    #[builder(default)]
+    #[doku(example = "false")]
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Path to the file where all skipped Discord mutations will be
+    /// recorded as JSON lines if `dry_run` is enabled.
+    ///
+    /// If it is not set, skipped Discord mutations will be logged only.
+    #[builder(default)]
+    #[doku(as = "String", example = "dry_run.jsonl")]
+    #[serde(default)]
+    pub dry_run_output: Option<PathBuf>,
+
+    /// Rollouts of commands or features that are not ready for
+    /// everyone yet (also known as feature gates).
+    ///
+    /// Changes to this setting are applied right away without
+    /// restarting Eden. Gates from this setting can still be changed
+    /// with `/admin feature` until Eden restarts or they're
+    /// changed from this setting again.
+    #[builder(default)]
+    #[serde(default)]
+    pub feature_gates: Vec<FeatureRollout>,
+
+    /// Parameters for configuring how Eden connects and receives
+    /// events from Discord's gateway.
+    ///
+    /// **Do not modify if you don't know anything about how Discord gateway works.**
+    ///
+    /// Transport compression (`zlib-stream`) cannot be configured here since
+    /// it is enabled at compile time with the `gateway-compression` feature.
+    #[builder(default)]
+    #[serde(default)]
+    pub gateway: Gateway,
+
     /// Parameters for configuring what Eden should behave when
     /// it interacts with Discord's REST/HTTP API.
     ///
@@ -32,6 +105,15 @@ pub struct Bot {
     #[serde(default)]
     pub http: Http,
 
+    /// Whether Eden should leave every guild/server other than the local
+    /// guild/server unless it is allowed with `/admin guilds allow`.
+    ///
+    /// The default value is false if not set.
+    #[builder(default)]
+    #[doku(example = "false")]
+    #[serde(default)]
+    pub leave_unallowed_guilds: bool,
+
     /// "Local guild/server" is where most of Eden's functionality so forth take place
     /// such as payment processes, administration, form applications and many more
     /// to add in the future.
@@ -47,20 +129,22 @@ pub struct Bot {
     #[serde(alias = "local_server")]
     pub local_guild: LocalGuild,
 
-    /// The default presence of the bot.
+    /// Parameters for configuring Eden's moderation features such as
+    /// detecting members sending the same message repeatedly.
+    #[builder(default)]
+    #[serde(default)]
+    pub moderation: Moderation,
+
+    /// The presence of the bot that is shown to everyone once
+    /// Eden connects to Discord.
     ///
-    /// Please refer to the documentation on how to manually configure
-    /// presences at: https://discord.com/developers/docs/topics/gateway-events#update-presence-gateway-presence-update-structure
+    /// If it is not set, the bot will be online without any activity.
     ///
-    /// If it is not set, it will set into a default presence
-    /// where no much activity is set for the bot.
+    /// Changes to this setting are applied right away without
+    /// restarting Eden.
     #[builder(default)]
-    #[doku(
-        as = "HashMap<String, String>",
-        example = "status = \"idle\"\nafk = true\n\n[[bot.presence.activities]]\n# Type 0 means playing\ntype = 0\nname = \"with Ferris\"\n# Use this if the type is 1 only\n# url = \"...\"\n\n# created_at = 0 (use Unix timestamps for this field)\n# And many more..."
-    )]
     #[serde(default)]
-    pub presence: Option<UpdatePresencePayload>,
+    pub presence: Presence,
 
     /// Parameters for sharding.
     ///
@@ -75,6 +159,8 @@ pub struct Bot {
     ///
     /// The default configuration of sharding will be a single shard configuration
     /// with an ID of 0 and size of 1 which is sufficient for small bots.
+    ///
+    /// Set `type` to `auto` to let Discord decide how many shards are needed.
     #[builder(default)]
     #[doku(example = "")]
     #[serde(default)]
@@ -103,10 +189,33 @@ pub struct LocalGuild {
     #[doku(as = "String", example = "<insert me>")]
     pub id: Id<GuildMarker>,
 
-    // TODO: Document this field
-    /// Alert admin channel.
+    /// Channel where Eden alerts the administrators about anything
+    /// that needs their attention.
+    ///
+    /// Alerts of specific kinds can be routed elsewhere with `alerts`.
     #[doku(as = "String", example = "<insert me>")]
     pub alert_channel_id: Id<ChannelMarker>,
+
+    /// Discord locales of the command translations to be registered
+    /// in the local guild/server (like `es-ES` or `pt-BR`).
+    ///
+    /// You may refer to the list of locales supported by Discord at:
+    /// https://discord.com/developers/docs/reference#locales
+    ///
+    /// If it is not set, all of the available translations will be registered.
+    #[builder(default)]
+    #[doku(example = "es-ES")]
+    #[serde(default)]
+    pub command_locales: Option<Vec<String>>,
+
+    /// Routes alerts of specific kinds (`billing`, `moderation`, `raid`,
+    /// `task_failures` and `system`) to other channels or to the DMs
+    /// of specific users.
+    ///
+    /// Alerts of kinds without a route are sent to `alert_channel_id`.
+    #[builder(default)]
+    #[serde(default)]
+    pub alerts: AlertRouting,
 }
 
 // TODO: allow Eden to do some shard queueing
@@ -133,6 +242,9 @@ pub enum Sharding {
         #[doku(as = "u64", example = "5")]
         total: NonZeroU64,
     },
+    /// Uses the amount of shards recommended by Discord once Eden
+    /// starts and connects all of them in this instance.
+    Auto,
 }
 
 impl Sharding {
@@ -148,24 +260,30 @@ impl Sharding {
         match self {
             Self::Single { id, .. } => *id,
             Self::Range { start, .. } => *start,
+            Self::Auto => 0,
         }
     }
 
     /// Number of shards to initialize.
+    ///
+    /// It returns `None` if it is decided by Discord once Eden starts.
     #[must_use]
-    pub fn size(&self) -> u64 {
+    pub fn size(&self) -> Option<u64> {
         match self {
-            Self::Single { .. } => 1,
-            Self::Range { start, end, .. } => end - start + 1,
+            Self::Single { .. } => Some(1),
+            Self::Range { start, end, .. } => Some(end - start + 1),
+            Self::Auto => None,
         }
     }
 
     /// Total shards needed to be utilized for the bot.
+    ///
+    /// It returns `None` if it is decided by Discord once Eden starts.
     #[must_use]
-    pub fn total(&self) -> u64 {
+    pub fn total(&self) -> Option<u64> {
         match self {
-            Self::Single { total, .. } => total.get(),
-            Self::Range { total, .. } => total.get(),
+            Self::Single { total, .. } | Self::Range { total, .. } => Some(total.get()),
+            Self::Auto => None,
         }
     }
 }
@@ -210,6 +328,7 @@ impl Sharding {
                         ));
                 }
             }
+            Self::Auto => {}
         };
         Ok(())
     }
@@ -225,6 +344,7 @@ impl Debug for Sharding {
                 .field("total", &total.get())
                 .finish(),
             Self::Single { id, total } => write!(f, "Single([{id}, {}])", total.get()),
+            Self::Auto => f.write_str("Auto"),
         }
     }
 }
@@ -238,7 +358,122 @@ impl Default for Sharding {
     }
 }
 
-#[serde_as]
+/// Kinds of data that can be kept in Eden's cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheResource {
+    Channel,
+    Emoji,
+    Guild,
+    GuildScheduledEvent,
+    Integration,
+    Member,
+    Message,
+    Presence,
+    Reaction,
+    Role,
+    StageInstance,
+    Sticker,
+    User,
+    UserCurrent,
+    VoiceState,
+}
+
+impl CacheResource {
+    /// Resources cached by default if `bot.http.cache_resources` is not set.
+    pub const DEFAULT: &'static [Self] = &[
+        Self::Channel,
+        Self::Guild,
+        Self::Role,
+        Self::User,
+        Self::UserCurrent,
+    ];
+
+    /// Resources that must be cached so the bot's permissions can be
+    /// computed without fetching the entire guild from Discord.
+    pub const REQUIRED: &'static [Self] = &[Self::Channel, Self::Guild, Self::Role];
+}
+
+#[derive(Debug, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct Gateway {
+    /// Encoding of the payloads sent from Discord.
+    ///
+    /// Only `json` is supported at the moment since the gateway library
+    /// that Eden uses does not support ETF (Erlang External Term Format).
+    ///
+    /// The default value is `json` if not set.
+    #[builder(default)]
+    #[doku(as = "String", example = "json")]
+    pub encoding: GatewayEncoding,
+
+    /// Total number of members where Discord will stop sending offline
+    /// members in the guild member list.
+    ///
+    /// Lower values make payloads of large guilds smaller when Eden
+    /// connects to the gateway. It must be between `50` and `250`.
+    ///
+    /// The default value is `50` if not set.
+    #[builder(default = 50)]
+    #[doku(example = "50")]
+    pub large_threshold: u64,
+
+    /// URL of an external identify queue server compatible with
+    /// twilight's gateway queue (`GET {url}?shard={id}`).
+    ///
+    /// Discord only allows a limited amount of shards to identify at
+    /// once, so multiple Eden instances that split their shards across
+    /// processes must share the same queue to avoid being rate limited.
+    ///
+    /// If it is not set, shards identify with a queue local to this
+    /// instance of Eden.
+    #[builder(default, setter(into, strip_option))]
+    #[doku(example = "http://localhost:7000")]
+    pub queue_url: Option<String>,
+}
+
+impl Gateway {
+    pub fn check(&self) -> Result<(), SettingsLoadError> {
+        if self.encoding == GatewayEncoding::Etf {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable("`gateway.encoding` = \"etf\" is not supported yet"));
+        }
+
+        if !(50..=250).contains(&self.large_threshold) {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable("`gateway.large_threshold` must be between 50 and 250"));
+        }
+
+        if let Some(url) = self.queue_url.as_deref()
+            && !(url.starts_with("http://") || url.starts_with("https://"))
+        {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable("`gateway.queue_url` must be an HTTP or HTTPS URL"));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Gateway {
+    fn default() -> Self {
+        Self {
+            encoding: GatewayEncoding::default(),
+            large_threshold: 50,
+            queue_url: None,
+        }
+    }
+}
+
+/// Encoding of the payloads sent from the gateway.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GatewayEncoding {
+    #[default]
+    Json,
+    Etf,
+}
+
 #[derive(Debug, Deserialize, Document, Serialize)]
 #[serde(default)]
 pub struct Http {
@@ -256,9 +491,8 @@ pub struct Http {
     /// Timeout for every HTTP requests
     ///
     /// The default value is 10 seconds if not set.
-    #[doku(as = "String", example = "30m")]
-    #[serde_as(as = "eden_utils::serial::AsHumanDuration")]
-    pub timeout: Duration,
+    #[doku(example = "10s")]
+    pub timeout: HumanDuration,
 
     /// Using cache allows Eden to minimize amount of REST/HTTP API requests,
     /// requesting too much will lead to ratelimits.
@@ -273,15 +507,50 @@ pub struct Http {
     /// The default value is false if not set.
     #[doku(example = "false")]
     pub use_cache: bool,
+
+    /// Kinds of data that Eden keeps in its cache. This has no effect
+    /// if `use_cache` is set to false.
+    ///
+    /// There are fifteen options to choose:
+    /// `channel`, `emoji`, `guild`, `guild_scheduled_event`, `integration`,
+    /// `member`, `message`, `presence`, `reaction`, `role`, `stage_instance`,
+    /// `sticker`, `user`, `user_current` and `voice_state`.
+    ///
+    /// Memory-constrained hosts may want to cache only a few of them
+    /// (like `member` and `channel`) and leave out `message` and `presence`.
+    ///
+    /// It defaults to `channel`, `guild`, `role`, `user` and `user_current`, if not set.
+    #[doku(as = "Vec<String>", example = "member")]
+    pub cache_resources: Vec<CacheResource>,
+}
+
+impl Http {
+    /// Checks whether every [required](CacheResource::REQUIRED)
+    /// resource is cached if caching is enabled.
+    pub fn check(&self) -> Result<(), SettingsLoadError> {
+        let has_required = CacheResource::REQUIRED
+            .iter()
+            .all(|v| self.cache_resources.contains(v));
+
+        if self.use_cache && !has_required {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable(
+                    "`http.cache_resources` must have `channel`, `guild` and `role` if `http.use_cache` is enabled",
+                ));
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for Http {
     fn default() -> Self {
         Self {
             use_cache: false,
+            cache_resources: CacheResource::DEFAULT.to_vec(),
             proxy: None,
             proxy_use_http: true,
-            timeout: Duration::from_secs(10),
+            timeout: HumanDuration::from_secs(10),
         }
     }
 }
@@ -299,21 +568,221 @@ pub struct Commands {
     #[doku(as = "String", example = "15m")]
     #[serde_as(as = "eden_utils::serial::AsHumanDuration")]
     pub inactivity_timeout: TimeDelta,
+
+    /// How long a command is allowed to run before it is cancelled
+    /// and the invoker is told that the command took too long.
+    ///
+    /// Set it to `0s` to let commands run for as long as they need.
+    ///
+    /// It defaults to 2 minutes, if not set.
+    #[builder(default = HumanDuration::from_mins(2))]
+    #[doku(example = "2m")]
+    pub execution_timeout: HumanDuration,
+
+    /// Whether Eden should store long command cooldowns into the
+    /// database so users cannot bypass them after Eden restarts.
+    ///
+    /// It defaults to true, if not set.
+    #[builder(default = true)]
+    #[doku(example = "true")]
+    pub persist_cooldowns: bool,
+
+    /// How long a command cooldown should at least last before
+    /// it is stored into the database.
+    ///
+    /// This has no effect if `persist_cooldowns` is set to false.
+    ///
+    /// It defaults to 1 hour, if not set.
+    #[builder(default = HumanDuration::from_mins(60))]
+    #[doku(example = "1h")]
+    pub persist_cooldowns_after: HumanDuration,
+
+    /// How long responses of expensive read-only commands (like
+    /// `/stats voice` and `/payer stats`) are reused before they
+    /// are computed again.
+    ///
+    /// Set it to `0s` to disable response caching.
+    ///
+    /// It defaults to 30 seconds, if not set.
+    #[builder(default = HumanDuration::from_secs(30))]
+    #[doku(example = "30s")]
+    pub response_cache_ttl: HumanDuration,
+
+    /// Cooldowns of commands that members have to wait for before
+    /// they can use the command again.
+    ///
+    /// A cooldown of a command group (like `payer`) is shared among
+    /// all of its subcommands. Cooldowns are refunded if the command
+    /// fails to run.
+    #[builder(default)]
+    pub cooldowns: Vec<CommandCooldownRule>,
 }
 
 impl Default for Commands {
     fn default() -> Self {
         Self {
             inactivity_timeout: TimeDelta::minutes(60 * 15),
+            execution_timeout: HumanDuration::from_mins(2),
+            persist_cooldowns: true,
+            persist_cooldowns_after: HumanDuration::from_mins(60),
+            response_cache_ttl: HumanDuration::from_secs(30),
+            cooldowns: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Document, Serialize, TypedBuilder)]
+pub struct CommandCooldownRule {
+    /// Path of the command or command group (like `payer register`).
+    #[builder(setter(into))]
+    #[doku(example = "payer register")]
+    pub command: String,
+
+    /// How long members have to wait before using the command again.
+    #[doku(example = "1d")]
+    pub duration: HumanDuration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Document, Serialize, TypedBuilder)]
+pub struct FeatureRollout {
+    /// Name of the gated feature or command path (like `payer pay`).
+    #[builder(setter(into))]
+    #[doku(example = "payer pay")]
+    pub name: String,
+
+    /// Percentage of users (from 0 to 100) that can use the feature.
+    #[doku(example = "50")]
+    pub percentage: u8,
+
+    /// Members with any of these roles can always use the feature.
+    #[builder(default)]
+    #[doku(as = "Vec<String>", example = "<insert me>")]
+    #[serde(default)]
+    pub roles: Vec<Id<RoleMarker>>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct Moderation {
+    /// How Eden should keep the content of recent messages in memory
+    /// to detect members sending the same message over and over again.
+    ///
+    /// There are two modes to choose:
+    /// - `plaintext` - keeps the message content as is.
+    /// - `hashed` - keeps only the salted SHA-256 hash of the message
+    ///   content along with its metadata (author, channel and when it
+    ///   is sent).
+    ///
+    /// Using `hashed` mode is more privacy friendly since the message
+    /// content cannot be recovered at all if Eden's memory is exposed.
+    /// However, the trade-off is that Eden can only detect messages
+    /// that are exactly the same (case and spacing insensitive) and
+    /// hashing takes a little bit of CPU time per message.
+    ///
+    /// The default value is `plaintext` if not set.
+    #[builder(default = ContentMode::Plaintext)]
+    #[doku(as = "String", example = "hashed")]
+    pub content_mode: ContentMode,
+
+    /// Salt used to hash message contents if `content_mode` is
+    /// set to `hashed`.
+    ///
+    /// **DO NOT SHARE THIS SALT TO ANYONE!**
+    ///
+    /// If it is not set, Eden will generate a random salt every time
+    /// it starts up.
+    #[builder(default, setter(into, strip_option))]
+    #[doku(as = "String", example = "<insert random text here>")]
+    pub content_salt: Option<ProtectedString>,
+
+    /// How many times a member can send the same message in a row
+    /// within the `duplicate_window` before Eden warns them.
+    ///
+    /// The default value is `3` if not set.
+    #[builder(default = 3)]
+    #[doku(example = "3")]
+    pub duplicate_threshold: u32,
+
+    /// The period of time where Eden will keep recent messages
+    /// from every member to detect duplicated messages.
+    ///
+    /// The default value is `30 seconds` if not set.
+    #[builder(default = TimeDelta::seconds(30))]
+    #[doku(as = "String", example = "30s")]
+    #[serde_as(as = "eden_utils::serial::AsHumanDuration")]
+    pub duplicate_window: TimeDelta,
+}
+
+impl Default for Moderation {
+    fn default() -> Self {
+        Self {
+            content_mode: ContentMode::default(),
+            content_salt: None,
+            duplicate_threshold: 3,
+            duplicate_window: TimeDelta::seconds(30),
         }
     }
 }
 
+/// How message contents are kept by Eden's moderation features.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentMode {
+    #[default]
+    Plaintext,
+    Hashed,
+}
+
 #[allow(clippy::unwrap_used)]
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn gateway_check() {
+        assert!(Gateway::default().check().is_ok());
+
+        let gateway = Gateway::builder().encoding(GatewayEncoding::Etf).build();
+        assert!(gateway.check().is_err());
+
+        let gateway = Gateway::builder().large_threshold(251).build();
+        assert!(gateway.check().is_err());
+
+        let gateway = Gateway::builder().queue_url("localhost:7000").build();
+        assert!(gateway.check().is_err());
+
+        let gateway = Gateway::builder()
+            .queue_url("http://localhost:7000")
+            .build();
+        assert!(gateway.check().is_ok());
+    }
+
+    #[test]
+    fn http_check() {
+        assert!(Http::default().check().is_ok());
+
+        let http = Http {
+            use_cache: true,
+            ..Http::default()
+        };
+        assert!(http.check().is_ok());
+
+        let http = Http {
+            use_cache: true,
+            cache_resources: vec![CacheResource::Member, CacheResource::Channel],
+            ..Http::default()
+        };
+        assert!(http.check().is_err());
+
+        // resources are not cached anyway
+        let http = Http {
+            cache_resources: Vec::new(),
+            ..Http::default()
+        };
+        assert!(http.check().is_ok());
+    }
+
     #[test]
     fn shard_check() {
         let case = Sharding::ONE;
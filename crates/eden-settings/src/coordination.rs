@@ -0,0 +1,69 @@
+use doku::Document;
+use eden_utils::types::HumanDuration;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// Name of the instance if `instance_name` and the `HOSTNAME`
+/// variable are not set.
+const DEFAULT_INSTANCE_NAME: &str = "eden";
+
+/// Parameters for running multiple Eden instances that share
+/// the same database.
+///
+/// Instances elect a leader among themselves and only the leader runs
+/// recurring tasks and registers commands. The other instances (followers)
+/// only run queued tasks until one of them takes over the leader.
+#[derive(Debug, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct Coordination {
+    /// Whether Eden should elect a leader with the other instances
+    /// sharing the same database.
+    ///
+    /// Turn it on if you're running multiple instances with the same
+    /// database, otherwise every instance runs recurring tasks and
+    /// registers commands by itself.
+    ///
+    /// The default value is false if not set.
+    #[builder(default)]
+    #[doku(example = "false")]
+    pub enabled: bool,
+
+    /// How often the instance lets the other instances know that it is
+    /// still alive. Followers also attempt to become the leader this often
+    /// in case the leader is gone.
+    ///
+    /// It defaults to `15` seconds if not set.
+    #[builder(default = HumanDuration::from_secs(15))]
+    #[doku(example = "15s")]
+    pub heartbeat_interval: HumanDuration,
+
+    /// Name of this instance to tell the instances apart in logs
+    /// and in the `instances` table.
+    ///
+    /// If it is not set, the `HOSTNAME` variable will be used
+    /// or `eden` if it is not set either.
+    #[builder(default, setter(into, strip_option))]
+    #[doku(example = "eden-1")]
+    pub instance_name: Option<String>,
+}
+
+impl Coordination {
+    #[must_use]
+    pub fn instance_name(&self) -> String {
+        self.instance_name
+            .clone()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_INSTANCE_NAME.into())
+    }
+}
+
+impl Default for Coordination {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            heartbeat_interval: HumanDuration::from_secs(15),
+            instance_name: None,
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use doku::Document;
-use eden_utils::types::Sensitive;
+use eden_utils::error::exts::ErrorExt;
+use eden_utils::types::{HumanDuration, Sensitive};
+use eden_utils::{Error, ErrorCategory, Result};
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
 use sqlx::postgres::PgConnectOptions;
+use sqlx::ConnectOptions;
 use std::str::FromStr;
-use std::time::Duration as StdDuration;
+use tracing::log::LevelFilter;
 use typed_builder::TypedBuilder;
 
-#[serde_as]
+use crate::SettingsLoadError;
+
 #[derive(Debug, Document, Deserialize, Serialize, TypedBuilder)]
 pub struct Database {
     /// Maximum amount of time to spend waiting for the database
@@ -19,10 +22,9 @@ pub struct Database {
     ///
     /// The default is `15` seconds, if not set.
     #[builder(default = Database::default_connect_timeout())]
-    #[doku(as = "String", example = "15s")]
+    #[doku(example = "15s")]
     #[serde(default = "Database::default_connect_timeout")]
-    #[serde_as(as = "eden_utils::serial::AsHumanDuration")]
-    pub connect_timeout: StdDuration,
+    pub connect_timeout: HumanDuration,
 
     /// Maximum idle duration for individual pooled connections.
     ///
@@ -31,10 +33,9 @@ pub struct Database {
     ///
     /// The default is `10` minutes, if not set.
     #[builder(default = Database::default_idle_timeout())]
-    #[doku(as = "String", example = "10m")]
+    #[doku(example = "10m")]
     #[serde(default = "Database::default_idle_timeout")]
-    #[serde_as(as = "eden_utils::serial::AsHumanDuration")]
-    pub idle_timeout: StdDuration,
+    pub idle_timeout: HumanDuration,
 
     /// Maximum amount of connections for Eden to maintain it
     /// most of the time.
@@ -65,10 +66,37 @@ pub struct Database {
     ///
     /// The default is `15` seconds, if not set.
     #[builder(default = Database::default_query_timeout())]
-    #[doku(as = "String", example = "15s")]
+    #[doku(example = "15s")]
     #[serde(default = "Database::default_query_timeout")]
-    #[serde_as(as = "eden_utils::serial::AsHumanDuration")]
-    pub query_timeout: StdDuration,
+    pub query_timeout: HumanDuration,
+
+    /// Minimum amount of time a query has to take before it is logged
+    /// as a slow query along with its SQL and how long it took. The
+    /// values bound to the query are never logged.
+    ///
+    /// Obtaining a connection from the pool that takes longer than
+    /// this is logged as well and counted in the health report.
+    ///
+    /// Set it to `0s` to disable slow query logging.
+    ///
+    /// The default is `1` second, if not set.
+    #[builder(default = Database::default_slow_query_threshold())]
+    #[doku(example = "1s")]
+    #[serde(default = "Database::default_slow_query_threshold")]
+    pub slow_query_threshold: HumanDuration,
+
+    /// Postgres schema where Eden keeps its tables, so Eden's tables
+    /// are kept apart from other applications sharing the same database.
+    ///
+    /// Eden creates the schema if it does not exist yet and it is
+    /// searched before `public` on every connection.
+    ///
+    /// It must only contain lowercase letters, digits and underscores.
+    ///
+    /// If it is not set, Eden keeps its tables in `public`.
+    #[builder(default, setter(into, strip_option))]
+    #[doku(example = "eden")]
+    pub schema: Option<String>,
 
     /// Connection URL to connect to the Postgres database.
     ///
@@ -82,23 +110,83 @@ pub struct Database {
 }
 
 impl Database {
+    /// Connection URL of the database including its credentials.
+    ///
+    /// It is only used to detect changes of the URL since
+    /// it cannot be seen from the [`Debug`](std::fmt::Debug) output.
+    pub(crate) fn url_for_diff(&self) -> String {
+        self.url.as_ref().0.to_url_lossy().to_string()
+    }
+
     #[must_use]
     pub fn as_postgres_connect_options(&self) -> PgConnectOptions {
-        self.url.as_ref().0.clone()
+        let threshold = self.slow_query_threshold.get();
+        let level = if threshold.is_zero() {
+            LevelFilter::Off
+        } else {
+            LevelFilter::Warn
+        };
+
+        // only the SQL is logged, bound values are not
+        let options = self
+            .url
+            .as_ref()
+            .0
+            .clone()
+            .log_slow_statements(level, threshold);
+
+        match self.schema.as_deref() {
+            Some(schema) => options.options([("search_path", format!("{schema},public"))]),
+            None => options,
+        }
     }
+
+    pub fn check(&self) -> Result<(), SettingsLoadError> {
+        if let Some(schema) = self.schema.as_deref()
+            && !is_valid_schema(schema)
+        {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable(format!(
+                    "`database.schema` is not a valid schema: {schema:?}"
+                )));
+        }
+        Ok(())
+    }
+}
+
+/// Only unquoted Postgres identifiers are allowed so the schema
+/// can be used in `search_path` and queries as is.
+fn is_valid_schema(schema: &str) -> bool {
+    const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+    let starts_with_letter = schema
+        .chars()
+        .next()
+        .is_some_and(|v| v.is_ascii_lowercase() || v == '_');
+
+    starts_with_letter
+        && schema.len() <= MAX_IDENTIFIER_LENGTH
+        && schema
+            .chars()
+            .all(|v| v.is_ascii_lowercase() || v.is_ascii_digit() || v == '_')
+        && !schema.starts_with("pg_")
 }
 
 impl Database {
-    fn default_connect_timeout() -> StdDuration {
-        StdDuration::from_secs(15)
+    fn default_connect_timeout() -> HumanDuration {
+        HumanDuration::from_secs(15)
     }
 
-    fn default_idle_timeout() -> StdDuration {
-        StdDuration::from_secs(60 * 10)
+    fn default_idle_timeout() -> HumanDuration {
+        HumanDuration::from_mins(10)
     }
 
-    fn default_query_timeout() -> StdDuration {
-        StdDuration::from_secs(15)
+    fn default_query_timeout() -> HumanDuration {
+        HumanDuration::from_secs(15)
+    }
+
+    fn default_slow_query_threshold() -> HumanDuration {
+        HumanDuration::from_secs(1)
     }
 
     fn default_max_connections() -> u32 {
@@ -174,7 +262,24 @@ impl serde::Serialize for SerializableUrl {
     where
         S: serde::Serializer,
     {
-        use sqlx::ConnectOptions;
         self.0.to_url_lossy().to_string().serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_allow_unquoted_schemas() {
+        assert!(is_valid_schema("eden"));
+        assert!(is_valid_schema("_eden_2"));
+
+        assert!(!is_valid_schema(""));
+        assert!(!is_valid_schema("Eden"));
+        assert!(!is_valid_schema("2eden"));
+        assert!(!is_valid_schema("pg_eden"));
+        assert!(!is_valid_schema("eden; DROP SCHEMA public"));
+        assert!(!is_valid_schema(&"e".repeat(64)));
+    }
+}
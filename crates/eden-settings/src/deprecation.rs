@@ -0,0 +1,280 @@
+use config::builder::DefaultState;
+use config::{ConfigBuilder, FileFormat, Map, Value};
+use eden_utils::error::exts::{ErrorExt, IntoTypedError, ResultExt};
+use eden_utils::error::tags::Suggestion;
+use eden_utils::{Error, ErrorCategory, Result};
+use std::fmt::Display;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Table, TableLike};
+
+use crate::SettingsLoadError;
+
+/// Setting that has been renamed. Renaming a table renames
+/// every setting inside it as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenamedSetting {
+    pub old: &'static str,
+    pub new: &'static str,
+}
+
+/// Every setting that has been renamed. Settings with their old names
+/// are still loaded but Eden warns about them until they're renamed.
+pub const RENAMED_SETTINGS: &[RenamedSetting] = &[
+    // task queue settings from the old `bot` crate
+    RenamedSetting {
+        old: "queue",
+        new: "worker",
+    },
+];
+
+/// Deprecated setting found while loading the settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecatedSetting {
+    pub key: &'static str,
+    pub renamed_to: &'static str,
+}
+
+impl Display for DeprecatedSetting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` has been renamed to `{}`",
+            self.key, self.renamed_to
+        )
+    }
+}
+
+impl From<&RenamedSetting> for DeprecatedSetting {
+    fn from(value: &RenamedSetting) -> Self {
+        Self {
+            key: value.old,
+            renamed_to: value.new,
+        }
+    }
+}
+
+/// Copies the values of deprecated settings to their new names. Values
+/// already set with the new names take precedence over them.
+pub fn apply(
+    mut builder: ConfigBuilder<DefaultState>,
+) -> Result<(ConfigBuilder<DefaultState>, Vec<DeprecatedSetting>), SettingsLoadError> {
+    let root = builder
+        .build_cloned()
+        .into_typed_error()
+        .change_context(SettingsLoadError)?
+        .collect()
+        .into_typed_error()
+        .change_context(SettingsLoadError)?;
+
+    let mut deprecated = Vec::new();
+    for renamed in RENAMED_SETTINGS {
+        let Some(value) = lookup(&root, renamed.old) else {
+            continue;
+        };
+        deprecated.push(DeprecatedSetting::from(renamed));
+
+        // tables are flattened so values set with the new names are kept
+        let mut entries = vec![(renamed.new.to_string(), value.clone())];
+        while let Some((key, value)) = entries.pop() {
+            match value.clone().into_table() {
+                Ok(table) if !table.is_empty() => {
+                    entries.extend(table.into_iter().map(|(k, v)| (format!("{key}.{k}"), v)));
+                }
+                _ if lookup(&root, &key).is_some() => {}
+                _ => {
+                    builder = builder
+                        .set_override(&key, value)
+                        .into_typed_error()
+                        .change_context(SettingsLoadError)
+                        .attach_printable_lazy(|| format!("could not override {key:?}"))?;
+                }
+            }
+        }
+    }
+
+    Ok((builder, deprecated))
+}
+
+fn lookup<'a>(root: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    let mut parts = key.split('.');
+    let mut value = root.get(parts.next()?)?;
+    for part in parts {
+        let config::ValueKind::Table(table) = &value.kind else {
+            return None;
+        };
+        value = table.get(part)?;
+    }
+    Some(value)
+}
+
+/// Renames every deprecated setting in a settings file while keeping
+/// its comments and formatting. It returns the renamed settings.
+///
+/// Only TOML settings files can be migrated.
+pub fn migrate_file(path: &Path) -> Result<Vec<DeprecatedSetting>, SettingsLoadError> {
+    if crate::format::detect(path, None) != FileFormat::Toml {
+        return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+            .attach_printable(format!("cannot migrate settings file: {}", path.display()))
+            .attach(Suggestion::new(
+                "only TOML settings files can be migrated, rename the deprecated settings manually",
+            )));
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .into_typed_error()
+        .change_context(SettingsLoadError)
+        .attach_printable_lazy(|| format!("could not read settings file: {}", path.display()))?;
+
+    let (migrated, renamed) = migrate_toml(&contents)
+        .attach_printable_lazy(|| format!("could not migrate settings file: {}", path.display()))?;
+
+    if !renamed.is_empty() {
+        std::fs::write(path, migrated)
+            .into_typed_error()
+            .change_context(SettingsLoadError)
+            .attach_printable_lazy(|| {
+                format!("could not write settings file: {}", path.display())
+            })?;
+    }
+
+    Ok(renamed)
+}
+
+fn migrate_toml(contents: &str) -> Result<(String, Vec<DeprecatedSetting>), SettingsLoadError> {
+    let mut document = contents
+        .parse::<DocumentMut>()
+        .into_typed_error()
+        .change_context(SettingsLoadError)?;
+
+    let mut renamed = Vec::new();
+    for setting in RENAMED_SETTINGS {
+        let Some(item) = remove_item(document.as_table_mut(), setting.old) else {
+            continue;
+        };
+        insert_item(document.as_table_mut(), setting.new, item).ok_or_else(|| {
+            Error::context(ErrorCategory::Unknown, SettingsLoadError).attach_printable(format!(
+                "could not rename `{}` to `{}` since `{}` is not a table",
+                setting.old, setting.new, setting.new
+            ))
+        })?;
+        renamed.push(DeprecatedSetting::from(setting));
+    }
+
+    Ok((document.to_string(), renamed))
+}
+
+fn remove_item(table: &mut dyn TableLike, key: &str) -> Option<Item> {
+    match key.split_once('.') {
+        Some((head, rest)) => remove_item(table.get_mut(head)?.as_table_like_mut()?, rest),
+        None => table.remove(key),
+    }
+}
+
+/// Values already set with the new name are kept over the old ones.
+fn insert_item(table: &mut dyn TableLike, key: &str, item: Item) -> Option<()> {
+    if let Some((head, rest)) = key.split_once('.') {
+        let mut implicit = Table::new();
+        implicit.set_implicit(true);
+
+        let parent = table.entry(head).or_insert(Item::Table(implicit));
+        return insert_item(parent.as_table_like_mut()?, rest, item);
+    }
+
+    let Some(existing) = table.get_mut(key) else {
+        table.insert(key, item);
+        return Some(());
+    };
+
+    let existing = existing.as_table_like_mut()?;
+    let Ok(old) = item.into_table() else {
+        return Some(());
+    };
+
+    for (key, value) in old {
+        if !existing.contains_key(&key) {
+            existing.insert(&key, value);
+        }
+    }
+    Some(())
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::Config;
+
+    fn from_toml(settings: &str) -> ConfigBuilder<DefaultState> {
+        Config::builder().add_source(config::File::from_str(settings, FileFormat::Toml))
+    }
+
+    #[test]
+    fn should_load_renamed_settings() {
+        let (builder, deprecated) = apply(from_toml(
+            r"
+[queue]
+max_running_tasks = 5
+max_task_retries = 2
+
+[worker]
+max_task_retries = 7
+",
+        ))
+        .unwrap();
+
+        let config = builder.build().unwrap();
+        assert_eq!(config.get_int("worker.max_running_tasks").unwrap(), 5);
+        assert_eq!(config.get_int("worker.max_task_retries").unwrap(), 7);
+        assert_eq!(
+            deprecated,
+            vec![DeprecatedSetting {
+                key: "queue",
+                renamed_to: "worker",
+            }]
+        );
+
+        let (_, deprecated) = apply(from_toml("[worker]\nmax_task_retries = 7\n")).unwrap();
+        assert!(deprecated.is_empty());
+    }
+
+    #[test]
+    fn should_migrate_toml_files() {
+        let (migrated, renamed) = migrate_toml(
+            r"
+# tasks
+[queue]
+max_running_tasks = 5 # comment
+",
+        )
+        .unwrap();
+
+        assert_eq!(renamed.len(), 1);
+        assert!(migrated.contains("# tasks"));
+        assert!(migrated.contains("[worker]"));
+        assert!(migrated.contains("max_running_tasks = 5 # comment"));
+        assert!(!migrated.contains("queue"));
+
+        let (migrated, _) = migrate_toml(
+            r"
+[queue]
+max_running_tasks = 5
+max_task_retries = 2
+
+[worker]
+max_task_retries = 7
+",
+        )
+        .unwrap();
+
+        let document = migrated.parse::<DocumentMut>().unwrap();
+        assert_eq!(
+            document["worker"]["max_running_tasks"].as_integer(),
+            Some(5)
+        );
+        assert_eq!(document["worker"]["max_task_retries"].as_integer(), Some(7));
+        assert!(document.get("queue").is_none());
+
+        let (_, renamed) = migrate_toml("[worker]\nmax_task_retries = 7\n").unwrap();
+        assert!(renamed.is_empty());
+    }
+}
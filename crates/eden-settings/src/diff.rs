@@ -0,0 +1,376 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+
+use eden_utils::types::{ProtectedString, Sensitive};
+
+use crate::{Settings, Translation};
+
+/// Settings that can only be applied after Eden restarts since
+/// they change the shard topology or resources made on startup.
+const RESTART_REQUIRED: &[&str] = &[
+    "bot.anonymization_key",
+    "bot.gateway",
+    "bot.http",
+    "bot.sharding",
+    "bot.token",
+    "coordination",
+    "database",
+    "features",
+    "startup",
+    "threads",
+    "translation",
+    "worker",
+];
+
+/// A single setting that has been changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingsChange {
+    /// Path of the setting (like `bot.local_guild.alert_channel_id`).
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl SettingsChange {
+    /// Whether this setting can only be applied after Eden restarts.
+    #[must_use]
+    pub fn requires_restart(&self) -> bool {
+        RESTART_REQUIRED.iter().any(|prefix| {
+            self.path == *prefix
+                || self
+                    .path
+                    .strip_prefix(prefix)
+                    .is_some_and(|v| v.starts_with('.'))
+        })
+    }
+}
+
+impl Display for SettingsChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let before = self.before.as_deref().unwrap_or("<none>");
+        let after = self.after.as_deref().unwrap_or("<none>");
+        write!(f, "{}: {before} -> {after}", self.path)
+    }
+}
+
+/// Difference between two settings snapshots.
+///
+/// Snapshots are taken from the [`Debug`] output of the settings,
+/// so sensitive values are always masked as `<redacted>`. Changes
+/// of sensitive values in [`Settings`] are detected separately by
+/// comparing their hashes, and are shown as `<redacted>` as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SettingsDiff {
+    pub changes: Vec<SettingsChange>,
+}
+
+impl SettingsDiff {
+    #[must_use]
+    pub fn compute(before: &impl Debug, after: &impl Debug) -> Self {
+        let before = flatten(&format!("{before:#?}"));
+        let mut after = flatten(&format!("{after:#?}"));
+
+        let mut changes = Vec::new();
+        for (path, value) in before {
+            let new_value = after.remove(&path);
+            if new_value.as_ref() != Some(&value) {
+                changes.push(SettingsChange {
+                    path,
+                    before: Some(value),
+                    after: new_value,
+                });
+            }
+        }
+        changes.extend(after.into_iter().map(|(path, value)| SettingsChange {
+            path,
+            before: None,
+            after: Some(value),
+        }));
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Self { changes }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether any of the changes can only be applied after Eden restarts.
+    #[must_use]
+    pub fn requires_restart(&self) -> bool {
+        self.changes.iter().any(SettingsChange::requires_restart)
+    }
+}
+
+impl Display for SettingsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, change) in self.changes.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{change}")?;
+            if change.requires_restart() {
+                f.write_str(" (requires restart)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Settings {
+    /// Compares the settings against the newer settings.
+    #[must_use]
+    pub fn diff(&self, new: &Settings) -> SettingsDiff {
+        let mut diff = SettingsDiff::compute(self, new);
+        let before = self.secret_hashes();
+        let mut after = new.secret_hashes();
+
+        let mut changes = Vec::new();
+        for (path, hash) in before {
+            let new_hash = after.remove(path);
+            if new_hash != Some(hash) {
+                changes.push((path, true, new_hash.is_some()));
+            }
+        }
+        changes.extend(after.into_keys().map(|path| (path, false, true)));
+
+        for (path, before, after) in changes {
+            // the value may have been added or removed which
+            // can be seen already from the `Debug` output
+            let is_listed = diff.changes.iter().any(|v| {
+                v.path == path
+                    || v.path
+                        .strip_prefix(path)
+                        .is_some_and(|v| v.starts_with('.'))
+            });
+            if is_listed {
+                continue;
+            }
+
+            let redacted = || REDACTED.to_string();
+            diff.changes.push(SettingsChange {
+                path: path.to_string(),
+                before: before.then(redacted),
+                after: after.then(redacted),
+            });
+        }
+        diff.changes.sort_by(|a, b| a.path.cmp(&b.path));
+        diff
+    }
+
+    /// Hashes of every sensitive setting that is set, keyed by
+    /// their path since their [`Debug`] output is always redacted.
+    fn secret_hashes(&self) -> BTreeMap<&'static str, u64> {
+        let mut values = BTreeMap::new();
+        let mut insert = |path: &'static str, value: Option<&str>| {
+            if let Some(value) = value {
+                values.insert(path, hash_secret(value));
+            }
+        };
+
+        insert("bot.token", Some(self.bot.token.expose()));
+        insert(
+            "bot.anonymization_key",
+            Some(self.bot.anonymization_key.expose()),
+        );
+        insert(
+            "bot.http.proxy",
+            self.bot.http.proxy.as_ref().map(Sensitive::as_str),
+        );
+        insert(
+            "bot.moderation.content_salt",
+            self.bot
+                .moderation
+                .content_salt
+                .as_ref()
+                .map(ProtectedString::expose),
+        );
+        insert("database.url", Some(&self.database.url_for_diff()));
+
+        let dsn = self.sentry.as_ref().map(|v| v.dsn.as_ref().to_string());
+        insert("sentry.dsn", dsn.as_deref());
+
+        let api_key = match &self.translation {
+            Some(Translation::DeepL { api_key }) => Some(api_key.expose()),
+            Some(Translation::LibreTranslate { api_key, .. }) => {
+                api_key.as_ref().map(ProtectedString::expose)
+            }
+            None => None,
+        };
+        insert("translation.api_key", api_key);
+
+        values
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+fn hash_secret(value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Flattens the pretty [`Debug`] output of a value into its
+/// path and value pairs.
+pub(crate) fn flatten(snapshot: &str) -> BTreeMap<String, String> {
+    // path of the nested values and how many unnamed values
+    // (like in a list or a tuple) have been found in that level
+    let mut stack: Vec<(String, usize)> = Vec::new();
+    let mut values = BTreeMap::new();
+
+    for line in snapshot.lines() {
+        let line = line.trim();
+        let line = line.strip_suffix(',').unwrap_or(line);
+        if matches!(line, "}" | "]" | ")") {
+            stack.pop();
+            continue;
+        }
+
+        let (key, value) = match line.split_once(": ") {
+            Some((key, value)) if is_field_name(key) => (key.to_string(), value),
+            _ => match stack.last_mut() {
+                Some((_, index)) => {
+                    *index += 1;
+                    ((*index - 1).to_string(), line)
+                }
+                // the name of the root value itself
+                None => {
+                    stack.push((String::new(), 0));
+                    continue;
+                }
+            },
+        };
+
+        let path = stack
+            .iter()
+            .skip(1)
+            .map(|(name, _)| name.as_str())
+            .chain(std::iter::once(key.as_str()))
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if value.ends_with(['{', '[', '(']) {
+            stack.push((key, 0));
+        } else {
+            values.insert(path, value.to_string());
+        }
+    }
+
+    values
+}
+
+fn is_field_name(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|v| v.is_ascii_alphanumeric() || v == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eden_utils::types::Sensitive;
+
+    #[derive(Debug)]
+    struct Inner {
+        id: u64,
+        tags: Vec<&'static str>,
+    }
+
+    #[derive(Debug)]
+    struct Fake {
+        name: &'static str,
+        token: Sensitive<&'static str>,
+        inner: Option<Inner>,
+    }
+
+    fn fake(token: &'static str, id: u64, tags: Vec<&'static str>) -> Fake {
+        Fake {
+            name: "eden",
+            token: Sensitive::new(token),
+            inner: Some(Inner { id, tags }),
+        }
+    }
+
+    #[test]
+    fn should_flatten_nested_values() {
+        let values = flatten(&format!("{:#?}", fake("a", 1, vec!["x", "y"])));
+        assert_eq!(values["name"], "\"eden\"");
+        assert_eq!(values["token"], "<redacted>");
+        assert_eq!(values["inner.0.id"], "1");
+        assert_eq!(values["inner.0.tags.1"], "\"y\"");
+    }
+
+    #[test]
+    fn should_diff_without_leaking_sensitive_values() {
+        let diff = SettingsDiff::compute(&fake("a", 1, vec!["x"]), &fake("b", 2, vec!["x", "y"]));
+        let paths = diff
+            .changes
+            .iter()
+            .map(|v| v.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["inner.0.id", "inner.0.tags.1"]);
+
+        assert_eq!(diff.changes[0].to_string(), "inner.0.id: 1 -> 2");
+        assert_eq!(diff.changes[1].before, None);
+        assert!(!diff.to_string().contains("token"));
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn settings(token: &str, salt: Option<&str>) -> Settings {
+        let mut source = format!(
+            "[bot]\ntoken = \"{token}\"\n\
+             [bot.local_guild]\nid = \"1\"\nalert_channel_id = \"2\"\n\
+             [database]\nurl = \"postgres://eden@localhost/eden\"\n"
+        );
+        if let Some(salt) = salt {
+            source.push_str(&format!("[bot.moderation]\ncontent_salt = \"{salt}\"\n"));
+        }
+
+        config::Config::builder()
+            .add_source(config::File::from_str(&source, config::FileFormat::Toml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+
+    #[test]
+    fn should_detect_changes_of_sensitive_settings() {
+        let diff = settings("a", None).diff(&settings("b", None));
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].path, "bot.token");
+        assert_eq!(
+            diff.changes[0].to_string(),
+            "bot.token: <redacted> -> <redacted>"
+        );
+        assert!(diff.requires_restart());
+
+        let diff = settings("a", None).diff(&settings("a", Some("salt")));
+        let paths = diff
+            .changes
+            .iter()
+            .filter(|v| v.path == "bot.moderation.content_salt")
+            .count();
+        assert_eq!(paths, 1);
+        assert!(!diff.to_string().contains("salt\""));
+
+        assert!(settings("a", Some("salt"))
+            .diff(&settings("a", Some("salt")))
+            .is_empty());
+    }
+
+    #[test]
+    fn should_detect_changes_requiring_restart() {
+        let change = |path: &str| SettingsChange {
+            path: path.into(),
+            before: None,
+            after: None,
+        };
+        assert!(change("bot.sharding.total").requires_restart());
+        assert!(change("threads").requires_restart());
+        assert!(!change("bot.local_guild.alert_channel_id").requires_restart());
+        assert!(!change("bot.tokens").requires_restart());
+    }
+}
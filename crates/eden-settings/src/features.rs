@@ -0,0 +1,44 @@
+use doku::Document;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// Switches to turn off chat features of Eden without recompiling it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct Features {
+    /// Whether father belt replies to messages sent in the local guild
+    /// (like telling members not to scream or censoring bad words).
+    ///
+    /// It can still be turned off per guild with `/settings fatherbelt`.
+    ///
+    /// It defaults to true, if not set.
+    #[builder(default = true)]
+    #[doku(example = "true")]
+    pub father_belt: bool,
+
+    /// Whether father belt greets members who introduce themselves
+    /// (like "I'm Ferris"). It has no effect if `father_belt` is off.
+    ///
+    /// It defaults to true, if not set.
+    #[builder(default = true)]
+    #[doku(example = "true")]
+    pub introductions: bool,
+
+    /// Whether members sending the same message over and over
+    /// are warned about spamming.
+    ///
+    /// It defaults to true, if not set.
+    #[builder(default = true)]
+    #[doku(example = "true")]
+    pub anti_spam: bool,
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            father_belt: true,
+            introductions: true,
+            anti_spam: true,
+        }
+    }
+}
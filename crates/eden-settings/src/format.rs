@@ -0,0 +1,54 @@
+use config::FileFormat;
+use std::path::Path;
+
+/// Parses the value of `EDEN_SETTINGS_FORMAT` variable.
+#[must_use]
+pub fn parse_hint(value: &str) -> Option<FileFormat> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "toml" => Some(FileFormat::Toml),
+        "yaml" | "yml" => Some(FileFormat::Yaml),
+        "json" => Some(FileFormat::Json),
+        _ => None,
+    }
+}
+
+/// Picks the format of a settings file from its extension.
+///
+/// Files with an unknown extension (or without it) will be read with
+/// the `hint` format if it is given. Otherwise, it falls back to TOML.
+#[must_use]
+pub fn detect(path: &Path, hint: Option<FileFormat>) -> FileFormat {
+    let extension = path
+        .extension()
+        .and_then(|v| v.to_str())
+        .and_then(parse_hint);
+
+    extension.or(hint).unwrap_or(FileFormat::Toml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_detect_from_extension() {
+        assert_eq!(detect(Path::new("eden.toml"), None), FileFormat::Toml);
+        assert_eq!(detect(Path::new("eden.yml"), None), FileFormat::Yaml);
+        assert_eq!(detect(Path::new("eden.YAML"), None), FileFormat::Yaml);
+        assert_eq!(detect(Path::new("eden.json"), None), FileFormat::Json);
+
+        // the extension takes precedence over the hint
+        let hint = Some(FileFormat::Json);
+        assert_eq!(detect(Path::new("eden.yml"), hint), FileFormat::Yaml);
+    }
+
+    #[test]
+    fn should_use_hint_for_unknown_extensions() {
+        let hint = Some(FileFormat::Yaml);
+        assert_eq!(detect(Path::new("settings"), hint), FileFormat::Yaml);
+        assert_eq!(detect(Path::new("eden.conf"), hint), FileFormat::Yaml);
+        assert_eq!(detect(Path::new("eden.conf"), None), FileFormat::Toml);
+        assert_eq!(parse_hint(" JSON "), Some(FileFormat::Json));
+        assert_eq!(parse_hint("ini"), None);
+    }
+}
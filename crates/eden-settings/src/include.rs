@@ -0,0 +1,209 @@
+use config::{Config, ConfigError, FileFormat};
+use eden_utils::error::exts::{ErrorExt, IntoTypedError, ResultExt};
+use eden_utils::{Error, ErrorCategory, Result};
+use std::path::{Path, PathBuf};
+
+use crate::SettingsLoadError;
+
+/// Resolves every settings file included with the `include` directive
+/// starting from the settings file from `path`, in merging order.
+///
+/// Included files are merged before the file that includes them, so
+/// values from the including file always take precedence. Every pattern
+/// is relative to the directory of the including file and its matches
+/// are sorted by path so the merging order is deterministic.
+///
+/// Files that are included more than once are merged only once.
+pub fn resolve_files(
+    path: &Path,
+    format_hint: Option<FileFormat>,
+) -> Result<Vec<PathBuf>, SettingsLoadError> {
+    let mut resolved = Vec::new();
+    visit(path, format_hint, &mut Vec::new(), &mut resolved)?;
+    Ok(resolved)
+}
+
+fn visit(
+    path: &Path,
+    format_hint: Option<FileFormat>,
+    stack: &mut Vec<PathBuf>,
+    resolved: &mut Vec<PathBuf>,
+) -> Result<(), SettingsLoadError> {
+    let path = path
+        .canonicalize()
+        .into_typed_error()
+        .change_context(SettingsLoadError)
+        .attach_printable_lazy(|| format!("could not find settings file: {}", path.display()))?;
+
+    if stack.contains(&path) {
+        let chain = stack
+            .iter()
+            .chain(std::iter::once(&path))
+            .map(|v| v.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+            .attach_printable(format!("settings files include each other: {chain}")));
+    }
+
+    if resolved.contains(&path) {
+        return Ok(());
+    }
+
+    let base = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    stack.push(path.clone());
+    for pattern in read_include_patterns(&path, format_hint)? {
+        for included in expand_pattern(&base, &pattern)? {
+            visit(&included, format_hint, stack, resolved)?;
+        }
+    }
+    stack.pop();
+
+    resolved.push(path);
+    Ok(())
+}
+
+fn read_include_patterns(
+    path: &Path,
+    format_hint: Option<FileFormat>,
+) -> Result<Vec<String>, SettingsLoadError> {
+    let format = crate::format::detect(path, format_hint);
+    let config = Config::builder()
+        .add_source(config::File::from(path.to_path_buf()).format(format))
+        .build()
+        .into_typed_error()
+        .change_context(SettingsLoadError)
+        .attach_printable_lazy(|| format!("could not read settings file: {}", path.display()))?;
+
+    match config.get::<Vec<String>>("include") {
+        Ok(patterns) => Ok(patterns),
+        Err(ConfigError::NotFound(..)) => Ok(Vec::new()),
+        Err(error) => Err(error)
+            .into_typed_error()
+            .change_context(SettingsLoadError)
+            .attach_printable_lazy(|| {
+                format!("`include` must be a list of paths in: {}", path.display())
+            }),
+    }
+}
+
+/// Expands a path pattern which may contain `*` and `?` wildcards in
+/// its file name (like `extra/*.toml`) into paths sorted by name.
+fn expand_pattern(base: &Path, pattern: &str) -> Result<Vec<PathBuf>, SettingsLoadError> {
+    let pattern = base.join(pattern);
+    let Some(file_name) = pattern.file_name().and_then(|v| v.to_str()) else {
+        return Ok(vec![pattern]);
+    };
+
+    if !file_name.contains(['*', '?']) {
+        return Ok(vec![pattern]);
+    }
+
+    let dir = pattern.parent().unwrap_or(base);
+    let entries = std::fs::read_dir(dir)
+        .into_typed_error()
+        .change_context(SettingsLoadError)
+        .attach_printable_lazy(|| format!("could not read directory: {}", dir.display()))?;
+
+    let mut matches = Vec::new();
+    for entry in entries {
+        let entry = entry
+            .into_typed_error()
+            .change_context(SettingsLoadError)
+            .attach_printable_lazy(|| format!("could not read directory: {}", dir.display()))?;
+
+        let path = entry.path();
+        let is_match = path.is_file()
+            && path
+                .file_name()
+                .and_then(|v| v.to_str())
+                .is_some_and(|name| matches_wildcard(file_name, name));
+
+        if is_match {
+            matches.push(path);
+        }
+    }
+    matches.sort();
+
+    Ok(matches)
+}
+
+fn matches_wildcard(pattern: &str, input: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let input = input.chars().collect::<Vec<_>>();
+
+    // position of the last `*` in the pattern and where it starts in the input
+    let mut backtrack: Option<(usize, usize)> = None;
+    let (mut p, mut i) = (0, 0);
+    while i < input.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, i));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == input[i] => {
+                p += 1;
+                i += 1;
+            }
+            _ => match backtrack {
+                Some((star, start)) => {
+                    backtrack = Some((star, start + 1));
+                    p = star + 1;
+                    i = start + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("eden-{name}-{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(dir.join("extra")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn should_match_wildcards() {
+        assert!(matches_wildcard("*.toml", "roles.toml"));
+        assert!(matches_wildcard("role?.toml", "roles.toml"));
+        assert!(matches_wildcard("*", "roles.toml"));
+        assert!(!matches_wildcard("*.toml", "roles.yml"));
+        assert!(!matches_wildcard("a*b*c", "abca"));
+    }
+
+    #[test]
+    fn should_resolve_in_merging_order() {
+        let dir = temp_dir("include-order");
+        std::fs::write(dir.join("eden.toml"), r#"include = ["extra/*.toml"]"#).unwrap();
+        std::fs::write(dir.join("extra/b.toml"), "").unwrap();
+        std::fs::write(dir.join("extra/a.toml"), r#"include = ["b.toml"]"#).unwrap();
+        std::fs::write(dir.join("extra/c.txt"), "").unwrap();
+
+        let files = resolve_files(&dir.join("eden.toml"), None).unwrap();
+        let names = files
+            .iter()
+            .map(|v| v.file_name().unwrap().to_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["b.toml", "a.toml", "eden.toml"]);
+    }
+
+    #[test]
+    fn should_detect_cycles() {
+        let dir = temp_dir("include-cycle");
+        std::fs::write(dir.join("eden.toml"), r#"include = ["extra/a.toml"]"#).unwrap();
+        std::fs::write(dir.join("extra/a.toml"), r#"include = ["../eden.toml"]"#).unwrap();
+
+        assert!(resolve_files(&dir.join("eden.toml"), None).is_err());
+    }
+}
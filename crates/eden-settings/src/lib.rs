@@ -1,27 +1,60 @@
 #![feature(debug_closure_helpers, let_chains)]
+use config::builder::DefaultState;
 use config::{Config, ConfigBuilder};
 use doku::Document;
 use eden_utils::build;
-use eden_utils::env::var_opt_parsed;
+use eden_utils::env::{var_opt, var_opt_parsed};
 use eden_utils::error::exts::AnonymizedResultExt;
+use eden_utils::error::exts::ErrorExt;
 use eden_utils::error::exts::IntoTypedError;
 use eden_utils::error::exts::ResultExt;
 use eden_utils::error::tags::Suggestion;
 use eden_utils::Result as EdenResult;
+use eden_utils::{Error, ErrorCategory};
 use serde::Deserialize;
 use std::path::{Path, PathBuf};
 use typed_builder::TypedBuilder;
 
+mod alerts;
 mod bot;
+mod coordination;
 mod database;
+mod deprecation;
+mod diff;
 mod error;
+mod features;
+mod format;
+mod include;
 mod logging;
+mod overrides;
+mod presence;
+mod retention;
+mod schema;
+mod secrets;
 mod sentry;
+mod snapshot;
+mod startup;
+mod translation;
+mod validation;
+mod watch;
 
+pub use self::alerts::*;
 pub use self::bot::*;
+pub use self::coordination::Coordination;
 pub use self::database::*;
+pub use self::deprecation::{migrate_file, DeprecatedSetting};
+pub use self::diff::{SettingsChange, SettingsDiff};
+pub use self::features::Features;
 pub use self::logging::*;
+pub use self::overrides::SettingsOverrides;
+pub use self::presence::*;
+pub use self::retention::*;
+pub use self::secrets::{encrypt_secret, generate_secrets_key, SecretSource};
 pub use self::sentry::*;
+pub use self::snapshot::{SettingsGroup, SettingsSnapshot};
+pub use self::startup::Startup;
+pub use self::translation::Translation;
+pub use self::watch::{SettingsUpdate, SettingsWatcher};
 
 pub use self::error::SettingsLoadError;
 pub use eden_tasks::Settings as Worker;
@@ -29,16 +62,51 @@ pub use eden_tasks::Settings as Worker;
 #[derive(Debug, Document, Deserialize, TypedBuilder)]
 pub struct Settings {
     pub bot: Bot,
+
+    /// Parameters for running multiple Eden instances that share
+    /// the same database.
+    #[builder(default)]
+    #[serde(default)]
+    pub coordination: Coordination,
+
     pub database: Database,
 
+    /// Switches to turn off chat features of Eden without
+    /// recompiling it. Every feature is on by default.
+    #[builder(default)]
+    #[serde(default)]
+    pub features: Features,
+
     #[builder(default)]
     #[serde(default)]
     pub logging: Logging,
 
+    /// How long Eden keeps the data it generates (like voice stats and
+    /// task history). Data older than its retention period is deleted
+    /// every night.
+    ///
+    /// Set any of these to `0s` to keep that data forever.
+    #[builder(default)]
+    #[serde(default)]
+    pub retention: Retention,
+
     #[builder(default)]
     #[serde(default)]
     pub sentry: Option<Sentry>,
 
+    /// Parameters for the startup gate where Eden waits for the
+    /// services it depends on before it starts.
+    #[builder(default)]
+    #[serde(default)]
+    pub startup: Startup,
+
+    /// Lets members translate messages with the "Translate message"
+    /// command once it is enabled in the guild with
+    /// `/settings translation enabled`.
+    #[builder(default)]
+    #[serde(default)]
+    pub translation: Option<Translation>,
+
     #[builder(default)]
     #[serde(default)]
     pub worker: Worker,
@@ -48,6 +116,26 @@ pub struct Settings {
     #[doku(skip)]
     path: Option<PathBuf>,
 
+    #[builder(setter(skip), default = None)]
+    #[serde(skip)]
+    #[doku(skip)]
+    profile: Option<String>,
+
+    #[builder(setter(skip), default = None)]
+    #[serde(skip)]
+    #[doku(skip)]
+    environment: Option<String>,
+
+    #[builder(setter(skip), default)]
+    #[serde(skip)]
+    #[doku(skip)]
+    overrides: SettingsOverrides,
+
+    #[builder(setter(skip), default)]
+    #[serde(skip)]
+    #[doku(skip)]
+    deprecated: Vec<DeprecatedSetting>,
+
     /// How many CPU threads which Eden will utilize.
     ///
     /// The good rule of thumb when setting the amount of CPU threads
@@ -69,6 +157,12 @@ pub struct Settings {
 
 impl Settings {
     pub fn from_env() -> EdenResult<Self, SettingsLoadError> {
+        Self::from_env_with(SettingsOverrides::new())
+    }
+
+    /// Loads the settings like [`Settings::from_env`] but with the
+    /// `overrides` taking precedence over the loaded values.
+    pub fn from_env_with(overrides: SettingsOverrides) -> EdenResult<Self, SettingsLoadError> {
         let mut builder = Config::builder().add_source(
             config::Environment::with_prefix("EDEN")
                 .prefix_separator("_")
@@ -76,42 +170,85 @@ impl Settings {
                 .convert_case(config::Case::Snake),
         );
 
-        let resolved_path = Self::resolve_path()?;
+        let format_hint = Self::format_hint()?;
+        let resolved_path = match overrides.path.clone() {
+            Some(path) => Some(path),
+            None => Self::resolve_path()?,
+        };
+        let environment = var_opt("EDEN_ENV").change_context(SettingsLoadError)?;
         if let Some(resolved_path) = resolved_path.as_ref() {
-            // this is to enforce users to use yaml instead
-            let source: config::File<config::FileSourceFile, config::FileFormat> =
-                resolved_path.clone().into();
+            let files = Self::resolve_files(resolved_path, environment.as_deref(), format_hint)
+                .attach_printable_lazy(|| format!("using settings environment: {environment:?}"))?;
+
+            for path in files {
+                let format = self::format::detect(&path, format_hint);
+                let source: config::File<config::FileSourceFile, config::FileFormat> = path.into();
+                builder = builder.add_source(source.format(format));
+            }
+        }
 
-            builder = builder.add_source(source.format(config::FileFormat::Toml));
+        let profile = var_opt("EDEN_PROFILE").change_context(SettingsLoadError)?;
+        if let Some(profile) = profile.as_deref() {
+            builder = Self::apply_profile(builder, profile)
+                .attach_printable_lazy(|| format!("using settings profile: {profile:?}"))?;
         }
 
         let builder = Self::resolve_alternative_vars(builder)
             .change_context(SettingsLoadError)
             .attach_printable("could not resolve settings path")?;
 
-        let mut settings: Settings = builder
+        let (mut builder, deprecated) = self::deprecation::apply(builder)?;
+        for (key, value) in overrides.values() {
+            builder = builder
+                .set_override(key, value)
+                .into_typed_error()
+                .change_context(SettingsLoadError)
+                .attach_printable_lazy(|| format!("could not override {key:?}"))?;
+        }
+
+        let builder = self::secrets::resolve_all(builder)?;
+
+        let config = builder
             .build()
             .into_typed_error()
             .change_context(SettingsLoadError)
-            .and_then(|v| {
-                v.try_deserialize()
-                    .into_typed_error()
-                    .change_context(SettingsLoadError)
-            })
             .attach_printable_lazy(|| format!("using settings file: {resolved_path:?}"))?;
 
-        settings.path = resolved_path;
-        settings.bot.sharding.check()?;
+        // Problems found are collected so all of them can be fixed in one go
+        let mut problems = self::validation::Problems::new();
+        self::validation::check_raw(&config, &mut problems);
 
-        if let Some(sentry) = settings.sentry.as_ref() {
-            sentry.check()?;
-        }
+        let deserialized = config
+            .try_deserialize::<Settings>()
+            .into_typed_error()
+            .change_context(SettingsLoadError);
+
+        let mut settings = match deserialized {
+            Ok(settings) => settings,
+            Err(error) => {
+                return Err(problems.fail(error))
+                    .attach_printable_lazy(|| format!("using settings file: {resolved_path:?}"));
+            }
+        };
+
+        self::validation::check(&settings, &mut problems);
+        problems
+            .into_result()
+            .attach_printable_lazy(|| format!("using settings file: {resolved_path:?}"))?;
+
+        settings.path = resolved_path;
+        settings.profile = profile;
+        settings.environment = environment;
+        settings.overrides = overrides;
+        settings.deprecated = deprecated;
 
         Ok(settings)
     }
 
     const ALTERNATIVE_FILE_PATHS: &[&'static str] = &[
         "eden.toml",
+        "eden.yml",
+        "eden.yaml",
         #[cfg(windows)]
         "%USERPROFILE%/.eden/settings.toml",
         // these are only applicable in Unix systems
@@ -119,6 +256,73 @@ impl Settings {
         "/etc/eden/settings.toml",
     ];
 
+    /// Gets the format of settings files from the `EDEN_SETTINGS_FORMAT`
+    /// variable. It is only used for files with unknown extensions.
+    fn format_hint() -> EdenResult<Option<config::FileFormat>, SettingsLoadError> {
+        let Some(value) = var_opt("EDEN_SETTINGS_FORMAT").change_context(SettingsLoadError)? else {
+            return Ok(None);
+        };
+
+        self::format::parse_hint(&value).map(Some).ok_or_else(|| {
+            Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable(format!("unknown settings format: {value:?}"))
+                .attach(Suggestion::new(
+                    "`EDEN_SETTINGS_FORMAT` must be either `toml`, `yaml` or `json`",
+                ))
+        })
+    }
+
+    /// Gets the path of the settings file for the specified environment
+    /// which overlays the base settings file at `base`.
+    ///
+    /// The environment is inserted before the extension of the base
+    /// settings file (`eden.toml` becomes `eden.production.toml`).
+    #[must_use]
+    pub fn environment_path(base: &Path, environment: &str) -> PathBuf {
+        let mut file_name = base.file_stem().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(environment);
+        if let Some(extension) = base.extension() {
+            file_name.push(".");
+            file_name.push(extension);
+        }
+        base.with_file_name(file_name)
+    }
+
+    /// Resolves every settings file to be merged in merging order, starting
+    /// from the base settings file and then its environment overlay
+    /// (if `environment` is set) along with the files they include.
+    fn resolve_files(
+        base: &Path,
+        environment: Option<&str>,
+        format_hint: Option<config::FileFormat>,
+    ) -> EdenResult<Vec<PathBuf>, SettingsLoadError> {
+        let mut files = self::include::resolve_files(base, format_hint)?;
+        let Some(environment) = environment else {
+            return Ok(files);
+        };
+
+        let overlay = Self::environment_path(base, environment);
+        if !overlay.is_file() {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable(format!(
+                    "could not find settings file for environment {environment:?}: {}",
+                    overlay.display()
+                ))
+                .attach(Suggestion::new(
+                    "create the settings file for the environment or unset `EDEN_ENV`",
+                )));
+        }
+
+        for path in self::include::resolve_files(&overlay, format_hint)? {
+            // files shared by both are merged once, in their last position
+            files.retain(|v| *v != path);
+            files.push(path);
+        }
+
+        Ok(files)
+    }
+
     pub fn resolve_path() -> EdenResult<Option<PathBuf>, SettingsLoadError> {
         // EDEN_SETTINGS
         let mut resolved_path = var_opt_parsed::<PathBuf>("EDEN_SETTINGS")
@@ -184,16 +388,124 @@ impl Settings {
             ..Default::default()
         };
 
-        doku::to_toml_fmt::<Self>(&fmt)
+        let mut docs = doku::to_toml_fmt::<Self>(&fmt);
+        docs.push_str(EXTRA_DOCS);
+        docs
+    }
+
+    /// Generates JSON Schema of [`Settings`] so editors can validate
+    /// and complete settings files.
+    #[must_use]
+    pub fn generate_json_schema() -> String {
+        format!("{:#}", self::schema::generate(&Self::ty()))
     }
 }
 
+const EXTRA_DOCS: &str = r#"
+
+# Settings can be split across multiple files with the `include`
+# directive. It must be placed at the top of the file.
+# 
+# Paths are relative to the file including them and may contain
+# `*` or `?` wildcards in their file names. Included files are merged
+# in alphabetical order before the file including them, so values
+# from the file including them always take precedence.
+# 
+# include = ["extra/*.toml"]
+
+# Settings files may be written in TOML, YAML or JSON. The format is
+# picked from the file extension (`.toml`, `.yml`, `.yaml` or `.json`).
+# 
+# Files with other extensions are read as TOML unless
+# `EDEN_SETTINGS_FORMAT` is set to `toml`, `yaml` or `json`.
+
+# Sensitive values (like `bot.token` and `database.url`) can be read
+# from somewhere else when Eden loads its settings by replacing them
+# with a table that only has the `from` key:
+# - `file:<path>` reads the entire file
+# - `systemd:<name>` reads a systemd credential (`LoadCredential=`)
+# - `vault:<path>#<key>` reads a key of a HashiCorp Vault secret
+#   (`VAULT_ADDR` and `VAULT_TOKEN` must be set)
+# - `encrypted:<blob>` decrypts a secret encrypted with
+#   `cargo xtask secret encrypt` (`EDEN_SECRETS_KEY` or
+#   `EDEN_SECRETS_KEY_FILE` must be set)
+# 
+# [bot]
+# token = { from = "file:/run/secrets/eden_token" }
+# 
+# [database]
+# url = { from = "vault:secret/data/eden#database_url" }
+# 
+# [sentry]
+# dsn = { from = "encrypted:<blob>" }
+
+# Settings can also be set with `EDEN_` variables (like `EDEN_BOT_TOKEN`
+# for `bot.token`). Variables are also loaded from the `.env` file in
+# the current directory or from the file at `EDEN_DOTENV` if it is set,
+# unless they're already set.
+
+# Settings for a specific environment can be kept in a separate file
+# next to the settings file, named after the environment selected with
+# `EDEN_ENV` (`eden.production.toml` if `EDEN_ENV` is `production`).
+# 
+# It is merged over the settings file, so it only needs to have values
+# that differ in that environment (like `bot.token` and IDs).
+
+# Profiles allow one settings file to serve multiple environments
+# (like development, staging and production).
+# 
+# Every value inside `[profiles.<name>]` will be merged over
+# the settings above if `EDEN_PROFILE` is set to `<name>`.
+# 
+# [profiles.dev.bot]
+# dry_run = true
+# 
+# [profiles.prod.logging]
+# style = "json""#;
+
 impl Settings {
     /// Current working path for the [`Settings`] file.
     #[must_use]
     pub fn path(&self) -> Option<&Path> {
         self.path.as_deref()
     }
+
+    /// Selected settings profile from the `EDEN_PROFILE` variable.
+    #[must_use]
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Selected settings environment from the `EDEN_ENV` variable.
+    #[must_use]
+    pub fn environment(&self) -> Option<&str> {
+        self.environment.as_deref()
+    }
+
+    /// Settings overridden from outside of the settings files
+    /// and environment variables.
+    #[must_use]
+    pub fn overrides(&self) -> &SettingsOverrides {
+        &self.overrides
+    }
+
+    /// Deprecated settings found while loading the settings.
+    #[must_use]
+    pub fn deprecated(&self) -> &[DeprecatedSetting] {
+        &self.deprecated
+    }
+
+    /// Warns about every deprecated setting found while loading
+    /// the settings. It is expected to be called once logging is set up.
+    pub fn warn_deprecated(&self) {
+        for setting in &self.deprecated {
+            tracing::warn!(
+                setting = %setting.key,
+                renamed_to = %setting.renamed_to,
+                "{setting}; run `eden config migrate` to rename it in the settings file"
+            );
+        }
+    }
 }
 
 impl Settings {
@@ -201,9 +513,49 @@ impl Settings {
         (num_cpus::get_physical() / 2).max(1)
     }
 
+    /// Merges the values of the selected profile (`[profiles.<name>]`)
+    /// over the base settings.
+    fn apply_profile(
+        mut builder: ConfigBuilder<DefaultState>,
+        profile: &str,
+    ) -> EdenResult<ConfigBuilder<DefaultState>, SettingsLoadError> {
+        let base = builder
+            .build_cloned()
+            .into_typed_error()
+            .change_context(SettingsLoadError)?;
+
+        let table = base
+            .get_table(&format!("profiles.{profile}"))
+            .into_typed_error()
+            .change_context(SettingsLoadError)
+            .attach_printable_lazy(|| format!("could not find settings profile {profile:?}"))
+            .attach(Suggestion::new(
+                "`EDEN_PROFILE` must match one of `[profiles.<name>]` tables in the settings file",
+            ))?;
+
+        // tables are flattened so profiles can override nested values only
+        let mut entries = table.into_iter().collect::<Vec<_>>();
+        while let Some((key, value)) = entries.pop() {
+            match value.clone().into_table() {
+                Ok(table) => {
+                    entries.extend(table.into_iter().map(|(k, v)| (format!("{key}.{k}"), v)));
+                }
+                Err(..) => {
+                    builder = builder
+                        .set_override(&key, value)
+                        .into_typed_error()
+                        .change_context(SettingsLoadError)
+                        .attach_printable_lazy(|| format!("could not override {key:?}"))?;
+                }
+            }
+        }
+
+        Ok(builder)
+    }
+
     fn resolve_alternative_vars(
-        mut builder: ConfigBuilder<config::builder::DefaultState>,
-    ) -> EdenResult<ConfigBuilder<config::builder::DefaultState>> {
+        mut builder: ConfigBuilder<DefaultState>,
+    ) -> EdenResult<ConfigBuilder<DefaultState>> {
         // `DATABASE_URL` is used for testing environments but this statement
         // will be disabled on release.
         if build::PROFILE != "release"
@@ -240,3 +592,72 @@ impl Settings {
         Ok(builder)
     }
 }
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: &str = r#"
+[bot]
+dry_run = false
+token = "base"
+
+[profiles.dev.bot]
+dry_run = true
+"#;
+
+    fn builder() -> ConfigBuilder<DefaultState> {
+        Config::builder().add_source(config::File::from_str(SETTINGS, config::FileFormat::Toml))
+    }
+
+    #[test]
+    fn should_merge_profile_over_base() {
+        let config = Settings::apply_profile(builder(), "dev")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(config.get_bool("bot.dry_run").unwrap());
+        assert_eq!(config.get_string("bot.token").unwrap(), "base");
+    }
+
+    #[test]
+    fn should_reject_unknown_profile() {
+        assert!(Settings::apply_profile(builder(), "prod").is_err());
+    }
+
+    #[test]
+    fn should_get_environment_path() {
+        assert_eq!(
+            Settings::environment_path(Path::new("config/eden.toml"), "production"),
+            Path::new("config/eden.production.toml")
+        );
+        assert_eq!(
+            Settings::environment_path(Path::new("/etc/eden/settings"), "staging"),
+            Path::new("/etc/eden/settings.staging")
+        );
+    }
+
+    #[test]
+    fn should_merge_environment_over_base() {
+        let dir = std::env::temp_dir().join(format!("eden-env-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let base = dir.join("eden.toml");
+        std::fs::write(&base, "[bot]\ntoken = \"base\"\ndry_run = true\n").unwrap();
+        let overlay = dir.join("eden.production.toml");
+        std::fs::write(&overlay, "[bot]\ntoken = \"prod\"\n").unwrap();
+
+        let mut builder = Config::builder();
+        for path in Settings::resolve_files(&base, Some("production"), None).unwrap() {
+            builder = builder.add_source(config::File::from(path));
+        }
+        let config = builder.build().unwrap();
+        assert_eq!(config.get_string("bot.token").unwrap(), "prod");
+        assert!(config.get_bool("bot.dry_run").unwrap());
+
+        assert!(Settings::resolve_files(&base, Some("staging"), None).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -30,9 +30,37 @@ pub struct Logging {
     ///
     /// The default value will filter only events and spans that
     /// have `info` level.
+    ///
+    /// Changes to this setting are applied right away without
+    /// restarting Eden.
     #[builder(default = "info".into())]
     #[doku(example = "info")]
     pub targets: String,
+
+    /// Spans of hot paths (like handling every message sent) that
+    /// are recorded only for a percentage of the time since recording
+    /// all of them is costly on big guilds. Errors are always recorded.
+    ///
+    /// Changes to this setting are applied right away without
+    /// restarting Eden. Sampling can still be changed with
+    /// `/admin logging` until Eden restarts or it is changed
+    /// from this setting again.
+    #[builder(default)]
+    #[serde(default)]
+    pub sampling: Vec<SpanSampling>,
+}
+
+#[derive(Debug, Clone, PartialEq, Document, Deserialize, Serialize, TypedBuilder)]
+pub struct SpanSampling {
+    /// Full path of the span (`<target>::<name>`) or its target
+    /// to sample every span of a module.
+    #[builder(setter(into))]
+    #[doku(example = "eden_bot::events::message_create::handle")]
+    pub span: String,
+
+    /// Percentage of spans (from 0 to 100) that are recorded.
+    #[doku(example = "1")]
+    pub percentage: f64,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -49,6 +77,7 @@ impl Default for Logging {
         Self {
             style: LoggingStyle::default(),
             targets: String::new(),
+            sampling: Vec::new(),
         }
     }
 }
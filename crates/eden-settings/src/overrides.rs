@@ -0,0 +1,68 @@
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+/// Settings given from outside of the settings files and environment
+/// variables (like the command-line arguments of Eden).
+///
+/// They take precedence over every other source of the settings and
+/// they're kept whenever the settings are reloaded.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SettingsOverrides {
+    /// Path of the settings file to load instead of the path
+    /// from the `EDEN_SETTINGS` variable.
+    pub path: Option<PathBuf>,
+    values: Vec<(String, String)>,
+}
+
+impl SettingsOverrides {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the value of the setting from its path (like `database.url`).
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        self.values.retain(|(k, _)| *k != key);
+        self.values.push((key, value.into()));
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.path.is_none() && self.values.is_empty()
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+// overridden values may be sensitive (like `bot.token`)
+impl Debug for SettingsOverrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let keys = self.values.iter().map(|(k, _)| k).collect::<Vec<_>>();
+        f.debug_struct("SettingsOverrides")
+            .field("path", &self.path)
+            .field("keys", &keys)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_last_value_and_redact_values() {
+        let mut overrides = SettingsOverrides::new();
+        overrides.set("bot.token", "first");
+        overrides.set("threads", "4");
+        overrides.set("bot.token", "second");
+
+        assert_eq!(
+            overrides.values().collect::<Vec<_>>(),
+            vec![("threads", "4"), ("bot.token", "second")]
+        );
+        assert!(!format!("{overrides:?}").contains("second"));
+    }
+}
@@ -0,0 +1,349 @@
+use doku::Document;
+use eden_utils::types::HumanDuration;
+use eden_utils::{error::exts::ResultExt, Error, ErrorCategory, Result};
+use serde::{Deserialize, Serialize};
+use twilight_model::gateway::payload::outgoing::update_presence::UpdatePresencePayload;
+use twilight_model::gateway::presence::{Activity, ActivityType, Status};
+use typed_builder::TypedBuilder;
+
+use crate::SettingsLoadError;
+
+/// Maximum length of the activity text allowed by Discord.
+const MAX_ACTIVITY_TEXT_LEN: usize = 128;
+
+/// Activities cannot be rotated faster than this so the shards
+/// are not rate limited by Discord for updating their presence.
+const MIN_ROTATION_INTERVAL: HumanDuration = HumanDuration::from_secs(60);
+
+/// Hosts that Discord accepts as stream URLs of streaming activities.
+const STREAM_HOSTS: &[&str] = &[
+    "https://twitch.tv/",
+    "https://www.twitch.tv/",
+    "https://youtube.com/",
+    "https://www.youtube.com/",
+];
+
+// `[[bot.presence.activities]]` from older versions of Eden has a
+// different format, so it must not be silently ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default, deny_unknown_fields)]
+pub struct Presence {
+    /// Online status of the bot.
+    ///
+    /// There are four options to choose:
+    /// - `online`
+    /// - `idle`
+    /// - `dnd` (do not disturb)
+    /// - `invisible`
+    ///
+    /// The default value is `online` if not set.
+    #[builder(default = Status::Online)]
+    #[doku(as = "String", example = "online")]
+    pub status: Status,
+
+    /// Whether the bot is away from keyboard.
+    ///
+    /// The default value is false if not set.
+    #[builder(default)]
+    #[doku(example = "false")]
+    pub afk: bool,
+
+    /// Activity shown below the bot's name.
+    ///
+    /// If it is not set, no activity will be shown.
+    #[builder(default, setter(strip_option))]
+    pub activity: Option<PresenceActivity>,
+
+    /// Other activities shown in turns with `activity`, switching
+    /// to the next one every `rotation_interval`.
+    ///
+    /// If it is empty, only `activity` will be shown.
+    #[builder(default)]
+    pub rotating_activities: Vec<PresenceActivity>,
+
+    /// How long each activity is shown before switching to the next
+    /// one. It must be at least 1 minute.
+    ///
+    /// The default value is `5` minutes if not set.
+    #[builder(default = Presence::default_rotation_interval())]
+    #[doku(example = "5m")]
+    pub rotation_interval: HumanDuration,
+}
+
+impl Presence {
+    /// Checks whether Discord accepts this presence.
+    pub fn check(&self) -> Result<(), SettingsLoadError> {
+        if self.status == Status::Offline {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError))
+                .attach_printable(
+                    "`bot.presence.status` = \"offline\" is not allowed, use \"invisible\" instead",
+                );
+        }
+
+        if self.is_rotating() && self.rotation_interval < MIN_ROTATION_INTERVAL {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError))
+                .attach_printable("`bot.presence.rotation_interval` must be at least 1 minute");
+        }
+
+        self.activities().try_for_each(PresenceActivity::check)
+    }
+
+    /// Whether there are multiple activities to be shown in turns.
+    #[must_use]
+    pub fn is_rotating(&self) -> bool {
+        self.activities().nth(1).is_some()
+    }
+
+    /// Creates the payload used to identify with or update the
+    /// presence of shards.
+    #[must_use]
+    pub fn to_payload(&self) -> UpdatePresencePayload {
+        self.to_payload_with(0)
+    }
+
+    /// Creates the payload with the activity shown after rotating
+    /// the activities `rotation` times.
+    #[must_use]
+    pub fn to_payload_with(&self, rotation: usize) -> UpdatePresencePayload {
+        let amount = self.activities().count().max(1);
+
+        // We're manually creating update presence since twilight
+        // won't allow us to use `new` function without getting an error
+        // if an empty set of activities is provided.
+        UpdatePresencePayload {
+            activities: self
+                .activities()
+                .nth(rotation % amount)
+                .map(PresenceActivity::to_activity)
+                .into_iter()
+                .collect(),
+            afk: self.afk,
+            since: None,
+            status: self.status,
+        }
+    }
+
+    fn activities(&self) -> impl Iterator<Item = &PresenceActivity> {
+        self.activity.iter().chain(&self.rotating_activities)
+    }
+
+    fn default_rotation_interval() -> HumanDuration {
+        HumanDuration::from_mins(5)
+    }
+}
+
+impl Default for Presence {
+    fn default() -> Self {
+        Self {
+            status: Status::Online,
+            afk: false,
+            activity: None,
+            rotating_activities: Vec::new(),
+            rotation_interval: Self::default_rotation_interval(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(deny_unknown_fields)]
+pub struct PresenceActivity {
+    /// Type of the activity.
+    ///
+    /// There are six options to choose:
+    /// - `playing` - "Playing {text}"
+    /// - `streaming` - "Streaming {text}" (requires `url`)
+    /// - `listening` - "Listening to {text}"
+    /// - `watching` - "Watching {text}"
+    /// - `competing` - "Competing in {text}"
+    /// - `custom` - shows `text` as a custom status
+    #[doku(as = "String", example = "streaming")]
+    #[serde(rename = "type")]
+    pub kind: PresenceActivityKind,
+
+    /// Text of the activity. It must not be longer than 128 characters.
+    #[builder(setter(into))]
+    #[doku(example = "with Ferris")]
+    pub text: String,
+
+    /// Twitch or YouTube URL of the stream.
+    ///
+    /// It is required for `streaming` activities and not
+    /// allowed for other types of activities.
+    #[builder(default, setter(into, strip_option))]
+    #[doku(example = "https://twitch.tv/ferris")]
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl PresenceActivity {
+    fn check(&self) -> Result<(), SettingsLoadError> {
+        let text_len = self.text.chars().count();
+        if text_len == 0 || text_len > MAX_ACTIVITY_TEXT_LEN {
+            return Err(Error::context(ErrorCategory::Unknown, SettingsLoadError))
+                .attach_printable("`bot.presence.activity.text` must have 1 to 128 characters");
+        }
+
+        match (self.kind, self.url.as_deref()) {
+            (PresenceActivityKind::Streaming, None) => {
+                Err(Error::context(ErrorCategory::Unknown, SettingsLoadError)).attach_printable(
+                    "`bot.presence.activity.url` is required for streaming activities",
+                )
+            }
+            (PresenceActivityKind::Streaming, Some(url))
+                if !STREAM_HOSTS.iter().any(|v| url.starts_with(v)) =>
+            {
+                Err(Error::context(ErrorCategory::Unknown, SettingsLoadError))
+                    .attach_printable("`bot.presence.activity.url` must be a Twitch or YouTube URL")
+            }
+            (PresenceActivityKind::Streaming, Some(..)) | (_, None) => Ok(()),
+            (_, Some(..)) => Err(Error::context(ErrorCategory::Unknown, SettingsLoadError))
+                .attach_printable(
+                    "`bot.presence.activity.url` is only allowed for streaming activities",
+                ),
+        }
+    }
+
+    fn to_activity(&self) -> Activity {
+        // Discord uses `state` as the text of custom statuses
+        // and ignores `name` of them.
+        let (name, state) = match self.kind {
+            PresenceActivityKind::Custom => {
+                (String::from("Custom Status"), Some(self.text.clone()))
+            }
+            _ => (self.text.clone(), None),
+        };
+
+        Activity {
+            application_id: None,
+            assets: None,
+            buttons: Vec::new(),
+            created_at: None,
+            details: None,
+            emoji: None,
+            flags: None,
+            id: None,
+            instance: None,
+            kind: self.kind.into(),
+            name,
+            party: None,
+            secrets: None,
+            state,
+            timestamps: None,
+            url: self.url.clone(),
+        }
+    }
+}
+
+/// Types of activities that bots are allowed to have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceActivityKind {
+    Playing,
+    Streaming,
+    Listening,
+    Watching,
+    Competing,
+    Custom,
+}
+
+impl From<PresenceActivityKind> for ActivityType {
+    fn from(value: PresenceActivityKind) -> Self {
+        match value {
+            PresenceActivityKind::Playing => Self::Playing,
+            PresenceActivityKind::Streaming => Self::Streaming,
+            PresenceActivityKind::Listening => Self::Listening,
+            PresenceActivityKind::Watching => Self::Watching,
+            PresenceActivityKind::Competing => Self::Competing,
+            PresenceActivityKind::Custom => Self::Custom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PresenceActivityKind as Kind;
+
+    fn activity(kind: Kind, url: Option<&str>) -> PresenceActivity {
+        PresenceActivity {
+            kind,
+            text: String::from("with Ferris"),
+            url: url.map(String::from),
+        }
+    }
+
+    #[test]
+    fn should_check_activities() {
+        let twitch = Some("https://twitch.tv/ferris");
+        assert!(activity(Kind::Playing, None).check().is_ok());
+        assert!(activity(Kind::Playing, twitch).check().is_err());
+        assert!(activity(Kind::Streaming, twitch).check().is_ok());
+        assert!(activity(Kind::Streaming, None).check().is_err());
+
+        let url = Some("https://example.com/ferris");
+        assert!(activity(Kind::Streaming, url).check().is_err());
+
+        let empty = PresenceActivity::builder()
+            .kind(Kind::Watching)
+            .text("")
+            .build();
+        assert!(empty.check().is_err());
+    }
+
+    #[test]
+    fn should_reject_unknown_fields() {
+        let old = serde_json::json!({
+            "activities": [{ "type": 0, "name": "with Ferris" }],
+        });
+        assert!(serde_json::from_value::<Presence>(old).is_err());
+
+        let activity = serde_json::json!({ "type": "playing", "name": "with Ferris" });
+        assert!(serde_json::from_value::<PresenceActivity>(activity).is_err());
+    }
+
+    #[test]
+    fn should_build_payload() {
+        let presence = Presence::builder()
+            .status(Status::Idle)
+            .activity(activity(Kind::Custom, None))
+            .build();
+
+        let payload = presence.to_payload();
+        assert_eq!(payload.status, Status::Idle);
+        assert_eq!(payload.activities.len(), 1);
+        assert_eq!(payload.activities[0].kind, ActivityType::Custom);
+        assert_eq!(payload.activities[0].state.as_deref(), Some("with Ferris"));
+
+        assert!(Presence::default().to_payload().activities.is_empty());
+    }
+
+    #[test]
+    fn should_rotate_activities() {
+        let presence = Presence::builder()
+            .activity(activity(Kind::Playing, None))
+            .rotating_activities(vec![activity(Kind::Watching, None)])
+            .build();
+
+        assert!(presence.is_rotating());
+        assert!(presence.check().is_ok());
+
+        let kinds = (0..3)
+            .map(|v| presence.to_payload_with(v).activities[0].kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                ActivityType::Playing,
+                ActivityType::Watching,
+                ActivityType::Playing
+            ]
+        );
+
+        let presence = Presence {
+            rotation_interval: HumanDuration::from_secs(10),
+            ..presence
+        };
+        assert!(presence.check().is_err());
+        assert!(!Presence::default().is_rotating());
+    }
+}
@@ -0,0 +1,172 @@
+use doku::Document;
+use eden_utils::types::HumanDuration;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// Kinds of data generated by Eden that are pruned according
+/// to their retention period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetentionCategory {
+    VoiceStats,
+    ProfileChanges,
+    RaidIncidents,
+    EmojiUploads,
+    EventReminders,
+    TaskHistory,
+    GuildSnapshots,
+    MemberCounts,
+}
+
+impl RetentionCategory {
+    pub const ALL: &'static [Self] = &[
+        Self::VoiceStats,
+        Self::ProfileChanges,
+        Self::RaidIncidents,
+        Self::EmojiUploads,
+        Self::EventReminders,
+        Self::TaskHistory,
+        Self::GuildSnapshots,
+        Self::MemberCounts,
+    ];
+
+    /// Name of the category as it is written in the settings.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::VoiceStats => "voice_stats",
+            Self::ProfileChanges => "profile_changes",
+            Self::RaidIncidents => "raid_incidents",
+            Self::EmojiUploads => "emoji_uploads",
+            Self::EventReminders => "event_reminders",
+            Self::TaskHistory => "task_history",
+            Self::GuildSnapshots => "guild_snapshots",
+            Self::MemberCounts => "member_counts",
+        }
+    }
+}
+
+/// Retention periods of every kind of data generated by Eden.
+#[derive(Debug, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct Retention {
+    /// How long daily voice activity stats of members are kept.
+    ///
+    /// Keep in mind that `/stats voice` cannot show voice activity
+    /// older than this period.
+    ///
+    /// It defaults to 365 days, if not set.
+    #[builder(default = HumanDuration::from_days(365))]
+    #[doku(example = "365d")]
+    pub voice_stats: HumanDuration,
+
+    /// How long changes of the guild's profile (name, icon, etc.)
+    /// are kept.
+    ///
+    /// It defaults to 180 days, if not set.
+    #[builder(default = HumanDuration::from_days(180))]
+    #[doku(example = "180d")]
+    pub profile_changes: HumanDuration,
+
+    /// How long raid incidents are kept after their lockdown ends.
+    /// Incidents with an active lockdown are never deleted.
+    ///
+    /// It defaults to 365 days, if not set.
+    #[builder(default = HumanDuration::from_days(365))]
+    #[doku(example = "365d")]
+    pub raid_incidents: HumanDuration,
+
+    /// How long emoji upload requests are kept after they are reviewed.
+    /// Pending requests are never deleted.
+    ///
+    /// It defaults to 90 days, if not set.
+    #[builder(default = HumanDuration::from_days(90))]
+    #[doku(example = "90d")]
+    pub emoji_uploads: HumanDuration,
+
+    /// How long sent reminders of guild scheduled events are kept
+    /// after their events have started.
+    ///
+    /// It defaults to 30 days, if not set.
+    #[builder(default = HumanDuration::from_days(30))]
+    #[doku(example = "30d")]
+    pub event_reminders: HumanDuration,
+
+    /// How long tasks that have either succeeded, failed or been
+    /// cancelled are kept.
+    ///
+    /// It defaults to 30 days, if not set.
+    #[builder(default = HumanDuration::from_days(30))]
+    #[doku(example = "30d")]
+    pub task_history: HumanDuration,
+
+    /// How long snapshots of guild data taken before risky admin
+    /// operations are kept. Snapshots cannot be restored with
+    /// `/admin restore-snapshot` once they are deleted.
+    ///
+    /// It defaults to 7 days, if not set.
+    #[builder(default = HumanDuration::from_days(7))]
+    #[doku(example = "7d")]
+    pub guild_snapshots: HumanDuration,
+
+    /// How long daily member counts of the guild are kept.
+    ///
+    /// Keep in mind that `/stats growth` cannot show member counts
+    /// older than this period.
+    ///
+    /// It defaults to 365 days, if not set.
+    #[builder(default = HumanDuration::from_days(365))]
+    #[doku(example = "365d")]
+    pub member_counts: HumanDuration,
+}
+
+impl Retention {
+    /// Gets the retention period of a category. It returns `None`
+    /// if data of that category should be kept forever.
+    #[must_use]
+    pub fn get(&self, category: RetentionCategory) -> Option<HumanDuration> {
+        let period = match category {
+            RetentionCategory::VoiceStats => self.voice_stats,
+            RetentionCategory::ProfileChanges => self.profile_changes,
+            RetentionCategory::RaidIncidents => self.raid_incidents,
+            RetentionCategory::EmojiUploads => self.emoji_uploads,
+            RetentionCategory::EventReminders => self.event_reminders,
+            RetentionCategory::TaskHistory => self.task_history,
+            RetentionCategory::GuildSnapshots => self.guild_snapshots,
+            RetentionCategory::MemberCounts => self.member_counts,
+        };
+        (!period.is_zero()).then_some(period)
+    }
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            voice_stats: HumanDuration::from_days(365),
+            profile_changes: HumanDuration::from_days(180),
+            raid_incidents: HumanDuration::from_days(365),
+            emoji_uploads: HumanDuration::from_days(90),
+            event_reminders: HumanDuration::from_days(30),
+            task_history: HumanDuration::from_days(30),
+            guild_snapshots: HumanDuration::from_days(7),
+            member_counts: HumanDuration::from_days(365),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_forever_if_zero() {
+        let retention = Retention::builder()
+            .task_history(HumanDuration::from_secs(0))
+            .build();
+
+        assert_eq!(retention.get(RetentionCategory::TaskHistory), None);
+        assert_eq!(
+            retention.get(RetentionCategory::VoiceStats),
+            Some(HumanDuration::from_days(365))
+        );
+    }
+}
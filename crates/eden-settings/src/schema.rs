@@ -0,0 +1,214 @@
+use doku::{Fields, Tag, Type, TypeKind};
+use serde_json::{json, Map, Value};
+
+/// Settings that can be read from a [secret source](crate::SecretSource)
+/// with `{ from = "..." }` instead of being written in the file.
+const SECRET_SETTINGS: &[[&str; 2]] = &[["bot", "token"], ["database", "url"]];
+
+/// Converts the [`doku`] type of the settings into JSON Schema.
+///
+/// Whether a field has a default value is not known from its type,
+/// so none of the fields are marked as required.
+#[must_use]
+pub fn generate(ty: &Type) -> Value {
+    let mut schema = convert(ty);
+    if let Value::Object(root) = &mut schema {
+        root.insert(
+            "$schema".into(),
+            json!("https://json-schema.org/draft/2020-12/schema"),
+        );
+        root.insert("title".into(), json!("Eden settings"));
+
+        // `include` and `profiles` are read before the settings are deserialized
+        if let Some(Value::Object(properties)) = root.get_mut("properties") {
+            properties.insert(
+                "include".into(),
+                json!({
+                    "description": "Settings files to be merged before this file.",
+                    "type": "array",
+                    "items": { "type": "string" },
+                }),
+            );
+            properties.insert(
+                "profiles".into(),
+                json!({
+                    "description": "Settings merged over this file if `EDEN_PROFILE` \
+                        is set to the name of the profile.",
+                    "type": "object",
+                    "additionalProperties": { "type": "object" },
+                }),
+            );
+
+            for [table, key] in SECRET_SETTINGS {
+                if let Some(value) = properties
+                    .get_mut(*table)
+                    .and_then(|v| v.get_mut("properties"))
+                    .and_then(|v| v.get_mut(*key))
+                {
+                    allow_secret_reference(value);
+                }
+            }
+        }
+    }
+    schema
+}
+
+/// Allows the setting to be set with a secret reference
+/// (`{ from = "..." }`) as well as its actual value.
+fn allow_secret_reference(schema: &mut Value) {
+    let mut value = schema.take();
+    let description = value.as_object_mut().and_then(|v| v.remove("description"));
+
+    *schema = json!({
+        "anyOf": [
+            value,
+            {
+                "type": "object",
+                "properties": {
+                    "from": {
+                        "description": "Where the secret is read from, like \
+                            `file:<path>`, `systemd:<name>`, `vault:<path>#<key>` \
+                            or `encrypted:<blob>`.",
+                        "type": "string",
+                    },
+                },
+                "required": ["from"],
+                "additionalProperties": false,
+            },
+        ],
+    });
+
+    if let Some(description) = description {
+        schema["description"] = description;
+    }
+}
+
+fn convert(ty: &Type) -> Value {
+    let mut schema = match &ty.kind {
+        TypeKind::Bool => json!({ "type": "boolean" }),
+        TypeKind::Float => json!({ "type": "number" }),
+        TypeKind::Integer => json!({ "type": "integer" }),
+        TypeKind::String => json!({ "type": "string" }),
+        TypeKind::Array { ty, size } => {
+            let mut schema = json!({ "type": "array", "items": convert(ty) });
+            if let Some(size) = size {
+                schema["minItems"] = json!(size);
+                schema["maxItems"] = json!(size);
+            }
+            schema
+        }
+        TypeKind::Tuple { fields } => json!({
+            "type": "array",
+            "prefixItems": fields.iter().map(convert).collect::<Vec<_>>(),
+            "minItems": fields.len(),
+            "maxItems": fields.len(),
+        }),
+        TypeKind::Map { value, .. } => json!({
+            "type": "object",
+            "additionalProperties": convert(value),
+        }),
+        // optional fields are left out instead of being set to null
+        TypeKind::Optional { ty } => convert(ty),
+        TypeKind::Struct { fields, .. } => convert_fields(fields),
+        TypeKind::Enum { tag, variants } => {
+            let variants = variants
+                .iter()
+                .map(|variant| {
+                    let mut schema = convert_variant(tag, variant.id, &variant.fields);
+                    if let Some(comment) = variant.comment {
+                        schema["description"] = json!(comment);
+                    }
+                    schema
+                })
+                .collect::<Vec<_>>();
+
+            json!({ "anyOf": variants })
+        }
+    };
+
+    if let (Some(comment), Value::Object(object)) = (ty.comment, &mut schema) {
+        object.insert("description".into(), json!(comment));
+    }
+    schema
+}
+
+fn convert_fields(fields: &Fields) -> Value {
+    match fields {
+        Fields::Named { fields } => {
+            let mut properties = Map::new();
+            for (name, field) in fields {
+                // flattened fields are merged into their parent like serde does
+                if field.flattened {
+                    if let Value::Object(mut flattened) = convert(&field.ty) {
+                        if let Some(Value::Object(inner)) = flattened.remove("properties") {
+                            properties.extend(inner);
+                        }
+                    }
+                    continue;
+                }
+                properties.insert((*name).to_string(), convert(&field.ty));
+            }
+            json!({ "type": "object", "properties": properties })
+        }
+        // newtypes are represented as their inner value
+        Fields::Unnamed { fields } if fields.len() == 1 => convert(&fields[0].ty),
+        Fields::Unnamed { fields } => json!({
+            "type": "array",
+            "prefixItems": fields.iter().map(|v| convert(&v.ty)).collect::<Vec<_>>(),
+        }),
+        Fields::Unit => json!({ "type": "null" }),
+    }
+}
+
+fn convert_variant(tag: &Tag, id: &str, fields: &Fields) -> Value {
+    match (tag, fields) {
+        (Tag::None, fields) => convert_fields(fields),
+        (Tag::External, Fields::Unit) => json!({ "const": id }),
+        (Tag::External, fields) => json!({
+            "type": "object",
+            "properties": { id: convert_fields(fields) },
+            "required": [id],
+            "additionalProperties": false,
+        }),
+        (Tag::Internal { tag }, fields) => {
+            let mut schema = match convert_fields(fields) {
+                schema @ Value::Object(..) if matches!(fields, Fields::Named { .. }) => schema,
+                _ => json!({ "type": "object", "properties": {} }),
+            };
+            schema["properties"][*tag] = json!({ "const": id });
+            schema["required"] = json!([tag]);
+            schema
+        }
+        (Tag::Adjacent { tag, .. }, Fields::Unit) => json!({
+            "type": "object",
+            "properties": { *tag: { "const": id } },
+            "required": [tag],
+        }),
+        (Tag::Adjacent { tag, content }, fields) => json!({
+            "type": "object",
+            "properties": {
+                *tag: { "const": id },
+                *content: convert_fields(fields),
+            },
+            "required": [tag, content],
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Settings;
+
+    #[test]
+    fn should_generate_settings_schema() {
+        let schema = super::generate(&<Settings as doku::Document>::ty());
+        let properties = &schema["properties"];
+
+        let token = &properties["bot"]["properties"]["token"];
+        assert_eq!(token["anyOf"][0]["type"], "string");
+        assert_eq!(token["anyOf"][1]["required"][0], "from");
+        assert!(properties["database"]["properties"]["url"]["anyOf"].is_array());
+        assert_eq!(properties["threads"]["type"], "integer");
+        assert_eq!(properties["include"]["type"], "array");
+    }
+}
@@ -0,0 +1,404 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use config::builder::DefaultState;
+use config::{ConfigBuilder, Map, Value};
+use eden_utils::error::exts::{ErrorExt, IntoTypedError, ResultExt};
+use eden_utils::error::tags::Suggestion;
+use eden_utils::{Error, ErrorCategory, Result};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::SettingsLoadError;
+
+/// How long Eden waits for Vault to respond with a secret.
+const VAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where the value of a sensitive setting (like `bot.token`) is read
+/// from when Eden loads its settings.
+///
+/// Sensitive settings refer to their source with a table that only has
+/// the `from` key, like `bot.token = { from = "file:/run/secrets/token" }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    /// `file:<path>` reads the entire file.
+    File(PathBuf),
+    /// `systemd:<name>` reads the credential passed by systemd
+    /// with `LoadCredential=` or `SetCredential=`.
+    SystemdCredential(String),
+    /// `vault:<path>#<key>` reads a key of a secret from HashiCorp Vault
+    /// located at `VAULT_ADDR` with the token from `VAULT_TOKEN`.
+    Vault { path: String, key: String },
+    /// `encrypted:<blob>` decrypts a secret encrypted with
+    /// [`encrypt_secret`] using the key from `EDEN_SECRETS_KEY`
+    /// or the file at `EDEN_SECRETS_KEY_FILE`.
+    Encrypted(String),
+}
+
+impl SecretSource {
+    /// Reads the value of the secret from its source.
+    pub fn resolve(&self) -> Result<String, SettingsLoadError> {
+        match self {
+            Self::File(path) => read_file(path.clone()),
+            Self::SystemdCredential(name) => {
+                let dir = eden_utils::env::var_opt("CREDENTIALS_DIRECTORY")
+                    .change_context(SettingsLoadError)?
+                    .ok_or_else(|| {
+                        Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                            .attach_printable(format!("could not find systemd credential {name:?}"))
+                            .attach(Suggestion::new(
+                                "systemd credentials are only available if Eden is run as a systemd service with `LoadCredential=`",
+                            ))
+                    })?;
+
+                read_file(PathBuf::from(dir).join(name))
+            }
+            Self::Vault { path, key } => read_vault(path, key),
+            Self::Encrypted(blob) => decrypt(&secrets_key()?, blob),
+        }
+    }
+}
+
+impl FromStr for SecretSource {
+    type Err = Error<SettingsLoadError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable(format!("invalid secret source: {s:?}"))
+                .attach(Suggestion::new(
+                    "secret sources must be either `file:<path>`, `systemd:<name>`, `vault:<path>#<key>` or `encrypted:<blob>`",
+                ))
+        };
+
+        let (scheme, value) = s.split_once(':').ok_or_else(invalid)?;
+        match scheme {
+            "file" if !value.is_empty() => Ok(Self::File(PathBuf::from(value))),
+            "systemd" if !value.is_empty() => Ok(Self::SystemdCredential(value.to_string())),
+            "vault" => match value.split_once('#') {
+                Some((path, key)) if !path.is_empty() && !key.is_empty() => Ok(Self::Vault {
+                    path: path.trim_matches('/').to_string(),
+                    key: key.to_string(),
+                }),
+                _ => Err(invalid()),
+            },
+            "encrypted" if !value.is_empty() => Ok(Self::Encrypted(value.to_string())),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Replaces every secret reference (`{ from = "..." }`) in the settings
+/// with the value read from its [source](SecretSource).
+///
+/// Secrets inside `[profiles.<name>]` are left alone since they're
+/// resolved once the profile is merged over the base settings.
+pub fn resolve_all(
+    mut builder: ConfigBuilder<DefaultState>,
+) -> Result<ConfigBuilder<DefaultState>, SettingsLoadError> {
+    let config = builder
+        .build_cloned()
+        .into_typed_error()
+        .change_context(SettingsLoadError)?;
+
+    let root = config
+        .collect()
+        .into_typed_error()
+        .change_context(SettingsLoadError)?;
+
+    let mut entries = root
+        .into_iter()
+        .filter(|(key, _)| key != "profiles")
+        .collect::<Vec<_>>();
+
+    while let Some((key, value)) = entries.pop() {
+        let Ok(table) = value.into_table() else {
+            continue;
+        };
+
+        let Some(source) = secret_reference(&table) else {
+            entries.extend(table.into_iter().map(|(k, v)| (format!("{key}.{k}"), v)));
+            continue;
+        };
+
+        let secret = source
+            .into_string()
+            .into_typed_error()
+            .change_context(SettingsLoadError)
+            .and_then(|v| v.parse::<SecretSource>())
+            .and_then(|v| v.resolve())
+            .attach_printable_lazy(|| format!("could not resolve secret of {key:?}"))?;
+
+        builder = builder
+            .set_override(&key, secret)
+            .into_typed_error()
+            .change_context(SettingsLoadError)
+            .attach_printable_lazy(|| format!("could not override {key:?}"))?;
+    }
+
+    Ok(builder)
+}
+
+/// Gets the source of a table if it only has the `from` key.
+fn secret_reference(table: &Map<String, Value>) -> Option<Value> {
+    if table.len() == 1 {
+        table.get("from").cloned()
+    } else {
+        None
+    }
+}
+
+fn read_file(path: PathBuf) -> Result<String, SettingsLoadError> {
+    let mut value = std::fs::read_to_string(&path)
+        .into_typed_error()
+        .change_context(SettingsLoadError)
+        .attach_printable_lazy(|| format!("could not read secret file: {}", path.display()))?;
+
+    // files written with `echo` or text editors usually end with a new line
+    let len = value.trim_end_matches(['\r', '\n']).len();
+    value.truncate(len);
+
+    Ok(value)
+}
+
+fn read_vault(path: &str, key: &str) -> Result<String, SettingsLoadError> {
+    let addr = eden_utils::env::var_opt("VAULT_ADDR").change_context(SettingsLoadError)?;
+    let token = eden_utils::env::var_opt("VAULT_TOKEN").change_context(SettingsLoadError)?;
+    let (Some(addr), Some(token)) = (addr, token) else {
+        return Err(
+            Error::context(ErrorCategory::Unknown, SettingsLoadError).attach_printable(
+                "`VAULT_ADDR` and `VAULT_TOKEN` are required to read Vault secrets",
+            ),
+        );
+    };
+
+    let url = format!("{}/v1/{path}", addr.trim_end_matches('/'));
+    let response = ureq::get(&url)
+        .set("X-Vault-Token", &token)
+        .timeout(VAULT_TIMEOUT)
+        .call()
+        .into_typed_error()
+        .change_context(SettingsLoadError)
+        .attach_printable_lazy(|| format!("could not request Vault secret at {path:?}"))?;
+
+    let body = response
+        .into_string()
+        .into_typed_error()
+        .change_context(SettingsLoadError)
+        .attach_printable_lazy(|| format!("could not read Vault secret at {path:?}"))?;
+
+    extract_vault_secret(&body, key).ok_or_else(|| {
+        Error::context(ErrorCategory::Unknown, SettingsLoadError).attach_printable(format!(
+            "could not find {key:?} in Vault secret at {path:?}"
+        ))
+    })
+}
+
+/// Generates a random key for encrypting secrets, encoded in base64.
+///
+/// It is meant to be stored in `EDEN_SECRETS_KEY` or in the file
+/// at `EDEN_SECRETS_KEY_FILE`, separately from the settings file.
+pub fn generate_secrets_key() -> Result<String, SettingsLoadError> {
+    let mut key = [0; 32];
+    SystemRandom::new().fill(&mut key).map_err(|_| {
+        Error::context(ErrorCategory::Unknown, SettingsLoadError)
+            .attach_printable("could not generate secrets key")
+    })?;
+    Ok(BASE64.encode(key))
+}
+
+/// Encrypts a secret with the key from `EDEN_SECRETS_KEY` or the file
+/// at `EDEN_SECRETS_KEY_FILE` so it can be stored in the settings file
+/// as `{ from = "encrypted:<blob>" }`.
+pub fn encrypt_secret(secret: &str) -> Result<String, SettingsLoadError> {
+    encrypt(&secrets_key()?, secret)
+}
+
+fn secrets_key() -> Result<LessSafeKey, SettingsLoadError> {
+    let key = eden_utils::env::var_opt("EDEN_SECRETS_KEY").change_context(SettingsLoadError)?;
+    let key = match key {
+        Some(key) => key,
+        None => {
+            let path = eden_utils::env::var_opt("EDEN_SECRETS_KEY_FILE")
+                .change_context(SettingsLoadError)?
+                .ok_or_else(|| {
+                    Error::context(ErrorCategory::Unknown, SettingsLoadError).attach_printable(
+                        "`EDEN_SECRETS_KEY` or `EDEN_SECRETS_KEY_FILE` is required for encrypted secrets",
+                    )
+                })?;
+
+            read_file(PathBuf::from(path))?
+        }
+    };
+
+    parse_secrets_key(&key)
+}
+
+/// Secrets are encrypted with AES-256-GCM and keys are 256 bits
+/// encoded in base64.
+fn parse_secrets_key(key: &str) -> Result<LessSafeKey, SettingsLoadError> {
+    BASE64
+        .decode(key.trim())
+        .ok()
+        .and_then(|bytes| UnboundKey::new(&AES_256_GCM, &bytes).ok())
+        .map(LessSafeKey::new)
+        .ok_or_else(|| {
+            Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable("invalid secrets key")
+                .attach(Suggestion::new(
+                    "secrets keys must be 32 bytes encoded in base64 (generate one with `cargo xtask secret key`)",
+                ))
+        })
+}
+
+/// Encrypted secrets are the random nonce followed by the ciphertext
+/// (with its tag), encoded in base64.
+fn encrypt(key: &LessSafeKey, secret: &str) -> Result<String, SettingsLoadError> {
+    let error = || {
+        Error::context(ErrorCategory::Unknown, SettingsLoadError)
+            .attach_printable("could not encrypt secret")
+    };
+
+    let mut nonce = [0; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce).map_err(|_| error())?;
+
+    let mut ciphertext = secret.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut ciphertext,
+    )
+    .map_err(|_| error())?;
+
+    let mut blob = nonce.to_vec();
+    blob.append(&mut ciphertext);
+    Ok(BASE64.encode(blob))
+}
+
+fn decrypt(key: &LessSafeKey, blob: &str) -> Result<String, SettingsLoadError> {
+    let invalid = || {
+        Error::context(ErrorCategory::Unknown, SettingsLoadError)
+            .attach_printable("could not decrypt encrypted secret")
+            .attach(Suggestion::new(
+                "make sure the secret is encrypted with the same key as `EDEN_SECRETS_KEY` or `EDEN_SECRETS_KEY_FILE`",
+            ))
+    };
+
+    let mut blob = BASE64.decode(blob.trim()).map_err(|_| invalid())?;
+    if blob.len() < NONCE_LEN {
+        return Err(invalid());
+    }
+
+    let mut ciphertext = blob.split_off(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(&blob).map_err(|_| invalid())?;
+    let secret = key
+        .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+        .map_err(|_| invalid())?;
+
+    String::from_utf8(secret.to_vec()).map_err(|_| invalid())
+}
+
+/// Gets the value of `key` from the response of Vault's KV secrets
+/// engine. Both version 1 and version 2 of the engine are supported.
+fn extract_vault_secret(body: &str, key: &str) -> Option<String> {
+    let response = serde_json::from_str::<serde_json::Value>(body).ok()?;
+    let data = response.get("data")?;
+
+    // version 2 wraps the secret with its metadata
+    let secret = match data.get("data") {
+        Some(inner) if inner.is_object() && data.get("metadata").is_some() => inner,
+        _ => data,
+    };
+
+    match secret.get(key)? {
+        serde_json::Value::String(value) => Some(value.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::Config;
+
+    #[test]
+    fn should_parse_sources() {
+        assert_eq!(
+            "file:/run/secrets/token".parse::<SecretSource>().unwrap(),
+            SecretSource::File(PathBuf::from("/run/secrets/token"))
+        );
+        assert_eq!(
+            "systemd:token".parse::<SecretSource>().unwrap(),
+            SecretSource::SystemdCredential(String::from("token"))
+        );
+        assert_eq!(
+            "vault:/secret/data/eden#token"
+                .parse::<SecretSource>()
+                .unwrap(),
+            SecretSource::Vault {
+                path: String::from("secret/data/eden"),
+                key: String::from("token"),
+            }
+        );
+
+        assert_eq!(
+            "encrypted:AAAA".parse::<SecretSource>().unwrap(),
+            SecretSource::Encrypted(String::from("AAAA"))
+        );
+
+        assert!("vault:secret/data/eden".parse::<SecretSource>().is_err());
+        assert!("encrypted:".parse::<SecretSource>().is_err());
+        assert!("file:".parse::<SecretSource>().is_err());
+        assert!("env:TOKEN".parse::<SecretSource>().is_err());
+    }
+
+    #[test]
+    fn should_extract_vault_secrets() {
+        let v1 = r#"{"data":{"token":"abc"}}"#;
+        assert_eq!(extract_vault_secret(v1, "token").as_deref(), Some("abc"));
+
+        let v2 = r#"{"data":{"data":{"token":"abc"},"metadata":{"version":1}}}"#;
+        assert_eq!(extract_vault_secret(v2, "token").as_deref(), Some("abc"));
+        assert_eq!(extract_vault_secret(v2, "url"), None);
+    }
+
+    #[test]
+    fn should_decrypt_encrypted_secrets() {
+        let key = parse_secrets_key(&generate_secrets_key().unwrap()).unwrap();
+        let blob = encrypt(&key, "hello").unwrap();
+        assert_eq!(decrypt(&key, &blob).unwrap(), "hello");
+
+        // nonces are random so the same secret is never encrypted the same way
+        assert_ne!(encrypt(&key, "hello").unwrap(), blob);
+
+        let other = parse_secrets_key(&generate_secrets_key().unwrap()).unwrap();
+        assert!(decrypt(&other, &blob).is_err());
+        assert!(decrypt(&key, "AAAA").is_err());
+        assert!(parse_secrets_key("AAAA").is_err());
+    }
+
+    #[test]
+    fn should_resolve_secret_references() {
+        let path = std::env::temp_dir().join(format!("eden-secret-{}", std::process::id()));
+        std::fs::write(&path, "hello\n").unwrap();
+
+        let settings = format!(
+            r#"
+[bot]
+token = {{ from = "file:{}" }}
+
+[profiles.dev.bot]
+token = {{ from = "systemd:token" }}
+"#,
+            path.display()
+        );
+        let builder = Config::builder()
+            .add_source(config::File::from_str(&settings, config::FileFormat::Toml));
+
+        let config = resolve_all(builder).unwrap().build().unwrap();
+        assert_eq!(config.get_string("bot.token").unwrap(), "hello");
+    }
+}
@@ -0,0 +1,48 @@
+use doku::Document;
+use eden_utils::types::HumanDuration;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+/// Parameters for the startup gate where Eden waits for the services
+/// it depends on before running migrations and connecting shards.
+#[derive(Debug, Deserialize, Document, Serialize, TypedBuilder)]
+#[serde(default)]
+pub struct Startup {
+    /// Whether Eden should wait for the database to accept connections
+    /// and for the Discord API to be reachable before it starts.
+    ///
+    /// It is useful if Eden may start before its database does
+    /// (like in docker-compose). Eden fails right away if any of them
+    /// is not available and it is turned off.
+    ///
+    /// The default value is true if not set.
+    #[builder(default = true)]
+    #[doku(example = "true")]
+    pub wait_for_dependencies: bool,
+
+    /// How long Eden waits for every dependency to be available
+    /// before it gives up and fails to start.
+    ///
+    /// It defaults to `1` minute if not set.
+    #[builder(default = HumanDuration::from_secs(60))]
+    #[doku(example = "1m")]
+    pub timeout: HumanDuration,
+
+    /// How long Eden waits before checking an unavailable
+    /// dependency again.
+    ///
+    /// It defaults to `2` seconds if not set.
+    #[builder(default = HumanDuration::from_secs(2))]
+    #[doku(example = "2s")]
+    pub retry_interval: HumanDuration,
+}
+
+impl Default for Startup {
+    fn default() -> Self {
+        Self {
+            wait_for_dependencies: true,
+            timeout: HumanDuration::from_secs(60),
+            retry_interval: HumanDuration::from_secs(2),
+        }
+    }
+}
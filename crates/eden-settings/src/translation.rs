@@ -0,0 +1,42 @@
+use doku::Document;
+use eden_utils::types::ProtectedString;
+use serde::{Deserialize, Serialize};
+
+/// Service used to translate messages with the "Translate message"
+/// command.
+#[derive(Debug, Document, Deserialize, Serialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum Translation {
+    /// Translates messages with DeepL.
+    DeepL {
+        /// Authentication key of your DeepL API account.
+        ///
+        /// Keys of DeepL API Free accounts (ending with `:fx`) are
+        /// sent to the free API instead.
+        #[doku(as = "String", example = "<insert key here>")]
+        api_key: ProtectedString,
+    },
+    /// Translates messages with a LibreTranslate instance.
+    LibreTranslate {
+        /// Base URL of the LibreTranslate instance.
+        #[doku(example = "https://libretranslate.com")]
+        url: String,
+
+        /// API key required by some LibreTranslate instances.
+        #[doku(as = "String", example = "<insert key here>")]
+        #[serde(default)]
+        api_key: Option<ProtectedString>,
+    },
+}
+
+impl Translation {
+    #[must_use]
+    pub fn has_valid_url(&self) -> bool {
+        match self {
+            Self::DeepL { .. } => true,
+            Self::LibreTranslate { url, .. } => {
+                url.starts_with("http://") || url.starts_with("https://")
+            }
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use config::{Config, ConfigError, Map, Value};
+use eden_utils::error::exts::ErrorExt;
+use eden_utils::error::tags::Suggestion;
+use eden_utils::{Error, ErrorCategory, Result};
+use std::path::Path;
+
+use crate::{Settings, SettingsLoadError};
+
+/// Collects every problem found in the settings so all of them
+/// can be reported at once instead of one at a time.
+#[derive(Default)]
+pub struct Problems(Option<Error<SettingsLoadError>>);
+
+impl Problems {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a problem with a message and a suggestion to fix it.
+    pub fn add(&mut self, message: impl Into<String>, suggestion: &'static str) {
+        self.push(
+            Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                .attach_printable(message.into())
+                .attach(Suggestion::new(suggestion)),
+        );
+    }
+
+    /// Adds the error of `result` as a problem if there's any.
+    pub fn check(&mut self, result: Result<(), SettingsLoadError>) {
+        if let Err(error) = result {
+            self.push(error);
+        }
+    }
+
+    pub fn push(&mut self, error: Error<SettingsLoadError>) {
+        match self.0.as_mut() {
+            Some(problems) => problems.extend_one(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns every problem found as one error.
+    pub fn into_result(self) -> Result<(), SettingsLoadError> {
+        self.0.map_or(Ok(()), Err)
+    }
+
+    /// Adds `error` as the last problem and returns every
+    /// problem found as one error.
+    pub fn fail(mut self, error: Error<SettingsLoadError>) -> Error<SettingsLoadError> {
+        self.push(error);
+        match self.0 {
+            Some(problems) => problems,
+            None => unreachable!("problems must not be empty after pushing one"),
+        }
+    }
+}
+
+/// Checks the raw values of the settings before they're deserialized
+/// since deserialization stops at the first invalid value it finds.
+pub fn check_raw(config: &Config, problems: &mut Problems) {
+    match config.get_string("bot.token") {
+        Ok(token) if !is_valid_token(&token) => problems.add(
+            "`bot.token` is not a valid Discord bot token",
+            "copy the token again from the Bot page of your application in the Discord Developer Portal",
+        ),
+        Ok(..) => {}
+        Err(ConfigError::NotFound(..)) => problems.add(
+            "`bot.token` is missing",
+            "set `bot.token` in the settings file or the `EDEN_BOT_TOKEN` variable",
+        ),
+        Err(..) => problems.add(
+            "`bot.token` must be a string",
+            "wrap the token in quotes",
+        ),
+    }
+
+    match config.get_string("bot.anonymization_key") {
+        Ok(key) if config.get_string("bot.token").is_ok_and(|v| v == key) => problems.add(
+            "`bot.anonymization_key` must not be the same as `bot.token`",
+            "generate a random secret with `openssl rand -hex 32` and use it instead",
+        ),
+        Ok(..) => {}
+        Err(ConfigError::NotFound(..)) => problems.add(
+            "`bot.anonymization_key` is missing",
+            "generate a random secret with `openssl rand -hex 32` and set it in `bot.anonymization_key`",
+        ),
+        Err(..) => problems.add(
+            "`bot.anonymization_key` must be a string",
+            "wrap the secret in quotes",
+        ),
+    }
+
+    if config.get_int("bot.sharding.total").is_ok_and(|v| v <= 0) {
+        problems.add(
+            "`bot.sharding.total` must be at least 1",
+            "set `bot.sharding.total` to 1 if you're running Eden with one shard",
+        );
+    }
+
+    let Ok(root) = config.collect() else {
+        return;
+    };
+
+    let mut ids = Vec::new();
+    collect_ids(String::new(), root, &mut ids);
+    for (path, value) in ids {
+        if !is_snowflake(&value) {
+            problems.add(
+                format!("`{path}` is not a valid Discord ID"),
+                "copy the ID from Discord with Developer Mode turned on",
+            );
+        }
+    }
+}
+
+/// Checks the deserialized settings.
+pub fn check(settings: &Settings, problems: &mut Problems) {
+    problems.check(settings.bot.gateway.check());
+    problems.check(settings.bot.http.check());
+    problems.check(settings.bot.presence.check());
+    problems.check(settings.bot.sharding.check());
+    problems.check(settings.database.check());
+
+    if settings.coordination.heartbeat_interval.is_zero() {
+        problems.add(
+            "`coordination.heartbeat_interval` must not be zero",
+            "remove `coordination.heartbeat_interval` to use the default of 15 seconds",
+        );
+    }
+
+    if settings.startup.retry_interval.is_zero() {
+        problems.add(
+            "`startup.retry_interval` must not be zero",
+            "remove `startup.retry_interval` to use the default of 2 seconds",
+        );
+    }
+
+    for sampling in &settings.logging.sampling {
+        if !(0. ..=100.).contains(&sampling.percentage) {
+            problems.add(
+                format!(
+                    "`logging.sampling` percentage of {:?} must be from 0 to 100",
+                    sampling.span
+                ),
+                "set the percentage to 100 to record every span",
+            );
+        }
+    }
+
+    if !settings.worker.has_valid_payload_key() {
+        problems.add(
+            "`worker.payload_key` is not a valid key",
+            "payload keys must be 32 bytes encoded in base64 (generate one with `cargo xtask secret key`)",
+        );
+    }
+
+    if let Some(sentry) = settings.sentry.as_ref() {
+        problems.check(sentry.check());
+    }
+
+    if settings
+        .translation
+        .as_ref()
+        .is_some_and(|v| !v.has_valid_url())
+    {
+        problems.add(
+            "`translation.url` is not a valid URL",
+            "use the full URL of the LibreTranslate instance (like `https://libretranslate.com`)",
+        );
+    }
+
+    let paths = [
+        ("bot.control_socket", settings.bot.control_socket.as_deref()),
+        ("bot.dry_run_output", settings.bot.dry_run_output.as_deref()),
+    ];
+    for (name, path) in paths {
+        let Some(path) = path else {
+            continue;
+        };
+
+        if !is_reachable(path) {
+            problems.add(
+                format!(
+                    "`{name}` is in a directory that does not exist: {}",
+                    path.display()
+                ),
+                "create the directory first or pick another path",
+            );
+        }
+    }
+}
+
+/// Gets every value of the local guild ID and channel IDs
+/// (`*channel_id`) along with their paths.
+fn collect_ids(parent: String, table: Map<String, Value>, output: &mut Vec<(String, Value)>) {
+    for (key, value) in table {
+        let path = if parent.is_empty() {
+            key.clone()
+        } else {
+            format!("{parent}.{key}")
+        };
+
+        // profiles are checked once they're merged over the base settings
+        if path == "profiles" {
+            continue;
+        }
+
+        if path == "bot.local_guild.id" || key.ends_with("channel_id") {
+            output.push((path, value));
+        } else if let Ok(table) = value.into_table() {
+            collect_ids(path, table, output);
+        }
+    }
+}
+
+fn is_snowflake(value: &Value) -> bool {
+    value.clone().into_uint().is_ok_and(|v| v > 0)
+}
+
+/// Discord bot tokens have three segments separated by dots.
+fn is_valid_token(token: &str) -> bool {
+    let segments = token.split('.').collect::<Vec<_>>();
+    segments.len() == 3
+        && segments.iter().all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// Whether a file can be created at `path` because its directory exists.
+fn is_reachable(path: &Path) -> bool {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.is_dir(),
+        _ => true,
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: &str = r#"
+[bot]
+token = "not a token"
+
+[bot.sharding]
+type = "single"
+id = 0
+total = 0
+
+[bot.local_guild]
+id = "<insert me>"
+alert_channel_id = 0
+
+[bot.local_guild.alerts.raid]
+channel_id = 1234
+
+[profiles.dev.bot.local_guild]
+id = "<insert me>"
+"#;
+
+    fn count(problems: Problems) -> usize {
+        let Err(error) = problems.into_result() else {
+            return 0;
+        };
+        error.get_attached::<Suggestion>().count()
+    }
+
+    #[test]
+    fn should_collect_every_raw_problem() {
+        let config = Config::builder()
+            .add_source(config::File::from_str(SETTINGS, config::FileFormat::Toml))
+            .build()
+            .unwrap();
+
+        let mut problems = Problems::new();
+        check_raw(&config, &mut problems);
+
+        // token, anonymization key, shard total, local guild ID
+        // and alert channel ID
+        assert_eq!(count(problems), 5);
+    }
+
+    #[test]
+    fn should_check_token_format() {
+        assert!(is_valid_token("MTA4NjQ.GhT5cQ.x1-y2_z3"));
+        assert!(!is_valid_token("MTA4NjQ.GhT5cQ"));
+        assert!(!is_valid_token("MTA4NjQ..x1y2z3"));
+        assert!(!is_valid_token("MTA4NjQ.GhT5cQ.x1 y2"));
+    }
+
+    #[test]
+    fn should_check_reachable_paths() {
+        assert!(is_reachable(Path::new("eden.sock")));
+        assert!(is_reachable(&std::env::temp_dir().join("eden.sock")));
+        assert!(!is_reachable(Path::new("/does/not/exist/eden.sock")));
+    }
+}
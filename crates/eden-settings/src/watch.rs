@@ -0,0 +1,228 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{broadcast, Notify};
+use tracing::{debug, info, trace, warn};
+
+use crate::{Settings, SettingsDiff};
+
+/// How often the settings files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maximum amount of updates kept for subscribers that
+/// have not received them yet.
+const UPDATES_CAPACITY: usize = 16;
+
+/// Settings reloaded from the settings files along with
+/// what has changed from the previous settings.
+#[derive(Debug, Clone)]
+pub struct SettingsUpdate {
+    pub previous: Arc<Settings>,
+    pub current: Arc<Settings>,
+    pub diff: SettingsDiff,
+}
+
+/// Watches the settings file (and the files it includes) and
+/// reloads the settings every time one of them is modified.
+///
+/// Every reload with changes is published to all subscribers as
+/// [`SettingsUpdate`]. Settings that cannot be loaded are logged
+/// and skipped until the files are modified again.
+///
+/// Cloning the watcher shares the same watcher. It stops watching
+/// once all of its clones are dropped.
+#[derive(Clone)]
+pub struct SettingsWatcher {
+    inner: Arc<WatcherInner>,
+}
+
+struct WatcherInner {
+    current: Mutex<Arc<Settings>>,
+    updates: broadcast::Sender<SettingsUpdate>,
+    reload: Notify,
+}
+
+impl Settings {
+    /// Starts watching the settings files which these settings are
+    /// loaded from. It must be called inside the Tokio runtime.
+    #[must_use]
+    pub fn watch(self: Arc<Self>) -> SettingsWatcher {
+        let files = watched_files(&self);
+        let (updates, _) = broadcast::channel(UPDATES_CAPACITY);
+        let inner = Arc::new(WatcherInner {
+            current: Mutex::new(self),
+            updates,
+            reload: Notify::new(),
+        });
+
+        tokio::spawn(poll(Arc::downgrade(&inner), files));
+        SettingsWatcher { inner }
+    }
+}
+
+impl SettingsWatcher {
+    /// Gets the latest settings loaded from the settings files.
+    #[must_use]
+    pub fn current(&self) -> Arc<Settings> {
+        self.inner.current()
+    }
+
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<SettingsUpdate> {
+        self.inner.updates.subscribe()
+    }
+
+    /// Reloads the settings right away even if none of the
+    /// settings files have been modified.
+    pub fn reload(&self) {
+        self.inner.reload.notify_one();
+    }
+}
+
+impl std::fmt::Debug for SettingsWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SettingsWatcher").finish_non_exhaustive()
+    }
+}
+
+async fn poll(inner: Weak<WatcherInner>, mut files: Vec<WatchedFile>) {
+    debug!("watching {} settings file(s)", files.len());
+    loop {
+        let Some(inner) = inner.upgrade() else {
+            break;
+        };
+
+        tokio::select! {
+            () = tokio::time::sleep(POLL_INTERVAL) => {
+                if !files.iter().any(WatchedFile::is_modified) {
+                    continue;
+                }
+                trace!("settings files have been modified");
+            }
+            () = inner.reload.notified() => {}
+        }
+
+        // remembering the files before loading the settings prevents
+        // invalid settings from being loaded over and over again
+        let current = inner.current();
+        files = watched_files(&current);
+        reload(&inner, current).await;
+    }
+    debug!("stopped watching settings files");
+}
+
+async fn reload(inner: &WatcherInner, previous: Arc<Settings>) {
+    info!("reloading settings");
+
+    // secrets may be requested from Vault which blocks the thread
+    let overrides = previous.overrides().clone();
+    let result = tokio::task::spawn_blocking(move || Settings::from_env_with(overrides)).await;
+    let settings = match result {
+        Ok(Ok(settings)) => settings,
+        Ok(Err(error)) => {
+            warn!(%error, "could not reload settings");
+            return;
+        }
+        Err(error) => {
+            warn!(%error, "settings reload has been aborted");
+            return;
+        }
+    };
+    settings.warn_deprecated();
+
+    let diff = previous.diff(&settings);
+    if diff.is_empty() {
+        info!("no settings have been changed");
+        return;
+    }
+
+    let current = Arc::new(settings);
+    inner.set_current(current.clone());
+
+    let update = SettingsUpdate {
+        previous,
+        current,
+        diff,
+    };
+
+    // nobody may be subscribed to the watcher yet
+    drop(inner.updates.send(update));
+}
+
+impl WatcherInner {
+    #[allow(clippy::unwrap_used)]
+    fn current(&self) -> Arc<Settings> {
+        self.current.lock().unwrap().clone()
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn set_current(&self, settings: Arc<Settings>) {
+        *self.current.lock().unwrap() = settings;
+    }
+}
+
+/// Settings file along with when it is modified the last time.
+#[derive(Debug)]
+struct WatchedFile {
+    path: PathBuf,
+    modified_at: Option<SystemTime>,
+}
+
+impl WatchedFile {
+    fn new(path: PathBuf) -> Self {
+        let modified_at = modified_at(&path);
+        Self { path, modified_at }
+    }
+
+    fn is_modified(&self) -> bool {
+        modified_at(&self.path) != self.modified_at
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|v| v.modified()).ok()
+}
+
+/// Gets every settings file to be watched, including the file of the
+/// selected environment. Only the settings file
+/// itself is watched if its included files cannot be resolved.
+fn watched_files(settings: &Settings) -> Vec<WatchedFile> {
+    let Some(path) = settings.path() else {
+        return Vec::new();
+    };
+
+    let files = Settings::format_hint()
+        .ok()
+        .and_then(|hint| Settings::resolve_files(path, settings.environment(), hint).ok())
+        .unwrap_or_else(|| vec![path.to_path_buf()]);
+
+    files.into_iter().map(WatchedFile::new).collect()
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn should_detect_modified_files() {
+        let path = std::env::temp_dir().join(format!("eden-watch-{}.toml", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+
+        let file = WatchedFile::new(path.clone());
+        assert!(!file.is_modified());
+
+        let modified_at = file.modified_at.unwrap() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified_at)
+            .unwrap();
+        assert!(file.is_modified());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(file.is_modified());
+    }
+}
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use typed_builder::TypedBuilder;
 use uuid::Uuid;
 
-use crate::types::{TaskPriority, TaskRawData, TaskStatus};
+use crate::types::{DedupPolicy, TaskPriority, TaskRawData, TaskStatus};
 
 #[derive(Debug, Clone, TypedBuilder)]
 pub struct InsertTaskForm {
@@ -18,6 +18,10 @@ pub struct InsertTaskForm {
     pub priority: TaskPriority,
     #[builder(default)]
     pub status: TaskStatus,
+    #[builder(default)]
+    pub dedup_key: Option<String>,
+    #[builder(default)]
+    pub dedup_policy: DedupPolicy,
 }
 
 #[derive(Debug, Clone, TypedBuilder)]
@@ -6,26 +6,59 @@ use uuid::Uuid;
 
 use crate::forms::{InsertTaskForm, UpdateTaskForm};
 use crate::paged_queries::{GetAllTasks, PullAllPendingTasks};
-use crate::types::{Task, TaskStatus, WorkerId};
+use crate::types::{DedupPolicy, Task, TaskStatus, WorkerId};
 
 impl Task {
-    pub async fn fail(conn: &mut sqlx::PgConnection, id: Uuid) -> Result<Self, QueryError> {
+    /// Postgres channel notified whenever a queued task that is
+    /// due within a second is inserted.
+    pub const NOTIFY_CHANNEL: &'static str = "queued_tasks";
+
+    /// Marks a task as failed. It returns `None` if the task does
+    /// not exist or has been cancelled.
+    pub async fn fail(conn: &mut sqlx::PgConnection, id: Uuid) -> Result<Option<Self>, QueryError> {
         sqlx::query_as::<_, Self>(
             r"UPDATE tasks
             SET status = $1,
                 attempts = attempts + 1
-            WHERE id = $2
+            WHERE id = $2 AND status <> $3
             RETURNING *",
         )
         .bind(TaskStatus::Failed)
         .bind(id)
-        .fetch_one(conn)
+        .bind(TaskStatus::Cancelled)
+        .fetch_optional(conn)
         .await
         .into_eden_error()
         .change_context(QueryError)
         .attach_printable("could not fail task from id")
     }
 
+    /// Cancels a queued or running task so it will never be pulled
+    /// again. It returns `None` if the task does not exist or has
+    /// already finished.
+    pub async fn cancel(
+        conn: &mut sqlx::PgConnection,
+        id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Self>, QueryError> {
+        sqlx::query_as::<_, Self>(
+            r"UPDATE tasks
+            SET status = $1, updated_at = $2
+            WHERE id = $3 AND status IN ($4, $5)
+            RETURNING *",
+        )
+        .bind(TaskStatus::Cancelled)
+        .bind(now)
+        .bind(id)
+        .bind(TaskStatus::Queued)
+        .bind(TaskStatus::Running)
+        .fetch_optional(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not cancel task from id")
+    }
+
     pub async fn from_id(
         conn: &mut sqlx::PgConnection,
         id: Uuid,
@@ -43,12 +76,16 @@ impl Task {
         GetAllTasks::new(worker_id)
     }
 
+    /// Pulls due queued tasks and claims them with `claim_id` so other
+    /// queue workers sharing the tasks table won't pull them as well.
     pub fn pull_all_pending(
         worker_id: WorkerId,
+        claim_id: Uuid,
         max_attempts: i32,
         now: Option<DateTime<Utc>>,
     ) -> PullAllPendingTasks {
         PullAllPendingTasks {
+            claim_id,
             limit: PullAllPendingTasks::DEFAULT_LIMIT,
             max_attempts,
             now: now.unwrap_or_else(Utc::now),
@@ -56,23 +93,25 @@ impl Task {
         }
     }
 
+    /// Requeues running tasks assigned to the worker that were retried
+    /// (or claimed if they're on their first attempt) at least
+    /// `threshold` ago.
     pub async fn requeue_stalled(
         conn: &mut sqlx::PgConnection,
         worker_id: WorkerId,
         threshold: TimeDelta,
         now: Option<DateTime<Utc>>,
     ) -> Result<u64, QueryError> {
+        let now = now.unwrap_or_else(Utc::now);
         sqlx::query(
             r"UPDATE tasks
-            SET status = $1, updated_at = $2
+            SET status = $1, updated_at = $2, claimed_by = NULL, claimed_at = NULL
             WHERE id IN (
                 SELECT id
                 FROM tasks
-                WHERE status = $3 AND current_timestamp >=
-                    TO_TIMESTAMP(EXTRACT(EPOCH FROM CASE WHEN last_retry IS NULL
-                        THEN current_timestamp
-                        ELSE last_retry
-                    END) + EXTRACT(EPOCH FROM $4))
+                WHERE status = $3 AND $2 >=
+                    TO_TIMESTAMP(EXTRACT(EPOCH FROM COALESCE(last_retry, claimed_at))
+                        + EXTRACT(EPOCH FROM $4))
                 AND get_worker_id_from_task(task_number, $6) = $5
                 FOR UPDATE SKIP LOCKED
             )",
@@ -90,6 +129,51 @@ impl Task {
         .attach_printable("could not requeue stalled tasks")
         .map(|v| v.rows_affected())
     }
+
+    /// Requeues running tasks assigned to the worker that are not claimed
+    /// by a live instance, which is an instance in the `instances` table
+    /// seen since `live_since`.
+    ///
+    /// It is meant to be called before the worker runs any task, since
+    /// these tasks are most likely left by a previous run of the worker
+    /// that crashed. Tasks claimed by live instances may be run by another
+    /// queue worker sharing the same worker ID, so they are left alone.
+    pub async fn requeue_orphaned(
+        conn: &mut sqlx::PgConnection,
+        worker_id: WorkerId,
+        live_since: DateTime<Utc>,
+        now: Option<DateTime<Utc>>,
+    ) -> Result<u64, QueryError> {
+        let now = now.unwrap_or_else(Utc::now);
+        sqlx::query(
+            r"UPDATE tasks
+            SET status = $1, updated_at = $2, claimed_by = NULL, claimed_at = NULL
+            WHERE id IN (
+                SELECT id
+                FROM tasks
+                WHERE status = $3
+                AND get_worker_id_from_task(task_number, $5) = $4
+                AND NOT EXISTS (
+                    SELECT 1 FROM instances
+                    WHERE instances.id = tasks.claimed_by
+                        AND instances.last_seen_at >= $6
+                )
+                FOR UPDATE SKIP LOCKED
+            )",
+        )
+        .bind(TaskStatus::Queued)
+        .bind(now)
+        .bind(TaskStatus::Running)
+        .bind(worker_id.assigned_sql())
+        .bind(worker_id.total_sql())
+        .bind(live_since.naive_utc())
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not requeue orphaned tasks")
+        .map(|v| v.rows_affected())
+    }
 }
 
 impl Task {
@@ -103,9 +187,19 @@ impl Task {
             .change_context(QueryError)
             .attach_printable("could not serialize task to insert task")?;
 
+        // tasks with the same type and deduplication key as a pending task
+        // return that task instead (the update is needed to return it)
+        let replace_deadline = form.dedup_policy == DedupPolicy::ReplaceDeadline;
         sqlx::query_as::<_, Task>(
-            r"INSERT INTO tasks (id, deadline, attempts, periodic, priority, status, data)
-            VALUES (COALESCE($1, gen_random_uuid()), $2, $3, $4, $5, $6, $7)
+            r"INSERT INTO tasks
+                (id, deadline, attempts, periodic, priority, status, data, dedup_key)
+            VALUES (COALESCE($1, gen_random_uuid()), $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT ((data ->> 'type'), dedup_key)
+                WHERE dedup_key IS NOT NULL AND status IN ('queued', 'running')
+                DO UPDATE SET deadline = CASE
+                    WHEN $9 AND tasks.status = 'queued' THEN EXCLUDED.deadline
+                    ELSE tasks.deadline
+                END
             RETURNING *",
         )
         .bind(form.id)
@@ -115,6 +209,8 @@ impl Task {
         .bind(form.priority)
         .bind(form.status)
         .bind(data)
+        .bind(form.dedup_key)
+        .bind(replace_deadline)
         .fetch_one(conn)
         .await
         .into_eden_error()
@@ -122,6 +218,9 @@ impl Task {
         .attach_printable("could not insert task")
     }
 
+    /// Updates a task from the form. It returns `None` if the task
+    /// does not exist or has been cancelled, so cancelled tasks
+    /// are never pulled again.
     pub async fn update(
         conn: &mut sqlx::PgConnection,
         id: Uuid,
@@ -147,7 +246,7 @@ impl Task {
                 status = COALESCE($5, status),
                 data = COALESCE($6, data),
                 updated_at = $7
-            WHERE id = $8
+            WHERE id = $8 AND status <> $9
             RETURNING *",
         )
         .bind(form.deadline)
@@ -160,6 +259,7 @@ impl Task {
         // bind this argument to update `updated_at` manually.
         .bind(Utc::now())
         .bind(id)
+        .bind(TaskStatus::Cancelled)
         .fetch_optional(conn)
         .await
         .into_eden_error()
@@ -171,7 +271,7 @@ impl Task {
         conn: &mut sqlx::PgConnection,
         id: Uuid,
     ) -> Result<Option<Self>, QueryError> {
-        sqlx::query_as::<_, Task>(r"DELETE FROM tasks WHERE id = $1")
+        sqlx::query_as::<_, Task>(r"DELETE FROM tasks WHERE id = $1 RETURNING *")
             .bind(id)
             .fetch_optional(conn)
             .await
@@ -217,6 +317,29 @@ impl Task {
             .attach_printable_lazy(|| format!("could not delete all tasks with type {task_type:?}"))
             .map(|v| v.rows_affected())
     }
+
+    /// Deletes tasks that have either succeeded, failed or cancelled
+    /// before `before` and returns how many were deleted.
+    pub async fn delete_finished_before(
+        conn: &mut sqlx::PgConnection,
+        before: DateTime<Utc>,
+    ) -> Result<u64, QueryError> {
+        sqlx::query(
+            r"DELETE FROM tasks
+            WHERE status IN ($1, $2, $3)
+                AND COALESCE(updated_at, created_at) < $4",
+        )
+        .bind(TaskStatus::Success)
+        .bind(TaskStatus::Failed)
+        .bind(TaskStatus::Cancelled)
+        .bind(before)
+        .execute(conn)
+        .await
+        .into_eden_error()
+        .change_context(QueryError)
+        .attach_printable("could not delete finished tasks")
+        .map(|v| v.rows_affected())
+    }
 }
 
 #[allow(clippy::unwrap_used, clippy::unreadable_literal)]
@@ -233,6 +356,13 @@ mod tests {
     async fn test_requeue_stalled(pool: sqlx::PgPool) -> eden_utils::Result<()> {
         let mut conn = pool.acquire().await.anonymize_error_into()?;
 
+        // first attempts have no `last_retry`
+        let claimed = test_utils::generate_task(&mut conn).await?;
+        Task::pull_all_pending(WorkerId::ONE, Uuid::new_v4(), 3, None)
+            .build()
+            .next(&mut conn)
+            .await?;
+
         let now = Utc::now();
         let threshold = TimeDelta::seconds(5);
 
@@ -256,6 +386,89 @@ mod tests {
         let total = Task::requeue_stalled(&mut conn, WorkerId::ONE, threshold, Some(now)).await?;
         assert_eq!(total, 1);
 
+        let later = now + threshold + TimeDelta::seconds(1);
+        let total = Task::requeue_stalled(&mut conn, WorkerId::ONE, threshold, Some(later)).await?;
+        assert_eq!(total, 1);
+
+        let claimed = Task::from_id(&mut conn, claimed.id).await?.unwrap();
+        assert_eq!(claimed.status, TaskStatus::Queued);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_requeue_orphaned(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let now = Utc::now();
+
+        // claimed just now by a previous run that crashed
+        let orphaned = test_utils::generate_task(&mut conn).await?;
+        Task::pull_all_pending(WorkerId::ONE, Uuid::new_v4(), 3, None)
+            .build()
+            .next(&mut conn)
+            .await?;
+
+        // claimed just now by another instance that is still alive
+        let live_id = Uuid::new_v4();
+        sqlx::query(r"INSERT INTO instances(id, name, last_seen_at) VALUES ($1, $2, $3)")
+            .bind(live_id)
+            .bind("eden-2")
+            .bind(now.naive_utc())
+            .execute(&mut *conn)
+            .await
+            .anonymize_error_into()?;
+
+        let claimed = test_utils::generate_task(&mut conn).await?;
+        Task::pull_all_pending(WorkerId::ONE, live_id, 3, None)
+            .build()
+            .next(&mut conn)
+            .await?;
+
+        let running = test_utils::generate_task(&mut conn).await?;
+        let form = UpdateTaskForm::builder()
+            .status(Some(TaskStatus::Running))
+            .last_retry(Some(Utc::now()))
+            .build();
+        Task::update(&mut conn, running.id, form).await?;
+
+        let queued = test_utils::generate_task(&mut conn).await?;
+
+        let live_since = now - TimeDelta::seconds(30);
+        let total = Task::requeue_orphaned(&mut conn, WorkerId::ONE, live_since, None).await?;
+        assert_eq!(total, 2);
+
+        let orphaned = Task::from_id(&mut conn, orphaned.id).await?.unwrap();
+        assert_eq!(orphaned.status, TaskStatus::Queued);
+
+        let running = Task::from_id(&mut conn, running.id).await?.unwrap();
+        assert_eq!(running.status, TaskStatus::Queued);
+
+        let claimed = Task::from_id(&mut conn, claimed.id).await?.unwrap();
+        assert_eq!(claimed.status, TaskStatus::Running);
+
+        let queued = Task::from_id(&mut conn, queued.id).await?.unwrap();
+        assert_eq!(queued.status, TaskStatus::Queued);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_notify_queued(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut listener = sqlx::postgres::PgListener::connect_with(&pool)
+            .await
+            .anonymize_error_into()?;
+
+        listener
+            .listen(Task::NOTIFY_CHANNEL)
+            .await
+            .anonymize_error_into()?;
+
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let task = test_utils::generate_task(&mut conn).await?;
+
+        let notification = listener.recv().await.anonymize_error_into()?;
+        assert_eq!(notification.payload(), task.id.to_string());
+
         Ok(())
     }
 
@@ -338,6 +551,44 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_insert_dedup(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+
+        let now = Utc::now();
+        let data = TaskRawData {
+            kind: "foo".into(),
+            inner: serde_json::Value::Null,
+        };
+        let form = |deadline, policy| {
+            InsertTaskForm::builder()
+                .deadline(deadline)
+                .data(data.clone())
+                .dedup_key(Some("bar".into()))
+                .dedup_policy(policy)
+                .build()
+        };
+
+        let task = Task::insert(&mut conn, form(now, DedupPolicy::Ignore)).await?;
+        let later = now + TimeDelta::minutes(5);
+
+        // it should collapse into the pending task
+        let ignored = Task::insert(&mut conn, form(later, DedupPolicy::Ignore)).await?;
+        assert_eq!(ignored.id, task.id);
+        assert_eq!(ignored.deadline.timestamp(), now.timestamp());
+
+        let replaced = Task::insert(&mut conn, form(later, DedupPolicy::ReplaceDeadline)).await?;
+        assert_eq!(replaced.id, task.id);
+        assert_eq!(replaced.deadline.timestamp(), later.timestamp());
+
+        // finished tasks no longer hold the key
+        Task::fail(&mut conn, task.id).await?;
+        let new_task = Task::insert(&mut conn, form(now, DedupPolicy::Ignore)).await?;
+        assert_ne!(new_task.id, task.id);
+
+        Ok(())
+    }
+
     #[sqlx::test(migrator = "crate::MIGRATOR")]
     async fn test_update(pool: sqlx::PgPool) -> eden_utils::Result<()> {
         let mut conn = pool.acquire().await.anonymize_error_into()?;
@@ -367,6 +618,40 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_cancel(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let task = test_utils::generate_task(&mut conn).await?;
+
+        let now = Utc::now();
+        let cancelled = Task::cancel(&mut conn, task.id, now)
+            .await
+            .anonymize_error()?
+            .unwrap();
+        assert_eq!(cancelled.status, TaskStatus::Cancelled);
+        assert!(cancelled.updated_at.is_some());
+
+        // finished tasks cannot be cancelled
+        let result = Task::cancel(&mut conn, task.id, now).await;
+        assert!(result.anonymize_error()?.is_none());
+
+        // cancelled tasks cannot be updated or failed
+        let form = UpdateTaskForm::builder()
+            .status(Some(TaskStatus::Queued))
+            .build();
+
+        let result = Task::update(&mut conn, task.id, form).await;
+        assert!(result.anonymize_error()?.is_none());
+
+        let result = Task::fail(&mut conn, task.id).await;
+        assert!(result.anonymize_error()?.is_none());
+
+        let result = Task::cancel(&mut conn, Uuid::new_v4(), now).await;
+        assert!(result.anonymize_error()?.is_none());
+
+        Ok(())
+    }
+
     #[sqlx::test(migrator = "crate::MIGRATOR")]
     async fn test_delete(pool: sqlx::PgPool) -> eden_utils::Result<()> {
         let mut conn = pool.acquire().await.anonymize_error_into()?;
@@ -386,4 +671,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_delete_finished_before(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let queued = test_utils::generate_task(&mut conn).await?;
+        let finished = test_utils::generate_task(&mut conn).await?;
+        Task::fail(&mut conn, finished.id).await.anonymize_error()?;
+
+        let cancelled = test_utils::generate_task(&mut conn).await?;
+        Task::cancel(&mut conn, cancelled.id, Utc::now())
+            .await
+            .anonymize_error()?;
+
+        let before = Utc::now() + TimeDelta::minutes(1);
+        let deleted = Task::delete_finished_before(&mut conn, before)
+            .await
+            .anonymize_error()?;
+        assert_eq!(deleted, 2);
+
+        assert!(Task::from_id(&mut conn, queued.id)
+            .await
+            .anonymize_error()?
+            .is_some());
+
+        Ok(())
+    }
 }
@@ -6,6 +6,7 @@ use crate::types::{Task, TaskStatus, WorkerId};
 
 #[must_use]
 pub struct GetAllTasks<'a> {
+    locked: bool,
     periodic: Option<bool>,
     status: Option<TaskStatus>,
     task_type: Option<&'a str>,
@@ -16,6 +17,7 @@ impl<'a> GetAllTasks<'a> {
     #[allow(clippy::new_without_default)]
     pub fn new(worker_id: WorkerId) -> Self {
         Self {
+            locked: true,
             periodic: None,
             status: None,
             task_type: None,
@@ -23,6 +25,14 @@ impl<'a> GetAllTasks<'a> {
         }
     }
 
+    /// Lists the tasks without locking them, so tasks being run by
+    /// queue workers are listed too and the query can be used in
+    /// read-only connections.
+    pub fn read_only(mut self) -> Self {
+        self.locked = false;
+        self
+    }
+
     pub fn periodic(mut self, periodic: bool) -> Self {
         self.periodic = Some(periodic);
         self
@@ -105,7 +115,10 @@ impl<'a> PageQueyer for GetAllTasks<'a> {
             f,
             r#"get_worker_id_from_task(task_number, ${total_workers_count}) = ${worker_id_count} "#
         )?;
-        f.write_str("FOR UPDATE SKIP LOCKED")
+        if self.locked {
+            f.write_str("FOR UPDATE SKIP LOCKED")?;
+        }
+        Ok(())
     }
 }
 
@@ -147,6 +160,17 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_read_only(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        test_utils::prepare_sample_tasks(&mut conn).await?;
+
+        let mut stream = Paginated::new(GetAllTasks::new(WorkerId::ONE).read_only()).size(3);
+        assert!(stream.next(&mut conn).await?.is_some());
+
+        Ok(())
+    }
+
     #[sqlx::test(migrator = "crate::MIGRATOR")]
     async fn test_pagination(pool: sqlx::PgPool) -> eden_utils::Result<()> {
         let mut conn = pool.acquire().await.anonymize_error_into()?;
@@ -4,11 +4,17 @@ use eden_utils::sql::{PageQueyer, Paginated, QueryError};
 use eden_utils::Result;
 use sqlx::postgres::PgArguments;
 use sqlx::Arguments;
+use uuid::Uuid;
 
 use crate::types::{Task, TaskStatus, WorkerId};
 
+/// Pulls queued tasks that are due and claims them for a queue worker.
+///
+/// Tasks are claimed with `FOR UPDATE SKIP LOCKED` so queue workers
+/// sharing the same tasks table never pull the same task twice.
 #[must_use]
 pub struct PullAllPendingTasks {
+    pub(crate) claim_id: Uuid,
     // how many tasks we can limit per query
     pub(crate) limit: u64,
     pub(crate) max_attempts: i32,
@@ -43,13 +49,15 @@ impl PageQueyer for PullAllPendingTasks {
         args.add(TaskStatus::Queued);
         args.add(self.worker_id.total_sql());
         args.add(self.worker_id.assigned_sql());
+        args.add(self.claim_id);
         args
     }
 
     fn build_sql(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "SELECT * FROM tasks ")?;
         write!(f, "WHERE status = $1 AND attempts < $2 ")?;
-        write!(f, "AND deadline <= $3 AND updated_at = $3 ")?;
+        write!(f, "AND deadline <= $3 ")?;
+        write!(f, "AND claimed_by = $7 AND claimed_at = $3 ")?;
         write!(f, "AND get_worker_id_from_task(task_number, $5) = $6 ")?;
         write!(
             f,
@@ -59,9 +67,10 @@ impl PageQueyer for PullAllPendingTasks {
     }
 
     async fn prerun(&self, conn: &mut sqlx::PgConnection) -> Result<(), QueryError> {
-        // this is to better differentiate which tasks are updated now
+        // tasks locked by other queue workers are being claimed by them
         sqlx::query(
             r"UPDATE tasks SET status = $1, updated_at = $3,
+                claimed_by = $8, claimed_at = $3,
                 last_retry = CASE WHEN attempts > 0
                     THEN $3
                     ELSE last_retry
@@ -73,6 +82,7 @@ impl PageQueyer for PullAllPendingTasks {
                     AND status = $4
                     AND get_worker_id_from_task(task_number, $5) = $6
                 LIMIT $7
+                FOR UPDATE SKIP LOCKED
             )",
         )
         .bind(TaskStatus::Running)
@@ -82,6 +92,7 @@ impl PageQueyer for PullAllPendingTasks {
         .bind(self.worker_id.total_sql())
         .bind(self.worker_id.assigned_sql())
         .bind((self.limit as i64).abs())
+        .bind(self.claim_id)
         .execute(conn)
         .await
         .into_eden_error()
@@ -108,7 +119,7 @@ mod tests {
         let later = Utc::now() + TimeDelta::seconds(200);
         test_utils::prepare_sample_tasks(&mut conn).await?;
 
-        let mut stream = Task::pull_all_pending(WorkerId::ONE, 3, Some(later))
+        let mut stream = Task::pull_all_pending(WorkerId::ONE, Uuid::new_v4(), 3, Some(later))
             .build()
             .size(3);
 
@@ -135,4 +146,36 @@ mod tests {
         assert!(!deadline_order_test.is_empty());
         Ok(())
     }
+
+    #[sqlx::test(migrator = "crate::MIGRATOR")]
+    async fn test_claims(pool: sqlx::PgPool) -> eden_utils::Result<()> {
+        let mut conn = pool.acquire().await.anonymize_error_into()?;
+        let later = Utc::now() + TimeDelta::seconds(200);
+        test_utils::prepare_sample_tasks(&mut conn).await?;
+
+        // two queue workers sharing the same worker ID
+        let first_id = Uuid::new_v4();
+        let second_id = Uuid::new_v4();
+
+        let first = Task::pull_all_pending(WorkerId::ONE, first_id, 3, Some(later))
+            .limit(4)
+            .build()
+            .next(&mut conn)
+            .await
+            .anonymize_error()?
+            .unwrap();
+
+        let second = Task::pull_all_pending(WorkerId::ONE, second_id, 3, Some(later))
+            .build()
+            .next(&mut conn)
+            .await
+            .anonymize_error()?
+            .unwrap();
+
+        assert_eq!(first.len(), 4);
+        assert!(first.iter().all(|v| v.claimed_by == Some(first_id)));
+        assert!(second.iter().all(|v| v.claimed_by == Some(second_id)));
+        assert!(first.iter().all(|a| second.iter().all(|b| a.id != b.id)));
+        Ok(())
+    }
 }
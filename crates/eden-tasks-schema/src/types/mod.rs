@@ -20,6 +20,12 @@ pub struct Task {
     pub periodic: bool,
     pub priority: TaskPriority,
     pub status: TaskStatus,
+    /// Claim ID of the queue worker that last pulled the task.
+    pub claimed_by: Option<Uuid>,
+    pub claimed_at: Option<DateTime<Utc>>,
+    /// Pending tasks of the same type cannot share the same
+    /// deduplication key.
+    pub dedup_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -42,6 +48,9 @@ impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for Task {
         let periodic = row.try_get("periodic")?;
         let priority = row.try_get("priority")?;
         let status = row.try_get("status")?;
+        let claimed_by = row.try_get("claimed_by")?;
+        let claimed_at = row.try_get::<Option<NaiveDateTime>, _>("claimed_at")?;
+        let dedup_key = row.try_get("dedup_key")?;
 
         Ok(Self {
             id,
@@ -54,6 +63,9 @@ impl<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow> for Task {
             periodic,
             priority,
             status,
+            claimed_by,
+            claimed_at: claimed_at.map(naive_to_dt),
+            dedup_key,
         })
     }
 }
@@ -76,6 +88,20 @@ pub enum TaskStatus {
     Success,
     #[default]
     Queued,
+    /// The task is cancelled before it has finished.
+    Cancelled,
+}
+
+/// What happens to the pending task if a task with the same type
+/// and deduplication key is scheduled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DedupPolicy {
+    /// Keeps the pending task as is.
+    #[default]
+    Ignore,
+    /// Moves the deadline of the pending task to the deadline
+    /// of the scheduled task if it has not started yet.
+    ReplaceDeadline,
 }
 
 #[cfg(test)]
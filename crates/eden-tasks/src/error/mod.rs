@@ -2,6 +2,10 @@ use thiserror::Error;
 
 pub mod tags;
 
+#[derive(Debug, Error)]
+#[error("could not create queue worker")]
+pub struct CreateWorkerError;
+
 #[derive(Debug, Error)]
 #[error("could not start queue worker")]
 pub struct WorkerStartError;
@@ -22,6 +26,14 @@ pub(crate) struct PerformTaskError;
 #[error("could not delete task")]
 pub(crate) struct DeleteTaskError;
 
+#[derive(Debug, Error)]
+#[error("could not requeue task")]
+pub(crate) struct RequeueTaskError;
+
+#[derive(Debug, Error)]
+#[error("could not cancel task")]
+pub(crate) struct CancelTaskError;
+
 #[derive(Debug, Error)]
 #[error("could not clear all task(s)")]
 pub(crate) struct ClearAllTasksError;
@@ -30,6 +42,10 @@ pub(crate) struct ClearAllTasksError;
 #[error("could not clear temporary task(s)")]
 pub(crate) struct ClearTemporaryTasksError;
 
+#[derive(Debug, Error)]
+#[error("could not requeue orphaned task(s)")]
+pub(crate) struct RequeueOrphanedTasksError;
+
 #[derive(Debug, Error)]
 #[error("could not update recurring task blacklist")]
 pub(crate) struct UpdateTaskBlacklistError;
@@ -0,0 +1,180 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Tasks are considered slowing down if their average latency
+/// is this many times longer than the baseline latency.
+const SLOWDOWN_FACTOR: u64 = 2;
+
+/// A change of how many tasks a worker can run at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ConcurrencyChange {
+    pub from: usize,
+    pub to: usize,
+    pub reason: &'static str,
+}
+
+/// Adjusts how many tasks a worker can run at the same time (within
+/// the configured bounds) based on how many tasks are waiting to run
+/// and how long tasks take to complete.
+#[derive(Debug)]
+pub(crate) struct ConcurrencyTuner {
+    min: usize,
+    max: usize,
+    current: AtomicUsize,
+    adjustments: AtomicU64,
+
+    // both are in microseconds
+    latency: AtomicU64,
+    baseline: AtomicU64,
+}
+
+impl ConcurrencyTuner {
+    pub fn new(min: usize, max: usize) -> Self {
+        Self {
+            min: min.min(max),
+            max,
+            current: AtomicUsize::new(max),
+            adjustments: AtomicU64::new(0),
+            latency: AtomicU64::new(0),
+            baseline: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.min < self.max
+    }
+
+    pub fn current(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    pub fn adjustments(&self) -> u64 {
+        self.adjustments.load(Ordering::Relaxed)
+    }
+
+    /// Average time it took for tasks to complete recently.
+    pub fn average_latency(&self) -> Duration {
+        Duration::from_micros(self.latency.load(Ordering::Relaxed))
+    }
+
+    // the update function never fails
+    #[allow(clippy::let_underscore_must_use)]
+    pub fn record_latency(&self, elapsed: Duration) {
+        let elapsed = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        let update = |average: u64| {
+            // exponential moving average so a single slow task
+            // won't affect the concurrency by itself.
+            Some(if average == 0 {
+                elapsed
+            } else {
+                average - average / 8 + elapsed / 8
+            })
+        };
+        let _ = self
+            .latency
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, update);
+    }
+
+    /// Adjusts the concurrency if needed. `waiting` is the amount of
+    /// tasks that are waiting for other tasks to complete.
+    pub fn tune(&self, waiting: usize, running: usize) -> Option<ConcurrencyChange> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let latency = self.latency.load(Ordering::Relaxed);
+        let baseline = self.update_baseline(latency);
+
+        let from = self.current();
+        let (to, reason) = next_concurrency(
+            from,
+            (self.min, self.max),
+            waiting,
+            running,
+            latency > baseline.saturating_mul(SLOWDOWN_FACTOR),
+        );
+        if from == to {
+            return None;
+        }
+
+        self.current.store(to, Ordering::Relaxed);
+        self.adjustments.fetch_add(1, Ordering::Relaxed);
+        Some(ConcurrencyChange { from, to, reason })
+    }
+
+    /// The baseline follows the lowest latency seen but slowly catches up
+    /// with the current latency, so a burst of fast tasks won't make other
+    /// tasks look slow forever.
+    fn update_baseline(&self, latency: u64) -> u64 {
+        let baseline = self.baseline.load(Ordering::Relaxed);
+        let baseline = if baseline == 0 || latency < baseline {
+            latency
+        } else {
+            baseline + (latency - baseline) / 16
+        };
+        self.baseline.store(baseline, Ordering::Relaxed);
+        baseline
+    }
+}
+
+fn next_concurrency(
+    current: usize,
+    (min, max): (usize, usize),
+    waiting: usize,
+    running: usize,
+    is_slowing_down: bool,
+) -> (usize, &'static str) {
+    let step = (current / 4).max(1);
+
+    if is_slowing_down {
+        let next = current.saturating_sub(step).max(min);
+        (next, "tasks are taking longer to complete")
+    } else if waiting > 0 {
+        ((current + step).min(max), "tasks are waiting to run")
+    } else if running < current / 2 {
+        (
+            current.saturating_sub(1).max(min),
+            "worker is underutilized",
+        )
+    } else {
+        (current, "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_scale_within_bounds() {
+        assert_eq!(next_concurrency(8, (2, 10), 5, 8, false).0, 10);
+        assert_eq!(next_concurrency(10, (2, 10), 5, 10, false).0, 10);
+        assert_eq!(next_concurrency(8, (2, 10), 5, 8, true).0, 6);
+        assert_eq!(next_concurrency(2, (2, 10), 5, 2, true).0, 2);
+        assert_eq!(next_concurrency(8, (2, 10), 0, 1, false).0, 7);
+        assert_eq!(next_concurrency(8, (2, 10), 0, 6, false).0, 8);
+    }
+
+    #[test]
+    fn should_not_tune_without_bounds() {
+        let tuner = ConcurrencyTuner::new(10, 10);
+        assert!(!tuner.is_enabled());
+        assert_eq!(tuner.tune(100, 10), None);
+        assert_eq!(tuner.current(), 10);
+    }
+
+    #[test]
+    fn should_scale_down_when_tasks_slow_down() {
+        let tuner = ConcurrencyTuner::new(2, 10);
+        tuner.record_latency(Duration::from_millis(10));
+        assert_eq!(tuner.tune(0, 10), None);
+
+        for _ in 0..20 {
+            tuner.record_latency(Duration::from_secs(1));
+        }
+        let change = tuner.tune(5, 10);
+        assert_eq!(change.map(|v| (v.from, v.to)), Some((10, 8)));
+        assert_eq!(tuner.current(), 8);
+        assert_eq!(tuner.adjustments(), 1);
+    }
+}
@@ -1 +1,138 @@
+use chrono::TimeDelta;
+use eden_utils::error::exts::ResultExt;
+use eden_utils::error::tags::Suggestion;
+use eden_utils::types::ProtectedString;
+use eden_utils::{Error, ErrorCategory, Result};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{broadcast, Mutex};
+use uuid::Uuid;
 
+use super::autotune::ConcurrencyTuner;
+use super::events::TaskErrorHistory;
+use super::health::TaskHealthTracker;
+use super::inner::QueueWorkerInner;
+use super::task_manager::QueueWorkerTaskManager;
+use super::{QueueWorker, WorkerId, TASK_EVENTS_CAPACITY};
+use crate::error::CreateWorkerError;
+use crate::registry::{PayloadCipher, TaskRegistry};
+use crate::settings::Settings;
+
+/// A [`QueueWorker`] whose settings have been checked but has
+/// no state yet.
+///
+/// It is useful if the state has to be created alongside the
+/// worker, like a reference to the struct that holds the worker.
+pub struct QueueWorkerBuilder {
+    id: WorkerId,
+    claim_id: Uuid,
+    claim_lease: TimeDelta,
+    cipher: PayloadCipher,
+    pool: sqlx::PgPool,
+    health: TaskHealthTracker,
+    task_manager: QueueWorkerTaskManager,
+    tuner: ConcurrencyTuner,
+
+    max_attempts: u16,
+    max_running_tasks: usize,
+    queued_tasks_per_batch: u64,
+    stalled_tasks_threshold: TimeDelta,
+}
+
+impl QueueWorkerBuilder {
+    pub fn new(
+        id: WorkerId,
+        pool: sqlx::PgPool,
+        settings: &Settings,
+    ) -> Result<Self, CreateWorkerError> {
+        let cipher = PayloadCipher::new(settings.payload_key.as_ref().map(ProtectedString::expose))
+            .map_err(|_| Error::context(ErrorCategory::Unknown, CreateWorkerError))
+            .attach_printable("invalid `worker.payload_key`")
+            .attach(Suggestion::new(
+                "`worker.payload_key` must be 32 bytes encoded in base64",
+            ))?;
+
+        Ok(Self {
+            id,
+            claim_id: Uuid::new_v4(),
+            claim_lease: TimeDelta::zero(),
+            cipher,
+            pool,
+            health: TaskHealthTracker::new(settings.failure_streak_threshold.get()),
+            task_manager: QueueWorkerTaskManager::new(settings.max_running_tasks.get(), id),
+            tuner: ConcurrencyTuner::new(
+                settings
+                    .min_running_tasks
+                    .unwrap_or(settings.max_running_tasks)
+                    .get(),
+                settings.max_running_tasks.get(),
+            ),
+
+            max_attempts: settings.max_task_retries,
+            max_running_tasks: settings.max_running_tasks.get(),
+            queued_tasks_per_batch: settings.queued_tasks_per_batch.get(),
+            stalled_tasks_threshold: settings.stalled_tasks_threshold.to_time_delta(),
+        })
+    }
+
+    /// Claims pulled tasks with the ID of the instance running the
+    /// queue worker instead of a random ID.
+    ///
+    /// Tasks claimed by an instance which has not been seen in the
+    /// `instances` table within `lease` are requeued once a queue worker
+    /// with the same worker ID starts, even if they're claimed recently.
+    #[must_use]
+    pub fn instance(mut self, id: Uuid, lease: TimeDelta) -> Self {
+        self.claim_id = id;
+        self.claim_lease = lease;
+        self
+    }
+
+    #[must_use]
+    pub fn build<S: Clone + Send + Sync + 'static>(self, state: S) -> QueueWorker<S> {
+        QueueWorker(Arc::new(QueueWorkerInner {
+            id: self.id,
+            claim_id: self.claim_id,
+            claim_lease: self.claim_lease,
+            registry: Arc::new(TaskRegistry::with_cipher(self.cipher)),
+            runs_recurring_tasks: AtomicBool::new(true),
+            paused: AtomicBool::new(false),
+
+            errors: TaskErrorHistory::new(),
+            events: broadcast::channel(TASK_EVENTS_CAPACITY).0,
+            health: self.health,
+            health_listener: OnceLock::new(),
+            pool: self.pool,
+            #[cfg(feature = "testkit")]
+            recorder: OnceLock::new(),
+            runner_handle: Mutex::new(None),
+            state,
+            task_manager: self.task_manager,
+            tuner: self.tuner,
+
+            max_attempts: self.max_attempts,
+            max_running_tasks: self.max_running_tasks,
+            queued_tasks_per_batch: self.queued_tasks_per_batch,
+            stalled_tasks_threshold: self.stalled_tasks_threshold,
+        }))
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_reject_invalid_payload_key() {
+        let pool = sqlx::PgPool::connect_lazy("postgres://test").unwrap();
+
+        let settings = Settings::builder()
+            .payload_key(ProtectedString::new("not base64"))
+            .build();
+        assert!(QueueWorkerBuilder::new(WorkerId::ONE, pool.clone(), &settings).is_err());
+
+        let settings = Settings::builder().build();
+        assert!(QueueWorkerBuilder::new(WorkerId::ONE, pool, &settings).is_ok());
+    }
+}
@@ -7,7 +7,7 @@ use sqlx::{pool::PoolConnection, Transaction};
 use tracing::{debug, info, trace, warn};
 use uuid::Uuid;
 
-use super::QueueWorker;
+use super::{QueueWorker, TaskEvent};
 use crate::{error::*, Scheduled};
 
 impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
@@ -107,11 +107,120 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
         if let Some(task) = task.as_ref() {
             let registry = &self.0.registry;
             registry.unblock_for_recurring_task(&task.data.kind).await;
+
+            self.0.errors.clear(id);
+            self.publish_event(TaskEvent::Deleted {
+                id,
+                kind: task.data.kind.clone(),
+            });
         }
 
         Ok(task.is_some())
     }
 
+    /// Attempts to requeue a task from the database using the specified
+    /// task id so it will be performed as soon as possible, regardless
+    /// of how many attempts it made before.
+    ///
+    /// Running or cancelled tasks cannot be requeued. It returns a boolean
+    /// whether the specified task exists and is neither running nor cancelled.
+    #[allow(private_interfaces)]
+    #[tracing::instrument(skip_all, fields(worker.id = %self.0.id))]
+    pub async fn requeue_task(&self, id: Uuid) -> Result<bool, RequeueTaskError> {
+        info!("requeueing task {id}");
+
+        let mut conn = self
+            .db_transaction()
+            .await
+            .change_context(RequeueTaskError)
+            .attach_printable_lazy(|| format!("with id: {id}"))?;
+
+        let task = Task::from_id(&mut conn, id)
+            .await
+            .change_context(RequeueTaskError)
+            .attach_printable_lazy(|| format!("with id: {id}"))?;
+
+        let Some(task) = task.filter(|v| v.status != TaskStatus::Running) else {
+            return Ok(false);
+        };
+
+        let form = UpdateTaskForm::builder()
+            .attempts(Some(0))
+            .deadline(Some(Utc::now()))
+            .status(Some(TaskStatus::Queued))
+            .build();
+
+        let updated = Task::update(&mut conn, id, form)
+            .await
+            .change_context(RequeueTaskError)
+            .attach_printable_lazy(|| format!("with id: {id}"))?;
+
+        if updated.is_none() {
+            return Ok(false);
+        }
+
+        conn.commit()
+            .await
+            .into_eden_error()
+            .change_context(RequeueTaskError)
+            .attach_printable("could not commit database transaction")?;
+
+        self.publish_event(TaskEvent::Requeued {
+            id,
+            kind: task.data.kind,
+        });
+
+        Ok(true)
+    }
+
+    /// Attempts to cancel a queued or running task from the database
+    /// using the specified task id.
+    ///
+    /// Unlike [deleting it](QueueWorker::delete_queued_task), the task is
+    /// kept with [`TaskStatus::Cancelled`] status and it is never pulled
+    /// again. The task is aborted if it is currently running in this
+    /// queue worker.
+    ///
+    /// It returns a boolean whether the specified task exists and has
+    /// not finished yet.
+    #[allow(private_interfaces)]
+    #[tracing::instrument(skip_all, fields(worker.id = %self.0.id))]
+    pub async fn cancel(&self, id: Uuid) -> Result<bool, CancelTaskError> {
+        info!("cancelling task {id}");
+
+        let mut conn = self
+            .db_connection()
+            .await
+            .change_context(CancelTaskError)
+            .attach_printable_lazy(|| format!("with id: {id}"))?;
+
+        let task = Task::cancel(&mut conn, id, Utc::now())
+            .await
+            .change_context(CancelTaskError)
+            .attach_printable_lazy(|| format!("with id: {id}"))?;
+
+        let Some(task) = task else {
+            return Ok(false);
+        };
+
+        if self.0.task_manager.cancel(id) {
+            debug!("aborted running task {id}");
+        }
+
+        if task.periodic {
+            let registry = &self.0.registry;
+            registry.unblock_for_recurring_task(&task.data.kind).await;
+        }
+
+        self.0.errors.clear(id);
+        self.publish_event(TaskEvent::Cancelled {
+            id,
+            kind: task.data.kind,
+        });
+
+        Ok(true)
+    }
+
     pub(crate) async fn clear_temporary_tasks(&self) -> Result<(), ClearTemporaryTasksError> {
         debug!("clearing temporary tasks");
 
@@ -162,12 +271,43 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
         Ok(())
     }
 
+    /// Requeues running tasks left by a previous run of this worker
+    /// that crashed, instead of waiting for them to be requeued once
+    /// they are considered stalled.
+    ///
+    /// Tasks claimed by live instances are left alone since another
+    /// process with the same worker ID may be running them. Every other
+    /// running task is requeued right away, even if it was claimed
+    /// just before the previous run crashed.
+    pub(crate) async fn requeue_orphaned_tasks(&self) -> Result<(), RequeueOrphanedTasksError> {
+        let mut conn = self
+            .db_connection()
+            .await
+            .change_context(RequeueOrphanedTasksError)?;
+
+        let live_since = Utc::now() - self.0.claim_lease;
+        let amount = Task::requeue_orphaned(&mut conn, self.id(), live_since, None)
+            .await
+            .change_context(RequeueOrphanedTasksError)?;
+
+        if amount > 0 {
+            warn!("requeued {amount} task(s) left running by a previous run of this worker");
+        } else {
+            debug!("no orphaned tasks to requeue");
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all, level = "debug")]
     pub(crate) async fn setup(&self) -> Result<(), WorkerStartError> {
         self.clear_temporary_tasks()
             .await
             .change_context(WorkerStartError)?;
 
+        self.requeue_orphaned_tasks()
+            .await
+            .change_context(WorkerStartError)?;
+
         self.update_recurring_tasks_blacklist()
             .await
             .change_context(WorkerStartError)
@@ -219,6 +359,7 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
         &self,
         id: Option<Uuid>,
         raw_data: TaskRawData,
+        dedup_key: Option<String>,
         scheduled: Scheduled,
         now: Option<DateTime<Utc>>,
         attempts: u16,
@@ -243,6 +384,17 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
                 .await;
         }
 
+        let raw_data = TaskRawData {
+            inner: self
+                .0
+                .registry
+                .seal_payload(&raw_data.kind, raw_data.inner)
+                .into_typed_error()
+                .change_context(ScheduleTaskError)
+                .attach_printable("could not encrypt task data")?,
+            kind: raw_data.kind,
+        };
+
         // not much data is lost when converted from u16 to i32
         let attempts = attempts as i32;
         let form = InsertTaskForm::builder()
@@ -250,6 +402,8 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
             .attempts(attempts)
             .data(raw_data)
             .deadline(deadline)
+            .dedup_key(dedup_key)
+            .dedup_policy(registry_item.dedup_policy)
             .periodic(registry_item.is_recurring)
             .priority(priority)
             .build();
@@ -276,6 +430,17 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
         scheduled: Scheduled,
         attempts: u16,
     ) -> Result<(), ScheduleTaskError> {
+        let raw_data = TaskRawData {
+            inner: self
+                .0
+                .registry
+                .seal_payload(&raw_data.kind, raw_data.inner)
+                .into_typed_error()
+                .change_context(ScheduleTaskError)
+                .attach_printable("could not encrypt task data")?,
+            kind: raw_data.kind,
+        };
+
         // not much data is lost when converted from u16 to i32
         let attempts = attempts as i32;
         let deadline = scheduled.timestamp(now);
@@ -290,10 +455,15 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
             .await
             .change_context(ScheduleTaskError)?;
 
-        Task::update(&mut conn, id, form)
+        // cancelled tasks are never requeued
+        let task = Task::update(&mut conn, id, form)
             .await
             .change_context(ScheduleTaskError)?;
 
+        if task.is_none() {
+            debug!("task {id:?} has been cancelled while running");
+        }
+
         Ok(())
     }
 }
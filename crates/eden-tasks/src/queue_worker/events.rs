@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+/// Maximum amount of errors kept per task. Older errors
+/// will be discarded once it reaches the limit.
+const MAX_ERRORS_PER_TASK: usize = 10;
+
+/// Things that happened to a task in a worker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TaskEvent {
+    /// The task has been performed successfully.
+    Completed { id: Uuid, kind: String },
+    /// The task has been cancelled before it has finished.
+    Cancelled { id: Uuid, kind: String },
+    /// The task has been deleted from the queue.
+    Deleted { id: Uuid, kind: String },
+    /// The task got an error or timed out and will be retried later.
+    Failed {
+        id: Uuid,
+        kind: String,
+        error: Option<String>,
+    },
+    /// The task has been requeued manually to be performed
+    /// as soon as possible.
+    Requeued { id: Uuid, kind: String },
+    /// The task asked to be performed again later.
+    Rescheduled { id: Uuid, kind: String },
+}
+
+/// An error that a task got while performing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskErrorEntry {
+    pub attempt: i32,
+    pub message: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Keeps recent errors of every task that has not completed yet.
+///
+/// Errors are only kept in memory, so they're gone after
+/// the worker restarts.
+#[derive(Debug, Default)]
+pub(crate) struct TaskErrorHistory {
+    errors: DashMap<Uuid, VecDeque<TaskErrorEntry>>,
+}
+
+impl TaskErrorHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: Uuid) -> Vec<TaskErrorEntry> {
+        self.errors
+            .get(&id)
+            .map(|v| v.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn latest(&self, id: Uuid) -> Option<TaskErrorEntry> {
+        self.errors.get(&id).and_then(|v| v.back().cloned())
+    }
+
+    pub fn record(&self, id: Uuid, entry: TaskErrorEntry) {
+        let mut errors = self.errors.entry(id).or_default();
+        if errors.len() >= MAX_ERRORS_PER_TASK {
+            errors.pop_front();
+        }
+        errors.push_back(entry);
+    }
+
+    pub fn clear(&self, id: Uuid) {
+        self.errors.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(attempt: i32) -> TaskErrorEntry {
+        TaskErrorEntry {
+            attempt,
+            message: format!("attempt {attempt} failed"),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn should_keep_recent_errors_only() {
+        let history = TaskErrorHistory::new();
+        let id = Uuid::new_v4();
+        for attempt in 0..15 {
+            history.record(id, entry(attempt));
+        }
+
+        let errors = history.get(id);
+        assert_eq!(errors.len(), MAX_ERRORS_PER_TASK);
+        assert_eq!(errors[0].attempt, 5);
+        assert_eq!(history.latest(id).map(|v| v.attempt), Some(14));
+
+        history.clear(id);
+        assert!(history.get(id).is_empty());
+        assert!(history.latest(id).is_none());
+    }
+
+    #[test]
+    fn should_serialize_events_with_tag() {
+        let id = Uuid::nil();
+        let event = TaskEvent::Completed {
+            id,
+            kind: "foo".into(),
+        };
+        let value = serde_json::to_value(&event).unwrap_or_default();
+        assert_eq!(value["event"], "completed");
+        assert_eq!(value["kind"], "foo");
+    }
+}
@@ -0,0 +1,129 @@
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Function that will be called if a task type started or stopped
+/// failing consecutively.
+pub type TaskHealthListener<S> =
+    Box<dyn Fn(S, TaskHealthEvent) -> BoxFuture<'static, ()> + Send + Sync + 'static>;
+
+/// Changes of health of a specific task type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskHealthEvent {
+    /// The task type has failed consecutively and reached the
+    /// configured failure streak threshold.
+    Failing { kind: String, streak: u32 },
+    /// The task type that was previously failing has completed
+    /// successfully after the given amount of consecutive failures.
+    Recovered { kind: String, streak: u32 },
+}
+
+impl TaskHealthEvent {
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Failing { kind, .. } | Self::Recovered { kind, .. } => kind,
+        }
+    }
+
+    #[must_use]
+    pub fn streak(&self) -> u32 {
+        match self {
+            Self::Failing { streak, .. } | Self::Recovered { streak, .. } => *streak,
+        }
+    }
+}
+
+/// Keeps track of consecutive failures per task type.
+pub(crate) struct TaskHealthTracker {
+    streaks: DashMap<String, u32>,
+    threshold: u32,
+    total_failures: AtomicU64,
+}
+
+impl TaskHealthTracker {
+    #[must_use]
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            streaks: DashMap::new(),
+            threshold,
+            total_failures: AtomicU64::new(0),
+        }
+    }
+
+    #[must_use]
+    pub fn streak(&self, kind: &str) -> u32 {
+        self.streaks.get(kind).map(|v| *v).unwrap_or_default()
+    }
+
+    /// Total number of failures of every task type.
+    #[must_use]
+    pub fn total_failures(&self) -> u64 {
+        self.total_failures.load(Ordering::Relaxed)
+    }
+
+    /// Records a failure of a task type. It returns an event only
+    /// if the failure streak reached the threshold for the first time.
+    pub fn record_failure(&self, kind: &str) -> Option<TaskHealthEvent> {
+        self.total_failures.fetch_add(1, Ordering::Relaxed);
+
+        let mut streak = self.streaks.entry(kind.to_string()).or_default();
+        *streak = streak.saturating_add(1);
+
+        (*streak == self.threshold).then(|| TaskHealthEvent::Failing {
+            kind: kind.to_string(),
+            streak: *streak,
+        })
+    }
+
+    /// Records a success of a task type. It returns an event only if
+    /// the task type was previously considered as failing.
+    pub fn record_success(&self, kind: &str) -> Option<TaskHealthEvent> {
+        let (_, streak) = self.streaks.remove(kind)?;
+        (streak >= self.threshold).then(|| TaskHealthEvent::Recovered {
+            kind: kind.to_string(),
+            streak,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_failing_once() {
+        let tracker = TaskHealthTracker::new(2);
+        assert_eq!(tracker.record_failure("foo"), None);
+        assert_eq!(
+            tracker.record_failure("foo"),
+            Some(TaskHealthEvent::Failing {
+                kind: "foo".into(),
+                streak: 2
+            })
+        );
+        assert_eq!(tracker.record_failure("foo"), None);
+        assert_eq!(tracker.streak("foo"), 3);
+        assert_eq!(tracker.streak("bar"), 0);
+        assert_eq!(tracker.total_failures(), 3);
+    }
+
+    #[test]
+    fn should_report_recovery_if_failing() {
+        let tracker = TaskHealthTracker::new(2);
+        assert_eq!(tracker.record_failure("foo"), None);
+        assert_eq!(tracker.record_success("foo"), None);
+
+        tracker.record_failure("foo");
+        tracker.record_failure("foo");
+        tracker.record_failure("foo");
+        assert_eq!(
+            tracker.record_success("foo"),
+            Some(TaskHealthEvent::Recovered {
+                kind: "foo".into(),
+                streak: 3
+            })
+        );
+        assert_eq!(tracker.streak("foo"), 0);
+    }
+}
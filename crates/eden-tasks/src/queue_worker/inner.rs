@@ -1,23 +1,47 @@
 use chrono::TimeDelta;
 use eden_tasks_schema::types::WorkerId;
 use std::fmt::Debug;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{broadcast, Mutex};
 use tokio::task::JoinHandle;
+use uuid::Uuid;
 
+use super::autotune::ConcurrencyTuner;
+use super::events::{TaskErrorHistory, TaskEvent};
+use super::health::{TaskHealthListener, TaskHealthTracker};
 use super::task_manager::QueueWorkerTaskManager;
 use super::QueueWorker;
 use crate::registry::TaskRegistry;
 
 pub struct QueueWorkerInner<S> {
     pub id: WorkerId,
+    /// Unique ID of this queue worker used to claim the tasks it pulls,
+    /// since other processes may share the same worker ID.
+    pub claim_id: Uuid,
+    /// How long claims of an instance last since it was last seen
+    /// in the `instances` table.
+    pub claim_lease: TimeDelta,
     pub registry: Arc<TaskRegistry<S>>,
+    /// Whether this queue worker runs recurring tasks. Only one of the
+    /// processes sharing the same database should run them.
+    pub runs_recurring_tasks: AtomicBool,
+    /// Whether this queue worker has stopped pulling new tasks.
+    pub paused: AtomicBool,
 
     // state
+    pub errors: TaskErrorHistory,
+    pub events: broadcast::Sender<TaskEvent>,
+    pub health: TaskHealthTracker,
+    pub health_listener: OnceLock<TaskHealthListener<S>>,
     pub pool: sqlx::PgPool,
+    /// Keeps scheduled tasks in memory if it is set.
+    #[cfg(feature = "testkit")]
+    pub recorder: OnceLock<super::recorder::ScheduleRecorder>,
     pub runner_handle: Mutex<Option<JoinHandle<()>>>,
     pub state: S,
     pub task_manager: QueueWorkerTaskManager,
+    pub tuner: ConcurrencyTuner,
 
     // configuration
     pub max_attempts: u16,
@@ -30,6 +54,8 @@ impl<S: Clone + Send + Sync + 'static> Debug for QueueWorkerInner<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueueWorker")
             .field("id", &self.id)
+            .field("claim_id", &self.claim_id)
+            .field("claim_lease", &self.claim_lease)
             .field("registry", &self.registry)
             .field("max_attempts", &self.max_attempts)
             .field("max_running_tasks", &self.max_running_tasks)
@@ -4,31 +4,47 @@ use eden_utils::error::tags::Suggestion;
 use eden_utils::sql::SqlErrorExt;
 use eden_utils::time::IntoStdDuration;
 use eden_utils::{Error, ErrorCategory, Result};
+use futures::future::BoxFuture;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 use tracing::{debug, info, trace, warn};
 use uuid::Uuid;
 
 use self::catch_unwind::CatchUnwindTaskFuture;
+use self::events::{TaskErrorEntry, TaskEvent};
 use self::inner::QueueWorkerInner;
-use self::task_manager::{PerformTaskAction, QueueWorkerTaskManager};
+use self::task_manager::PerformTaskAction;
 use crate::error::tags::ScheduleTaskTag;
-use crate::error::{ScheduleTaskError, TaskError, WorkerStartError};
-use crate::registry::{RegistryItem, TaskRegistry};
+use crate::error::{CreateWorkerError, ScheduleTaskError, TaskError, WorkerStartError};
+use crate::registry::RegistryItem;
 use crate::settings::Settings;
 use crate::{Scheduled, Task, TaskResult, TaskRunContext};
 
+mod autotune;
 mod builder;
 mod catch_unwind;
 mod database;
+mod events;
+mod health;
 mod inner;
+#[cfg(feature = "testkit")]
+mod recorder;
 mod runner;
 mod task_manager;
 
+pub use self::builder::QueueWorkerBuilder;
+pub use self::events::{TaskErrorEntry, TaskEvent};
+pub use self::health::{TaskHealthEvent, TaskHealthListener};
+#[cfg(feature = "testkit")]
+pub use self::recorder::ScheduledTask;
 pub use eden_tasks_schema::types::WorkerId;
 
+/// Maximum amount of task events that subscribers can lag behind.
+const TASK_EVENTS_CAPACITY: usize = 64;
+
 /// In Eden task queue architecture, there will be assigned workers
 /// to perform a task that is required. The queue system will equally
 /// distribute to all workers.
@@ -40,22 +56,17 @@ pub use eden_tasks_schema::types::WorkerId;
 pub struct QueueWorker<S>(Arc<QueueWorkerInner<S>>);
 
 impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
-    #[must_use]
-    pub fn new(id: WorkerId, pool: sqlx::PgPool, settings: &Settings, state: S) -> Self {
-        Self(Arc::new(QueueWorkerInner {
-            id,
-            registry: Arc::new(TaskRegistry::new()),
-
-            pool,
-            runner_handle: Mutex::new(None),
-            state,
-            task_manager: QueueWorkerTaskManager::new(settings.max_running_tasks.get(), id),
-
-            max_attempts: settings.max_task_retries,
-            max_running_tasks: settings.max_running_tasks.get(),
-            queued_tasks_per_batch: settings.queued_tasks_per_batch.get(),
-            stalled_tasks_threshold: settings.stalled_tasks_threshold,
-        }))
+    /// Creates a new queue worker.
+    ///
+    /// Use [`QueueWorkerBuilder`] if the state has to be created
+    /// alongside the worker.
+    pub fn new(
+        id: WorkerId,
+        pool: sqlx::PgPool,
+        settings: &Settings,
+        state: S,
+    ) -> Result<Self, CreateWorkerError> {
+        QueueWorkerBuilder::new(id, pool, settings).map(|v| v.build(state))
     }
 
     #[must_use]
@@ -73,11 +84,102 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
             .unwrap_or(true)
     }
 
+    /// Gets the current amount of consecutive failures of a task type.
+    #[must_use]
+    pub fn failure_streak<T: Task<State = S>>(&self) -> u32 {
+        self.0.health.streak(T::kind())
+    }
+
+    /// Gets how many times tasks have failed or timed out
+    /// since the worker started.
+    #[must_use]
+    pub fn total_failures(&self) -> u64 {
+        self.0.health.total_failures()
+    }
+
+    /// Whether this worker runs recurring tasks. It is true by default.
+    #[must_use]
+    pub fn runs_recurring_tasks(&self) -> bool {
+        self.0.runs_recurring_tasks.load(Ordering::Relaxed)
+    }
+
+    /// Sets whether this worker should run recurring tasks. Queued tasks
+    /// are still run either way.
+    ///
+    /// If multiple processes share the same database, only one of them
+    /// should run recurring tasks so they don't run more than once.
+    pub fn set_runs_recurring_tasks(&self, value: bool) {
+        let previous = self.0.runs_recurring_tasks.swap(value, Ordering::Relaxed);
+        if previous != value {
+            debug!("queue worker {} runs recurring tasks: {value}", self.0.id);
+        }
+    }
+
+    /// Whether this worker has stopped pulling new tasks.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.0.paused.load(Ordering::Relaxed)
+    }
+
+    /// Stops pulling new tasks (both queued and recurring) until
+    /// [`resume`](Self::resume) is called. Tasks that are already
+    /// running are allowed to finish.
+    ///
+    /// It returns false if the worker is already paused.
+    pub fn pause(&self) -> bool {
+        let changed = !self.0.paused.swap(true, Ordering::Relaxed);
+        if changed {
+            info!(
+                "paused queue worker {} with {} running task(s)",
+                self.0.id,
+                self.running_tasks()
+            );
+        }
+        changed
+    }
+
+    /// Starts pulling new tasks again after [`pause`](Self::pause).
+    ///
+    /// It returns false if the worker is not paused.
+    pub fn resume(&self) -> bool {
+        let changed = self.0.paused.swap(false, Ordering::Relaxed);
+        if changed {
+            info!("resumed queue worker {}", self.0.id);
+        }
+        changed
+    }
+
     #[must_use]
     pub fn running_tasks(&self) -> usize {
         self.0.task_manager.running_tasks()
     }
 
+    /// Gets how many tasks can run at the same time. It may change over
+    /// time if `min_running_tasks` is set in the worker settings.
+    #[must_use]
+    pub fn concurrency(&self) -> usize {
+        self.0.tuner.current()
+    }
+
+    /// Gets how many times the concurrency has been adjusted.
+    #[must_use]
+    pub fn concurrency_adjustments(&self) -> u64 {
+        self.0.tuner.adjustments()
+    }
+
+    /// Gets the recent errors of a task that has not completed yet
+    /// from the oldest to the newest.
+    #[must_use]
+    pub fn task_errors(&self, id: Uuid) -> Vec<TaskErrorEntry> {
+        self.0.errors.get(id)
+    }
+
+    /// Subscribes to every [task event](TaskEvent) happened in this worker.
+    #[must_use]
+    pub fn subscribe_events(&self) -> broadcast::Receiver<TaskEvent> {
+        self.0.events.subscribe()
+    }
+
     // strictly for testing only!
     #[doc(hidden)]
     #[must_use]
@@ -86,6 +188,34 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
     }
 }
 
+#[cfg(feature = "testkit")]
+impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
+    /// Keeps every task scheduled after this call in memory instead
+    /// of storing it into the database, so tests can assert on what
+    /// has been scheduled without connecting to the database.
+    pub fn record_scheduled(&self) {
+        self.0.recorder.get_or_init(Default::default);
+    }
+
+    /// Tasks scheduled since [recording](QueueWorker::record_scheduled)
+    /// has started, from the oldest to the newest.
+    #[must_use]
+    pub fn scheduled_tasks(&self) -> Vec<ScheduledTask> {
+        self.0
+            .recorder
+            .get()
+            .map(recorder::ScheduleRecorder::tasks)
+            .unwrap_or_default()
+    }
+
+    /// Forgets every recorded scheduled task.
+    pub fn clear_scheduled_tasks(&self) {
+        if let Some(recorder) = self.0.recorder.get() {
+            recorder.clear();
+        }
+    }
+}
+
 impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
     #[allow(clippy::unwrap_used)]
     #[must_use]
@@ -101,6 +231,24 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
         self
     }
 
+    /// Sets a function that will be called if a task type reached the
+    /// configured failure streak threshold or recovered from it.
+    #[must_use]
+    pub fn on_task_health<F>(self, listener: F) -> Self
+    where
+        F: Fn(S, TaskHealthEvent) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    {
+        assert!(
+            !self.is_running(),
+            "setting task health listener while the worker is running is not allowed!"
+        );
+        assert!(
+            self.0.health_listener.set(Box::new(listener)).is_ok(),
+            "task health listener is already set"
+        );
+        self
+    }
+
     /// Attempts to schedule a custom task into the queue
     /// to be ran in a later time.
     ///
@@ -110,6 +258,9 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
     /// error during the operation.
     ///
     /// It returns the queued job's id as [UUID](Uuid) to be referenced if needed.
+    /// If the task is deduplicated with a pending task (see
+    /// [`Task::dedup_key`](crate::Task::dedup_key)), the pending task's id is
+    /// returned instead.
     pub async fn schedule<T>(
         &self,
         task: T,
@@ -135,7 +286,18 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
                 .attach_lazy(|| ScheduleTaskTag::new(&task))?,
         };
 
-        self.queue(None, raw_data, scheduled, None, 0)
+        #[cfg(feature = "testkit")]
+        if let Some(recorder) = self.0.recorder.get() {
+            return Ok(recorder.record(ScheduledTask {
+                id: Uuid::new_v4(),
+                kind: raw_data.kind,
+                data: raw_data.inner,
+                dedup_key: task.dedup_key(),
+                scheduled,
+            }));
+        }
+
+        self.queue(None, raw_data, task.dedup_key(), scheduled, None, 0)
             .await
             .attach_lazy(|| ScheduleTaskTag::new(&task))
     }
@@ -229,6 +391,93 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
 }
 
 impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
+    pub(crate) fn record_task_health(&self, kind: &str, action: &PerformTaskAction) {
+        let event = match action {
+            PerformTaskAction::Completed => self.0.health.record_success(kind),
+            PerformTaskAction::RetryOnError | PerformTaskAction::RetryOnTimedOut => {
+                self.0.health.record_failure(kind)
+            }
+            _ => None,
+        };
+
+        let Some(event) = event else {
+            return;
+        };
+
+        match &event {
+            TaskHealthEvent::Failing { kind, streak } => {
+                warn!("task {kind:?} failed {streak} time(s) in a row");
+            }
+            TaskHealthEvent::Recovered { kind, streak } => {
+                info!("task {kind:?} recovered after {streak} consecutive failure(s)");
+            }
+        }
+
+        if let Some(listener) = self.0.health_listener.get() {
+            let future = listener(self.0.state.clone(), event);
+            eden_utils::tokio::spawn("eden_tasks::worker::notify_task_health", future);
+        }
+    }
+
+    /// Adjusts the concurrency of the worker based on how many tasks
+    /// are waiting to run and how long tasks take to complete.
+    pub(crate) fn tune_concurrency(&self) {
+        let task_manager = &self.0.task_manager;
+        let running = task_manager.running_tasks();
+        let waiting = task_manager.pending_tasks().saturating_sub(running);
+
+        let Some(change) = self.0.tuner.tune(waiting, running) else {
+            return;
+        };
+        task_manager.resize(change.to);
+
+        info!(
+            worker.concurrency = change.to,
+            worker.tasks.waiting = waiting,
+            worker.tasks.latency = ?self.0.tuner.average_latency(),
+            "adjusted concurrency of queue worker {} from {} to {}: {}",
+            self.0.id,
+            change.from,
+            change.to,
+            change.reason
+        );
+    }
+
+    pub(crate) fn record_task_event(&self, id: Uuid, kind: &str, action: &PerformTaskAction) {
+        let kind = kind.to_string();
+        let event = match action {
+            PerformTaskAction::Completed => {
+                self.0.errors.clear(id);
+                TaskEvent::Completed { id, kind }
+            }
+            PerformTaskAction::Delete => {
+                self.0.errors.clear(id);
+                TaskEvent::Deleted { id, kind }
+            }
+            PerformTaskAction::RetryIn(..) => TaskEvent::Rescheduled { id, kind },
+            PerformTaskAction::RetryOnError | PerformTaskAction::RetryOnTimedOut => {
+                let error = self.0.errors.latest(id).map(|v| v.message);
+                TaskEvent::Failed { id, kind, error }
+            }
+        };
+        self.publish_event(event);
+    }
+
+    // nobody may be subscribed to task events at the moment
+    #[allow(clippy::let_underscore_must_use)]
+    pub(crate) fn publish_event(&self, event: TaskEvent) {
+        let _ = self.0.events.send(event);
+    }
+
+    fn record_task_error(&self, ctx: &TaskRunContext, message: String) {
+        let entry = TaskErrorEntry {
+            attempt: ctx.attempts,
+            message,
+            occurred_at: chrono::Utc::now(),
+        };
+        self.0.errors.record(ctx.id, entry);
+    }
+
     async fn perform_task(
         &self,
         task: &(dyn Task<State = S> + 'static),
@@ -271,6 +520,7 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
                     "task {:?} got a rejection error",
                     registry_item.kind
                 );
+                self.record_task_error(ctx, error.to_string());
                 PerformTaskAction::Delete
             }
             Err(error) => {
@@ -280,6 +530,7 @@ impl<S: Clone + Send + Sync + 'static> QueueWorker<S> {
                     "task {:?} got an error",
                     registry_item.kind,
                 );
+                self.record_task_error(ctx, error.to_string());
 
                 let action = error
                     .get_attached_any::<PerformTaskAction>()
@@ -0,0 +1,59 @@
+use serde::de::DeserializeOwned;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::Scheduled;
+
+/// A task that has been [scheduled](super::QueueWorker::schedule) while
+/// the queue worker is [recording](super::QueueWorker::record_scheduled).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledTask {
+    pub id: Uuid,
+    pub kind: String,
+    pub data: serde_json::Value,
+    pub dedup_key: Option<String>,
+    pub scheduled: Scheduled,
+}
+
+impl ScheduledTask {
+    /// Deserializes the data of the task if it is a type of `T`.
+    #[must_use]
+    pub fn parse<T: crate::Task + DeserializeOwned>(&self) -> Option<T> {
+        if self.kind != T::kind() {
+            return None;
+        }
+        serde_json::from_value(self.data.clone()).ok()
+    }
+}
+
+/// Keeps scheduled tasks in memory instead of storing them
+/// into the database.
+#[derive(Debug, Default)]
+pub(crate) struct ScheduleRecorder {
+    tasks: Mutex<Vec<ScheduledTask>>,
+}
+
+impl ScheduleRecorder {
+    pub fn record(&self, task: ScheduledTask) -> Uuid {
+        let id = task.id;
+        self.tasks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(task);
+        id
+    }
+
+    pub fn tasks(&self) -> Vec<ScheduledTask> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn clear(&self) {
+        self.tasks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+    }
+}
@@ -2,9 +2,11 @@ use chrono::{DateTime, Utc};
 use eden_tasks_schema::types::Task;
 use eden_utils::sql::SqlErrorExt;
 use eden_utils::Result;
+use sqlx::postgres::PgListener;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 use tracing::{debug, info, trace, warn, Instrument};
 
 use super::task_manager::{PendingTask, QueueWorkerTaskManager};
@@ -13,9 +15,11 @@ use super::QueueWorker;
 #[derive(Clone)]
 pub struct QueueWorkerRunner<S> {
     errors: Arc<AtomicUsize>,
+    is_listening: Arc<AtomicBool>,
     pull_queue_block: Arc<AtomicBool>,
     should_setup_worker: Arc<AtomicBool>,
     task_manager: QueueWorkerTaskManager,
+    wakeup: Arc<Notify>,
     worker: QueueWorker<S>,
 }
 
@@ -24,9 +28,11 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
     pub fn new(worker: QueueWorker<S>, setup_later: bool) -> Self {
         Self {
             errors: Arc::new(AtomicUsize::new(0)),
+            is_listening: Arc::new(AtomicBool::new(false)),
             pull_queue_block: Arc::new(AtomicBool::new(true)),
             should_setup_worker: Arc::new(AtomicBool::new(setup_later)),
             task_manager: worker.0.task_manager.clone(),
+            wakeup: Arc::new(Notify::new()),
             worker,
         }
     }
@@ -39,8 +45,13 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
     ))]
     pub async fn run(mut self) {
         info!("started queue worker {}", self.worker.id());
+        eden_utils::tokio::spawn(
+            "eden_tasks::worker::listen_queued_tasks",
+            self.clone().listen().in_current_span(),
+        );
 
         let mut sleep_duration = DEFAULT_INTERVAL;
+        let mut last_tuned = Instant::now();
         loop {
             trace!("runner loop start");
 
@@ -72,18 +83,94 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
             let elapsed = instant.elapsed();
             trace!(?elapsed, "runner loop ended");
 
-            let sleep = Box::pin(tokio::time::sleep(sleep_duration));
+            if last_tuned.elapsed() >= TUNE_INTERVAL {
+                self.worker.tune_concurrency();
+                last_tuned = Instant::now();
+            }
+
+            let interval = if sleep_duration == DEFAULT_INTERVAL {
+                self.idle_interval(Utc::now()).await
+            } else {
+                sleep_duration
+            };
+
+            let sleep = Box::pin(tokio::time::sleep(interval));
             let closed = Box::pin(self.task_manager.closed());
             tokio::select! {
                 _ = closed => {
                     info!("closing queue worker {}", self.worker.id());
                     break;
                 }
+                () = self.wakeup.notified() => {
+                    trace!("woken up by a newly queued task");
+                }
                 _ = sleep => {}
             }
         }
     }
 
+    /// How long to wait before polling for pending tasks again.
+    ///
+    /// Newly queued tasks wake up the runner while it is listening for
+    /// them, so it only needs to poll often enough for the tasks that
+    /// are not notified (like recurring and retried tasks).
+    async fn idle_interval(&self, now: DateTime<Utc>) -> Duration {
+        if !self.is_listening.load(Ordering::Relaxed) {
+            return DEFAULT_INTERVAL;
+        }
+
+        let mut interval = LISTENING_INTERVAL;
+        if !self.worker.runs_recurring_tasks() {
+            return interval;
+        }
+
+        for task in self.worker.0.registry.recurring_tasks().await.iter() {
+            let Some(deadline) = task.deadline().await else {
+                continue;
+            };
+            let until = (deadline - now).to_std().unwrap_or_default();
+            interval = interval.min(until);
+        }
+        interval.max(DEFAULT_INTERVAL)
+    }
+
+    /// Wakes up the runner whenever a task that is due within a second
+    /// is queued. It falls back to polling while it cannot listen until
+    /// it has reconnected to the database.
+    async fn listen(self) {
+        loop {
+            tokio::select! {
+                Err(error) = self.receive_notifications() => {
+                    warn!(%error, "could not listen for queued tasks, falling back to polling");
+                }
+                _ = self.task_manager.closed() => break,
+            }
+            self.is_listening.store(false, Ordering::Relaxed);
+
+            tokio::select! {
+                () = tokio::time::sleep(RECONNECT_DELAY) => {}
+                _ = self.task_manager.closed() => break,
+            }
+        }
+        self.is_listening.store(false, Ordering::Relaxed);
+    }
+
+    async fn receive_notifications(&self) -> std::result::Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(&self.worker.0.pool).await?;
+        listener.listen(Task::NOTIFY_CHANNEL).await?;
+
+        debug!("listening for queued tasks");
+        self.is_listening.store(true, Ordering::Relaxed);
+
+        // tasks may have been queued while reconnecting
+        self.wakeup.notify_one();
+        loop {
+            listener.recv().await?;
+            trace!("received queued task notification");
+            self.wakeup.notify_one();
+        }
+    }
+
     async fn next_action(&self, now: DateTime<Utc>) -> RunnerAction {
         // Setup worker if the database is healthy after some time
         let should_setup_worker = self.should_setup_worker.load(Ordering::Relaxed);
@@ -139,6 +226,10 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
     #[tracing::instrument(skip_all, fields(%now), name = "loop", level = "debug")]
     async fn run_pending_tasks(&self, now: DateTime<Utc>) -> Result<()> {
         self.worker.requeue_stalled_tasks(now).await?;
+        if self.worker.is_paused() {
+            trace!("queue worker is paused, not pulling pending tasks");
+            return Ok(());
+        }
 
         let pending_tasks = self.pull_pending_tasks(now).await?;
         if pending_tasks.len() > 0 {
@@ -150,6 +241,7 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
         let pull_queue_block_tx = self.pull_queue_block.clone();
         let process_queue_tasks = self.pull_queue_block.load(Ordering::Relaxed);
         let task_manager = self.task_manager.clone();
+        let wakeup = self.wakeup.clone();
         let worker = self.worker.clone();
 
         let span = tracing::Span::current();
@@ -177,6 +269,7 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
 
                 debug!("batch of queued tasks completed");
                 pull_queue_block_tx.store(true, Ordering::Relaxed);
+                wakeup.notify_one();
             }
             .instrument(span),
         );
@@ -188,7 +281,10 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
         let mut pending_tasks = Vec::new();
         let registry = &self.worker.0.registry;
 
-        for task in registry.recurring_tasks().await.iter() {
+        // another process sharing the database may run them instead
+        let runs_recurring_tasks = self.worker.runs_recurring_tasks();
+        let recurring_tasks = registry.recurring_tasks().await;
+        for task in recurring_tasks.iter().filter(|_| runs_recurring_tasks) {
             let should_not_run = task.is_blocked().await || task.is_running();
             if should_not_run {
                 continue;
@@ -207,6 +303,7 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
                 task: task.clone(),
             });
         }
+        drop(recurring_tasks);
 
         let can_pull_queued = self.pull_queue_block.load(Ordering::Relaxed);
         if can_pull_queued {
@@ -217,10 +314,12 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
 
             // wait for queued tasks to be finished before moving into
             // the next batch of tasks.
-            let mut stream = Task::pull_all_pending(self.worker.id(), max_attempts, Some(now))
-                .limit(self.worker.0.queued_tasks_per_batch)
-                .build()
-                .size(50);
+            let claim_id = self.worker.0.claim_id;
+            let mut stream =
+                Task::pull_all_pending(self.worker.id(), claim_id, max_attempts, Some(now))
+                    .limit(self.worker.0.queued_tasks_per_batch)
+                    .build()
+                    .size(50);
 
             let mut conn = self.worker.db_connection().await?;
             let mut pulled_queued_tasks = 0;
@@ -251,6 +350,11 @@ impl<S: Clone + Send + Sync + 'static> QueueWorkerRunner<S> {
 // We need to wait for 30 seconds if one iteration fails
 const TIMED_OUT_INTERVAL: Duration = Duration::from_secs(30);
 const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+// Newly queued tasks are notified while listening, so polling is
+// only needed for tasks that are due later
+const LISTENING_INTERVAL: Duration = Duration::from_secs(1);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const TUNE_INTERVAL: Duration = Duration::from_secs(10);
 
 const MAX_ERRORS_UNTIL_TIMED_OUT: usize = 2;
 
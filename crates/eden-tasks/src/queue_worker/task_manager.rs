@@ -1,4 +1,5 @@
 use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
 use eden_tasks_schema::forms::UpdateTaskForm;
 use eden_tasks_schema::types::{Task, TaskPriority, TaskRawData, TaskStatus, WorkerId};
 use eden_utils::error::exts::{AnonymizedResultExt, ResultExt};
@@ -8,7 +9,8 @@ use pin_project_lite::pin_project;
 use std::future::Future;
 use std::ops::Deref;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 use tokio::sync::futures::Notified;
 use tokio::sync::{Notify, Semaphore, SemaphorePermit};
 use tokio::task::JoinHandle;
@@ -16,6 +18,7 @@ use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 use tokio_util::task::task_tracker::TaskTrackerWaitFuture;
 use tokio_util::task::TaskTracker;
 use tracing::{debug, info, trace, warn, Instrument, Span};
+use uuid::Uuid;
 
 use crate::error::PerformTaskError;
 use crate::registry::{RecurringTask, RegistryItem};
@@ -31,8 +34,15 @@ impl QueueWorkerTaskManager {
     pub fn new(concurrency: usize, id: WorkerId) -> Self {
         Self(Arc::new(QueueWorkerTaskManagerInner {
             aborted: CancellationToken::new(),
+            cancellations: DashMap::new(),
             close_token: CancellationToken::new(),
             changed_tasks_notify: Arc::new(Notify::new()),
+            concurrency: Mutex::new(Concurrency {
+                shrinking: false,
+                size: concurrency,
+                target: concurrency,
+            }),
+            concurrency_changed: Notify::new(),
             futures: TaskTracker::new(),
             id,
             pending_tasks: Arc::new(AtomicUsize::new(0)),
@@ -44,8 +54,15 @@ impl QueueWorkerTaskManager {
 
 pub struct QueueWorkerTaskManagerInner {
     aborted: CancellationToken,
+    /// Cancellation tokens of every pending and running task
+    /// in this queue worker.
+    cancellations: DashMap<Uuid, CancellationToken>,
     close_token: CancellationToken,
     changed_tasks_notify: Arc<Notify>,
+    concurrency: Mutex<Concurrency>,
+    /// Wakes up the task shrinking the semaphore if the target
+    /// concurrency has changed.
+    concurrency_changed: Notify,
     futures: TaskTracker,
     id: WorkerId,
     pending_tasks: Arc<AtomicUsize>,
@@ -62,6 +79,23 @@ pub struct WorkerPermitTaskGuard<'a> {
     running_tasks: Arc<AtomicUsize>,
 }
 
+/// How many tasks can run at the same time.
+struct Concurrency {
+    /// Whether a task is waiting for running tasks to complete
+    /// so their permits can be taken out from the semaphore.
+    shrinking: bool,
+    /// Permits that are owned by the semaphore (including the
+    /// permits held by running tasks).
+    size: usize,
+    target: usize,
+}
+
+/// Removes the cancellation token of a task once it has finished.
+struct CancellationGuard {
+    id: Uuid,
+    manager: QueueWorkerTaskManager,
+}
+
 pub enum PendingTask {
     Recurring {
         deadline: DateTime<Utc>,
@@ -75,6 +109,16 @@ impl QueueWorkerTaskManager {
         self.aborted.cancel();
     }
 
+    /// Aborts a pending or running task. It returns false if the
+    /// task is not pending or running in this queue worker.
+    pub fn cancel(&self, id: Uuid) -> bool {
+        let Some((_, token)) = self.cancellations.remove(&id) else {
+            return false;
+        };
+        token.cancel();
+        true
+    }
+
     #[must_use]
     pub fn is_closed(&self) -> bool {
         self.close_token.is_cancelled()
@@ -109,6 +153,73 @@ impl QueueWorkerTaskManager {
         self.futures.wait()
     }
 
+    /// Changes how many tasks can run at the same time. Shrinking takes
+    /// effect once enough running tasks have completed.
+    ///
+    /// The concurrency is reconciled toward the latest target, so
+    /// growing while a shrink is still pending is not lost.
+    pub fn resize(&self, to: usize) {
+        let mut concurrency = self.lock_concurrency();
+        concurrency.target = to;
+
+        if concurrency.size < to {
+            self.semaphore.add_permits(to - concurrency.size);
+            concurrency.size = to;
+        } else if concurrency.size > to {
+            let forgotten = self.semaphore.forget_permits(concurrency.size - to);
+            concurrency.size -= forgotten;
+        }
+
+        if concurrency.size > concurrency.target && !concurrency.shrinking {
+            concurrency.shrinking = true;
+
+            let manager = self.clone();
+            eden_utils::tokio::spawn("eden_tasks::worker::shrink_concurrency", async move {
+                manager.shrink().await
+            });
+        }
+        drop(concurrency);
+
+        self.concurrency_changed.notify_waiters();
+    }
+
+    /// Takes out permits of completed tasks from the semaphore
+    /// until it reaches the target concurrency.
+    async fn shrink(&self) {
+        loop {
+            let permit = tokio::select! {
+                result = self.semaphore.acquire() => result.ok(),
+                () = self.concurrency_changed.notified() => None,
+                () = self.aborted.cancelled() => {
+                    self.lock_concurrency().shrinking = false;
+                    return;
+                }
+            };
+
+            // permits acquired after the target has grown are given
+            // back to the semaphore once dropped.
+            let mut concurrency = self.lock_concurrency();
+            if let Some(permit) = permit
+                && concurrency.size > concurrency.target
+            {
+                permit.forget();
+                concurrency.size -= 1;
+            }
+
+            if concurrency.size <= concurrency.target {
+                concurrency.shrinking = false;
+                return;
+            }
+        }
+    }
+
+    fn lock_concurrency(&self) -> MutexGuard<'_, Concurrency> {
+        // the concurrency is always left in a valid state
+        self.concurrency
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     pub fn running_tasks_changed(&self) -> RunningTasksChanged<'_> {
         RunningTasksChanged {
             fut: self.changed_tasks_notify.notified(),
@@ -118,7 +229,6 @@ impl QueueWorkerTaskManager {
 }
 
 impl QueueWorkerTaskManager {
-    #[allow(clippy::expect_used)]
     pub fn handle_pending_task<S>(
         &self,
         now: DateTime<Utc>,
@@ -144,44 +254,73 @@ impl QueueWorkerTaskManager {
             task.rust_type = tracing::field::Empty,
         );
 
+        let cancellation = CancellationToken::new();
+        self.cancellations.insert(ctx.id, cancellation.clone());
+
         self.futures.spawn(
             async move {
-                let Some(_permit) = manager.permit_task().await else {
-                    warn!("aborted awaiting task {:?} ({})", ctx.id, task.kind());
-                    return;
+                let _guard = CancellationGuard {
+                    id: ctx.id,
+                    manager: manager.clone(),
                 };
-                let _guard = task.as_recurring_task().map(|v| v.running_guard());
-
-                let (action, boxed_task) = manager.perform_task(&worker, &task, &ctx).await;
-                let boxed_task = boxed_task.expect("unexpected boxed_task to be None");
-
-                let is_completed = matches!(action, PerformTaskAction::Completed);
-                let result = task
-                    .handle_task_action(&ctx, boxed_task, &worker, action)
-                    .await;
-
-                if let Err(error) = result {
-                    warn!(%error, "task {:?} failed to perform post-task action", ctx.id);
-                    return;
-                }
-
-                // Unblock if it is periodic task, if nothing goes wrong
-                let option = worker.0.registry.get_recurring_task(task.kind()).await;
-                if let Some(task) = option
-                    && task.is_blocked().await
-                    && is_completed
-                {
-                    info!(
-                        "unblocked recurring task {:?} ({}). allowing task to run periodically",
-                        task.kind, task.rust_name
-                    );
-                    task.set_blocked(false).await;
+                tokio::select! {
+                    () = manager.run_pending_task(&worker, &task, &ctx) => {}
+                    () = cancellation.cancelled() => {
+                        info!("cancelled task {:?} ({})", ctx.id, task.kind());
+                    }
                 }
             }
             .instrument(span),
         )
     }
 
+    #[allow(clippy::expect_used)]
+    async fn run_pending_task<S>(
+        &self,
+        worker: &QueueWorker<S>,
+        task: &PendingTask,
+        ctx: &TaskRunContext,
+    ) where
+        S: Clone + Send + Sync + 'static,
+    {
+        let Some(_permit) = self.permit_task().await else {
+            warn!("aborted awaiting task {:?} ({})", ctx.id, task.kind());
+            return;
+        };
+        let _guard = task.as_recurring_task().map(|v| v.running_guard());
+
+        let started_at = Instant::now();
+        let (action, boxed_task) = self.perform_task(worker, task, ctx).await;
+        worker.0.tuner.record_latency(started_at.elapsed());
+        let boxed_task = boxed_task.expect("unexpected boxed_task to be None");
+
+        worker.record_task_health(task.kind(), &action);
+        worker.record_task_event(ctx.id, task.kind(), &action);
+
+        let is_completed = matches!(action, PerformTaskAction::Completed);
+        let result = task
+            .handle_task_action(ctx, boxed_task, worker, action)
+            .await;
+
+        if let Err(error) = result {
+            warn!(%error, "task {:?} failed to perform post-task action", ctx.id);
+            return;
+        }
+
+        // Unblock if it is periodic task, if nothing goes wrong
+        let option = worker.0.registry.get_recurring_task(task.kind()).await;
+        if let Some(task) = option
+            && task.is_blocked().await
+            && is_completed
+        {
+            info!(
+                "unblocked recurring task {:?} ({}). allowing task to run periodically",
+                task.kind, task.rust_name
+            );
+            task.set_blocked(false).await;
+        }
+    }
+
     async fn perform_task<S>(
         &self,
         worker: &QueueWorker<S>,
@@ -277,6 +416,12 @@ impl Deref for QueueWorkerTaskManager {
     }
 }
 
+impl Drop for CancellationGuard {
+    fn drop(&mut self) {
+        self.manager.cancellations.remove(&self.id);
+    }
+}
+
 impl<'a> Drop for WorkerPermitTaskGuard<'a> {
     fn drop(&mut self) {
         self.pending_tasks.fetch_sub(1, Ordering::Relaxed);
@@ -406,10 +551,14 @@ impl PendingTask {
                         .status(Some(TaskStatus::Success))
                         .build();
 
-                    return Task::update(&mut conn, context.id, form)
+                    let task = Task::update(&mut conn, context.id, form)
                         .await
-                        .map(|_| ())
-                        .anonymize_error();
+                        .anonymize_error()?;
+
+                    if task.is_none() {
+                        debug!("task {:?} has been cancelled while running", context.id);
+                    }
+                    return Ok(());
                 }
                 Delete => {
                     debug!("deleted task for {:?}", info.data.kind);
@@ -438,10 +587,12 @@ impl PendingTask {
             );
 
             if !is_recurring {
-                return Task::fail(&mut conn, context.id)
-                    .await
-                    .map(|_| ())
-                    .anonymize_error();
+                let task = Task::fail(&mut conn, context.id).await.anonymize_error()?;
+
+                if task.is_none() {
+                    debug!("task {:?} has been cancelled while running", context.id);
+                }
+                return Ok(());
             }
         }
 
@@ -464,6 +615,7 @@ impl PendingTask {
                 kind: self.kind().into(),
                 inner: serde_json::Value::Null,
             },
+            None,
             Scheduled::In(retry_in),
             Some(now),
             1,
@@ -525,3 +677,45 @@ impl PendingTask {
         }
     }
 }
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn should_shrink_once_tasks_complete() {
+        let manager = QueueWorkerTaskManager::new(2, WorkerId::ONE);
+        let permits = manager.semaphore.acquire_many(2).await.unwrap();
+
+        manager.resize(1);
+        settle().await;
+        assert_eq!(manager.semaphore.available_permits(), 0);
+
+        drop(permits);
+        settle().await;
+        assert_eq!(manager.semaphore.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_grow_while_shrinking() {
+        let manager = QueueWorkerTaskManager::new(2, WorkerId::ONE);
+        let permits = manager.semaphore.acquire_many(2).await.unwrap();
+
+        manager.resize(1);
+        settle().await;
+        manager.resize(4);
+        settle().await;
+        assert_eq!(manager.semaphore.available_permits(), 2);
+
+        drop(permits);
+        settle().await;
+        assert_eq!(manager.semaphore.available_permits(), 4);
+    }
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use dashmap::mapref::one::Ref;
 use dashmap::DashMap;
-use eden_tasks_schema::types::TaskPriority;
+use eden_tasks_schema::types::{DedupPolicy, TaskPriority};
 use serde::de::DeserializeOwned;
 use std::any::type_name;
 use std::fmt::Debug;
@@ -11,7 +11,10 @@ use tracing::{debug, info, trace, warn};
 
 use crate::task::Task;
 
+mod payload;
 mod recurring;
+
+pub use self::payload::{InvalidPayloadKeyError, PayloadCipher, PayloadCipherError};
 pub use self::recurring::RecurringTask;
 
 /// Responsible for keeping all registered metadata of tasks
@@ -20,13 +23,21 @@ pub struct TaskRegistry<S> {
     // We want to keep it read-only so that we don't have to use
     // Mutex to access a list of registered recurring tasks.
     recurring_tasks: RwLock<Vec<Arc<RecurringTask>>>,
+    cipher: Arc<PayloadCipher>,
 }
 
 impl<S: Clone + Send + Sync + 'static> TaskRegistry<S> {
     pub fn new() -> Self {
+        Self::with_cipher(PayloadCipher::disabled())
+    }
+
+    /// Creates a registry that decrypts the data of tasks with
+    /// `cipher` before deserializing them.
+    pub fn with_cipher(cipher: PayloadCipher) -> Self {
         Self {
             items: Arc::new(DashMap::new()),
             recurring_tasks: RwLock::new(Vec::new()),
+            cipher: Arc::new(cipher),
         }
     }
 
@@ -42,13 +53,17 @@ impl<S: Clone + Send + Sync + 'static> TaskRegistry<S> {
         );
         trace!("registered task {type_name:?} ({kind})");
 
-        let deserializer: DeserializerFn<S> = Box::new(|value| {
+        let cipher = self.cipher.clone();
+        let deserializer: DeserializerFn<S> = Box::new(move |value| {
+            let value = cipher.open(kind, value).map_err(serde::de::Error::custom)?;
+
             let task: T = serde_json::from_value(value)?;
             Ok(Box::new(task))
         });
 
         let is_recurring = T::trigger().is_recurring();
         let item: RegistryItem<S> = RegistryItem {
+            dedup_policy: T::dedup_policy(),
             deserializer,
             kind,
             is_recurring,
@@ -78,6 +93,16 @@ impl<S: Clone + Send + Sync + 'static> TaskRegistry<S> {
     pub fn is_task_registered<T: Task<State = S>>(&self) -> bool {
         self.items.contains_key(T::kind())
     }
+
+    /// Encrypts the data of a task before it is stored in the
+    /// database, if a payload key is configured.
+    pub(crate) fn seal_payload(
+        &self,
+        kind: &str,
+        data: serde_json::Value,
+    ) -> Result<serde_json::Value, PayloadCipherError> {
+        self.cipher.seal(kind, data)
+    }
 }
 
 impl<S: Clone + Send + Sync + 'static> TaskRegistry<S> {
@@ -155,11 +180,13 @@ impl<S: Clone + Send + Sync + 'static> Debug for TaskRegistry<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TaskRegistry")
             .field("items", &self.items.len())
+            .field("cipher", &self.cipher)
             .finish()
     }
 }
 
 pub struct RegistryItem<S> {
+    pub(crate) dedup_policy: DedupPolicy,
     pub(crate) deserializer: DeserializerFn<S>,
     pub(crate) kind: &'static str,
     pub(crate) is_recurring: bool,
@@ -0,0 +1,166 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::Value;
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// Key of the object that holds the encrypted data of a task.
+const ENCRYPTED_KEY: &str = "$encrypted";
+
+#[derive(Debug, Error)]
+#[error("invalid task payload key")]
+pub struct InvalidPayloadKeyError;
+
+#[derive(Debug, Error)]
+#[error("could not encrypt or decrypt task data")]
+pub struct PayloadCipherError;
+
+/// Encrypts the data of tasks before they're stored in the database
+/// and decrypts them before they're deserialized, if a payload key
+/// is configured.
+///
+/// Encrypted data is stored as `{ "$encrypted": "<blob>" }` where the
+/// blob is the random nonce followed by the ciphertext (with its tag),
+/// encoded in base64. The task type is used as the associated data, so
+/// the data of one task type cannot be passed as another.
+///
+/// Data that is not encrypted is left as is, so tasks stored before
+/// the key is configured can still be performed.
+pub struct PayloadCipher(Option<LessSafeKey>);
+
+impl PayloadCipher {
+    #[must_use]
+    pub const fn disabled() -> Self {
+        Self(None)
+    }
+
+    /// Payload keys are 32 bytes encoded in base64.
+    pub fn new(key: Option<&str>) -> Result<Self, InvalidPayloadKeyError> {
+        let Some(key) = key else {
+            return Ok(Self::disabled());
+        };
+
+        BASE64
+            .decode(key.trim())
+            .ok()
+            .and_then(|bytes| UnboundKey::new(&AES_256_GCM, &bytes).ok())
+            .map(|key| Self(Some(LessSafeKey::new(key))))
+            .ok_or(InvalidPayloadKeyError)
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn seal(&self, kind: &str, data: Value) -> Result<Value, PayloadCipherError> {
+        let Some(key) = self.0.as_ref() else {
+            return Ok(data);
+        };
+
+        // recurring tasks have no data to protect
+        if data.is_null() {
+            return Ok(data);
+        }
+
+        let mut nonce = [0; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| PayloadCipherError)?;
+
+        let mut ciphertext = serde_json::to_vec(&data).map_err(|_| PayloadCipherError)?;
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(kind.as_bytes()),
+            &mut ciphertext,
+        )
+        .map_err(|_| PayloadCipherError)?;
+
+        let mut blob = nonce.to_vec();
+        blob.append(&mut ciphertext);
+
+        let mut object = serde_json::Map::new();
+        object.insert(ENCRYPTED_KEY.into(), Value::String(BASE64.encode(blob)));
+        Ok(Value::Object(object))
+    }
+
+    pub fn open(&self, kind: &str, data: Value) -> Result<Value, PayloadCipherError> {
+        let Some(blob) = encrypted_blob(&data) else {
+            return Ok(data);
+        };
+
+        // the key may have been removed after the task is stored
+        let key = self.0.as_ref().ok_or(PayloadCipherError)?;
+        let mut blob = BASE64.decode(blob).map_err(|_| PayloadCipherError)?;
+        if blob.len() < NONCE_LEN {
+            return Err(PayloadCipherError);
+        }
+
+        let mut ciphertext = blob.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&blob).map_err(|_| PayloadCipherError)?;
+        let plaintext = key
+            .open_in_place(nonce, Aad::from(kind.as_bytes()), &mut ciphertext)
+            .map_err(|_| PayloadCipherError)?;
+
+        serde_json::from_slice(plaintext).map_err(|_| PayloadCipherError)
+    }
+}
+
+impl Debug for PayloadCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadCipher")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+fn encrypted_blob(data: &Value) -> Option<&str> {
+    let object = data.as_object()?;
+    if object.len() != 1 {
+        return None;
+    }
+    object.get(ENCRYPTED_KEY)?.as_str()
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const KEY: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    #[test]
+    fn should_encrypt_task_data() {
+        let cipher = PayloadCipher::new(Some(KEY)).unwrap();
+        let data = json!({ "message": "hello", "amount": 5 });
+
+        let sealed = cipher.seal("send_dm", data.clone()).unwrap();
+        assert!(encrypted_blob(&sealed).is_some());
+        assert!(!sealed.to_string().contains("hello"));
+
+        assert_eq!(cipher.open("send_dm", sealed.clone()).unwrap(), data);
+        assert!(cipher.open("other_task", sealed.clone()).is_err());
+        assert!(PayloadCipher::disabled().open("send_dm", sealed).is_err());
+    }
+
+    #[test]
+    fn should_leave_unencrypted_data_alone() {
+        let cipher = PayloadCipher::new(Some(KEY)).unwrap();
+        assert_eq!(cipher.seal("tick", Value::Null).unwrap(), Value::Null);
+
+        let data = json!({ "message": "hello" });
+        assert_eq!(cipher.open("send_dm", data.clone()).unwrap(), data);
+
+        let disabled = PayloadCipher::disabled();
+        assert_eq!(disabled.seal("send_dm", data.clone()).unwrap(), data);
+    }
+
+    #[test]
+    fn should_reject_invalid_keys() {
+        assert!(PayloadCipher::new(Some("not base64")).is_err());
+        assert!(PayloadCipher::new(Some("AAEC")).is_err());
+    }
+}
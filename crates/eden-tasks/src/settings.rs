@@ -1,15 +1,24 @@
-use chrono::TimeDelta;
 use doku::Document;
 use eden_tasks_schema::types::WorkerId;
+use eden_utils::types::{HumanDuration, ProtectedString};
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
-use std::num::{NonZeroU64, NonZeroUsize};
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 use typed_builder::TypedBuilder;
 
-#[serde_as]
+use crate::registry::PayloadCipher;
+
 #[derive(Debug, Deserialize, Document, Serialize, TypedBuilder)]
 #[serde(default)]
 pub struct Settings {
+    /// Amount of consecutive failures of a specific task type before
+    /// Eden alerts the administrators about it. Eden will also notify
+    /// them once the task type completes successfully again.
+    ///
+    /// It defaults to `3` failures if not set.
+    #[doku(as = "u32", example = "3")]
+    #[builder(default = NonZeroU32::new(3).unwrap())]
+    pub failure_streak_threshold: NonZeroU32,
+
     /// Assigned queue worker ID. This field allows for the entire
     /// workers to equally distribute tasks based on their worker ID
     /// without any conflicts.
@@ -37,6 +46,32 @@ pub struct Settings {
     #[builder(default = 3)]
     pub max_task_retries: u16,
 
+    /// Minimum amount of tasks running at the same time.
+    ///
+    /// If it is set, Eden will adjust how many tasks can run at the same
+    /// time (between this and `max_running_tasks`) depending on how many
+    /// tasks are waiting to run and how long tasks take to complete.
+    ///
+    /// It is not set by default, so `max_running_tasks` is always used.
+    #[doku(as = "usize", example = "2")]
+    #[builder(default)]
+    pub min_running_tasks: Option<NonZeroUsize>,
+
+    /// Key used to encrypt the data of tasks before they're stored
+    /// in the database, since it may contain user data (like DM text).
+    /// It must be 32 bytes encoded in base64, which can be generated
+    /// with `cargo xtask secret key`.
+    ///
+    /// **DO NOT SHARE THIS KEY TO ANYONE!** Tasks encrypted with it
+    /// cannot be performed anymore once the key is changed or removed.
+    ///
+    /// Tasks stored before the key is set can still be performed.
+    ///
+    /// If it is not set, the data of tasks is stored as is.
+    #[doku(as = "String", example = "<insert key here>")]
+    #[builder(default, setter(into, strip_option))]
+    pub payload_key: Option<ProtectedString>,
+
     /// Processes a specified number of queued tasks in a batch and waits
     /// for all them to complete before proceeding to another batch of
     /// queued tasks.
@@ -50,21 +85,32 @@ pub struct Settings {
     /// tasks stalled and must be requeued again.
     ///
     /// It defaults to `30 minutes` if not set.
-    #[doku(as = "String", example = "30m")]
-    #[serde_as(as = "eden_utils::serial::AsHumanDuration")]
-    #[builder(default = TimeDelta::minutes(30))]
-    pub stalled_tasks_threshold: TimeDelta,
+    #[doku(example = "30m")]
+    #[builder(default = HumanDuration::from_mins(30))]
+    pub stalled_tasks_threshold: HumanDuration,
 }
 
 impl Default for Settings {
     #[allow(clippy::unwrap_used)]
     fn default() -> Self {
         Self {
+            failure_streak_threshold: NonZeroU32::new(3).unwrap(),
             id: WorkerId::ONE,
             max_running_tasks: NonZeroUsize::new(10).unwrap(),
             max_task_retries: 3,
+            min_running_tasks: None,
+            payload_key: None,
             queued_tasks_per_batch: NonZeroU64::new(50).unwrap(),
-            stalled_tasks_threshold: TimeDelta::minutes(30),
+            stalled_tasks_threshold: HumanDuration::from_mins(30),
         }
     }
 }
+
+impl Settings {
+    /// Whether `payload_key` is not set or is a valid key.
+    #[must_use]
+    pub fn has_valid_payload_key(&self) -> bool {
+        let key = self.payload_key.as_ref().map(ProtectedString::expose);
+        PayloadCipher::new(key).is_ok()
+    }
+}
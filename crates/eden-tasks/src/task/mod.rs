@@ -4,7 +4,7 @@ mod trigger;
 pub use self::run_context::TaskRunContext;
 pub use self::trigger::*;
 
-pub use eden_tasks_schema::types::{TaskPriority, TaskStatus};
+pub use eden_tasks_schema::types::{DedupPolicy, TaskPriority, TaskStatus};
 
 use async_trait::async_trait;
 use chrono::TimeDelta;
@@ -88,6 +88,29 @@ pub trait Task: Debug + Send + Sync + 'static {
         false
     }
 
+    /// Key that tells the same logical task apart from other tasks
+    /// of the same type (like the ID of the user to send a DM to).
+    ///
+    /// Scheduling a task while another pending task of the same type has
+    /// the same key does not queue another one. What happens to the pending
+    /// task depends on [`Task::dedup_policy`].
+    ///
+    /// It defaults to `None` which means that this task is never deduplicated.
+    fn dedup_key(&self) -> Option<String> {
+        None
+    }
+
+    /// What happens to the pending task if the same task (having the same
+    /// [deduplication key](Task::dedup_key)) is scheduled.
+    ///
+    /// It defaults to [`DedupPolicy::Ignore`].
+    fn dedup_policy() -> DedupPolicy
+    where
+        Self: Sized,
+    {
+        DedupPolicy::Ignore
+    }
+
     /// The delay before a task is processed again after an error.
     ///
     /// It starts with 1 minute, then 2 minutes and so on.
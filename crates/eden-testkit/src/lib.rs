@@ -0,0 +1,229 @@
+//! Fakes of Eden's bot, task queue and contexts to test features and
+//! commands without connecting to Discord or the database.
+//!
+//! ```ignore
+//! let bot = FakeBot::new();
+//! let ctx = bot.command_context(fake_command_data("ping"));
+//! // run the command with `ctx`...
+//!
+//! let task = bot.queue().assert_scheduled::<RemoveTempRole>();
+//! ```
+use eden_bot::features::FeatureEvent;
+use eden_bot::testkit::{CommandContext, EventContext};
+use eden_bot::util::event_bus::Recorder;
+use eden_bot::{Bot, BotRef};
+use eden_settings::Settings;
+use eden_tasks::queue_worker::ScheduledTask;
+use eden_tasks::{QueueWorker, Task};
+use serde::de::DeserializeOwned;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+use twilight_model::application::interaction::application_command::CommandData;
+
+pub use eden_bot::testkit::{
+    generate_fake_command_data as fake_command_data,
+    generate_fake_command_interaction as fake_command_interaction,
+    generate_fake_settings as fake_settings, FAKE_CHANNEL_ID, FAKE_GUILD_ID, FAKE_USER_ID,
+};
+
+/// How long [`FakeBot::assert_published`] waits for an event.
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Eden's bot with [fake settings](fake_settings) that records every
+/// event it publishes and keeps every task it schedules in memory.
+///
+/// It does not connect to the database until a query is made.
+#[derive(Debug)]
+pub struct FakeBot {
+    bot: Bot,
+    events: Recorder<FeatureEvent>,
+}
+
+impl FakeBot {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_settings(fake_settings())
+    }
+
+    /// Creates a fake bot with custom settings.
+    ///
+    /// It panics if the bot cannot be created with the settings.
+    #[allow(clippy::unwrap_used)]
+    #[must_use]
+    pub fn with_settings(settings: Settings) -> Self {
+        let bot = Bot::new(Arc::new(settings)).unwrap();
+        bot.queue.record_scheduled();
+
+        let events = eden_bot::testkit::record_events(&bot);
+        Self { bot, events }
+    }
+
+    #[must_use]
+    pub fn bot(&self) -> &Bot {
+        &self.bot
+    }
+
+    /// Tasks scheduled by the bot.
+    #[must_use]
+    pub fn queue(&self) -> FakeQueue {
+        FakeQueue {
+            queue: self.bot.queue.clone(),
+        }
+    }
+
+    /// Creates an event context with a shard that never connects
+    /// to the Discord gateway.
+    #[must_use]
+    pub fn event_context(&self) -> EventContext {
+        eden_bot::testkit::generate_fake_event_context(self.bot.clone())
+    }
+
+    /// Creates a command context as if [`FAKE_USER_ID`] invoked
+    /// the command in [`FAKE_CHANNEL_ID`] of the local guild.
+    #[must_use]
+    pub fn command_context(&self, data: CommandData) -> CommandContext {
+        let ctx = self.event_context();
+        eden_bot::testkit::generate_fake_command_context(&ctx, data)
+    }
+
+    /// Events published by the bot so far, from the oldest to the newest.
+    pub fn events(&mut self) -> &[FeatureEvent] {
+        self.events.drain()
+    }
+
+    /// Waits until the bot publishes an event that matches the
+    /// predicate (or has published it already).
+    ///
+    /// It panics if no matching event is published within 5 seconds.
+    pub async fn assert_published<F>(&mut self, predicate: F) -> FeatureEvent
+    where
+        F: FnMut(&FeatureEvent) -> bool,
+    {
+        match self.events.wait_for(PUBLISH_TIMEOUT, predicate).await {
+            Some(event) => event,
+            None => panic!(
+                "expected event to be published, got: {:?}",
+                self.events.events()
+            ),
+        }
+    }
+}
+
+impl Default for FakeBot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for FakeBot {
+    type Target = Bot;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bot
+    }
+}
+
+/// Tasks scheduled by a [`FakeBot`] which are kept in memory
+/// instead of storing them into the database.
+#[derive(Debug, Clone)]
+pub struct FakeQueue {
+    queue: QueueWorker<BotRef>,
+}
+
+impl FakeQueue {
+    /// Every scheduled task, from the oldest to the newest.
+    #[must_use]
+    pub fn tasks(&self) -> Vec<ScheduledTask> {
+        self.queue.scheduled_tasks()
+    }
+
+    /// Scheduled tasks of type `T`, from the oldest to the newest.
+    #[must_use]
+    pub fn scheduled<T: Task + DeserializeOwned>(&self) -> Vec<T> {
+        self.tasks()
+            .iter()
+            .filter_map(ScheduledTask::parse)
+            .collect()
+    }
+
+    /// Forgets every scheduled task.
+    pub fn clear(&self) {
+        self.queue.clear_scheduled_tasks();
+    }
+
+    /// Gets the latest scheduled task of type `T`.
+    ///
+    /// It panics if no task of type `T` has been scheduled.
+    #[must_use]
+    pub fn assert_scheduled<T: Task + DeserializeOwned>(&self) -> T {
+        let Some(task) = self.scheduled::<T>().pop() else {
+            panic!(
+                "expected {:?} to be scheduled, got: {:?}",
+                T::kind(),
+                self.kinds()
+            );
+        };
+        task
+    }
+
+    /// It panics if a task of type `T` has been scheduled.
+    pub fn assert_not_scheduled<T: Task + DeserializeOwned>(&self) {
+        let count = self.scheduled::<T>().len();
+        assert!(
+            count == 0,
+            "expected {:?} not to be scheduled, got {count} task(s)",
+            T::kind()
+        );
+    }
+
+    /// It panics if any task has been scheduled.
+    pub fn assert_empty(&self) {
+        let kinds = self.kinds();
+        assert!(
+            kinds.is_empty(),
+            "expected no scheduled tasks, got: {kinds:?}"
+        );
+    }
+
+    fn kinds(&self) -> Vec<String> {
+        self.tasks().into_iter().map(|v| v.kind).collect()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eden_bot::tasks::RemoveTempRole;
+    use eden_tasks::Scheduled;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn should_record_scheduled_tasks() {
+        let bot = FakeBot::new();
+        let queue = bot.queue();
+        queue.assert_empty();
+
+        let grant_id = Uuid::new_v4();
+        let task = RemoveTempRole { grant_id };
+        bot.queue.schedule(task, Scheduled::now()).await.unwrap();
+
+        let task = queue.assert_scheduled::<RemoveTempRole>();
+        assert_eq!(task.grant_id, grant_id);
+        assert_eq!(queue.tasks()[0].scheduled, Scheduled::now());
+
+        queue.clear();
+        queue.assert_not_scheduled::<RemoveTempRole>();
+    }
+
+    #[tokio::test]
+    async fn should_create_command_context() {
+        let bot = FakeBot::new();
+        let ctx = bot.command_context(fake_command_data("ping"));
+        assert_eq!(ctx.channel_id, FAKE_CHANNEL_ID);
+        assert_eq!(ctx.interaction.guild_id, Some(FAKE_GUILD_ID));
+        assert_eq!(ctx.interaction.author_id(), Some(FAKE_USER_ID));
+        assert_eq!(ctx.command_name(), "ping");
+    }
+}
@@ -1,4 +1,5 @@
 use std::error::Error as StdError;
+use std::path::Path;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -9,8 +10,29 @@ use crate::Result;
 #[error("Could not load environment variable")]
 pub struct LoadEnvError;
 
-pub fn init() {
-    dotenvy::dotenv().ok();
+/// Loads variables from the `.env` file in the current directory
+/// (or one of its parents) or from the file at `EDEN_DOTENV` if it is
+/// set. Variables that are already set are not overridden.
+///
+/// The `.env` file is optional unless `EDEN_DOTENV` is set.
+pub fn init() -> Result<(), LoadEnvError> {
+    let Some(path) = std::env::var_os("EDEN_DOTENV") else {
+        return match dotenvy::dotenv() {
+            Ok(..) => Ok(()),
+            Err(error) if error.not_found() => Ok(()),
+            Err(error) => Err(error)
+                .into_typed_error()
+                .change_context(LoadEnvError)
+                .attach_printable("could not load .env file"),
+        };
+    };
+
+    let path = Path::new(&path);
+    dotenvy::from_path(path)
+        .into_typed_error()
+        .change_context(LoadEnvError)
+        .attach_printable_lazy(|| format!("could not load .env file: {}", path.display()))
+        .attach_printable("using `EDEN_DOTENV` variable")
 }
 
 #[track_caller]
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use strum_macros::Display;
 use twilight_model::guild::Permissions;
 
@@ -15,7 +16,11 @@ pub enum ErrorCategory {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum UserErrorCategory {
+    /// Some of the command's arguments are invalid.
+    InvalidArguments,
     MissingPermissions,
+    /// The user has to wait until the given time to use the command again.
+    OnCooldown(DateTime<Utc>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
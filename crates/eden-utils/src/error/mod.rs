@@ -174,6 +174,12 @@ impl<T: Context> Error<T> {
     {
         self.report.request_ref::<N>()
     }
+
+    /// Adds another error alongside this error so multiple errors
+    /// can be reported at once.
+    pub fn extend_one(&mut self, error: Self) {
+        self.report.extend_one(error.report);
+    }
 }
 
 impl Error {
@@ -215,7 +221,7 @@ impl Error {
     /// Installs hooks from all errors and tags in [`eden_utils`](crate)
     /// and sets up preferences from [`error_stack`] tailored for Eden.
     pub fn init() {
-        use self::tags::Suggestion;
+        use self::tags::{Suggestion, TelemetryTags};
         use crate::sql::tags::{DatabaseErrorType, PostgresErrorInfo};
         use crate::twilight::tags::DiscordHttpErrorInfo;
 
@@ -225,6 +231,7 @@ impl Error {
         Report::set_color_mode(ColorMode::None);
 
         Suggestion::install_hook();
+        TelemetryTags::install_hook();
         DatabaseErrorType::install_hook();
         PostgresErrorInfo::install_hook();
         DiscordHttpErrorInfo::install_hook();
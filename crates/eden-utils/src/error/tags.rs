@@ -1,6 +1,7 @@
 use serde::ser::SerializeMap;
 use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -49,3 +50,96 @@ impl Suggestion {
         });
     }
 }
+
+/// Structured key-value tags attached to an [`Error`](crate::Error)
+/// so errors can be filtered by them from logs and Sentry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TelemetryTags(BTreeMap<&'static str, String>);
+
+impl TelemetryTags {
+    /// Amount of hex characters kept from hashed values.
+    const HASH_LENGTH: usize = 16;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Adds a tag. It replaces the previous value of the tag if it exists.
+    #[must_use]
+    pub fn with(mut self, key: &'static str, value: impl Display) -> Self {
+        self.0.insert(key, value.to_string());
+        self
+    }
+
+    /// Adds a tag with its value [anonymized](crate::hash::anonymize) so
+    /// identifiable values such as guild IDs can still be grouped without
+    /// exposing them.
+    #[must_use]
+    pub fn with_hashed(self, key: &'static str, value: impl Display) -> Self {
+        let mut hash = crate::hash::anonymize::value(value.to_string()).to_string();
+        hash.truncate(Self::HASH_LENGTH);
+        self.with(key, hash)
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.0.iter().map(|(k, v)| (*k, v.as_str()))
+    }
+}
+
+impl Serialize for TelemetryTags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len() + 1))?;
+        map.serialize_entry("_type", "TELEMETRY")?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl TelemetryTags {
+    pub(crate) fn install_hook() {
+        crate::Error::install_serde_hook::<Self>();
+        crate::Error::install_hook::<Self>(move |this, ctx| {
+            let tags = this
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            ctx.push_body(format!("telemetry: {tags}"));
+        });
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_hash_telemetry_tags() {
+        let tags = TelemetryTags::new()
+            .with("command", "settings")
+            .with_hashed("guild", 1234);
+
+        assert_eq!(tags.get("command"), Some("settings"));
+
+        let guild = tags.get("guild").unwrap();
+        assert_eq!(guild.len(), TelemetryTags::HASH_LENGTH);
+        assert_ne!(guild, "1234");
+
+        // hashed values should be consistent to be able to group them
+        let other = TelemetryTags::new().with_hashed("guild", 1234);
+        assert_eq!(other.get("guild"), Some(guild));
+    }
+}
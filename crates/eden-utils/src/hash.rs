@@ -222,6 +222,153 @@ pub mod stream {
     }
 }
 
+pub mod anonymize {
+    use hmac::{Hmac, Mac};
+    use serde::Serialize;
+    use sha2::Sha256;
+    use std::fmt::{Debug, Display};
+    use std::sync::OnceLock;
+    use tracing::warn;
+    use twilight_model::id::Id;
+    use zeroize::Zeroizing;
+
+    static GLOBAL: OnceLock<Anonymizer> = OnceLock::new();
+
+    /// Hashes identifiable values (like Discord snowflakes) with a secret
+    /// so they can be correlated across logs, metrics and Sentry events
+    /// without storing the raw values.
+    ///
+    /// Unlike plain hashes, anonymized values cannot be reversed by hashing
+    /// every possible snowflake unless the secret is known. The same value
+    /// is always anonymized the same way as long as the secret stays the same.
+    pub struct Anonymizer {
+        secret: Zeroizing<Vec<u8>>,
+    }
+
+    impl Anonymizer {
+        #[must_use]
+        pub fn new(secret: impl AsRef<[u8]>) -> Self {
+            Self {
+                secret: Zeroizing::new(secret.as_ref().to_vec()),
+            }
+        }
+
+        /// Installs this anonymizer to be used by [`snowflake`] and [`value`].
+        ///
+        /// It will be ignored if another anonymizer has been installed or
+        /// the global anonymizer has been used before installing one.
+        pub fn install(self) {
+            if GLOBAL.set(self).is_err() {
+                warn!("an anonymizer is already installed, ignoring the new one");
+            }
+        }
+
+        /// Gets the [installed](Self::install) anonymizer.
+        ///
+        /// If there is none installed, a random secret is used instead
+        /// which makes anonymized values change every time Eden restarts.
+        pub fn global() -> &'static Self {
+            GLOBAL.get_or_init(|| {
+                warn!("no anonymizer is installed, using a random secret");
+                Self::new(uuid::Uuid::new_v4().as_bytes())
+            })
+        }
+
+        #[must_use]
+        pub fn anonymize(&self, value: impl AsRef<[u8]>) -> AnonymizedId {
+            #[allow(clippy::expect_used)]
+            let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+                .expect("HMAC should accept secrets of any size");
+
+            mac.update(value.as_ref());
+
+            let hash = mac.finalize().into_bytes();
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(&hash[..8]);
+            AnonymizedId(u64::from_be_bytes(bytes))
+        }
+
+        /// Anonymizes a snowflake from its decimal form, so it is the same
+        /// as anonymizing the displayed ID (like with [`TelemetryTags`]).
+        ///
+        /// [`TelemetryTags`]: crate::error::tags::TelemetryTags
+        #[must_use]
+        pub fn snowflake<T>(&self, id: Id<T>) -> AnonymizedId {
+            self.anonymize(id.get().to_string())
+        }
+    }
+
+    impl Debug for Anonymizer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Anonymizer").finish_non_exhaustive()
+        }
+    }
+
+    /// Anonymizes a snowflake with the [global anonymizer](Anonymizer::global).
+    #[must_use]
+    pub fn snowflake<T>(id: Id<T>) -> AnonymizedId {
+        Anonymizer::global().snowflake(id)
+    }
+
+    /// Anonymizes any value with the [global anonymizer](Anonymizer::global).
+    #[must_use]
+    pub fn value(value: impl AsRef<[u8]>) -> AnonymizedId {
+        Anonymizer::global().anonymize(value)
+    }
+
+    /// Anonymized value from [`Anonymizer`].
+    ///
+    /// It is displayed as a 16 characters long hexadecimal string.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct AnonymizedId(u64);
+
+    impl Debug for AnonymizedId {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "AnonymizedId({self})")
+        }
+    }
+
+    impl Display for AnonymizedId {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:016x}", self.0)
+        }
+    }
+
+    impl Serialize for AnonymizedId {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use twilight_model::id::marker::UserMarker;
+
+        #[test]
+        fn should_anonymize_consistently() {
+            let id = Id::<UserMarker>::new(1234);
+            let anonymizer = Anonymizer::new("secret");
+            assert_eq!(anonymizer.snowflake(id), anonymizer.snowflake(id));
+            assert_eq!(anonymizer.snowflake(id).to_string().len(), 16);
+            assert_ne!(
+                anonymizer.snowflake(id),
+                anonymizer.snowflake(Id::new(1235))
+            );
+
+            // snowflakes are anonymized the same way as displayed IDs
+            assert_eq!(anonymizer.snowflake(id), anonymizer.anonymize("1234"));
+
+            // values must not be correlated across different secrets
+            let other = Anonymizer::new("other secret");
+            assert_ne!(anonymizer.snowflake(id), other.snowflake(id));
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("Could not get content hash")]
 pub struct HashError;
@@ -16,6 +16,7 @@ pub mod build;
 pub mod env;
 pub mod error;
 pub mod time;
+pub mod trace;
 pub mod types;
 pub mod vec;
 
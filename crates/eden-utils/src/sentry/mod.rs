@@ -6,8 +6,9 @@ use std::panic::Location;
 use tracing::warn;
 
 use crate::{
-    error::{GuildErrorCategory, UserErrorCategory},
+    error::{tags::TelemetryTags, GuildErrorCategory, UserErrorCategory},
     sql::SqlErrorExt,
+    trace::TraceId,
     twilight::{error::TwilightHttpErrorExt, tags::DiscordHttpErrorInfo},
     Error, ErrorCategory,
 };
@@ -52,6 +53,20 @@ fn event_from_error<C>(error: &Error<C>) -> Event<'static> {
         );
     }
 
+    // allow errors to be filtered by their telemetry tags in Sentry
+    for tags in error.report.request_ref::<TelemetryTags>() {
+        for (key, value) in tags.iter() {
+            event.tags.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    // ties the event to the logs of the request where it happened
+    if let Some(trace_id) = TraceId::current() {
+        event
+            .tags
+            .insert(crate::trace::FIELD_NAME.into(), trace_id.to_string());
+    }
+
     event.exception = exceptions.into();
     event.level = sentry::Level::Error;
     event.extra = extra;
@@ -154,9 +169,15 @@ fn category_to_exception_type<C>(error: &Error<C>) -> String {
             }
         },
         ErrorCategory::User(cat) => match cat {
+            UserErrorCategory::InvalidArguments => {
+                format!("User gave invalid arguments")
+            }
             UserErrorCategory::MissingPermissions => {
                 format!("User tried to perform with insufficient permissions")
             }
+            UserErrorCategory::OnCooldown(..) => {
+                format!("User tried to perform while on cooldown")
+            }
         },
         ErrorCategory::Unknown => "Error".into(),
     }
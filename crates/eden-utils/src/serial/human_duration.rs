@@ -1,8 +1,22 @@
 use chrono::TimeDelta;
-use fundu::DurationParser;
+use fundu::{DurationParser, TimeUnit};
 use serde_with::{DeserializeAs, SerializeAs};
 use std::time::Duration as StdDuration;
 
+/// Parser of human readable durations (like `30s`, `5m` or `1h`)
+/// shared by every human duration type in Eden.
+pub(crate) const PARSER: DurationParser<'static> = DurationParser::builder()
+    .time_units(&[
+        TimeUnit::MilliSecond,
+        TimeUnit::Second,
+        TimeUnit::Minute,
+        TimeUnit::Hour,
+        TimeUnit::Day,
+    ])
+    .allow_time_unit_delimiter()
+    .disable_exponent()
+    .build();
+
 pub struct AsHumanDuration;
 
 struct StdVisitor;
@@ -18,21 +32,8 @@ impl<'de> serde::de::Visitor<'de> for StdVisitor {
     where
         E: serde::de::Error,
     {
-        use fundu::TimeUnit;
         use serde::de::Error as DeError;
 
-        const PARSER: DurationParser<'static> = DurationParser::builder()
-            .time_units(&[
-                TimeUnit::MilliSecond,
-                TimeUnit::Second,
-                TimeUnit::Minute,
-                TimeUnit::Hour,
-                TimeUnit::Day,
-            ])
-            .allow_time_unit_delimiter()
-            .disable_exponent()
-            .build();
-
         let parsed = PARSER.parse(v).map_err(DeError::custom)?;
         StdDuration::try_from(parsed).map_err(DeError::custom)
     }
@@ -51,21 +52,8 @@ impl<'de> serde::de::Visitor<'de> for ChronoVisitor {
     where
         E: serde::de::Error,
     {
-        use fundu::TimeUnit;
         use serde::de::Error as DeError;
 
-        const PARSER: DurationParser<'static> = DurationParser::builder()
-            .time_units(&[
-                TimeUnit::MilliSecond,
-                TimeUnit::Second,
-                TimeUnit::Minute,
-                TimeUnit::Hour,
-                TimeUnit::Day,
-            ])
-            .allow_time_unit_delimiter()
-            .disable_exponent()
-            .build();
-
         let parsed = PARSER.parse(v).map_err(DeError::custom)?;
         TimeDelta::try_from(parsed).map_err(DeError::custom)
     }
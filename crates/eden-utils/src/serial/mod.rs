@@ -1,2 +1,4 @@
 mod human_duration;
 pub use self::human_duration::*;
+
+pub(crate) use self::human_duration::PARSER;
@@ -32,3 +32,118 @@ impl InstantExt for Instant {
         DateTime::<Utc>::from(starting_time)
     }
 }
+
+/// Parses a human-readable duration like `30m`, `12h` or `1w 2d`.
+///
+/// Supported units are `w` (weeks), `d` (days), `h` (hours),
+/// `m` (minutes) and `s` (seconds). It returns `None` if the input
+/// is invalid or the total duration is zero.
+#[must_use]
+pub fn parse_time_delta(input: &str) -> Option<TimeDelta> {
+    let mut total = TimeDelta::zero();
+    let mut number = String::new();
+
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let value = number.parse::<i64>().ok()?;
+        number.clear();
+
+        let delta = match c.to_ascii_lowercase() {
+            'w' => TimeDelta::try_weeks(value),
+            'd' => TimeDelta::try_days(value),
+            'h' => TimeDelta::try_hours(value),
+            'm' => TimeDelta::try_minutes(value),
+            's' => TimeDelta::try_seconds(value),
+            _ => None,
+        }?;
+        total = total.checked_add(&delta)?;
+    }
+
+    // trailing numbers without any units are not allowed
+    if !number.is_empty() || total <= TimeDelta::zero() {
+        return None;
+    }
+
+    Some(total)
+}
+
+/// How Discord should display a timestamp from [`discord_timestamp`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimestampStyle {
+    /// `16:20`
+    ShortTime,
+    /// `16:20:30`
+    LongTime,
+    /// `20/04/2021`
+    ShortDate,
+    /// `20 April 2021`
+    LongDate,
+    /// `20 April 2021 16:20`
+    ShortDateTime,
+    /// `Tuesday, 20 April 2021 16:20`
+    LongDateTime,
+    /// `in 3 days` or `2 months ago`
+    Relative,
+}
+
+impl TimestampStyle {
+    #[must_use]
+    pub const fn as_char(self) -> char {
+        match self {
+            Self::ShortTime => 't',
+            Self::LongTime => 'T',
+            Self::ShortDate => 'd',
+            Self::LongDate => 'D',
+            Self::ShortDateTime => 'f',
+            Self::LongDateTime => 'F',
+            Self::Relative => 'R',
+        }
+    }
+}
+
+/// Formats the date and time into Discord's timestamp markup (like
+/// `<t:1618953630:R>`) which will be displayed in the reader's time zone.
+#[must_use]
+pub fn discord_timestamp(dt: DateTime<Utc>, style: TimestampStyle) -> String {
+    format!("<t:{}:{}>", dt.timestamp(), style.as_char())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_format_discord_timestamp() {
+        let dt = DateTime::from_timestamp(1_618_953_630, 0).unwrap_or_default();
+        assert_eq!(
+            discord_timestamp(dt, TimestampStyle::Relative),
+            "<t:1618953630:R>"
+        );
+        assert_eq!(
+            discord_timestamp(dt, TimestampStyle::LongDateTime),
+            "<t:1618953630:F>"
+        );
+    }
+
+    #[test]
+    fn should_parse_time_delta() {
+        assert_eq!(parse_time_delta("30m"), Some(TimeDelta::minutes(30)));
+        assert_eq!(
+            parse_time_delta("1w 2d"),
+            Some(TimeDelta::weeks(1) + TimeDelta::days(2))
+        );
+        assert_eq!(
+            parse_time_delta("1H30M"),
+            Some(TimeDelta::hours(1) + TimeDelta::minutes(30))
+        );
+        assert_eq!(parse_time_delta(""), None);
+        assert_eq!(parse_time_delta("0d"), None);
+        assert_eq!(parse_time_delta("30"), None);
+        assert_eq!(parse_time_delta("d"), None);
+        assert_eq!(parse_time_delta("5y"), None);
+    }
+}
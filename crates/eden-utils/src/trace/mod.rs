@@ -0,0 +1,90 @@
+use std::fmt::{Debug, Display};
+use std::future::Future;
+use std::str::FromStr;
+
+pub mod sampling;
+
+tokio::task_local! {
+    static CURRENT: TraceId;
+}
+
+/// Name of the span field where the trace ID is recorded, so every
+/// log line emitted while handling a request can be looked up by it.
+pub const FIELD_NAME: &str = "trace_id";
+
+/// Identifies every log line and error emitted while Eden handles
+/// a single request (like an interaction).
+///
+/// It is displayed as a 16 characters long hexadecimal string.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceId(u64);
+
+impl TraceId {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(uuid::Uuid::new_v4().as_u64_pair().0)
+    }
+
+    /// Gets the trace ID of the request currently being handled.
+    #[must_use]
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|v| *v).ok()
+    }
+
+    /// Runs `future` with this trace ID as the [current](Self::current)
+    /// trace ID and records it into the current span.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        let span = tracing::Span::current();
+        if !span.is_disabled() {
+            span.record(FIELD_NAME, tracing::field::display(self));
+        }
+        CURRENT.scope(self, future).await
+    }
+}
+
+impl Default for TraceId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for TraceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TraceId({self})")
+    }
+}
+
+impl Display for TraceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl FromStr for TraceId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(s.trim(), 16).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_display_and_parse() {
+        let id = TraceId(0xbeef);
+        assert_eq!(id.to_string(), "000000000000beef");
+        assert_eq!("000000000000beef".parse::<TraceId>(), Ok(id));
+    }
+
+    #[tokio::test]
+    async fn should_scope_current_trace_id() {
+        assert_eq!(TraceId::current(), None);
+
+        let id = TraceId::new();
+        let current = id.scope(async { TraceId::current() }).await;
+        assert_eq!(current, Some(id));
+    }
+}
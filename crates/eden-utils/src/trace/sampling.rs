@@ -0,0 +1,258 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
+use tracing::warn;
+
+static GLOBAL: OnceLock<SpanSampler> = OnceLock::new();
+
+const PER_MILLION: u64 = 1_000_000;
+
+/// Decides which spans of hot paths (like handling every message sent)
+/// are recorded, so instrumenting them does not get too costly
+/// on big guilds.
+///
+/// Spans are matched by their full path (`<target>::<name>`, like
+/// `eden_bot::events::message_create::handle`) or by their target
+/// to match every span of a module. Spans without any matching rule
+/// are always recorded.
+///
+/// Changing the rules rebuilds the interest cache of every callsite,
+/// so only spans with a matching rule have to be sampled each time.
+#[derive(Debug)]
+pub struct SpanSampler {
+    enabled: AtomicBool,
+    rules: RwLock<Vec<SamplingRule>>,
+}
+
+#[derive(Debug)]
+struct SamplingRule {
+    span: String,
+    percentage: f64,
+    per_million: u64,
+    seen: AtomicU64,
+}
+
+impl SamplingRule {
+    fn new(span: String, percentage: f64) -> Self {
+        let percentage = percentage.clamp(0., 100.);
+
+        // the percentage is already clamped
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let per_million = (percentage * 10_000.).round() as u64;
+
+        Self {
+            span,
+            percentage,
+            per_million,
+            seen: AtomicU64::new(0),
+        }
+    }
+}
+
+impl SpanSampler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+            rules: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Gets the span sampler used by Eden's logger.
+    #[must_use]
+    pub fn global() -> &'static Self {
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Whether spans are sampled. Every span is recorded
+    /// if it is turned off.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, value: bool) {
+        if self.enabled.swap(value, Ordering::Relaxed) != value {
+            tracing::callsite::rebuild_interest_cache();
+        }
+    }
+
+    /// Whether any span is sampled by a rule.
+    #[must_use]
+    pub fn has_rules(&self) -> bool {
+        self.is_enabled() && !self.read_rules().is_empty()
+    }
+
+    /// Whether the span with the given target and name is
+    /// sampled by a rule.
+    #[must_use]
+    pub fn has_rule(&self, target: &str, name: &str) -> bool {
+        self.is_enabled() && find_rule(&self.read_rules(), target, name).is_some()
+    }
+
+    /// Gets every sampling rule (span and the percentage
+    /// of it being recorded) sorted by span.
+    #[must_use]
+    pub fn rules(&self) -> Vec<(String, f64)> {
+        let rules = self.read_rules();
+        let mut rules = rules
+            .iter()
+            .map(|v| (v.span.clone(), v.percentage))
+            .collect::<Vec<_>>();
+
+        rules.sort_by(|a, b| a.0.cmp(&b.0));
+        rules
+    }
+
+    /// Records only the given percentage (from 0 to 100) of spans
+    /// matching `span`, replacing the previous rule if there's any.
+    pub fn set(&self, span: impl Into<String>, percentage: f64) {
+        let rule = SamplingRule::new(span.into(), percentage);
+        let mut rules = self.write_rules();
+        rules.retain(|v| v.span != rule.span);
+        rules.push(rule);
+        drop(rules);
+
+        tracing::callsite::rebuild_interest_cache();
+    }
+
+    /// Removes the sampling rule of `span`. It returns false
+    /// if there's no rule for it.
+    pub fn remove(&self, span: &str) -> bool {
+        let mut rules = self.write_rules();
+        let len = rules.len();
+        rules.retain(|v| v.span != span);
+
+        let removed = rules.len() != len;
+        drop(rules);
+
+        if removed {
+            tracing::callsite::rebuild_interest_cache();
+        }
+        removed
+    }
+
+    /// Replaces every sampling rule with the given ones.
+    pub fn replace_all<I, T>(&self, rules: I)
+    where
+        I: IntoIterator<Item = (T, f64)>,
+        T: Into<String>,
+    {
+        let rules = rules
+            .into_iter()
+            .map(|(span, percentage)| SamplingRule::new(span.into(), percentage))
+            .collect();
+
+        *self.write_rules() = rules;
+        tracing::callsite::rebuild_interest_cache();
+    }
+
+    /// Whether the span with the given target and name should be
+    /// recorded. Rules with a full path take precedence over rules
+    /// matching their target.
+    ///
+    /// Spans are recorded evenly, so the first span is always recorded
+    /// and one in every 100 spans is recorded if it is set to 1%.
+    #[must_use]
+    pub fn should_record(&self, target: &str, name: &str) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+
+        let rules = self.read_rules();
+        let Some(rule) = find_rule(&rules, target, name) else {
+            return true;
+        };
+
+        // integers are used to avoid rounding errors of floats
+        let seen = rule.seen.fetch_add(1, Ordering::Relaxed);
+        let recorded = |seen: u64| (seen * rule.per_million).div_ceil(PER_MILLION);
+        recorded(seen + 1) > recorded(seen)
+    }
+
+    fn read_rules(&self) -> std::sync::RwLockReadGuard<'_, Vec<SamplingRule>> {
+        self.rules.read().unwrap_or_else(|error| {
+            warn!("span sampling rules are poisoned");
+            error.into_inner()
+        })
+    }
+
+    fn write_rules(&self) -> std::sync::RwLockWriteGuard<'_, Vec<SamplingRule>> {
+        self.rules.write().unwrap_or_else(|error| {
+            warn!("span sampling rules are poisoned");
+            error.into_inner()
+        })
+    }
+}
+
+impl Default for SpanSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn find_rule<'a>(rules: &'a [SamplingRule], target: &str, name: &str) -> Option<&'a SamplingRule> {
+    rules
+        .iter()
+        .find(|v| matches_path(&v.span, target, Some(name)))
+        .or_else(|| rules.iter().find(|v| matches_path(&v.span, target, None)))
+}
+
+fn matches_path(span: &str, target: &str, name: Option<&str>) -> bool {
+    let Some(name) = name else {
+        return span == target;
+    };
+
+    span.strip_prefix(target)
+        .and_then(|v| v.strip_prefix("::"))
+        .is_some_and(|v| v == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: &str = "eden_bot::events::message_create";
+
+    fn count_recorded(sampler: &SpanSampler, name: &str, spans: usize) -> usize {
+        (0..spans)
+            .filter(|_| sampler.should_record(TARGET, name))
+            .count()
+    }
+
+    #[test]
+    fn should_sample_evenly() {
+        let sampler = SpanSampler::new();
+        assert_eq!(count_recorded(&sampler, "handle", 100), 100);
+
+        sampler.set(format!("{TARGET}::handle"), 1.);
+        assert!(sampler.should_record(TARGET, "handle"));
+        assert_eq!(count_recorded(&sampler, "handle", 99), 0);
+        assert_eq!(count_recorded(&sampler, "handle", 1000), 10);
+
+        sampler.set(format!("{TARGET}::handle"), 0.);
+        assert_eq!(count_recorded(&sampler, "handle", 100), 0);
+
+        sampler.set_enabled(false);
+        assert_eq!(count_recorded(&sampler, "handle", 100), 100);
+    }
+
+    #[test]
+    fn should_prefer_full_path_over_target() {
+        let sampler = SpanSampler::new();
+        sampler.replace_all([
+            (TARGET, 0.),
+            ("eden_bot::events::message_create::handle", 100.),
+        ]);
+
+        assert_eq!(count_recorded(&sampler, "handle", 10), 10);
+        assert_eq!(count_recorded(&sampler, "other", 10), 0);
+        assert!(sampler.should_record("eden_bot::events", "handle_event"));
+
+        assert!(sampler.has_rule(TARGET, "other"));
+        assert!(!sampler.has_rule("eden_bot::events", "handle_event"));
+
+        assert!(sampler.remove(TARGET));
+        assert!(!sampler.remove(TARGET));
+        assert_eq!(count_recorded(&sampler, "other", 10), 10);
+    }
+}
@@ -0,0 +1,201 @@
+use serde::Serialize;
+
+/// Common JSON error codes returned from Discord API.
+///
+/// Refer to: https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(into = "u64")]
+pub enum DiscordJsonErrorCode {
+    /// General error (such as a malformed request body, amongst other things)
+    General,
+    UnknownChannel,
+    UnknownGuild,
+    UnknownMember,
+    UnknownMessage,
+    UnknownRole,
+    UnknownUser,
+    UnknownEmoji,
+    UnknownWebhook,
+    UnknownInteraction,
+    /// Maximum number of pins reached for the channel
+    MaxPins,
+    /// Maximum number of guild roles reached
+    MaxRoles,
+    /// Maximum number of reactions reached
+    MaxReactions,
+    /// Maximum number of guild channels reached
+    MaxChannels,
+    /// Interaction has already been acknowledged
+    InteractionAlreadyAcknowledged,
+    /// Missing access to the resource (usually a channel)
+    MissingAccess,
+    /// Cannot execute action on a DM channel
+    CannotExecuteOnDM,
+    /// Cannot send messages to this user
+    CannotDMUser,
+    /// Lacking permissions to perform that action
+    MissingPermissions,
+    /// Invalid authentication token provided
+    InvalidToken,
+    /// Invalid form body or invalid `Content-Type` provided
+    InvalidFormBody,
+    /// Reaction was blocked
+    ReactionBlocked,
+    /// The resource is overloaded
+    ResourceOverloaded,
+    /// Any JSON error code that is not covered above.
+    Other(u64),
+}
+
+impl DiscordJsonErrorCode {
+    #[must_use]
+    pub fn from_code(code: u64) -> Self {
+        match code {
+            0 => Self::General,
+            10003 => Self::UnknownChannel,
+            10004 => Self::UnknownGuild,
+            10007 => Self::UnknownMember,
+            10008 => Self::UnknownMessage,
+            10011 => Self::UnknownRole,
+            10013 => Self::UnknownUser,
+            10014 => Self::UnknownEmoji,
+            10015 => Self::UnknownWebhook,
+            10062 => Self::UnknownInteraction,
+            30003 => Self::MaxPins,
+            30005 => Self::MaxRoles,
+            30010 => Self::MaxReactions,
+            30013 => Self::MaxChannels,
+            40060 => Self::InteractionAlreadyAcknowledged,
+            50001 => Self::MissingAccess,
+            50003 => Self::CannotExecuteOnDM,
+            50007 => Self::CannotDMUser,
+            50013 => Self::MissingPermissions,
+            50014 => Self::InvalidToken,
+            50035 => Self::InvalidFormBody,
+            90001 => Self::ReactionBlocked,
+            130_000 => Self::ResourceOverloaded,
+            n => Self::Other(n),
+        }
+    }
+
+    #[must_use]
+    pub fn code(&self) -> u64 {
+        match self {
+            Self::General => 0,
+            Self::UnknownChannel => 10003,
+            Self::UnknownGuild => 10004,
+            Self::UnknownMember => 10007,
+            Self::UnknownMessage => 10008,
+            Self::UnknownRole => 10011,
+            Self::UnknownUser => 10013,
+            Self::UnknownEmoji => 10014,
+            Self::UnknownWebhook => 10015,
+            Self::UnknownInteraction => 10062,
+            Self::MaxPins => 30003,
+            Self::MaxRoles => 30005,
+            Self::MaxReactions => 30010,
+            Self::MaxChannels => 30013,
+            Self::InteractionAlreadyAcknowledged => 40060,
+            Self::MissingAccess => 50001,
+            Self::CannotExecuteOnDM => 50003,
+            Self::CannotDMUser => 50007,
+            Self::MissingPermissions => 50013,
+            Self::InvalidToken => 50014,
+            Self::InvalidFormBody => 50035,
+            Self::ReactionBlocked => 90001,
+            Self::ResourceOverloaded => 130_000,
+            Self::Other(n) => *n,
+        }
+    }
+
+    /// Whether the requested resource does not exist or has been deleted.
+    #[must_use]
+    pub fn is_unknown_resource(&self) -> bool {
+        matches!(
+            self,
+            Self::UnknownChannel
+                | Self::UnknownGuild
+                | Self::UnknownMember
+                | Self::UnknownMessage
+                | Self::UnknownRole
+                | Self::UnknownUser
+                | Self::UnknownEmoji
+                | Self::UnknownWebhook
+                | Self::UnknownInteraction
+        )
+    }
+
+    /// Whether the bot has no access or lacks permissions to
+    /// perform the requested action.
+    #[must_use]
+    pub fn is_missing_permissions(&self) -> bool {
+        matches!(self, Self::MissingAccess | Self::MissingPermissions)
+    }
+
+    /// Whether the bot cannot send direct messages to the user.
+    #[must_use]
+    pub fn is_cannot_dm(&self) -> bool {
+        matches!(self, Self::CannotDMUser)
+    }
+
+    /// Whether a certain limit has been reached from Discord.
+    #[must_use]
+    pub fn is_limit_reached(&self) -> bool {
+        matches!(
+            self,
+            Self::MaxPins | Self::MaxRoles | Self::MaxReactions | Self::MaxChannels
+        )
+    }
+
+    /// Whether the request with this error code may succeed if
+    /// it is sent again at a later time.
+    ///
+    /// Most of JSON error codes are caused by the request itself
+    /// or the state of the resource so they are not retryable.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::ResourceOverloaded)
+    }
+}
+
+impl From<u64> for DiscordJsonErrorCode {
+    fn from(value: u64) -> Self {
+        Self::from_code(value)
+    }
+}
+
+impl From<DiscordJsonErrorCode> for u64 {
+    fn from(value: DiscordJsonErrorCode) -> Self {
+        value.code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiscordJsonErrorCode;
+
+    #[test]
+    fn should_roundtrip_codes() {
+        for code in [0, 10008, 30010, 50001, 50007, 50013, 130_000, 12345] {
+            assert_eq!(DiscordJsonErrorCode::from_code(code).code(), code);
+        }
+    }
+
+    #[test]
+    fn should_map_unknown_codes_to_other() {
+        assert_eq!(
+            DiscordJsonErrorCode::from_code(12345),
+            DiscordJsonErrorCode::Other(12345)
+        );
+    }
+
+    #[test]
+    fn predicates() {
+        assert!(DiscordJsonErrorCode::UnknownMessage.is_unknown_resource());
+        assert!(DiscordJsonErrorCode::MissingAccess.is_missing_permissions());
+        assert!(DiscordJsonErrorCode::MissingPermissions.is_missing_permissions());
+        assert!(DiscordJsonErrorCode::CannotDMUser.is_cannot_dm());
+        assert!(DiscordJsonErrorCode::MaxReactions.is_limit_reached());
+        assert!(!DiscordJsonErrorCode::MissingAccess.is_retryable());
+    }
+}
@@ -1,5 +1,7 @@
 use serde::Serialize;
 
+use super::codes::DiscordJsonErrorCode;
+
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum DiscordHttpErrorInfo {
@@ -11,16 +13,14 @@ pub enum DiscordHttpErrorInfo {
 }
 
 impl DiscordHttpErrorInfo {
-    // https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
     #[must_use]
     pub fn has_missing_access(&self) -> bool {
-        self.api_code().map(|v| v == 50001).unwrap_or_default()
+        self.json_code() == Some(DiscordJsonErrorCode::MissingAccess)
     }
 
-    // https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
     #[must_use]
     pub fn is_invalid_token(&self) -> bool {
-        self.api_code().map(|v| v == 50014).unwrap_or_default()
+        self.json_code() == Some(DiscordJsonErrorCode::InvalidToken)
     }
 
     #[must_use]
@@ -31,6 +31,22 @@ impl DiscordHttpErrorInfo {
         }
     }
 
+    /// Gets the typed version of [`DiscordHttpErrorInfo::api_code`].
+    #[must_use]
+    pub fn json_code(&self) -> Option<DiscordJsonErrorCode> {
+        self.api_code().map(DiscordJsonErrorCode::from_code)
+    }
+
+    /// Whether the failed request may succeed if it is sent again
+    /// at a later time.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Outage | Self::Ratelimited | Self::TimedOut | Self::Unknown => true,
+            Self::Response(code) => DiscordJsonErrorCode::from_code(*code).is_retryable(),
+        }
+    }
+
     pub(crate) fn install_hook() {
         crate::Error::install_serde_hook::<Self>();
         crate::Error::install_hook::<Self>(|this, ctx| match this {
@@ -0,0 +1,164 @@
+use chrono::TimeDelta;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use std::time::Duration as StdDuration;
+
+use crate::serial::PARSER;
+
+/// Duration which can be parsed from and displayed as
+/// a human readable string (like `30s`, `5m` or `1h`).
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HumanDuration(StdDuration);
+
+impl HumanDuration {
+    #[must_use]
+    pub const fn new(duration: StdDuration) -> Self {
+        Self(duration)
+    }
+
+    #[must_use]
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(StdDuration::from_secs(secs))
+    }
+
+    #[must_use]
+    pub const fn from_mins(mins: u64) -> Self {
+        Self(StdDuration::from_secs(mins * 60))
+    }
+
+    #[must_use]
+    pub const fn from_days(days: u64) -> Self {
+        Self(StdDuration::from_secs(days * 24 * 60 * 60))
+    }
+
+    #[must_use]
+    pub const fn get(self) -> StdDuration {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Converts the duration into [`TimeDelta`], saturating to
+    /// [`TimeDelta::MAX`] if it is too large.
+    #[must_use]
+    pub fn to_time_delta(self) -> TimeDelta {
+        TimeDelta::from_std(self.0).unwrap_or(TimeDelta::MAX)
+    }
+}
+
+impl From<StdDuration> for HumanDuration {
+    fn from(value: StdDuration) -> Self {
+        Self(value)
+    }
+}
+
+impl From<HumanDuration> for StdDuration {
+    fn from(value: HumanDuration) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed = PARSER.parse(s.trim()).map_err(ToString::to_string)?;
+        let duration = StdDuration::try_from(parsed).map_err(ToString::to_string)?;
+        Ok(Self(duration))
+    }
+}
+
+impl Debug for HumanDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for HumanDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: &[(u128, &str)] = &[
+            (24 * 60 * 60 * 1000, "d"),
+            (60 * 60 * 1000, "h"),
+            (60 * 1000, "m"),
+            (1000, "s"),
+        ];
+
+        let millis = self.0.as_millis();
+        if millis == 0 {
+            return f.write_str("0s");
+        }
+
+        // picks the largest unit that can represent it without losing precision
+        for (size, unit) in UNITS {
+            if millis % size == 0 {
+                return write!(f, "{}{unit}", millis / size);
+            }
+        }
+        write!(f, "{millis}ms")
+    }
+}
+
+impl<'de> Deserialize<'de> for HumanDuration {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as DeError;
+
+        let value = String::deserialize(deserializer)?;
+        value
+            .parse()
+            .map_err(|e| DeError::custom(format!("invalid duration {value:?}: {e}")))
+    }
+}
+
+impl Serialize for HumanDuration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl doku::Document for HumanDuration {
+    fn ty() -> doku::Type {
+        <String as doku::Document>::ty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_durations() {
+        assert_eq!("30s".parse(), Ok(HumanDuration::from_secs(30)));
+        assert_eq!("5m".parse(), Ok(HumanDuration::from_mins(5)));
+        assert_eq!("1h".parse(), Ok(HumanDuration::from_mins(60)));
+        assert_eq!(
+            "250ms".parse(),
+            Ok(HumanDuration::new(StdDuration::from_millis(250)))
+        );
+        assert!("-5m".parse::<HumanDuration>().is_err());
+        assert!("5 parsecs".parse::<HumanDuration>().is_err());
+    }
+
+    #[test]
+    fn should_display_durations() {
+        assert_eq!(HumanDuration::from_secs(30).to_string(), "30s");
+        assert_eq!(HumanDuration::from_secs(90).to_string(), "90s");
+        assert_eq!(HumanDuration::from_mins(60 * 24).to_string(), "1d");
+        assert_eq!(
+            HumanDuration::new(StdDuration::from_millis(1500)).to_string(),
+            "1500ms"
+        );
+
+        let duration = HumanDuration::from_mins(15);
+        assert_eq!(duration.to_string().parse(), Ok(duration));
+    }
+}
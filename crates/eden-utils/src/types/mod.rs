@@ -1,5 +1,7 @@
+mod human_duration;
 mod protected_string;
 mod sensitive;
 
+pub use self::human_duration::*;
 pub use self::protected_string::*;
 pub use self::sensitive::*;
@@ -0,0 +1,113 @@
+use eden_bot::util::http::request_for_model;
+use eden_bot::Bot;
+use eden_settings::{Settings, SettingsOverrides};
+use eden_utils::error::exts::*;
+use eden_utils::{Error, ErrorCategory, Result};
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Instant;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("Eden is not ready to start")]
+pub struct CheckSettingsError;
+
+/// Checks whether Eden can start with the settings without starting it.
+///
+/// It loads and validates the settings, connects to the database with
+/// the configured timeouts and verifies the bot token with Discord.
+/// Every check is reported to stderr and it fails if any of them fails.
+pub fn run(overrides: SettingsOverrides) -> Result<(), CheckSettingsError> {
+    let settings = match Settings::from_env_with(overrides) {
+        Ok(settings) => settings,
+        Err(error) => {
+            report_failure("Settings", error);
+            return Err(failed(1));
+        }
+    };
+
+    let source = settings
+        .path()
+        .map_or_else(|| String::from("<none>"), |v| v.display().to_string());
+    report_success("Settings", format!("loaded from {source}"));
+    for setting in settings.deprecated() {
+        eprintln!("! Settings: {setting}");
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .into_typed_error()
+        .change_context(CheckSettingsError)
+        .attach_printable("could not build tokio runtime")?;
+
+    let failures = runtime.block_on(async {
+        let bot = match Bot::new(Arc::new(settings)) {
+            Ok(bot) => bot,
+            Err(error) => {
+                report_failure("Bot", error);
+                return 1;
+            }
+        };
+
+        let mut failures = 0;
+        match check_database(&bot).await {
+            Ok(message) => report_success("Database", message),
+            Err(error) => {
+                report_failure("Database", error);
+                failures += 1;
+            }
+        }
+        match check_token(&bot).await {
+            Ok(message) => report_success("Bot token", message),
+            Err(error) => {
+                report_failure("Bot token", error);
+                failures += 1;
+            }
+        }
+        failures
+    });
+
+    if failures > 0 {
+        return Err(failed(failures));
+    }
+
+    eprintln!("Eden is ready to start");
+    Ok(())
+}
+
+async fn check_database(bot: &Bot) -> Result<String> {
+    let started = Instant::now();
+
+    // connections are set up with the configured timeouts on connect
+    let conn = bot.db_read().await?;
+    drop(conn);
+
+    Ok(format!("connected in {} ms", started.elapsed().as_millis()))
+}
+
+async fn check_token(bot: &Bot) -> Result<String> {
+    let application = request_for_model(bot, bot.http.current_user_application())
+        .await
+        .anonymize_error()
+        .attach_printable("could not get the current application")?;
+
+    Ok(format!(
+        "authorized as {} ({})",
+        application.name, application.id
+    ))
+}
+
+fn failed(failures: usize) -> Error<CheckSettingsError> {
+    Error::context(ErrorCategory::Unknown, CheckSettingsError)
+        .attach_printable(format!("{failures} check(s) failed"))
+}
+
+fn report_success(check: &str, message: impl Display) {
+    eprintln!("✓ {check}: {message}");
+}
+
+fn report_failure(check: &str, error: impl Display) {
+    eprintln!("✗ {check}: failed");
+    eprintln!("{error}");
+}
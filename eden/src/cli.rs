@@ -0,0 +1,106 @@
+use clap::{Parser, Subcommand};
+use eden_settings::{SecretSource, Settings, SettingsLoadError, SettingsOverrides};
+use eden_utils::error::exts::*;
+use eden_utils::{Error, ErrorCategory, Result};
+use std::path::PathBuf;
+
+/// Command-line arguments of Eden.
+///
+/// Every option overrides its setting from the settings file
+/// and environment variables.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Args {
+    /// Settings file to load instead of the path from `EDEN_SETTINGS`.
+    #[arg(long, value_name = "PATH")]
+    pub settings: Option<PathBuf>,
+
+    /// How many CPU threads which Eden will utilize.
+    #[arg(long)]
+    pub threads: Option<usize>,
+
+    /// File to read the Discord bot token from.
+    #[arg(long = "bot.token-file", value_name = "PATH")]
+    pub bot_token_file: Option<PathBuf>,
+
+    /// URL of the database to connect to.
+    #[arg(long = "database.url", value_name = "URL")]
+    pub database_url: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Commands that run instead of starting Eden.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Manages the settings file of Eden.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Renames every deprecated setting in the settings file to its
+    /// new name. Files included by the settings file are left alone,
+    /// they can be migrated with `--settings <PATH>`.
+    Migrate,
+
+    /// Checks whether Eden can start with its settings: the settings
+    /// are valid, the database can be connected to and the bot token
+    /// is accepted by Discord. It exits with a non-zero status if any
+    /// of the checks fails.
+    Check,
+}
+
+impl Args {
+    /// Turns the arguments into settings overrides.
+    pub fn overrides(&self) -> Result<SettingsOverrides, SettingsLoadError> {
+        let mut overrides = SettingsOverrides::new();
+        overrides.path.clone_from(&self.settings);
+
+        if let Some(threads) = self.threads {
+            overrides.set("threads", threads.to_string());
+        }
+
+        if let Some(path) = self.bot_token_file.as_ref() {
+            let token = SecretSource::File(path.clone()).resolve()?;
+            overrides.set("bot.token", token);
+        }
+
+        if let Some(url) = self.database_url.as_ref() {
+            overrides.set("database.url", url.as_str());
+        }
+
+        Ok(overrides)
+    }
+
+    /// Runs the command from the arguments.
+    pub fn run(&self, command: &Command) -> Result<()> {
+        match command {
+            Command::Config(ConfigCommand::Migrate) => self.migrate_settings().anonymize_error(),
+            Command::Config(ConfigCommand::Check) => {
+                crate::check::run(self.overrides()?).anonymize_error()
+            }
+        }
+    }
+
+    fn migrate_settings(&self) -> Result<(), SettingsLoadError> {
+        let path = match self.settings.clone() {
+            Some(path) => path,
+            None => Settings::resolve_path()?.ok_or_else(|| {
+                Error::context(ErrorCategory::Unknown, SettingsLoadError)
+                    .attach_printable("could not find settings file to migrate")
+            })?,
+        };
+
+        let renamed = eden_settings::migrate_file(&path)?;
+        if renamed.is_empty() {
+            eprintln!("No deprecated settings found in {}", path.display());
+        }
+        for setting in renamed {
+            eprintln!("Renamed `{}` to `{}`", setting.key, setting.renamed_to);
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use eden_settings::Settings;
 use eden_utils::build;
 
+pub mod check;
+pub mod cli;
 pub mod logging;
 pub mod sentry;
 
@@ -42,10 +44,17 @@ Y88888P Y8888D' Y88888P VP   V8P
     } else {
         eprintln!("{}:\t<none>", header.paint("Settings file"));
     }
+    if let Some(profile) = settings.profile() {
+        eprintln!("{}:\t\t{profile}", header.paint("Profile"));
+    }
     eprintln!(
         "{}:\t{}",
         header.paint("Shard(s)"),
-        settings.bot.sharding.size(),
+        settings
+            .bot
+            .sharding
+            .size()
+            .map_or_else(|| String::from("auto"), |v| v.to_string()),
     );
     eprintln!("{}:\t{}", header.paint("Threads"), settings.threads);
 
@@ -1,16 +1,29 @@
-use eden_settings::{LoggingStyle, Settings};
+use eden_settings::{LoggingStyle, Settings, SettingsUpdate};
 use eden_utils::build;
 use eden_utils::error::tags::Suggestion;
+use eden_utils::trace::sampling::SpanSampler;
 use eden_utils::{error::exts::*, Result};
 use sentry::integrations::tracing::EventFilter;
+use std::cell::Cell;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tracing::callsite::Identifier;
 use tracing::level_filters::LevelFilter;
-use tracing::{Level, Metadata};
+use tracing::span::{Attributes, Id};
+use tracing::subscriber::Interest;
+use tracing::{info, warn, Level, Metadata, Subscriber};
 use tracing_error::ErrorLayer;
-use tracing_subscriber::{layer::SubscriberExt, Layer};
+use tracing_subscriber::filter::FilterExt;
+use tracing_subscriber::layer::{Context, Filter};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{layer::SubscriberExt, reload, EnvFilter, Layer, Registry};
 
 const DIRECTIVES_SUGGESTION: &'static str = "Read the syntax guide for filter directives at:\nhttps://docs.rs/tracing-subscriber/0.3.18/tracing_subscriber/filter/struct.EnvFilter.html#directives";
 
-pub fn init(settings: &Settings) -> Result<()> {
+/// Handle to replace the log targets filter while Eden is running.
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+pub fn init(settings: &Settings) -> Result<LogFilterHandle> {
     // I don't know how it happens but it somehow fixed the issue
     // of some events not emitted through the console likely
     // because of inconsistences `log` and `tracing` crates.
@@ -18,16 +31,8 @@ pub fn init(settings: &Settings) -> Result<()> {
         .into_typed_error()
         .attach_printable("could not initialize log tracer")?;
 
-    let env_filter = tracing_subscriber::EnvFilter::builder()
-        .with_default_directive(if build::PROFILE == "release" {
-            LevelFilter::WARN.into()
-        } else {
-            LevelFilter::INFO.into()
-        })
-        .parse(&settings.logging.targets)
-        .into_typed_error()
-        .attach_printable("could not parse log targets")
-        .attach(Suggestion::new(DIRECTIVES_SUGGESTION))?;
+    let (env_filter, filter_handle) = reload::Layer::new(log_filter(&settings.logging.targets)?);
+    apply_sampling(settings);
 
     let sentry_filter = if let Some(sentry) = settings.sentry.as_ref() {
         let filter = tracing_subscriber::EnvFilter::builder()
@@ -56,7 +61,7 @@ pub fn init(settings: &Settings) -> Result<()> {
             .without_time()
             .boxed(),
     }
-    .with_filter(env_filter);
+    .with_filter(env_filter.and(SamplingFilter));
 
     let sentry_layer = sentry::integrations::tracing::layer()
         .event_filter(event_filter)
@@ -71,9 +76,137 @@ pub fn init(settings: &Settings) -> Result<()> {
         .into_typed_error()
         .attach_printable("unable to setup tracing")?;
 
+    Ok(filter_handle)
+}
+
+fn log_filter(targets: &str) -> Result<EnvFilter> {
+    let filter = EnvFilter::builder()
+        .with_default_directive(if build::PROFILE == "release" {
+            LevelFilter::WARN.into()
+        } else {
+            LevelFilter::INFO.into()
+        })
+        .parse(targets)
+        .into_typed_error()
+        .attach_printable("could not parse log targets")
+        .attach(Suggestion::new(DIRECTIVES_SUGGESTION))?;
+
+    Ok(filter)
+}
+
+/// Applies changes to `logging.targets` and `logging.sampling` from
+/// the reloaded settings until the settings watcher is dropped.
+pub async fn listen(handle: LogFilterHandle, mut updates: Receiver<SettingsUpdate>) {
+    loop {
+        let update = match updates.recv().await {
+            Ok(update) => update,
+            Err(RecvError::Lagged(..)) => continue,
+            Err(RecvError::Closed) => break,
+        };
+
+        let (previous, current) = (&update.previous.logging, &update.current.logging);
+        if previous.sampling != current.sampling {
+            apply_sampling(&update.current);
+            info!("span sampling has been changed");
+        }
+
+        let targets = &current.targets;
+        if previous.targets == *targets {
+            continue;
+        }
+
+        match replace_filter(&handle, targets) {
+            Ok(()) => info!("log targets have been changed to {targets:?}"),
+            Err(error) => warn!(%error, "could not apply new log targets"),
+        }
+    }
+}
+
+fn replace_filter(handle: &LogFilterHandle, targets: &str) -> Result<()> {
+    let filter = log_filter(targets)?;
+    handle
+        .reload(filter)
+        .into_typed_error()
+        .attach_printable("could not replace log targets")?;
+
     Ok(())
 }
 
+fn apply_sampling(settings: &Settings) {
+    let rules = settings.logging.sampling.iter();
+    SpanSampler::global().replace_all(rules.map(|v| (v.span.clone(), v.percentage)));
+}
+
+/// Records only the spans picked by the [global span sampler](SpanSampler).
+///
+/// Unsampled spans are still created so `ERROR` events inside them keep
+/// their span context, but any other event inside them is not recorded.
+/// Spans with `ERROR` level are always recorded.
+struct SamplingFilter;
+
+/// Marks a span that is not picked by the span sampler.
+struct Unsampled;
+
+thread_local! {
+    /// Callsite of the span that is about to be created on this thread
+    /// if the span sampler did not pick it.
+    static UNSAMPLED_CALLSITE: Cell<Option<Identifier>> = const { Cell::new(None) };
+}
+
+impl<S> Filter<S> for SamplingFilter
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn enabled(&self, metadata: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        if *metadata.level() == Level::ERROR {
+            return true;
+        }
+
+        if metadata.is_span() {
+            // the span is only marked after it is created
+            let sampler = SpanSampler::global();
+            let recorded = sampler.should_record(metadata.target(), metadata.name());
+            UNSAMPLED_CALLSITE.set((!recorded).then(|| metadata.callsite()));
+            return true;
+        }
+
+        cx.lookup_current().map_or(true, |span| {
+            span.scope()
+                .all(|v| v.extensions().get::<Unsampled>().is_none())
+        })
+    }
+
+    fn callsite_enabled(&self, metadata: &'static Metadata<'static>) -> Interest {
+        let sampler = SpanSampler::global();
+        let sampled = if *metadata.level() == Level::ERROR {
+            false
+        } else if metadata.is_span() {
+            sampler.has_rule(metadata.target(), metadata.name())
+        } else {
+            // any event may be inside of an unsampled span
+            sampler.has_rules()
+        };
+
+        // the interest cache is rebuilt every time sampling rules change
+        if sampled {
+            Interest::sometimes()
+        } else {
+            Interest::always()
+        }
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, cx: Context<'_, S>) {
+        let callsite = attrs.metadata().callsite();
+        if UNSAMPLED_CALLSITE.take().as_ref() != Some(&callsite) {
+            return;
+        }
+
+        if let Some(span) = cx.span(id) {
+            span.extensions_mut().insert(Unsampled);
+        }
+    }
+}
+
 fn event_filter(metadata: &Metadata<'_>) -> EventFilter {
     let has_error = metadata.fields().iter().any(|v| v.name() == "error");
     match metadata.level() {
@@ -1,10 +1,21 @@
+use clap::Parser;
+use eden::cli::Args;
+use eden::logging::LogFilterHandle;
 use eden_settings::Settings;
 use eden_utils::error::exts::*;
+use eden_utils::hash::anonymize::Anonymizer;
 use eden_utils::Result;
 use std::sync::Arc;
 
-async fn bootstrap(settings: Settings) -> Result<()> {
-    let result = tokio::try_join!(eden_bot::start(Arc::new(settings)), async {
+async fn bootstrap(settings: Settings, log_filter: LogFilterHandle) -> Result<()> {
+    let settings = Arc::new(settings);
+    let watcher = settings.clone().watch();
+    eden_utils::tokio::spawn(
+        "eden::logging::listen",
+        eden::logging::listen(log_filter, watcher.subscribe()),
+    );
+
+    let result = tokio::try_join!(eden_bot::start(settings, watcher), async {
         eden_utils::shutdown::catch_signals().await;
         Ok(())
     });
@@ -13,8 +24,17 @@ async fn bootstrap(settings: Settings) -> Result<()> {
 }
 
 fn start() -> Result<()> {
-    let settings = Settings::from_env()?;
-    eden::logging::init(&settings)?;
+    eden_utils::env::init()?;
+
+    let args = Args::parse();
+    if let Some(command) = args.command.as_ref() {
+        return args.run(command);
+    }
+
+    let settings = Settings::from_env_with(args.overrides()?)?;
+    let log_filter = eden::logging::init(&settings)?;
+    settings.warn_deprecated();
+    Anonymizer::new(settings.bot.anonymization_key.expose()).install();
     eden::print_launch(&settings);
 
     let _sentry = eden::sentry::init(&settings);
@@ -24,7 +44,7 @@ fn start() -> Result<()> {
         .build()
         .into_typed_error()
         .attach_printable("could not build tokio runtime")?
-        .block_on(bootstrap(settings))
+        .block_on(bootstrap(settings, log_filter))
         .inspect_err(eden_utils::sentry::capture_error)
 }
 
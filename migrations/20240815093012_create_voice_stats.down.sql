@@ -0,0 +1 @@
+DROP TABLE voice_stats;
@@ -0,0 +1,8 @@
+CREATE TABLE voice_stats (
+    "guild_id" BIGINT NOT NULL,
+    "user_id" BIGINT NOT NULL,
+    "day" DATE NOT NULL,
+    "seconds" BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY ("guild_id", "user_id", "day")
+);
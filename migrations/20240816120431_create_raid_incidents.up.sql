@@ -0,0 +1,22 @@
+CREATE TABLE raid_incidents (
+    "id" UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "guild_id" BIGINT NOT NULL,
+    "joins" INT NOT NULL,
+
+    -- these are needed to restore the guild once the lockdown ends
+    "previous_everyone_permissions" BIGINT,
+    "previous_verification_level" SMALLINT,
+
+    "ended_at" TIMESTAMP WITHOUT TIME ZONE,
+    "ended_by" BIGINT,
+
+    CONSTRAINT positive_joins CHECK("joins" > 0)
+);
+
+-- only one lockdown can be active per guild
+CREATE UNIQUE INDEX raid_incidents_active_idx ON raid_incidents("guild_id")
+    WHERE "ended_at" IS NULL;
@@ -0,0 +1 @@
+DROP TABLE temp_role_grants;
@@ -0,0 +1,21 @@
+CREATE TABLE temp_role_grants (
+    "id" UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+    "updated_at" TIMESTAMP,
+
+    "guild_id" BIGINT NOT NULL,
+    "user_id" BIGINT NOT NULL,
+    "role_id" BIGINT NOT NULL,
+    "granted_by" BIGINT NOT NULL,
+
+    "expires_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "removed_at" TIMESTAMP WITHOUT TIME ZONE
+);
+SELECT manage_updated_at('temp_role_grants');
+
+-- granting the same role again extends the active grant instead
+CREATE UNIQUE INDEX temp_role_grants_active_idx
+    ON temp_role_grants("guild_id", "user_id", "role_id")
+    WHERE "removed_at" IS NULL;
@@ -0,0 +1,14 @@
+CREATE TABLE user_preferences (
+    "user_id" BIGINT NOT NULL,
+    "key" VARCHAR(50) NOT NULL,
+    "value" JSONB NOT NULL,
+
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+    "updated_at" TIMESTAMP,
+
+    PRIMARY KEY ("user_id", "key"),
+    CONSTRAINT non_empty_key CHECK(length("key") > 0)
+);
+SELECT manage_updated_at('user_preferences');
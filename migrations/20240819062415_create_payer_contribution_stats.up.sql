@@ -0,0 +1,17 @@
+-- Cached aggregates of every payer's contributions. This table is
+-- refreshed periodically so /payer stats does not have to go through
+-- every bill and payment on every invocation.
+CREATE TABLE payer_contribution_stats (
+    "payer_id" BIGINT PRIMARY KEY NOT NULL
+        REFERENCES payers(id) ON DELETE CASCADE,
+
+    "paid_bills" INTEGER NOT NULL DEFAULT 0,
+    "total_paid" NUMERIC NOT NULL DEFAULT 0,
+    "currency" VARCHAR(3),
+    "current_streak" INTEGER NOT NULL DEFAULT 0,
+    "longest_streak" INTEGER NOT NULL DEFAULT 0,
+
+    "refreshed_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc'))
+);
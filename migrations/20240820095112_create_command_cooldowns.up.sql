@@ -0,0 +1,8 @@
+CREATE TABLE command_cooldowns (
+    "user_id" BIGINT NOT NULL,
+    "bucket" VARCHAR(100) NOT NULL,
+    "expires_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+
+    PRIMARY KEY ("user_id", "bucket"),
+    CONSTRAINT non_empty_bucket CHECK(length("bucket") > 0)
+);
@@ -0,0 +1,14 @@
+CREATE TABLE guild_profile_changes (
+    "id" UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "guild_id" BIGINT NOT NULL,
+    "field" VARCHAR(30) NOT NULL,
+    "before" TEXT,
+    "after" TEXT
+);
+
+CREATE INDEX guild_profile_changes_guild_idx
+    ON guild_profile_changes("guild_id", "created_at" DESC);
@@ -0,0 +1,21 @@
+CREATE TABLE emoji_uploads (
+    "id" UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+    "updated_at" TIMESTAMP WITHOUT TIME ZONE,
+
+    "guild_id" BIGINT NOT NULL,
+    "requested_by" BIGINT NOT NULL,
+    "name" VARCHAR(32) NOT NULL,
+
+    -- image data URI of the emoji, cleared once it is reviewed
+    "image" TEXT,
+
+    "approved" BOOLEAN,
+    "reviewed_by" BIGINT,
+    "emoji_id" BIGINT
+);
+
+CREATE INDEX emoji_uploads_guild_idx ON emoji_uploads("guild_id");
+SELECT manage_updated_at('emoji_uploads');
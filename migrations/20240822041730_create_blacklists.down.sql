@@ -0,0 +1,2 @@
+DROP TABLE allowed_guilds;
+DROP TABLE blacklisted_users;
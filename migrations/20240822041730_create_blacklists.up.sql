@@ -0,0 +1,18 @@
+CREATE TABLE blacklisted_users (
+    "user_id" BIGINT PRIMARY KEY NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "blacklisted_by" BIGINT NOT NULL,
+    "reason" VARCHAR(500)
+);
+
+CREATE TABLE allowed_guilds (
+    "guild_id" BIGINT PRIMARY KEY NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "allowed_by" BIGINT NOT NULL
+);
@@ -0,0 +1,13 @@
+CREATE TABLE scheduled_event_reminders (
+    "event_id" BIGINT NOT NULL,
+    "lead_minutes" INT NOT NULL,
+    "starts_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "guild_id" BIGINT NOT NULL,
+
+    -- rescheduled events are reminded again since their start time changes
+    PRIMARY KEY ("event_id", "lead_minutes", "starts_at")
+);
@@ -0,0 +1,25 @@
+ALTER TABLE payer_applications
+    DROP COLUMN "interviewer_id",
+    DROP COLUMN "interview_requested_at";
+
+CREATE OR REPLACE FUNCTION check_identity_if_not_occupied_from_payers_application()
+    RETURNS TRIGGER
+    AS $$
+DECLARE
+    "exists" BOOLEAN = FALSE;
+BEGIN
+    SELECT EXISTS(
+        SELECT * FROM payer_applications
+        WHERE "java_username" = NEW.name
+            OR "bedrock_username" = NEW.name
+    ) INTO "exists";
+
+    IF ("exists" = TRUE) THEN
+        RAISE EXCEPTION 'username % is already occupied', NEW.name
+        USING ERRCODE = "23505";
+    END IF;
+
+    RETURN NEW;
+END;
+$$
+LANGUAGE plpgsql;
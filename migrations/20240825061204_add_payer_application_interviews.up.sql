@@ -0,0 +1,29 @@
+ALTER TABLE payer_applications
+    -- set once an admin asks the applicant for an interview
+    ADD COLUMN "interviewer_id" BIGINT,
+    ADD COLUMN "interview_requested_at" TIMESTAMP WITHOUT TIME ZONE;
+
+-- accepted applicants are registered as payers with the same usernames
+-- from their application, so their own application must not occupy them
+CREATE OR REPLACE FUNCTION check_identity_if_not_occupied_from_payers_application()
+    RETURNS TRIGGER
+    AS $$
+DECLARE
+    "exists" BOOLEAN = FALSE;
+BEGIN
+    SELECT EXISTS(
+        SELECT * FROM payer_applications
+        WHERE ("java_username" = NEW.name
+            OR "bedrock_username" = NEW.name)
+            AND "user_id" != NEW.payer_id
+    ) INTO "exists";
+
+    IF ("exists" = TRUE) THEN
+        RAISE EXCEPTION 'username % is already occupied', NEW.name
+        USING ERRCODE = "23505";
+    END IF;
+
+    RETURN NEW;
+END;
+$$
+LANGUAGE plpgsql;
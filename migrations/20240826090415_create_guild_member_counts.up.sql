@@ -0,0 +1,7 @@
+CREATE TABLE guild_member_counts (
+    "guild_id" BIGINT NOT NULL,
+    "day" DATE NOT NULL,
+    "members" BIGINT NOT NULL,
+
+    PRIMARY KEY ("guild_id", "day")
+);
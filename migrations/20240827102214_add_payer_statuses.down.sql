@@ -0,0 +1,20 @@
+DROP TRIGGER on_bills_changed ON bills;
+DROP FUNCTION on_bills_changed;
+
+DROP TRIGGER on_payment_changed ON payments;
+DROP FUNCTION on_payment_changed;
+
+DROP TRIGGER on_payer_registered ON payers;
+DROP FUNCTION on_payer_registered;
+
+DROP TRIGGER compute_new_payer_status ON payers;
+DROP FUNCTION compute_new_payer_status;
+
+DROP FUNCTION refresh_payer_status;
+DROP FUNCTION compute_payer_status;
+
+DROP TABLE payer_status_changes;
+
+ALTER TABLE payers
+    DROP CONSTRAINT valid_status,
+    DROP COLUMN "status";
@@ -0,0 +1,158 @@
+-- Whether a payer is up to date with their bills. Only bills due
+-- after the payer has registered are counted.
+--
+-- * settled - every counted bill is paid
+-- * active  - some bills are not paid yet but none of them is overdue
+-- * lapsed  - some bills are not paid after their deadline
+ALTER TABLE payers
+    ADD COLUMN "status" VARCHAR(10) NOT NULL DEFAULT 'settled',
+    ADD CONSTRAINT valid_status CHECK ("status" IN ('active', 'settled', 'lapsed'));
+
+-- Outbox of payer status transitions to be applied by Eden (like
+-- giving or removing the payer role). Eden is notified through the
+-- `payer_status_changes` channel whenever a change is recorded.
+CREATE TABLE payer_status_changes (
+    "id" BIGINT PRIMARY KEY NOT NULL GENERATED ALWAYS AS IDENTITY,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+    "payer_id" BIGINT NOT NULL REFERENCES payers(id) ON DELETE CASCADE,
+    -- it is null if the payer has just registered
+    "previous_status" VARCHAR(10),
+    "status" VARCHAR(10) NOT NULL,
+    "attempts" INTEGER NOT NULL DEFAULT 0,
+    "processed_at" TIMESTAMP WITHOUT TIME ZONE
+);
+
+CREATE INDEX payer_status_changes_pending_idx
+    ON payer_status_changes ("id") WHERE "processed_at" IS NULL;
+
+CREATE OR REPLACE FUNCTION compute_payer_status ("target_id" BIGINT, "registered_on" DATE)
+    RETURNS VARCHAR
+    AS $$
+DECLARE
+    "unpaid_bills" INTEGER;
+    "overdue_bills" INTEGER;
+BEGIN
+    SELECT
+        COUNT(*),
+        COUNT(*) FILTER (WHERE bill.deadline < (now() at TIME ZONE ('utc'))::date)
+    INTO "unpaid_bills", "overdue_bills"
+    FROM bills bill
+    WHERE bill.deadline >= "registered_on"
+        AND NOT EXISTS (
+            SELECT 1 FROM payments payment
+            WHERE payment.bill_id = bill.id
+                AND payment.payer_id = "target_id"
+                AND payment.data->'status'->>'type' = 'success'
+        );
+
+    IF ("overdue_bills" > 0) THEN
+        RETURN 'lapsed';
+    ELSIF ("unpaid_bills" > 0) THEN
+        RETURN 'active';
+    END IF;
+    RETURN 'settled';
+END;
+$$
+LANGUAGE plpgsql;
+
+-- Recomputes the payer's status and records it into the outbox if
+-- it has changed. It returns whether the status has changed.
+CREATE OR REPLACE FUNCTION refresh_payer_status ("target_id" BIGINT)
+    RETURNS BOOLEAN
+    AS $$
+DECLARE
+    "registered_on" DATE;
+    "previous" VARCHAR(10);
+    "current" VARCHAR(10);
+BEGIN
+    SELECT status, created_at::date INTO "previous", "registered_on"
+    FROM payers WHERE id = "target_id";
+
+    IF NOT FOUND THEN
+        RETURN FALSE;
+    END IF;
+
+    "current" := compute_payer_status("target_id", "registered_on");
+    IF ("current" = "previous") THEN
+        RETURN FALSE;
+    END IF;
+
+    UPDATE payers SET status = "current" WHERE id = "target_id";
+    INSERT INTO payer_status_changes (payer_id, previous_status, status)
+        VALUES ("target_id", "previous", "current");
+
+    PERFORM pg_notify('payer_status_changes', "target_id"::text);
+    RETURN TRUE;
+END;
+$$
+LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION compute_new_payer_status ()
+    RETURNS TRIGGER
+    AS $$
+BEGIN
+    NEW.status := compute_payer_status(NEW.id, NEW.created_at::date);
+    RETURN NEW;
+END;
+$$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER compute_new_payer_status
+BEFORE INSERT ON payers
+FOR EACH ROW EXECUTE PROCEDURE compute_new_payer_status();
+
+CREATE OR REPLACE FUNCTION on_payer_registered ()
+    RETURNS TRIGGER
+    AS $$
+BEGIN
+    INSERT INTO payer_status_changes (payer_id, previous_status, status)
+        VALUES (NEW.id, NULL, NEW.status);
+
+    PERFORM pg_notify('payer_status_changes', NEW.id::text);
+    RETURN NULL;
+END;
+$$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER on_payer_registered
+AFTER INSERT ON payers
+FOR EACH ROW EXECUTE PROCEDURE on_payer_registered();
+
+CREATE OR REPLACE FUNCTION on_payment_changed ()
+    RETURNS TRIGGER
+    AS $$
+BEGIN
+    IF (TG_OP <> 'INSERT') THEN
+        PERFORM refresh_payer_status(OLD.payer_id);
+    END IF;
+    IF (TG_OP <> 'DELETE') THEN
+        PERFORM refresh_payer_status(NEW.payer_id);
+    END IF;
+    RETURN NULL;
+END;
+$$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER on_payment_changed
+AFTER INSERT OR UPDATE OR DELETE ON payments
+FOR EACH ROW EXECUTE PROCEDURE on_payment_changed();
+
+-- every payer may owe a new or changed bill
+CREATE OR REPLACE FUNCTION on_bills_changed ()
+    RETURNS TRIGGER
+    AS $$
+BEGIN
+    PERFORM refresh_payer_status(id) FROM payers;
+    RETURN NULL;
+END;
+$$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER on_bills_changed
+AFTER INSERT OR UPDATE OR DELETE ON bills
+FOR EACH STATEMENT EXECUTE PROCEDURE on_bills_changed();
+
+-- payers registered before this migration are given their
+-- statuses without recording them as changes
+UPDATE payers SET status = compute_payer_status(id, created_at::date);
@@ -0,0 +1,2 @@
+DROP TRIGGER on_task_queued ON tasks;
+DROP FUNCTION on_task_queued;
@@ -0,0 +1,20 @@
+-- Queue workers listen to this channel so they can pick up newly
+-- queued tasks right away instead of waiting for their next poll.
+-- Tasks due later than a second from now are left to polling.
+CREATE OR REPLACE FUNCTION on_task_queued ()
+    RETURNS TRIGGER
+    AS $$
+BEGIN
+    IF (NEW.deadline <= (now() at TIME ZONE ('utc')) + INTERVAL '1 second') THEN
+        PERFORM pg_notify('queued_tasks', NEW.id::text);
+    END IF;
+    RETURN NULL;
+END;
+$$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER on_task_queued
+AFTER INSERT ON tasks
+FOR EACH ROW
+WHEN (NEW.status = 'queued')
+EXECUTE PROCEDURE on_task_queued();
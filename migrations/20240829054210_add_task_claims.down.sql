@@ -0,0 +1,3 @@
+ALTER TABLE tasks
+    DROP COLUMN "claimed_by",
+    DROP COLUMN "claimed_at";
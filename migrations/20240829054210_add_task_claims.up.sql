@@ -0,0 +1,6 @@
+-- Queue workers claim the tasks they pull so multiple processes
+-- can share the tasks table (even with the same worker ID) without
+-- running the same task twice.
+ALTER TABLE tasks
+    ADD COLUMN "claimed_by" UUID,
+    ADD COLUMN "claimed_at" TIMESTAMP;
@@ -0,0 +1,16 @@
+CREATE TABLE command_aliases (
+    "guild_id" BIGINT NOT NULL,
+    "name" VARCHAR(32) NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "created_by" BIGINT NOT NULL,
+    -- Path of the aliased command (like `payer pay_bill`)
+    "command" VARCHAR(100) NOT NULL,
+    -- Preset arguments keyed by option name. Values are kept
+    -- as they're typed and parsed once the alias is used.
+    "arguments" JSONB NOT NULL DEFAULT '{}',
+
+    PRIMARY KEY ("guild_id", "name")
+);
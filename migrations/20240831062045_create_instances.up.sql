@@ -0,0 +1,12 @@
+-- Eden processes sharing this database. The leader is elected with
+-- an advisory lock and this table only shows who holds it.
+CREATE TABLE instances (
+    "id" UUID PRIMARY KEY,
+    "name" VARCHAR(100) NOT NULL,
+    "started_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "last_seen_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    "is_leader" BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,2 @@
+DROP INDEX tasks_dedup_key_idx;
+ALTER TABLE tasks DROP COLUMN "dedup_key";
@@ -0,0 +1,7 @@
+-- Scheduling a task with the same type and deduplication key as
+-- a pending (or running) task collapses into that task instead
+-- of queueing another one.
+ALTER TABLE tasks ADD COLUMN "dedup_key" TEXT;
+
+CREATE UNIQUE INDEX tasks_dedup_key_idx ON tasks ((data ->> 'type'), dedup_key)
+    WHERE dedup_key IS NOT NULL AND status IN ('queued', 'running');
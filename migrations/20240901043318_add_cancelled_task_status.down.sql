@@ -0,0 +1,3 @@
+-- Values cannot be removed from enums in Postgres, so cancelled
+-- tasks are marked as failed instead.
+UPDATE tasks SET status = 'failed' WHERE status = 'cancelled';
@@ -0,0 +1,3 @@
+-- Cancelled tasks are kept (unlike deleted tasks) so it can be seen
+-- later on that they were cancelled, but they're never pulled again.
+ALTER TYPE task_status ADD VALUE IF NOT EXISTS 'cancelled';
@@ -0,0 +1,17 @@
+CREATE TABLE guild_snapshots (
+    "id" UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "guild_id" BIGINT NOT NULL,
+    "created_by" BIGINT NOT NULL,
+    -- Which operation the snapshot was taken before
+    "reason" VARCHAR(200) NOT NULL,
+    -- Guild data at the time of the snapshot. It is versioned
+    -- with the `_v` key so older snapshots can still be restored.
+    "data" JSONB NOT NULL
+);
+
+CREATE INDEX guild_snapshots_guild_idx
+    ON guild_snapshots("guild_id", "created_at" DESC);
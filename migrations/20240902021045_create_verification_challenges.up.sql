@@ -0,0 +1,16 @@
+CREATE TABLE verification_challenges (
+    -- Random value put in the custom IDs of the prompt's buttons
+    "nonce" UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "guild_id" BIGINT NOT NULL,
+    "user_id" BIGINT NOT NULL,
+    -- Expected answer of the prompt. It is never sent to Discord
+    -- so members cannot find it from the buttons' custom IDs.
+    "answer" VARCHAR(16) NOT NULL
+);
+
+CREATE INDEX verification_challenges_member_idx
+    ON verification_challenges("guild_id", "user_id");
@@ -0,0 +1,8 @@
+ALTER TABLE raid_incidents ADD COLUMN "previous_everyone_permissions" BIGINT;
+
+UPDATE raid_incidents
+    SET "previous_everyone_permissions" = "removed_everyone_permissions";
+
+ALTER TABLE raid_incidents
+    DROP COLUMN "removed_everyone_permissions",
+    DROP COLUMN "disabled_invites";
@@ -0,0 +1,10 @@
+ALTER TABLE raid_incidents
+    ADD COLUMN "removed_everyone_permissions" BIGINT,
+    ADD COLUMN "disabled_invites" BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- lockdowns only remove `Send Messages` and `Send Messages in Threads`
+UPDATE raid_incidents
+    SET "removed_everyone_permissions" = "previous_everyone_permissions" & 274877908992
+    WHERE "previous_everyone_permissions" IS NOT NULL;
+
+ALTER TABLE raid_incidents DROP COLUMN "previous_everyone_permissions";
@@ -0,0 +1,6 @@
+ALTER TABLE payer_contribution_stats
+    ADD COLUMN "total_paid" NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN "currency" VARCHAR(3);
+
+-- stats are refreshed periodically so they will be recomputed anyway
+ALTER TABLE payer_contribution_stats DROP COLUMN "totals";
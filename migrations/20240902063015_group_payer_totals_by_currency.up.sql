@@ -0,0 +1,12 @@
+-- Bills may be priced in different currencies, so totals are kept
+-- per currency instead of summing different currencies together.
+ALTER TABLE payer_contribution_stats
+    ADD COLUMN "totals" JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+UPDATE payer_contribution_stats
+    SET "totals" = jsonb_build_object("currency", "total_paid"::text)
+    WHERE "currency" IS NOT NULL;
+
+ALTER TABLE payer_contribution_stats
+    DROP COLUMN "total_paid",
+    DROP COLUMN "currency";
@@ -0,0 +1,17 @@
+-- Recent admin operations that can be reverted with /admin undo.
+-- They are kept in the database so they survive restarts.
+CREATE TABLE undo_entries (
+    "id" UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE
+        NOT NULL
+        DEFAULT (now() at TIME ZONE ('utc')),
+
+    "guild_id" BIGINT NOT NULL,
+    "actor_id" BIGINT NOT NULL,
+    "description" VARCHAR(200) NOT NULL,
+    -- How to revert the operation. It is (de)serialized by the bot.
+    "action" JSONB NOT NULL
+);
+
+CREATE INDEX undo_entries_guild_idx
+    ON undo_entries("guild_id", "created_at" DESC);
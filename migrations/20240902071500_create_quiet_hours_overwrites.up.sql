@@ -0,0 +1,13 @@
+-- Permissions of the @everyone overwrite in read-only channels before
+-- quiet hours started, so they can be restored once quiet hours end.
+CREATE TABLE quiet_hours_overwrites (
+    "channel_id" BIGINT PRIMARY KEY,
+    "guild_id" BIGINT NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (now() at TIME ZONE ('utc')),
+
+    -- both are NULL if the channel has no overwrite for @everyone
+    "allow" BIGINT,
+    "deny" BIGINT,
+
+    CONSTRAINT complete_overwrite CHECK(("allow" IS NULL) = ("deny" IS NULL))
+);
@@ -0,0 +1 @@
+ALTER TABLE payer_status_changes DROP COLUMN "claimed_at";
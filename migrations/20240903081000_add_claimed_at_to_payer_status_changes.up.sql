@@ -0,0 +1,4 @@
+-- Changes are claimed by a process before applying them to Discord
+-- so the transaction does not have to be kept open in the meantime.
+ALTER TABLE payer_status_changes
+    ADD COLUMN "claimed_at" TIMESTAMP WITHOUT TIME ZONE;
@@ -2,6 +2,7 @@ use clap::{Args, Subcommand};
 use eden_utils::Result;
 
 mod settings;
+mod settings_schema;
 
 #[derive(Debug, Args)]
 pub struct GenerateArgs {
@@ -14,10 +15,15 @@ enum GenerateSubcommand {
     /// Generates the entire documentation of settings in every
     /// and saves it in `config/eden.example.toml`.
     Settings,
+
+    /// Generates JSON Schema of the settings and saves it in
+    /// `config/eden.schema.json` for editors to validate settings files.
+    SettingsSchema,
 }
 
 pub fn run(args: &GenerateArgs) -> Result<()> {
     match args.subcommand {
         GenerateSubcommand::Settings => self::settings::run(),
+        GenerateSubcommand::SettingsSchema => self::settings_schema::run(),
     }
 }
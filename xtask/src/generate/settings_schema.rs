@@ -0,0 +1,15 @@
+use eden_settings::Settings;
+use eden_utils::error::exts::{AnonymizeErrorInto, AnonymizedResultExt};
+use eden_utils::Result;
+
+const SETTINGS_SCHEMA_FILE: &str = concat!(env!("CARGO_WORKSPACE_DIR"), "config/eden.schema.json");
+
+pub fn run() -> Result<()> {
+    let contents = Settings::generate_json_schema();
+    std::fs::write(SETTINGS_SCHEMA_FILE, contents)
+        .anonymize_error_into()
+        .attach_printable_lazy(|| format!("could not write file for {SETTINGS_SCHEMA_FILE}"))?;
+
+    println!("Generated settings schema at: {SETTINGS_SCHEMA_FILE}");
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use clap::Args;
+use eden_utils::error::exts::{AnonymizeErrorInto, AnonymizedResultExt};
+use eden_utils::trace::TraceId;
+use eden_utils::Result;
+
+#[derive(Debug, Args)]
+pub struct LogsArgs {
+    /// Trace ID shown in the footer of error messages for users
+    /// with developer mode on.
+    trace_id: TraceId,
+
+    /// Log file to look up. Logs are read from the standard input
+    /// if it is not specified (like `docker logs eden | xtask logs <id>`).
+    file: Option<PathBuf>,
+}
+
+/// Prints every log line emitted while Eden handled the request
+/// with the specified trace ID.
+pub fn run(args: &LogsArgs) -> Result<()> {
+    let reader: Box<dyn Read> = match args.file.as_ref() {
+        Some(path) => Box::new(
+            std::fs::File::open(path)
+                .anonymize_error_into()
+                .attach_printable_lazy(|| format!("could not open log file {}", path.display()))?,
+        ),
+        None => Box::new(std::io::stdin()),
+    };
+
+    let needle = args.trace_id.to_string();
+    let mut found = 0;
+    for line in BufReader::new(reader).lines() {
+        let line = line
+            .anonymize_error_into()
+            .attach_printable("could not read logs")?;
+
+        if line.contains(&needle) {
+            println!("{line}");
+            found += 1;
+        }
+    }
+
+    eprintln!("Found {found} log line(s) with trace ID {needle}");
+    Ok(())
+}
@@ -6,6 +6,8 @@ use nu_ansi_term::{Color, Style};
 
 mod docker;
 mod generate;
+mod logs;
+mod secret;
 
 #[derive(Parser)]
 #[command(version, author, long_about)]
@@ -29,11 +31,17 @@ enum TaskSubcommand {
 
     /// Generates something.
     Generate(self::generate::GenerateArgs),
+
+    /// Looks up every log line of a request from its trace ID.
+    Logs(self::logs::LogsArgs),
+
+    /// Generates keys and encrypts secrets for the settings file.
+    Secret(self::secret::SecretArgs),
 }
 
 fn main() -> Result<()> {
     eden_utils::Error::init();
-    eden_utils::env::init();
+    eden_utils::env::init()?;
 
     let args = TaskArgs::parse();
     let level = match args.debug {
@@ -51,6 +59,8 @@ fn main() -> Result<()> {
     match args.subcommand {
         TaskSubcommand::Docker(cmd) => self::docker::run(&cmd),
         TaskSubcommand::Generate(cmd) => self::generate::run(&cmd),
+        TaskSubcommand::Logs(cmd) => self::logs::run(&cmd),
+        TaskSubcommand::Secret(cmd) => self::secret::run(&cmd),
     }
 }
 
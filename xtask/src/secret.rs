@@ -0,0 +1,47 @@
+use std::io::Read;
+
+use clap::{Args, Subcommand};
+use eden_utils::error::exts::*;
+use eden_utils::Result;
+
+#[derive(Debug, Args)]
+pub struct SecretArgs {
+    #[clap(subcommand)]
+    subcommand: SecretSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SecretSubcommand {
+    /// Generates a new key for encrypted secrets. It should be stored
+    /// in `EDEN_SECRETS_KEY` or in the file at `EDEN_SECRETS_KEY_FILE`.
+    Key,
+
+    /// Encrypts a secret read from the standard input with the key
+    /// from `EDEN_SECRETS_KEY` or the file at `EDEN_SECRETS_KEY_FILE`
+    /// (like `echo -n <token> | xtask secret encrypt`).
+    Encrypt,
+}
+
+pub fn run(args: &SecretArgs) -> Result<()> {
+    match args.subcommand {
+        SecretSubcommand::Key => {
+            let key = eden_settings::generate_secrets_key().anonymize_error()?;
+            println!("{key}");
+        }
+        SecretSubcommand::Encrypt => {
+            let mut secret = String::new();
+            std::io::stdin()
+                .read_to_string(&mut secret)
+                .anonymize_error_into()
+                .attach_printable("could not read secret")?;
+
+            // `echo` and terminals usually add a new line at the end
+            let len = secret.trim_end_matches(['\r', '\n']).len();
+            secret.truncate(len);
+
+            let blob = eden_settings::encrypt_secret(&secret).anonymize_error()?;
+            println!("{{ from = \"encrypted:{blob}\" }}");
+        }
+    }
+    Ok(())
+}